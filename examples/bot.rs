@@ -1,5 +1,6 @@
 use diself::prelude::*;
 use std::env;
+use std::time::Duration;
 
 struct MyBot;
 
@@ -8,42 +9,31 @@ impl EventHandler for MyBot {
     async fn on_ready(&self, _ctx: &Context, user: User) {
         println!("🤖 {} is ready!", user.tag())
     }
+}
 
-    async fn on_message_create(&self, ctx: &Context, msg: Message) {
-        if msg.author.id != ctx.user.id {
-            return;
-        }
-        let (command, args) = if let Some(content) = msg.content.strip_prefix('!') {
-            let mut parts = content.split_whitespace();
-            let command = parts.next().unwrap_or("");
-            let args: Vec<&str> = parts.collect();
-            (command, args)
-        } else {
-            return;
-        };
-
-        match command {
-            "ping" => {
-                let _ = msg.reply(&ctx.http, "Pong!").await;
-            }
-            "echo" => {
-                let response = args.join(" ");
-                let _ = msg.reply(&ctx.http, response).await;
-            }
-            _ => {
-                let _ = msg
-                    .reply(
-                        &ctx.http,
-                        "Unknown command. Try `!ping` or `!echo <message>`.",
-                    )
-                    .await;
-            }
-        }
-    }
+fn framework() -> CommandFramework {
+    CommandFramework::new("!")
+        .command(
+            Command::new("ping", |ctx, msg, _args| async move {
+                msg.reply(&ctx.http, "Pong!").await?;
+                Ok(())
+            })
+            .description("Checks that the bot is alive"),
+        )
+        .command(
+            Command::new("echo", |ctx, msg, args| async move {
+                msg.reply(&ctx.http, args.rest()).await?;
+                Ok(())
+            })
+            .alias("say")
+            .usage("<message>")
+            .description("Repeats your message")
+            .cooldown(Duration::from_secs(3)),
+        )
 }
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
     tracing_subscriber::fmt()
         .with_max_level(tracing::Level::DEBUG)
         .init();
@@ -52,7 +42,9 @@ async fn main() -> Result<()> {
 
     println!("🦀 Starting Bot...\n");
 
-    let client = Client::new(token, MyBot);
+    let client = Client::builder(token, MyBot)
+        .with_framework(framework())
+        .build();
     client.start().await?;
     Ok(())
 }
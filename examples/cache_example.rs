@@ -19,7 +19,7 @@ impl EventHandler for CacheBot {
 }
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
     tracing_subscriber::fmt()
         .with_max_level(tracing::Level::DEBUG)
         .init();
@@ -34,6 +34,14 @@ async fn main() -> Result<()> {
         cache_channels: true,
         cache_guilds: true,
         cache_relationships: true,
+        cache_presences: true,
+        cache_voice_states: true,
+        cache_members: true,
+        cache_roles: true,
+        cache_emojis: true,
+        cache_threads: true,
+        cache_messages: true,
+        limits: CacheLimits::default(),
     };
 
     let client = Client::new(token, CacheBot).with_cache_config(cache_config);
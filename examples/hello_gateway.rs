@@ -1,8 +1,7 @@
 use diself::gateway::Connection;
-use diself::Result;
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Actiate logging with tracing
     tracing_subscriber::fmt()
         .with_max_level(tracing::Level::DEBUG)
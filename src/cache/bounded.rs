@@ -0,0 +1,209 @@
+use crate::cache::CachePolicy;
+use dashmap::DashMap;
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Hit, miss and eviction counters of a cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheCounters {
+    /// Lookups that found an entry
+    pub hits: u64,
+    /// Lookups that found nothing (or an expired entry)
+    pub misses: u64,
+    /// Entries dropped by the size limit or the TTL
+    pub evictions: u64,
+}
+
+/// Concurrent map applying a `CachePolicy`, shared by the entity caches
+#[derive(Clone)]
+pub(crate) struct BoundedMap<K: Eq + Hash, V> {
+    entries: Arc<DashMap<K, Slot<V>>>,
+    policy: CachePolicy,
+    clock: Arc<AtomicU64>,
+    counters: Arc<Counters>,
+}
+
+struct Slot<V> {
+    value: V,
+    inserted_at: Instant,
+    last_used: AtomicU64,
+}
+
+#[derive(Default)]
+struct Counters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> BoundedMap<K, V> {
+    pub(crate) fn new(policy: CachePolicy) -> Self {
+        Self {
+            entries: Arc::new(DashMap::new()),
+            policy,
+            clock: Arc::new(AtomicU64::new(0)),
+            counters: Arc::new(Counters::default()),
+        }
+    }
+
+    pub(crate) fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let found = self.entries.get(key).and_then(|slot| {
+            if self.is_expired(&slot) {
+                return None;
+            }
+            slot.last_used.store(self.tick(), Ordering::Relaxed);
+            Some(slot.value.clone())
+        });
+
+        if found.is_some() {
+            self.counters.hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.counters.misses.fetch_add(1, Ordering::Relaxed);
+            if self
+                .entries
+                .remove_if(key, |_, slot| self.is_expired(slot))
+                .is_some()
+            {
+                self.counters.evictions.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        found
+    }
+
+    /// Like `get`, without counting a hit or miss nor refreshing the entry's LRU position
+    ///
+    /// Used by the cache's own lookups, so the counters only reflect the callers'.
+    pub(crate) fn peek<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.entries
+            .get(key)
+            .filter(|slot| !self.is_expired(slot))
+            .map(|slot| slot.value.clone())
+    }
+
+    pub(crate) fn insert(&self, key: K, value: V) {
+        let slot = Slot {
+            value,
+            inserted_at: Instant::now(),
+            last_used: AtomicU64::new(self.tick()),
+        };
+        self.entries.insert(key, slot);
+        self.enforce_max_entries();
+    }
+
+    pub(crate) fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.entries.remove(key).map(|(_, slot)| slot.value)
+    }
+
+    pub(crate) fn retain(&self, mut keep: impl FnMut(&K, &V) -> bool) {
+        self.entries.retain(|key, slot| keep(key, &slot.value));
+    }
+
+    /// Returns the live values accepted by `filter`
+    pub(crate) fn filter_values(&self, filter: impl Fn(&K, &V) -> bool) -> Vec<V> {
+        self.entries
+            .iter()
+            .filter(|entry| !self.is_expired(entry.value()))
+            .filter(|entry| filter(entry.key(), &entry.value().value))
+            .map(|entry| entry.value().value.clone())
+            .collect()
+    }
+
+    pub(crate) fn values(&self) -> Vec<V> {
+        self.filter_values(|_, _| true)
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.purge_expired();
+        self.entries.len()
+    }
+
+    pub(crate) fn clear(&self) {
+        self.entries.clear();
+    }
+
+    pub(crate) fn counters(&self) -> CacheCounters {
+        CacheCounters {
+            hits: self.counters.hits.load(Ordering::Relaxed),
+            misses: self.counters.misses.load(Ordering::Relaxed),
+            evictions: self.counters.evictions.load(Ordering::Relaxed),
+        }
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
+
+    fn is_expired(&self, slot: &Slot<V>) -> bool {
+        self.policy
+            .ttl
+            .is_some_and(|ttl| slot.inserted_at.elapsed() >= ttl)
+    }
+
+    fn purge_expired(&self) {
+        if self.policy.ttl.is_none() {
+            return;
+        }
+        let before = self.entries.len();
+        self.entries.retain(|_, slot| !self.is_expired(slot));
+        let purged = before.saturating_sub(self.entries.len());
+        self.counters
+            .evictions
+            .fetch_add(purged as u64, Ordering::Relaxed);
+    }
+
+    /// Evicts the least recently used entries once the map grows past `max_entries`.
+    ///
+    /// A tenth of the limit is evicted at once so inserting into a full cache does not
+    /// scan every entry each time.
+    fn enforce_max_entries(&self) {
+        let Some(max_entries) = self.policy.max_entries else {
+            return;
+        };
+        let len = self.entries.len();
+        if len <= max_entries {
+            return;
+        }
+        self.purge_expired();
+        let len = self.entries.len();
+        if len <= max_entries {
+            return;
+        }
+
+        let excess = len - max_entries + max_entries / 10;
+        let mut by_age: Vec<(u64, K)> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                (
+                    entry.value().last_used.load(Ordering::Relaxed),
+                    entry.key().clone(),
+                )
+            })
+            .collect();
+        let excess = excess.min(by_age.len());
+        if excess < by_age.len() {
+            by_age.select_nth_unstable_by_key(excess, |(last_used, _)| *last_used);
+        }
+
+        for (_, key) in by_age.into_iter().take(excess) {
+            if self.entries.remove(&key).is_some() {
+                self.counters.evictions.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
@@ -1,11 +1,18 @@
-use crate::cache::{CacheConfig, ChannelCache, GuildCache, RelationshipCache, UserCache};
+use crate::cache::{
+    CacheConfig, CacheSnapshot, ChannelCache, EmojiCache, GuildCache, MemberCache, MessageCache,
+    PresenceCache, RelationshipCache, RoleCache, ThreadCache, UserCache, VoiceStateCache,
+    CACHE_SNAPSHOT_VERSION,
+};
 use crate::model::{
-    Channel, Guild, MergedMember, Message, PassiveChannelState, PassiveUpdateV1, Presence,
-    ReadStateContainer, ReadStateEntry, ReadySupplemental, Relationship, User,
+    Channel, ChannelPinsUpdate, ChannelRecipientEvent, ChannelType, Emoji, Guild, MergedMember,
+    Message, PartialMember, PassiveChannelState, PassiveUpdateV1, Presence, ReadStateContainer,
+    ReadStateEntry, ReadySupplemental, Relationship, Role, ThreadListSync, ThreadMember,
+    ThreadMembersUpdate, User, VoiceState,
 };
 use dashmap::DashMap;
 use parking_lot::RwLock;
-use serde_json::Value;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
 /// Thread-safe cache for Discord entities
@@ -16,9 +23,20 @@ pub struct Cache {
     channel_cache: ChannelCache,
     guild_cache: GuildCache,
     relationship_cache: RelationshipCache,
+    presence_cache: PresenceCache,
+    voice_state_cache: VoiceStateCache,
+    member_cache: MemberCache,
+    role_cache: RoleCache,
+    emoji_cache: EmojiCache,
+    thread_cache: ThreadCache,
+    message_cache: MessageCache,
     read_states: Arc<DashMap<String, ReadStateEntry>>,
     guild_members: Arc<DashMap<String, Vec<MergedMember>>>,
     passive_channel_states: Arc<DashMap<String, PassiveChannelState>>,
+    /// Recipient user ID -> DM channel ID
+    dm_channels: Arc<DashMap<String, String>>,
+    /// Pending friend suggestions (READY `friend_suggestion_count`)
+    friend_suggestion_count: Arc<AtomicUsize>,
     /// Current user
     current_user: Arc<RwLock<Option<User>>>,
 }
@@ -32,13 +50,27 @@ impl Cache {
     /// Creates a new cache with custom configuration
     pub fn with_config(config: CacheConfig) -> Self {
         Self {
-            user_cache: UserCache::new(config.cache_users),
-            channel_cache: ChannelCache::new(config.cache_channels),
-            guild_cache: GuildCache::new(config.cache_guilds),
-            relationship_cache: RelationshipCache::new(config.cache_relationships),
+            user_cache: UserCache::new(config.cache_users).with_policy(config.limits.users),
+            channel_cache: ChannelCache::new(config.cache_channels)
+                .with_policy(config.limits.channels),
+            guild_cache: GuildCache::new(config.cache_guilds).with_policy(config.limits.guilds),
+            relationship_cache: RelationshipCache::new(config.cache_relationships)
+                .with_policy(config.limits.relationships),
+            presence_cache: PresenceCache::new(config.cache_presences)
+                .with_policy(config.limits.presences),
+            voice_state_cache: VoiceStateCache::new(config.cache_voice_states)
+                .with_policy(config.limits.voice_states),
+            member_cache: MemberCache::new(config.cache_members).with_policy(config.limits.members),
+            role_cache: RoleCache::new(config.cache_roles).with_policy(config.limits.roles),
+            emoji_cache: EmojiCache::new(config.cache_emojis).with_policy(config.limits.emojis),
+            thread_cache: ThreadCache::new(config.cache_threads).with_policy(config.limits.threads),
+            message_cache: MessageCache::new(config.cache_messages)
+                .with_policy(config.limits.messages),
             read_states: Arc::new(DashMap::new()),
             guild_members: Arc::new(DashMap::new()),
             passive_channel_states: Arc::new(DashMap::new()),
+            dm_channels: Arc::new(DashMap::new()),
+            friend_suggestion_count: Arc::new(AtomicUsize::new(0)),
             config,
             current_user: Arc::new(RwLock::new(None)),
         }
@@ -56,8 +88,13 @@ impl Cache {
         }
         self.initialize_users(data["users"].clone());
         self.initialize_guilds(data["guilds"].clone());
+        self.initialize_private_channels(data["private_channels"].clone());
         self.initialize_relationships(data["relationships"].clone());
         self.initialize_read_states(data["read_state"].clone());
+        if let Some(count) = data["friend_suggestion_count"].as_u64() {
+            self.friend_suggestion_count
+                .store(count as usize, Ordering::Relaxed);
+        }
     }
 
     /// Updates cache state from one gateway dispatch event payload.
@@ -70,24 +107,85 @@ impl Cache {
             "PASSIVE_UPDATE_V1" => self.update_from_passive_update(data),
             "CHANNEL_CREATE" | "CHANNEL_UPDATE" | "THREAD_CREATE" | "THREAD_UPDATE" => {
                 if let Ok(channel) = serde_json::from_value::<Channel>(data.clone()) {
+                    if channel.is_thread() {
+                        self.cache_thread(channel.clone());
+                    } else if let Some(guild_id) = &channel.guild_id {
+                        if let Some(mut guild) = self.guild_cache.peek(guild_id) {
+                            guild.channels.retain(|cached| cached.id != channel.id);
+                            guild.channels.push(channel.clone());
+                            self.cache_guild(guild);
+                        }
+                    }
                     self.cache_channel(channel);
                 }
             }
             "CHANNEL_DELETE" | "THREAD_DELETE" => {
                 if let Some(channel_id) = data.get("id").and_then(|v| v.as_str()) {
+                    if let Some(mut guild) = data
+                        .get("guild_id")
+                        .and_then(|v| v.as_str())
+                        .and_then(|guild_id| self.guild_cache.peek(guild_id))
+                    {
+                        if guild.channels.iter().any(|cached| cached.id == channel_id) {
+                            guild.channels.retain(|cached| cached.id != channel_id);
+                            self.cache_guild(guild);
+                        }
+                    }
                     self.remove_channel(channel_id);
+                    self.remove_thread(channel_id);
+                    self.thread_cache.remove_channel(channel_id);
+                    self.message_cache.remove_channel(channel_id);
                 }
             }
-            "THREAD_LIST_SYNC" => {
-                if let Some(threads) = data.get("threads").and_then(|v| v.as_array()) {
-                    for thread in threads {
-                        if let Ok(channel) = serde_json::from_value::<Channel>(thread.clone()) {
-                            self.cache_channel(channel);
+            "CHANNEL_PINS_UPDATE" => {
+                if let Ok(update) = serde_json::from_value::<ChannelPinsUpdate>(data.clone()) {
+                    if let Some(mut channel) = self.channel_cache.peek(&update.channel_id) {
+                        channel.last_pin_timestamp = update.last_pin_timestamp;
+                        self.cache_channel(channel);
+                    }
+                }
+            }
+            "CHANNEL_RECIPIENT_ADD" | "CHANNEL_RECIPIENT_REMOVE" => {
+                if let Ok(event) = serde_json::from_value::<ChannelRecipientEvent>(data.clone()) {
+                    if let Some(mut channel) = self.channel_cache.peek(&event.channel_id) {
+                        let recipients = channel.recipients.get_or_insert_with(Vec::new);
+                        recipients.retain(|user| user.id != event.user.id);
+                        if event_type == "CHANNEL_RECIPIENT_ADD" {
+                            recipients.push(event.user.clone());
                         }
+                        self.cache_channel(channel);
                     }
+                    self.cache_user(event.user);
+                }
+            }
+            "THREAD_LIST_SYNC" => {
+                if let Ok(sync) = serde_json::from_value::<ThreadListSync>(data.clone()) {
+                    self.sync_threads(sync);
+                }
+            }
+            "THREAD_MEMBER_UPDATE" => {
+                if let Ok(member) = serde_json::from_value::<ThreadMember>(data.clone()) {
+                    let thread_id = member.thread_id.clone();
+                    self.update_thread(&thread_id, |thread| thread.member = Some(member.clone()));
+                }
+            }
+            "THREAD_MEMBERS_UPDATE" => {
+                if let Ok(update) = serde_json::from_value::<ThreadMembersUpdate>(data.clone()) {
+                    self.update_thread_members(update);
                 }
             }
             "GUILD_CREATE" | "GUILD_UPDATE" => {
+                if let (Some(guild_id), Some(voice_states)) = (
+                    data.get("id").and_then(|v| v.as_str()),
+                    data.get("voice_states"),
+                ) {
+                    self.replace_guild_voice_states(guild_id, voice_states);
+                }
+                if let Some(guild_id) = data.get("id").and_then(|v| v.as_str()) {
+                    self.upsert_members_from_array(guild_id, data.get("members"));
+                    self.replace_guild_roles_and_emojis(guild_id, data);
+                    self.cache_guild_threads(guild_id, data.get("threads"));
+                }
                 if let Ok(guild) = serde_json::from_value::<Guild>(data.clone()) {
                     for channel in &guild.channels {
                         self.cache_channel(channel.clone());
@@ -101,40 +199,126 @@ impl Cache {
             "GUILD_DELETE" => {
                 if let Some(guild_id) = data.get("id").and_then(|v| v.as_str()) {
                     self.remove_guild(guild_id);
+                    self.member_cache.remove_guild(guild_id);
+                    self.role_cache.remove_guild(guild_id);
+                    self.emoji_cache.remove_guild(guild_id);
+                    self.thread_cache.remove_guild(guild_id);
+                    self.message_cache.remove_guild(guild_id);
+                }
+            }
+            "GUILD_ROLE_CREATE" | "GUILD_ROLE_UPDATE" => {
+                if let (Some(guild_id), Ok(role)) = (
+                    data.get("guild_id").and_then(|v| v.as_str()),
+                    serde_json::from_value::<Role>(data["role"].clone()),
+                ) {
+                    if let Some(mut guild) = self.guild_cache.peek(guild_id) {
+                        guild.roles.retain(|cached| cached.id != role.id);
+                        guild.roles.push(role.clone());
+                        self.cache_guild(guild);
+                    }
+                    self.cache_role(guild_id, role);
+                }
+            }
+            "GUILD_ROLE_DELETE" => {
+                if let (Some(guild_id), Some(role_id)) = (
+                    data.get("guild_id").and_then(|v| v.as_str()),
+                    data.get("role_id").and_then(|v| v.as_str()),
+                ) {
+                    if let Some(mut guild) = self.guild_cache.peek(guild_id) {
+                        guild.roles.retain(|cached| cached.id != role_id);
+                        self.cache_guild(guild);
+                    }
+                    self.remove_role(role_id);
+                }
+            }
+            "GUILD_EMOJIS_UPDATE" => {
+                if let (Some(guild_id), Ok(emojis)) = (
+                    data.get("guild_id").and_then(|v| v.as_str()),
+                    serde_json::from_value::<Vec<Emoji>>(data["emojis"].clone()),
+                ) {
+                    if let Some(mut guild) = self.guild_cache.peek(guild_id) {
+                        guild.emojis = emojis.clone();
+                        self.cache_guild(guild);
+                    }
+                    self.emoji_cache.replace_guild(guild_id, emojis);
+                }
+            }
+            "VOICE_STATE_UPDATE" => {
+                if let Ok(voice_state) = serde_json::from_value::<VoiceState>(data.clone()) {
+                    if let Some(member) = &voice_state.member {
+                        self.cache_user(member.user.clone());
+                    }
+                    if let (Some(guild_id), Some(member)) =
+                        (&voice_state.guild_id, data.get("member"))
+                    {
+                        self.upsert_member_from_partial(guild_id, &voice_state.user_id, member);
+                    }
+                    self.cache_voice_state(voice_state);
                 }
             }
             "RELATIONSHIP_ADD" => {
                 if let Ok(relationship) = serde_json::from_value::<Relationship>(data.clone()) {
+                    if let Some(user) = &relationship.user {
+                        self.cache_user(user.clone());
+                    }
                     self.cache_relationship(relationship);
                 }
             }
+            "RELATIONSHIP_UPDATE" => self.update_relationship_from_partial(data),
             "RELATIONSHIP_REMOVE" => {
                 if let Some(user_id) = data.get("id").and_then(|v| v.as_str()) {
                     self.remove_relationship(user_id);
                 }
             }
+            "FRIEND_SUGGESTION_CREATE" => {
+                self.friend_suggestion_count.fetch_add(1, Ordering::Relaxed);
+            }
+            "FRIEND_SUGGESTION_DELETE" => {
+                let _ = self.friend_suggestion_count.fetch_update(
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                    |count| count.checked_sub(1),
+                );
+            }
             "MESSAGE_CREATE" | "MESSAGE_UPDATE" => {
+                self.upsert_message_from_partial(data);
                 if let Ok(message) = serde_json::from_value::<Message>(data.clone()) {
+                    if event_type == "MESSAGE_CREATE" {
+                        self.update_read_state_from_message(&message);
+                    }
+                    if let Some(guild_id) = &message.guild_id {
+                        self.update_members_from_message(guild_id, &message.author.id, data);
+                    }
                     self.cache_user(message.author);
                     for user in message.mentions {
                         self.cache_user(user);
                     }
                     if let Some(thread) = message.thread {
+                        self.cache_thread(thread.clone());
                         self.cache_channel(thread);
                     }
                 }
             }
+            "MESSAGE_DELETE" => {
+                if let Some(message_id) = data.get("id").and_then(|v| v.as_str()) {
+                    self.remove_message(message_id);
+                }
+            }
+            "MESSAGE_DELETE_BULK" => {
+                for message_id in data
+                    .get("ids")
+                    .and_then(|v| v.as_array())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|v| v.as_str())
+                {
+                    self.remove_message(message_id);
+                }
+            }
+            "MESSAGE_ACK" => self.update_read_state_from_ack(data),
             "USER_UPDATE" => {
                 self.upsert_user_from_partial(data);
-                if let Some(user_id) = data.get("id").and_then(|v| v.as_str()) {
-                    if let Some(current) = self.current_user() {
-                        if current.id == user_id {
-                            if let Some(updated) = self.user(user_id) {
-                                self.set_current_user(updated);
-                            }
-                        }
-                    }
-                }
+                self.update_current_user_from_partial(data);
             }
             "PRESENCE_UPDATE" => {
                 if let Some(user_payload) = data.get("user") {
@@ -146,6 +330,20 @@ impl Cache {
                 if let Some(user_payload) = data.get("user") {
                     self.upsert_user_from_partial(user_payload);
                 }
+                if let (Some(guild_id), Some(user_id)) = (
+                    data.get("guild_id").and_then(|v| v.as_str()),
+                    data.pointer("/user/id").and_then(|v| v.as_str()),
+                ) {
+                    self.upsert_member_from_partial(guild_id, user_id, data);
+                }
+            }
+            "GUILD_MEMBER_REMOVE" => {
+                if let (Some(guild_id), Some(user_id)) = (
+                    data.get("guild_id").and_then(|v| v.as_str()),
+                    data.pointer("/user/id").and_then(|v| v.as_str()),
+                ) {
+                    self.remove_member(guild_id, user_id);
+                }
             }
             "GUILD_MEMBERS_CHUNK" => {
                 if let Some(members) = data.get("members").and_then(|v| v.as_array()) {
@@ -155,6 +353,18 @@ impl Cache {
                         }
                     }
                 }
+                if let Some(guild_id) = data.get("guild_id").and_then(|v| v.as_str()) {
+                    self.upsert_members_from_array(guild_id, data.get("members"));
+                }
+            }
+            "TYPING_START" => {
+                if let (Some(guild_id), Some(user_id), Some(member)) = (
+                    data.get("guild_id").and_then(|v| v.as_str()),
+                    data.get("user_id").and_then(|v| v.as_str()),
+                    data.get("member"),
+                ) {
+                    self.upsert_member_from_partial(guild_id, user_id, member);
+                }
             }
             _ => {}
         }
@@ -237,6 +447,93 @@ impl Cache {
         self.relationship_cache.friends()
     }
 
+    /// Returns the number of pending friend suggestions (from READY, kept up to date with
+    /// FRIEND_SUGGESTION_CREATE / FRIEND_SUGGESTION_DELETE)
+    pub fn friend_suggestion_count(&self) -> usize {
+        self.friend_suggestion_count.load(Ordering::Relaxed)
+    }
+
+    /// Merges a partial RELATIONSHIP_UPDATE payload (type, nickname, ...) into the cached relationship.
+    fn update_relationship_from_partial(&self, data: &Value) {
+        let Some(user_id) = data.get("id").and_then(|v| v.as_str()) else {
+            return;
+        };
+        let Some(existing) = self.relationship_cache.peek(user_id) else {
+            if let Ok(relationship) = serde_json::from_value::<Relationship>(data.clone()) {
+                self.cache_relationship(relationship);
+            }
+            return;
+        };
+
+        let Ok(mut merged) = serde_json::to_value(&existing) else {
+            return;
+        };
+        if let (Some(target), Some(source)) = (merged.as_object_mut(), data.as_object()) {
+            for (key, value) in source {
+                target.insert(key.clone(), value.clone());
+            }
+        }
+        if let Ok(relationship) = serde_json::from_value::<Relationship>(merged) {
+            self.cache_relationship(relationship);
+        }
+    }
+
+    // ==================== Presences ====================
+
+    /// Gets the last known presence of a user
+    pub fn presence(&self, user_id: &str) -> Option<Presence> {
+        self.presence_cache.get(user_id)
+    }
+
+    /// Inserts or updates the presence of a user
+    pub fn cache_presence(&self, user_id: impl Into<String>, presence: Presence) {
+        self.presence_cache.insert(user_id, presence);
+    }
+
+    /// Returns the number of cached presences
+    pub fn presence_count(&self) -> usize {
+        self.presence_cache.count()
+    }
+
+    // ==================== Voice States ====================
+
+    /// Gets the voice state of a user (`None` guild for private calls)
+    pub fn voice_state(&self, guild_id: Option<&str>, user_id: &str) -> Option<VoiceState> {
+        self.voice_state_cache.get(guild_id, user_id)
+    }
+
+    /// Gets the voice states of the users connected to a voice channel
+    pub fn voice_states_in_channel(&self, channel_id: &str) -> Vec<VoiceState> {
+        self.voice_state_cache.in_channel(channel_id)
+    }
+
+    /// Gets the voice states of the users connected to a guild's voice channels
+    pub fn guild_voice_states(&self, guild_id: &str) -> Vec<VoiceState> {
+        self.voice_state_cache.in_guild(guild_id)
+    }
+
+    /// Inserts or updates a voice state (removed when `channel_id` is null)
+    pub fn cache_voice_state(&self, voice_state: VoiceState) {
+        self.voice_state_cache.insert(voice_state);
+    }
+
+    /// Returns the number of cached voice states
+    pub fn voice_state_count(&self) -> usize {
+        self.voice_state_cache.count()
+    }
+
+    /// Replaces the voice states of a guild from a raw `voice_states` array
+    fn replace_guild_voice_states(&self, guild_id: &str, voice_states: &Value) {
+        let Some(voice_states) = voice_states.as_array() else {
+            return;
+        };
+        let voice_states = voice_states
+            .iter()
+            .filter_map(|state| serde_json::from_value::<VoiceState>(state.clone()).ok())
+            .collect();
+        self.voice_state_cache.replace_guild(guild_id, voice_states);
+    }
+
     // ==================== Read States ====================
 
     /// Initializes read-state cache from the READY event's `read_state` payload.
@@ -261,6 +558,30 @@ impl Cache {
             .collect()
     }
 
+    /// Gets read-state entries with unacknowledged messages.
+    pub fn unread_read_states(&self) -> Vec<ReadStateEntry> {
+        self.read_states
+            .iter()
+            .filter(|entry| entry.value().is_unread())
+            .map(|entry| entry.value().clone())
+            .collect()
+    }
+
+    /// Whether a channel has messages newer than its last acknowledged one.
+    pub fn is_unread(&self, channel_id: &str) -> bool {
+        self.read_states
+            .get(channel_id)
+            .is_some_and(|entry| entry.value().is_unread())
+    }
+
+    /// Gets the number of unacknowledged mentions in a channel.
+    pub fn mention_count(&self, channel_id: &str) -> u64 {
+        self.read_states
+            .get(channel_id)
+            .map(|entry| entry.value().mentions())
+            .unwrap_or(0)
+    }
+
     // ==================== Channels ====================
 
     /// Gets a channel from cache by ID
@@ -270,11 +591,17 @@ impl Cache {
 
     /// Inserts or updates a channel in cache
     pub fn cache_channel(&self, channel: Channel) {
+        if channel.kind == ChannelType::DM && self.channel_cache.is_enabled() {
+            if let Some(user_id) = channel.recipient_user_ids().into_iter().next() {
+                self.dm_channels.insert(user_id, channel.id.clone());
+            }
+        }
         self.channel_cache.insert(channel);
     }
 
     /// Removes a channel from cache
     pub fn remove_channel(&self, channel_id: &str) -> Option<Channel> {
+        self.dm_channels.retain(|_, dm_id| dm_id != channel_id);
         self.channel_cache.remove(channel_id)
     }
 
@@ -288,10 +615,202 @@ impl Cache {
         self.channel_cache.all()
     }
 
+    // ==================== Private Channels ====================
+
+    /// Initializes DM and group DM channels with the `private_channels` of the READY event
+    pub fn initialize_private_channels(&self, data: serde_json::Value) {
+        if let Ok(channels) = serde_json::from_value::<Vec<Channel>>(data) {
+            channels
+                .into_iter()
+                .for_each(|channel| self.cache_channel(channel));
+        }
+    }
+
+    /// Gets the cached DM channel with a user
+    pub fn dm_channel_with(&self, user_id: &str) -> Option<Channel> {
+        let channel_id = self.dm_channels.get(user_id)?.value().clone();
+        let channel = self.channel(&channel_id);
+        if channel.is_none() {
+            // Evicted from the channel cache
+            self.dm_channels.remove(user_id);
+        }
+        channel
+    }
+
+    /// Gets every cached DM and group DM channel
+    pub fn private_channels(&self) -> Vec<Channel> {
+        self.channels()
+            .into_iter()
+            .filter(|channel| channel.is_dm())
+            .collect()
+    }
+
+    // ==================== Threads ====================
+
+    /// Gets an active thread from cache by ID
+    pub fn thread(&self, thread_id: &str) -> Option<Channel> {
+        self.thread_cache.get(thread_id)
+    }
+
+    /// Gets the active threads of a parent channel
+    pub fn active_threads(&self, parent_id: &str) -> Vec<Channel> {
+        self.thread_cache.in_channel(parent_id)
+    }
+
+    /// Gets the active threads of a guild
+    pub fn guild_threads(&self, guild_id: &str) -> Vec<Channel> {
+        self.thread_cache.in_guild(guild_id)
+    }
+
+    /// Gets the active threads of a guild the current user joined
+    pub fn joined_threads(&self, guild_id: &str) -> Vec<Channel> {
+        self.guild_threads(guild_id)
+            .into_iter()
+            .filter(|thread| thread.member.is_some())
+            .collect()
+    }
+
+    /// Inserts or updates a thread in cache (archived threads are removed)
+    pub fn cache_thread(&self, thread: Channel) {
+        self.thread_cache.insert(thread);
+    }
+
+    /// Removes a thread from cache
+    pub fn remove_thread(&self, thread_id: &str) -> Option<Channel> {
+        self.thread_cache.remove(thread_id)
+    }
+
+    /// Returns the number of cached active threads
+    pub fn thread_count(&self) -> usize {
+        self.thread_cache.count()
+    }
+
+    // ==================== Messages ====================
+
+    /// Gets a message received through the gateway from cache by ID
+    pub fn message(&self, message_id: &str) -> Option<Message> {
+        self.message_cache.get(message_id)
+    }
+
+    /// Gets the cached messages of a channel, oldest first
+    pub fn channel_messages(&self, channel_id: &str) -> Vec<Message> {
+        self.message_cache.in_channel(channel_id)
+    }
+
+    /// Inserts or replaces a message in cache
+    pub fn cache_message(&self, message: Message) {
+        self.message_cache.insert(message);
+    }
+
+    /// Removes a message from cache
+    pub fn remove_message(&self, message_id: &str) -> Option<Message> {
+        self.message_cache.remove(message_id)
+    }
+
+    /// Returns the number of cached messages
+    pub fn message_count(&self) -> usize {
+        self.message_cache.count()
+    }
+
+    /// Merges a full or partial (MESSAGE_UPDATE) message payload into the cached message
+    fn upsert_message_from_partial(&self, partial: &Value) {
+        if !self.message_cache.is_enabled() {
+            return;
+        }
+        let Some(message_id) = partial.get("id").and_then(|v| v.as_str()) else {
+            return;
+        };
+        let mut merged = self
+            .message_cache
+            .peek(message_id)
+            .and_then(|message| serde_json::to_value(message).ok())
+            .unwrap_or_else(|| json!({}));
+        merge_object_values(&mut merged, partial);
+
+        if let Ok(message) = serde_json::from_value::<Message>(merged) {
+            self.cache_message(message);
+        }
+    }
+
+    /// Applies a change to a thread, in both the thread and the channel cache
+    fn update_thread(&self, thread_id: &str, update: impl Fn(&mut Channel)) {
+        if let Some(mut thread) = self.thread_cache.peek(thread_id) {
+            update(&mut thread);
+            self.cache_thread(thread);
+        }
+        if let Some(mut channel) = self.channel_cache.peek(thread_id) {
+            update(&mut channel);
+            self.cache_channel(channel);
+        }
+    }
+
+    /// Replaces the active threads of the synced channels (or of the whole guild)
+    fn sync_threads(&self, sync: ThreadListSync) {
+        match &sync.channel_ids {
+            Some(channel_ids) => channel_ids
+                .iter()
+                .for_each(|parent_id| self.thread_cache.remove_channel(parent_id)),
+            None => self.thread_cache.remove_guild(&sync.guild_id),
+        }
+        for mut thread in sync.threads {
+            thread.guild_id.get_or_insert_with(|| sync.guild_id.clone());
+            if let Some(member) = sync.members.iter().find(|m| m.thread_id == thread.id) {
+                thread.member = Some(member.clone());
+            }
+            self.cache_thread(thread.clone());
+            self.cache_channel(thread);
+        }
+    }
+
+    /// Applies a THREAD_MEMBERS_UPDATE: member count, and whether the current user is in
+    fn update_thread_members(&self, update: ThreadMembersUpdate) {
+        let current_user_id = self.current_user().map(|user| user.id);
+        let added = current_user_id.as_ref().and_then(|user_id| {
+            update
+                .added_members
+                .iter()
+                .find(|member| &member.user_id == user_id)
+                .cloned()
+        });
+        let removed = current_user_id
+            .as_ref()
+            .is_some_and(|user_id| update.removed_member_ids.contains(user_id));
+
+        self.update_thread(&update.id, |thread| {
+            thread.member_count = Some(update.member_count);
+            if let Some(member) = &added {
+                thread.member = Some(member.clone());
+            } else if removed {
+                thread.member = None;
+            }
+        });
+    }
+
+    /// Caches the active threads of a GUILD_CREATE payload
+    fn cache_guild_threads(&self, guild_id: &str, threads: Option<&Value>) {
+        let Some(threads) = threads.and_then(|v| v.as_array()) else {
+            return;
+        };
+        for thread in threads {
+            if let Ok(mut thread) = serde_json::from_value::<Channel>(thread.clone()) {
+                thread.guild_id.get_or_insert_with(|| guild_id.to_string());
+                self.cache_thread(thread.clone());
+                self.cache_channel(thread);
+            }
+        }
+    }
+
     // ==================== Guilds ====================
 
     /// Initializes guild cache with data from the READY event
     pub fn initialize_guilds(&self, data: serde_json::Value) {
+        if let Some(guilds) = data.as_array() {
+            for guild in guilds {
+                if let Some(guild_id) = guild.get("id").and_then(|v| v.as_str()) {
+                    self.replace_guild_roles_and_emojis(guild_id, guild);
+                }
+            }
+        }
         self.channel_cache.initialize_from_ready(data.clone());
         self.guild_cache.initialize_from_ready(data);
     }
@@ -321,6 +840,217 @@ impl Cache {
         self.guild_cache.all()
     }
 
+    // ==================== Members ====================
+
+    /// Gets a guild member
+    pub fn member(&self, guild_id: &str, user_id: &str) -> Option<PartialMember> {
+        self.member_cache.get(guild_id, user_id)
+    }
+
+    /// Gets every cached member of a guild
+    pub fn members_in_guild(&self, guild_id: &str) -> Vec<PartialMember> {
+        self.member_cache.in_guild(guild_id)
+    }
+
+    /// Gets the cached members of a guild having a role
+    pub fn members_with_role(&self, guild_id: &str, role_id: &str) -> Vec<PartialMember> {
+        self.members_in_guild(guild_id)
+            .into_iter()
+            .filter(|member| member.has_role(role_id))
+            .collect()
+    }
+
+    /// Gets the nickname of a guild member
+    pub fn member_nick(&self, guild_id: &str, user_id: &str) -> Option<String> {
+        self.member(guild_id, user_id)
+            .and_then(|member| member.nick)
+    }
+
+    /// Gets the name shown for a user in a guild: nickname, then global name, then username
+    pub fn member_display_name(&self, guild_id: &str, user_id: &str) -> Option<String> {
+        if let Some(nick) = self.member_nick(guild_id, user_id) {
+            return Some(nick);
+        }
+        let user = self.user(user_id)?;
+        Some(user.global_name.unwrap_or(user.username))
+    }
+
+    /// Inserts or replaces a guild member
+    pub fn cache_member(&self, member: PartialMember) {
+        self.member_cache.insert(member);
+    }
+
+    /// Removes a guild member from cache
+    pub fn remove_member(&self, guild_id: &str, user_id: &str) -> Option<PartialMember> {
+        self.member_cache.remove(guild_id, user_id)
+    }
+
+    /// Returns the number of cached members (all guilds)
+    pub fn member_count(&self) -> usize {
+        self.member_cache.count()
+    }
+
+    /// Merges a full or partial member payload into the cached member
+    fn upsert_member_from_partial(&self, guild_id: &str, user_id: &str, partial: &Value) {
+        if !self.member_cache.is_enabled() {
+            return;
+        }
+        let mut merged = self
+            .member_cache
+            .peek(guild_id, user_id)
+            .and_then(|member| serde_json::to_value(member).ok())
+            .unwrap_or_else(|| json!({}));
+        merge_object_values(&mut merged, partial);
+        merged["guild_id"] = Value::from(guild_id);
+        merged["user_id"] = Value::from(user_id);
+
+        if let Ok(member) = serde_json::from_value::<PartialMember>(merged) {
+            self.cache_member(member);
+        }
+    }
+
+    /// Merges a `members` array (GUILD_CREATE, GUILD_MEMBERS_CHUNK) into the member cache
+    fn upsert_members_from_array(&self, guild_id: &str, members: Option<&Value>) {
+        let Some(members) = members.and_then(|v| v.as_array()) else {
+            return;
+        };
+        for member in members {
+            if let Some(user_id) = member.pointer("/user/id").and_then(|v| v.as_str()) {
+                self.upsert_member_from_partial(guild_id, user_id, member);
+            }
+        }
+    }
+
+    /// Caches the author and mentioned members a guild message carries
+    fn update_members_from_message(&self, guild_id: &str, author_id: &str, data: &Value) {
+        if let Some(member) = data.get("member") {
+            self.upsert_member_from_partial(guild_id, author_id, member);
+        }
+        for mention in data
+            .get("mentions")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+        {
+            if let (Some(user_id), Some(member)) = (
+                mention.get("id").and_then(|v| v.as_str()),
+                mention.get("member"),
+            ) {
+                self.upsert_member_from_partial(guild_id, user_id, member);
+            }
+        }
+    }
+
+    // ==================== Roles ====================
+
+    /// Gets a role from cache by ID
+    pub fn role(&self, role_id: &str) -> Option<Role> {
+        self.role_cache.get(role_id)
+    }
+
+    /// Gets the roles of a guild, sorted by position (lowest first)
+    pub fn guild_roles(&self, guild_id: &str) -> Vec<Role> {
+        self.role_cache.in_guild(guild_id)
+    }
+
+    /// Gets the cached roles of a guild member, sorted by position (lowest first)
+    pub fn member_roles(&self, guild_id: &str, user_id: &str) -> Vec<Role> {
+        let Some(member) = self.member(guild_id, user_id) else {
+            return Vec::new();
+        };
+        self.guild_roles(guild_id)
+            .into_iter()
+            .filter(|role| member.has_role(&role.id))
+            .collect()
+    }
+
+    /// Inserts or updates a role in cache
+    pub fn cache_role(&self, guild_id: &str, role: Role) {
+        self.role_cache.insert(guild_id, role);
+    }
+
+    /// Removes a role from cache
+    pub fn remove_role(&self, role_id: &str) -> Option<Role> {
+        self.role_cache.remove(role_id)
+    }
+
+    /// Returns the number of cached roles (all guilds)
+    pub fn role_count(&self) -> usize {
+        self.role_cache.count()
+    }
+
+    // ==================== Emojis ====================
+
+    /// Gets a custom emoji from cache by ID
+    pub fn emoji(&self, emoji_id: &str) -> Option<Emoji> {
+        self.emoji_cache.get(emoji_id)
+    }
+
+    /// Gets the custom emojis of a guild
+    pub fn guild_emojis(&self, guild_id: &str) -> Vec<Emoji> {
+        self.emoji_cache.in_guild(guild_id)
+    }
+
+    /// Gets the custom emojis the current user can send in a guild (`None` for DMs)
+    ///
+    /// See `Emoji::is_usable`: without Nitro, only static emojis of the guild itself are
+    /// usable, and emojis restricted to roles require the current user to have one of them
+    /// (per the member cache).
+    pub fn usable_emojis(&self, guild_id: Option<&str>) -> Vec<Emoji> {
+        let Some(current_user) = self.current_user() else {
+            return Vec::new();
+        };
+        let has_nitro = current_user.has_nitro();
+        self.emoji_cache
+            .all()
+            .into_iter()
+            .filter(|(emoji_guild_id, emoji)| {
+                let member_roles = if emoji.roles.is_empty() {
+                    Vec::new()
+                } else {
+                    self.member_cache
+                        .peek(emoji_guild_id, &current_user.id)
+                        .map(|member| member.roles)
+                        .unwrap_or_default()
+                };
+                let in_own_guild = guild_id == Some(emoji_guild_id.as_str());
+                emoji.is_usable(in_own_guild, has_nitro, &member_roles)
+            })
+            .map(|(_, emoji)| emoji)
+            .collect()
+    }
+
+    /// Inserts or updates a custom emoji in cache
+    pub fn cache_emoji(&self, guild_id: &str, emoji: Emoji) {
+        self.emoji_cache.insert(guild_id, emoji);
+    }
+
+    /// Removes a custom emoji from cache
+    pub fn remove_emoji(&self, emoji_id: &str) -> Option<Emoji> {
+        self.emoji_cache.remove(emoji_id)
+    }
+
+    /// Returns the number of cached custom emojis (all guilds)
+    pub fn emoji_count(&self) -> usize {
+        self.emoji_cache.count()
+    }
+
+    /// Replaces the roles and emojis of a guild from a guild payload (fields left out are kept)
+    fn replace_guild_roles_and_emojis(&self, guild_id: &str, data: &Value) {
+        if let Some(Ok(roles)) = data
+            .get("roles")
+            .map(|roles| serde_json::from_value::<Vec<Role>>(roles.clone()))
+        {
+            self.role_cache.replace_guild(guild_id, roles);
+        }
+        if let Some(Ok(emojis)) = data
+            .get("emojis")
+            .map(|emojis| serde_json::from_value::<Vec<Emoji>>(emojis.clone()))
+        {
+            self.emoji_cache.replace_guild(guild_id, emojis);
+        }
+    }
+
     // ==================== Supplemental Guild Members ====================
 
     /// Gets merged supplemental members by guild id.
@@ -359,9 +1089,18 @@ impl Cache {
         self.channel_cache.clear();
         self.guild_cache.clear();
         self.relationship_cache.clear();
+        self.presence_cache.clear();
+        self.voice_state_cache.clear();
+        self.member_cache.clear();
+        self.role_cache.clear();
+        self.emoji_cache.clear();
+        self.thread_cache.clear();
+        self.message_cache.clear();
         self.read_states.clear();
         self.guild_members.clear();
         self.passive_channel_states.clear();
+        self.dm_channels.clear();
+        self.friend_suggestion_count.store(0, Ordering::Relaxed);
         *self.current_user.write() = None;
     }
 
@@ -373,6 +1112,7 @@ impl Cache {
     /// Clears only the channel cache
     pub fn clear_channels(&self) {
         self.channel_cache.clear();
+        self.dm_channels.clear();
     }
 
     /// Clears only the guild cache
@@ -385,12 +1125,131 @@ impl Cache {
         self.relationship_cache.clear();
     }
 
+    /// Clears only the presence cache
+    pub fn clear_presences(&self) {
+        self.presence_cache.clear();
+    }
+
+    /// Clears only the voice state cache
+    pub fn clear_voice_states(&self) {
+        self.voice_state_cache.clear();
+    }
+
+    /// Clears only the member cache
+    pub fn clear_members(&self) {
+        self.member_cache.clear();
+    }
+
+    /// Clears only the role cache
+    pub fn clear_roles(&self) {
+        self.role_cache.clear();
+    }
+
+    /// Clears only the emoji cache
+    pub fn clear_emojis(&self) {
+        self.emoji_cache.clear();
+    }
+
+    /// Clears only the thread cache
+    pub fn clear_threads(&self) {
+        self.thread_cache.clear();
+    }
+
+    /// Clears only the message cache
+    pub fn clear_messages(&self) {
+        self.message_cache.clear();
+    }
+
+    // ==================== Uncounted Lookups ====================
+
+    /// Gets a user without counting the lookup in `stats`, for the client's own lookups
+    pub(crate) fn peek_user(&self, user_id: &str) -> Option<User> {
+        self.user_cache.peek(user_id)
+    }
+
+    /// Gets a channel without counting the lookup in `stats`
+    pub(crate) fn peek_channel(&self, channel_id: &str) -> Option<Channel> {
+        self.channel_cache.peek(channel_id)
+    }
+
+    /// Gets a message without counting the lookup in `stats`
+    pub(crate) fn peek_message(&self, message_id: &str) -> Option<Message> {
+        self.message_cache.peek(message_id)
+    }
+
+    /// Gets a guild without counting the lookup in `stats`
+    pub(crate) fn peek_guild(&self, guild_id: &str) -> Option<Guild> {
+        self.guild_cache.peek(guild_id)
+    }
+
+    /// Gets a member without counting the lookup in `stats`
+    pub(crate) fn peek_member(&self, guild_id: &str, user_id: &str) -> Option<PartialMember> {
+        self.member_cache.peek(guild_id, user_id)
+    }
+
+    // ==================== Snapshots ====================
+
+    /// Copies the cached users, guilds, channels and relationships into a snapshot
+    pub fn snapshot(&self) -> CacheSnapshot {
+        CacheSnapshot {
+            version: CACHE_SNAPSHOT_VERSION,
+            users: self.user_cache.all(),
+            guilds: self.guild_cache.all(),
+            channels: self.channel_cache.all(),
+            relationships: self.relationship_cache.all(),
+        }
+    }
+
+    /// Inserts the entities of a snapshot, replacing cached entries with the same ID
+    ///
+    /// Disabled caches stay empty, and cache limits apply as for any insert.
+    pub fn restore(&self, snapshot: CacheSnapshot) {
+        snapshot
+            .users
+            .into_iter()
+            .for_each(|user| self.cache_user(user));
+        snapshot
+            .guilds
+            .into_iter()
+            .for_each(|guild| self.cache_guild(guild));
+        snapshot
+            .channels
+            .into_iter()
+            .for_each(|channel| self.cache_channel(channel));
+        snapshot
+            .relationships
+            .into_iter()
+            .for_each(|relationship| self.cache_relationship(relationship));
+    }
+
     /// Gets cache statistics
     pub fn stats(&self) -> CacheStats {
+        let counters = [
+            self.user_cache.counters(),
+            self.channel_cache.counters(),
+            self.guild_cache.counters(),
+            self.relationship_cache.counters(),
+            self.presence_cache.counters(),
+            self.voice_state_cache.counters(),
+            self.member_cache.counters(),
+            self.role_cache.counters(),
+            self.emoji_cache.counters(),
+            self.thread_cache.counters(),
+            self.message_cache.counters(),
+        ];
         CacheStats {
             users: self.user_count(),
             channels: self.channel_count(),
             guilds: self.guild_count(),
+            members: self.member_count(),
+            presences: self.presence_count(),
+            roles: self.role_count(),
+            emojis: self.emoji_count(),
+            threads: self.thread_count(),
+            messages: self.message_count(),
+            hits: counters.iter().map(|c| c.hits).sum(),
+            misses: counters.iter().map(|c| c.misses).sum(),
+            evictions: counters.iter().map(|c| c.evictions).sum(),
         }
     }
 
@@ -403,7 +1262,7 @@ impl Cache {
         let Some(user_id) = partial.get("id").and_then(|v| v.as_str()) else {
             return;
         };
-        let Some(existing) = self.user(user_id) else {
+        let Some(existing) = self.user_cache.peek(user_id) else {
             return;
         };
 
@@ -419,6 +1278,23 @@ impl Cache {
         }
     }
 
+    /// Merges a USER_UPDATE of the current user, even when the user cache is disabled
+    fn update_current_user_from_partial(&self, partial: &Value) {
+        let Some(current) = self.current_user() else {
+            return;
+        };
+        if partial.get("id").and_then(|v| v.as_str()) != Some(current.id.as_str()) {
+            return;
+        }
+        let Ok(mut merged) = serde_json::to_value(&current) else {
+            return;
+        };
+        merge_object_values(&mut merged, partial);
+        if let Ok(user) = serde_json::from_value::<User>(merged) {
+            self.set_current_user(user);
+        }
+    }
+
     fn update_user_presence_from_event(&self, presence_event: &Value) {
         let Some(user_id) = presence_event
             .get("user")
@@ -428,11 +1304,12 @@ impl Cache {
             return;
         };
 
-        let Some(mut user) = self.user(user_id) else {
+        let Ok(presence) = serde_json::from_value::<Presence>(presence_event.clone()) else {
             return;
         };
+        self.cache_presence(user_id, presence.clone());
 
-        if let Some(presence) = parse_presence_event(presence_event) {
+        if let Some(mut user) = self.user_cache.peek(user_id) {
             user.presence = Some(presence);
             self.cache_user(user.clone());
 
@@ -453,30 +1330,75 @@ impl Cache {
             return;
         };
 
-        for entry in &ready_supplemental.merged_presences.friends {
-            self.update_user_presence_from_merged_entry(entry);
+        for entry in ready_supplemental.presences() {
+            self.update_user_presence(&entry.user_id, entry.presence.clone());
         }
 
-        for entries in &ready_supplemental.merged_presences.guilds {
-            for entry in entries {
-                self.update_user_presence_from_merged_entry(entry);
-            }
+        for guild in ready_supplemental
+            .guilds
+            .iter()
+            .filter(|guild| !guild.id.is_empty())
+        {
+            self.voice_state_cache
+                .replace_guild(&guild.id, guild.voice_states.clone());
         }
 
-        if let Some(guilds) = data.get("guilds").and_then(|v| v.as_array()) {
-            for (idx, guild_payload) in guilds.iter().enumerate() {
-                let Some(guild_id) = guild_payload.get("id").and_then(|v| v.as_str()) else {
-                    continue;
-                };
-                let members = ready_supplemental
-                    .merged_members
-                    .get(idx)
-                    .cloned()
-                    .unwrap_or_default();
-                if !members.is_empty() {
-                    self.guild_members.insert(guild_id.to_string(), members);
+        for (guild_id, members) in ready_supplemental.guild_members() {
+            for member in members {
+                if let Ok(payload) = serde_json::to_value(member) {
+                    self.upsert_member_from_partial(guild_id, &member.user_id, &payload);
                 }
             }
+            if !members.is_empty() {
+                self.guild_members
+                    .insert(guild_id.to_string(), members.to_vec());
+            }
+        }
+    }
+
+    fn update_read_state_from_message(&self, message: &Message) {
+        let current_user_id = self.current_user().map(|user| user.id);
+        let is_own = current_user_id.as_deref() == Some(message.author.id.as_str());
+        let mentions_me = current_user_id
+            .as_deref()
+            .is_some_and(|id| message.mentions.iter().any(|user| user.id == id));
+
+        let mut entry = self
+            .read_states
+            .entry(message.channel_id.clone())
+            .or_insert_with(|| ReadStateEntry::new(message.channel_id.clone()));
+        entry.last_message_id = Some(message.id.clone());
+        if is_own {
+            // Sending a message acknowledges the channel
+            entry.last_acked_id = Some(message.id.clone());
+            entry.mention_count = Some(0);
+        } else if mentions_me || message.mention_everyone {
+            entry.mention_count = Some(entry.mentions() + 1);
+        }
+    }
+
+    fn update_read_state_from_ack(&self, data: &Value) {
+        let Some(channel_id) = data.get("channel_id").and_then(|v| v.as_str()) else {
+            return;
+        };
+
+        let mut entry = self
+            .read_states
+            .entry(channel_id.to_string())
+            .or_insert_with(|| ReadStateEntry::new(channel_id));
+        if let Some(message_id) = data.get("message_id").and_then(|v| v.as_str()) {
+            entry.last_acked_id = Some(message_id.to_string());
+        }
+        entry.mention_count = Some(
+            data.get("mention_count")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0),
+        );
+        if let Some(last_viewed) = data.get("last_viewed").and_then(|v| v.as_u64()) {
+            entry.last_viewed = Some(last_viewed);
+        }
+        if let Some(flags) = data.get("flags").and_then(|v| v.as_u64()) {
+            entry.flags = Some(flags);
         }
     }
 
@@ -484,9 +1406,12 @@ impl Cache {
         let Ok(payload) = serde_json::from_value::<PassiveUpdateV1>(data.clone()) else {
             return;
         };
+        if let Some(voice_states) = data.get("voice_states") {
+            self.replace_guild_voice_states(&payload.guild_id, voice_states);
+        }
 
         for state in payload.channels {
-            if let Some(mut channel) = self.channel(&state.id) {
+            if let Some(mut channel) = self.channel_cache.peek(&state.id) {
                 channel.last_message_id = state.last_message_id.clone();
                 channel.last_pin_timestamp = state.last_pin_timestamp.clone();
                 self.cache_channel(channel);
@@ -495,16 +1420,10 @@ impl Cache {
         }
     }
 
-    fn update_user_presence_from_merged_entry(&self, entry: &Value) {
-        let Some(user_id) = entry.get("user_id").and_then(|v| v.as_str()) else {
-            return;
-        };
-
-        let Some(mut user) = self.user(user_id) else {
-            return;
-        };
+    fn update_user_presence(&self, user_id: &str, presence: Presence) {
+        self.cache_presence(user_id, presence.clone());
 
-        if let Some(presence) = parse_merged_presence_entry(entry) {
+        if let Some(mut user) = self.user_cache.peek(user_id) {
             user.presence = Some(presence);
             self.cache_user(user.clone());
 
@@ -532,6 +1451,29 @@ pub struct CacheStats {
     pub users: usize,
     pub channels: usize,
     pub guilds: usize,
+    pub members: usize,
+    pub presences: usize,
+    pub roles: usize,
+    pub emojis: usize,
+    pub threads: usize,
+    pub messages: usize,
+    /// Lookups that found an entry, all caches combined
+    pub hits: u64,
+    /// Lookups that found nothing, all caches combined
+    pub misses: u64,
+    /// Entries dropped by `CacheLimits`, all caches combined
+    pub evictions: u64,
+}
+
+impl CacheStats {
+    /// Share of lookups that found an entry (0.0 before any lookup)
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / lookups as f64
+    }
 }
 
 fn merge_object_values(target: &mut Value, patch: &Value) {
@@ -546,42 +1488,3 @@ fn merge_object_values(target: &mut Value, patch: &Value) {
         target_obj.insert(key.clone(), value.clone());
     }
 }
-
-fn parse_presence_event(event: &Value) -> Option<Presence> {
-    let status = event.get("status").and_then(|v| v.as_str())?.to_string();
-    let activities = event
-        .get("activities")
-        .and_then(|v| v.as_array())
-        .cloned()
-        .unwrap_or_default();
-    let client_status = event.get("client_status").cloned();
-    let since = event.get("since").and_then(|v| v.as_i64());
-    let afk = event.get("afk").and_then(|v| v.as_bool());
-
-    Some(Presence {
-        status,
-        activities,
-        client_status: client_status.and_then(|v| serde_json::from_value(v).ok()),
-        since,
-        afk,
-    })
-}
-
-fn parse_merged_presence_entry(entry: &Value) -> Option<Presence> {
-    let status = entry.get("status").and_then(|v| v.as_str())?.to_string();
-    let activities = entry
-        .get("activities")
-        .and_then(|v| v.as_array())
-        .cloned()
-        .unwrap_or_default();
-    let client_status = entry.get("client_status").cloned();
-    let afk = entry.get("afk").and_then(|v| v.as_bool());
-
-    Some(Presence {
-        status,
-        activities,
-        client_status: client_status.and_then(|v| serde_json::from_value(v).ok()),
-        since: None,
-        afk,
-    })
-}
@@ -1,19 +1,19 @@
+use crate::cache::bounded::{BoundedMap, CacheCounters};
+use crate::cache::CachePolicy;
 use crate::model::{Channel, Guild};
-use dashmap::DashMap;
-use std::sync::Arc;
 
 /// Cache for channels (channel_id -> Channel)
 #[derive(Clone)]
 pub struct ChannelCache {
     enabled: bool,
-    channels: Arc<DashMap<String, Channel>>,
+    channels: BoundedMap<String, Channel>,
 }
 
 impl ChannelCache {
     pub fn new(enabled: bool) -> Self {
         Self {
             enabled,
-            channels: Arc::new(DashMap::new()),
+            channels: BoundedMap::new(CachePolicy::default()),
         }
     }
 
@@ -21,8 +21,24 @@ impl ChannelCache {
         self.enabled
     }
 
+    /// Applies a size/age limit, dropping the current entries
+    pub fn with_policy(mut self, policy: CachePolicy) -> Self {
+        self.channels = BoundedMap::new(policy);
+        self
+    }
+
+    /// Returns the hit, miss and eviction counters
+    pub fn counters(&self) -> CacheCounters {
+        self.channels.counters()
+    }
+
     pub fn get(&self, channel_id: &str) -> Option<Channel> {
-        self.channels.get(channel_id).map(|entry| entry.clone())
+        self.channels.get(channel_id)
+    }
+
+    /// Like `get`, without counting the lookup in the cache counters
+    pub(crate) fn peek(&self, channel_id: &str) -> Option<Channel> {
+        self.channels.peek(channel_id)
     }
 
     pub fn insert(&self, channel: Channel) {
@@ -32,7 +48,7 @@ impl ChannelCache {
     }
 
     pub fn remove(&self, channel_id: &str) -> Option<Channel> {
-        self.channels.remove(channel_id).map(|(_, channel)| channel)
+        self.channels.remove(channel_id)
     }
 
     pub fn count(&self) -> usize {
@@ -40,10 +56,7 @@ impl ChannelCache {
     }
 
     pub fn all(&self) -> Vec<Channel> {
-        self.channels
-            .iter()
-            .map(|entry| entry.value().clone())
-            .collect()
+        self.channels.values()
     }
 
     pub fn clear(&self) {
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 /// Configuration for the global cache behavior.
 #[derive(Debug, Clone)]
 pub struct CacheConfig {
@@ -9,6 +11,22 @@ pub struct CacheConfig {
     pub cache_guilds: bool,
     /// Whether to cache relationships.
     pub cache_relationships: bool,
+    /// Whether to cache presences.
+    pub cache_presences: bool,
+    /// Whether to cache voice states.
+    pub cache_voice_states: bool,
+    /// Whether to cache guild members.
+    pub cache_members: bool,
+    /// Whether to cache guild roles.
+    pub cache_roles: bool,
+    /// Whether to cache guild custom emojis.
+    pub cache_emojis: bool,
+    /// Whether to cache active threads.
+    pub cache_threads: bool,
+    /// Whether to cache messages received through the gateway.
+    pub cache_messages: bool,
+    /// Size and age limits of each cache (unbounded by default, except messages).
+    pub limits: CacheLimits,
 }
 
 impl Default for CacheConfig {
@@ -18,6 +36,98 @@ impl Default for CacheConfig {
             cache_channels: true,
             cache_guilds: true,
             cache_relationships: true,
+            cache_presences: true,
+            cache_voice_states: true,
+            cache_members: true,
+            cache_roles: true,
+            cache_emojis: true,
+            cache_threads: true,
+            cache_messages: true,
+            limits: CacheLimits::default(),
+        }
+    }
+}
+
+/// Per-cache eviction policies.
+///
+/// Every cache is unbounded by default, except messages which keep the last
+/// `DEFAULT_MESSAGE_CACHE_SIZE` messages.
+///
+/// # Example
+/// ```
+/// use diself::cache::{CacheConfig, CacheLimits, CachePolicy};
+/// use std::time::Duration;
+///
+/// let config = CacheConfig {
+///     limits: CacheLimits {
+///         users: CachePolicy::default().max_entries(10_000),
+///         presences: CachePolicy::default().ttl(Duration::from_secs(600)),
+///         ..CacheLimits::default()
+///     },
+///     ..CacheConfig::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheLimits {
+    pub users: CachePolicy,
+    pub channels: CachePolicy,
+    pub guilds: CachePolicy,
+    pub members: CachePolicy,
+    pub roles: CachePolicy,
+    pub emojis: CachePolicy,
+    pub threads: CachePolicy,
+    pub relationships: CachePolicy,
+    pub presences: CachePolicy,
+    pub voice_states: CachePolicy,
+    pub messages: CachePolicy,
+}
+
+/// Number of messages kept by the default message cache policy
+pub const DEFAULT_MESSAGE_CACHE_SIZE: usize = 1_000;
+
+impl Default for CacheLimits {
+    fn default() -> Self {
+        Self {
+            users: CachePolicy::default(),
+            channels: CachePolicy::default(),
+            guilds: CachePolicy::default(),
+            members: CachePolicy::default(),
+            roles: CachePolicy::default(),
+            emojis: CachePolicy::default(),
+            threads: CachePolicy::default(),
+            relationships: CachePolicy::default(),
+            presences: CachePolicy::default(),
+            voice_states: CachePolicy::default(),
+            messages: CachePolicy::default().max_entries(DEFAULT_MESSAGE_CACHE_SIZE),
         }
     }
 }
+
+/// Eviction policy of one cache
+///
+/// Past `max_entries`, the least recently used entries are evicted. Entries older than
+/// `ttl` (since their last insert) are dropped on access. The default policy is unbounded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CachePolicy {
+    pub(crate) max_entries: Option<usize>,
+    pub(crate) ttl: Option<Duration>,
+}
+
+impl CachePolicy {
+    /// Sets the maximum number of entries (min 1)
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = Some(max_entries.max(1));
+        self
+    }
+
+    /// Sets how long an entry stays valid after being inserted or updated
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Whether this policy never evicts
+    pub fn is_unbounded(&self) -> bool {
+        self.max_entries.is_none() && self.ttl.is_none()
+    }
+}
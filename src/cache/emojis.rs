@@ -0,0 +1,91 @@
+use crate::cache::bounded::{BoundedMap, CacheCounters};
+use crate::cache::CachePolicy;
+use crate::model::Emoji;
+
+/// Cache for guild custom emojis (emoji_id -> (guild_id, Emoji))
+#[derive(Clone)]
+pub struct EmojiCache {
+    enabled: bool,
+    emojis: BoundedMap<String, (String, Emoji)>,
+}
+
+impl EmojiCache {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            emojis: BoundedMap::new(CachePolicy::default()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Applies a size/age limit, dropping the current entries
+    pub fn with_policy(mut self, policy: CachePolicy) -> Self {
+        self.emojis = BoundedMap::new(policy);
+        self
+    }
+
+    /// Returns the hit, miss and eviction counters
+    pub fn counters(&self) -> CacheCounters {
+        self.emojis.counters()
+    }
+
+    pub fn get(&self, emoji_id: &str) -> Option<Emoji> {
+        self.emojis.get(emoji_id).map(|(_, emoji)| emoji)
+    }
+
+    /// Returns the ID of the guild owning an emoji
+    pub fn guild_of(&self, emoji_id: &str) -> Option<String> {
+        self.emojis.peek(emoji_id).map(|(guild_id, _)| guild_id)
+    }
+
+    /// Inserts a custom emoji (emojis without ID are ignored)
+    pub fn insert(&self, guild_id: impl Into<String>, emoji: Emoji) {
+        if !self.enabled {
+            return;
+        }
+        if let Some(emoji_id) = emoji.id.clone() {
+            self.emojis.insert(emoji_id, (guild_id.into(), emoji));
+        }
+    }
+
+    pub fn remove(&self, emoji_id: &str) -> Option<Emoji> {
+        self.emojis.remove(emoji_id).map(|(_, emoji)| emoji)
+    }
+
+    /// Replaces every emoji of a guild
+    pub fn replace_guild(&self, guild_id: &str, emojis: Vec<Emoji>) {
+        self.remove_guild(guild_id);
+        for emoji in emojis {
+            self.insert(guild_id, emoji);
+        }
+    }
+
+    /// Removes every emoji of a guild
+    pub fn remove_guild(&self, guild_id: &str) {
+        self.emojis.retain(|_, (guild, _)| guild != guild_id);
+    }
+
+    pub fn in_guild(&self, guild_id: &str) -> Vec<Emoji> {
+        self.emojis
+            .filter_values(|_, (guild, _)| guild == guild_id)
+            .into_iter()
+            .map(|(_, emoji)| emoji)
+            .collect()
+    }
+
+    /// Returns every cached emoji with the ID of its guild
+    pub fn all(&self) -> Vec<(String, Emoji)> {
+        self.emojis.values()
+    }
+
+    pub fn count(&self) -> usize {
+        self.emojis.len()
+    }
+
+    pub fn clear(&self) {
+        self.emojis.clear();
+    }
+}
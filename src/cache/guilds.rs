@@ -1,19 +1,19 @@
+use crate::cache::bounded::{BoundedMap, CacheCounters};
+use crate::cache::CachePolicy;
 use crate::model::Guild;
-use dashmap::DashMap;
-use std::sync::Arc;
 
 /// Cache for guilds (guild_id -> Guild)
 #[derive(Clone)]
 pub struct GuildCache {
     enabled: bool,
-    guilds: Arc<DashMap<String, Guild>>,
+    guilds: BoundedMap<String, Guild>,
 }
 
 impl GuildCache {
     pub fn new(enabled: bool) -> Self {
         Self {
             enabled,
-            guilds: Arc::new(DashMap::new()),
+            guilds: BoundedMap::new(CachePolicy::default()),
         }
     }
 
@@ -21,8 +21,24 @@ impl GuildCache {
         self.enabled
     }
 
+    /// Applies a size/age limit, dropping the current entries
+    pub fn with_policy(mut self, policy: CachePolicy) -> Self {
+        self.guilds = BoundedMap::new(policy);
+        self
+    }
+
+    /// Returns the hit, miss and eviction counters
+    pub fn counters(&self) -> CacheCounters {
+        self.guilds.counters()
+    }
+
     pub fn get(&self, guild_id: &str) -> Option<Guild> {
-        self.guilds.get(guild_id).map(|entry| entry.clone())
+        self.guilds.get(guild_id)
+    }
+
+    /// Like `get`, without counting the lookup in the cache counters
+    pub(crate) fn peek(&self, guild_id: &str) -> Option<Guild> {
+        self.guilds.peek(guild_id)
     }
 
     pub fn insert(&self, guild: Guild) {
@@ -32,7 +48,7 @@ impl GuildCache {
     }
 
     pub fn remove(&self, guild_id: &str) -> Option<Guild> {
-        self.guilds.remove(guild_id).map(|(_, guild)| guild)
+        self.guilds.remove(guild_id)
     }
 
     pub fn count(&self) -> usize {
@@ -40,10 +56,7 @@ impl GuildCache {
     }
 
     pub fn all(&self) -> Vec<Guild> {
-        self.guilds
-            .iter()
-            .map(|entry| entry.value().clone())
-            .collect()
+        self.guilds.values()
     }
 
     pub fn clear(&self) {
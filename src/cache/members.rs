@@ -0,0 +1,76 @@
+use crate::cache::bounded::{BoundedMap, CacheCounters};
+use crate::cache::CachePolicy;
+use crate::model::PartialMember;
+
+/// Cache for guild members ((guild_id, user_id) -> PartialMember)
+#[derive(Clone)]
+pub struct MemberCache {
+    enabled: bool,
+    members: BoundedMap<(String, String), PartialMember>,
+}
+
+impl MemberCache {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            members: BoundedMap::new(CachePolicy::default()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Applies a size/age limit, dropping the current entries
+    pub fn with_policy(mut self, policy: CachePolicy) -> Self {
+        self.members = BoundedMap::new(policy);
+        self
+    }
+
+    /// Returns the hit, miss and eviction counters
+    pub fn counters(&self) -> CacheCounters {
+        self.members.counters()
+    }
+
+    pub fn get(&self, guild_id: &str, user_id: &str) -> Option<PartialMember> {
+        self.members
+            .get(&(guild_id.to_string(), user_id.to_string()))
+    }
+
+    /// Like `get`, without counting the lookup in the cache counters
+    pub(crate) fn peek(&self, guild_id: &str, user_id: &str) -> Option<PartialMember> {
+        self.members
+            .peek(&(guild_id.to_string(), user_id.to_string()))
+    }
+
+    pub fn insert(&self, member: PartialMember) {
+        if !self.enabled {
+            return;
+        }
+        self.members
+            .insert((member.guild_id.clone(), member.user_id.clone()), member);
+    }
+
+    pub fn remove(&self, guild_id: &str, user_id: &str) -> Option<PartialMember> {
+        self.members
+            .remove(&(guild_id.to_string(), user_id.to_string()))
+    }
+
+    /// Removes every member of a guild
+    pub fn remove_guild(&self, guild_id: &str) {
+        self.members.retain(|(guild, _), _| guild != guild_id);
+    }
+
+    pub fn in_guild(&self, guild_id: &str) -> Vec<PartialMember> {
+        self.members
+            .filter_values(|(guild, _), _| guild == guild_id)
+    }
+
+    pub fn count(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn clear(&self) {
+        self.members.clear();
+    }
+}
@@ -0,0 +1,85 @@
+use crate::cache::bounded::{BoundedMap, CacheCounters};
+use crate::cache::CachePolicy;
+use crate::model::Message;
+
+/// Cache for recently seen messages (message_id -> Message)
+///
+/// Keeps the messages received through the gateway so update and delete events can
+/// be compared to the previous state.
+#[derive(Clone)]
+pub struct MessageCache {
+    enabled: bool,
+    messages: BoundedMap<String, Message>,
+}
+
+impl MessageCache {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            messages: BoundedMap::new(CachePolicy::default()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Applies a size/age limit, dropping the current entries
+    pub fn with_policy(mut self, policy: CachePolicy) -> Self {
+        self.messages = BoundedMap::new(policy);
+        self
+    }
+
+    /// Returns the hit, miss and eviction counters
+    pub fn counters(&self) -> CacheCounters {
+        self.messages.counters()
+    }
+
+    pub fn get(&self, message_id: &str) -> Option<Message> {
+        self.messages.get(message_id)
+    }
+
+    /// Like `get`, without counting the lookup in the cache counters
+    pub(crate) fn peek(&self, message_id: &str) -> Option<Message> {
+        self.messages.peek(message_id)
+    }
+
+    pub fn insert(&self, message: Message) {
+        if self.enabled {
+            self.messages.insert(message.id.clone(), message);
+        }
+    }
+
+    pub fn remove(&self, message_id: &str) -> Option<Message> {
+        self.messages.remove(message_id)
+    }
+
+    /// Returns the cached messages of a channel, oldest first
+    pub fn in_channel(&self, channel_id: &str) -> Vec<Message> {
+        let mut messages = self
+            .messages
+            .filter_values(|_, message| message.channel_id == channel_id);
+        messages.sort_by_key(|message| message.message_id());
+        messages
+    }
+
+    /// Removes the messages of a channel
+    pub fn remove_channel(&self, channel_id: &str) {
+        self.messages
+            .retain(|_, message| message.channel_id != channel_id);
+    }
+
+    /// Removes every message of a guild
+    pub fn remove_guild(&self, guild_id: &str) {
+        self.messages
+            .retain(|_, message| message.guild_id.as_deref() != Some(guild_id));
+    }
+
+    pub fn count(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn clear(&self) {
+        self.messages.clear();
+    }
+}
@@ -1,13 +1,31 @@
+mod bounded;
 mod cache;
 mod channels;
 mod config;
+mod emojis;
 mod guilds;
+mod members;
+mod messages;
+mod presences;
 mod relationships;
+mod roles;
+mod snapshot;
+mod threads;
 mod users;
+mod voice_states;
 
+pub use bounded::CacheCounters;
 pub use cache::{Cache, CacheStats};
 pub use channels::ChannelCache;
-pub use config::CacheConfig;
+pub use config::{CacheConfig, CacheLimits, CachePolicy, DEFAULT_MESSAGE_CACHE_SIZE};
+pub use emojis::EmojiCache;
 pub use guilds::GuildCache;
+pub use members::MemberCache;
+pub use messages::MessageCache;
+pub use presences::PresenceCache;
 pub use relationships::RelationshipCache;
+pub use roles::RoleCache;
+pub use snapshot::{CacheSnapshot, CACHE_SNAPSHOT_VERSION};
+pub use threads::ThreadCache;
 pub use users::UserCache;
+pub use voice_states::VoiceStateCache;
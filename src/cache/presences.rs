@@ -0,0 +1,56 @@
+use crate::cache::bounded::{BoundedMap, CacheCounters};
+use crate::cache::CachePolicy;
+use crate::model::Presence;
+
+/// Cache for presences (user_id -> Presence)
+#[derive(Clone)]
+pub struct PresenceCache {
+    enabled: bool,
+    presences: BoundedMap<String, Presence>,
+}
+
+impl PresenceCache {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            presences: BoundedMap::new(CachePolicy::default()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Applies a size/age limit, dropping the current entries
+    pub fn with_policy(mut self, policy: CachePolicy) -> Self {
+        self.presences = BoundedMap::new(policy);
+        self
+    }
+
+    /// Returns the hit, miss and eviction counters
+    pub fn counters(&self) -> CacheCounters {
+        self.presences.counters()
+    }
+
+    pub fn get(&self, user_id: &str) -> Option<Presence> {
+        self.presences.get(user_id)
+    }
+
+    pub fn insert(&self, user_id: impl Into<String>, presence: Presence) {
+        if self.enabled {
+            self.presences.insert(user_id.into(), presence);
+        }
+    }
+
+    pub fn remove(&self, user_id: &str) -> Option<Presence> {
+        self.presences.remove(user_id)
+    }
+
+    pub fn count(&self) -> usize {
+        self.presences.len()
+    }
+
+    pub fn clear(&self) {
+        self.presences.clear();
+    }
+}
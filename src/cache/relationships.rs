@@ -1,19 +1,19 @@
+use crate::cache::bounded::{BoundedMap, CacheCounters};
+use crate::cache::CachePolicy;
 use crate::model::{Relationship, RelationshipType};
-use dashmap::DashMap;
-use std::sync::Arc;
 
 /// Cache for relationships (user_id -> Relationship)
 #[derive(Clone)]
 pub struct RelationshipCache {
     enabled: bool,
-    relationships: Arc<DashMap<String, Relationship>>,
+    relationships: BoundedMap<String, Relationship>,
 }
 
 impl RelationshipCache {
     pub fn new(enabled: bool) -> Self {
         Self {
             enabled,
-            relationships: Arc::new(DashMap::new()),
+            relationships: BoundedMap::new(CachePolicy::default()),
         }
     }
 
@@ -21,8 +21,24 @@ impl RelationshipCache {
         self.enabled
     }
 
+    /// Applies a size/age limit, dropping the current entries
+    pub fn with_policy(mut self, policy: CachePolicy) -> Self {
+        self.relationships = BoundedMap::new(policy);
+        self
+    }
+
+    /// Returns the hit, miss and eviction counters
+    pub fn counters(&self) -> CacheCounters {
+        self.relationships.counters()
+    }
+
     pub fn get(&self, user_id: &str) -> Option<Relationship> {
-        self.relationships.get(user_id).map(|entry| entry.clone())
+        self.relationships.get(user_id)
+    }
+
+    /// Like `get`, without counting the lookup in the cache counters
+    pub(crate) fn peek(&self, user_id: &str) -> Option<Relationship> {
+        self.relationships.peek(user_id)
     }
 
     pub fn insert(&self, relationship: Relationship) {
@@ -33,7 +49,7 @@ impl RelationshipCache {
     }
 
     pub fn remove(&self, user_id: &str) -> Option<Relationship> {
-        self.relationships.remove(user_id).map(|(_, rel)| rel)
+        self.relationships.remove(user_id)
     }
 
     pub fn count(&self) -> usize {
@@ -41,10 +57,7 @@ impl RelationshipCache {
     }
 
     pub fn all(&self) -> Vec<Relationship> {
-        self.relationships
-            .iter()
-            .map(|entry| entry.value().clone())
-            .collect()
+        self.relationships.values()
     }
 
     pub fn clear(&self) {
@@ -70,9 +83,6 @@ impl RelationshipCache {
 
     pub fn friends(&self) -> Vec<Relationship> {
         self.relationships
-            .iter()
-            .filter(|entry| entry.value().kind == RelationshipType::Friend)
-            .map(|entry| entry.value().clone())
-            .collect()
+            .filter_values(|_, relationship| relationship.kind == RelationshipType::Friend)
     }
 }
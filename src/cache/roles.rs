@@ -0,0 +1,86 @@
+use crate::cache::bounded::{BoundedMap, CacheCounters};
+use crate::cache::CachePolicy;
+use crate::model::Role;
+
+/// Cache for guild roles (role_id -> (guild_id, Role))
+#[derive(Clone)]
+pub struct RoleCache {
+    enabled: bool,
+    roles: BoundedMap<String, (String, Role)>,
+}
+
+impl RoleCache {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            roles: BoundedMap::new(CachePolicy::default()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Applies a size/age limit, dropping the current entries
+    pub fn with_policy(mut self, policy: CachePolicy) -> Self {
+        self.roles = BoundedMap::new(policy);
+        self
+    }
+
+    /// Returns the hit, miss and eviction counters
+    pub fn counters(&self) -> CacheCounters {
+        self.roles.counters()
+    }
+
+    pub fn get(&self, role_id: &str) -> Option<Role> {
+        self.roles.get(role_id).map(|(_, role)| role)
+    }
+
+    /// Returns the ID of the guild owning a role
+    pub fn guild_of(&self, role_id: &str) -> Option<String> {
+        self.roles.peek(role_id).map(|(guild_id, _)| guild_id)
+    }
+
+    pub fn insert(&self, guild_id: impl Into<String>, role: Role) {
+        if self.enabled {
+            self.roles.insert(role.id.clone(), (guild_id.into(), role));
+        }
+    }
+
+    pub fn remove(&self, role_id: &str) -> Option<Role> {
+        self.roles.remove(role_id).map(|(_, role)| role)
+    }
+
+    /// Replaces every role of a guild
+    pub fn replace_guild(&self, guild_id: &str, roles: Vec<Role>) {
+        self.remove_guild(guild_id);
+        for role in roles {
+            self.insert(guild_id, role);
+        }
+    }
+
+    /// Removes every role of a guild
+    pub fn remove_guild(&self, guild_id: &str) {
+        self.roles.retain(|_, (guild, _)| guild != guild_id);
+    }
+
+    /// Returns the roles of a guild, sorted by position (lowest first)
+    pub fn in_guild(&self, guild_id: &str) -> Vec<Role> {
+        let mut roles: Vec<Role> = self
+            .roles
+            .filter_values(|_, (guild, _)| guild == guild_id)
+            .into_iter()
+            .map(|(_, role)| role)
+            .collect();
+        roles.sort_by_key(|role| role.position);
+        roles
+    }
+
+    pub fn count(&self) -> usize {
+        self.roles.len()
+    }
+
+    pub fn clear(&self) {
+        self.roles.clear();
+    }
+}
@@ -0,0 +1,74 @@
+use crate::error::{Error, Result};
+use crate::model::{Channel, Guild, Relationship, User};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Format version written in snapshots, bumped when the layout changes
+pub const CACHE_SNAPSHOT_VERSION: u32 = 1;
+
+/// Serializable copy of the users, guilds, channels and relationships of a `Cache`
+///
+/// Restoring a snapshot on start lets handlers read cached state right away, before
+/// READY (and its guild payloads) repopulates the cache.
+///
+/// # Example
+/// ```ignore
+/// client.cache().snapshot().save("cache.json").await?;
+///
+/// // Later, in a new process
+/// let snapshot = CacheSnapshot::load("cache.json").await?;
+/// client.cache().restore(snapshot);
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CacheSnapshot {
+    /// Format version (see `CACHE_SNAPSHOT_VERSION`)
+    pub version: u32,
+
+    /// Cached users
+    #[serde(default)]
+    pub users: Vec<User>,
+
+    /// Cached guilds
+    #[serde(default)]
+    pub guilds: Vec<Guild>,
+
+    /// Cached channels
+    #[serde(default)]
+    pub channels: Vec<Channel>,
+
+    /// Cached relationships
+    #[serde(default)]
+    pub relationships: Vec<Relationship>,
+}
+
+impl CacheSnapshot {
+    /// Returns `true` if the snapshot holds no entity
+    pub fn is_empty(&self) -> bool {
+        self.users.is_empty()
+            && self.guilds.is_empty()
+            && self.channels.is_empty()
+            && self.relationships.is_empty()
+    }
+
+    /// Writes the snapshot as JSON to a file
+    pub async fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let json = serde_json::to_vec(self)?;
+        tokio::fs::write(path, json).await?;
+        Ok(())
+    }
+
+    /// Reads a snapshot previously written with `save`
+    ///
+    /// Fails if the file was written by an incompatible version of the crate.
+    pub async fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let bytes = tokio::fs::read(path).await?;
+        let snapshot: Self = serde_json::from_slice(&bytes)?;
+        if snapshot.version != CACHE_SNAPSHOT_VERSION {
+            return Err(Error::UnsupportedVersion {
+                found: snapshot.version,
+                supported: CACHE_SNAPSHOT_VERSION,
+            });
+        }
+        Ok(snapshot)
+    }
+}
@@ -0,0 +1,91 @@
+use crate::cache::bounded::{BoundedMap, CacheCounters};
+use crate::cache::CachePolicy;
+use crate::model::Channel;
+
+/// Cache for active threads (thread_id -> Channel), indexed by parent channel
+#[derive(Clone)]
+pub struct ThreadCache {
+    enabled: bool,
+    threads: BoundedMap<String, Channel>,
+}
+
+impl ThreadCache {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            threads: BoundedMap::new(CachePolicy::default()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Applies a size/age limit, dropping the current entries
+    pub fn with_policy(mut self, policy: CachePolicy) -> Self {
+        self.threads = BoundedMap::new(policy);
+        self
+    }
+
+    /// Returns the hit, miss and eviction counters
+    pub fn counters(&self) -> CacheCounters {
+        self.threads.counters()
+    }
+
+    pub fn get(&self, thread_id: &str) -> Option<Channel> {
+        self.threads.get(thread_id)
+    }
+
+    /// Like `get`, without counting the lookup in the cache counters
+    pub(crate) fn peek(&self, thread_id: &str) -> Option<Channel> {
+        self.threads.peek(thread_id)
+    }
+
+    /// Inserts an active thread; archived threads are removed instead
+    pub fn insert(&self, thread: Channel) {
+        if !self.enabled {
+            return;
+        }
+        if thread.is_archived() {
+            self.threads.remove(&thread.id);
+        } else {
+            self.threads.insert(thread.id.clone(), thread);
+        }
+    }
+
+    pub fn remove(&self, thread_id: &str) -> Option<Channel> {
+        self.threads.remove(thread_id)
+    }
+
+    /// Returns the active threads of a parent channel
+    pub fn in_channel(&self, parent_id: &str) -> Vec<Channel> {
+        self.threads
+            .filter_values(|_, thread| thread.parent_id.as_deref() == Some(parent_id))
+    }
+
+    /// Returns the active threads of a guild
+    pub fn in_guild(&self, guild_id: &str) -> Vec<Channel> {
+        self.threads
+            .filter_values(|_, thread| thread.guild_id.as_deref() == Some(guild_id))
+    }
+
+    /// Removes the threads of a parent channel
+    pub fn remove_channel(&self, parent_id: &str) {
+        self.threads
+            .retain(|_, thread| thread.parent_id.as_deref() != Some(parent_id));
+    }
+
+    /// Removes every thread of a guild
+    pub fn remove_guild(&self, guild_id: &str) {
+        self.threads
+            .retain(|_, thread| thread.guild_id.as_deref() != Some(guild_id));
+    }
+
+    pub fn count(&self) -> usize {
+        self.threads.len()
+    }
+
+    pub fn clear(&self) {
+        self.threads.clear();
+    }
+}
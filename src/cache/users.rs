@@ -1,18 +1,18 @@
+use crate::cache::bounded::{BoundedMap, CacheCounters};
+use crate::cache::CachePolicy;
 use crate::model::User;
-use dashmap::DashMap;
-use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct UserCache {
     enabled: bool,
-    users: Arc<DashMap<String, User>>,
+    users: BoundedMap<String, User>,
 }
 
 impl UserCache {
     pub fn new(enabled: bool) -> Self {
         Self {
             enabled,
-            users: Arc::new(DashMap::new()),
+            users: BoundedMap::new(CachePolicy::default()),
         }
     }
 
@@ -20,8 +20,24 @@ impl UserCache {
         self.enabled
     }
 
+    /// Applies a size/age limit, dropping the current entries
+    pub fn with_policy(mut self, policy: CachePolicy) -> Self {
+        self.users = BoundedMap::new(policy);
+        self
+    }
+
+    /// Returns the hit, miss and eviction counters
+    pub fn counters(&self) -> CacheCounters {
+        self.users.counters()
+    }
+
     pub fn get(&self, user_id: &str) -> Option<User> {
-        self.users.get(user_id).map(|entry| entry.clone())
+        self.users.get(user_id)
+    }
+
+    /// Like `get`, without counting the lookup in the cache counters
+    pub(crate) fn peek(&self, user_id: &str) -> Option<User> {
+        self.users.peek(user_id)
     }
 
     pub fn insert(&self, user: User) {
@@ -31,7 +47,7 @@ impl UserCache {
     }
 
     pub fn remove(&self, user_id: &str) -> Option<User> {
-        self.users.remove(user_id).map(|(_, user)| user)
+        self.users.remove(user_id)
     }
 
     pub fn count(&self) -> usize {
@@ -39,10 +55,7 @@ impl UserCache {
     }
 
     pub fn all(&self) -> Vec<User> {
-        self.users
-            .iter()
-            .map(|entry| entry.value().clone())
-            .collect()
+        self.users.values()
     }
 
     pub fn clear(&self) {
@@ -0,0 +1,80 @@
+use crate::cache::bounded::{BoundedMap, CacheCounters};
+use crate::cache::CachePolicy;
+use crate::model::VoiceState;
+
+/// Cache for voice states ((guild_id, user_id) -> VoiceState, `None` guild for private calls)
+#[derive(Clone)]
+pub struct VoiceStateCache {
+    enabled: bool,
+    voice_states: BoundedMap<(Option<String>, String), VoiceState>,
+}
+
+impl VoiceStateCache {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            voice_states: BoundedMap::new(CachePolicy::default()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Applies a size/age limit, dropping the current entries
+    pub fn with_policy(mut self, policy: CachePolicy) -> Self {
+        self.voice_states = BoundedMap::new(policy);
+        self
+    }
+
+    /// Returns the hit, miss and eviction counters
+    pub fn counters(&self) -> CacheCounters {
+        self.voice_states.counters()
+    }
+
+    pub fn get(&self, guild_id: Option<&str>, user_id: &str) -> Option<VoiceState> {
+        self.voice_states
+            .get(&(guild_id.map(ToOwned::to_owned), user_id.to_string()))
+    }
+
+    /// Inserts a voice state, or removes it when the user left the channel
+    pub fn insert(&self, voice_state: VoiceState) {
+        if !self.enabled {
+            return;
+        }
+        let key = (voice_state.guild_id.clone(), voice_state.user_id.clone());
+        if voice_state.is_connected() {
+            self.voice_states.insert(key, voice_state);
+        } else {
+            self.voice_states.remove(&key);
+        }
+    }
+
+    /// Replaces every voice state of a guild
+    pub fn replace_guild(&self, guild_id: &str, voice_states: Vec<VoiceState>) {
+        self.voice_states
+            .retain(|(guild, _), _| guild.as_deref() != Some(guild_id));
+        for mut voice_state in voice_states {
+            voice_state.guild_id = Some(guild_id.to_string());
+            self.insert(voice_state);
+        }
+    }
+
+    pub fn in_channel(&self, channel_id: &str) -> Vec<VoiceState> {
+        self.voice_states
+            .filter_values(|_, voice_state| voice_state.channel_id.as_deref() == Some(channel_id))
+    }
+
+    pub fn in_guild(&self, guild_id: &str) -> Vec<VoiceState> {
+        self.voice_states
+            .filter_values(|(guild, _), _| guild.as_deref() == Some(guild_id))
+    }
+
+    pub fn count(&self) -> usize {
+        self.voice_states.len()
+    }
+
+    pub fn clear(&self) {
+        self.voice_states.clear();
+    }
+}
@@ -0,0 +1,223 @@
+//! URLs and downloads of the images served by the Discord CDN
+//!
+//! Every builder returns an `ImageUrl`, animated (`.gif`) when the hash starts with `a_`
+//! and PNG otherwise, whose size and format can be changed.
+//! SEE: <https://docs.discord.food/reference#cdn-formatting>
+//!
+//! # Example
+//! ```
+//! use diself::cdn::{self, ImageFormat};
+//!
+//! let url = cdn::user_avatar("123", "a_hash").size(256).format(ImageFormat::WebP);
+//! assert_eq!(url.to_string(), "https://cdn.discordapp.com/avatars/123/a_hash.webp?size=256");
+//! ```
+
+use crate::error::{Error, Result};
+use crate::http::HttpClient;
+use std::fmt;
+
+/// Base URL of the Discord CDN
+pub const CDN_URL: &str = "https://cdn.discordapp.com";
+
+/// Image format of a CDN asset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    WebP,
+    /// Only available for animated assets
+    Gif,
+}
+
+impl ImageFormat {
+    /// File extension of the format
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Jpeg => "jpg",
+            ImageFormat::WebP => "webp",
+            ImageFormat::Gif => "gif",
+        }
+    }
+}
+
+/// URL of a CDN image, displayed as the full URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageUrl {
+    path: String,
+    animated: bool,
+    format: Option<ImageFormat>,
+    size: Option<u16>,
+}
+
+impl ImageUrl {
+    /// Image at `path` (e.g. `/avatars/{user_id}/{hash}`), without extension
+    pub fn new(path: impl Into<String>, animated: bool) -> Self {
+        Self {
+            path: path.into(),
+            animated,
+            format: None,
+            size: None,
+        }
+    }
+
+    /// Image of an asset hash, animated if the hash starts with `a_`
+    fn from_hash(path: String, hash: &str) -> Self {
+        Self::new(path, is_animated(hash))
+    }
+
+    /// Sets the size in pixels, rounded up to a power of two between 16 and 4096
+    pub fn size(mut self, size: u16) -> Self {
+        self.size = Some(size.clamp(16, 4096).next_power_of_two());
+        self
+    }
+
+    /// Sets the format (GIF for animated assets and PNG otherwise by default)
+    ///
+    /// `ImageFormat::Gif` is ignored for assets that are not animated.
+    pub fn format(mut self, format: ImageFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Whether the asset is animated
+    pub fn is_animated(&self) -> bool {
+        self.animated
+    }
+
+    /// Returns the format the URL points to
+    pub fn image_format(&self) -> ImageFormat {
+        match self.format {
+            Some(ImageFormat::Gif) if !self.animated => ImageFormat::Png,
+            Some(format) => format,
+            None if self.animated => ImageFormat::Gif,
+            None => ImageFormat::Png,
+        }
+    }
+
+    /// Downloads the image
+    pub async fn fetch(&self, http: &HttpClient) -> Result<Vec<u8>> {
+        fetch(http, self.to_string()).await
+    }
+}
+
+impl fmt::Display for ImageUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}.{}",
+            CDN_URL,
+            self.path,
+            self.image_format().extension()
+        )?;
+        if let Some(size) = self.size {
+            write!(f, "?size={}", size)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<ImageUrl> for String {
+    fn from(url: ImageUrl) -> Self {
+        url.to_string()
+    }
+}
+
+/// Whether an asset hash is animated (starts with `a_`)
+pub fn is_animated(hash: &str) -> bool {
+    hash.starts_with("a_")
+}
+
+/// Avatar of a user
+pub fn user_avatar(user_id: &str, hash: &str) -> ImageUrl {
+    ImageUrl::from_hash(format!("/avatars/{}/{}", user_id, hash), hash)
+}
+
+/// Default avatar of users without one, `index` being returned by `User::default_avatar_index`
+pub fn default_avatar(index: u8) -> ImageUrl {
+    ImageUrl::new(format!("/embed/avatars/{}", index), false)
+}
+
+/// Profile banner of a user
+pub fn user_banner(user_id: &str, hash: &str) -> ImageUrl {
+    ImageUrl::from_hash(format!("/banners/{}/{}", user_id, hash), hash)
+}
+
+/// Avatar of a member in a guild
+pub fn member_avatar(guild_id: &str, user_id: &str, hash: &str) -> ImageUrl {
+    let path = format!("/guilds/{}/users/{}/avatars/{}", guild_id, user_id, hash);
+    ImageUrl::from_hash(path, hash)
+}
+
+/// Profile banner of a member in a guild
+pub fn member_banner(guild_id: &str, user_id: &str, hash: &str) -> ImageUrl {
+    let path = format!("/guilds/{}/users/{}/banners/{}", guild_id, user_id, hash);
+    ImageUrl::from_hash(path, hash)
+}
+
+/// Avatar decoration (always a static PNG, the animation being in APNG)
+pub fn avatar_decoration(asset: &str) -> ImageUrl {
+    ImageUrl::new(format!("/avatar-decoration-presets/{}", asset), false)
+}
+
+/// Icon of a guild
+pub fn guild_icon(guild_id: &str, hash: &str) -> ImageUrl {
+    ImageUrl::from_hash(format!("/icons/{}/{}", guild_id, hash), hash)
+}
+
+/// Invite splash of a guild
+pub fn guild_splash(guild_id: &str, hash: &str) -> ImageUrl {
+    ImageUrl::from_hash(format!("/splashes/{}/{}", guild_id, hash), hash)
+}
+
+/// Discovery splash of a guild
+pub fn guild_discovery_splash(guild_id: &str, hash: &str) -> ImageUrl {
+    ImageUrl::from_hash(format!("/discovery-splashes/{}/{}", guild_id, hash), hash)
+}
+
+/// Banner of a guild
+pub fn guild_banner(guild_id: &str, hash: &str) -> ImageUrl {
+    ImageUrl::from_hash(format!("/banners/{}/{}", guild_id, hash), hash)
+}
+
+/// Badge of a server tag
+pub fn guild_tag_badge(guild_id: &str, hash: &str) -> ImageUrl {
+    ImageUrl::new(format!("/guild-tag-badges/{}/{}", guild_id, hash), false)
+}
+
+/// Icon of a role
+pub fn role_icon(role_id: &str, hash: &str) -> ImageUrl {
+    ImageUrl::new(format!("/role-icons/{}/{}", role_id, hash), false)
+}
+
+/// Icon of a group DM
+pub fn channel_icon(channel_id: &str, hash: &str) -> ImageUrl {
+    ImageUrl::new(format!("/channel-icons/{}/{}", channel_id, hash), false)
+}
+
+/// Icon of an application
+pub fn application_icon(application_id: &str, hash: &str) -> ImageUrl {
+    ImageUrl::new(format!("/app-icons/{}/{}", application_id, hash), false)
+}
+
+/// Custom emoji
+pub fn emoji(emoji_id: &str, animated: bool) -> ImageUrl {
+    ImageUrl::new(format!("/emojis/{}", emoji_id), animated)
+}
+
+/// Banner of a sticker pack
+pub fn sticker_pack_banner(asset_id: &str) -> ImageUrl {
+    let path = format!("/app-assets/710982414301790216/store/{}", asset_id);
+    ImageUrl::new(path, false)
+}
+
+/// Downloads a CDN file (or any public URL)
+pub async fn fetch(http: &HttpClient, url: impl AsRef<str>) -> Result<Vec<u8>> {
+    let response = http.cdn_get(url).await?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(Error::from_status(status.as_u16(), body));
+    }
+    Ok(response.bytes().await?.to_vec())
+}
@@ -0,0 +1,184 @@
+use crate::client::bulk::{BulkReport, PacedRequests};
+use crate::error::{Error, Result};
+use crate::http::{api_url, HttpClient};
+use crate::model::{Relationship, RelationshipAction, RelationshipType};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::Duration;
+
+/// Format version written in block list files, bumped when the layout changes
+pub const BLOCK_LIST_VERSION: u32 = 1;
+
+/// Default pause between two users of a block list import
+pub const DEFAULT_IMPORT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Blocked and ignored users of an account, to move them to another account
+///
+/// # Example
+/// ```ignore
+/// let list = ctx.relationships.export_block_list(&ctx.http).await?;
+/// list.save("blocked.json").await?;
+///
+/// // Logged in with the other account
+/// let list = BlockList::load("blocked.json").await?;
+/// let report = ctx.relationships.import_block_list(&ctx.http, &list).await?;
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BlockList {
+    /// Format version (see `BLOCK_LIST_VERSION`)
+    pub version: u32,
+
+    /// Blocked users
+    #[serde(default)]
+    pub blocked: Vec<BlockListEntry>,
+
+    /// Ignored users
+    #[serde(default)]
+    pub ignored: Vec<BlockListEntry>,
+}
+
+/// A user of a `BlockList`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockListEntry {
+    /// ID of the user
+    pub id: String,
+
+    /// Username when the list was exported, for reference
+    #[serde(default)]
+    pub username: Option<String>,
+}
+
+impl BlockListEntry {
+    fn from_relationship(relationship: &Relationship) -> Self {
+        Self {
+            id: relationship.id.clone(),
+            username: relationship.user.as_ref().map(|user| user.username.clone()),
+        }
+    }
+}
+
+impl BlockList {
+    /// Picks the blocked and ignored users among relationships
+    pub fn from_relationships(relationships: &[Relationship]) -> Self {
+        Self {
+            version: BLOCK_LIST_VERSION,
+            blocked: relationships
+                .iter()
+                .filter(|relationship| relationship.is_blocked())
+                .map(BlockListEntry::from_relationship)
+                .collect(),
+            ignored: relationships
+                .iter()
+                .filter(|relationship| relationship.user_ignored)
+                .map(BlockListEntry::from_relationship)
+                .collect(),
+        }
+    }
+
+    /// Returns `true` if the list holds no user
+    pub fn is_empty(&self) -> bool {
+        self.blocked.is_empty() && self.ignored.is_empty()
+    }
+
+    /// Writes the list as JSON to a file
+    pub async fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let json = serde_json::to_vec_pretty(self)?;
+        tokio::fs::write(path, json).await?;
+        Ok(())
+    }
+
+    /// Reads a list previously written with `save`
+    ///
+    /// Fails if the file was written by an incompatible version of the crate.
+    pub async fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let bytes = tokio::fs::read(path).await?;
+        let list: Self = serde_json::from_slice(&bytes)?;
+        if list.version != BLOCK_LIST_VERSION {
+            return Err(Error::UnsupportedVersion {
+                found: list.version,
+                supported: BLOCK_LIST_VERSION,
+            });
+        }
+        Ok(list)
+    }
+}
+
+/// Outcome of `RelationshipsManager::import_block_list`, see `BulkReport` for the failed users
+#[derive(Debug, Default)]
+pub struct BlockListImport {
+    /// Users blocked
+    pub blocked: Vec<String>,
+
+    /// Users ignored
+    pub ignored: Vec<String>,
+
+    /// Users already blocked or ignored, left untouched
+    pub skipped: Vec<String>,
+
+    /// Users that could not be blocked or ignored, with the last error
+    pub failed: Vec<(String, Error)>,
+}
+
+impl BulkReport for BlockListImport {
+    fn failures(&self) -> &[(String, Error)] {
+        &self.failed
+    }
+}
+
+/// Blocks and ignores the users of a list, skipping the ones already handled
+///
+/// Requests are paced by `interval` and wait out rate limits, see `PacedRequests`.
+pub(crate) async fn import(
+    http: &HttpClient,
+    list: &BlockList,
+    current: &[Relationship],
+    interval: Duration,
+) -> BlockListImport {
+    let blocked: HashSet<&str> = current
+        .iter()
+        .filter(|relationship| relationship.kind == RelationshipType::Blocked)
+        .map(|relationship| relationship.id.as_str())
+        .collect();
+    let ignored: HashSet<&str> = current
+        .iter()
+        .filter(|relationship| relationship.user_ignored)
+        .map(|relationship| relationship.id.as_str())
+        .collect();
+
+    let mut report = BlockListImport::default();
+    let mut requests = PacedRequests::new(interval);
+    let changes = list
+        .blocked
+        .iter()
+        .map(|entry| (entry, true))
+        .chain(list.ignored.iter().map(|entry| (entry, false)));
+    for (entry, block) in changes {
+        let done = if block { &blocked } else { &ignored };
+        if done.contains(entry.id.as_str()) {
+            report.skipped.push(entry.id.clone());
+            continue;
+        }
+        match requests.run(|| apply(http, &entry.id, block)).await {
+            Ok(()) if block => report.blocked.push(entry.id.clone()),
+            Ok(()) => report.ignored.push(entry.id.clone()),
+            Err(err) => {
+                tracing::warn!("Block list import failed for {}: {}", entry.id, err);
+                report.failed.push((entry.id.clone(), err));
+            }
+        }
+    }
+    report
+}
+
+/// Blocks (or ignores) a user
+async fn apply(http: &HttpClient, user_id: &str, block: bool) -> Result<()> {
+    let result = if block {
+        let url = api_url(&format!("/users/@me/relationships/{}", user_id));
+        http.put(&url, RelationshipAction::Block.body()).await
+    } else {
+        let url = api_url(&format!("/users/@me/relationships/{}/ignore", user_id));
+        http.put(&url, serde_json::json!({})).await
+    };
+    result.map(|_| ())
+}
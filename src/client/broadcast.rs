@@ -0,0 +1,61 @@
+use crate::client::bulk::{BulkReport, PacedRequests};
+use crate::error::Error;
+use crate::http::HttpClient;
+use crate::model::{CreateMessage, Message};
+use std::time::Duration;
+
+/// Default pause between two messages of a broadcast
+pub const DEFAULT_BROADCAST_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Outcome of `Context::broadcast`, see `BulkReport` for the failed channels
+#[derive(Debug, Default)]
+pub struct BroadcastReport {
+    /// Messages sent, in the order of the channels
+    pub sent: Vec<Message>,
+
+    /// Channels the message could not be sent to, with the last error
+    pub failed: Vec<(String, Error)>,
+}
+
+impl BulkReport for BroadcastReport {
+    fn failures(&self) -> &[(String, Error)] {
+        &self.failed
+    }
+}
+
+/// Sends a message to every channel, one after the other
+///
+/// Channels are paced by `interval`. A rate limit left over by the retry policy pauses
+/// the broadcast for `retry_after` before the channel is tried again, and other errors
+/// are recorded without stopping the broadcast.
+pub(crate) async fn broadcast(
+    http: &HttpClient,
+    channel_ids: Vec<String>,
+    message: &CreateMessage,
+    interval: Duration,
+) -> BroadcastReport {
+    let mut report = BroadcastReport::default();
+    let mut requests = PacedRequests::new(interval);
+    let mut seen = std::collections::HashSet::new();
+    for channel_id in channel_ids {
+        if !seen.insert(channel_id.clone()) {
+            continue;
+        }
+
+        let url = crate::http::api_url(&format!("/channels/{}/messages", channel_id));
+        let result = requests.run(|| http.post(&url, message)).await;
+
+        let sent = match result {
+            Ok(response) => serde_json::from_value::<Message>(response).map_err(Error::from),
+            Err(err) => Err(err),
+        };
+        match sent {
+            Ok(message) => report.sent.push(message),
+            Err(err) => {
+                tracing::warn!("Broadcast to {} failed: {}", channel_id, err);
+                report.failed.push((channel_id, err));
+            }
+        }
+    }
+    report
+}
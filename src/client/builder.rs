@@ -1,8 +1,16 @@
-use crate::cache::{Cache, CacheConfig};
-use crate::client::{Client, EventHandler};
-use crate::error::{CaptchaInfo, Result};
-use crate::http::HttpClient;
+use crate::cache::{Cache, CacheConfig, CacheLimits};
+use crate::client::{Client, CollectorHub, DispatchMode, EventHandler, TypeMap, TypeMapKey};
+use crate::error::{CaptchaInfo, MfaInfo, Result};
+#[cfg(feature = "framework")]
+use crate::framework::CommandFramework;
+use crate::gateway::{ConnectionProperties, IdentifyConfig, IdentifyPreset, SessionState};
+use crate::http::{HttpClient, MfaCode, RateLimitInfo, RetryPolicy};
+use crate::model::AllowedMentions;
+#[cfg(feature = "triggers")]
+use crate::triggers::TriggerSet;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 pub struct ClientBuilder<H>
 where
@@ -12,6 +20,20 @@ where
     handler: H,
     http: HttpClient,
     cache_config: CacheConfig,
+    session_state: Option<SessionState>,
+    data: TypeMap,
+    shard_count: Option<u32>,
+    dispatch_mode: DispatchMode,
+    shutdown_timeout: Option<Duration>,
+    gateway_url: Option<String>,
+    identify: IdentifyConfig,
+    cache_snapshot: Option<PathBuf>,
+    event_recording: Option<PathBuf>,
+    collectors: Option<CollectorHub>,
+    #[cfg(feature = "framework")]
+    framework: Option<CommandFramework>,
+    #[cfg(feature = "triggers")]
+    triggers: Option<TriggerSet>,
 }
 
 impl<H> ClientBuilder<H>
@@ -27,6 +49,20 @@ where
             handler,
             http,
             cache_config: CacheConfig::default(),
+            session_state: None,
+            data: TypeMap::new(),
+            shard_count: None,
+            dispatch_mode: DispatchMode::default(),
+            shutdown_timeout: None,
+            gateway_url: None,
+            identify: IdentifyConfig::default(),
+            cache_snapshot: None,
+            event_recording: None,
+            collectors: None,
+            #[cfg(feature = "framework")]
+            framework: None,
+            #[cfg(feature = "triggers")]
+            triggers: None,
         }
     }
 
@@ -41,10 +77,26 @@ where
             cache_channels: false,
             cache_guilds: false,
             cache_relationships: false,
+            cache_presences: false,
+            cache_voice_states: false,
+            cache_members: false,
+            cache_roles: false,
+            cache_emojis: false,
+            cache_threads: false,
+            cache_messages: false,
+            limits: CacheLimits::default(),
         };
         self
     }
 
+    /// Uses an HTTP client built beforehand, e.g. with custom timeouts from `HttpClient::builder`.
+    ///
+    /// It replaces the HTTP settings of earlier calls (captcha handler, retry policy, ...), so call it first.
+    pub fn with_http_client(mut self, http: HttpClient) -> Self {
+        self.http = http;
+        self
+    }
+
     pub fn with_captcha_handler<F, Fut>(mut self, handler: F) -> Self
     where
         F: Fn(CaptchaInfo) -> Fut + Send + Sync + 'static,
@@ -54,8 +106,185 @@ where
         self
     }
 
+    /// Sets how many captchas are solved for one request (see `HttpClient::with_captcha_retries`).
+    pub fn with_captcha_retries(mut self, retries: u32) -> Self {
+        self.http = self.http.with_captcha_retries(retries);
+        self
+    }
+
+    pub fn with_mfa_handler<F, Fut>(mut self, handler: F) -> Self
+    where
+        F: Fn(MfaInfo) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<MfaCode>> + Send + 'static,
+    {
+        self.http = self.http.with_mfa_handler(handler);
+        self
+    }
+
+    /// Sets a hook called with every rate limited HTTP response (see `HttpClient::with_ratelimit_hook`).
+    pub fn with_ratelimit_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&RateLimitInfo) + Send + Sync + 'static,
+    {
+        self.http = self.http.with_ratelimit_hook(hook);
+        self
+    }
+
+    /// Sets the client properties shared by the HTTP headers (`User-Agent`, `X-Super-Properties`)
+    /// and the gateway IDENTIFY payload.
+    pub fn with_connection_properties(mut self, properties: ConnectionProperties) -> Self {
+        self.http = self.http.with_connection_properties(properties);
+        self
+    }
+
+    /// Sets the IANA timezone sent in the `X-Discord-Timezone` header.
+    pub fn with_timezone(mut self, timezone: impl Into<String>) -> Self {
+        self.http = self.http.with_timezone(timezone);
+        self
+    }
+
+    /// Sets how transient HTTP failures (429, 5xx, connection errors) are retried.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.http = self.http.with_retry_policy(policy);
+        self
+    }
+
+    /// Sends API requests to another base URL (see `HttpClient::with_api_base_url`).
+    pub fn with_api_base_url(mut self, url: impl AsRef<str>) -> Self {
+        self.http = self.http.with_api_base_url(url);
+        self
+    }
+
+    /// Sets the mention policy of every message sent or edited by the client (see
+    /// `HttpClient::with_default_allowed_mentions`).
+    pub fn with_default_allowed_mentions(mut self, allowed_mentions: AllowedMentions) -> Self {
+        self.http = self.http.with_default_allowed_mentions(allowed_mentions);
+        self
+    }
+
+    /// Connects to a custom gateway URL (see `Gateway::connect_with_url`).
+    pub fn with_gateway_url(mut self, url: impl Into<String>) -> Self {
+        self.gateway_url = Some(url.into());
+        self
+    }
+
+    /// Sends the capabilities and intents of a preset in IDENTIFY (`UserDefault` by default).
+    ///
+    /// Resets the overrides of `with_capabilities` and `with_intents` made before.
+    pub fn with_identify_preset(mut self, preset: IdentifyPreset) -> Self {
+        self.identify = preset.into();
+        self
+    }
+
+    /// Overrides the client capabilities sent in IDENTIFY.
+    pub fn with_capabilities(mut self, capabilities: u32) -> Self {
+        self.identify.capabilities = capabilities;
+        self
+    }
+
+    /// Overrides the gateway intents sent in IDENTIFY (`None` to send no intents).
+    pub fn with_intents(mut self, intents: Option<u32>) -> Self {
+        self.identify.intents = intents;
+        self
+    }
+
+    /// Resumes a previously saved gateway session instead of sending a fresh IDENTIFY.
+    pub fn with_session_state(mut self, state: SessionState) -> Self {
+        self.session_state = Some(state);
+        self
+    }
+
+    /// Restores the cache from a snapshot file on start and saves it on shutdown (see `Client::with_cache_snapshot`).
+    pub fn with_cache_snapshot(mut self, path: impl Into<PathBuf>) -> Self {
+        self.cache_snapshot = Some(path.into());
+        self
+    }
+
+    /// Writes every dispatch payload received to a file (see `Client::with_event_recording`).
+    pub fn with_event_recording(mut self, path: impl Into<PathBuf>) -> Self {
+        self.event_recording = Some(path.into());
+        self
+    }
+
+    /// Splits the gateway connection into `shard_count` shards (see `Client::with_shards`).
+    pub fn with_shards(mut self, shard_count: u32) -> Self {
+        self.shard_count = Some(shard_count);
+        self
+    }
+
+    /// Sets how event handlers are run (see `DispatchMode`).
+    pub fn with_dispatch_mode(mut self, mode: DispatchMode) -> Self {
+        self.dispatch_mode = mode;
+        self
+    }
+
+    /// Sets how long handlers still running at shutdown may take (see `Client::with_shutdown_timeout`).
+    pub fn with_shutdown_timeout(mut self, timeout: Duration) -> Self {
+        self.shutdown_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the collector hub exposed to handlers (see `Client::with_collectors`).
+    pub fn with_collectors(mut self, collectors: CollectorHub) -> Self {
+        self.collectors = Some(collectors);
+        self
+    }
+
+    /// Stores a value in the shared data map exposed to handlers as `ctx.data`.
+    pub fn with_data<K: TypeMapKey>(mut self, value: K::Value) -> Self {
+        self.data.insert::<K>(value);
+        self
+    }
+
+    /// Routes MESSAGE_CREATE events through a command framework.
+    #[cfg(feature = "framework")]
+    pub fn with_framework(mut self, framework: CommandFramework) -> Self {
+        self.framework = Some(framework);
+        self
+    }
+
+    /// Runs auto-reply triggers on MESSAGE_CREATE events (see `Client::with_triggers`).
+    #[cfg(feature = "triggers")]
+    pub fn with_triggers(mut self, triggers: TriggerSet) -> Self {
+        self.triggers = Some(triggers);
+        self
+    }
+
     pub fn build(self) -> Client {
         let cache = Cache::with_config(self.cache_config);
-        Client::from_parts(self.token, Arc::new(self.handler), self.http, cache)
+        let mut client = Client::from_parts(self.token, Arc::new(self.handler), self.http, cache)
+            .with_type_map(self.data)
+            .with_dispatch_mode(self.dispatch_mode)
+            .with_identify_config(self.identify);
+        if let Some(state) = self.session_state {
+            client = client.with_session_state(state);
+        }
+        if let Some(shard_count) = self.shard_count {
+            client = client.with_shards(shard_count);
+        }
+        if let Some(timeout) = self.shutdown_timeout {
+            client = client.with_shutdown_timeout(timeout);
+        }
+        if let Some(url) = self.gateway_url {
+            client = client.with_gateway_url(url);
+        }
+        if let Some(path) = self.cache_snapshot {
+            client = client.with_cache_snapshot(path);
+        }
+        if let Some(path) = self.event_recording {
+            client = client.with_event_recording(path);
+        }
+        if let Some(collectors) = self.collectors {
+            client = client.with_collectors(collectors);
+        }
+        #[cfg(feature = "framework")]
+        if let Some(framework) = self.framework {
+            client = client.with_framework(framework);
+        }
+        #[cfg(feature = "triggers")]
+        if let Some(triggers) = self.triggers {
+            client = client.with_triggers(triggers);
+        }
+        client
     }
 }
@@ -0,0 +1,65 @@
+use crate::error::{Error, Result};
+use std::future::Future;
+use std::time::Duration;
+
+/// Times a request is retried after a rate limit outlasting the retry policy
+pub(crate) const RATE_LIMIT_RETRIES: u32 = 3;
+
+/// Outcome of an operation repeated over many IDs (`Context::broadcast`,
+/// `RelationshipsManager::import_block_list`, ...)
+pub trait BulkReport {
+    /// IDs that could not be handled, with the last error of each
+    fn failures(&self) -> &[(String, Error)];
+
+    /// Whether every ID was handled
+    fn is_complete(&self) -> bool {
+        self.failures().is_empty()
+    }
+
+    /// Returns the IDs that could not be handled
+    fn failed_ids(&self) -> Vec<&str> {
+        self.failures().iter().map(|(id, _)| id.as_str()).collect()
+    }
+}
+
+/// Runs requests one after the other, `interval` apart
+///
+/// A rate limit left over by the retry policy pauses for `retry_after` before the
+/// request is tried again, up to `RATE_LIMIT_RETRIES` times.
+pub(crate) struct PacedRequests {
+    interval: Duration,
+    started: bool,
+}
+
+impl PacedRequests {
+    pub(crate) fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            started: false,
+        }
+    }
+
+    /// Waits for the end of the interval since the previous request, then runs `request`
+    pub(crate) async fn run<T, F, Fut>(&mut self, mut request: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        if self.started && !self.interval.is_zero() {
+            tokio::time::sleep(self.interval).await;
+        }
+        self.started = true;
+
+        let mut retries = 0;
+        loop {
+            match request().await {
+                Err(Error::RateLimit { retry_after }) if retries < RATE_LIMIT_RETRIES => {
+                    retries += 1;
+                    tracing::debug!("Bulk request rate limited, waiting {}s", retry_after);
+                    tokio::time::sleep(Duration::from_secs_f64(retry_after.max(0.0))).await;
+                }
+                result => return result,
+            }
+        }
+    }
+}
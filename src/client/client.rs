@@ -1,14 +1,30 @@
-use crate::cache::{Cache, CacheConfig};
-use crate::client::{ClientBuilder, Context, DispatchEvent, DispatchEventType, EventHandler};
-use crate::error::{CaptchaInfo, Result};
-use crate::gateway::Gateway;
-use crate::http::HttpClient;
-use crate::model::{Message, PassiveUpdateV1, ReadySupplemental, User};
-use serde_json::Value;
+use crate::cache::{Cache, CacheConfig, CacheLimits, CacheSnapshot};
+use crate::client::dispatch::EventDispatcher;
+use crate::client::{
+    ClientBuilder, CollectorHub, Context, DispatchMode, EventHandler, MessageScheduler, TypeMap,
+    TypeMapKey,
+};
+use crate::error::{CaptchaInfo, Error, MfaInfo, Result};
+#[cfg(feature = "framework")]
+use crate::framework::CommandFramework;
+use crate::gateway::{
+    EventRecorder, Gateway, GatewayMetrics, IdentifyConfig, ReplayGateway, SessionState,
+    ShardManager, ShardRunner,
+};
+use crate::http::{HttpClient, MfaCode, RateLimitInfo};
+#[cfg(feature = "triggers")]
+use crate::triggers::TriggerSet;
+use parking_lot::RwLock;
+use std::future::Future;
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Notify;
 
+/// How long handlers still running at shutdown may take before `start` returns anyway
+const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// Main client struct for the selfbot.   
 /// Handles connection to the gateway and dispatching events to the event handler.
 /// Also holds an instance of the HTTP client for making API requests.
@@ -28,6 +44,14 @@ use tokio::sync::Notify;
 ///     cache_channels: true,
 ///     cache_guilds: true,
 ///     cache_relationships: true,
+///     cache_presences: true,
+///     cache_voice_states: true,
+///     cache_members: true,
+///     cache_roles: true,
+///     cache_emojis: true,
+///     cache_threads: true,
+///     cache_messages: true,
+///     limits: CacheLimits::default(),
 /// };
 ///async fn main() {
 ///     let client = Client::new("your_token_here", MyHandler).with_cache_config(cache_config);
@@ -42,8 +66,23 @@ pub struct Client {
     handler: Arc<dyn EventHandler>,
     http: HttpClient,
     cache: Cache,
+    session: Arc<RwLock<Option<SessionState>>>,
+    data: Arc<tokio::sync::RwLock<TypeMap>>,
+    #[cfg(feature = "framework")]
+    framework: Option<Arc<CommandFramework>>,
+    #[cfg(feature = "triggers")]
+    triggers: Option<Arc<TriggerSet>>,
     shutdown_requested: Arc<AtomicBool>,
     shutdown_notify: Arc<Notify>,
+    shard_count: Option<u32>,
+    dispatch_mode: DispatchMode,
+    shutdown_timeout: Duration,
+    gateway_url: Option<String>,
+    identify: IdentifyConfig,
+    cache_snapshot: Option<PathBuf>,
+    event_recording: Option<PathBuf>,
+    collectors: CollectorHub,
+    scheduler: MessageScheduler,
 }
 
 impl Client {
@@ -68,13 +107,29 @@ impl Client {
         http: HttpClient,
         cache: Cache,
     ) -> Self {
+        let scheduler = MessageScheduler::paused(http.clone());
         Self {
             token,
             handler,
             http,
             cache,
+            session: Arc::new(RwLock::new(None)),
+            data: Arc::new(tokio::sync::RwLock::new(TypeMap::new())),
+            #[cfg(feature = "framework")]
+            framework: None,
+            #[cfg(feature = "triggers")]
+            triggers: None,
             shutdown_requested: Arc::new(AtomicBool::new(false)),
             shutdown_notify: Arc::new(Notify::new()),
+            shard_count: None,
+            dispatch_mode: DispatchMode::default(),
+            shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+            gateway_url: None,
+            identify: IdentifyConfig::default(),
+            cache_snapshot: None,
+            event_recording: None,
+            collectors: CollectorHub::new(),
+            scheduler,
         }
     }
 
@@ -102,6 +157,14 @@ impl Client {
             cache_channels: false,
             cache_guilds: false,
             cache_relationships: false,
+            cache_presences: false,
+            cache_voice_states: false,
+            cache_members: false,
+            cache_roles: false,
+            cache_emojis: false,
+            cache_threads: false,
+            cache_messages: false,
+            limits: CacheLimits::default(),
         });
         self
     }
@@ -131,6 +194,241 @@ impl Client {
         self
     }
 
+    /// Sets how many captchas are solved for one request (1 by default)
+    ///
+    /// See `HttpClient::with_captcha_retries`.
+    pub fn with_captcha_retries(mut self, retries: u32) -> Self {
+        self.http = self.http.with_captcha_retries(retries);
+        self
+    }
+
+    /// Sets an MFA handler for this client
+    ///
+    /// The handler will be called when a sensitive endpoint (guild deletion, vanity URL
+    /// edits, ...) requires MFA. It should return the code finishing the challenge.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use diself::prelude::*;
+    ///
+    /// let client = Client::new(token, MyHandler)
+    ///     .with_mfa_handler(|info| async move {
+    ///         println!("MFA required: {:?}", info.methods);
+    ///         Ok(MfaCode::totp(read_code_from_authenticator()))
+    ///     });
+    /// ```
+    pub fn with_mfa_handler<F, Fut>(mut self, handler: F) -> Self
+    where
+        F: Fn(MfaInfo) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<MfaCode>> + Send + 'static,
+    {
+        self.http = self.http.with_mfa_handler(handler);
+        self
+    }
+
+    /// Sets a hook called with every rate limited (429) HTTP response
+    ///
+    /// See `HttpClient::with_ratelimit_hook`.
+    pub fn with_ratelimit_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&RateLimitInfo) + Send + Sync + 'static,
+    {
+        self.http = self.http.with_ratelimit_hook(hook);
+        self
+    }
+
+    /// Sets a previously saved gateway session to resume on `start()`
+    ///
+    /// A resumed session receives no READY, so the client fills the cache (current user,
+    /// relationships, DM channels and guilds) from the REST API instead.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let state = SessionState::load("session.json").await?;
+    /// let client = Client::new(token, MyHandler).with_session_state(state);
+    /// ```
+    pub fn with_session_state(self, state: SessionState) -> Self {
+        *self.session.write() = Some(state);
+        self
+    }
+
+    /// Stores a value in the shared data map exposed to handlers as `ctx.data`
+    ///
+    /// Fails with `Error::InvalidArgument` if the map is locked through a handle returned
+    /// by `data()`. `ClientBuilder::with_data` cannot fail.
+    ///
+    /// # Example
+    /// ```ignore
+    /// struct Counter;
+    ///
+    /// impl TypeMapKey for Counter {
+    ///     type Value = AtomicU64;
+    /// }
+    ///
+    /// let client = Client::new(token, MyHandler).with_data::<Counter>(AtomicU64::new(0))?;
+    /// ```
+    pub fn with_data<K: TypeMapKey>(self, value: K::Value) -> Result<Self> {
+        self.data
+            .try_write()
+            .map_err(|_| {
+                Error::InvalidArgument("the data map is locked by another handle".to_string())
+            })?
+            .insert::<K>(value);
+        Ok(self)
+    }
+
+    pub(crate) fn with_type_map(mut self, data: TypeMap) -> Self {
+        self.data = Arc::new(tokio::sync::RwLock::new(data));
+        self
+    }
+
+    /// Sets a command framework that receives every MESSAGE_CREATE
+    ///
+    /// # Example
+    /// ```ignore
+    /// use diself::framework::{Command, CommandFramework};
+    ///
+    /// let framework = CommandFramework::new("!").command(Command::new("ping", |ctx, msg, _| async move {
+    ///     msg.reply(&ctx.http, "Pong!").await?;
+    ///     Ok(())
+    /// }));
+    /// let client = Client::new(token, MyHandler).with_framework(framework);
+    /// ```
+    #[cfg(feature = "framework")]
+    pub fn with_framework(mut self, framework: CommandFramework) -> Self {
+        self.framework = Some(Arc::new(framework));
+        self
+    }
+
+    /// Sets auto-reply triggers run on every MESSAGE_CREATE, before `on_message_create`
+    ///
+    /// # Example
+    /// ```ignore
+    /// use diself::triggers::{Trigger, TriggerSet};
+    ///
+    /// let triggers = TriggerSet::new().trigger(Trigger::exact("ping").reply("pong"));
+    /// let client = Client::new(token, MyHandler).with_triggers(triggers);
+    /// ```
+    #[cfg(feature = "triggers")]
+    pub fn with_triggers(mut self, triggers: TriggerSet) -> Self {
+        self.triggers = Some(Arc::new(triggers));
+        self
+    }
+
+    /// Splits the gateway connection into `shard_count` shards
+    ///
+    /// Useful for accounts in thousands of guilds, where a single READY gets too large.
+    /// Shards identify 5 seconds apart and their events reach the same handler.
+    /// Saved sessions are not resumed in sharded mode.
+    pub fn with_shards(mut self, shard_count: u32) -> Self {
+        self.shard_count = Some(shard_count);
+        self
+    }
+
+    /// Sets how event handlers are run (sequentially by default)
+    ///
+    /// # Example
+    /// ```ignore
+    /// // Slow handlers no longer delay other channels, messages of a channel stay ordered
+    /// let client = Client::new(token, MyHandler)
+    ///     .with_dispatch_mode(DispatchMode::OrderedPerChannel { max_concurrency: 32 });
+    /// ```
+    pub fn with_dispatch_mode(mut self, mode: DispatchMode) -> Self {
+        self.dispatch_mode = mode;
+        self
+    }
+
+    /// Sets how long handlers still running at shutdown may take (10 seconds by default)
+    ///
+    /// Only matters with a concurrent `DispatchMode`: handlers running past the timeout
+    /// are left running in the background.
+    pub fn with_shutdown_timeout(mut self, timeout: Duration) -> Self {
+        self.shutdown_timeout = timeout;
+        self
+    }
+
+    /// Connects to a custom gateway URL, e.g. a local gateway proxy
+    ///
+    /// The URL must include the query string (`?v=10&encoding=json`). For the API base URL,
+    /// see `HttpClient::with_api_base_url`.
+    pub fn with_gateway_url(mut self, url: impl Into<String>) -> Self {
+        self.gateway_url = Some(url.into());
+        self
+    }
+
+    /// Sets the capabilities and intents sent in IDENTIFY (`IdentifyPreset::UserDefault` by
+    /// default)
+    pub fn with_identify_config(mut self, config: impl Into<IdentifyConfig>) -> Self {
+        self.identify = config.into();
+        self
+    }
+
+    /// Restores the cache from a snapshot file on start, and saves it there on shutdown
+    ///
+    /// A missing or unreadable file is skipped with a warning. Combined with a saved
+    /// `SessionState`, handlers see cached state before READY arrives.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let client = Client::new(token, MyHandler).with_cache_snapshot("cache.json");
+    /// ```
+    pub fn with_cache_snapshot(mut self, path: impl Into<PathBuf>) -> Self {
+        self.cache_snapshot = Some(path.into());
+        self
+    }
+
+    /// Writes every dispatch payload received to a file (newline-delimited JSON) while running
+    ///
+    /// The file is truncated on start. Replay it offline with `Client::replay` to test
+    /// handlers and cache logic against real traffic.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let client = Client::new(token, MyHandler).with_event_recording("events.ndjson");
+    /// ```
+    pub fn with_event_recording(mut self, path: impl Into<PathBuf>) -> Self {
+        self.event_recording = Some(path.into());
+        self
+    }
+
+    /// Sets the collector hub exposed to handlers as `ctx.collectors`
+    ///
+    /// Use it to change the collector buffer size or to be told when collectors lag behind.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let collectors = CollectorHub::with_capacity(1024)
+    ///     .with_lag_handler(|skipped| eprintln!("A collector missed {skipped} events"));
+    /// let client = Client::new(token, MyHandler).with_collectors(collectors);
+    /// ```
+    pub fn with_collectors(mut self, collectors: CollectorHub) -> Self {
+        self.collectors = collectors;
+        self
+    }
+
+    /// Returns the message scheduler shared with every handler `Context`
+    ///
+    /// Schedules are kept across restarts of the client, but nothing is sent while it
+    /// is stopped.
+    pub fn scheduler(&self) -> &MessageScheduler {
+        &self.scheduler
+    }
+
+    /// Returns the latest resumable gateway session state (if connected at least once)
+    pub fn session_state(&self) -> Option<SessionState> {
+        self.session.read().clone()
+    }
+
+    /// Returns how event handlers are run
+    pub fn dispatch_mode(&self) -> DispatchMode {
+        self.dispatch_mode
+    }
+
+    /// Returns the data map shared with every handler `Context`
+    pub fn data(&self) -> Arc<tokio::sync::RwLock<TypeMap>> {
+        Arc::clone(&self.data)
+    }
+
     /// Returns a reference to the HTTP client
     pub fn http(&self) -> &HttpClient {
         &self.http
@@ -141,21 +439,104 @@ impl Client {
         &self.cache
     }
 
-    /// Starts the client and listens for events
+    /// Starts the client and listens for events until `shutdown` is called
+    /// or the gateway connection closes
     pub async fn start(&self) -> Result<()> {
         self.shutdown_requested.store(false, Ordering::SeqCst);
+        // The previous run closed the hub on shutdown
+        self.collectors.reopen();
         tracing::info!("Starting Discord client...");
 
-        let mut gateway = Gateway::connect(&self.token).await?;
+        self.restore_cache_snapshot().await;
+        let dispatcher = self.dispatcher().await?;
+
+        if let Some(shard_count) = self.shard_count {
+            return self.start_sharded(shard_count, dispatcher).await;
+        }
+
+        let saved_session = self.session.read().clone();
+        let resuming = saved_session.is_some();
+        if resuming {
+            tracing::info!("Resuming saved gateway session");
+        }
+        let mut gateway = Gateway::connect_inner(
+            self.token.clone(),
+            saved_session,
+            self.http.connection_properties().clone(),
+            None,
+            GatewayMetrics::default(),
+            self.gateway_url.clone(),
+            self.identify,
+        )
+        .await?;
 
         tracing::info!("Client connected, listening for events...");
 
-        let ctx = Context::create(self.http.clone(), self.cache.clone()).await?;
+        let ctx = Context::create(self.http.clone(), self.cache.clone())
+            .await?
+            .with_session(self.session.clone())
+            .with_data(self.data.clone())
+            .with_collectors(self.collectors.clone())
+            .with_scheduler(self.scheduler.clone())
+            .with_gateway_metrics(gateway.metrics().clone())
+            .with_gateway(gateway.sender());
+        if resuming {
+            self.seed_resumed_cache().await;
+        }
+        self.scheduler.resume(self.http.clone());
+
+        // Collectors, the scheduler and in-flight handlers are wrapped up even when
+        // the gateway fails
+        let result = self.listen(&mut gateway, &ctx, &dispatcher).await;
+        self.finish_in_flight(&ctx, &dispatcher).await;
+        result
+    }
+
+    /// Like `start`, but shuts the client down once `signal` completes
+    ///
+    /// # Example
+    /// ```ignore
+    /// let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+    /// // Call `tx.send(())` from anywhere to stop the client
+    /// client.start_with_shutdown(async { let _ = rx.await; }).await?;
+    /// ```
+    pub async fn start_with_shutdown(&self, signal: impl Future<Output = ()>) -> Result<()> {
+        let start = self.start();
+        tokio::pin!(start);
+        // `start` is polled first so its flag reset happens before `shutdown` is called
+        tokio::select! {
+            biased;
+            result = &mut start => return result,
+            _ = signal => {
+                tracing::info!("Shutdown signal completed");
+                self.shutdown();
+            }
+        }
+        start.await
+    }
 
+    /// Like `start`, but shuts the client down gracefully on Ctrl-C
+    pub async fn start_until_ctrl_c(&self) -> Result<()> {
+        self.start_with_shutdown(async {
+            if let Err(e) = tokio::signal::ctrl_c().await {
+                tracing::error!("Failed to listen for Ctrl-C: {}", e);
+                std::future::pending::<()>().await;
+            }
+        })
+        .await
+    }
+
+    /// Dispatches gateway events until shutdown or until the connection closes
+    async fn listen(
+        &self,
+        gateway: &mut Gateway,
+        ctx: &Context,
+        dispatcher: &EventDispatcher,
+    ) -> Result<()> {
         loop {
             if self.shutdown_requested.load(Ordering::SeqCst) {
                 tracing::info!("Shutdown requested, stopping client loop");
-                gateway.shutdown().await?;
+                gateway.shutdown_resumable().await?;
                 break;
             }
 
@@ -164,380 +545,234 @@ impl Client {
                 _ = self.shutdown_notify.notified() => None,
             };
 
+            *self.session.write() = gateway.session_state();
+
             match next_event {
                 Some(event) => {
                     if let Some(event) = event {
-                        if let Err(e) = self.handle_event(&ctx, event).await {
-                            tracing::error!("Error handling event: {}", e);
-                        }
+                        dispatcher.handle_event(ctx, event).await;
                     } else {
                         tracing::warn!("Gateway connection closed");
-                        gateway.shutdown().await?;
+                        gateway.shutdown_resumable().await?;
                         break;
                     }
                 }
                 None => {
                     tracing::info!("Shutdown signal received, closing gateway");
-                    gateway.shutdown().await?;
+                    gateway.shutdown_resumable().await?;
                     break;
                 }
             }
         }
-
         Ok(())
     }
 
-    pub fn shutdown(&self) {
-        self.shutdown_requested.store(true, Ordering::SeqCst);
-        self.shutdown_notify.notify_waiters();
+    async fn start_sharded(&self, shard_count: u32, dispatcher: EventDispatcher) -> Result<()> {
+        let mut manager = ShardManager::new(&self.token, shard_count)
+            .with_properties(self.http.connection_properties().clone())
+            .with_identify_config(self.identify);
+        if let Some(url) = &self.gateway_url {
+            manager = manager.with_gateway_url(url);
+        }
+        let mut runner = manager.spawn();
+
+        tracing::info!("Starting {} shards, listening for events...", shard_count);
+
+        let mut ctx = Context::create(self.http.clone(), self.cache.clone())
+            .await?
+            .with_session(self.session.clone())
+            .with_data(self.data.clone())
+            .with_collectors(self.collectors.clone())
+            .with_scheduler(self.scheduler.clone())
+            .with_gateway(runner.sender());
+        if let Some(metrics) = runner.metrics(0) {
+            ctx = ctx.with_gateway_metrics(metrics.clone());
+        }
+        self.scheduler.resume(self.http.clone());
+
+        // Like `start`, every exit pauses the scheduler and wraps up collectors and handlers
+        let result = self.listen_shards(&mut runner, &ctx, &dispatcher).await;
+        runner.shutdown().await;
+        self.finish_in_flight(&ctx, &dispatcher).await;
+        result
     }
 
-    async fn handle_event(&self, ctx: &Context, event: Value) -> Result<()> {
-        self.handler.on_gateway_payload(ctx, &event).await;
-
-        let op = event.get("op").and_then(|v| v.as_u64());
-
-        // Opcode 0 = Dispatch (events)
-        if op == Some(0) {
-            if let Some(event_type) = event.get("t").and_then(|v| v.as_str()) {
-                let sequence = event.get("s").and_then(|v| v.as_u64());
-                let data = event.get("d").cloned().unwrap_or(Value::Null);
-                let dispatch = DispatchEvent::from_gateway_payload(event_type, sequence, data);
-
-                let dispatch_kind = dispatch.kind.clone();
-                let dispatch_name = dispatch.name().to_string();
-                let maybe_old_user = if matches!(dispatch_kind, DispatchEventType::UserUpdate) {
-                    dispatch
-                        .data
-                        .get("id")
-                        .and_then(|v| v.as_str())
-                        .and_then(|id| ctx.cache.user(id))
-                } else {
-                    None
-                };
-
-                ctx.cache.update_from_dispatch(&dispatch_name, &dispatch.data);
-                ctx.collectors.dispatch(dispatch.clone());
-                self.handler.on_dispatch(ctx, dispatch.clone()).await;
-                self.dispatch_raw_event(ctx, &dispatch).await;
-
-                match dispatch_kind {
-                    DispatchEventType::Ready => {
-                        if let Some(user) = ctx.cache.current_user() {
-                            self.handler.on_ready(ctx, user).await;
-                        }
-                    }
-                    DispatchEventType::ReadySupplemental => {
-                        self.handler
-                            .on_ready_supplemental(ctx, ctx.user.clone(), dispatch.data.clone())
-                            .await;
-                        if let Ok(data) =
-                            serde_json::from_value::<ReadySupplemental>(dispatch.data.clone())
-                        {
-                            self.handler
-                                .on_ready_supplemental_typed(ctx, ctx.user.clone(), data)
-                                .await;
-                        }
-                    }
-                    DispatchEventType::MessageCreate => {
-                        if let Ok(message) = serde_json::from_value::<Message>(dispatch.data) {
-                            self.handler.on_message_create(ctx, message).await;
-                        }
-                    }
-                    DispatchEventType::MessageUpdate => {
-                        if let Ok(message) = serde_json::from_value::<Message>(dispatch.data) {
-                            self.handler.on_message_update(ctx, message).await;
-                        }
-                    }
-                    DispatchEventType::MessageDelete => {
-                        let data = dispatch.data;
-                        if let (Some(channel_id), Some(message_id)) =
-                            (data["channel_id"].as_str(), data["id"].as_str())
-                        {
-                            self.handler
-                                .on_message_delete(
-                                    ctx,
-                                    channel_id.to_string(),
-                                    message_id.to_string(),
-                                )
-                                .await;
-                        }
-                    }
-                    DispatchEventType::UserUpdate => {
-                        if let Ok(new_user) = serde_json::from_value::<User>(dispatch.data) {
-                            let old_user = maybe_old_user.unwrap_or_else(|| new_user.clone());
-                            self.handler.on_user_update(ctx, old_user, new_user).await;
-                        }
-                    }
-                    DispatchEventType::Unknown(name) => {
-                        tracing::trace!("Unhandled dispatch event: {}", name);
-                    }
-                    _ => {}
+    /// Dispatches the events of every shard until shutdown, a shard error, or until
+    /// every shard connection closes
+    async fn listen_shards(
+        &self,
+        runner: &mut ShardRunner,
+        ctx: &Context,
+        dispatcher: &EventDispatcher,
+    ) -> Result<()> {
+        loop {
+            if self.shutdown_requested.load(Ordering::SeqCst) {
+                tracing::info!("Shutdown requested, stopping shards");
+                return Ok(());
+            }
+
+            let next_event = tokio::select! {
+                event = runner.next() => event,
+                _ = self.shutdown_notify.notified() => {
+                    tracing::info!("Shutdown signal received, stopping shards");
+                    return Ok(());
+                }
+            };
+
+            match next_event {
+                Some(Ok(event)) => dispatcher.handle_event(ctx, event.payload).await,
+                Some(Err(e)) => return Err(e),
+                None => {
+                    tracing::warn!("Every shard connection closed");
+                    return Ok(());
                 }
             }
         }
+    }
 
-        Ok(())
+    /// Ends collectors and holds scheduled messages, then waits for handler tasks still
+    /// running (up to `shutdown_timeout`)
+    async fn finish_in_flight(&self, ctx: &Context, dispatcher: &EventDispatcher) {
+        ctx.collectors.close();
+        self.scheduler.pause();
+        if !dispatcher.drain(self.shutdown_timeout).await {
+            tracing::warn!(
+                "Handlers still running after {:?}, stopping without them",
+                self.shutdown_timeout
+            );
+        }
+        self.save_cache_snapshot().await;
+    }
+
+    async fn restore_cache_snapshot(&self) {
+        let Some(path) = &self.cache_snapshot else {
+            return;
+        };
+        if !path.exists() {
+            return;
+        }
+        match CacheSnapshot::load(path).await {
+            Ok(snapshot) => {
+                tracing::info!("Restoring cache snapshot from {}", path.display());
+                self.cache.restore(snapshot);
+            }
+            Err(e) => tracing::warn!("Failed to load cache snapshot {}: {}", path.display(), e),
+        }
     }
 
-    async fn dispatch_raw_event(&self, ctx: &Context, dispatch: &DispatchEvent) {
-        match dispatch.kind {
-            DispatchEventType::Ready => self.handler.on_ready_event(ctx, dispatch.data.clone()).await,
-            DispatchEventType::ReadySupplemental => self
-                .handler
-                .on_ready_supplemental_event(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::Resumed => self.handler.on_resumed_event(ctx, dispatch.data.clone()).await,
-            DispatchEventType::ApplicationCommandPermissionsUpdate => self
-                .handler
-                .on_application_command_permissions_update(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::AutoModerationRuleCreate => self
-                .handler
-                .on_auto_moderation_rule_create(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::AutoModerationRuleUpdate => self
-                .handler
-                .on_auto_moderation_rule_update(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::AutoModerationRuleDelete => self
-                .handler
-                .on_auto_moderation_rule_delete(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::AutoModerationActionExecution => self
-                .handler
-                .on_auto_moderation_action_execution(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::ChannelCreate => self.handler.on_channel_create(ctx, dispatch.data.clone()).await,
-            DispatchEventType::ChannelUpdate => self.handler.on_channel_update(ctx, dispatch.data.clone()).await,
-            DispatchEventType::ChannelDelete => self.handler.on_channel_delete(ctx, dispatch.data.clone()).await,
-            DispatchEventType::ChannelPinsUpdate => self
-                .handler
-                .on_channel_pins_update(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::ThreadCreate => self.handler.on_thread_create(ctx, dispatch.data.clone()).await,
-            DispatchEventType::ThreadUpdate => self.handler.on_thread_update(ctx, dispatch.data.clone()).await,
-            DispatchEventType::ThreadDelete => self.handler.on_thread_delete(ctx, dispatch.data.clone()).await,
-            DispatchEventType::ThreadListSync => self.handler.on_thread_list_sync(ctx, dispatch.data.clone()).await,
-            DispatchEventType::ThreadMemberUpdate => self
-                .handler
-                .on_thread_member_update(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::ThreadMembersUpdate => self
-                .handler
-                .on_thread_members_update(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::EntitlementCreate => self
-                .handler
-                .on_entitlement_create(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::EntitlementUpdate => self
-                .handler
-                .on_entitlement_update(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::EntitlementDelete => self
-                .handler
-                .on_entitlement_delete(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::GuildCreate => self.handler.on_guild_create(ctx, dispatch.data.clone()).await,
-            DispatchEventType::GuildUpdate => self.handler.on_guild_update(ctx, dispatch.data.clone()).await,
-            DispatchEventType::GuildDelete => self.handler.on_guild_delete(ctx, dispatch.data.clone()).await,
-            DispatchEventType::GuildAuditLogEntryCreate => self
-                .handler
-                .on_guild_audit_log_entry_create(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::GuildBanAdd => self.handler.on_guild_ban_add(ctx, dispatch.data.clone()).await,
-            DispatchEventType::GuildBanRemove => self
-                .handler
-                .on_guild_ban_remove(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::GuildEmojisUpdate => self
-                .handler
-                .on_guild_emojis_update(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::GuildStickersUpdate => self
-                .handler
-                .on_guild_stickers_update(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::GuildIntegrationsUpdate => self
-                .handler
-                .on_guild_integrations_update(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::GuildMemberAdd => self.handler.on_guild_member_add(ctx, dispatch.data.clone()).await,
-            DispatchEventType::GuildMemberRemove => self
-                .handler
-                .on_guild_member_remove(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::GuildMemberUpdate => self
-                .handler
-                .on_guild_member_update(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::GuildMembersChunk => self
-                .handler
-                .on_guild_members_chunk(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::GuildRoleCreate => self.handler.on_guild_role_create(ctx, dispatch.data.clone()).await,
-            DispatchEventType::GuildRoleUpdate => self.handler.on_guild_role_update(ctx, dispatch.data.clone()).await,
-            DispatchEventType::GuildRoleDelete => self.handler.on_guild_role_delete(ctx, dispatch.data.clone()).await,
-            DispatchEventType::GuildScheduledEventCreate => self
-                .handler
-                .on_guild_scheduled_event_create(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::GuildScheduledEventUpdate => self
-                .handler
-                .on_guild_scheduled_event_update(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::GuildScheduledEventDelete => self
-                .handler
-                .on_guild_scheduled_event_delete(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::GuildScheduledEventUserAdd => self
-                .handler
-                .on_guild_scheduled_event_user_add(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::GuildScheduledEventUserRemove => self
-                .handler
-                .on_guild_scheduled_event_user_remove(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::GuildSoundboardSoundCreate => self
-                .handler
-                .on_guild_soundboard_sound_create(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::GuildSoundboardSoundUpdate => self
-                .handler
-                .on_guild_soundboard_sound_update(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::GuildSoundboardSoundDelete => self
-                .handler
-                .on_guild_soundboard_sound_delete(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::GuildSoundboardSoundsUpdate => self
-                .handler
-                .on_guild_soundboard_sounds_update(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::IntegrationCreate => self
-                .handler
-                .on_integration_create(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::IntegrationUpdate => self
-                .handler
-                .on_integration_update(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::IntegrationDelete => self
-                .handler
-                .on_integration_delete(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::InteractionCreate => self
-                .handler
-                .on_interaction_create(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::InviteCreate => self.handler.on_invite_create(ctx, dispatch.data.clone()).await,
-            DispatchEventType::InviteDelete => self.handler.on_invite_delete(ctx, dispatch.data.clone()).await,
-            DispatchEventType::MessageCreate => self
-                .handler
-                .on_message_create_event(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::MessageUpdate => self
-                .handler
-                .on_message_update_event(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::MessageDelete => self
-                .handler
-                .on_message_delete_event(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::MessageDeleteBulk => self
-                .handler
-                .on_message_delete_bulk(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::MessageReactionAdd => self
-                .handler
-                .on_message_reaction_add(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::MessageReactionRemove => self
-                .handler
-                .on_message_reaction_remove(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::MessageReactionRemoveAll => self
-                .handler
-                .on_message_reaction_remove_all(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::MessageReactionRemoveEmoji => self
-                .handler
-                .on_message_reaction_remove_emoji(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::MessagePollVoteAdd => self
-                .handler
-                .on_message_poll_vote_add(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::MessagePollVoteRemove => self
-                .handler
-                .on_message_poll_vote_remove(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::PresenceUpdate => self
-                .handler
-                .on_presence_update(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::PassiveUpdateV1 => self
-                .handler
-                .on_passive_update_v1(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::StageInstanceCreate => self
-                .handler
-                .on_stage_instance_create(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::StageInstanceUpdate => self
-                .handler
-                .on_stage_instance_update(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::StageInstanceDelete => self
-                .handler
-                .on_stage_instance_delete(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::SubscriptionCreate => self
-                .handler
-                .on_subscription_create(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::SubscriptionUpdate => self
-                .handler
-                .on_subscription_update(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::SubscriptionDelete => self
-                .handler
-                .on_subscription_delete(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::TypingStart => self.handler.on_typing_start(ctx, dispatch.data.clone()).await,
-            DispatchEventType::UserUpdate => self.handler.on_user_update_event(ctx, dispatch.data.clone()).await,
-            DispatchEventType::VoiceChannelEffectSend => self
-                .handler
-                .on_voice_channel_effect_send(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::VoiceStateUpdate => self
-                .handler
-                .on_voice_state_update(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::VoiceServerUpdate => self
-                .handler
-                .on_voice_server_update(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::WebhooksUpdate => self
-                .handler
-                .on_webhooks_update(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::RelationshipAdd => self
-                .handler
-                .on_relationship_add(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::RelationshipRemove => self
-                .handler
-                .on_relationship_remove(ctx, dispatch.data.clone())
-                .await,
-            DispatchEventType::Unknown(_) => {}
+    /// Fills the cache from the REST API when resuming a saved session
+    ///
+    /// A resumed session never receives READY. The current user is fetched by
+    /// `Context::create`, and relationships, DM channels and guilds are fetched here
+    /// unless a cache snapshot already restored them.
+    async fn seed_resumed_cache(&self) {
+        if self.cache.relationship_count() == 0 {
+            if let Some(data) = self.fetch_for_cache("/users/@me/relationships").await {
+                self.cache.initialize_relationships(data);
+            }
+        }
+        if self.cache.private_channels().is_empty() {
+            if let Some(data) = self.fetch_for_cache("/users/@me/channels").await {
+                self.cache.initialize_private_channels(data);
+            }
+        }
+        if self.cache.guild_count() == 0 {
+            if let Some(data) = self.fetch_for_cache("/users/@me/guilds").await {
+                self.cache.initialize_guilds(data);
+            }
         }
+    }
 
-        if let DispatchEventType::PassiveUpdateV1 = dispatch.kind {
-            if let Ok(data) = serde_json::from_value::<PassiveUpdateV1>(dispatch.data.clone()) {
-                self.handler.on_passive_update_v1_typed(ctx, data).await;
+    async fn fetch_for_cache(&self, path: &str) -> Option<serde_json::Value> {
+        match self.http.get(&crate::http::api_url(path)).await {
+            Ok(data) => Some(data),
+            Err(e) => {
+                tracing::warn!("Failed to fetch {} for the resumed session: {}", path, e);
+                None
             }
         }
     }
+
+    async fn save_cache_snapshot(&self) {
+        let Some(path) = &self.cache_snapshot else {
+            return;
+        };
+        if let Err(e) = self.cache.snapshot().save(path).await {
+            tracing::warn!("Failed to save cache snapshot {}: {}", path.display(), e);
+        }
+    }
+
+    /// Feeds recorded gateway payloads to the cache, collectors and handlers, without
+    /// connecting to Discord
+    ///
+    /// The current user is read from the READY payload of the recording. HTTP requests
+    /// made by handlers still go through the client's `HttpClient`, so point it at a mock
+    /// transport when testing. Triggers set with `with_triggers` are not run, so recorded
+    /// messages never get live auto-replies, and messages scheduled by handlers are
+    /// dropped unsent. Returns once every payload was handled.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let client = Client::new(token, MyHandler);
+    /// client.replay(ReplayGateway::open("events.ndjson").await?).await?;
+    /// assert!(client.cache().user("123").is_some());
+    /// ```
+    pub async fn replay(&self, mut replay: ReplayGateway) -> Result<()> {
+        let user = replay.current_user()?;
+        // A hub of its own, so closing it at the end leaves the client's collectors running
+        let collectors = CollectorHub::with_capacity(self.collectors.capacity());
+        let ctx = Context::new(self.http.clone(), user, self.cache.clone())
+            .with_session(self.session.clone())
+            .with_data(self.data.clone())
+            .with_collectors(collectors)
+            .with_scheduler(MessageScheduler::paused(self.http.clone()));
+        // Triggers would answer recorded messages through the live API
+        let dispatcher = EventDispatcher::new(
+            self.handler.clone(),
+            #[cfg(feature = "framework")]
+            self.framework.clone(),
+            #[cfg(feature = "triggers")]
+            None,
+            self.dispatch_mode,
+        );
+
+        while let Some(event) = replay.next_event() {
+            dispatcher.handle_event(&ctx, event).await;
+        }
+
+        ctx.collectors.close();
+        if !dispatcher.drain(self.shutdown_timeout).await {
+            tracing::warn!(
+                "Handlers still running after {:?}, stopping replay without them",
+                self.shutdown_timeout
+            );
+        }
+        Ok(())
+    }
+
+    /// Event dispatcher of `start`, recording events if `with_event_recording` was called
+    async fn dispatcher(&self) -> Result<EventDispatcher> {
+        let recorder = match &self.event_recording {
+            Some(path) => Some(EventRecorder::create(path).await?),
+            None => None,
+        };
+        let dispatcher = EventDispatcher::new(
+            self.handler.clone(),
+            #[cfg(feature = "framework")]
+            self.framework.clone(),
+            #[cfg(feature = "triggers")]
+            self.triggers.clone(),
+            self.dispatch_mode,
+        );
+        Ok(dispatcher.with_recorder(recorder))
+    }
+
+    /// Asks a running `start` to stop
+    ///
+    /// The gateway connection is closed with a resumable close code, collectors are ended
+    /// and `start` returns once in-flight handlers are done (see `with_shutdown_timeout`).
+    pub fn shutdown(&self) {
+        self.shutdown_requested.store(true, Ordering::SeqCst);
+        self.shutdown_notify.notify_waiters();
+    }
 }
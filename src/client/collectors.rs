@@ -1,11 +1,15 @@
 use crate::client::{DispatchEvent, DispatchEventType};
-use crate::model::{Emoji, Message};
+use crate::model::{Emoji, Message, ReactionType, TypingStart};
+use futures::Stream;
 use serde_json::Value;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::sync::{broadcast, mpsc};
+use std::task::{Context, Poll};
+use tokio::sync::{broadcast, mpsc, watch};
 use tokio::time::{self, Duration, Instant};
 
-/// Options shared by message/reaction collectors.
+/// Options shared by message/edit/delete/reaction/typing collectors.
 ///
 /// `time` defines the maximum lifetime of the collector.
 /// `max` defines how many items can be collected before closing.
@@ -35,27 +39,135 @@ impl Default for CollectorOptions {
     }
 }
 
+/// Default number of dispatches buffered for each collector before it lags behind.
+pub const DEFAULT_COLLECTOR_CAPACITY: usize = 256;
+
+/// Callback run when a collector lags behind, with the number of skipped dispatches.
+pub type CollectorLagHandler = Arc<dyn Fn(u64) + Send + Sync>;
+
+/// Dispatch and lag counters of a `CollectorHub`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CollectorStats {
+    /// Dispatches broadcast to the collectors
+    pub dispatched: u64,
+    /// Times a collector fell behind the buffer
+    pub lags: u64,
+    /// Dispatches skipped by lagging collectors (counted once per collector)
+    pub dropped: u64,
+}
+
+#[derive(Debug, Default)]
+struct HubCounters {
+    dispatched: AtomicU64,
+    lags: AtomicU64,
+    dropped: AtomicU64,
+}
+
 /// Internal collector dispatcher fed by gateway dispatch events.
 ///
-/// This hub powers `Context::message_collector(...)` and
-/// `Context::reaction_collector(...)`.
+/// This hub powers `Context::message_collector(...)`, `Context::edit_collector(...)`,
+/// `Context::delete_collector(...)`, `Context::reaction_collector(...)` and
+/// `Context::typing_collector(...)`.
+///
+/// Each collector buffers up to `capacity` dispatches. A collector slower than the
+/// gateway skips the oldest ones: this is logged, counted in `stats()` and reported to
+/// the lag handler, if any.
+///
+/// # Example
+/// ```ignore
+/// use diself::prelude::*;
+///
+/// let collectors = CollectorHub::with_capacity(1024).with_lag_handler(|skipped| {
+///     eprintln!("A collector missed {skipped} events");
+/// });
+/// let client = Client::new(token, MyHandler).with_collectors(collectors);
+/// ```
 #[derive(Clone)]
 pub struct CollectorHub {
     tx: broadcast::Sender<DispatchEvent>,
+    closed: Arc<watch::Sender<bool>>,
+    capacity: usize,
+    counters: Arc<HubCounters>,
+    lag_handler: Option<CollectorLagHandler>,
 }
 
 impl CollectorHub {
-    /// Creates a new collector hub.
+    /// Creates a new collector hub buffering `DEFAULT_COLLECTOR_CAPACITY` dispatches.
     pub fn new() -> Self {
-        let (tx, _) = broadcast::channel(256);
-        Self { tx }
+        Self::with_capacity(DEFAULT_COLLECTOR_CAPACITY)
+    }
+
+    /// Creates a new collector hub buffering `capacity` dispatches per collector.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is 0.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let (tx, _) = broadcast::channel(capacity);
+        let (closed, _) = watch::channel(false);
+        Self {
+            tx,
+            closed: Arc::new(closed),
+            capacity,
+            counters: Arc::new(HubCounters::default()),
+            lag_handler: None,
+        }
+    }
+
+    /// Sets a callback run when a collector lags behind, with the number of skipped dispatches.
+    ///
+    /// Only collectors created afterwards report to it.
+    pub fn with_lag_handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(u64) + Send + Sync + 'static,
+    {
+        self.lag_handler = Some(Arc::new(handler));
+        self
+    }
+
+    /// Number of dispatches buffered per collector.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the dispatch and lag counters.
+    pub fn stats(&self) -> CollectorStats {
+        CollectorStats {
+            dispatched: self.counters.dispatched.load(Ordering::Relaxed),
+            lags: self.counters.lags.load(Ordering::Relaxed),
+            dropped: self.counters.dropped.load(Ordering::Relaxed),
+        }
     }
 
     /// Broadcasts one dispatch event to all active collectors.
     pub fn dispatch(&self, event: DispatchEvent) {
+        self.counters.dispatched.fetch_add(1, Ordering::Relaxed);
         let _ = self.tx.send(event);
     }
 
+    /// Subscribes to the dispatches broadcast from now on.
+    #[cfg(feature = "voice")]
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<DispatchEvent> {
+        self.tx.subscribe()
+    }
+
+    /// Ends every active collector, and every collector created afterwards until the client
+    /// starts again.
+    ///
+    /// Called by the client on shutdown, so handlers waiting on a collector can return.
+    pub fn close(&self) {
+        self.closed.send_replace(true);
+    }
+
+    /// Lets collectors run again after `close`, called by the client when it (re)starts.
+    pub(crate) fn reopen(&self) {
+        self.closed.send_replace(false);
+    }
+
+    /// Returns `true` once `close` was called.
+    pub fn is_closed(&self) -> bool {
+        *self.closed.borrow()
+    }
+
     /// Creates a message collector listening to `MESSAGE_CREATE`.
     ///
     /// # Example
@@ -81,60 +193,62 @@ impl CollectorHub {
     where
         F: Fn(&Message) -> bool + Send + Sync + 'static,
     {
-        let mut rx = self.tx.subscribe();
-        let (out_tx, out_rx) = mpsc::unbounded_channel();
-        let filter = Arc::new(filter);
-
-        tokio::spawn(async move {
-            let deadline = options.time.map(|t| Instant::now() + t);
-            let mut collected = 0usize;
-
-            loop {
-                if let Some(max) = options.max {
-                    if collected >= max {
-                        break;
-                    }
-                }
-
-                let event = if let Some(deadline) = deadline {
-                    let now = Instant::now();
-                    if now >= deadline {
-                        break;
-                    }
-                    match time::timeout_at(deadline, rx.recv()).await {
-                        Ok(Ok(evt)) => evt,
-                        Ok(Err(broadcast::error::RecvError::Lagged(_))) => continue,
-                        Ok(Err(broadcast::error::RecvError::Closed)) => break,
-                        Err(_) => break,
-                    }
-                } else {
-                    match rx.recv().await {
-                        Ok(evt) => evt,
-                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
-                        Err(broadcast::error::RecvError::Closed) => break,
-                    }
-                };
-
-                if event.kind != DispatchEventType::MessageCreate {
-                    continue;
-                }
-
-                let Ok(message) = serde_json::from_value::<Message>(event.data.clone()) else {
-                    continue;
-                };
+        let rx = self.spawn_collector(options, filter, |event| {
+            if event.kind != DispatchEventType::MessageCreate {
+                return None;
+            }
+            serde_json::from_value::<Message>(event.data.clone()).ok()
+        });
 
-                if !(filter)(&message) {
-                    continue;
-                }
+        MessageCollector { rx }
+    }
 
-                if out_tx.send(message).is_err() {
-                    break;
-                }
-                collected += 1;
+    /// Creates an edit collector listening to `MESSAGE_UPDATE`.
+    ///
+    /// Partial updates (e.g. link embeds resolved after sending) carry no author or
+    /// content and are not collected.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use diself::{CollectorOptions, Context};
+    ///
+    /// async fn example(ctx: &Context, response_id: &str) {
+    ///     let response_id = response_id.to_string();
+    ///     let mut collector = ctx.edit_collector(CollectorOptions::default(), move |m| {
+    ///         m.id == response_id
+    ///     });
+    ///
+    ///     if let Some(edited) = collector.next().await {
+    ///         println!("Edited to: {}", edited.content);
+    ///     }
+    /// }
+    /// ```
+    pub fn edit_collector<F>(&self, options: CollectorOptions, filter: F) -> EditCollector
+    where
+        F: Fn(&Message) -> bool + Send + Sync + 'static,
+    {
+        let rx = self.spawn_collector(options, filter, |event| {
+            if event.kind != DispatchEventType::MessageUpdate {
+                return None;
             }
+            serde_json::from_value::<Message>(event.data.clone()).ok()
         });
 
-        MessageCollector { rx: out_rx }
+        EditCollector { rx }
+    }
+
+    /// Creates a delete collector listening to message deletions.
+    ///
+    /// Events supported:
+    /// - `MESSAGE_DELETE`
+    /// - `MESSAGE_DELETE_BULK` (one `DeleteCollectEvent` per deleted message)
+    pub fn delete_collector<F>(&self, options: CollectorOptions, filter: F) -> DeleteCollector
+    where
+        F: Fn(&DeleteCollectEvent) -> bool + Send + Sync + 'static,
+    {
+        let rx = self.spawn_collector(options, filter, DeleteCollectEvent::from_dispatch);
+
+        DeleteCollector { rx }
     }
 
     /// Creates a reaction collector listening to reaction add/remove dispatches.
@@ -145,57 +259,116 @@ impl CollectorHub {
     pub fn reaction_collector<F>(&self, options: CollectorOptions, filter: F) -> ReactionCollector
     where
         F: Fn(&ReactionCollectEvent) -> bool + Send + Sync + 'static,
+    {
+        let rx = self.spawn_collector(options, filter, ReactionCollectEvent::from_dispatch);
+
+        ReactionCollector { rx }
+    }
+
+    /// Creates a typing collector listening to `TYPING_START`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use diself::{CollectorOptions, Context};
+    ///
+    /// async fn example(ctx: &Context, channel_id: &str) {
+    ///     let channel_id = channel_id.to_string();
+    ///     let mut collector = ctx.typing_collector(CollectorOptions::default(), move |t| {
+    ///         t.channel_id == channel_id
+    ///     });
+    ///
+    ///     while let Some(typing) = collector.next().await {
+    ///         println!("{} is typing...", typing.user_id);
+    ///     }
+    /// }
+    /// ```
+    pub fn typing_collector<F>(&self, options: CollectorOptions, filter: F) -> TypingCollector
+    where
+        F: Fn(&TypingStart) -> bool + Send + Sync + 'static,
+    {
+        let rx = self.spawn_collector(options, filter, |event| {
+            if event.kind != DispatchEventType::TypingStart {
+                return None;
+            }
+            serde_json::from_value::<TypingStart>(event.data.clone()).ok()
+        });
+
+        TypingCollector { rx }
+    }
+
+    /// Spawns the task shared by all collectors: it converts dispatches with `parse`
+    /// (to zero, one or more items), keeps items accepted by `filter`, and stops on
+    /// timeout, `max`, hub close or receiver drop.
+    fn spawn_collector<T, I, F, P>(
+        &self,
+        options: CollectorOptions,
+        filter: F,
+        parse: P,
+    ) -> mpsc::UnboundedReceiver<T>
+    where
+        T: Send + 'static,
+        I: IntoIterator<Item = T>,
+        F: Fn(&T) -> bool + Send + Sync + 'static,
+        P: Fn(&DispatchEvent) -> I + Send + 'static,
     {
         let mut rx = self.tx.subscribe();
+        let mut closed = self.closed.subscribe();
         let (out_tx, out_rx) = mpsc::unbounded_channel();
         let filter = Arc::new(filter);
+        let counters = self.counters.clone();
+        let lag_handler = self.lag_handler.clone();
 
         tokio::spawn(async move {
             let deadline = options.time.map(|t| Instant::now() + t);
             let mut collected = 0usize;
 
-            loop {
-                if let Some(max) = options.max {
-                    if collected >= max {
-                        break;
-                    }
+            'collect: loop {
+                if options.max.is_some_and(|max| collected >= max) {
+                    break;
+                }
+                if *closed.borrow_and_update() {
+                    break;
                 }
 
-                let event = if let Some(deadline) = deadline {
-                    let now = Instant::now();
-                    if now >= deadline {
-                        break;
-                    }
-                    match time::timeout_at(deadline, rx.recv()).await {
-                        Ok(Ok(evt)) => evt,
-                        Ok(Err(broadcast::error::RecvError::Lagged(_))) => continue,
-                        Ok(Err(broadcast::error::RecvError::Closed)) => break,
-                        Err(_) => break,
-                    }
-                } else {
-                    match rx.recv().await {
-                        Ok(evt) => evt,
-                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
-                        Err(broadcast::error::RecvError::Closed) => break,
+                let received = async {
+                    match deadline {
+                        Some(deadline) => time::timeout_at(deadline, rx.recv()).await.ok(),
+                        None => Some(rx.recv().await),
                     }
                 };
-
-                let Some(reaction_event) = ReactionCollectEvent::from_dispatch(&event) else {
-                    continue;
+                let event = tokio::select! {
+                    received = received => match received {
+                        Some(Ok(evt)) => evt,
+                        Some(Err(broadcast::error::RecvError::Lagged(skipped))) => {
+                            counters.lags.fetch_add(1, Ordering::Relaxed);
+                            counters.dropped.fetch_add(skipped, Ordering::Relaxed);
+                            tracing::warn!("Collector lagged behind, {} dispatches skipped", skipped);
+                            if let Some(handler) = &lag_handler {
+                                handler(skipped);
+                            }
+                            continue;
+                        }
+                        Some(Err(broadcast::error::RecvError::Closed)) | None => break,
+                    },
+                    _ = closed.changed() => break,
                 };
 
-                if !(filter)(&reaction_event) {
-                    continue;
-                }
-
-                if out_tx.send(reaction_event).is_err() {
-                    break;
+                for item in parse(&event) {
+                    if !(filter)(&item) {
+                        continue;
+                    }
+                    if out_tx.send(item).is_err() {
+                        break 'collect;
+                    }
+                    collected += 1;
+                    if options.max.is_some_and(|max| collected >= max) {
+                        continue 'collect;
+                    }
                 }
-                collected += 1;
             }
         });
 
-        ReactionCollector { rx: out_rx }
+        out_rx
     }
 }
 
@@ -208,6 +381,25 @@ impl Default for CollectorHub {
 /// Collector over `Message` values.
 ///
 /// Built through `Context::message_collector(...)`.
+///
+/// Every collector also implements `futures::Stream`, so `StreamExt` combinators
+/// can be chained on it.
+///
+/// # Example
+/// ```ignore
+/// use diself::{CollectorOptions, Context};
+/// use futures::StreamExt;
+///
+/// async fn example(ctx: &Context) {
+///     let numbers: Vec<u64> = ctx
+///         .message_collector(CollectorOptions::default(), |_| true)
+///         .filter_map(|m| async move { m.content.parse().ok() })
+///         .take(3)
+///         .collect()
+///         .await;
+///     println!("Got {numbers:?}");
+/// }
+/// ```
 pub struct MessageCollector {
     rx: mpsc::UnboundedReceiver<Message>,
 }
@@ -228,6 +420,122 @@ impl MessageCollector {
     }
 }
 
+impl Stream for MessageCollector {
+    type Item = Message;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+/// Collector over edited `Message` values.
+///
+/// Built through `Context::edit_collector(...)`.
+pub struct EditCollector {
+    rx: mpsc::UnboundedReceiver<Message>,
+}
+
+impl EditCollector {
+    /// Waits for the next collected edit.
+    pub async fn next(&mut self) -> Option<Message> {
+        self.rx.recv().await
+    }
+
+    /// Drains all remaining collected edits until the collector closes.
+    pub async fn collect(mut self) -> Vec<Message> {
+        let mut out = Vec::new();
+        while let Some(item) = self.rx.recv().await {
+            out.push(item);
+        }
+        out
+    }
+}
+
+impl Stream for EditCollector {
+    type Item = Message;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+/// Message deletion passed to `DeleteCollector` consumers.
+#[derive(Debug, Clone)]
+pub struct DeleteCollectEvent {
+    pub channel_id: String,
+    pub message_id: String,
+    pub guild_id: Option<String>,
+    /// Whether the message was deleted with others (`MESSAGE_DELETE_BULK`)
+    pub bulk: bool,
+}
+
+impl DeleteCollectEvent {
+    fn from_dispatch(event: &DispatchEvent) -> Vec<Self> {
+        let data = &event.data;
+        let Some(channel_id) = data.get("channel_id").and_then(Value::as_str) else {
+            return Vec::new();
+        };
+        let guild_id = data.get("guild_id").and_then(Value::as_str);
+        let (message_ids, bulk): (Vec<&str>, bool) = match event.kind {
+            DispatchEventType::MessageDelete => (
+                data.get("id").and_then(Value::as_str).into_iter().collect(),
+                false,
+            ),
+            DispatchEventType::MessageDeleteBulk => (
+                data.get("ids")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_str)
+                    .collect(),
+                true,
+            ),
+            _ => return Vec::new(),
+        };
+
+        message_ids
+            .into_iter()
+            .map(|message_id| Self {
+                channel_id: channel_id.to_string(),
+                message_id: message_id.to_string(),
+                guild_id: guild_id.map(ToOwned::to_owned),
+                bulk,
+            })
+            .collect()
+    }
+}
+
+/// Collector over `DeleteCollectEvent` values.
+///
+/// Built through `Context::delete_collector(...)`.
+pub struct DeleteCollector {
+    rx: mpsc::UnboundedReceiver<DeleteCollectEvent>,
+}
+
+impl DeleteCollector {
+    /// Waits for the next collected deletion.
+    pub async fn next(&mut self) -> Option<DeleteCollectEvent> {
+        self.rx.recv().await
+    }
+
+    /// Drains all remaining collected deletions until closed.
+    pub async fn collect(mut self) -> Vec<DeleteCollectEvent> {
+        let mut out = Vec::new();
+        while let Some(item) = self.rx.recv().await {
+            out.push(item);
+        }
+        out
+    }
+}
+
+impl Stream for DeleteCollector {
+    type Item = DeleteCollectEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
 /// Type of reaction dispatch captured by `ReactionCollector`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ReactionEventType {
@@ -246,6 +554,7 @@ pub struct ReactionCollectEvent {
     pub user_id: String,
     pub guild_id: Option<String>,
     pub emoji: Emoji,
+    pub reaction_type: ReactionType,
 }
 
 impl ReactionCollectEvent {
@@ -265,6 +574,10 @@ impl ReactionCollectEvent {
             .and_then(Value::as_str)
             .map(ToOwned::to_owned);
         let emoji = serde_json::from_value::<Emoji>(data.get("emoji")?.clone()).ok()?;
+        let reaction_type = data
+            .get("type")
+            .and_then(|kind| serde_json::from_value(kind.clone()).ok())
+            .unwrap_or_default();
 
         Some(Self {
             kind,
@@ -273,6 +586,7 @@ impl ReactionCollectEvent {
             user_id,
             guild_id,
             emoji,
+            reaction_type,
         })
     }
 }
@@ -299,3 +613,42 @@ impl ReactionCollector {
         out
     }
 }
+
+impl Stream for ReactionCollector {
+    type Item = ReactionCollectEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+/// Collector over `TypingStart` values.
+///
+/// Built through `Context::typing_collector(...)`.
+pub struct TypingCollector {
+    rx: mpsc::UnboundedReceiver<TypingStart>,
+}
+
+impl TypingCollector {
+    /// Waits for the next collected typing event.
+    pub async fn next(&mut self) -> Option<TypingStart> {
+        self.rx.recv().await
+    }
+
+    /// Drains all remaining collected typing events until closed.
+    pub async fn collect(mut self) -> Vec<TypingStart> {
+        let mut out = Vec::new();
+        while let Some(item) = self.rx.recv().await {
+            out.push(item);
+        }
+        out
+    }
+}
+
+impl Stream for TypingCollector {
+    type Item = TypingStart;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
@@ -1,13 +1,32 @@
 use crate::cache::Cache;
+use crate::client::broadcast::{self, BroadcastReport, DEFAULT_BROADCAST_INTERVAL};
 use crate::client::{
-    ChannelsManager, CollectorHub, CollectorOptions, GuildsManager, MessageCollector,
-    ReactionCollectEvent, ReactionCollector, RelationshipsManager, UsersManager,
+    ChannelsManager, CollectorHub, CollectorOptions, ConnectionsManager, DeleteCollectEvent,
+    DeleteCollector, EditCollector, GiftsManager, GuildsManager, InteractionsManager,
+    InvitesManager, MessageCollector, MessageScheduler, OAuthManager, PollsManager, PremiumManager,
+    ReactionCollectEvent, ReactionCollector, ReactionEventType, RelationshipsManager,
+    SearchManager, StickersManager, TypeMap, TypingCollector, UsersManager, VoiceStateController,
 };
+#[cfg(feature = "voice")]
+use crate::error::Error;
 use crate::error::Result;
+use crate::gateway::{GatewayMetrics, GatewaySender, SessionState};
 use crate::http::HttpClient;
-use crate::model::{Channel, Message, User};
+#[cfg(feature = "settings-proto")]
+use crate::model::settings_proto::CustomStatus;
+use crate::model::{
+    Channel, CreateMessage, EditPresence, GuildFeatures, GuildJoin, GuildSubscription, GuildTag,
+    Message, TypingStart, User,
+};
+#[cfg(feature = "settings-proto")]
+use crate::model::{CreateActivity, Timestamp};
+#[cfg(feature = "voice")]
+use crate::voice::{VoiceConnection, VoiceServerInfo};
+use parking_lot::RwLock;
 use serde_json::json;
 use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
 
 /// Context passed to event handlers.
 /// Contains references to useful clients and data.
@@ -27,8 +46,38 @@ pub struct Context {
     pub relationships: RelationshipsManager,
     /// Channels API Manager
     pub channels: ChannelsManager,
+    /// Application commands / interactions API manager
+    pub interactions: InteractionsManager,
+    /// Connected accounts API manager
+    pub connections: ConnectionsManager,
+    /// Invites API manager
+    pub invites: InvitesManager,
+    /// Poll voting API manager
+    pub polls: PollsManager,
+    /// Message search API manager
+    pub search: SearchManager,
+    /// Premium (Nitro, boosts) API manager
+    pub premium: PremiumManager,
+    /// Gift codes API manager
+    pub gifts: GiftsManager,
+    /// Stickers API manager
+    pub stickers: StickersManager,
+    /// OAuth2 application authorization API manager
+    pub oauth: OAuthManager,
     /// Collector hub for message/reaction collectors
     pub collectors: CollectorHub,
+    /// Scheduler of delayed and recurring messages
+    pub scheduler: MessageScheduler,
+    /// Voice state toggles of the current user (mute, camera, Go Live)
+    pub voice: VoiceStateController,
+    /// Shared state configured with `ClientBuilder::with_data`
+    pub data: Arc<tokio::sync::RwLock<TypeMap>>,
+    /// Gateway session shared with the running client
+    session: Arc<RwLock<Option<SessionState>>>,
+    /// Health metrics of the running gateway (shard 0 when sharded)
+    gateway_metrics: GatewayMetrics,
+    /// Command sender of the running gateway (every shard when sharded)
+    gateway: GatewaySender,
 }
 
 impl Context {
@@ -36,6 +85,8 @@ impl Context {
     pub fn new(http: HttpClient, user: User, cache: Cache) -> Self {
         // Cache the current user
         cache.set_current_user(user.clone());
+        let scheduler = MessageScheduler::new(http.clone());
+        let voice = VoiceStateController::new(GatewaySender::default(), user.id.clone());
         Self {
             http,
             user,
@@ -44,7 +95,22 @@ impl Context {
             guilds: GuildsManager,
             relationships: RelationshipsManager,
             channels: ChannelsManager,
+            interactions: InteractionsManager,
+            connections: ConnectionsManager,
+            invites: InvitesManager,
+            polls: PollsManager,
+            search: SearchManager,
+            premium: PremiumManager,
+            gifts: GiftsManager,
+            stickers: StickersManager,
+            oauth: OAuthManager,
             collectors: CollectorHub::new(),
+            scheduler,
+            voice,
+            data: Arc::new(tokio::sync::RwLock::new(TypeMap::new())),
+            session: Arc::new(RwLock::new(None)),
+            gateway_metrics: GatewayMetrics::default(),
+            gateway: GatewaySender::default(),
         }
     }
 
@@ -54,6 +120,8 @@ impl Context {
         let response = http.get(&url).await?;
         let user: User = serde_json::from_value(response)?;
         cache.set_current_user(user.clone());
+        let scheduler = MessageScheduler::new(http.clone());
+        let voice = VoiceStateController::new(GatewaySender::default(), user.id.clone());
         Ok(Self {
             http,
             user,
@@ -62,7 +130,22 @@ impl Context {
             guilds: GuildsManager,
             relationships: RelationshipsManager,
             channels: ChannelsManager,
+            interactions: InteractionsManager,
+            connections: ConnectionsManager,
+            invites: InvitesManager,
+            polls: PollsManager,
+            search: SearchManager,
+            premium: PremiumManager,
+            gifts: GiftsManager,
+            stickers: StickersManager,
+            oauth: OAuthManager,
             collectors: CollectorHub::new(),
+            scheduler,
+            voice,
+            data: Arc::new(tokio::sync::RwLock::new(TypeMap::new())),
+            session: Arc::new(RwLock::new(None)),
+            gateway_metrics: GatewayMetrics::default(),
+            gateway: GatewaySender::default(),
         })
     }
 
@@ -94,6 +177,59 @@ impl Context {
         self.collectors.message_collector(options, filter)
     }
 
+    /// Creates an edit collector for MESSAGE_UPDATE events.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use diself::{CollectorOptions, Context};
+    /// use std::time::Duration;
+    ///
+    /// async fn example(ctx: &Context, response_id: &str) {
+    ///     let response_id = response_id.to_string();
+    ///     let mut collector = ctx.edit_collector(
+    ///         CollectorOptions {
+    ///             time: Some(Duration::from_secs(30)),
+    ///             max: Some(1),
+    ///         },
+    ///         move |m| m.id == response_id,
+    ///     );
+    ///
+    ///     if let Some(edited) = collector.next().await {
+    ///         println!("Now reads: {}", edited.content);
+    ///     }
+    /// }
+    /// ```
+    pub fn edit_collector<F>(&self, options: CollectorOptions, filter: F) -> EditCollector
+    where
+        F: Fn(&Message) -> bool + Send + Sync + 'static,
+    {
+        self.collectors.edit_collector(options, filter)
+    }
+
+    /// Creates a delete collector for MESSAGE_DELETE and MESSAGE_DELETE_BULK events.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use diself::{CollectorOptions, Context};
+    ///
+    /// async fn example(ctx: &Context, channel_id: &str) {
+    ///     let channel_id = channel_id.to_string();
+    ///     let mut collector = ctx.delete_collector(CollectorOptions::default(), move |d| {
+    ///         d.channel_id == channel_id
+    ///     });
+    ///
+    ///     while let Some(deleted) = collector.next().await {
+    ///         println!("Deleted {}", deleted.message_id);
+    ///     }
+    /// }
+    /// ```
+    pub fn delete_collector<F>(&self, options: CollectorOptions, filter: F) -> DeleteCollector
+    where
+        F: Fn(&DeleteCollectEvent) -> bool + Send + Sync + 'static,
+    {
+        self.collectors.delete_collector(options, filter)
+    }
+
     /// Creates a reaction collector for reaction add/remove events.
     ///
     /// # Example
@@ -120,11 +256,333 @@ impl Context {
         self.collectors.reaction_collector(options, filter)
     }
 
+    /// Creates a typing collector for TYPING_START events.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use diself::{CollectorOptions, Context};
+    /// use std::time::Duration;
+    ///
+    /// async fn example(ctx: &Context, user_id: &str) {
+    ///     let user_id = user_id.to_string();
+    ///     let mut collector = ctx.typing_collector(
+    ///         CollectorOptions {
+    ///             time: Some(Duration::from_secs(60)),
+    ///             max: Some(1),
+    ///         },
+    ///         move |t| t.user_id == user_id,
+    ///     );
+    ///
+    ///     if let Some(typing) = collector.next().await {
+    ///         println!("Typing in {}", typing.channel_id);
+    ///     }
+    /// }
+    /// ```
+    pub fn typing_collector<F>(&self, options: CollectorOptions, filter: F) -> TypingCollector
+    where
+        F: Fn(&TypingStart) -> bool + Send + Sync + 'static,
+    {
+        self.collectors.typing_collector(options, filter)
+    }
+
+    /// Waits for the next message replying to `message`, sent by another user.
+    ///
+    /// Returns `None` if no reply arrives within `timeout` (or the client shuts down).
+    ///
+    /// # Example
+    /// ```ignore
+    /// use diself::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// async fn example(ctx: &Context, msg: &Message) -> diself::Result<()> {
+    ///     let prompt = msg.reply(&ctx.http, "Are you sure? Reply yes or no").await?;
+    ///     match ctx.await_reply(&prompt, Duration::from_secs(30)).await {
+    ///         Some(answer) if answer.content.eq_ignore_ascii_case("yes") => {}
+    ///         _ => return Ok(()),
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn await_reply(&self, message: &Message, timeout: Duration) -> Option<Message> {
+        let message_id = message.id.clone();
+        let user_id = self.user.id.clone();
+        let options = CollectorOptions {
+            time: Some(timeout),
+            max: Some(1),
+        };
+        self.message_collector(options, move |m| {
+            m.author.id != user_id
+                && m.message_reference
+                    .as_ref()
+                    .and_then(|reference| reference.message_id.as_deref())
+                    == Some(message_id.as_str())
+        })
+        .next()
+        .await
+    }
+
+    /// Waits for another user to react to `message` with `emoji`.
+    ///
+    /// `emoji` uses the format of `Message::react` (`"👍"` or `"name:id"`).
+    /// Returns `None` if no reaction is added within `timeout` (or the client shuts down).
+    ///
+    /// # Example
+    /// ```ignore
+    /// use diself::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// async fn example(ctx: &Context, prompt: &Message) -> diself::Result<()> {
+    ///     prompt.react(&ctx.http, "✅").await?;
+    ///     if let Some(reaction) = ctx.await_reaction(prompt, "✅", Duration::from_secs(30)).await {
+    ///         println!("Confirmed by {}", reaction.user_id);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn await_reaction(
+        &self,
+        message: &Message,
+        emoji: impl Into<String>,
+        timeout: Duration,
+    ) -> Option<ReactionCollectEvent> {
+        let message_id = message.id.clone();
+        let user_id = self.user.id.clone();
+        let emoji = emoji.into();
+        let options = CollectorOptions {
+            time: Some(timeout),
+            max: Some(1),
+        };
+        self.reaction_collector(options, move |r| {
+            r.kind == ReactionEventType::Add
+                && r.message_id == message_id
+                && r.user_id != user_id
+                && r.emoji.matches(&emoji)
+        })
+        .next()
+        .await
+    }
+
+    /// Sends a message once, at `at`, and returns the ID of the schedule.
+    ///
+    /// Shortcut for `ctx.scheduler.schedule_message(...)`, see `MessageScheduler`.
+    pub fn schedule_message(
+        &self,
+        channel_id: impl Into<String>,
+        message: CreateMessage,
+        at: std::time::Instant,
+    ) -> Result<u64> {
+        self.scheduler.schedule_message(channel_id, message, at)
+    }
+
+    /// Sends a message every `every` and returns the ID of the schedule.
+    ///
+    /// Shortcut for `ctx.scheduler.schedule_recurring(...)`, see `MessageScheduler`.
+    pub fn schedule_recurring(
+        &self,
+        channel_id: impl Into<String>,
+        message: CreateMessage,
+        every: Duration,
+    ) -> Result<u64> {
+        self.scheduler
+            .schedule_recurring(channel_id, message, every)
+    }
+
+    /// Sends a message to many channels, one per second, and reports which sends failed.
+    ///
+    /// A failure (missing access, rate limit, ...) doesn't stop the broadcast. Channels
+    /// listed twice get the message once.
+    pub async fn broadcast<I>(&self, channel_ids: I, message: CreateMessage) -> BroadcastReport
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.broadcast_with_interval(channel_ids, message, DEFAULT_BROADCAST_INTERVAL)
+            .await
+    }
+
+    /// Same as `broadcast`, with a custom pause between two channels
+    pub async fn broadcast_with_interval<I>(
+        &self,
+        channel_ids: I,
+        message: CreateMessage,
+        interval: Duration,
+    ) -> BroadcastReport
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let channel_ids = channel_ids.into_iter().map(Into::into).collect();
+        broadcast::broadcast(&self.http, channel_ids, &message, interval).await
+    }
+
     /// Gets the current user reference
     pub fn current_user(&self) -> &User {
         &self.user
     }
 
+    /// Gets the current gateway session ID (required to invoke interactions)
+    pub fn session_id(&self) -> Option<String> {
+        self.session
+            .read()
+            .as_ref()
+            .map(|state| state.session_id.clone())
+    }
+
+    /// Returns the gateway health metrics (heartbeat latency, reconnects, throughput)
+    pub fn gateway_metrics(&self) -> &GatewayMetrics {
+        &self.gateway_metrics
+    }
+
+    /// Returns the command sender of the running gateway
+    ///
+    /// Commands fail when the context is not attached to a running client.
+    pub fn gateway(&self) -> &GatewaySender {
+        &self.gateway
+    }
+
+    /// Updates the presence (status and activities) of the current user
+    ///
+    /// # Example
+    /// ```ignore
+    /// use diself::model::{CreateActivity, EditPresence, OnlineStatus};
+    ///
+    /// let activity = CreateActivity::playing("Minecraft")
+    ///     .details("Survival")
+    ///     .state("In the Nether")
+    ///     .started_at(started_ms);
+    /// ctx.set_presence(EditPresence::new().status(OnlineStatus::Idle).activity(activity))?;
+    /// ```
+    pub fn set_presence(&self, presence: EditPresence) -> Result<()> {
+        self.gateway.update_presence(&presence)
+    }
+
+    /// Chooses the events received from a guild (typing, activities, member lists, ...)
+    ///
+    /// Every guild of READY is subscribed to with `GuildSubscription::all()`; member lists
+    /// and member presences need an explicit subscription.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use diself::model::GuildSubscription;
+    ///
+    /// ctx.subscribe_guild("guild_id", GuildSubscription::all().channel("channel_id", [(0, 99)]))?;
+    /// ```
+    pub fn subscribe_guild(
+        &self,
+        guild_id: impl Into<String>,
+        subscription: GuildSubscription,
+    ) -> Result<()> {
+        self.gateway
+            .subscribe_guilds([(guild_id.into(), subscription)])
+    }
+
+    /// Sets the custom status of the current user
+    ///
+    /// Saves it in the protobuf user settings (like `UsersManager::set_custom_status`) so
+    /// it persists across sessions, then replaces the custom status activity of the
+    /// current presence (type 4). The presence update is skipped when no gateway
+    /// connection is running. `emoji` takes the format of `Message::react` (`"🦀"` or
+    /// `"name:id"`) or a custom emoji mention.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use diself::model::Timestamp;
+    ///
+    /// let in_one_hour = Timestamp::from_unix_ms(Timestamp::now().unix_ms() + 3_600_000);
+    /// ctx.set_custom_status("Coding", Some("🦀"), in_one_hour).await?;
+    /// ```
+    #[cfg(feature = "settings-proto")]
+    pub async fn set_custom_status(
+        &self,
+        text: impl Into<String>,
+        emoji: Option<&str>,
+        expires_at: Option<Timestamp>,
+    ) -> Result<()> {
+        let text = text.into();
+        let mut custom_status = CustomStatus::new(text.clone());
+        if let Some(emoji) = emoji {
+            custom_status = custom_status.emoji(emoji);
+        }
+        if let Some(expires_at) = expires_at {
+            custom_status = custom_status.expires_at_ms(expires_at.unix_ms().max(0) as u64);
+        }
+        self.users
+            .set_custom_status(&self.http, Some(custom_status))
+            .await?;
+
+        let mut activity = CreateActivity::custom(text);
+        if let Some(emoji) = emoji {
+            activity = activity.emoji(emoji);
+        }
+        self.update_custom_status_activity(Some(activity));
+        Ok(())
+    }
+
+    /// Removes the custom status of the current user, from the protobuf user settings
+    /// and the current presence
+    #[cfg(feature = "settings-proto")]
+    pub async fn clear_custom_status(&self) -> Result<()> {
+        self.users.set_custom_status(&self.http, None).await?;
+        self.update_custom_status_activity(None);
+        Ok(())
+    }
+
+    #[cfg(feature = "settings-proto")]
+    fn update_custom_status_activity(&self, activity: Option<CreateActivity>) {
+        let presence = self.gateway.presence().custom_status(activity);
+        if let Err(err) = self.gateway.update_presence(&presence) {
+            tracing::debug!("Custom status saved without presence update: {}", err);
+        }
+    }
+
+    /// Gateway heartbeat latency (`None` before the first heartbeat ACK)
+    ///
+    /// # Example
+    /// ```ignore
+    /// let latency = ctx.latency().map(|l| l.as_millis()).unwrap_or_default();
+    /// msg.reply(&ctx.http, format!("Pong! {latency}ms")).await?;
+    /// ```
+    pub fn latency(&self) -> Option<std::time::Duration> {
+        self.gateway_metrics.latency()
+    }
+
+    /// Shares the client's gateway session with this context
+    pub(crate) fn with_session(mut self, session: Arc<RwLock<Option<SessionState>>>) -> Self {
+        self.session = session;
+        self
+    }
+
+    /// Shares the running gateway's metrics with this context
+    pub(crate) fn with_gateway_metrics(mut self, metrics: GatewayMetrics) -> Self {
+        self.gateway_metrics = metrics;
+        self
+    }
+
+    /// Sends gateway commands of this context through the running gateway
+    pub(crate) fn with_gateway(mut self, gateway: GatewaySender) -> Self {
+        self.voice = VoiceStateController::new(gateway.clone(), self.user.id.clone());
+        self.gateway = gateway;
+        self
+    }
+
+    /// Shares the client's data store with this context
+    pub(crate) fn with_data(mut self, data: Arc<tokio::sync::RwLock<TypeMap>>) -> Self {
+        self.data = data;
+        self
+    }
+
+    /// Uses the client's collector hub in this context
+    pub(crate) fn with_collectors(mut self, collectors: CollectorHub) -> Self {
+        self.collectors = collectors;
+        self
+    }
+
+    /// Uses the client's message scheduler in this context
+    pub(crate) fn with_scheduler(mut self, scheduler: MessageScheduler) -> Self {
+        self.scheduler = scheduler;
+        self
+    }
+
     // ==================== Image Data Helpers ====================
 
     /// Converts image bytes to Discord Data URI format
@@ -202,6 +660,157 @@ impl Context {
         Ok(user)
     }
 
+    /// Fetches the server tags the current user can display, from the cached guilds with
+    /// the `GUILD_TAGS` feature
+    ///
+    /// # Example
+    /// ```ignore
+    /// if let Some(tag) = ctx.available_guild_tags().await?.first() {
+    ///     ctx.users.set_primary_guild(&ctx.http, true, &tag.guild_id).await?;
+    /// }
+    /// ```
+    pub async fn available_guild_tags(&self) -> Result<Vec<GuildTag>> {
+        let guild_ids = self
+            .cache
+            .guilds()
+            .into_iter()
+            .filter(|guild| guild.features.contains(&GuildFeatures::GuildTags))
+            .map(|guild| guild.id);
+        self.users.available_guild_tags(&self.http, guild_ids).await
+    }
+
+    /// Joins a guild from its ID or an invite code or link, then subscribes to its events
+    ///
+    /// See `GuildsManager::join`. The gateway subscription is skipped when no gateway
+    /// connection is running.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let join = ctx.join_guild("https://discord.gg/rust", false).await?;
+    /// if let Some(form) = join.pending_verification {
+    ///     println!("{} questions to answer", form.form_fields.len());
+    /// }
+    /// ```
+    pub async fn join_guild(
+        &self,
+        guild_or_invite: impl AsRef<str>,
+        lurker: bool,
+    ) -> Result<GuildJoin> {
+        let session_id = self.session_id();
+        let join = self
+            .guilds
+            .join(&self.http, guild_or_invite, lurker, session_id.as_deref())
+            .await?;
+        if let Err(err) = self.gateway.subscribe_guild(&join.guild_id) {
+            tracing::debug!(
+                "Joined guild {} without subscribing: {}",
+                join.guild_id,
+                err
+            );
+        }
+        Ok(join)
+    }
+
+    /// Joins a voice channel and connects to its voice server, to play audio
+    ///
+    /// Waits for the voice state and server of the channel on the gateway, so the client
+    /// must be running.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use diself::voice::OggOpusSource;
+    ///
+    /// let voice = ctx.join_voice("guild_id", "channel_id").await?;
+    /// voice.play(OggOpusSource::open("song.ogg").await?).await?;
+    /// ctx.leave_voice("guild_id")?;
+    /// ```
+    #[cfg(feature = "voice")]
+    pub async fn join_voice(
+        &self,
+        guild_id: impl AsRef<str>,
+        channel_id: impl AsRef<str>,
+    ) -> Result<VoiceConnection> {
+        self.connect_voice(Some(guild_id.as_ref()), channel_id.as_ref())
+            .await
+    }
+
+    /// Joins the call of a DM or group DM and connects to its voice server, to play audio
+    ///
+    /// See `join_voice`; hang up with `ctx.channels.leave_call(ctx.gateway())`.
+    #[cfg(feature = "voice")]
+    pub async fn join_call_voice(&self, channel_id: impl AsRef<str>) -> Result<VoiceConnection> {
+        self.connect_voice(None, channel_id.as_ref()).await
+    }
+
+    /// Leaves the voice channel the current user is connected to in a guild
+    pub fn leave_voice(&self, guild_id: impl AsRef<str>) -> Result<()> {
+        self.gateway
+            .update_voice_state(Some(guild_id.as_ref()), None, false, false)
+    }
+
+    /// Sends a voice state update and connects to the voice server it is answered with
+    #[cfg(feature = "voice")]
+    pub(crate) async fn connect_voice(
+        &self,
+        guild_id: Option<&str>,
+        channel_id: &str,
+    ) -> Result<VoiceConnection> {
+        use crate::client::DispatchEventType;
+        use tokio::sync::broadcast::error::RecvError;
+
+        let server_id = guild_id.unwrap_or(channel_id).to_string();
+        let mut events = self.collectors.subscribe();
+        self.gateway
+            .update_voice_state(guild_id, Some(channel_id), false, false)?;
+
+        let wait = async {
+            let mut session_id = None;
+            let mut server = None;
+            loop {
+                let event = match events.recv().await {
+                    Ok(event) => event,
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => {
+                        return Err(Error::Voice("client shut down".to_string()));
+                    }
+                };
+                let data = &event.data;
+                match event.kind {
+                    DispatchEventType::VoiceStateUpdate
+                        if data["user_id"].as_str() == Some(&self.user.id) =>
+                    {
+                        session_id = data["session_id"].as_str().map(str::to_string);
+                    }
+                    DispatchEventType::VoiceServerUpdate
+                        if data["guild_id"].as_str().or(data["channel_id"].as_str())
+                            == Some(&server_id) =>
+                    {
+                        // A null endpoint means the voice server is not allocated yet
+                        if let (Some(token), Some(endpoint)) =
+                            (data["token"].as_str(), data["endpoint"].as_str())
+                        {
+                            server = Some((token.to_string(), endpoint.to_string()));
+                        }
+                    }
+                    _ => {}
+                }
+                if let (Some(session_id), Some((token, endpoint))) = (&session_id, &server) {
+                    return Ok(VoiceServerInfo {
+                        server_id: server_id.clone(),
+                        user_id: self.user.id.clone(),
+                        session_id: session_id.clone(),
+                        token: token.clone(),
+                        endpoint: endpoint.clone(),
+                    });
+                }
+            }
+        };
+        let info = tokio::time::timeout(Duration::from_secs(10), wait)
+            .await
+            .map_err(|_| Error::Voice("no voice server received".to_string()))??;
+        VoiceConnection::connect(info).await
+    }
+
     /// Gets a user by ID
     pub async fn get_user(&self, user_id: impl AsRef<str>) -> Result<User> {
         let url = crate::http::api_url(&format!("/users/{}", user_id.as_ref()));
@@ -477,16 +1086,22 @@ impl Context {
         });
         let response = self.http.post(&url, body).await?;
         let channel: Channel = serde_json::from_value(response)?;
+        self.cache.cache_channel(channel.clone());
         Ok(channel)
     }
 
     /// Sends a DM to a user
+    ///
+    /// Reuses the cached DM channel with the user if there is one, and opens it otherwise.
     pub async fn send_dm(
         &self,
         user_id: impl AsRef<str>,
         content: impl Into<String>,
     ) -> Result<Message> {
-        let channel = self.create_dm(user_id).await?;
+        let channel = match self.cache.dm_channel_with(user_id.as_ref()) {
+            Some(channel) => channel,
+            None => self.create_dm(user_id).await?,
+        };
         self.send_message(&channel.id, content).await
     }
 
@@ -0,0 +1,104 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+/// Key type used to store and retrieve values from a [`TypeMap`].
+///
+/// # Example
+/// ```
+/// use diself::client::{TypeMap, TypeMapKey};
+///
+/// struct Counter;
+///
+/// impl TypeMapKey for Counter {
+///     type Value = u64;
+/// }
+///
+/// let mut data = TypeMap::new();
+/// data.insert::<Counter>(0);
+/// *data.get_mut::<Counter>().unwrap() += 1;
+/// assert_eq!(data.get::<Counter>(), Some(&1));
+/// ```
+pub trait TypeMapKey: Any {
+    /// Type of the value stored under this key
+    type Value: Send + Sync + 'static;
+}
+
+/// Type-keyed store for state shared between event handlers (DB pools, counters, ...).
+///
+/// Exposed to handlers as `ctx.data` behind an `Arc<RwLock<_>>`.
+#[derive(Default)]
+pub struct TypeMap {
+    entries: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+impl TypeMap {
+    /// Creates an empty map
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts a value, returning the previous value for this key if any
+    pub fn insert<K: TypeMapKey>(&mut self, value: K::Value) -> Option<K::Value> {
+        self.entries
+            .insert(TypeId::of::<K>(), Box::new(value))
+            .and_then(|old| old.downcast().ok())
+            .map(|old| *old)
+    }
+
+    /// Gets a reference to the value stored for this key
+    pub fn get<K: TypeMapKey>(&self) -> Option<&K::Value> {
+        self.entries
+            .get(&TypeId::of::<K>())
+            .and_then(|value| value.downcast_ref())
+    }
+
+    /// Gets a mutable reference to the value stored for this key
+    pub fn get_mut<K: TypeMapKey>(&mut self) -> Option<&mut K::Value> {
+        self.entries
+            .get_mut(&TypeId::of::<K>())
+            .and_then(|value| value.downcast_mut())
+    }
+
+    /// Gets the value stored for this key, inserting the result of `default` if missing
+    pub fn get_or_insert_with<K: TypeMapKey>(
+        &mut self,
+        default: impl FnOnce() -> K::Value,
+    ) -> &mut K::Value {
+        self.entries
+            .entry(TypeId::of::<K>())
+            .or_insert_with(|| Box::new(default()))
+            .downcast_mut()
+            .expect("TypeMap entry always matches its key type")
+    }
+
+    /// Removes and returns the value stored for this key
+    pub fn remove<K: TypeMapKey>(&mut self) -> Option<K::Value> {
+        self.entries
+            .remove(&TypeId::of::<K>())
+            .and_then(|value| value.downcast().ok())
+            .map(|value| *value)
+    }
+
+    /// Whether a value is stored for this key
+    pub fn contains_key<K: TypeMapKey>(&self) -> bool {
+        self.entries.contains_key(&TypeId::of::<K>())
+    }
+
+    /// Number of stored values
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the map is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl std::fmt::Debug for TypeMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TypeMap")
+            .field("len", &self.entries.len())
+            .finish()
+    }
+}
@@ -0,0 +1,1006 @@
+use crate::client::{Context, DispatchEvent, DispatchEventType, EventHandler};
+#[cfg(feature = "framework")]
+use crate::framework::CommandFramework;
+use crate::gateway::EventRecorder;
+use crate::model::{
+    Call, CallDelete, Channel, ChannelPinsUpdate, ChannelRecipientEvent, FriendSuggestion, Guild,
+    GuildBanEvent, GuildMemberAdd, GuildMemberRemove, GuildMemberUpdate, Message,
+    MessageDeleteBulk, MessageReactionAdd, MessageReactionRemove, MessageReactionRemoveAll,
+    MessageReactionRemoveEmoji, Modal, PartialMember, PassiveUpdateV1, PresenceUpdateEvent,
+    ReadyPayload, ReadySupplemental, Relationship, RelationshipType, ThreadDelete, ThreadListSync,
+    ThreadMember, ThreadMembersUpdate, TypingStart, User, VoiceState,
+};
+#[cfg(feature = "triggers")]
+use crate::triggers::TriggerSet;
+use dashmap::{DashMap, DashSet};
+use serde_json::Value;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::{oneshot, Notify, Semaphore};
+use tokio::time::{self, Duration, Instant};
+use tracing::Instrument;
+
+/// How event handler callbacks are run
+///
+/// The cache and collectors are always updated in gateway order before handlers run,
+/// so handlers see the same cache state whatever the mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DispatchMode {
+    /// Handlers run one event at a time, in gateway order (default)
+    ///
+    /// A slow handler delays every following event.
+    #[default]
+    Sequential,
+    /// Each event is handled on its own task, at most `max_concurrency` at once
+    ///
+    /// Events may be handled out of order. While `max_concurrency` handlers are running,
+    /// the next event waits for one of them to finish.
+    Concurrent { max_concurrency: usize },
+    /// Like `Concurrent`, but events of the same channel are handled in gateway order
+    ///
+    /// Events without a `channel_id` are ordered among themselves.
+    OrderedPerChannel { max_concurrency: usize },
+}
+
+/// Cached state of the entity a dispatch changes, read before the cache applies it
+///
+/// Passed to the handlers of update callbacks taking the old value.
+#[derive(Debug, Clone, Default)]
+enum Previous {
+    #[default]
+    None,
+    User(Box<User>),
+    Channel(Box<Channel>),
+    Message(Box<Message>),
+    Guild(Box<Guild>),
+    Member(Box<PartialMember>),
+    /// Whether the current user was already being rung in the call of a CALL_* dispatch
+    Ringing(bool),
+}
+
+/// Handler-side state of a running client, cheap to clone into spawned tasks
+#[derive(Clone)]
+pub(crate) struct EventDispatcher {
+    handler: Arc<dyn EventHandler>,
+    #[cfg(feature = "framework")]
+    framework: Option<Arc<CommandFramework>>,
+    #[cfg(feature = "triggers")]
+    triggers: Option<Arc<TriggerSet>>,
+    mode: DispatchMode,
+    permits: Option<Arc<Semaphore>>,
+    channel_tails: Arc<DashMap<String, (u64, oneshot::Receiver<()>)>>,
+    next_job_id: Arc<AtomicU64>,
+    in_flight: Arc<InFlight>,
+    recorder: Option<EventRecorder>,
+    /// Channels whose call is ringing the current user
+    ringing_calls: Arc<DashSet<String>>,
+}
+
+/// Number of spawned handler tasks still running
+#[derive(Default)]
+struct InFlight {
+    count: AtomicUsize,
+    idle: Notify,
+}
+
+/// Marks one handler task as running until dropped (even if the handler panics)
+struct InFlightGuard(Arc<InFlight>);
+
+impl InFlightGuard {
+    fn new(in_flight: &Arc<InFlight>) -> Self {
+        in_flight.count.fetch_add(1, Ordering::SeqCst);
+        Self(in_flight.clone())
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if self.0.count.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.0.idle.notify_waiters();
+        }
+    }
+}
+
+impl EventDispatcher {
+    pub(crate) fn new(
+        handler: Arc<dyn EventHandler>,
+        #[cfg(feature = "framework")] framework: Option<Arc<CommandFramework>>,
+        #[cfg(feature = "triggers")] triggers: Option<Arc<TriggerSet>>,
+        mode: DispatchMode,
+    ) -> Self {
+        let permits = match mode {
+            DispatchMode::Sequential => None,
+            DispatchMode::Concurrent { max_concurrency }
+            | DispatchMode::OrderedPerChannel { max_concurrency } => {
+                Some(Arc::new(Semaphore::new(max_concurrency.max(1))))
+            }
+        };
+        Self {
+            handler,
+            #[cfg(feature = "framework")]
+            framework,
+            #[cfg(feature = "triggers")]
+            triggers,
+            mode,
+            permits,
+            channel_tails: Arc::new(DashMap::new()),
+            next_job_id: Arc::new(AtomicU64::new(0)),
+            in_flight: Arc::new(InFlight::default()),
+            recorder: None,
+            ringing_calls: Arc::new(DashSet::new()),
+        }
+    }
+
+    /// Writes every dispatch payload to `recorder` before handling it
+    pub(crate) fn with_recorder(mut self, recorder: Option<EventRecorder>) -> Self {
+        self.recorder = recorder;
+        self
+    }
+
+    /// Waits for spawned handler tasks to finish, returning `false` if some are still
+    /// running after `timeout`
+    pub(crate) async fn drain(&self, timeout: Duration) -> bool {
+        let finished = async {
+            loop {
+                let idle = self.in_flight.idle.notified();
+                if self.in_flight.count.load(Ordering::SeqCst) == 0 {
+                    return;
+                }
+                idle.await;
+            }
+        };
+        time::timeout(timeout, finished).await.is_ok()
+    }
+
+    /// Updates the cache and collectors in gateway order, then runs the handlers per the dispatch mode
+    pub(crate) async fn handle_event(&self, ctx: &Context, event: Value) {
+        let dispatch = DispatchEvent::from_gateway_value(&event);
+        if let (Some(recorder), Some(_)) = (&self.recorder, &dispatch) {
+            if let Err(e) = recorder.record(&event) {
+                tracing::warn!("Failed to record gateway event: {}", e);
+            }
+        }
+        let old_state = dispatch
+            .as_ref()
+            .map(|dispatch| self.previous_state(ctx, dispatch))
+            .unwrap_or_default();
+        if let Some(dispatch) = &dispatch {
+            ctx.cache
+                .update_from_dispatch(dispatch.name(), &dispatch.data);
+            ctx.collectors.dispatch(dispatch.clone());
+            #[cfg(feature = "metrics")]
+            record_metrics(ctx, dispatch);
+        }
+
+        let span = tracing::debug_span!(
+            "gateway_event",
+            event = dispatch.as_ref().map_or("", DispatchEvent::name),
+            seq = dispatch.as_ref().and_then(|dispatch| dispatch.sequence),
+        );
+
+        let Some(permits) = self.permits.clone() else {
+            self.run_handlers_timed(ctx, event, dispatch, old_state)
+                .instrument(span)
+                .await;
+            return;
+        };
+        // Taken before spawning, so at most `max_concurrency` tasks exist: once they are all
+        // busy, the gateway loop waits here instead of piling up tasks.
+        let Ok(permit) = permits.acquire_owned().await else {
+            return;
+        };
+
+        // Waiting for the previous event of the channel happens in the task, so the
+        // gateway loop (and its heartbeats) never blocks on a slow channel.
+        let previous = match (&self.mode, &dispatch) {
+            (DispatchMode::OrderedPerChannel { .. }, Some(dispatch)) => {
+                Some(self.enqueue_in_channel(ordering_key(dispatch)))
+            }
+            _ => None,
+        };
+
+        let dispatcher = self.clone();
+        let ctx = ctx.clone();
+        let guard = InFlightGuard::new(&self.in_flight);
+        let task = async move {
+            let _guard = guard;
+            let _permit = permit;
+            let done = match previous {
+                Some((key, job_id, previous, done)) => {
+                    if let Some(previous) = previous {
+                        let _ = previous.await;
+                    }
+                    Some((key, job_id, done))
+                }
+                None => None,
+            };
+
+            dispatcher
+                .run_handlers_timed(&ctx, event, dispatch, old_state)
+                .await;
+
+            if let Some((key, job_id, done)) = done {
+                let _ = done.send(());
+                dispatcher
+                    .channel_tails
+                    .remove_if(&key, |_, (tail_id, _)| *tail_id == job_id);
+            }
+        };
+        tokio::spawn(task.instrument(span));
+    }
+
+    /// Runs the handlers of an event, logging how long they took
+    async fn run_handlers_timed(
+        &self,
+        ctx: &Context,
+        event: Value,
+        dispatch: Option<DispatchEvent>,
+        previous: Previous,
+    ) {
+        let started = Instant::now();
+        self.run_handlers(ctx, event, dispatch, previous).await;
+        tracing::debug!(elapsed = ?started.elapsed(), "Event handled");
+    }
+
+    /// Registers a job at the end of a channel queue, returning the receiver of the job before it
+    #[allow(clippy::type_complexity)]
+    fn enqueue_in_channel(
+        &self,
+        key: String,
+    ) -> (
+        String,
+        u64,
+        Option<oneshot::Receiver<()>>,
+        oneshot::Sender<()>,
+    ) {
+        let job_id = self.next_job_id.fetch_add(1, Ordering::Relaxed);
+        let (done, tail) = oneshot::channel();
+        let previous = self
+            .channel_tails
+            .insert(key.clone(), (job_id, tail))
+            .map(|(_, previous)| previous);
+        (key, job_id, previous, done)
+    }
+
+    /// Cached state of the entity a dispatch updates, before the cache applies it
+    fn previous_state(&self, ctx: &Context, dispatch: &DispatchEvent) -> Previous {
+        let id = dispatch.data.get("id").and_then(|v| v.as_str());
+        let state = match dispatch.name() {
+            "USER_UPDATE" => id
+                .and_then(|id| {
+                    ctx.cache
+                        .peek_user(id)
+                        .or_else(|| ctx.cache.current_user().filter(|user| user.id == id))
+                })
+                .map(|user| Previous::User(Box::new(user))),
+            "CHANNEL_UPDATE" => id
+                .and_then(|id| ctx.cache.peek_channel(id))
+                .map(|channel| Previous::Channel(Box::new(channel))),
+            "MESSAGE_UPDATE" => id
+                .and_then(|id| ctx.cache.peek_message(id))
+                .map(|message| Previous::Message(Box::new(message))),
+            "GUILD_UPDATE" => id
+                .and_then(|id| ctx.cache.peek_guild(id))
+                .map(|guild| Previous::Guild(Box::new(guild))),
+            "GUILD_MEMBER_UPDATE" => {
+                let guild_id = dispatch.data.get("guild_id").and_then(|v| v.as_str());
+                let user_id = dispatch.data.pointer("/user/id").and_then(|v| v.as_str());
+                guild_id
+                    .zip(user_id)
+                    .and_then(|(guild_id, user_id)| ctx.cache.peek_member(guild_id, user_id))
+                    .map(|member| Previous::Member(Box::new(member)))
+            }
+            "CALL_CREATE" | "CALL_UPDATE" | "CALL_DELETE" => {
+                Some(Previous::Ringing(self.track_ringing(ctx, dispatch)))
+            }
+            _ => None,
+        };
+        state.unwrap_or_default()
+    }
+
+    /// Records whether a call rings the current user, returning whether it already did
+    fn track_ringing(&self, ctx: &Context, dispatch: &DispatchEvent) -> bool {
+        let Some(channel_id) = dispatch.data.get("channel_id").and_then(|v| v.as_str()) else {
+            return false;
+        };
+        let ringing = dispatch.kind != DispatchEventType::CallDelete
+            && dispatch.data["ringing"]
+                .as_array()
+                .is_some_and(|ids| ids.iter().any(|id| id.as_str() == Some(&ctx.user.id)));
+        if ringing {
+            !self.ringing_calls.insert(channel_id.to_string())
+        } else {
+            self.ringing_calls.remove(channel_id).is_some()
+        }
+    }
+}
+
+impl EventDispatcher {
+    /// Runs the handler callbacks of one gateway payload
+    async fn run_handlers(
+        &self,
+        ctx: &Context,
+        event: Value,
+        dispatch: Option<DispatchEvent>,
+        previous: Previous,
+    ) {
+        self.handler.on_gateway_payload(ctx, &event).await;
+
+        let Some(dispatch) = dispatch else {
+            return;
+        };
+        self.handler.on_dispatch(ctx, dispatch.clone()).await;
+        let was_ringing = matches!(previous, Previous::Ringing(true));
+        self.dispatch_raw_event(ctx, &dispatch, was_ringing).await;
+
+        match dispatch.kind {
+            DispatchEventType::Ready => {
+                if let Some(user) = ctx.cache.current_user() {
+                    self.handler.on_ready(ctx, user).await;
+                }
+                match serde_json::from_value::<ReadyPayload>(dispatch.data.clone()) {
+                    Ok(ready) => self.handler.on_ready_full(ctx, ready).await,
+                    Err(e) => tracing::warn!("Failed to parse READY payload: {}", e),
+                }
+            }
+            DispatchEventType::ReadySupplemental => {
+                self.handler
+                    .on_ready_supplemental(ctx, ctx.user.clone(), dispatch.data.clone())
+                    .await;
+                if let Ok(data) = serde_json::from_value::<ReadySupplemental>(dispatch.data.clone())
+                {
+                    self.handler
+                        .on_ready_supplemental_typed(ctx, ctx.user.clone(), data)
+                        .await;
+                }
+            }
+            DispatchEventType::MessageCreate => {
+                if let Ok(message) = serde_json::from_value::<Message>(dispatch.data) {
+                    #[cfg(feature = "framework")]
+                    if let Some(framework) = &self.framework {
+                        if let Err(e) = framework.dispatch(ctx, &message).await {
+                            tracing::error!("Command failed: {}", e);
+                        }
+                    }
+                    #[cfg(feature = "triggers")]
+                    if let Some(triggers) = &self.triggers {
+                        if let Err(e) = triggers.dispatch(ctx, &message).await {
+                            tracing::error!("Trigger failed: {}", e);
+                        }
+                    }
+                    self.handler.on_message_create(ctx, message).await;
+                }
+            }
+            DispatchEventType::MessageUpdate => {
+                // Partial updates (e.g. embeds resolved later) only parse once merged in cache
+                let new_message = serde_json::from_value::<Message>(dispatch.data.clone())
+                    .ok()
+                    .or_else(|| {
+                        dispatch.data["id"]
+                            .as_str()
+                            .and_then(|id| ctx.cache.peek_message(id))
+                    });
+                if let Some(new_message) = new_message {
+                    let old_message = match previous {
+                        Previous::Message(message) => Some(*message),
+                        _ => None,
+                    };
+                    self.handler
+                        .on_message_update(ctx, old_message, new_message)
+                        .await;
+                }
+            }
+            DispatchEventType::GuildUpdate => {
+                if let Ok(new_guild) = serde_json::from_value::<Guild>(dispatch.data) {
+                    let old_guild = match previous {
+                        Previous::Guild(guild) => Some(*guild),
+                        _ => None,
+                    };
+                    self.handler
+                        .on_guild_update_typed(ctx, old_guild, new_guild)
+                        .await;
+                }
+            }
+            DispatchEventType::GuildMemberUpdate => {
+                if let Ok(event) = serde_json::from_value::<GuildMemberUpdate>(dispatch.data) {
+                    let old_member = match previous {
+                        Previous::Member(member) => Some(*member),
+                        _ => None,
+                    };
+                    self.handler.on_member_update(ctx, old_member, event).await;
+                }
+            }
+            DispatchEventType::MessageDelete => {
+                let data = dispatch.data;
+                if let (Some(channel_id), Some(message_id)) =
+                    (data["channel_id"].as_str(), data["id"].as_str())
+                {
+                    self.handler
+                        .on_message_delete(ctx, channel_id.to_string(), message_id.to_string())
+                        .await;
+                }
+            }
+            DispatchEventType::ChannelCreate => {
+                if let Ok(channel) = serde_json::from_value::<Channel>(dispatch.data) {
+                    self.handler.on_channel_create_typed(ctx, channel).await;
+                }
+            }
+            DispatchEventType::ChannelUpdate => {
+                if let Ok(new_channel) = serde_json::from_value::<Channel>(dispatch.data) {
+                    let old_channel = match previous {
+                        Previous::Channel(channel) => Some(*channel),
+                        _ => None,
+                    };
+                    self.handler
+                        .on_channel_update_typed(ctx, old_channel, new_channel)
+                        .await;
+                }
+            }
+            DispatchEventType::ChannelDelete => {
+                if let Ok(channel) = serde_json::from_value::<Channel>(dispatch.data) {
+                    self.handler.on_channel_delete_typed(ctx, channel).await;
+                }
+            }
+            DispatchEventType::UserUpdate => {
+                if let Ok(new_user) = serde_json::from_value::<User>(dispatch.data) {
+                    let old_user = match previous {
+                        Previous::User(user) => *user,
+                        _ => new_user.clone(),
+                    };
+                    let (old_tag, new_tag) = (old_user.guild_tag(), new_user.guild_tag());
+                    let is_current_user = new_user.id == ctx.user.id;
+                    self.handler.on_user_update(ctx, old_user, new_user).await;
+                    if is_current_user && old_tag != new_tag {
+                        self.handler
+                            .on_guild_tag_update(ctx, old_tag, new_tag)
+                            .await;
+                    }
+                }
+            }
+            DispatchEventType::Unknown(name) => {
+                tracing::trace!("Unhandled dispatch event: {}", name);
+            }
+            _ => {}
+        }
+    }
+
+    async fn dispatch_raw_event(&self, ctx: &Context, dispatch: &DispatchEvent, was_ringing: bool) {
+        match dispatch.kind {
+            DispatchEventType::Ready => self.handler.on_ready_event(ctx, dispatch.data.clone()).await,
+            DispatchEventType::ReadySupplemental => self
+                .handler
+                .on_ready_supplemental_event(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::Resumed => self.handler.on_resumed_event(ctx, dispatch.data.clone()).await,
+            DispatchEventType::ApplicationCommandPermissionsUpdate => self
+                .handler
+                .on_application_command_permissions_update(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::AutoModerationRuleCreate => self
+                .handler
+                .on_auto_moderation_rule_create(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::AutoModerationRuleUpdate => self
+                .handler
+                .on_auto_moderation_rule_update(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::AutoModerationRuleDelete => self
+                .handler
+                .on_auto_moderation_rule_delete(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::AutoModerationActionExecution => self
+                .handler
+                .on_auto_moderation_action_execution(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::CallCreate => self.handler.on_call_create(ctx, dispatch.data.clone()).await,
+            DispatchEventType::CallUpdate => self.handler.on_call_update(ctx, dispatch.data.clone()).await,
+            DispatchEventType::CallDelete => self.handler.on_call_delete(ctx, dispatch.data.clone()).await,
+            DispatchEventType::ChannelCreate => self.handler.on_channel_create(ctx, dispatch.data.clone()).await,
+            DispatchEventType::ChannelUpdate => self.handler.on_channel_update(ctx, dispatch.data.clone()).await,
+            DispatchEventType::ChannelDelete => self.handler.on_channel_delete(ctx, dispatch.data.clone()).await,
+            DispatchEventType::ChannelPinsUpdate => self
+                .handler
+                .on_channel_pins_update(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::ChannelRecipientAdd => self
+                .handler
+                .on_channel_recipient_add(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::ChannelRecipientRemove => self
+                .handler
+                .on_channel_recipient_remove(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::ThreadCreate => self.handler.on_thread_create(ctx, dispatch.data.clone()).await,
+            DispatchEventType::ThreadUpdate => self.handler.on_thread_update(ctx, dispatch.data.clone()).await,
+            DispatchEventType::ThreadDelete => self.handler.on_thread_delete(ctx, dispatch.data.clone()).await,
+            DispatchEventType::ThreadListSync => self.handler.on_thread_list_sync(ctx, dispatch.data.clone()).await,
+            DispatchEventType::ThreadMemberUpdate => self
+                .handler
+                .on_thread_member_update(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::ThreadMembersUpdate => self
+                .handler
+                .on_thread_members_update(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::EntitlementCreate => self
+                .handler
+                .on_entitlement_create(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::EntitlementUpdate => self
+                .handler
+                .on_entitlement_update(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::EntitlementDelete => self
+                .handler
+                .on_entitlement_delete(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::GuildCreate => self.handler.on_guild_create(ctx, dispatch.data.clone()).await,
+            DispatchEventType::GuildUpdate => self.handler.on_guild_update(ctx, dispatch.data.clone()).await,
+            DispatchEventType::GuildDelete => self.handler.on_guild_delete(ctx, dispatch.data.clone()).await,
+            DispatchEventType::GuildAuditLogEntryCreate => self
+                .handler
+                .on_guild_audit_log_entry_create(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::GuildBanAdd => self.handler.on_guild_ban_add(ctx, dispatch.data.clone()).await,
+            DispatchEventType::GuildBanRemove => self
+                .handler
+                .on_guild_ban_remove(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::GuildEmojisUpdate => self
+                .handler
+                .on_guild_emojis_update(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::GuildStickersUpdate => self
+                .handler
+                .on_guild_stickers_update(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::GuildIntegrationsUpdate => self
+                .handler
+                .on_guild_integrations_update(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::GuildMemberAdd => self.handler.on_guild_member_add(ctx, dispatch.data.clone()).await,
+            DispatchEventType::GuildMemberRemove => self
+                .handler
+                .on_guild_member_remove(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::GuildMemberUpdate => self
+                .handler
+                .on_guild_member_update(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::GuildMembersChunk => self
+                .handler
+                .on_guild_members_chunk(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::GuildRoleCreate => self.handler.on_guild_role_create(ctx, dispatch.data.clone()).await,
+            DispatchEventType::GuildRoleUpdate => self.handler.on_guild_role_update(ctx, dispatch.data.clone()).await,
+            DispatchEventType::GuildRoleDelete => self.handler.on_guild_role_delete(ctx, dispatch.data.clone()).await,
+            DispatchEventType::GuildScheduledEventCreate => self
+                .handler
+                .on_guild_scheduled_event_create(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::GuildScheduledEventUpdate => self
+                .handler
+                .on_guild_scheduled_event_update(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::GuildScheduledEventDelete => self
+                .handler
+                .on_guild_scheduled_event_delete(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::GuildScheduledEventUserAdd => self
+                .handler
+                .on_guild_scheduled_event_user_add(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::GuildScheduledEventUserRemove => self
+                .handler
+                .on_guild_scheduled_event_user_remove(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::GuildSoundboardSoundCreate => self
+                .handler
+                .on_guild_soundboard_sound_create(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::GuildSoundboardSoundUpdate => self
+                .handler
+                .on_guild_soundboard_sound_update(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::GuildSoundboardSoundDelete => self
+                .handler
+                .on_guild_soundboard_sound_delete(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::GuildSoundboardSoundsUpdate => self
+                .handler
+                .on_guild_soundboard_sounds_update(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::IntegrationCreate => self
+                .handler
+                .on_integration_create(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::IntegrationUpdate => self
+                .handler
+                .on_integration_update(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::IntegrationDelete => self
+                .handler
+                .on_integration_delete(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::InteractionCreate => self
+                .handler
+                .on_interaction_create(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::InteractionModalCreate => self
+                .handler
+                .on_interaction_modal_create(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::InviteCreate => self.handler.on_invite_create(ctx, dispatch.data.clone()).await,
+            DispatchEventType::InviteDelete => self.handler.on_invite_delete(ctx, dispatch.data.clone()).await,
+            DispatchEventType::MessageCreate => self
+                .handler
+                .on_message_create_event(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::MessageUpdate => self
+                .handler
+                .on_message_update_event(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::MessageDelete => self
+                .handler
+                .on_message_delete_event(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::MessageDeleteBulk => self
+                .handler
+                .on_message_delete_bulk(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::MessageReactionAdd => self
+                .handler
+                .on_message_reaction_add(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::MessageReactionRemove => self
+                .handler
+                .on_message_reaction_remove(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::MessageReactionRemoveAll => self
+                .handler
+                .on_message_reaction_remove_all(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::MessageReactionRemoveEmoji => self
+                .handler
+                .on_message_reaction_remove_emoji(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::MessagePollVoteAdd => self
+                .handler
+                .on_message_poll_vote_add(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::MessagePollVoteRemove => self
+                .handler
+                .on_message_poll_vote_remove(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::MessageAck => self
+                .handler
+                .on_message_ack(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::PresenceUpdate => self
+                .handler
+                .on_presence_update(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::PassiveUpdateV1 => self
+                .handler
+                .on_passive_update_v1(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::StageInstanceCreate => self
+                .handler
+                .on_stage_instance_create(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::StageInstanceUpdate => self
+                .handler
+                .on_stage_instance_update(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::StageInstanceDelete => self
+                .handler
+                .on_stage_instance_delete(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::SubscriptionCreate => self
+                .handler
+                .on_subscription_create(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::SubscriptionUpdate => self
+                .handler
+                .on_subscription_update(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::SubscriptionDelete => self
+                .handler
+                .on_subscription_delete(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::TypingStart => self.handler.on_typing_start(ctx, dispatch.data.clone()).await,
+            DispatchEventType::UserUpdate => self.handler.on_user_update_event(ctx, dispatch.data.clone()).await,
+            DispatchEventType::VoiceChannelEffectSend => self
+                .handler
+                .on_voice_channel_effect_send(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::VoiceStateUpdate => self
+                .handler
+                .on_voice_state_update(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::VoiceServerUpdate => self
+                .handler
+                .on_voice_server_update(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::WebhooksUpdate => self
+                .handler
+                .on_webhooks_update(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::RelationshipAdd => self
+                .handler
+                .on_relationship_add(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::RelationshipRemove => self
+                .handler
+                .on_relationship_remove(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::RelationshipUpdate => self
+                .handler
+                .on_relationship_update(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::FriendSuggestionCreate => self
+                .handler
+                .on_friend_suggestion_create(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::FriendSuggestionDelete => self
+                .handler
+                .on_friend_suggestion_delete(ctx, dispatch.data.clone())
+                .await,
+            DispatchEventType::Unknown(_) => {}
+        }
+
+        if let DispatchEventType::PassiveUpdateV1 = dispatch.kind {
+            if let Ok(data) = serde_json::from_value::<PassiveUpdateV1>(dispatch.data.clone()) {
+                self.handler.on_passive_update_v1_typed(ctx, data).await;
+            }
+        }
+
+        if let DispatchEventType::TypingStart = dispatch.kind {
+            if let Ok(data) = serde_json::from_value::<TypingStart>(dispatch.data.clone()) {
+                self.handler.on_typing_start_typed(ctx, data).await;
+            }
+        }
+
+        if let DispatchEventType::PresenceUpdate = dispatch.kind {
+            if let Ok(data) = serde_json::from_value::<PresenceUpdateEvent>(dispatch.data.clone()) {
+                self.handler.on_presence_update_typed(ctx, data).await;
+            }
+        }
+
+        if let DispatchEventType::VoiceStateUpdate = dispatch.kind {
+            if let Ok(data) = serde_json::from_value::<VoiceState>(dispatch.data.clone()) {
+                self.handler.on_voice_state_update_typed(ctx, data).await;
+            }
+        }
+
+        if let DispatchEventType::ChannelPinsUpdate = dispatch.kind {
+            if let Ok(data) = serde_json::from_value::<ChannelPinsUpdate>(dispatch.data.clone()) {
+                self.handler.on_channel_pins_update_typed(ctx, data).await;
+            }
+        }
+
+        match dispatch.kind {
+            DispatchEventType::ThreadCreate | DispatchEventType::ThreadUpdate => {
+                if let Ok(thread) = serde_json::from_value::<Channel>(dispatch.data.clone()) {
+                    if dispatch.kind == DispatchEventType::ThreadCreate {
+                        self.handler.on_thread_create_typed(ctx, thread).await;
+                    } else {
+                        self.handler.on_thread_update_typed(ctx, thread).await;
+                    }
+                }
+            }
+            DispatchEventType::ThreadDelete => {
+                if let Ok(data) = serde_json::from_value::<ThreadDelete>(dispatch.data.clone()) {
+                    self.handler.on_thread_delete_typed(ctx, data).await;
+                }
+            }
+            DispatchEventType::ThreadListSync => {
+                if let Ok(data) = serde_json::from_value::<ThreadListSync>(dispatch.data.clone()) {
+                    self.handler.on_thread_list_sync_typed(ctx, data).await;
+                }
+            }
+            DispatchEventType::ThreadMemberUpdate => {
+                if let Ok(member) = serde_json::from_value::<ThreadMember>(dispatch.data.clone()) {
+                    self.handler
+                        .on_thread_member_update_typed(ctx, member)
+                        .await;
+                }
+            }
+            DispatchEventType::ThreadMembersUpdate => {
+                if let Ok(data) =
+                    serde_json::from_value::<ThreadMembersUpdate>(dispatch.data.clone())
+                {
+                    self.handler.on_thread_members_update_typed(ctx, data).await;
+                }
+            }
+            _ => {}
+        }
+
+        if let DispatchEventType::InteractionModalCreate = dispatch.kind {
+            if let Ok(modal) = serde_json::from_value::<Modal>(dispatch.data.clone()) {
+                self.handler
+                    .on_interaction_modal_create_typed(ctx, modal)
+                    .await;
+            }
+        }
+
+        if let DispatchEventType::ChannelRecipientAdd | DispatchEventType::ChannelRecipientRemove =
+            dispatch.kind
+        {
+            if let Ok(event) =
+                serde_json::from_value::<ChannelRecipientEvent>(dispatch.data.clone())
+            {
+                if dispatch.kind == DispatchEventType::ChannelRecipientAdd {
+                    self.handler.on_group_recipient_add(ctx, event).await;
+                } else {
+                    self.handler.on_group_recipient_remove(ctx, event).await;
+                }
+            }
+        }
+
+        match dispatch.kind {
+            DispatchEventType::CallCreate => {
+                if let Ok(call) = serde_json::from_value::<Call>(dispatch.data.clone()) {
+                    if !was_ringing && call.is_ringing(&ctx.user.id) {
+                        self.handler.on_incoming_call(ctx, call.clone()).await;
+                    }
+                    self.handler.on_call_create_typed(ctx, call).await;
+                }
+            }
+            DispatchEventType::CallUpdate => {
+                if let Ok(call) = serde_json::from_value::<Call>(dispatch.data.clone()) {
+                    // Being added to the ringing list of an existing call
+                    if !was_ringing && call.is_ringing(&ctx.user.id) {
+                        self.handler.on_incoming_call(ctx, call.clone()).await;
+                    }
+                    self.handler.on_call_update_typed(ctx, call).await;
+                }
+            }
+            DispatchEventType::CallDelete => {
+                if let Ok(data) = serde_json::from_value::<CallDelete>(dispatch.data.clone()) {
+                    self.handler.on_call_delete_typed(ctx, data).await;
+                }
+            }
+            _ => {}
+        }
+
+        match dispatch.kind {
+            DispatchEventType::MessageDeleteBulk => {
+                if let Ok(data) = serde_json::from_value::<MessageDeleteBulk>(dispatch.data.clone())
+                {
+                    self.handler.on_message_delete_bulk_typed(ctx, data).await;
+                }
+            }
+            DispatchEventType::MessageReactionAdd => {
+                if let Ok(data) =
+                    serde_json::from_value::<MessageReactionAdd>(dispatch.data.clone())
+                {
+                    self.handler.on_message_reaction_add_typed(ctx, data).await;
+                }
+            }
+            DispatchEventType::MessageReactionRemove => {
+                if let Ok(data) =
+                    serde_json::from_value::<MessageReactionRemove>(dispatch.data.clone())
+                {
+                    self.handler
+                        .on_message_reaction_remove_typed(ctx, data)
+                        .await;
+                }
+            }
+            DispatchEventType::MessageReactionRemoveAll => {
+                if let Ok(data) =
+                    serde_json::from_value::<MessageReactionRemoveAll>(dispatch.data.clone())
+                {
+                    self.handler
+                        .on_message_reaction_remove_all_typed(ctx, data)
+                        .await;
+                }
+            }
+            DispatchEventType::MessageReactionRemoveEmoji => {
+                if let Ok(data) =
+                    serde_json::from_value::<MessageReactionRemoveEmoji>(dispatch.data.clone())
+                {
+                    self.handler
+                        .on_message_reaction_remove_emoji_typed(ctx, data)
+                        .await;
+                }
+            }
+            _ => {}
+        }
+
+        match dispatch.kind {
+            DispatchEventType::GuildMemberAdd => {
+                if let Ok(event) = serde_json::from_value::<GuildMemberAdd>(dispatch.data.clone()) {
+                    self.handler.on_member_join(ctx, event).await;
+                }
+            }
+            DispatchEventType::GuildMemberRemove => {
+                if let Ok(event) =
+                    serde_json::from_value::<GuildMemberRemove>(dispatch.data.clone())
+                {
+                    self.handler.on_member_remove(ctx, event).await;
+                }
+            }
+            DispatchEventType::GuildBanAdd | DispatchEventType::GuildBanRemove => {
+                if let Ok(event) = serde_json::from_value::<GuildBanEvent>(dispatch.data.clone()) {
+                    if dispatch.kind == DispatchEventType::GuildBanAdd {
+                        self.handler.on_ban_add(ctx, event).await;
+                    } else {
+                        self.handler.on_ban_remove(ctx, event).await;
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        if let DispatchEventType::RelationshipAdd | DispatchEventType::RelationshipRemove =
+            dispatch.kind
+        {
+            if let Ok(mut relationship) =
+                serde_json::from_value::<Relationship>(dispatch.data.clone())
+            {
+                if relationship.user.is_none() {
+                    relationship.user = ctx.cache.peek_user(&relationship.id);
+                }
+                match (&dispatch.kind, relationship.kind) {
+                    (DispatchEventType::RelationshipAdd, RelationshipType::IncomingRequest) => {
+                        self.handler.on_friend_request(ctx, relationship).await
+                    }
+                    (DispatchEventType::RelationshipAdd, RelationshipType::Friend) => {
+                        self.handler.on_friend_add(ctx, relationship).await
+                    }
+                    (DispatchEventType::RelationshipAdd, RelationshipType::Blocked) => {
+                        self.handler.on_blocked(ctx, relationship).await
+                    }
+                    (DispatchEventType::RelationshipRemove, RelationshipType::Friend) => {
+                        self.handler.on_friend_remove(ctx, relationship).await
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        match dispatch.kind {
+            DispatchEventType::FriendSuggestionCreate => {
+                if let Ok(suggestion) =
+                    serde_json::from_value::<FriendSuggestion>(dispatch.data.clone())
+                {
+                    self.handler.on_friend_suggestion(ctx, suggestion).await;
+                }
+            }
+            DispatchEventType::FriendSuggestionDelete => {
+                if let Some(user_id) = dispatch.data["suggested_user_id"].as_str() {
+                    self.handler
+                        .on_friend_suggestion_remove(ctx, user_id.to_string())
+                        .await;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn ordering_key(dispatch: &DispatchEvent) -> String {
+    dispatch
+        .data
+        .get("channel_id")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Dispatches between two cache size samples
+#[cfg(feature = "metrics")]
+const CACHE_METRICS_INTERVAL: u64 = 64;
+
+#[cfg(feature = "metrics")]
+fn record_metrics(ctx: &Context, dispatch: &DispatchEvent) {
+    crate::metrics::record_event(dispatch.name());
+    let sample = matches!(dispatch.kind, DispatchEventType::Ready)
+        || dispatch
+            .sequence
+            .is_some_and(|sequence| sequence % CACHE_METRICS_INTERVAL == 0);
+    if sample {
+        crate::metrics::record_cache_stats(&ctx.cache.stats());
+    }
+}
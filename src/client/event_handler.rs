@@ -1,5 +1,12 @@
 use crate::client::{Context, DispatchEvent};
-use crate::model::{Message, PassiveUpdateV1, ReadySupplemental, User};
+use crate::model::{
+    Call, CallDelete, Channel, ChannelPinsUpdate, ChannelRecipientEvent, FriendSuggestion, Guild,
+    GuildBanEvent, GuildMemberAdd, GuildMemberRemove, GuildMemberUpdate, GuildTag, Message,
+    MessageDeleteBulk, MessageReactionAdd, MessageReactionRemove, MessageReactionRemoveAll,
+    MessageReactionRemoveEmoji, Modal, PartialMember, PassiveUpdateV1, PresenceUpdateEvent,
+    ReadyPayload, ReadySupplemental, Relationship, ThreadDelete, ThreadListSync, ThreadMember,
+    ThreadMembersUpdate, TypingStart, User, VoiceState,
+};
 use async_trait::async_trait;
 use serde_json::Value;
 
@@ -43,6 +50,11 @@ pub trait EventHandler: Send + Sync {
         let _ = (ctx, user);
     }
 
+    /// Called after `on_ready` with the whole READY payload
+    async fn on_ready_full(&self, ctx: &Context, ready: ReadyPayload) {
+        let _ = (ctx, ready);
+    }
+
     /// Called soon after the READY event, and giving extra information about the session
     async fn on_ready_supplemental(&self, ctx: &Context, user: User, data: Value) {
         let _ = (ctx, user, data);
@@ -63,9 +75,14 @@ pub trait EventHandler: Send + Sync {
         let _ = (ctx, message);
     }
 
-    /// Called when a message is edited
-    async fn on_message_update(&self, ctx: &Context, message: Message) {
-        let _ = (ctx, message);
+    /// Called when a message is edited, with the cached message before the edit (if it was cached)
+    async fn on_message_update(
+        &self,
+        ctx: &Context,
+        old_message: Option<Message>,
+        new_message: Message,
+    ) {
+        let _ = (ctx, old_message, new_message);
     }
 
     /// Called when a message is deleted
@@ -78,6 +95,17 @@ pub trait EventHandler: Send + Sync {
         let _ = (ctx, old_user, new_user);
     }
 
+    /// Called when the server tag displayed by the current user changes (`None` when
+    /// hidden)
+    async fn on_guild_tag_update(
+        &self,
+        ctx: &Context,
+        old_tag: Option<GuildTag>,
+        new_tag: Option<GuildTag>,
+    ) {
+        let _ = (ctx, old_tag, new_tag);
+    }
+
     // ==================== Raw Dispatch Coverage ====================
     // One callback per DispatchEventType (raw JSON payload), discord.js-style coverage.
 
@@ -105,6 +133,15 @@ pub trait EventHandler: Send + Sync {
     async fn on_auto_moderation_action_execution(&self, ctx: &Context, data: Value) {
         let _ = (ctx, data);
     }
+    async fn on_call_create(&self, ctx: &Context, data: Value) {
+        let _ = (ctx, data);
+    }
+    async fn on_call_update(&self, ctx: &Context, data: Value) {
+        let _ = (ctx, data);
+    }
+    async fn on_call_delete(&self, ctx: &Context, data: Value) {
+        let _ = (ctx, data);
+    }
     async fn on_channel_create(&self, ctx: &Context, data: Value) {
         let _ = (ctx, data);
     }
@@ -117,6 +154,12 @@ pub trait EventHandler: Send + Sync {
     async fn on_channel_pins_update(&self, ctx: &Context, data: Value) {
         let _ = (ctx, data);
     }
+    async fn on_channel_recipient_add(&self, ctx: &Context, data: Value) {
+        let _ = (ctx, data);
+    }
+    async fn on_channel_recipient_remove(&self, ctx: &Context, data: Value) {
+        let _ = (ctx, data);
+    }
     async fn on_thread_create(&self, ctx: &Context, data: Value) {
         let _ = (ctx, data);
     }
@@ -231,6 +274,9 @@ pub trait EventHandler: Send + Sync {
     async fn on_interaction_create(&self, ctx: &Context, data: Value) {
         let _ = (ctx, data);
     }
+    async fn on_interaction_modal_create(&self, ctx: &Context, data: Value) {
+        let _ = (ctx, data);
+    }
     async fn on_invite_create(&self, ctx: &Context, data: Value) {
         let _ = (ctx, data);
     }
@@ -267,6 +313,9 @@ pub trait EventHandler: Send + Sync {
     async fn on_message_poll_vote_remove(&self, ctx: &Context, data: Value) {
         let _ = (ctx, data);
     }
+    async fn on_message_ack(&self, ctx: &Context, data: Value) {
+        let _ = (ctx, data);
+    }
     async fn on_presence_update(&self, ctx: &Context, data: Value) {
         let _ = (ctx, data);
     }
@@ -315,9 +364,236 @@ pub trait EventHandler: Send + Sync {
     async fn on_relationship_remove(&self, ctx: &Context, data: Value) {
         let _ = (ctx, data);
     }
+    async fn on_relationship_update(&self, ctx: &Context, data: Value) {
+        let _ = (ctx, data);
+    }
+    async fn on_friend_suggestion_create(&self, ctx: &Context, data: Value) {
+        let _ = (ctx, data);
+    }
+    async fn on_friend_suggestion_delete(&self, ctx: &Context, data: Value) {
+        let _ = (ctx, data);
+    }
 
     /// Typed PASSIVE_UPDATE_V1 callback.
     async fn on_passive_update_v1_typed(&self, ctx: &Context, data: PassiveUpdateV1) {
         let _ = (ctx, data);
     }
+
+    /// Typed TYPING_START callback.
+    async fn on_typing_start_typed(&self, ctx: &Context, data: TypingStart) {
+        let _ = (ctx, data);
+    }
+
+    /// Typed PRESENCE_UPDATE callback.
+    async fn on_presence_update_typed(&self, ctx: &Context, data: PresenceUpdateEvent) {
+        let _ = (ctx, data);
+    }
+
+    /// Typed VOICE_STATE_UPDATE callback (`channel_id` is `None` when the user left).
+    async fn on_voice_state_update_typed(&self, ctx: &Context, data: VoiceState) {
+        let _ = (ctx, data);
+    }
+
+    /// Called when someone sends the current user a friend request.
+    async fn on_friend_request(&self, ctx: &Context, relationship: Relationship) {
+        let _ = (ctx, relationship);
+    }
+
+    /// Called when a user becomes a friend (request accepted either way).
+    async fn on_friend_add(&self, ctx: &Context, relationship: Relationship) {
+        let _ = (ctx, relationship);
+    }
+
+    /// Called when a friend is removed.
+    async fn on_friend_remove(&self, ctx: &Context, relationship: Relationship) {
+        let _ = (ctx, relationship);
+    }
+
+    /// Called when the current user blocks someone.
+    async fn on_blocked(&self, ctx: &Context, relationship: Relationship) {
+        let _ = (ctx, relationship);
+    }
+
+    /// Called when Discord suggests a new friend (FRIEND_SUGGESTION_CREATE).
+    async fn on_friend_suggestion(&self, ctx: &Context, suggestion: FriendSuggestion) {
+        let _ = (ctx, suggestion);
+    }
+
+    /// Called when a friend suggestion is removed (FRIEND_SUGGESTION_DELETE), with the ID of
+    /// the suggested user.
+    async fn on_friend_suggestion_remove(&self, ctx: &Context, user_id: String) {
+        let _ = (ctx, user_id);
+    }
+
+    /// Typed CHANNEL_PINS_UPDATE callback.
+    async fn on_channel_pins_update_typed(&self, ctx: &Context, data: ChannelPinsUpdate) {
+        let _ = (ctx, data);
+    }
+
+    /// Typed THREAD_CREATE callback (also sent when the current user is added to a private thread).
+    async fn on_thread_create_typed(&self, ctx: &Context, thread: Channel) {
+        let _ = (ctx, thread);
+    }
+
+    /// Typed THREAD_UPDATE callback.
+    async fn on_thread_update_typed(&self, ctx: &Context, thread: Channel) {
+        let _ = (ctx, thread);
+    }
+
+    /// Typed THREAD_DELETE callback.
+    async fn on_thread_delete_typed(&self, ctx: &Context, data: ThreadDelete) {
+        let _ = (ctx, data);
+    }
+
+    /// Typed THREAD_LIST_SYNC callback.
+    async fn on_thread_list_sync_typed(&self, ctx: &Context, data: ThreadListSync) {
+        let _ = (ctx, data);
+    }
+
+    /// Typed THREAD_MEMBER_UPDATE callback (the current user's thread member changed).
+    async fn on_thread_member_update_typed(&self, ctx: &Context, member: ThreadMember) {
+        let _ = (ctx, member);
+    }
+
+    /// Typed THREAD_MEMBERS_UPDATE callback.
+    async fn on_thread_members_update_typed(&self, ctx: &Context, data: ThreadMembersUpdate) {
+        let _ = (ctx, data);
+    }
+
+    /// Typed INTERACTION_MODAL_CREATE callback.
+    ///
+    /// Called when an application opens a modal in response to an interaction of the current user.
+    async fn on_interaction_modal_create_typed(&self, ctx: &Context, modal: Modal) {
+        let _ = (ctx, modal);
+    }
+
+    /// Called when a user joins a group DM the current user is in.
+    async fn on_group_recipient_add(&self, ctx: &Context, event: ChannelRecipientEvent) {
+        let _ = (ctx, event);
+    }
+
+    /// Called when a user leaves (or is removed from) a group DM the current user is in.
+    async fn on_group_recipient_remove(&self, ctx: &Context, event: ChannelRecipientEvent) {
+        let _ = (ctx, event);
+    }
+
+    /// Typed CALL_CREATE callback.
+    async fn on_call_create_typed(&self, ctx: &Context, call: Call) {
+        let _ = (ctx, call);
+    }
+
+    /// Typed CALL_UPDATE callback.
+    async fn on_call_update_typed(&self, ctx: &Context, call: Call) {
+        let _ = (ctx, call);
+    }
+
+    /// Typed CALL_DELETE callback.
+    async fn on_call_delete_typed(&self, ctx: &Context, data: CallDelete) {
+        let _ = (ctx, data);
+    }
+
+    /// Called when a DM or group DM call starts ringing the current user.
+    ///
+    /// Fires on CALL_CREATE, and on CALL_UPDATE when the current user is added to the
+    /// ringing list of an existing call. Answer with `ctx.channels.join_call` or `ctx.channels.decline_call`.
+    async fn on_incoming_call(&self, ctx: &Context, call: Call) {
+        let _ = (ctx, call);
+    }
+
+    /// Typed MESSAGE_DELETE_BULK callback.
+    async fn on_message_delete_bulk_typed(&self, ctx: &Context, data: MessageDeleteBulk) {
+        let _ = (ctx, data);
+    }
+
+    /// Typed MESSAGE_REACTION_ADD callback.
+    async fn on_message_reaction_add_typed(&self, ctx: &Context, data: MessageReactionAdd) {
+        let _ = (ctx, data);
+    }
+
+    /// Typed MESSAGE_REACTION_REMOVE callback.
+    async fn on_message_reaction_remove_typed(&self, ctx: &Context, data: MessageReactionRemove) {
+        let _ = (ctx, data);
+    }
+
+    /// Typed MESSAGE_REACTION_REMOVE_ALL callback.
+    async fn on_message_reaction_remove_all_typed(
+        &self,
+        ctx: &Context,
+        data: MessageReactionRemoveAll,
+    ) {
+        let _ = (ctx, data);
+    }
+
+    /// Typed MESSAGE_REACTION_REMOVE_EMOJI callback.
+    async fn on_message_reaction_remove_emoji_typed(
+        &self,
+        ctx: &Context,
+        data: MessageReactionRemoveEmoji,
+    ) {
+        let _ = (ctx, data);
+    }
+
+    /// Called when a user joins a guild (GUILD_MEMBER_ADD).
+    ///
+    /// User accounts only receive member events for small guilds and for the guilds
+    /// they subscribed to (lazy guild requests).
+    async fn on_member_join(&self, ctx: &Context, event: GuildMemberAdd) {
+        let _ = (ctx, event);
+    }
+
+    /// Called when a guild member is updated (GUILD_MEMBER_UPDATE), with the cached member
+    /// before the update (if it was cached).
+    async fn on_member_update(
+        &self,
+        ctx: &Context,
+        old_member: Option<PartialMember>,
+        event: GuildMemberUpdate,
+    ) {
+        let _ = (ctx, old_member, event);
+    }
+
+    /// Called when a user leaves or is removed from a guild (GUILD_MEMBER_REMOVE).
+    async fn on_member_remove(&self, ctx: &Context, event: GuildMemberRemove) {
+        let _ = (ctx, event);
+    }
+
+    /// Typed CHANNEL_CREATE callback (guild channels, DMs and group DMs).
+    async fn on_channel_create_typed(&self, ctx: &Context, channel: Channel) {
+        let _ = (ctx, channel);
+    }
+
+    /// Typed CHANNEL_UPDATE callback, with the cached channel before the update (if it was cached).
+    async fn on_channel_update_typed(
+        &self,
+        ctx: &Context,
+        old_channel: Option<Channel>,
+        new_channel: Channel,
+    ) {
+        let _ = (ctx, old_channel, new_channel);
+    }
+
+    /// Typed CHANNEL_DELETE callback.
+    async fn on_channel_delete_typed(&self, ctx: &Context, channel: Channel) {
+        let _ = (ctx, channel);
+    }
+
+    /// Typed GUILD_UPDATE callback, with the cached guild before the update (if it was cached).
+    async fn on_guild_update_typed(
+        &self,
+        ctx: &Context,
+        old_guild: Option<Guild>,
+        new_guild: Guild,
+    ) {
+        let _ = (ctx, old_guild, new_guild);
+    }
+
+    /// Called when a user is banned from a guild (GUILD_BAN_ADD, needs the ban members permission).
+    async fn on_ban_add(&self, ctx: &Context, event: GuildBanEvent) {
+        let _ = (ctx, event);
+    }
+
+    /// Called when a user is unbanned from a guild (GUILD_BAN_REMOVE).
+    async fn on_ban_remove(&self, ctx: &Context, event: GuildBanEvent) {
+        let _ = (ctx, event);
+    }
 }
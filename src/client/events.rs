@@ -10,10 +10,15 @@ pub enum DispatchEventType {
     AutoModerationRuleUpdate,
     AutoModerationRuleDelete,
     AutoModerationActionExecution,
+    CallCreate,
+    CallUpdate,
+    CallDelete,
     ChannelCreate,
     ChannelUpdate,
     ChannelDelete,
     ChannelPinsUpdate,
+    ChannelRecipientAdd,
+    ChannelRecipientRemove,
     ThreadCreate,
     ThreadUpdate,
     ThreadDelete,
@@ -52,6 +57,7 @@ pub enum DispatchEventType {
     IntegrationUpdate,
     IntegrationDelete,
     InteractionCreate,
+    InteractionModalCreate,
     InviteCreate,
     InviteDelete,
     MessageCreate,
@@ -64,6 +70,7 @@ pub enum DispatchEventType {
     MessageReactionRemoveEmoji,
     MessagePollVoteAdd,
     MessagePollVoteRemove,
+    MessageAck,
     PresenceUpdate,
     PassiveUpdateV1,
     StageInstanceCreate,
@@ -80,6 +87,9 @@ pub enum DispatchEventType {
     WebhooksUpdate,
     RelationshipAdd,
     RelationshipRemove,
+    RelationshipUpdate,
+    FriendSuggestionCreate,
+    FriendSuggestionDelete,
     Unknown(String),
 }
 
@@ -94,10 +104,15 @@ impl DispatchEventType {
             "AUTO_MODERATION_RULE_UPDATE" => Self::AutoModerationRuleUpdate,
             "AUTO_MODERATION_RULE_DELETE" => Self::AutoModerationRuleDelete,
             "AUTO_MODERATION_ACTION_EXECUTION" => Self::AutoModerationActionExecution,
+            "CALL_CREATE" => Self::CallCreate,
+            "CALL_UPDATE" => Self::CallUpdate,
+            "CALL_DELETE" => Self::CallDelete,
             "CHANNEL_CREATE" => Self::ChannelCreate,
             "CHANNEL_UPDATE" => Self::ChannelUpdate,
             "CHANNEL_DELETE" => Self::ChannelDelete,
             "CHANNEL_PINS_UPDATE" => Self::ChannelPinsUpdate,
+            "CHANNEL_RECIPIENT_ADD" => Self::ChannelRecipientAdd,
+            "CHANNEL_RECIPIENT_REMOVE" => Self::ChannelRecipientRemove,
             "THREAD_CREATE" => Self::ThreadCreate,
             "THREAD_UPDATE" => Self::ThreadUpdate,
             "THREAD_DELETE" => Self::ThreadDelete,
@@ -136,6 +151,7 @@ impl DispatchEventType {
             "INTEGRATION_UPDATE" => Self::IntegrationUpdate,
             "INTEGRATION_DELETE" => Self::IntegrationDelete,
             "INTERACTION_CREATE" => Self::InteractionCreate,
+            "INTERACTION_MODAL_CREATE" => Self::InteractionModalCreate,
             "INVITE_CREATE" => Self::InviteCreate,
             "INVITE_DELETE" => Self::InviteDelete,
             "MESSAGE_CREATE" => Self::MessageCreate,
@@ -148,6 +164,7 @@ impl DispatchEventType {
             "MESSAGE_REACTION_REMOVE_EMOJI" => Self::MessageReactionRemoveEmoji,
             "MESSAGE_POLL_VOTE_ADD" => Self::MessagePollVoteAdd,
             "MESSAGE_POLL_VOTE_REMOVE" => Self::MessagePollVoteRemove,
+            "MESSAGE_ACK" => Self::MessageAck,
             "PRESENCE_UPDATE" => Self::PresenceUpdate,
             "PASSIVE_UPDATE_V1" => Self::PassiveUpdateV1,
             "STAGE_INSTANCE_CREATE" => Self::StageInstanceCreate,
@@ -164,6 +181,9 @@ impl DispatchEventType {
             "WEBHOOKS_UPDATE" => Self::WebhooksUpdate,
             "RELATIONSHIP_ADD" => Self::RelationshipAdd,
             "RELATIONSHIP_REMOVE" => Self::RelationshipRemove,
+            "RELATIONSHIP_UPDATE" => Self::RelationshipUpdate,
+            "FRIEND_SUGGESTION_CREATE" => Self::FriendSuggestionCreate,
+            "FRIEND_SUGGESTION_DELETE" => Self::FriendSuggestionDelete,
             _ => Self::Unknown(name.to_string()),
         }
     }
@@ -178,10 +198,15 @@ impl DispatchEventType {
             Self::AutoModerationRuleUpdate => "AUTO_MODERATION_RULE_UPDATE",
             Self::AutoModerationRuleDelete => "AUTO_MODERATION_RULE_DELETE",
             Self::AutoModerationActionExecution => "AUTO_MODERATION_ACTION_EXECUTION",
+            Self::CallCreate => "CALL_CREATE",
+            Self::CallUpdate => "CALL_UPDATE",
+            Self::CallDelete => "CALL_DELETE",
             Self::ChannelCreate => "CHANNEL_CREATE",
             Self::ChannelUpdate => "CHANNEL_UPDATE",
             Self::ChannelDelete => "CHANNEL_DELETE",
             Self::ChannelPinsUpdate => "CHANNEL_PINS_UPDATE",
+            Self::ChannelRecipientAdd => "CHANNEL_RECIPIENT_ADD",
+            Self::ChannelRecipientRemove => "CHANNEL_RECIPIENT_REMOVE",
             Self::ThreadCreate => "THREAD_CREATE",
             Self::ThreadUpdate => "THREAD_UPDATE",
             Self::ThreadDelete => "THREAD_DELETE",
@@ -220,6 +245,7 @@ impl DispatchEventType {
             Self::IntegrationUpdate => "INTEGRATION_UPDATE",
             Self::IntegrationDelete => "INTEGRATION_DELETE",
             Self::InteractionCreate => "INTERACTION_CREATE",
+            Self::InteractionModalCreate => "INTERACTION_MODAL_CREATE",
             Self::InviteCreate => "INVITE_CREATE",
             Self::InviteDelete => "INVITE_DELETE",
             Self::MessageCreate => "MESSAGE_CREATE",
@@ -232,6 +258,7 @@ impl DispatchEventType {
             Self::MessageReactionRemoveEmoji => "MESSAGE_REACTION_REMOVE_EMOJI",
             Self::MessagePollVoteAdd => "MESSAGE_POLL_VOTE_ADD",
             Self::MessagePollVoteRemove => "MESSAGE_POLL_VOTE_REMOVE",
+            Self::MessageAck => "MESSAGE_ACK",
             Self::PresenceUpdate => "PRESENCE_UPDATE",
             Self::PassiveUpdateV1 => "PASSIVE_UPDATE_V1",
             Self::StageInstanceCreate => "STAGE_INSTANCE_CREATE",
@@ -248,6 +275,9 @@ impl DispatchEventType {
             Self::WebhooksUpdate => "WEBHOOKS_UPDATE",
             Self::RelationshipAdd => "RELATIONSHIP_ADD",
             Self::RelationshipRemove => "RELATIONSHIP_REMOVE",
+            Self::RelationshipUpdate => "RELATIONSHIP_UPDATE",
+            Self::FriendSuggestionCreate => "FRIEND_SUGGESTION_CREATE",
+            Self::FriendSuggestionDelete => "FRIEND_SUGGESTION_DELETE",
             Self::Unknown(name) => name.as_str(),
         }
     }
@@ -269,6 +299,17 @@ impl DispatchEvent {
         }
     }
 
+    /// Builds a dispatch from a raw gateway payload (`None` unless it is an op 0 dispatch)
+    pub(crate) fn from_gateway_value(payload: &Value) -> Option<Self> {
+        if payload.get("op").and_then(|v| v.as_u64()) != Some(0) {
+            return None;
+        }
+        let event_type = payload.get("t").and_then(|v| v.as_str())?;
+        let sequence = payload.get("s").and_then(|v| v.as_u64());
+        let data = payload.get("d").cloned().unwrap_or(Value::Null);
+        Some(Self::from_gateway_payload(event_type, sequence, data))
+    }
+
     pub fn name(&self) -> &str {
         self.kind.as_str()
     }
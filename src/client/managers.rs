@@ -1,11 +1,30 @@
-use crate::error::Result;
-use crate::http::{api_url, HttpClient};
+use crate::client::block_list::{self, BlockList, BlockListImport, DEFAULT_IMPORT_INTERVAL};
+use crate::error::{Error, Result};
+use crate::gateway::GatewaySender;
+use crate::http::{api_url, generate_nonce, paginate, Direction, HttpClient};
+#[cfg(feature = "settings-proto")]
+use crate::model::settings_proto::{
+    CustomStatus, GuildFolders, PreloadedUserSettings, UserSettingsType,
+};
+#[cfg(feature = "settings-proto")]
+use crate::model::GuildFolder;
 use crate::model::{
-    Avatar, Ban, Channel, ForumTag, Guild, Member, Relationship, Role, SupplementalMember,
-    SupplementalMessageRequest, User, UserProfile,
+    ApplicationCommand, ApplicationCommandIndex, ApplicationCommandInteractionOption, AuditLog,
+    AuditLogActionType, AuthorizeInfo, AuthorizeRequest, Avatar, Ban, BoostSlot, BulkBanResponse,
+    BurstCredits, Channel, ChannelPins, Connection, ConnectionType, CreateForumPost, EditProfile,
+    EmbeddedApplication, Entitlement, ForumTag, FriendSuggestion, GiftCode, Guild, GuildAffinity,
+    GuildBoost, GuildDiscoveryMetadata, GuildFeatures, GuildJoin, GuildPreview, GuildProfile,
+    GuildTag, Integration, IntegrationApplication, Invite, JoinRequest, Member, MemberVerification,
+    Message, Permissions, PremiumUsage, RefreshedUrl, Relationship, RelationshipAction, Role,
+    SearchHas, SearchResults, Session, Sticker, StickerPack, Subscription, SupplementalMember,
+    SupplementalMessageRequest, ThreadListing, User, UserAffinity, UserProfile,
 };
+use futures::Stream;
 use serde_json::{json, Value};
 
+/// Longest timeout Discord accepts for a guild member.
+const MAX_TIMEOUT_DAYS: i64 = 28;
+
 /// Manager for user-related endpoints.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct UsersManager;
@@ -75,6 +94,84 @@ impl UsersManager {
         Ok(profile)
     }
 
+    /// Edits the current user and their profile, returning the updated user. (`PATCH /users/@me` and `PATCH /users/@me/profile`). SEE: <https://docs.discord.food/resources/user#modify-user-profile>
+    ///
+    /// The edit is validated before anything is sent. Each endpoint is only called when
+    /// one of its fields was set.
+    pub async fn edit_profile(&self, http: &HttpClient, profile: EditProfile) -> Result<User> {
+        profile.validate()?;
+        let (user, profile) = profile.into_bodies();
+        if !profile.is_empty() {
+            self.update_profile(http, profile).await?;
+        }
+        if user.is_empty() {
+            self.me(http).await
+        } else {
+            self.update_me(http, user).await
+        }
+    }
+
+    /// Changes the username of the current user (`PATCH /users/@me`). SEE: <https://docs.discord.food/resources/user#modify-current-user>
+    ///
+    /// Discord requires the account password. With MFA enabled, the challenge is finished
+    /// by the MFA handler of the HTTP client.
+    pub async fn change_username(
+        &self,
+        http: &HttpClient,
+        username: impl AsRef<str>,
+        password: impl AsRef<str>,
+    ) -> Result<User> {
+        let username = username.as_ref();
+        if !(2..=32).contains(&username.chars().count()) {
+            return Err(Error::InvalidArgument(
+                "username must be 2 to 32 characters".to_string(),
+            ));
+        }
+        let body = json!({ "username": username, "password": password.as_ref() });
+        let response = modify_account(http, body).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Changes the email of the current user (`PATCH /users/@me`). SEE: <https://docs.discord.food/resources/user#modify-current-user>
+    ///
+    /// Accounts with a verified email must also pass the `email_token` obtained by
+    /// confirming the change from the current address.
+    pub async fn change_email(
+        &self,
+        http: &HttpClient,
+        email: impl AsRef<str>,
+        password: impl AsRef<str>,
+        email_token: Option<&str>,
+    ) -> Result<User> {
+        let mut body = json!({ "email": email.as_ref(), "password": password.as_ref() });
+        if let Some(email_token) = email_token {
+            body["email_token"] = json!(email_token);
+        }
+        let response = modify_account(http, body).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Changes the password of the current user and returns the new token (`PATCH /users/@me`). SEE: <https://docs.discord.food/resources/user#modify-current-user>
+    ///
+    /// Discord invalidates the previous token: requests made with it fail afterwards, so
+    /// build a new client with the returned one.
+    pub async fn change_password(
+        &self,
+        http: &HttpClient,
+        password: impl AsRef<str>,
+        new_password: impl AsRef<str>,
+    ) -> Result<String> {
+        let body = json!({
+            "password": password.as_ref(),
+            "new_password": new_password.as_ref(),
+        });
+        let response = modify_account(http, body).await?;
+        response["token"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or(Error::InvalidPayload)
+    }
+
     /// Fetches the mutual relationship between the current user and another user (`GET /users/{user_id}/relationships`).
     pub async fn mutual_relationship(
         &self,
@@ -124,6 +221,35 @@ impl UsersManager {
         Ok(user)
     }
 
+    /// Stops displaying a server tag. Returns a user object on success. (`PUT /users/@me/clan`). SEE: <https://docs.discord.food/resources/user#set-guild-identity>
+    pub async fn clear_primary_guild(&self, http: &HttpClient) -> Result<User> {
+        let response = http
+            .put(
+                api_url("/users/@me/clan"),
+                json!({ "identity_enabled": false, "identity_guild_id": null }),
+            )
+            .await?;
+        let user = serde_json::from_value(response)?;
+        Ok(user)
+    }
+
+    /// Fetches the server tags the current user can display, among the given guilds.
+    ///
+    /// Guilds without a tag are skipped. Use `Context::available_guild_tags` to check every
+    /// cached guild with the `GUILD_TAGS` feature.
+    pub async fn available_guild_tags(
+        &self,
+        http: &HttpClient,
+        guild_ids: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<Vec<GuildTag>> {
+        let mut tags = Vec::new();
+        for guild_id in guild_ids {
+            let profile = GuildsManager.profile(http, guild_id).await?;
+            tags.extend(profile.guild_tag());
+        }
+        Ok(tags)
+    }
+
     /// Get Recent Avatars of the current user (`GET /users/@me/avatars`).
     pub async fn recent_avatars(&self, http: &HttpClient) -> Result<Vec<Avatar>> {
         let response = http.get(api_url("/users/@me/avatars")).await?;
@@ -160,12 +286,197 @@ impl UsersManager {
         http.delete(api_url("/users/@me/hypesquad/online")).await?;
         Ok(())
     }
+
+    /// Fetches the users the current user interacts with the most, highest score first (`GET /users/@me/affinities/users`). SEE: <https://docs.discord.food/resources/user#get-user-affinities>
+    pub async fn user_affinities(&self, http: &HttpClient) -> Result<Vec<UserAffinity>> {
+        let response = http.get(api_url("/users/@me/affinities/users")).await?;
+        let mut affinities: Vec<UserAffinity> =
+            serde_json::from_value(response["user_affinities"].clone())?;
+        affinities.sort_by(|a, b| b.affinity.total_cmp(&a.affinity));
+        Ok(affinities)
+    }
+
+    /// Fetches the guilds the current user interacts with the most, highest score first (`GET /users/@me/affinities/guilds`). SEE: <https://docs.discord.food/resources/user#get-guild-affinities>
+    pub async fn guild_affinities(&self, http: &HttpClient) -> Result<Vec<GuildAffinity>> {
+        let response = http.get(api_url("/users/@me/affinities/guilds")).await?;
+        let mut affinities: Vec<GuildAffinity> =
+            serde_json::from_value(response["guild_affinities"].clone())?;
+        affinities.sort_by(|a, b| b.affinity.total_cmp(&a.affinity));
+        Ok(affinities)
+    }
+
+    /// Fetches the sessions the current user is logged in on. (`GET /auth/sessions`). SEE: <https://docs.discord.food/resources/auth-session#get-auth-sessions>
+    pub async fn sessions(&self, http: &HttpClient) -> Result<Vec<Session>> {
+        let response = http.get(api_url("/auth/sessions")).await?;
+        let sessions = serde_json::from_value(response["user_sessions"].clone())?;
+        Ok(sessions)
+    }
+
+    /// Logs out the sessions with the given hashed IDs. (`POST /auth/sessions/logout`). SEE: <https://docs.discord.food/resources/auth-session#logout-auth-sessions>
+    pub async fn logout_sessions(
+        &self,
+        http: &HttpClient,
+        session_id_hashes: Vec<String>,
+    ) -> Result<()> {
+        http.post(
+            api_url("/auth/sessions/logout"),
+            json!({ "session_id_hashes": session_id_hashes }),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Logs out a single session by its hashed ID. (`POST /auth/sessions/logout`)
+    pub async fn logout_session(
+        &self,
+        http: &HttpClient,
+        session_id_hash: impl Into<String>,
+    ) -> Result<()> {
+        self.logout_sessions(http, vec![session_id_hash.into()])
+            .await
+    }
+
+    /// Fetches the base64 encoded protobuf settings of the given type. (`GET /users/@me/settings-proto/{type}`). SEE: <https://docs.discord.food/resources/user-settings-proto#get-user-settings-proto>
+    #[cfg(feature = "settings-proto")]
+    pub async fn settings_proto(
+        &self,
+        http: &HttpClient,
+        kind: UserSettingsType,
+    ) -> Result<String> {
+        let response = http
+            .get(api_url(&format!(
+                "/users/@me/settings-proto/{}",
+                kind as u8
+            )))
+            .await?;
+        Ok(response["settings"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string())
+    }
+
+    /// Merges base64 encoded protobuf settings into the stored ones and returns the result. (`PATCH /users/@me/settings-proto/{type}`). SEE: <https://docs.discord.food/resources/user-settings-proto#modify-user-settings-proto>
+    #[cfg(feature = "settings-proto")]
+    pub async fn update_settings_proto(
+        &self,
+        http: &HttpClient,
+        kind: UserSettingsType,
+        settings: impl Into<String>,
+    ) -> Result<String> {
+        let response = http
+            .patch(
+                api_url(&format!("/users/@me/settings-proto/{}", kind as u8)),
+                json!({ "settings": settings.into() }),
+            )
+            .await?;
+        Ok(response["settings"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string())
+    }
+
+    /// Fetches and decodes the preloaded user settings (status, custom status, theme, guild folders).
+    #[cfg(feature = "settings-proto")]
+    pub async fn preloaded_settings(&self, http: &HttpClient) -> Result<PreloadedUserSettings> {
+        let settings = self
+            .settings_proto(http, UserSettingsType::PreloadedUserSettings)
+            .await?;
+        PreloadedUserSettings::from_base64(&settings)
+    }
+
+    /// Merges the fields set in `settings` into the preloaded user settings and returns the result.
+    #[cfg(feature = "settings-proto")]
+    pub async fn update_preloaded_settings(
+        &self,
+        http: &HttpClient,
+        settings: &PreloadedUserSettings,
+    ) -> Result<PreloadedUserSettings> {
+        let settings = self
+            .update_settings_proto(
+                http,
+                UserSettingsType::PreloadedUserSettings,
+                settings.to_base64(),
+            )
+            .await?;
+        PreloadedUserSettings::from_base64(&settings)
+    }
+
+    /// Sets (or clears with `None`) the current user's custom status.
+    #[cfg(feature = "settings-proto")]
+    pub async fn set_custom_status(
+        &self,
+        http: &HttpClient,
+        custom_status: Option<CustomStatus>,
+    ) -> Result<PreloadedUserSettings> {
+        self.update_preloaded_settings(
+            http,
+            &PreloadedUserSettings::with_custom_status(custom_status),
+        )
+        .await
+    }
+
+    /// Fetches the current user's guild folders, in sidebar order. (`GET /users/@me/settings-proto/1`). SEE: <https://docs.discord.food/resources/user-settings-proto#guild-folders>
+    #[cfg(feature = "settings-proto")]
+    pub async fn guild_folders(&self, http: &HttpClient) -> Result<Vec<GuildFolder>> {
+        let settings = self.preloaded_settings(http).await?;
+        Ok(settings
+            .guild_folders
+            .as_ref()
+            .map(Vec::from)
+            .unwrap_or_default())
+    }
+
+    /// Replaces the current user's guild folders and guild order. (`PATCH /users/@me/settings-proto/1`). SEE: <https://docs.discord.food/resources/user-settings-proto#guild-folders>
+    ///
+    /// Every guild must appear in exactly one entry; use `GuildFolder::single` for guilds outside folders.
+    #[cfg(feature = "settings-proto")]
+    pub async fn set_guild_folders(
+        &self,
+        http: &HttpClient,
+        folders: Vec<GuildFolder>,
+    ) -> Result<Vec<GuildFolder>> {
+        let settings = self
+            .update_preloaded_settings(
+                http,
+                &PreloadedUserSettings::with_guild_folders(GuildFolders::from(folders.as_slice())),
+            )
+            .await?;
+        Ok(settings
+            .guild_folders
+            .as_ref()
+            .map(Vec::from)
+            .unwrap_or_default())
+    }
 }
 
 /// Manager for guild-related endpoints.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct GuildsManager;
 
+/// Filters of `GuildsManager::audit_log`, every field being optional
+#[derive(Debug, Clone, Default)]
+pub struct AuditLogParams {
+    /// Only entries of actions taken by this user
+    pub user_id: Option<String>,
+    /// Only entries of this action type
+    pub action_type: Option<AuditLogActionType>,
+    /// Only entries before this entry ID
+    pub before: Option<String>,
+    /// Only entries after this entry ID
+    pub after: Option<String>,
+    /// Maximum number of entries to return (1-100, default 50)
+    pub limit: Option<u8>,
+}
+
+/// Options of `GuildsManager::ban_member` and `GuildsManager::bulk_ban_members`.
+#[derive(Debug, Clone, Default)]
+pub struct BanOptions {
+    /// Seconds of messages to delete, up to 7 days (604800)
+    pub delete_message_seconds: Option<u64>,
+    /// Audit log reason
+    pub reason: Option<String>,
+}
+
 impl GuildsManager {
     /// Fetches current guild member objects for the current user (`/users/@me/guilds/{guild.id}/member`).
     pub async fn me_member(&self, http: &HttpClient, guild_id: impl AsRef<str>) -> Result<Member> {
@@ -195,6 +506,235 @@ impl GuildsManager {
         Ok(guild)
     }
 
+    /// Fetches the public preview of a guild, readable without joining it if the guild is discoverable. (`GET /guilds/{guild.id}/preview`). SEE: <https://docs.discord.food/resources/guild#get-guild-preview>
+    pub async fn preview(
+        &self,
+        http: &HttpClient,
+        guild_id: impl AsRef<str>,
+    ) -> Result<GuildPreview> {
+        let response = http
+            .get(api_url(&format!("/guilds/{}/preview", guild_id.as_ref())))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Fetches the public profile of a guild, including its server tag. (`GET /guilds/{guild.id}/profile`). SEE: <https://docs.discord.food/resources/discovery#get-guild-profile>
+    pub async fn profile(
+        &self,
+        http: &HttpClient,
+        guild_id: impl AsRef<str>,
+    ) -> Result<GuildProfile> {
+        let response = http
+            .get(api_url(&format!("/guilds/{}/profile", guild_id.as_ref())))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Fetches the activities that can be launched in the voice channels of a guild. (`GET /activities/guilds/{guild.id}/shelf`)
+    pub async fn activity_shelf(
+        &self,
+        http: &HttpClient,
+        guild_id: impl AsRef<str>,
+    ) -> Result<Vec<EmbeddedApplication>> {
+        let response = http
+            .get(api_url(&format!(
+                "/activities/guilds/{}/shelf",
+                guild_id.as_ref()
+            )))
+            .await?;
+        // The applications may come wrapped in an object, next to the shelf bundles
+        let applications = match response.get("applications") {
+            Some(applications) => applications.clone(),
+            None => response,
+        };
+        Ok(serde_json::from_value(applications)?)
+    }
+
+    /// Fetches the discovery listing settings of a guild. Requires `MANAGE_GUILD`. (`GET /guilds/{guild.id}/discovery-metadata`). SEE: <https://docs.discord.food/resources/discovery#get-guild-discovery-metadata>
+    pub async fn discovery_metadata(
+        &self,
+        http: &HttpClient,
+        guild_id: impl AsRef<str>,
+    ) -> Result<GuildDiscoveryMetadata> {
+        let response = http
+            .get(api_url(&format!(
+                "/guilds/{}/discovery-metadata",
+                guild_id.as_ref()
+            )))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Joins a discoverable guild without an invite. (`PUT /guilds/{guild.id}/members/@me`). SEE: <https://docs.discord.food/resources/guild#join-guild>
+    ///
+    /// With `lurker`, the guild is previewed instead: it is readable until the gateway session
+    /// ends, without being joined. Lurking requires the gateway `session_id`.
+    pub async fn join_guild_via_discovery(
+        &self,
+        http: &HttpClient,
+        guild_id: impl AsRef<str>,
+        lurker: bool,
+        session_id: Option<&str>,
+    ) -> Result<Guild> {
+        let mut url = api_url(&format!(
+            "/guilds/{}/members/@me?lurker={}",
+            guild_id.as_ref(),
+            lurker
+        ));
+        if let Some(session_id) = session_id {
+            url.push_str(&format!("&session_id={}", urlencoding::encode(session_id)));
+        }
+        let response = http.put(url, json!({})).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Joins a guild from its ID (discoverable guilds) or an invite code or link.
+    ///
+    /// Rules-only membership screening is accepted with a join request; screening with
+    /// questions is returned in `GuildJoin::pending_verification`. With `lurker`, the guild is
+    /// previewed instead (see `join_guild_via_discovery`). Use `Context::join_guild` to also
+    /// subscribe to the guild events on the gateway.
+    pub async fn join(
+        &self,
+        http: &HttpClient,
+        guild_or_invite: impl AsRef<str>,
+        lurker: bool,
+        session_id: Option<&str>,
+    ) -> Result<GuildJoin> {
+        let target = guild_or_invite.as_ref();
+        let is_guild_id = target.len() >= 17 && target.chars().all(|c| c.is_ascii_digit());
+        let invite_code = (!is_guild_id).then(|| {
+            Invite::codes_in(target)
+                .into_iter()
+                .next()
+                .unwrap_or_else(|| target.to_string())
+        });
+
+        let (guild_id, gated) = match &invite_code {
+            Some(code) if !lurker => {
+                let invite = InvitesManager.accept(http, code).await?;
+                let gated = invite.guild.as_ref().is_some_and(|guild| {
+                    guild
+                        .features
+                        .iter()
+                        .any(|feature| feature == "MEMBER_VERIFICATION_GATE_ENABLED")
+                });
+                (invite_guild_id(invite)?, gated)
+            }
+            Some(code) => {
+                let guild_id = invite_guild_id(InvitesManager.get(http, code).await?)?;
+                let guild = self
+                    .join_guild_via_discovery(http, guild_id, true, session_id)
+                    .await?;
+                (guild.id, false)
+            }
+            None => {
+                let guild = self
+                    .join_guild_via_discovery(http, target, lurker, session_id)
+                    .await?;
+                let gated = guild
+                    .features
+                    .contains(&GuildFeatures::MemberVerificationGateEnabled);
+                (guild.id, gated)
+            }
+        };
+
+        let mut join = GuildJoin {
+            guild_id,
+            lurking: lurker,
+            join_request: None,
+            pending_verification: None,
+        };
+        if gated && !lurker {
+            let verification = self
+                .member_verification(http, &join.guild_id, invite_code.as_deref())
+                .await?;
+            if verification.is_rules_only() {
+                let request = self
+                    .submit_join_request(http, &join.guild_id, verification.accept_rules())
+                    .await?;
+                join.join_request = Some(request);
+            } else {
+                join.pending_verification = Some(verification);
+            }
+        }
+        Ok(join)
+    }
+
+    /// Fetches the membership screening form of a guild. (`GET /guilds/{guild.id}/member-verification`). SEE: <https://docs.discord.food/resources/guild#get-guild-member-verification>
+    ///
+    /// `invite_code` is required to read the form of a guild the user did not join yet.
+    pub async fn member_verification(
+        &self,
+        http: &HttpClient,
+        guild_id: impl AsRef<str>,
+        invite_code: Option<&str>,
+    ) -> Result<MemberVerification> {
+        let mut url = api_url(&format!(
+            "/guilds/{}/member-verification?with_guild=false",
+            guild_id.as_ref()
+        ));
+        if let Some(code) = invite_code {
+            url.push_str(&format!("&invite_code={}", urlencoding::encode(code)));
+        }
+        let response = http.get(url).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Fetches the current user's join request to a guild, if any. (`GET /guilds/{guild.id}/requests/@me`). SEE: <https://docs.discord.food/resources/guild#get-guild-join-request>
+    pub async fn join_request(
+        &self,
+        http: &HttpClient,
+        guild_id: impl AsRef<str>,
+    ) -> Result<Option<JoinRequest>> {
+        let response = http
+            .get(api_url(&format!(
+                "/guilds/{}/requests/@me",
+                guild_id.as_ref()
+            )))
+            .await?;
+        let request = match response.get("request") {
+            Some(request) => request.clone(),
+            None => response,
+        };
+        Ok(serde_json::from_value(request)?)
+    }
+
+    /// Submits the answers to the membership screening form of a guild. (`PUT /guilds/{guild.id}/requests/@me`). SEE: <https://docs.discord.food/resources/guild#create-guild-join-request>
+    ///
+    /// Guilds without applications approve rules-only requests right away.
+    pub async fn submit_join_request(
+        &self,
+        http: &HttpClient,
+        guild_id: impl AsRef<str>,
+        verification: MemberVerification,
+    ) -> Result<JoinRequest> {
+        let response = http
+            .put(
+                api_url(&format!("/guilds/{}/requests/@me", guild_id.as_ref())),
+                json!({
+                    "version": verification.version,
+                    "form_fields": verification.form_fields,
+                }),
+            )
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Withdraws the current user's pending join request to a guild. (`DELETE /guilds/{guild.id}/requests/@me`). SEE: <https://docs.discord.food/resources/guild#delete-guild-join-request>
+    pub async fn withdraw_join_request(
+        &self,
+        http: &HttpClient,
+        guild_id: impl AsRef<str>,
+    ) -> Result<()> {
+        http.delete(api_url(&format!(
+            "/guilds/{}/requests/@me",
+            guild_id.as_ref()
+        )))
+        .await?;
+        Ok(())
+    }
+
     /// Leaves a guild (`DELETE /users/@me/guilds/{id}`).
     pub async fn leave(&self, http: &HttpClient, guild_id: impl AsRef<str>) -> Result<()> {
         http.delete(api_url(&format!("/users/@me/guilds/{}", guild_id.as_ref())))
@@ -215,9 +755,14 @@ impl GuildsManager {
         http: &HttpClient,
         guild_id: impl AsRef<str>,
         data: impl serde::Serialize,
+        reason: Option<&str>,
     ) -> Result<Guild> {
         let response = http
-            .patch(api_url(&format!("/guilds/{}", guild_id.as_ref())), data)
+            .patch_with_reason(
+                api_url(&format!("/guilds/{}", guild_id.as_ref())),
+                data,
+                reason,
+            )
             .await?;
         let guild = serde_json::from_value(response)?;
         Ok(guild)
@@ -272,6 +817,22 @@ impl GuildsManager {
         Ok(members)
     }
 
+    /// Streams every member of the guild, following the `after` cursor 1000 members at a time.
+    pub fn members_iter(
+        &self,
+        http: &HttpClient,
+        guild_id: impl AsRef<str>,
+    ) -> impl Stream<Item = Result<Member>> + Send + 'static {
+        paginate(
+            http,
+            api_url(&format!("/guilds/{}/members", guild_id.as_ref())),
+            Direction::After,
+            1000,
+            None,
+            |member: &Member| member.user.id.clone(),
+        )
+    }
+
     /// Fetches a list of guild member objects whose username or nickname contains a provided string. User must be a member of the guild.
     pub async fn search_members(
         &self,
@@ -338,21 +899,87 @@ impl GuildsManager {
         guild_id: impl AsRef<str>,
         user_id: impl AsRef<str>,
         data: impl serde::Serialize,
+        reason: Option<&str>,
     ) -> Result<Member> {
         let response = http
-            .patch(
+            .patch_with_reason(
                 api_url(&format!(
                     "/guilds/{}/members/{}",
                     guild_id.as_ref(),
                     user_id.as_ref()
                 )),
                 data,
+                reason,
             )
             .await?;
         let member = serde_json::from_value(response)?;
         Ok(member)
     }
 
+    /// Times out a member until the given time (max 28 days from now). (`PATCH /guilds/{guild.id}/members/{user.id}`). SEE: <https://docs.discord.food/resources/guild#modify-guild-member>
+    pub async fn timeout_member(
+        &self,
+        http: &HttpClient,
+        guild_id: impl AsRef<str>,
+        user_id: impl AsRef<str>,
+        until: chrono::DateTime<chrono::Utc>,
+        reason: Option<&str>,
+    ) -> Result<Member> {
+        if until > chrono::Utc::now() + chrono::Duration::days(MAX_TIMEOUT_DAYS) {
+            return Err(Error::InvalidArgument(format!(
+                "timeouts cannot exceed {MAX_TIMEOUT_DAYS} days"
+            )));
+        }
+        let until = until.to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+        self.edit_member(
+            http,
+            guild_id,
+            user_id,
+            json!({ "communication_disabled_until": until }),
+            reason,
+        )
+        .await
+    }
+
+    /// Times out a member for the given duration (max 28 days). (`PATCH /guilds/{guild.id}/members/{user.id}`). SEE: <https://docs.discord.food/resources/guild#modify-guild-member>
+    pub async fn timeout_member_for(
+        &self,
+        http: &HttpClient,
+        guild_id: impl AsRef<str>,
+        user_id: impl AsRef<str>,
+        duration: std::time::Duration,
+        reason: Option<&str>,
+    ) -> Result<Member> {
+        let duration = chrono::Duration::from_std(duration)
+            .map_err(|e| Error::InvalidArgument(format!("timeout duration: {e}")))?;
+        self.timeout_member(
+            http,
+            guild_id,
+            user_id,
+            chrono::Utc::now() + duration,
+            reason,
+        )
+        .await
+    }
+
+    /// Removes the timeout of a member. (`PATCH /guilds/{guild.id}/members/{user.id}`). SEE: <https://docs.discord.food/resources/guild#modify-guild-member>
+    pub async fn remove_timeout(
+        &self,
+        http: &HttpClient,
+        guild_id: impl AsRef<str>,
+        user_id: impl AsRef<str>,
+        reason: Option<&str>,
+    ) -> Result<Member> {
+        self.edit_member(
+            http,
+            guild_id,
+            user_id,
+            json!({ "communication_disabled_until": null }),
+            reason,
+        )
+        .await
+    }
+
     /// Modifies the current user's member in the guild. (`PATCH /guilds/{guild.id}/members/@me`). SEE: <https://docs.discord.food/resources/guild#modify-current-guild-member>
     pub async fn edit_me_member(
         &self,
@@ -397,8 +1024,9 @@ impl GuildsManager {
         guild_id: impl AsRef<str>,
         user_id: impl AsRef<str>,
         role_id: impl AsRef<str>,
+        reason: Option<&str>,
     ) -> Result<()> {
-        http.put(
+        http.put_with_reason(
             api_url(&format!(
                 "/guilds/{}/members/{}/roles/{}",
                 guild_id.as_ref(),
@@ -406,6 +1034,7 @@ impl GuildsManager {
                 role_id.as_ref()
             )),
             json!({}),
+            reason,
         )
         .await?;
         Ok(())
@@ -418,29 +1047,37 @@ impl GuildsManager {
         guild_id: impl AsRef<str>,
         user_id: impl AsRef<str>,
         role_id: impl AsRef<str>,
+        reason: Option<&str>,
     ) -> Result<()> {
-        http.delete(api_url(&format!(
-            "/guilds/{}/members/{}/roles/{}",
-            guild_id.as_ref(),
-            user_id.as_ref(),
-            role_id.as_ref()
-        )))
-        .await?;
-        Ok(())
-    }
-
+        http.delete_with_reason(
+            api_url(&format!(
+                "/guilds/{}/members/{}/roles/{}",
+                guild_id.as_ref(),
+                user_id.as_ref(),
+                role_id.as_ref()
+            )),
+            reason,
+        )
+        .await?;
+        Ok(())
+    }
+
     /// Removes/kicks a member from a guild (`DELETE /guilds/{guild.id}/members/{user.id}`). SEE: <https://docs.discord.food/resources/guild#remove-guild-member>
     pub async fn kick_member(
         &self,
         http: &HttpClient,
         guild_id: impl AsRef<str>,
         user_id: impl AsRef<str>,
+        reason: Option<&str>,
     ) -> Result<()> {
-        http.delete(api_url(&format!(
-            "/guilds/{}/members/{}",
-            guild_id.as_ref(),
-            user_id.as_ref()
-        )))
+        http.delete_with_reason(
+            api_url(&format!(
+                "/guilds/{}/members/{}",
+                guild_id.as_ref(),
+                user_id.as_ref()
+            )),
+            reason,
+        )
         .await?;
         Ok(())
     }
@@ -454,6 +1091,22 @@ impl GuildsManager {
         Ok(bans)
     }
 
+    /// Streams every ban of the guild, following the `after` cursor 1000 bans at a time.
+    pub fn bans_iter(
+        &self,
+        http: &HttpClient,
+        guild_id: impl AsRef<str>,
+    ) -> impl Stream<Item = Result<Ban>> + Send + 'static {
+        paginate(
+            http,
+            api_url(&format!("/guilds/{}/bans", guild_id.as_ref())),
+            Direction::After,
+            1000,
+            None,
+            |ban: &Ban| ban.user.id.clone(),
+        )
+    }
+
     /// Fetches a list of ban objects whose username or display name contains a provided string. (`GET /guilds/{guild.id}/bans/search?query={string}`). SEE: <https://docs.discord.food/resources/guild#search-guild-bans>
     pub async fn search_bans(
         &self,
@@ -499,8 +1152,7 @@ impl GuildsManager {
         http: &HttpClient,
         guild_id: impl AsRef<str>,
         user_id: impl AsRef<str>,
-        delete_message_seconds: Option<u64>,
-        reason: Option<&str>,
+        options: BanOptions,
     ) -> Result<()> {
         let url = api_url(&format!(
             "/guilds/{}/bans/{}",
@@ -508,27 +1160,31 @@ impl GuildsManager {
             user_id.as_ref()
         ));
         let mut body = json!({});
-        if let Some(seconds) = delete_message_seconds {
+        if let Some(seconds) = options.delete_message_seconds {
             body["delete_message_seconds"] = json!(seconds);
         }
-        if let Some(reason) = reason {
-            body["reason"] = json!(reason);
-        }
-        http.put(url, body).await?;
+        http.put_with_reason(url, body, options.reason.as_deref())
+            .await?;
         Ok(())
     }
 
-    /// Create multiples bans. (`POST /guilds/{guild.id}/bulk-ban`). SEE: <https://docs.discord.food/resources/guild#bulk-guild-ban>
+    /// Bans up to 200 users at once. (`POST /guilds/{guild.id}/bulk-ban`). SEE: <https://docs.discord.food/resources/guild#bulk-guild-ban>
     pub async fn bulk_ban_members(
         &self,
         http: &HttpClient,
         guild_id: impl AsRef<str>,
-        data: impl serde::Serialize,
-    ) -> Result<Value> {
+        user_ids: Vec<String>,
+        options: BanOptions,
+    ) -> Result<BulkBanResponse> {
+        let mut body = json!({ "user_ids": user_ids });
+        if let Some(seconds) = options.delete_message_seconds {
+            body["delete_message_seconds"] = json!(seconds);
+        }
         let response = http
-            .post(
+            .post_with_reason(
                 api_url(&format!("/guilds/{}/bulk-ban", guild_id.as_ref())),
-                data,
+                body,
+                options.reason.as_deref(),
             )
             .await?;
         let bans = serde_json::from_value(response)?;
@@ -541,16 +1197,60 @@ impl GuildsManager {
         http: &HttpClient,
         guild_id: impl AsRef<str>,
         user_id: impl AsRef<str>,
+        reason: Option<&str>,
     ) -> Result<()> {
-        http.delete(api_url(&format!(
-            "/guilds/{}/bans/{}",
-            guild_id.as_ref(),
-            user_id.as_ref()
-        )))
+        http.delete_with_reason(
+            api_url(&format!(
+                "/guilds/{}/bans/{}",
+                guild_id.as_ref(),
+                user_id.as_ref()
+            )),
+            reason,
+        )
         .await?;
         Ok(())
     }
 
+    /// Fetches the guild's audit log (`GET /guilds/{guild.id}/audit-logs`). Requires the VIEW_AUDIT_LOG permission. SEE: <https://docs.discord.food/resources/audit-log#get-guild-audit-log>
+    /// # More Info
+    /// - user_id?: Only entries from actions taken by this user
+    /// - action_type?: Only entries of this action type
+    /// - before?/after?: Entry ID cursors
+    /// - limit?: Maximum number of entries to return (1-100, default 50)
+    pub async fn audit_log(
+        &self,
+        http: &HttpClient,
+        guild_id: impl AsRef<str>,
+        params: AuditLogParams,
+    ) -> Result<AuditLog> {
+        let mut query_params = Vec::new();
+        if let Some(user_id) = params.user_id {
+            query_params.push(format!("user_id={}", user_id));
+        }
+        if let Some(action_type) = params.action_type {
+            query_params.push(format!("action_type={}", u16::from(action_type)));
+        }
+        if let Some(before) = params.before {
+            query_params.push(format!("before={}", before));
+        }
+        if let Some(after) = params.after {
+            query_params.push(format!("after={}", after));
+        }
+        if let Some(limit) = params.limit {
+            query_params.push(format!("limit={}", limit));
+        }
+
+        let mut url = api_url(&format!("/guilds/{}/audit-logs", guild_id.as_ref()));
+        if !query_params.is_empty() {
+            url.push('?');
+            url.push_str(&query_params.join("&"));
+        }
+
+        let response = http.get(url).await?;
+        let audit_log = serde_json::from_value(response)?;
+        Ok(audit_log)
+    }
+
     /// Fetches a lit of guild roles (`GET /guilds/{guild.id}/roles`). SEE: <https://docs.discord.food/resources/guild#get-guild-roles>
     pub async fn roles(&self, http: &HttpClient, guild_id: impl AsRef<str>) -> Result<Vec<Role>> {
         let response = http
@@ -660,11 +1360,13 @@ impl GuildsManager {
         http: &HttpClient,
         guild_id: impl AsRef<str>,
         data: impl serde::Serialize,
+        reason: Option<&str>,
     ) -> Result<Role> {
         let response = http
-            .post(
+            .post_with_reason(
                 api_url(&format!("/guilds/{}/roles", guild_id.as_ref())),
                 data,
+                reason,
             )
             .await?;
         let role = serde_json::from_value(response)?;
@@ -678,11 +1380,13 @@ impl GuildsManager {
         guild_id: impl AsRef<str>,
         role_id: impl AsRef<str>,
         position: u32,
+        reason: Option<&str>,
     ) -> Result<Vec<Role>> {
         let response = http
-            .patch(
+            .patch_with_reason(
                 api_url(&format!("/guilds/{}/roles", guild_id.as_ref())),
                 json!([{ "id": role_id.as_ref(), "position": position }]),
+                reason,
             )
             .await?;
         let roles = serde_json::from_value(response)?;
@@ -696,15 +1400,17 @@ impl GuildsManager {
         guild_id: impl AsRef<str>,
         role_id: impl AsRef<str>,
         data: impl serde::Serialize,
+        reason: Option<&str>,
     ) -> Result<Role> {
         let response = http
-            .patch(
+            .patch_with_reason(
                 api_url(&format!(
                     "/guilds/{}/roles/{}",
                     guild_id.as_ref(),
                     role_id.as_ref()
                 )),
                 data,
+                reason,
             )
             .await?;
         let role = serde_json::from_value(response)?;
@@ -717,12 +1423,76 @@ impl GuildsManager {
         http: &HttpClient,
         guild_id: impl AsRef<str>,
         role_id: impl AsRef<str>,
+        reason: Option<&str>,
     ) -> Result<()> {
-        http.delete(api_url(&format!(
-            "/guilds/{}/roles/{}",
-            guild_id.as_ref(),
-            role_id.as_ref()
-        )))
+        http.delete_with_reason(
+            api_url(&format!(
+                "/guilds/{}/roles/{}",
+                guild_id.as_ref(),
+                role_id.as_ref()
+            )),
+            reason,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Fetches the integrations of a guild, including bots and their applications. (`GET /guilds/{guild.id}/integrations`). SEE: <https://docs.discord.food/resources/integration#get-guild-integrations>
+    pub async fn integrations(
+        &self,
+        http: &HttpClient,
+        guild_id: impl AsRef<str>,
+    ) -> Result<Vec<Integration>> {
+        let response = http
+            .get(api_url(&format!(
+                "/guilds/{}/integrations?include_applications=true",
+                guild_id.as_ref()
+            )))
+            .await?;
+        let integrations = serde_json::from_value(response)?;
+        Ok(integrations)
+    }
+
+    /// Fetches the applications of the bots added to a guild, as listed on the Integrations page.
+    pub async fn bots(
+        &self,
+        http: &HttpClient,
+        guild_id: impl AsRef<str>,
+    ) -> Result<Vec<IntegrationApplication>> {
+        let integrations = self.integrations(http, guild_id).await?;
+        Ok(integrations
+            .into_iter()
+            .filter(Integration::is_bot)
+            .filter_map(|integration| integration.application)
+            .collect())
+    }
+
+    /// Removes an integration from a guild, kicking its bot if any. (`DELETE /guilds/{guild.id}/integrations/{integration.id}`). SEE: <https://docs.discord.food/resources/integration#delete-guild-integration>
+    pub async fn delete_integration(
+        &self,
+        http: &HttpClient,
+        guild_id: impl AsRef<str>,
+        integration_id: impl AsRef<str>,
+        reason: Option<&str>,
+    ) -> Result<()> {
+        http.delete_with_reason(
+            api_url(&format!(
+                "/guilds/{}/integrations/{}",
+                guild_id.as_ref(),
+                integration_id.as_ref()
+            )),
+            reason,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Marks every channel of a guild as read. (`POST /guilds/{guild.id}/ack`). SEE: <https://docs.discord.food/resources/guild#acknowledge-guild>
+    pub async fn ack(&self, http: &HttpClient, guild_id: impl AsRef<str>) -> Result<()> {
+        http.post(
+            api_url(&format!("/guilds/{}/ack", guild_id.as_ref())),
+            json!({}),
+        )
         .await?;
         Ok(())
     }
@@ -756,9 +1526,39 @@ impl RelationshipsManager {
         Ok(relationship)
     }
 
+    /// Sends a friend request to a user by ID (`PUT /users/@me/relationships/{id}`). SEE: <https://docs.discord.food/resources/relationships#create-relationship>
+    pub async fn send_friend_request_by_id(
+        &self,
+        http: &HttpClient,
+        user_id: impl AsRef<str>,
+    ) -> Result<()> {
+        self.put_relationship(http, user_id, RelationshipAction::Friend)
+            .await
+    }
+
+    /// Accepts an incoming friend request (`PUT /users/@me/relationships/{id}`). SEE: <https://docs.discord.food/resources/relationships#create-relationship>
+    pub async fn accept_friend_request(
+        &self,
+        http: &HttpClient,
+        user_id: impl AsRef<str>,
+    ) -> Result<()> {
+        self.put_relationship(http, user_id, RelationshipAction::Friend)
+            .await
+    }
+
+    /// Declines an incoming friend request (`DELETE /users/@me/relationships/{id}`).
+    pub async fn decline_friend_request(
+        &self,
+        http: &HttpClient,
+        user_id: impl AsRef<str>,
+    ) -> Result<()> {
+        self.remove(http, user_id).await
+    }
+
     /// Blocks a user (`PUT /users/@me/relationships/{id}` with `type=2`).
     pub async fn block(&self, http: &HttpClient, user_id: impl AsRef<str>) -> Result<()> {
-        self.put_relationship(http, user_id, 2).await
+        self.put_relationship(http, user_id, RelationshipAction::Block)
+            .await
     }
 
     /// Removes a relationship (`DELETE /users/@me/relationships/{id}`).
@@ -771,16 +1571,17 @@ impl RelationshipsManager {
         Ok(())
     }
 
-    async fn put_relationship(
+    /// Applies a relationship action to a user (`PUT /users/@me/relationships/{id}`). SEE: <https://docs.discord.food/resources/relationships#create-relationship>
+    pub async fn put_relationship(
         &self,
         http: &HttpClient,
         user_id: impl AsRef<str>,
-        relation_type: u8,
+        action: RelationshipAction,
     ) -> Result<()> {
         let response = http
             .put(
                 api_url(&format!("/users/@me/relationships/{}", user_id.as_ref())),
-                json!({ "type": relation_type }),
+                action.body(),
             )
             .await?;
 
@@ -832,6 +1633,57 @@ impl RelationshipsManager {
         Ok(relationship)
     }
 
+    /// Fetches the friend suggestions of the current user (`GET /friend-suggestions`). SEE: <https://docs.discord.food/resources/relationships#get-friend-suggestions>
+    pub async fn friend_suggestions(&self, http: &HttpClient) -> Result<Vec<FriendSuggestion>> {
+        let response = http.get(api_url("/friend-suggestions")).await?;
+        let suggestions = serde_json::from_value(response)?;
+        Ok(suggestions)
+    }
+
+    /// Dismisses a friend suggestion (`DELETE /friend-suggestions/{user.id}`). SEE: <https://docs.discord.food/resources/relationships#remove-friend-suggestion>
+    pub async fn delete_friend_suggestion(
+        &self,
+        http: &HttpClient,
+        user_id: impl AsRef<str>,
+    ) -> Result<()> {
+        http.delete(api_url(&format!(
+            "/friend-suggestions/{}",
+            user_id.as_ref()
+        )))
+        .await?;
+        Ok(())
+    }
+
+    /// Exports the blocked and ignored users of the current user (`GET /users/@me/relationships`)
+    pub async fn export_block_list(&self, http: &HttpClient) -> Result<BlockList> {
+        let relationships = self.list(http).await?;
+        Ok(BlockList::from_relationships(&relationships))
+    }
+
+    /// Blocks and ignores the users of an exported list, one per second
+    ///
+    /// Users already blocked or ignored are skipped, and failures are collected instead
+    /// of stopping the import.
+    pub async fn import_block_list(
+        &self,
+        http: &HttpClient,
+        list: &BlockList,
+    ) -> Result<BlockListImport> {
+        self.import_block_list_with_interval(http, list, DEFAULT_IMPORT_INTERVAL)
+            .await
+    }
+
+    /// Same as `import_block_list`, with a custom pause between two users
+    pub async fn import_block_list_with_interval(
+        &self,
+        http: &HttpClient,
+        list: &BlockList,
+        interval: std::time::Duration,
+    ) -> Result<BlockListImport> {
+        let current = self.list(http).await?;
+        Ok(block_list::import(http, list, &current, interval).await)
+    }
+
     /// Removes a relationship with another user. (`DELETE /users/@me/relationships/{user.id}`). SEE: <https://docs.discord.food/resources/relationships#remove-relationship>
     pub async fn delete(&self, http: &HttpClient, user_id: impl AsRef<str>) -> Result<()> {
         http.delete(api_url(&format!(
@@ -878,6 +1730,20 @@ pub struct SearchThreadsParams {
     pub min_id: Option<String>,
 }
 
+/// One channel to acknowledge with `ChannelsManager::ack_bulk`.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct BulkAckEntry {
+    /// The ID of the channel (or other resource) to acknowledge
+    pub channel_id: String,
+
+    /// The ID of the last message read in the channel
+    pub message_id: String,
+
+    /// The type of read state to acknowledge, defaults to channel read states when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_state_type: Option<u8>,
+}
+
 impl ChannelsManager {
     /// Fetches a list of active DM channel objects the user is participating in. (`GET /users/@me/channels`). SEE: <https://docs.discord.food/resources/channel#get-private-channels>
     pub async fn dm_channels(&self, http: &HttpClient) -> Result<Vec<Channel>> {
@@ -934,11 +1800,13 @@ impl ChannelsManager {
         http: &HttpClient,
         guild_id: impl AsRef<str>,
         data: impl serde::Serialize,
+        reason: Option<&str>,
     ) -> Result<Channel> {
         let response = http
-            .post(
+            .post_with_reason(
                 api_url(&format!("/guilds/{}/channels", guild_id.as_ref())),
                 data,
+                reason,
             )
             .await?;
         let channel = serde_json::from_value(response)?;
@@ -960,11 +1828,13 @@ impl ChannelsManager {
         http: &HttpClient,
         guild_id: impl AsRef<str>,
         data: impl serde::Serialize,
+        reason: Option<&str>,
     ) -> Result<Vec<Channel>> {
         let response = http
-            .patch(
+            .patch_with_reason(
                 api_url(&format!("/guilds/{}/channels", guild_id.as_ref())),
                 data,
+                reason,
             )
             .await?;
         let channels = serde_json::from_value(response)?;
@@ -990,9 +1860,14 @@ impl ChannelsManager {
         http: &HttpClient,
         channel_id: impl AsRef<str>,
         data: impl serde::Serialize,
+        reason: Option<&str>,
     ) -> Result<Channel> {
         let response = http
-            .patch(api_url(&format!("/channels/{}", channel_id.as_ref())), data)
+            .patch_with_reason(
+                api_url(&format!("/channels/{}", channel_id.as_ref())),
+                data,
+                reason,
+            )
             .await?;
         let channel = serde_json::from_value(response)?;
         Ok(channel)
@@ -1004,12 +1879,13 @@ impl ChannelsManager {
         http: &HttpClient,
         channel_id: impl AsRef<str>,
         silent: Option<bool>,
+        reason: Option<&str>,
     ) -> Result<()> {
         let mut url = api_url(&format!("/channels/{}", channel_id.as_ref()));
         if let Some(silent) = silent {
             url.push_str(&format!("?silent={}", silent));
         }
-        http.delete(url).await?;
+        http.delete_with_reason(url, reason).await?;
         Ok(())
     }
 
@@ -1020,14 +1896,16 @@ impl ChannelsManager {
         channel_id: impl AsRef<str>,
         overwrite_id: impl AsRef<str>,
         data: impl serde::Serialize,
+        reason: Option<&str>,
     ) -> Result<()> {
-        http.put(
+        http.put_with_reason(
             api_url(&format!(
                 "/channels/{}/permissions/{}",
                 channel_id.as_ref(),
                 overwrite_id.as_ref()
             )),
             data,
+            reason,
         )
         .await?;
         Ok(())
@@ -1039,12 +1917,16 @@ impl ChannelsManager {
         http: &HttpClient,
         channel_id: impl AsRef<str>,
         overwrite_id: impl AsRef<str>,
+        reason: Option<&str>,
     ) -> Result<()> {
-        http.delete(api_url(&format!(
-            "/channels/{}/permissions/{}",
-            channel_id.as_ref(),
-            overwrite_id.as_ref()
-        )))
+        http.delete_with_reason(
+            api_url(&format!(
+                "/channels/{}/permissions/{}",
+                channel_id.as_ref(),
+                overwrite_id.as_ref()
+            )),
+            reason,
+        )
         .await?;
         Ok(())
     }
@@ -1063,6 +1945,103 @@ impl ChannelsManager {
         Ok(())
     }
 
+    /// Fetches the pinned messages in a channel, most recently pinned first. (`GET /channels/{channel.id}/messages/pins`). SEE: <https://docs.discord.food/resources/message#get-channel-pins>
+    pub async fn pins(
+        &self,
+        http: &HttpClient,
+        channel_id: impl AsRef<str>,
+        before: Option<String>,
+        limit: Option<u8>,
+    ) -> Result<ChannelPins> {
+        let mut query_params = Vec::new();
+        if let Some(before) = before {
+            query_params.push(format!("before={before}"));
+        }
+        if let Some(limit) = limit {
+            query_params.push(format!("limit={limit}"));
+        }
+
+        let mut url = api_url(&format!("/channels/{}/messages/pins", channel_id.as_ref()));
+        if !query_params.is_empty() {
+            url.push('?');
+            url.push_str(&query_params.join("&"));
+        }
+
+        let response = http.get(url).await?;
+        let pins = serde_json::from_value(response)?;
+        Ok(pins)
+    }
+
+    /// Pins a message in a channel. (`PUT /channels/{channel.id}/messages/pins/{message.id}`). SEE: <https://docs.discord.food/resources/message#pin-message>
+    pub async fn pin_message(
+        &self,
+        http: &HttpClient,
+        channel_id: impl AsRef<str>,
+        message_id: impl AsRef<str>,
+        reason: Option<&str>,
+    ) -> Result<()> {
+        http.put_with_reason(
+            api_url(&format!(
+                "/channels/{}/messages/pins/{}",
+                channel_id.as_ref(),
+                message_id.as_ref()
+            )),
+            json!({}),
+            reason,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Unpins a message in a channel. (`DELETE /channels/{channel.id}/messages/pins/{message.id}`). SEE: <https://docs.discord.food/resources/message#unpin-message>
+    pub async fn unpin_message(
+        &self,
+        http: &HttpClient,
+        channel_id: impl AsRef<str>,
+        message_id: impl AsRef<str>,
+        reason: Option<&str>,
+    ) -> Result<()> {
+        http.delete_with_reason(
+            api_url(&format!(
+                "/channels/{}/messages/pins/{}",
+                channel_id.as_ref(),
+                message_id.as_ref()
+            )),
+            reason,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Marks a channel as read up to the given message. (`POST /channels/{channel.id}/messages/{message.id}/ack`). SEE: <https://docs.discord.food/resources/message#acknowledge-message>
+    pub async fn ack_message(
+        &self,
+        http: &HttpClient,
+        channel_id: impl AsRef<str>,
+        message_id: impl AsRef<str>,
+    ) -> Result<()> {
+        http.post(
+            api_url(&format!(
+                "/channels/{}/messages/{}/ack",
+                channel_id.as_ref(),
+                message_id.as_ref()
+            )),
+            json!({ "token": null }),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Marks multiple channels as read at once. (`POST /read-states/ack-bulk`). SEE: <https://docs.discord.food/resources/message#acknowledge-messages>
+    pub async fn ack_bulk(&self, http: &HttpClient, read_states: Vec<BulkAckEntry>) -> Result<()> {
+        http.post(
+            api_url("/read-states/ack-bulk"),
+            json!({ "read_states": read_states }),
+        )
+        .await?;
+        Ok(())
+    }
+
     /// Checks if the current user is eligible to ring a call in the DM channel. (`GET /channels/{channel.id}/call`). SEE: <https://docs.discord.food/resources/channel#get-call-eligibility>
     pub async fn check_call_eligibility(
         &self,
@@ -1174,6 +2153,73 @@ impl ChannelsManager {
         Ok(())
     }
 
+    /// Renames a group DM (`None` clears the name). (`PATCH /channels/{channel.id}`). SEE: <https://docs.discord.food/resources/channel#modify-channel>
+    pub async fn set_group_name(
+        &self,
+        http: &HttpClient,
+        channel_id: impl AsRef<str>,
+        name: Option<&str>,
+    ) -> Result<Channel> {
+        self.edit_channel(http, channel_id, json!({ "name": name }), None)
+            .await
+    }
+
+    /// Sets the icon of a group DM from a `data:` URI (`None` removes it). (`PATCH /channels/{channel.id}`). SEE: <https://docs.discord.food/resources/channel#modify-channel>
+    pub async fn set_group_icon(
+        &self,
+        http: &HttpClient,
+        channel_id: impl AsRef<str>,
+        icon: Option<&str>,
+    ) -> Result<Channel> {
+        self.edit_channel(http, channel_id, json!({ "icon": icon }), None)
+            .await
+    }
+
+    /// Transfers the ownership of a group DM to another recipient. (`PATCH /channels/{channel.id}`). SEE: <https://docs.discord.food/resources/channel#modify-channel>
+    /// # More Info
+    /// - You have to be the owner of the Group DM.
+    pub async fn transfer_group_ownership(
+        &self,
+        http: &HttpClient,
+        channel_id: impl AsRef<str>,
+        user_id: impl AsRef<str>,
+    ) -> Result<Channel> {
+        self.edit_channel(http, channel_id, json!({ "owner": user_id.as_ref() }), None)
+            .await
+    }
+
+    /// Fetches the invites of a channel or group DM. (`GET /channels/{channel.id}/invites`). SEE: <https://docs.discord.food/resources/invite#get-channel-invites>
+    pub async fn invites(
+        &self,
+        http: &HttpClient,
+        channel_id: impl AsRef<str>,
+    ) -> Result<Vec<Invite>> {
+        InvitesManager.channel_invites(http, channel_id).await
+    }
+
+    /// Creates an invite to a group DM. (`POST /channels/{channel.id}/invites`). SEE: <https://docs.discord.food/resources/invite#create-channel-invite>
+    /// # Params
+    /// - max_age?: Seconds before the invite expires (0 for never, default 86400)
+    pub async fn create_group_invite(
+        &self,
+        http: &HttpClient,
+        channel_id: impl AsRef<str>,
+        max_age: Option<u32>,
+    ) -> Result<Invite> {
+        let mut body = json!({});
+        if let Some(max_age) = max_age {
+            body["max_age"] = json!(max_age);
+        }
+        InvitesManager.create(http, channel_id, body).await
+    }
+
+    /// Revokes an invite. (`DELETE /invites/{invite.code}`). SEE: <https://docs.discord.food/resources/invite#delete-invite>
+    pub async fn revoke_invite(&self, http: &HttpClient, code: impl AsRef<str>) -> Result<()> {
+        http.delete(api_url(&format!("/invites/{}", code.as_ref())))
+            .await?;
+        Ok(())
+    }
+
     /// Modifies a message request's status. (`PUT /channels/{channel.id}/recipients/@me`) SEE: <https://docs.discord.food/resources/channel#update-message-request>
     /// # consent_status
     /// - 0: UNSPECIFIED - The DM isn't a message request
@@ -1238,17 +2284,11 @@ impl ChannelsManager {
     }
 
     /// Returns all active threads in the guild (`GET /guilds/{guild.id}/threads/active`). SEE: <https://docs.discord.food/resources/channel#get-guild-active-threads>
-    /// # Response Example
-    /// ```json
-    /// {
-    /// "threads": Vec<Channel>, // The list of active threads in the guild
-    /// "members": Vec<ThreadMember> // The list of thread member objects for the active threads
-    /// }
     pub async fn active_threads(
         &self,
         http: &HttpClient,
         guild_id: impl AsRef<str>,
-    ) -> Result<Value> {
+    ) -> Result<ThreadListing> {
         let response = http
             .get(api_url(&format!(
                 "/guilds/{}/threads/active",
@@ -1263,31 +2303,21 @@ impl ChannelsManager {
     /// # More Info
     /// - before?: ISO8601 timestamp to get threads before a certain time
     /// - limit?: Maximum number of threads to return (2-100, default 50)
-    /// # Response Example
-    /// ```json
-    /// {
-    /// "threads": Vec<Channel>, // The list of archived threads in the channel
-    /// "members": Vec<ThreadMember> // The list of thread member objects for the archived threads
-    /// "has_more": bool // Whether there are more threads that can be returned with a subsequent request
-    /// }
-    /// ```
     pub async fn public_archived_threads(
         &self,
         http: &HttpClient,
         channel_id: impl AsRef<str>,
         before: Option<&str>,
         limit: Option<u8>,
-    ) -> Result<Value> {
-        let mut url = api_url(&format!(
-            "/channels/{}/threads/archived/public",
-            channel_id.as_ref()
-        ));
-        if let Some(before) = before {
-            url.push_str(&format!("?before={}", before));
-        }
-        if let Some(limit) = limit {
-            url.push_str(&format!("&limit={}", limit));
-        }
+    ) -> Result<ThreadListing> {
+        let url = archived_threads_url(
+            api_url(&format!(
+                "/channels/{}/threads/archived/public",
+                channel_id.as_ref()
+            )),
+            before,
+            limit,
+        );
         let response = http.get(url).await?;
         let threads = serde_json::from_value(response)?;
         Ok(threads)
@@ -1297,30 +2327,21 @@ impl ChannelsManager {
     /// # More Info
     /// - before?: ISO8601 timestamp to get threads before a certain time
     /// - limit?: Maximum number of threads to return (2-100, default 50)
-    /// # Response Example
-    /// ```json
-    /// {
-    /// "threads": Vec<Channel>, // The list of archived threads in the channel
-    /// "members": Vec<ThreadMember> // The list of thread member objects for the archived threads
-    /// "has_more": bool // Whether there are more threads that can be returned with a subsequent request
-    /// }
     pub async fn private_archived_threads(
         &self,
         http: &HttpClient,
         channel_id: impl AsRef<str>,
         before: Option<&str>,
         limit: Option<u8>,
-    ) -> Result<Value> {
-        let mut url = api_url(&format!(
-            "/channels/{}/threads/archived/private",
-            channel_id.as_ref()
-        ));
-        if let Some(before) = before {
-            url.push_str(&format!("?before={}", before));
-        }
-        if let Some(limit) = limit {
-            url.push_str(&format!("&limit={}", limit));
-        }
+    ) -> Result<ThreadListing> {
+        let url = archived_threads_url(
+            api_url(&format!(
+                "/channels/{}/threads/archived/private",
+                channel_id.as_ref()
+            )),
+            before,
+            limit,
+        );
         let response = http.get(url).await?;
         let threads = serde_json::from_value(response)?;
         Ok(threads)
@@ -1330,31 +2351,21 @@ impl ChannelsManager {
     /// # More Info
     /// - before?: ISO8601 timestamp to get threads before a certain time
     /// - limit?: Maximum number of threads to return (2-100, default 50)
-    /// # Response Example
-    /// ```json
-    /// {
-    /// "threads": Vec<Channel>, // The list of archived threads in the channel
-    /// "members": Vec<ThreadMember> // The list of thread member objects for the archived threads
-    /// "has_more": bool // Whether there are more threads that can be returned with a subsequent request
-    /// }
-    /// ```
     pub async fn joined_private_archived_threads(
         &self,
         http: &HttpClient,
         channel_id: impl AsRef<str>,
         before: Option<&str>,
         limit: Option<u8>,
-    ) -> Result<Value> {
-        let mut url = api_url(&format!(
-            "/channels/{}/users/@me/threads/archived/private",
-            channel_id.as_ref()
-        ));
-        if let Some(before) = before {
-            url.push_str(&format!("?before={}", before));
-        }
-        if let Some(limit) = limit {
-            url.push_str(&format!("&limit={}", limit));
-        }
+    ) -> Result<ThreadListing> {
+        let url = archived_threads_url(
+            api_url(&format!(
+                "/channels/{}/users/@me/threads/archived/private",
+                channel_id.as_ref()
+            )),
+            before,
+            limit,
+        );
         let response = http.get(url).await?;
         let threads = serde_json::from_value(response)?;
         Ok(threads)
@@ -1366,7 +2377,7 @@ impl ChannelsManager {
         http: &HttpClient,
         channel_id: impl AsRef<str>,
         params: SearchThreadsParams,
-    ) -> Result<Value> {
+    ) -> Result<ThreadListing> {
         let mut url = api_url(&format!("/channels/{}/threads/search", channel_id.as_ref()));
         let mut query_params = Vec::new();
         if let Some(name) = params.name {
@@ -1471,6 +2482,16 @@ impl ChannelsManager {
         Ok(thread)
     }
 
+    /// Creates a post, a thread with its starter message, in a forum or media channel. (`POST /channels/{channel.id}/threads`). SEE: <https://docs.discord.food/resources/channel#create-thread>
+    pub async fn create_forum_post(
+        &self,
+        http: &HttpClient,
+        channel_id: impl AsRef<str>,
+        post: CreateForumPost,
+    ) -> Result<Channel> {
+        self.create_thread(http, channel_id, post).await
+    }
+
     /// Adds the current user to a thread. (`PUT /channels/{channel.id}/thread-members/@me`). SEE: <https://docs.discord.food/resources/channel#join-thread>
     pub async fn join_thread(&self, http: &HttpClient, channel_id: impl AsRef<str>) -> Result<()> {
         http.put(
@@ -1609,4 +2630,941 @@ impl ChannelsManager {
         .await?;
         Ok(())
     }
+
+    /// Fetches freshly signed URLs for expired attachment links. (`POST /attachments/refresh-urls`). SEE: <https://docs.discord.food/resources/message#refresh-attachment-urls>
+    pub async fn refresh_attachment_urls(
+        &self,
+        http: &HttpClient,
+        attachment_urls: Vec<String>,
+    ) -> Result<Vec<RefreshedUrl>> {
+        let response = http
+            .post(
+                api_url("/attachments/refresh-urls"),
+                json!({ "attachment_urls": attachment_urls }),
+            )
+            .await?;
+        let urls = serde_json::from_value(response["refreshed_urls"].clone())?;
+        Ok(urls)
+    }
+
+    /// Rings recipients of a DM or group DM call (all of them with `None`). (`POST /channels/{channel.id}/call/ring`). SEE: <https://docs.discord.food/resources/voice#ring-channel-recipients>
+    pub async fn ring_call(
+        &self,
+        http: &HttpClient,
+        channel_id: impl AsRef<str>,
+        recipients: Option<Vec<String>>,
+    ) -> Result<()> {
+        http.post(
+            api_url(&format!("/channels/{}/call/ring", channel_id.as_ref())),
+            json!({ "recipients": recipients }),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Stops ringing recipients of a DM or group DM call (the current user with `None`). (`POST /channels/{channel.id}/call/stop-ringing`). SEE: <https://docs.discord.food/resources/voice#stop-ringing-channel-recipients>
+    pub async fn stop_ringing_call(
+        &self,
+        http: &HttpClient,
+        channel_id: impl AsRef<str>,
+        recipients: Option<Vec<String>>,
+    ) -> Result<()> {
+        http.post(
+            api_url(&format!(
+                "/channels/{}/call/stop-ringing",
+                channel_id.as_ref()
+            )),
+            json!({ "recipients": recipients }),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Joins (or starts) the call of a DM or group DM with a voice state update (gateway op 4)
+    ///
+    /// Starting a call does not ring the recipients; follow with `ring_call`.
+    pub fn join_call(&self, gateway: &GatewaySender, channel_id: impl AsRef<str>) -> Result<()> {
+        gateway.update_voice_state(None, Some(channel_id.as_ref()), false, false)
+    }
+
+    /// Leaves the DM or group DM call the current user is connected to
+    pub fn leave_call(&self, gateway: &GatewaySender) -> Result<()> {
+        gateway.update_voice_state(None, None, false, false)
+    }
+
+    /// Declines an incoming call, stopping the ringing for the current user
+    pub async fn decline_call(&self, http: &HttpClient, channel_id: impl AsRef<str>) -> Result<()> {
+        self.stop_ringing_call(http, channel_id, None).await
+    }
+}
+
+/// Manager for application command and interaction endpoints.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InteractionsManager;
+
+impl InteractionsManager {
+    /// Fetches the application commands usable in a channel. (`GET /channels/{channel.id}/application-command-index`). SEE: <https://docs.discord.food/interactions/application-commands#get-channel-application-command-index>
+    pub async fn channel_command_index(
+        &self,
+        http: &HttpClient,
+        channel_id: impl AsRef<str>,
+    ) -> Result<ApplicationCommandIndex> {
+        let response = http
+            .get(api_url(&format!(
+                "/channels/{}/application-command-index",
+                channel_id.as_ref()
+            )))
+            .await?;
+        let index = serde_json::from_value(response)?;
+        Ok(index)
+    }
+
+    /// Fetches the application commands usable in a guild. (`GET /guilds/{guild.id}/application-command-index`). SEE: <https://docs.discord.food/interactions/application-commands#get-guild-application-command-index>
+    pub async fn guild_command_index(
+        &self,
+        http: &HttpClient,
+        guild_id: impl AsRef<str>,
+    ) -> Result<ApplicationCommandIndex> {
+        let response = http
+            .get(api_url(&format!(
+                "/guilds/{}/application-command-index",
+                guild_id.as_ref()
+            )))
+            .await?;
+        let index = serde_json::from_value(response)?;
+        Ok(index)
+    }
+
+    /// Fetches the commands of user-installed applications. (`GET /users/@me/application-command-index`). SEE: <https://docs.discord.food/interactions/application-commands#get-user-application-command-index>
+    pub async fn user_command_index(&self, http: &HttpClient) -> Result<ApplicationCommandIndex> {
+        let response = http
+            .get(api_url("/users/@me/application-command-index"))
+            .await?;
+        let index = serde_json::from_value(response)?;
+        Ok(index)
+    }
+
+    /// Invokes an application command in a channel. (`POST /interactions`). SEE: <https://docs.discord.food/interactions/receiving-and-responding#create-interaction>
+    ///
+    /// `session_id` must be the current gateway session (see `Context::session_id`).
+    /// Returns the nonce sent with the interaction, which is echoed back in
+    /// `INTERACTION_CREATE` / `INTERACTION_SUCCESS` / `INTERACTION_FAILURE` events.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let index = ctx.interactions.channel_command_index(&ctx.http, channel_id).await?;
+    /// if let Some(command) = index.find("ping", None) {
+    ///     let session_id = ctx.session_id().unwrap_or_default();
+    ///     ctx.interactions
+    ///         .invoke_command(&ctx.http, session_id, channel_id, guild_id, command, vec![])
+    ///         .await?;
+    /// }
+    /// ```
+    pub async fn invoke_command(
+        &self,
+        http: &HttpClient,
+        session_id: impl AsRef<str>,
+        channel_id: impl AsRef<str>,
+        guild_id: Option<&str>,
+        command: &ApplicationCommand,
+        options: Vec<ApplicationCommandInteractionOption>,
+    ) -> Result<String> {
+        let nonce = generate_nonce();
+        let mut body = json!({
+            "type": 2,
+            "application_id": command.application_id,
+            "channel_id": channel_id.as_ref(),
+            "session_id": session_id.as_ref(),
+            "data": command.interaction_data(options),
+            "nonce": nonce,
+            "analytics_location": "slash_ui",
+        });
+        if let Some(guild_id) = guild_id {
+            body["guild_id"] = json!(guild_id);
+        }
+        http.post(api_url("/interactions"), body).await?;
+        Ok(nonce)
+    }
+}
+
+/// Appends the `before` and `limit` query parameters of the archived thread endpoints.
+fn archived_threads_url(mut url: String, before: Option<&str>, limit: Option<u8>) -> String {
+    let mut query = Vec::new();
+    if let Some(before) = before {
+        query.push(format!("before={}", urlencoding::encode(before)));
+    }
+    if let Some(limit) = limit {
+        query.push(format!("limit={}", limit));
+    }
+    if !query.is_empty() {
+        url.push('?');
+        url.push_str(&query.join("&"));
+    }
+    url
+}
+
+/// Manager for invite endpoints.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InvitesManager;
+
+impl InvitesManager {
+    /// Resolves an invite code, with approximate member counts and expiration. (`GET /invites/{invite.code}`). SEE: <https://docs.discord.food/resources/invite#get-invite>
+    pub async fn get(&self, http: &HttpClient, code: impl AsRef<str>) -> Result<Invite> {
+        let response = http
+            .get(api_url(&format!(
+                "/invites/{}?with_counts=true&with_expiration=true",
+                code.as_ref()
+            )))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Accepts an invite, joining its guild or group DM, or sending a friend request. (`POST /invites/{invite.code}`). SEE: <https://docs.discord.food/resources/invite#accept-invite>
+    pub async fn accept(&self, http: &HttpClient, code: impl AsRef<str>) -> Result<Invite> {
+        let response = http
+            .post(api_url(&format!("/invites/{}", code.as_ref())), json!({}))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Deletes an invite. (`DELETE /invites/{invite.code}`). SEE: <https://docs.discord.food/resources/invite#delete-invite>
+    pub async fn delete(&self, http: &HttpClient, code: impl AsRef<str>) -> Result<Invite> {
+        let response = http
+            .delete(api_url(&format!("/invites/{}", code.as_ref())))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Fetches the invites of a channel or group DM, with their metadata. (`GET /channels/{channel.id}/invites`). SEE: <https://docs.discord.food/resources/invite#get-channel-invites>
+    pub async fn channel_invites(
+        &self,
+        http: &HttpClient,
+        channel_id: impl AsRef<str>,
+    ) -> Result<Vec<Invite>> {
+        let response = http
+            .get(api_url(&format!(
+                "/channels/{}/invites",
+                channel_id.as_ref()
+            )))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Fetches the invites of a guild, with their metadata. (`GET /guilds/{guild.id}/invites`). SEE: <https://docs.discord.food/resources/invite#get-guild-invites>
+    pub async fn guild_invites(
+        &self,
+        http: &HttpClient,
+        guild_id: impl AsRef<str>,
+    ) -> Result<Vec<Invite>> {
+        let response = http
+            .get(api_url(&format!("/guilds/{}/invites", guild_id.as_ref())))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Creates an invite to a channel or group DM. (`POST /channels/{channel.id}/invites`). SEE: <https://docs.discord.food/resources/invite#create-channel-invite>
+    /// # Params
+    /// - max_age?: Seconds before the invite expires (0 for never, default 86400)
+    /// - max_uses?: Max number of uses (0 for unlimited, default 0)
+    /// - temporary?: Whether the invite only grants temporary membership
+    /// - unique?: Whether to always create a new invite instead of reusing a similar one
+    pub async fn create(
+        &self,
+        http: &HttpClient,
+        channel_id: impl AsRef<str>,
+        options: Value,
+    ) -> Result<Invite> {
+        let response = http
+            .post(
+                api_url(&format!("/channels/{}/invites", channel_id.as_ref())),
+                options,
+            )
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Creates an invite launching an activity in a voice channel. (`POST /channels/{channel.id}/invites`). SEE: <https://docs.discord.food/resources/invite#create-channel-invite>
+    ///
+    /// Opening the invite joins the voice channel and starts the activity (see
+    /// `EmbeddedApplication` for well-known application IDs). `max_age` is in seconds
+    /// (0 for never).
+    pub async fn create_activity_invite(
+        &self,
+        http: &HttpClient,
+        channel_id: impl AsRef<str>,
+        application_id: impl AsRef<str>,
+        max_age: u32,
+    ) -> Result<Invite> {
+        self.create(
+            http,
+            channel_id,
+            json!({
+                "target_type": 2,
+                "target_application_id": application_id.as_ref(),
+                "max_age": max_age,
+            }),
+        )
+        .await
+    }
+}
+
+/// Manager for connected accounts (Spotify, Steam, GitHub, ...).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConnectionsManager;
+
+impl ConnectionsManager {
+    /// Fetches the current user's connections. (`GET /users/@me/connections`). SEE: <https://docs.discord.food/resources/connected-accounts#get-user-connections>
+    pub async fn list(&self, http: &HttpClient) -> Result<Vec<Connection>> {
+        let response = http.get(api_url("/users/@me/connections")).await?;
+        let connections = serde_json::from_value(response)?;
+        Ok(connections)
+    }
+
+    /// Modifies a connection. (`PATCH /users/@me/connections/{connection.type}/{connection.id}`). SEE: <https://docs.discord.food/resources/connected-accounts#modify-user-connection>
+    pub async fn edit(
+        &self,
+        http: &HttpClient,
+        kind: &ConnectionType,
+        connection_id: impl AsRef<str>,
+        data: impl serde::Serialize,
+    ) -> Result<Connection> {
+        let response = http
+            .patch(
+                api_url(&format!(
+                    "/users/@me/connections/{}/{}",
+                    kind,
+                    connection_id.as_ref()
+                )),
+                data,
+            )
+            .await?;
+        let connection = serde_json::from_value(response)?;
+        Ok(connection)
+    }
+
+    /// Shows or hides a connection on the current user's profile.
+    pub async fn set_visibility(
+        &self,
+        http: &HttpClient,
+        kind: &ConnectionType,
+        connection_id: impl AsRef<str>,
+        visible: bool,
+    ) -> Result<Connection> {
+        self.edit(
+            http,
+            kind,
+            connection_id,
+            json!({ "visibility": u8::from(visible) }),
+        )
+        .await
+    }
+
+    /// Removes a connection. (`DELETE /users/@me/connections/{connection.type}/{connection.id}`). SEE: <https://docs.discord.food/resources/connected-accounts#delete-user-connection>
+    pub async fn delete(
+        &self,
+        http: &HttpClient,
+        kind: &ConnectionType,
+        connection_id: impl AsRef<str>,
+    ) -> Result<()> {
+        http.delete(api_url(&format!(
+            "/users/@me/connections/{}/{}",
+            kind,
+            connection_id.as_ref()
+        )))
+        .await?;
+        Ok(())
+    }
+
+    /// Fetches the connections another user shows on their profile. (`GET /users/{user.id}/profile`)
+    pub async fn user_connections(
+        &self,
+        http: &HttpClient,
+        user_id: impl AsRef<str>,
+    ) -> Result<Vec<Connection>> {
+        let profile = UsersManager.get_profile(http, user_id).await?;
+        Ok(profile.connected_accounts)
+    }
+}
+
+/// Manager for poll voting endpoints.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PollsManager;
+
+impl PollsManager {
+    /// Votes for answers of a poll, replacing previous votes. (`PUT /channels/{channel.id}/polls/{message.id}/answers/@me`). SEE: <https://docs.discord.food/resources/poll#create-poll-vote>
+    pub async fn vote(
+        &self,
+        http: &HttpClient,
+        channel_id: impl AsRef<str>,
+        message_id: impl AsRef<str>,
+        answer_ids: Vec<u32>,
+    ) -> Result<()> {
+        let answer_ids: Vec<String> = answer_ids.iter().map(|id| id.to_string()).collect();
+        http.put(
+            api_url(&format!(
+                "/channels/{}/polls/{}/answers/@me",
+                channel_id.as_ref(),
+                message_id.as_ref()
+            )),
+            json!({ "answer_ids": answer_ids }),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Removes the current user's votes from a poll.
+    pub async fn remove_vote(
+        &self,
+        http: &HttpClient,
+        channel_id: impl AsRef<str>,
+        message_id: impl AsRef<str>,
+    ) -> Result<()> {
+        self.vote(http, channel_id, message_id, Vec::new()).await
+    }
+
+    /// Fetches users who voted for an answer. (`GET /channels/{channel.id}/polls/{message.id}/answers/{answer.id}`). SEE: <https://docs.discord.food/resources/poll#get-answer-voters>
+    /// # Params
+    /// - after?: Snowflake - Get users after this user ID
+    /// - limit?: number - Max number of users to return (1-100, default 25)
+    pub async fn voters(
+        &self,
+        http: &HttpClient,
+        channel_id: impl AsRef<str>,
+        message_id: impl AsRef<str>,
+        answer_id: u32,
+        after: Option<&str>,
+        limit: Option<u8>,
+    ) -> Result<Vec<User>> {
+        let mut url = api_url(&format!(
+            "/channels/{}/polls/{}/answers/{}",
+            channel_id.as_ref(),
+            message_id.as_ref(),
+            answer_id
+        ));
+        let mut query_params = Vec::new();
+        if let Some(after) = after {
+            query_params.push(format!("after={after}"));
+        }
+        if let Some(limit) = limit {
+            query_params.push(format!("limit={limit}"));
+        }
+        if !query_params.is_empty() {
+            url.push('?');
+            url.push_str(&query_params.join("&"));
+        }
+
+        let response = http.get(url).await?;
+        let users = response
+            .get("users")
+            .cloned()
+            .unwrap_or(Value::Array(vec![]));
+        Ok(serde_json::from_value(users)?)
+    }
+
+    /// Immediately ends a poll created by the current user. (`POST /channels/{channel.id}/polls/{message.id}/expire`). SEE: <https://docs.discord.food/resources/poll#expire-poll>
+    pub async fn expire(
+        &self,
+        http: &HttpClient,
+        channel_id: impl AsRef<str>,
+        message_id: impl AsRef<str>,
+    ) -> Result<Message> {
+        let response = http
+            .post(
+                api_url(&format!(
+                    "/channels/{}/polls/{}/expire",
+                    channel_id.as_ref(),
+                    message_id.as_ref()
+                )),
+                json!({}),
+            )
+            .await?;
+        let message = serde_json::from_value(response)?;
+        Ok(message)
+    }
+}
+
+/// Manager for message search endpoints.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SearchManager;
+
+/// Filters of a message search, built with chained setters.
+///
+/// # Example
+/// ```ignore
+/// let params = SearchParams::new()
+///     .author_id(user_id)
+///     .has(SearchHas::Image)
+///     .content("screenshot");
+/// let results = ctx.search.guild_messages(&ctx.http, guild_id, &params).await?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SearchParams {
+    content: Option<String>,
+    author_ids: Vec<String>,
+    mentions: Vec<String>,
+    has: Vec<SearchHas>,
+    channel_ids: Vec<String>,
+    before: Option<String>,
+    after: Option<String>,
+    pinned: Option<bool>,
+    include_nsfw: Option<bool>,
+    oldest_first: bool,
+    limit: Option<u8>,
+    offset: u32,
+}
+
+impl SearchParams {
+    /// Creates a search matching every message
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only messages containing this text
+    pub fn content(mut self, content: impl Into<String>) -> Self {
+        self.content = Some(content.into());
+        self
+    }
+
+    /// Only messages sent by this user (can be repeated)
+    pub fn author_id(mut self, user_id: impl Into<String>) -> Self {
+        self.author_ids.push(user_id.into());
+        self
+    }
+
+    /// Only messages mentioning this user (can be repeated)
+    pub fn mentions(mut self, user_id: impl Into<String>) -> Self {
+        self.mentions.push(user_id.into());
+        self
+    }
+
+    /// Only messages containing this type of content (can be repeated)
+    pub fn has(mut self, has: SearchHas) -> Self {
+        self.has.push(has);
+        self
+    }
+
+    /// Only messages sent in this channel (guild searches, can be repeated)
+    pub fn channel_id(mut self, channel_id: impl Into<String>) -> Self {
+        self.channel_ids.push(channel_id.into());
+        self
+    }
+
+    /// Only messages older than this message ID
+    pub fn before(mut self, message_id: impl Into<String>) -> Self {
+        self.before = Some(message_id.into());
+        self
+    }
+
+    /// Only messages newer than this message ID
+    pub fn after(mut self, message_id: impl Into<String>) -> Self {
+        self.after = Some(message_id.into());
+        self
+    }
+
+    /// Only pinned (or unpinned) messages
+    pub fn pinned(mut self, pinned: bool) -> Self {
+        self.pinned = Some(pinned);
+        self
+    }
+
+    /// Whether to include messages of NSFW channels
+    pub fn include_nsfw(mut self, include_nsfw: bool) -> Self {
+        self.include_nsfw = Some(include_nsfw);
+        self
+    }
+
+    /// Returns the oldest messages first instead of the newest
+    pub fn oldest_first(mut self) -> Self {
+        self.oldest_first = true;
+        self
+    }
+
+    /// Sets the page size (1-25, default 25)
+    pub fn limit(mut self, limit: u8) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Sets the number of results to skip (max 9975)
+    pub fn offset(mut self, offset: u32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Returns the parameters of the page following `results`, if any
+    pub fn next_page(&self, results: &SearchResults) -> Option<Self> {
+        let fetched = results.messages.len() as u32;
+        let offset = self.offset + fetched;
+        if fetched == 0 || offset as u64 >= results.total_results {
+            return None;
+        }
+        Some(self.clone().offset(offset))
+    }
+
+    fn query(&self) -> String {
+        let mut query_params = Vec::new();
+        if let Some(content) = &self.content {
+            query_params.push(format!("content={}", urlencoding::encode(content)));
+        }
+        for author_id in &self.author_ids {
+            query_params.push(format!("author_id={author_id}"));
+        }
+        for mention in &self.mentions {
+            query_params.push(format!("mentions={mention}"));
+        }
+        for has in &self.has {
+            query_params.push(format!("has={}", has.as_str()));
+        }
+        for channel_id in &self.channel_ids {
+            query_params.push(format!("channel_id={channel_id}"));
+        }
+        if let Some(before) = &self.before {
+            query_params.push(format!("max_id={before}"));
+        }
+        if let Some(after) = &self.after {
+            query_params.push(format!("min_id={after}"));
+        }
+        if let Some(pinned) = self.pinned {
+            query_params.push(format!("pinned={pinned}"));
+        }
+        if let Some(include_nsfw) = self.include_nsfw {
+            query_params.push(format!("include_nsfw={include_nsfw}"));
+        }
+        if self.oldest_first {
+            query_params.push("sort_order=asc".to_string());
+        }
+        if let Some(limit) = self.limit {
+            query_params.push(format!("limit={limit}"));
+        }
+        if self.offset > 0 {
+            query_params.push(format!("offset={}", self.offset));
+        }
+        query_params.join("&")
+    }
+}
+
+impl SearchManager {
+    /// Searches messages in a guild. (`GET /guilds/{guild.id}/messages/search`). SEE: <https://docs.discord.food/resources/message#search-messages>
+    pub async fn guild_messages(
+        &self,
+        http: &HttpClient,
+        guild_id: impl AsRef<str>,
+        params: &SearchParams,
+    ) -> Result<SearchResults> {
+        let url = api_url(&format!("/guilds/{}/messages/search", guild_id.as_ref()));
+        self.search(http, url, params).await
+    }
+
+    /// Searches messages in a channel or DM. (`GET /channels/{channel.id}/messages/search`). SEE: <https://docs.discord.food/resources/message#search-messages>
+    pub async fn channel_messages(
+        &self,
+        http: &HttpClient,
+        channel_id: impl AsRef<str>,
+        params: &SearchParams,
+    ) -> Result<SearchResults> {
+        let url = api_url(&format!(
+            "/channels/{}/messages/search",
+            channel_id.as_ref()
+        ));
+        self.search(http, url, params).await
+    }
+
+    async fn search(
+        &self,
+        http: &HttpClient,
+        mut url: String,
+        params: &SearchParams,
+    ) -> Result<SearchResults> {
+        let query = params.query();
+        if !query.is_empty() {
+            url.push('?');
+            url.push_str(&query);
+        }
+        let response = http.get(url).await?;
+        let results = serde_json::from_value(response)?;
+        Ok(results)
+    }
+}
+
+/// Manager for the read-only premium (Nitro) endpoints and guild boosts.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PremiumManager;
+
+impl PremiumManager {
+    /// Fetches the current user's subscriptions (Nitro, boosts, applications). (`GET /users/@me/billing/subscriptions`). SEE: <https://docs.discord.food/resources/subscription#get-user-subscriptions>
+    pub async fn subscriptions(&self, http: &HttpClient) -> Result<Vec<Subscription>> {
+        let response = http
+            .get(api_url("/users/@me/billing/subscriptions"))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Fetches the current user's entitlements. (`GET /users/@me/entitlements`). SEE: <https://docs.discord.food/resources/entitlement#get-user-entitlements>
+    pub async fn entitlements(&self, http: &HttpClient) -> Result<Vec<Entitlement>> {
+        let response = http.get(api_url("/users/@me/entitlements")).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Fetches the gifts bought by the current user that were not redeemed yet. (`GET /users/@me/entitlements/gifts`). SEE: <https://docs.discord.food/resources/entitlement#get-user-gift-entitlements>
+    pub async fn gift_inventory(&self, http: &HttpClient) -> Result<Vec<Entitlement>> {
+        let response = http.get(api_url("/users/@me/entitlements/gifts")).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Fetches the super reaction credits of the current user. (`GET /users/@me/burst-credits`). SEE: <https://docs.discord.food/resources/user#get-user-burst-credits>
+    pub async fn burst_credits(&self, http: &HttpClient) -> Result<BurstCredits> {
+        let response = http.get(api_url("/users/@me/burst-credits")).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Fetches how much the current user used the Nitro perks. (`GET /users/@me/premium-usage`). SEE: <https://docs.discord.food/resources/user#get-user-premium-usage>
+    pub async fn usage(&self, http: &HttpClient) -> Result<PremiumUsage> {
+        let response = http.get(api_url("/users/@me/premium-usage")).await?;
+        // The counters are wrapped in `premium_usage`
+        let usage = response.get("premium_usage").cloned().unwrap_or(response);
+        Ok(serde_json::from_value(usage)?)
+    }
+
+    /// Fetches the current user's boost slots. (`GET /users/@me/guilds/premium/subscription-slots`). SEE: <https://docs.discord.food/resources/premium-referral#get-user-premium-guild-subscription-slots>
+    pub async fn boost_slots(&self, http: &HttpClient) -> Result<Vec<BoostSlot>> {
+        let response = http
+            .get(api_url("/users/@me/guilds/premium/subscription-slots"))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Boosts a guild with the first available boost slot. (`PUT /guilds/{guild.id}/premium/subscriptions`). SEE: <https://docs.discord.food/resources/guild#add-guild-premium-subscriptions>
+    ///
+    /// Fails with `Error::InvalidArgument` if every slot is used or in cooldown.
+    pub async fn use_boost_slot(
+        &self,
+        http: &HttpClient,
+        guild_id: impl AsRef<str>,
+    ) -> Result<GuildBoost> {
+        let slot = self
+            .boost_slots(http)
+            .await?
+            .into_iter()
+            .find(BoostSlot::is_available)
+            .ok_or_else(|| Error::InvalidArgument("no boost slot available".to_string()))?;
+        let response = http
+            .put(
+                api_url(&format!(
+                    "/guilds/{}/premium/subscriptions",
+                    guild_id.as_ref()
+                )),
+                json!({ "user_premium_guild_subscription_slot_ids": [slot.id] }),
+            )
+            .await?;
+        let boosts: Vec<GuildBoost> = serde_json::from_value(response)?;
+        boosts.into_iter().next().ok_or(Error::InvalidPayload)
+    }
+}
+
+/// Manager for gift code endpoints.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GiftsManager;
+
+impl GiftsManager {
+    /// Resolves a gift code, with its subscription plan. (`GET /entitlements/gift-codes/{code}`). SEE: <https://docs.discord.food/resources/entitlement#get-gift-code>
+    ///
+    /// Accepts a bare code or a `discord.gift` link.
+    pub async fn resolve(&self, http: &HttpClient, code: impl AsRef<str>) -> Result<GiftCode> {
+        let response = http
+            .get(api_url(&format!(
+                "/entitlements/gift-codes/{}?with_application=false&with_subscription_plan=true",
+                gift_code(code.as_ref())
+            )))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Redeems a gift code, returning the granted entitlement. (`POST /entitlements/gift-codes/{code}/redeem`). SEE: <https://docs.discord.food/resources/entitlement#redeem-gift-code>
+    ///
+    /// `channel_id` is the channel the gift was sent in, if any.
+    pub async fn redeem(
+        &self,
+        http: &HttpClient,
+        code: impl AsRef<str>,
+        channel_id: Option<&str>,
+    ) -> Result<Entitlement> {
+        let response = http
+            .post(
+                api_url(&format!(
+                    "/entitlements/gift-codes/{}/redeem",
+                    gift_code(code.as_ref())
+                )),
+                json!({ "channel_id": channel_id }),
+            )
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Fetches the gift codes the current user created for a SKU. (`GET /users/@me/entitlements/gift-codes`). SEE: <https://docs.discord.food/resources/entitlement#get-user-gift-codes>
+    pub async fn list(
+        &self,
+        http: &HttpClient,
+        sku_id: impl AsRef<str>,
+        subscription_plan_id: Option<&str>,
+    ) -> Result<Vec<GiftCode>> {
+        let mut url = api_url(&format!(
+            "/users/@me/entitlements/gift-codes?sku_id={}",
+            sku_id.as_ref()
+        ));
+        if let Some(plan_id) = subscription_plan_id {
+            url.push_str(&format!("&subscription_plan_id={}", plan_id));
+        }
+        let response = http.get(url).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Creates a gift link from a gift the current user owns (see `PremiumManager::gift_inventory`). (`POST /users/@me/entitlements/gift-codes`). SEE: <https://docs.discord.food/resources/entitlement#create-user-gift-code>
+    pub async fn create(
+        &self,
+        http: &HttpClient,
+        sku_id: impl AsRef<str>,
+        subscription_plan_id: Option<&str>,
+    ) -> Result<GiftCode> {
+        let response = http
+            .post(
+                api_url("/users/@me/entitlements/gift-codes"),
+                json!({
+                    "sku_id": sku_id.as_ref(),
+                    "subscription_plan_id": subscription_plan_id,
+                }),
+            )
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Revokes a gift code created by the current user. (`DELETE /users/@me/entitlements/gift-codes/{code}`). SEE: <https://docs.discord.food/resources/entitlement#revoke-user-gift-code>
+    pub async fn revoke(&self, http: &HttpClient, code: impl AsRef<str>) -> Result<()> {
+        http.delete(api_url(&format!(
+            "/users/@me/entitlements/gift-codes/{}",
+            gift_code(code.as_ref())
+        )))
+        .await?;
+        Ok(())
+    }
+}
+
+/// Returns the guild an invite is for
+fn invite_guild_id(invite: Invite) -> Result<String> {
+    invite
+        .guild_id
+        .or(invite.guild.map(|guild| guild.id))
+        .ok_or_else(|| Error::InvalidArgument("the invite is not for a guild".to_string()))
+}
+
+/// Extracts the code of a `discord.gift` link, or returns the input as is
+fn gift_code(code: &str) -> String {
+    GiftCode::codes_in(code)
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| code.to_string())
+}
+
+/// Manager for the OAuth2 authorization of applications by the current user.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OAuthManager;
+
+impl OAuthManager {
+    /// Fetches what the authorize page shows: application, bot and the guilds it can be added to. (`GET /oauth2/authorize`). SEE: <https://docs.discord.food/topics/oauth2#get-current-authorization-information>
+    pub async fn authorize_info(
+        &self,
+        http: &HttpClient,
+        request: &AuthorizeRequest,
+    ) -> Result<AuthorizeInfo> {
+        let response = http
+            .get(api_url(&format!("/oauth2/authorize?{}", request.query())))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Authorizes an application, returning the URL to redirect to. (`POST /oauth2/authorize`). SEE: <https://docs.discord.food/topics/oauth2#authorize>
+    ///
+    /// For the `bot` scope, the bot is added to the guild of the request.
+    pub async fn authorize(&self, http: &HttpClient, request: &AuthorizeRequest) -> Result<String> {
+        let response = http
+            .post(
+                api_url(&format!("/oauth2/authorize?{}", request.query())),
+                request.body(),
+            )
+            .await?;
+        Ok(response["location"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string())
+    }
+
+    /// Adds a bot to a guild, with its application commands. Requires `MANAGE_GUILD` in the guild.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use diself::model::Permissions;
+    ///
+    /// ctx.oauth
+    ///     .add_bot(&ctx.http, "1234", "5678", Permissions::SEND_MESSAGES | Permissions::EMBED_LINKS)
+    ///     .await?;
+    /// ```
+    pub async fn add_bot(
+        &self,
+        http: &HttpClient,
+        client_id: impl Into<String>,
+        guild_id: impl Into<String>,
+        permissions: Permissions,
+    ) -> Result<String> {
+        let request = AuthorizeRequest::bot(client_id, permissions).guild_id(guild_id);
+        self.authorize(http, &request).await
+    }
+}
+
+/// Manager for sticker endpoints.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StickersManager;
+
+impl StickersManager {
+    /// Fetches a sticker by ID (`GET /stickers/{sticker_id}`). SEE: <https://docs.discord.food/resources/sticker#get-sticker>
+    pub async fn get(&self, http: &HttpClient, sticker_id: impl AsRef<str>) -> Result<Sticker> {
+        let response = http
+            .get(api_url(&format!("/stickers/{}", sticker_id.as_ref())))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Lists the packs of standard stickers (`GET /sticker-packs`). SEE: <https://docs.discord.food/resources/sticker#get-sticker-packs>
+    pub async fn packs(&self, http: &HttpClient) -> Result<Vec<StickerPack>> {
+        let response = http.get(api_url("/sticker-packs")).await?;
+        Ok(serde_json::from_value(response["sticker_packs"].clone())?)
+    }
+
+    /// Fetches a pack of standard stickers (`GET /sticker-packs/{pack_id}`). SEE: <https://docs.discord.food/resources/sticker#get-sticker-pack>
+    pub async fn pack(&self, http: &HttpClient, pack_id: impl AsRef<str>) -> Result<StickerPack> {
+        let response = http
+            .get(api_url(&format!("/sticker-packs/{}", pack_id.as_ref())))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Lists the stickers of a guild (`GET /guilds/{guild_id}/stickers`). SEE: <https://docs.discord.food/resources/sticker#list-guild-stickers>
+    pub async fn guild_stickers(
+        &self,
+        http: &HttpClient,
+        guild_id: impl AsRef<str>,
+    ) -> Result<Vec<Sticker>> {
+        let response = http
+            .get(api_url(&format!("/guilds/{}/stickers", guild_id.as_ref())))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+}
+
+/// Sends a `PATCH /users/@me` that requires the account password
+async fn modify_account(http: &HttpClient, body: Value) -> Result<Value> {
+    if body["password"].as_str().unwrap_or_default().is_empty() {
+        return Err(Error::InvalidArgument(
+            "the account password is required".to_string(),
+        ));
+    }
+    http.patch(api_url("/users/@me"), body).await
 }
@@ -1,20 +1,42 @@
+mod block_list;
+mod broadcast;
+mod bulk;
 mod builder;
 mod client;
 mod collectors;
 mod context;
+mod data;
+mod dispatch;
 mod event_handler;
 mod events;
 mod managers;
+mod scheduler;
+mod voice_state;
 
+pub use block_list::{
+    BlockList, BlockListEntry, BlockListImport, BLOCK_LIST_VERSION, DEFAULT_IMPORT_INTERVAL,
+};
+pub use broadcast::{BroadcastReport, DEFAULT_BROADCAST_INTERVAL};
 pub use builder::ClientBuilder;
+pub use bulk::BulkReport;
 pub use client::Client;
 pub use collectors::{
-    CollectorHub, CollectorOptions, MessageCollector, ReactionCollectEvent, ReactionCollector,
-    ReactionEventType,
+    CollectorHub, CollectorLagHandler, CollectorOptions, CollectorStats, DeleteCollectEvent,
+    DeleteCollector, EditCollector, MessageCollector, ReactionCollectEvent, ReactionCollector,
+    ReactionEventType, TypingCollector, DEFAULT_COLLECTOR_CAPACITY,
 };
 pub use context::Context;
+pub use data::{TypeMap, TypeMapKey};
+pub use dispatch::DispatchMode;
 pub use event_handler::EventHandler;
 pub use events::{DispatchEvent, DispatchEventType};
 pub use managers::{
-    ChannelsManager, GuildsManager, RelationshipsManager, SearchThreadsParams, UsersManager,
+    AuditLogParams, BanOptions, BulkAckEntry, ChannelsManager, ConnectionsManager, GiftsManager,
+    GuildsManager, InteractionsManager, InvitesManager, OAuthManager, PollsManager, PremiumManager,
+    RelationshipsManager, SearchManager, SearchParams, SearchThreadsParams, StickersManager,
+    UsersManager,
+};
+pub use scheduler::{
+    MessageScheduler, ScheduledMessage, MIN_RECURRING_INTERVAL, SCHEDULE_FILE_VERSION,
 };
+pub use voice_state::VoiceStateController;
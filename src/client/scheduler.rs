@@ -0,0 +1,308 @@
+use crate::error::{Error, Result};
+use crate::http::HttpClient;
+use crate::model::CreateMessage;
+use dashmap::DashMap;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::{watch, Notify};
+
+/// Format version written by `MessageScheduler::save`
+pub const SCHEDULE_FILE_VERSION: u32 = 1;
+
+/// Shortest interval accepted by `MessageScheduler::schedule_recurring`
+pub const MIN_RECURRING_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A message waiting to be sent by a `MessageScheduler`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledMessage {
+    /// ID of the schedule, used to cancel it
+    pub id: u64,
+
+    /// Channel the message is sent to
+    pub channel_id: String,
+
+    /// Body of the message (a serialized `CreateMessage`)
+    pub message: Value,
+
+    /// When the message is next sent, in milliseconds since the Unix epoch
+    pub send_at_ms: u64,
+
+    /// Interval between two sends (recurring schedules only)
+    pub every: Option<Duration>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ScheduleFile {
+    version: u32,
+    schedules: Vec<ScheduledMessage>,
+}
+
+struct Entry {
+    schedule: ScheduledMessage,
+    cancel: Arc<Notify>,
+}
+
+/// Sends messages at a given time or at a fixed interval
+///
+/// Every schedule runs in its own task until it is sent (or cancelled, for recurring
+/// ones). Failed sends are logged and not retried. Schedules live in memory: use
+/// `save` and `load` to keep them across restarts.
+///
+/// The scheduler of a `Client` (`Client::scheduler`, `ctx.scheduler`) is shared by every
+/// run of the client: nothing is sent while the client is stopped, and pending schedules
+/// go on when it starts again. Tasks end once every handle to the scheduler is dropped.
+///
+/// # Example
+/// ```ignore
+/// use diself::prelude::*;
+/// use std::time::{Duration, Instant};
+///
+/// async fn example(ctx: &Context) -> diself::Result<()> {
+///     ctx.schedule_message(
+///         "123",
+///         CreateMessage::new().content("Meeting in 5 minutes"),
+///         Instant::now() + Duration::from_secs(25 * 60),
+///     )?;
+///     let daily = ctx.schedule_recurring(
+///         "123",
+///         CreateMessage::new().content("Daily reminder"),
+///         Duration::from_secs(24 * 60 * 60),
+///     )?;
+///
+///     // Later
+///     ctx.scheduler.cancel(daily);
+///     ctx.scheduler.save("schedules.json").await?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct MessageScheduler {
+    http: Arc<RwLock<HttpClient>>,
+    schedules: Arc<DashMap<u64, Entry>>,
+    next_id: Arc<AtomicU64>,
+    running: Arc<watch::Sender<bool>>,
+}
+
+impl MessageScheduler {
+    /// Creates a scheduler sending messages with `http`
+    pub fn new(http: HttpClient) -> Self {
+        Self {
+            http: Arc::new(RwLock::new(http)),
+            schedules: Arc::new(DashMap::new()),
+            next_id: Arc::new(AtomicU64::new(1)),
+            running: Arc::new(watch::Sender::new(true)),
+        }
+    }
+
+    /// Creates a scheduler that sends nothing until `resume` is called
+    pub(crate) fn paused(http: HttpClient) -> Self {
+        let scheduler = Self::new(http);
+        scheduler.pause();
+        scheduler
+    }
+
+    /// Holds every schedule until `resume`; a message being sent is not interrupted
+    pub(crate) fn pause(&self) {
+        self.running.send_replace(false);
+    }
+
+    /// Sends messages with `http` from now on, including the ones due while paused
+    pub(crate) fn resume(&self, http: HttpClient) {
+        *self.http.write() = http;
+        self.running.send_replace(true);
+    }
+
+    /// Sends a message once, at `at`, and returns the ID of the schedule
+    ///
+    /// Fails if `message` can't be serialized.
+    pub fn schedule_message(
+        &self,
+        channel_id: impl Into<String>,
+        message: CreateMessage,
+        at: Instant,
+    ) -> Result<u64> {
+        let send_at_ms = now_ms() + at.saturating_duration_since(Instant::now()).as_millis() as u64;
+        self.schedule(channel_id.into(), message, send_at_ms, None)
+    }
+
+    /// Sends a message every `every`, starting one interval from now, and returns the
+    /// ID of the schedule
+    ///
+    /// Fails with `Error::InvalidArgument` if `every` is shorter than
+    /// `MIN_RECURRING_INTERVAL`, or if `message` can't be serialized.
+    pub fn schedule_recurring(
+        &self,
+        channel_id: impl Into<String>,
+        message: CreateMessage,
+        every: Duration,
+    ) -> Result<u64> {
+        check_interval(every)?;
+        let send_at_ms = now_ms() + every.as_millis() as u64;
+        self.schedule(channel_id.into(), message, send_at_ms, Some(every))
+    }
+
+    /// Cancels a schedule, returning `false` if it was already sent or cancelled
+    pub fn cancel(&self, id: u64) -> bool {
+        match self.schedules.remove(&id) {
+            Some((_, entry)) => {
+                entry.cancel.notify_one();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Cancels every pending schedule
+    pub fn cancel_all(&self) {
+        let ids: Vec<u64> = self.schedules.iter().map(|entry| *entry.key()).collect();
+        for id in ids {
+            self.cancel(id);
+        }
+    }
+
+    /// Returns the pending schedules, next to be sent first
+    pub fn scheduled(&self) -> Vec<ScheduledMessage> {
+        let mut schedules: Vec<ScheduledMessage> = self
+            .schedules
+            .iter()
+            .map(|entry| entry.schedule.clone())
+            .collect();
+        schedules.sort_by_key(|schedule| (schedule.send_at_ms, schedule.id));
+        schedules
+    }
+
+    /// Writes the pending schedules as JSON to a file
+    pub async fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let file = ScheduleFile {
+            version: SCHEDULE_FILE_VERSION,
+            schedules: self.scheduled(),
+        };
+        let json = serde_json::to_vec(&file)?;
+        tokio::fs::write(path, json).await?;
+        Ok(())
+    }
+
+    /// Schedules the messages of a file written with `save`, returning how many were loaded
+    ///
+    /// Loaded schedules get new IDs. Messages whose time passed while the bot was
+    /// offline are sent right away, once. Nothing is loaded if a recurring schedule
+    /// is shorter than `MIN_RECURRING_INTERVAL`.
+    pub async fn load(&self, path: impl AsRef<Path>) -> Result<usize> {
+        let bytes = tokio::fs::read(path).await?;
+        let file: ScheduleFile = serde_json::from_slice(&bytes)?;
+        if file.version != SCHEDULE_FILE_VERSION {
+            return Err(Error::UnsupportedVersion {
+                found: file.version,
+                supported: SCHEDULE_FILE_VERSION,
+            });
+        }
+        for every in file.schedules.iter().filter_map(|schedule| schedule.every) {
+            check_interval(every)?;
+        }
+        let count = file.schedules.len();
+        for schedule in file.schedules {
+            let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+            self.spawn(ScheduledMessage { id, ..schedule });
+        }
+        Ok(count)
+    }
+
+    fn schedule(
+        &self,
+        channel_id: String,
+        message: CreateMessage,
+        send_at_ms: u64,
+        every: Option<Duration>,
+    ) -> Result<u64> {
+        let message = serde_json::to_value(message)?;
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.spawn(ScheduledMessage {
+            id,
+            channel_id,
+            message,
+            send_at_ms,
+            every,
+        });
+        Ok(id)
+    }
+
+    fn spawn(&self, schedule: ScheduledMessage) {
+        let id = schedule.id;
+        let cancel = Arc::new(Notify::new());
+        self.schedules.insert(
+            id,
+            Entry {
+                schedule: schedule.clone(),
+                cancel: cancel.clone(),
+            },
+        );
+
+        let http = self.http.clone();
+        let schedules = self.schedules.clone();
+        // Only the receiver goes to the task, so it ends once the scheduler is dropped
+        let mut running = self.running.subscribe();
+        let mut schedule = schedule;
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    resumed = running.wait_for(|running| *running) => {
+                        if resumed.is_err() {
+                            return;
+                        }
+                    }
+                    _ = cancel.notified() => return,
+                }
+                let delay = Duration::from_millis(schedule.send_at_ms.saturating_sub(now_ms()));
+                tokio::select! {
+                    _ = tokio::time::sleep(delay) => {}
+                    paused = running.wait_for(|running| !*running) => match paused {
+                        Ok(_) => continue,
+                        Err(_) => return,
+                    },
+                    _ = cancel.notified() => return,
+                }
+
+                let url =
+                    crate::http::api_url(&format!("/channels/{}/messages", schedule.channel_id));
+                let http = http.read().clone();
+                if let Err(err) = http.post(&url, &schedule.message).await {
+                    tracing::warn!("Scheduled message {} failed to send: {}", id, err);
+                }
+
+                let Some(every) = schedule.every else {
+                    schedules.remove(&id);
+                    return;
+                };
+                let every_ms = every.as_millis() as u64;
+                schedule.send_at_ms = (schedule.send_at_ms + every_ms).max(now_ms());
+                match schedules.get_mut(&id) {
+                    Some(mut entry) => entry.schedule.send_at_ms = schedule.send_at_ms,
+                    None => return,
+                }
+            }
+        });
+    }
+}
+
+/// Rejects intervals that would send messages back to back
+fn check_interval(every: Duration) -> Result<()> {
+    if every < MIN_RECURRING_INTERVAL {
+        return Err(Error::InvalidArgument(format!(
+            "recurring interval must be at least {:?}, got {:?}",
+            MIN_RECURRING_INTERVAL, every
+        )));
+    }
+    Ok(())
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default()
+}
@@ -0,0 +1,78 @@
+use crate::error::{Error, Result};
+use crate::gateway::GatewaySender;
+use crate::model::EditVoiceState;
+
+/// Toggles of the voice state of the current user (mute, deafen, camera, Go Live)
+///
+/// Every toggle resends the full voice state of the channel the current user joined
+/// (with `Context::join_voice` or `GatewaySender::update_voice_state`), like the official
+/// clients do. Obtained with `ctx.voice`.
+///
+/// # Example
+/// ```ignore
+/// ctx.voice.set_video(true)?;
+/// ctx.voice.set_streaming(true)?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct VoiceStateController {
+    gateway: GatewaySender,
+    user_id: String,
+}
+
+impl VoiceStateController {
+    pub(crate) fn new(gateway: GatewaySender, user_id: impl Into<String>) -> Self {
+        Self {
+            gateway,
+            user_id: user_id.into(),
+        }
+    }
+
+    /// Returns the voice state of the current user (`None` when not in a voice channel)
+    pub fn current(&self) -> Option<EditVoiceState> {
+        self.gateway.voice_state()
+    }
+
+    /// Whether the current user is streaming with Go Live
+    pub fn is_streaming(&self) -> bool {
+        self.current().is_some_and(|state| state.is_streaming())
+    }
+
+    /// Mutes or unmutes the current user
+    pub fn set_mute(&self, mute: bool) -> Result<()> {
+        let state = self.connected()?;
+        self.gateway.set_voice_state(&state.self_mute(mute))
+    }
+
+    /// Deafens or undeafens the current user
+    pub fn set_deaf(&self, deaf: bool) -> Result<()> {
+        let state = self.connected()?;
+        self.gateway.set_voice_state(&state.self_deaf(deaf))
+    }
+
+    /// Enables or disables the camera of the current user
+    pub fn set_video(&self, video: bool) -> Result<()> {
+        let state = self.connected()?;
+        self.gateway.set_voice_state(&state.self_video(video))
+    }
+
+    /// Starts or ends a Go Live stream in the current voice channel
+    pub fn set_streaming(&self, streaming: bool) -> Result<()> {
+        let state = self.connected()?;
+        if state.is_streaming() == streaming {
+            return Ok(());
+        }
+        let channel_id = state.channel_id().unwrap_or_default();
+        if streaming {
+            self.gateway.create_stream(state.guild_id(), channel_id)
+        } else {
+            let stream_key = state.stream_key(&self.user_id).unwrap_or_default();
+            self.gateway.delete_stream(&stream_key)
+        }
+    }
+
+    fn connected(&self) -> Result<EditVoiceState> {
+        self.current().ok_or_else(|| {
+            Error::InvalidArgument("the current user is not in a voice channel".to_string())
+        })
+    }
+}
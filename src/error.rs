@@ -1,14 +1,52 @@
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+/// A captcha challenge of a request, handed to the captcha handler
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CaptchaInfo {
+    /// Why the captcha is required (`captcha-required`, or `invalid-response` when the
+    /// previous key was rejected)
     pub captcha_key: Vec<String>,
     pub captcha_sitekey: String,
     pub captcha_service: String,
     pub captcha_session_id: Option<String>,
+    /// hCaptcha enterprise `rqdata`, to pass to the widget along with the site key
     pub captcha_rqdata: Option<String>,
     pub captcha_rqtoken: Option<String>,
+    /// URL of the page the captcha is shown on, set by the client
+    #[serde(default)]
+    pub site_url: Option<String>,
+}
+
+/// A multi-factor authentication challenge of a sensitive endpoint (guild deletion,
+/// vanity URL edits, ...). SEE: <https://docs.discord.food/authentication#mfa-verification>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MfaInfo {
+    /// Ticket to finish the challenge with
+    pub ticket: String,
+
+    /// Methods the challenge can be finished with
+    #[serde(default)]
+    pub methods: Vec<MfaMethod>,
+}
+
+/// A method an MFA challenge can be finished with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MfaMethod {
+    /// Type of the method ("totp", "sms", "backup", "password" or "webauthn")
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    /// Whether backup codes can be used instead of TOTP codes
+    #[serde(default)]
+    pub backup_codes_allowed: bool,
+}
+
+impl MfaInfo {
+    /// Whether the challenge can be finished with a method type (e.g. "totp")
+    pub fn supports(&self, kind: &str) -> bool {
+        self.methods.iter().any(|method| method.kind == kind)
+    }
 }
 
 #[derive(Error, Debug)]
@@ -17,7 +55,7 @@ pub enum Error {
     GatewayConnection(String),
 
     #[error("WebSocket error: {0}")]
-    WebSocket(#[from] tokio_tungstenite::tungstenite::Error),
+    WebSocket(#[source] Box<tokio_tungstenite::tungstenite::Error>),
 
     #[error("JSON parsing error: {0}")]
     Json(#[from] serde_json::Error),
@@ -31,14 +69,104 @@ pub enum Error {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
+    #[error("HTTP {status} - {body}")]
+    ServerError { status: u16, body: String },
+
+    /// A request rejected with a 4xx status (other than captchas, MFA and rate limits)
+    #[error("HTTP {status} - {body}")]
+    ClientError {
+        status: u16,
+        /// JSON error code of the response, if any
+        /// SEE: <https://docs.discord.food/topics/opcodes-and-status-codes#json-error-codes>
+        code: Option<u64>,
+        body: String,
+    },
+
     #[error("Rate limited for {retry_after}s")]
     RateLimit { retry_after: f64 },
 
     #[error("Captcha required but no handler provided")]
-    CaptchaRequired(CaptchaInfo),
+    CaptchaRequired(Box<CaptchaInfo>),
 
     #[error("Captcha handler failed: {0}")]
     CaptchaHandlerFailed(String),
+
+    #[error("Captcha key rejected after {attempts} attempt(s)")]
+    CaptchaRejected {
+        info: Box<CaptchaInfo>,
+        attempts: u32,
+    },
+
+    #[error("MFA required but no handler provided")]
+    MfaRequired(MfaInfo),
+
+    #[error("Gateway closed with code {code}: {reason}")]
+    GatewayClosed { code: u16, reason: String },
+
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+
+    #[error("File format version {found} is not supported (expected {supported})")]
+    UnsupportedVersion { found: u32, supported: u32 },
+
+    #[cfg(feature = "voice")]
+    #[error("Voice error: {0}")]
+    Voice(String),
+
+    #[cfg(feature = "settings-proto")]
+    #[error("Protobuf decoding error: {0}")]
+    Protobuf(#[from] prost::DecodeError),
+}
+
+// Boxed to keep `Result` small, the websocket error being the largest payload
+impl From<tokio_tungstenite::tungstenite::Error> for Error {
+    fn from(error: tokio_tungstenite::tungstenite::Error) -> Self {
+        Error::WebSocket(Box::new(error))
+    }
+}
+
+impl Error {
+    /// Error of a response with a non-success status: `ServerError` for 5xx statuses,
+    /// `ClientError` otherwise
+    pub(crate) fn from_status(status: u16, body: String) -> Self {
+        if status >= 500 {
+            return Error::ServerError { status, body };
+        }
+        let code = serde_json::from_str::<serde_json::Value>(&body)
+            .ok()
+            .and_then(|json| json.get("code").and_then(|code| code.as_u64()));
+        Error::ClientError { status, code, body }
+    }
+
+    /// Returns the HTTP status of a rejected request
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Error::ServerError { status, .. } | Error::ClientError { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// Returns the JSON error code of a rejected request (e.g. `10006` for unknown invites)
+    pub fn api_code(&self) -> Option<u64> {
+        match self {
+            Error::ClientError { code, .. } => *code,
+            _ => None,
+        }
+    }
+
+    /// Returns the known gateway close code, if this error is a gateway close
+    pub fn gateway_close_code(&self) -> Option<crate::gateway::GatewayCloseCode> {
+        match self {
+            Error::GatewayClosed { code, .. } => crate::gateway::GatewayCloseCode::from_code(*code),
+            _ => None,
+        }
+    }
+
+    /// Whether this error is a gateway close that must not be retried
+    pub fn is_fatal_gateway_close(&self) -> bool {
+        self.gateway_close_code()
+            .is_some_and(|code| code.is_fatal())
+    }
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,131 @@
+use crate::error::{Error, Result};
+use std::str::FromStr;
+
+/// Arguments passed to a command, split on whitespace.
+///
+/// Double quotes group several words into one argument: `!say "hello world" 3`
+/// yields the arguments `hello world` and `3`.
+///
+/// # Example
+/// ```
+/// use diself::framework::Args;
+///
+/// let mut args = Args::new(r#""hello world" 3 and the rest"#);
+/// assert_eq!(args.single::<String>().unwrap(), "hello world");
+/// assert_eq!(args.single::<u32>().unwrap(), 3);
+/// assert_eq!(args.rest(), "and the rest");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Args {
+    raw: String,
+    /// Parsed arguments with the byte offset where each one starts in `raw`
+    tokens: Vec<(String, usize)>,
+    position: usize,
+}
+
+impl Args {
+    /// Splits a raw argument string
+    pub fn new(raw: impl Into<String>) -> Self {
+        let raw = raw.into();
+        let tokens = tokenize(&raw);
+        Self {
+            raw,
+            tokens,
+            position: 0,
+        }
+    }
+
+    /// Parses the next argument as `T` and advances
+    pub fn single<T>(&mut self) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display,
+    {
+        let (token, _) = self
+            .tokens
+            .get(self.position)
+            .ok_or_else(|| Error::InvalidArgument("missing argument".to_string()))?;
+        let value = token
+            .parse::<T>()
+            .map_err(|e| Error::InvalidArgument(format!("`{}`: {}", token, e)))?;
+        self.position += 1;
+        Ok(value)
+    }
+
+    /// Parses the next argument as `T` if present and valid, advancing only on success
+    pub fn optional<T: FromStr>(&mut self) -> Option<T> {
+        let (token, _) = self.tokens.get(self.position)?;
+        let value = token.parse::<T>().ok()?;
+        self.position += 1;
+        Some(value)
+    }
+
+    /// Returns the next argument without advancing
+    pub fn current(&self) -> Option<&str> {
+        self.tokens
+            .get(self.position)
+            .map(|(token, _)| token.as_str())
+    }
+
+    /// Returns the unparsed remainder of the input, as typed
+    pub fn rest(&self) -> &str {
+        match self.tokens.get(self.position) {
+            Some((_, start)) => self.raw[*start..].trim_end(),
+            None => "",
+        }
+    }
+
+    /// Returns the full raw argument string
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+
+    /// Number of arguments not consumed yet
+    pub fn remaining(&self) -> usize {
+        self.tokens.len() - self.position
+    }
+
+    /// Total number of arguments
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    /// Whether there are no arguments at all
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+}
+
+fn tokenize(raw: &str) -> Vec<(String, usize)> {
+    let mut tokens = Vec::new();
+    let mut chars = raw.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let mut token = String::new();
+        if c == '"' {
+            chars.next();
+            for (_, c) in chars.by_ref() {
+                if c == '"' {
+                    break;
+                }
+                token.push(c);
+            }
+        } else {
+            while let Some(&(_, c)) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                token.push(c);
+                chars.next();
+            }
+        }
+        tokens.push((token, start));
+    }
+
+    tokens
+}
@@ -0,0 +1,108 @@
+use crate::client::Context;
+use crate::error::Result;
+use crate::framework::Args;
+use crate::model::Message;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Boxed future returned by command handlers
+pub type CommandFuture = Pin<Box<dyn Future<Output = Result<()>> + Send>>;
+
+/// Type for command handler callbacks
+pub type CommandHandler = Arc<dyn Fn(Context, Message, Args) -> CommandFuture + Send + Sync>;
+
+/// A command registered in a `CommandFramework`.
+///
+/// # Example
+/// ```ignore
+/// use diself::framework::Command;
+/// use std::time::Duration;
+///
+/// let echo = Command::new("echo", |ctx, msg, args| async move {
+///     msg.reply(&ctx.http, args.rest()).await?;
+///     Ok(())
+/// })
+/// .alias("say")
+/// .description("Repeats your message")
+/// .usage("<text>")
+/// .cooldown(Duration::from_secs(5));
+/// ```
+#[derive(Clone)]
+pub struct Command {
+    /// Name used to invoke the command
+    pub name: String,
+    /// Alternative names
+    pub aliases: Vec<String>,
+    /// Description shown in help
+    pub description: Option<String>,
+    /// Argument usage shown in help
+    pub usage: Option<String>,
+    /// Per-user cooldown between two invocations
+    pub cooldown: Option<Duration>,
+    pub(crate) handler: CommandHandler,
+}
+
+impl Command {
+    /// Creates a command with a name and an async handler
+    pub fn new<F, Fut>(name: impl Into<String>, handler: F) -> Self
+    where
+        F: Fn(Context, Message, Args) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        Self {
+            name: name.into(),
+            aliases: Vec::new(),
+            description: None,
+            usage: None,
+            cooldown: None,
+            handler: Arc::new(move |ctx, msg, args| Box::pin(handler(ctx, msg, args))),
+        }
+    }
+
+    /// Adds an alternative name for the command
+    pub fn alias(mut self, alias: impl Into<String>) -> Self {
+        self.aliases.push(alias.into());
+        self
+    }
+
+    /// Sets the description shown in help
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the argument usage shown in help (e.g. `<user> [reason]`)
+    pub fn usage(mut self, usage: impl Into<String>) -> Self {
+        self.usage = Some(usage.into());
+        self
+    }
+
+    /// Sets the per-user cooldown between two invocations
+    pub fn cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = Some(cooldown);
+        self
+    }
+
+    /// Whether `name` is this command's name or one of its aliases
+    pub fn matches(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name)
+            || self
+                .aliases
+                .iter()
+                .any(|alias| alias.eq_ignore_ascii_case(name))
+    }
+}
+
+impl std::fmt::Debug for Command {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Command")
+            .field("name", &self.name)
+            .field("aliases", &self.aliases)
+            .field("description", &self.description)
+            .field("usage", &self.usage)
+            .field("cooldown", &self.cooldown)
+            .finish_non_exhaustive()
+    }
+}
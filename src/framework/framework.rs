@@ -0,0 +1,239 @@
+use crate::client::{Context, EventHandler};
+use crate::error::Result;
+use crate::framework::{Args, Command};
+use crate::model::Message;
+use async_trait::async_trait;
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Cooldown checks between two removals of the expired cooldowns
+const COOLDOWN_PRUNE_INTERVAL: u64 = 256;
+
+/// Result of feeding one message to `CommandFramework::dispatch`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandOutcome {
+    /// The message is not a command (no prefix, or not allowed to run commands)
+    Ignored,
+    /// The prefix matched but no command has this name
+    Unknown(String),
+    /// The command is on cooldown for this user
+    Cooldown {
+        command: String,
+        remaining: Duration,
+    },
+    /// The built-in help command replied
+    Help,
+    /// The command ran successfully
+    Executed(String),
+}
+
+/// Prefix command dispatcher.
+///
+/// Register it with `ClientBuilder::with_framework`, or use it directly as the
+/// client's `EventHandler`.
+///
+/// By default only messages sent by the current user trigger commands, and a
+/// built-in `help` command lists registered commands.
+///
+/// # Example
+/// ```ignore
+/// use diself::framework::{Command, CommandFramework};
+/// use diself::prelude::*;
+/// use std::time::Duration;
+///
+/// let framework = CommandFramework::new("!")
+///     .command(Command::new("ping", |ctx, msg, _args| async move {
+///         msg.reply(&ctx.http, "Pong!").await?;
+///         Ok(())
+///     }))
+///     .command(
+///         Command::new("repeat", |ctx, msg, mut args| async move {
+///             let times: usize = args.single()?;
+///             msg.reply(&ctx.http, args.rest().repeat(times)).await?;
+///             Ok(())
+///         })
+///         .alias("r")
+///         .usage("<times> <text>")
+///         .cooldown(Duration::from_secs(10)),
+///     );
+///
+/// let client = Client::builder(token, MyHandler).with_framework(framework).build();
+/// ```
+pub struct CommandFramework {
+    prefix: String,
+    commands: Vec<Command>,
+    allow_others: bool,
+    help: bool,
+    /// End of the cooldown of each `command:user` pair
+    cooldowns: Arc<DashMap<String, Instant>>,
+    cooldown_checks: Arc<AtomicU64>,
+}
+
+impl CommandFramework {
+    /// Creates a framework with a command prefix
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            commands: Vec::new(),
+            allow_others: false,
+            help: true,
+            cooldowns: Arc::new(DashMap::new()),
+            cooldown_checks: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Registers a command
+    pub fn command(mut self, command: Command) -> Self {
+        self.commands.push(command);
+        self
+    }
+
+    /// Lets messages from other users trigger commands (default: only the current user)
+    pub fn allow_others(mut self, allow: bool) -> Self {
+        self.allow_others = allow;
+        self
+    }
+
+    /// Disables the built-in `help` command
+    pub fn without_help(mut self) -> Self {
+        self.help = false;
+        self
+    }
+
+    /// Gets the command prefix
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Gets all registered commands
+    pub fn commands(&self) -> &[Command] {
+        &self.commands
+    }
+
+    /// Finds a command by name or alias
+    pub fn find(&self, name: &str) -> Option<&Command> {
+        self.commands.iter().find(|command| command.matches(name))
+    }
+
+    /// Generates the help listing of all registered commands
+    pub fn help_text(&self) -> String {
+        let mut out = String::from("**Commands**\n");
+        for command in &self.commands {
+            out.push_str(&self.command_line(command));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Generates the help of one command (if it exists)
+    pub fn command_help(&self, name: &str) -> Option<String> {
+        let command = self.find(name)?;
+        let mut out = self.command_line(command);
+        if let Some(cooldown) = command.cooldown {
+            let _ = write!(out, "\nCooldown: {}s", cooldown.as_secs());
+        }
+        Some(out)
+    }
+
+    fn command_line(&self, command: &Command) -> String {
+        let mut line = format!("`{}{}", self.prefix, command.name);
+        if let Some(usage) = &command.usage {
+            let _ = write!(line, " {}", usage);
+        }
+        line.push('`');
+        if let Some(description) = &command.description {
+            let _ = write!(line, " - {}", description);
+        }
+        if !command.aliases.is_empty() {
+            let _ = write!(line, " (aliases: {})", command.aliases.join(", "));
+        }
+        line
+    }
+
+    /// Parses a message and runs the matching command.
+    ///
+    /// Errors returned by the command handler are propagated.
+    pub async fn dispatch(&self, ctx: &Context, msg: &Message) -> Result<CommandOutcome> {
+        if !self.allow_others && msg.author.id != ctx.user.id {
+            return Ok(CommandOutcome::Ignored);
+        }
+
+        let Some((name, rest)) = self.split_command(&msg.content) else {
+            return Ok(CommandOutcome::Ignored);
+        };
+
+        let Some(command) = self.find(name) else {
+            if self.help && name.eq_ignore_ascii_case("help") {
+                let target = rest.trim();
+                let text = if target.is_empty() {
+                    self.help_text()
+                } else {
+                    self.command_help(target)
+                        .unwrap_or_else(|| format!("Unknown command `{}`", target))
+                };
+                msg.reply(&ctx.http, text).await?;
+                return Ok(CommandOutcome::Help);
+            }
+            return Ok(CommandOutcome::Unknown(name.to_string()));
+        };
+
+        if let Some(remaining) = self.check_cooldown(command, &msg.author.id) {
+            return Ok(CommandOutcome::Cooldown {
+                command: command.name.clone(),
+                remaining,
+            });
+        }
+
+        (command.handler)(ctx.clone(), msg.clone(), Args::new(rest)).await?;
+        Ok(CommandOutcome::Executed(command.name.clone()))
+    }
+
+    /// Splits `<prefix><name> <rest>` into `(name, rest)`
+    fn split_command<'a>(&self, content: &'a str) -> Option<(&'a str, &'a str)> {
+        let content = content.strip_prefix(self.prefix.as_str())?;
+        let (name, rest) = match content.find(char::is_whitespace) {
+            Some(idx) => (&content[..idx], content[idx..].trim_start()),
+            None => (content, ""),
+        };
+        if name.is_empty() {
+            return None;
+        }
+        Some((name, rest))
+    }
+
+    /// Returns the remaining cooldown, or records this invocation
+    fn check_cooldown(&self, command: &Command, user_id: &str) -> Option<Duration> {
+        let cooldown = command.cooldown?;
+        let key = format!("{}:{}", command.name, user_id);
+        let now = Instant::now();
+        let checks = self.cooldown_checks.fetch_add(1, Ordering::Relaxed);
+        if checks.is_multiple_of(COOLDOWN_PRUNE_INTERVAL) {
+            self.cooldowns.retain(|_, until| *until > now);
+        }
+
+        match self.cooldowns.entry(key) {
+            Entry::Occupied(entry) if *entry.get() > now => Some(*entry.get() - now),
+            Entry::Occupied(mut entry) => {
+                entry.insert(now + cooldown);
+                None
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(now + cooldown);
+                None
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl EventHandler for CommandFramework {
+    async fn on_message_create(&self, ctx: &Context, message: Message) {
+        if let Err(e) = self.dispatch(ctx, &message).await {
+            tracing::error!("Command failed: {}", e);
+        }
+    }
+}
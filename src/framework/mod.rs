@@ -0,0 +1,8 @@
+mod args;
+mod command;
+#[allow(clippy::module_inception)]
+mod framework;
+
+pub use args::Args;
+pub use command::{Command, CommandFuture, CommandHandler};
+pub use framework::{CommandFramework, CommandOutcome};
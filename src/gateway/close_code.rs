@@ -0,0 +1,66 @@
+/// Close codes sent by the Discord gateway. SEE: <https://docs.discord.food/topics/opcodes-and-status-codes#gateway-close-event-codes>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum GatewayCloseCode {
+    UnknownError = 4000,
+    UnknownOpcode = 4001,
+    DecodeError = 4002,
+    NotAuthenticated = 4003,
+    AuthenticationFailed = 4004,
+    AlreadyAuthenticated = 4005,
+    InvalidSequence = 4007,
+    RateLimited = 4008,
+    SessionTimedOut = 4009,
+    InvalidShard = 4010,
+    ShardingRequired = 4011,
+    InvalidApiVersion = 4012,
+    InvalidIntents = 4013,
+    DisallowedIntents = 4014,
+}
+
+impl GatewayCloseCode {
+    /// Maps a raw close code to a known gateway close code
+    pub fn from_code(code: u16) -> Option<Self> {
+        let code = match code {
+            4000 => Self::UnknownError,
+            4001 => Self::UnknownOpcode,
+            4002 => Self::DecodeError,
+            4003 => Self::NotAuthenticated,
+            4004 => Self::AuthenticationFailed,
+            4005 => Self::AlreadyAuthenticated,
+            4007 => Self::InvalidSequence,
+            4008 => Self::RateLimited,
+            4009 => Self::SessionTimedOut,
+            4010 => Self::InvalidShard,
+            4011 => Self::ShardingRequired,
+            4012 => Self::InvalidApiVersion,
+            4013 => Self::InvalidIntents,
+            4014 => Self::DisallowedIntents,
+            _ => return None,
+        };
+        Some(code)
+    }
+
+    /// Returns the raw close code
+    pub fn code(self) -> u16 {
+        self as u16
+    }
+
+    /// Whether reconnecting cannot succeed (bad token, invalid intents, ...)
+    pub fn is_fatal(self) -> bool {
+        matches!(
+            self,
+            Self::AuthenticationFailed
+                | Self::InvalidShard
+                | Self::ShardingRequired
+                | Self::InvalidApiVersion
+                | Self::InvalidIntents
+                | Self::DisallowedIntents
+        )
+    }
+
+    /// Whether the session can still be resumed after this close
+    pub fn can_resume(self) -> bool {
+        !self.is_fatal() && !matches!(self, Self::InvalidSequence | Self::SessionTimedOut)
+    }
+}
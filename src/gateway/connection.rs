@@ -1,11 +1,19 @@
 use crate::error::{Error, Result};
+use crate::gateway::ratelimit::SendRateLimiter;
 use futures_util::{SinkExt, StreamExt};
 use serde_json::Value;
+use std::collections::VecDeque;
 use tokio::net::TcpStream;
+use tokio::time::Instant;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tokio_tungstenite::tungstenite::protocol::CloseFrame;
 use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
 
 pub struct Connection {
     pub ws: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    limiter: SendRateLimiter,
+    queued: VecDeque<Value>,
+    retry_at: Option<Instant>,
 }
 
 impl Connection {
@@ -18,9 +26,18 @@ impl Connection {
             .map_err(|e| Error::GatewayConnection(e.to_string()))?;
 
         tracing::info!("Successfully connected!");
-        Ok(Self { ws })
+        Ok(Self {
+            ws,
+            limiter: SendRateLimiter::new(),
+            queued: VecDeque::new(),
+            retry_at: None,
+        })
     }
 
+    /// Receives the next JSON payload.
+    ///
+    /// Returns `Ok(None)` when the stream ends, and `Error::GatewayClosed`
+    /// when the server sends a close frame with a code.
     pub async fn receive(&mut self) -> Result<Option<Value>> {
         while let Some(msg) = self.ws.next().await {
             let msg = msg?;
@@ -35,7 +52,13 @@ impl Connection {
                 }
                 Message::Close(frame) => {
                     tracing::warn!("WebSocket closed: {:?}", frame);
-                    return Ok(None);
+                    return match frame {
+                        Some(frame) => Err(Error::GatewayClosed {
+                            code: frame.code.into(),
+                            reason: frame.reason.into_owned(),
+                        }),
+                        None => Ok(None),
+                    };
                 }
                 _ => {
                     //ignore other message types (binary, ping, pong)
@@ -46,7 +69,60 @@ impl Connection {
         Ok(None)
     }
 
+    /// Sends a JSON payload, staying under 120 sends per minute.
+    ///
+    /// Heartbeats (op 1) are sent right away and can use a few sends that other
+    /// payloads leave free. Other payloads are queued once the limit is reached,
+    /// and sent later by `flush_queued` (see `retry_at`), keeping their order.
     pub async fn send(&mut self, payload: &Value) -> Result<()> {
+        let now = Instant::now();
+        if payload.get("op").and_then(Value::as_u64) == Some(1) {
+            if let Some(delay) = self.limiter.try_acquire(now, true) {
+                tracing::warn!(
+                    "Gateway send rate limit reached, sending heartbeat anyway ({:?} early)",
+                    delay
+                );
+            }
+            return self.write(payload).await;
+        }
+
+        if !self.queued.is_empty() {
+            self.queued.push_back(payload.clone());
+            return Ok(());
+        }
+        match self.limiter.try_acquire(now, false) {
+            None => self.write(payload).await,
+            Some(delay) => {
+                tracing::warn!("Gateway send rate limit reached, queueing for {:?}", delay);
+                self.queued.push_back(payload.clone());
+                self.retry_at = Some(now + delay);
+                Ok(())
+            }
+        }
+    }
+
+    /// When the payloads queued by the send rate limit can be flushed, if any are queued
+    pub fn retry_at(&self) -> Option<Instant> {
+        self.retry_at
+    }
+
+    /// Sends the queued payloads the send rate limit allows, keeping the rest queued
+    pub async fn flush_queued(&mut self) -> Result<()> {
+        self.retry_at = None;
+        while let Some(payload) = self.queued.front() {
+            let now = Instant::now();
+            if let Some(delay) = self.limiter.try_acquire(now, false) {
+                self.retry_at = Some(now + delay);
+                break;
+            }
+            let payload = payload.clone();
+            self.queued.pop_front();
+            self.write(&payload).await?;
+        }
+        Ok(())
+    }
+
+    async fn write(&mut self, payload: &Value) -> Result<()> {
         let text = serde_json::to_string(payload)?;
         let redacted = redact_gateway_payload(payload);
         let redacted_text = serde_json::to_string(&redacted)?;
@@ -56,8 +132,18 @@ impl Connection {
         Ok(())
     }
 
+    /// Closes the socket with the normal close code (1000)
     pub async fn close(&mut self) -> Result<()> {
-        self.ws.close(None).await?;
+        self.close_with_code(1000, "").await
+    }
+
+    /// Sends a close frame with the given code and reason, then closes the socket
+    pub async fn close_with_code(&mut self, code: u16, reason: &str) -> Result<()> {
+        let frame = CloseFrame {
+            code: CloseCode::from(code),
+            reason: reason.to_string().into(),
+        };
+        self.ws.close(Some(frame)).await?;
         Ok(())
     }
 }
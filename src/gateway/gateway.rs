@@ -1,15 +1,23 @@
 use crate::error::{Error, Result};
-use crate::gateway::{Connection, Identify};
+use crate::gateway::sender::guild_subscriptions_payloads;
+use crate::gateway::{
+    Connection, ConnectionProperties, GatewayMetrics, GatewaySender, Identify, IdentifyConfig,
+    SessionState,
+};
+use crate::model::GuildSubscription;
 use rand::Rng;
 use serde_json::{json, Value};
+use tokio::sync::mpsc;
 use tokio::time::{self, Duration, Interval, Instant};
 
-const DEFAULT_GATEWAY_URL: &str = "wss://gateway.discord.gg/?v=10&encoding=json";
+/// Gateway URL used when no custom URL is set
+pub const DEFAULT_GATEWAY_URL: &str = "wss://gateway.discord.gg/?v=10&encoding=json";
 const INVALID_SESSION_RETRY_DELAY: Duration = Duration::from_secs(1);
 const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(60);
 
 pub struct Gateway {
     token: String,
+    properties: ConnectionProperties,
     connection: Option<Connection>,
     heartbeat: Option<Interval>,
     heartbeat_interval_ms: u64,
@@ -19,12 +27,126 @@ pub struct Gateway {
     session_id: Option<String>,
     resume_gateway_url: Option<String>,
     reconnect_attempts: u32,
+    shard: Option<[u32; 2]>,
+    metrics: GatewayMetrics,
+    connected_once: bool,
+    gateway_url: Option<String>,
+    identify: IdentifyConfig,
+    commands_tx: mpsc::UnboundedSender<Value>,
+    commands: mpsc::UnboundedReceiver<Value>,
+    presence: Option<Value>,
 }
 
 impl Gateway {
     pub async fn connect(token: impl Into<String>) -> Result<Self> {
+        Self::connect_inner(
+            token.into(),
+            None,
+            ConnectionProperties::default_client(),
+            None,
+            GatewayMetrics::default(),
+            None,
+            IdentifyConfig::default(),
+        )
+        .await
+    }
+
+    /// Connects and tries to RESUME a previously saved session.
+    ///
+    /// Falls back to a fresh IDENTIFY if Discord rejects the resume.
+    pub async fn connect_with_session(
+        token: impl Into<String>,
+        session: SessionState,
+    ) -> Result<Self> {
+        Self::connect_inner(
+            token.into(),
+            Some(session),
+            ConnectionProperties::default_client(),
+            None,
+            GatewayMetrics::default(),
+            None,
+            IdentifyConfig::default(),
+        )
+        .await
+    }
+
+    /// Connects identifying with custom connection properties, resuming `session` if given.
+    ///
+    /// Use the same properties as the `HttpClient` so both transports look like one client.
+    pub async fn connect_with_properties(
+        token: impl Into<String>,
+        properties: ConnectionProperties,
+        session: Option<SessionState>,
+    ) -> Result<Self> {
+        Self::connect_inner(
+            token.into(),
+            session,
+            properties,
+            None,
+            GatewayMetrics::default(),
+            None,
+            IdentifyConfig::default(),
+        )
+        .await
+    }
+
+    /// Connects as shard `shard_id` of `shard_count`, resuming `session` if given.
+    ///
+    /// Discord only sends events of the guilds assigned to the shard (`(guild_id >> 22) % shard_count`).
+    pub async fn connect_shard(
+        token: impl Into<String>,
+        properties: ConnectionProperties,
+        shard_id: u32,
+        shard_count: u32,
+        session: Option<SessionState>,
+    ) -> Result<Self> {
+        Self::connect_inner(
+            token.into(),
+            session,
+            properties,
+            Some([shard_id, shard_count]),
+            GatewayMetrics::default(),
+            None,
+            IdentifyConfig::default(),
+        )
+        .await
+    }
+
+    /// Connects to a custom gateway URL (e.g. a local proxy), resuming `session` if given.
+    ///
+    /// The URL must include the query string (`?v=10&encoding=json`). Every reconnect
+    /// and resume goes through this URL instead of the `resume_gateway_url` sent by Discord.
+    pub async fn connect_with_url(
+        url: impl Into<String>,
+        token: impl Into<String>,
+        properties: ConnectionProperties,
+        session: Option<SessionState>,
+    ) -> Result<Self> {
+        Self::connect_inner(
+            token.into(),
+            session,
+            properties,
+            None,
+            GatewayMetrics::default(),
+            Some(url.into()),
+            IdentifyConfig::default(),
+        )
+        .await
+    }
+
+    pub(crate) async fn connect_inner(
+        token: String,
+        session: Option<SessionState>,
+        properties: ConnectionProperties,
+        shard: Option<[u32; 2]>,
+        metrics: GatewayMetrics,
+        gateway_url: Option<String>,
+        identify: IdentifyConfig,
+    ) -> Result<Self> {
+        let (commands_tx, commands) = mpsc::unbounded_channel();
         let mut gateway = Self {
-            token: token.into(),
+            token,
+            properties,
             connection: None,
             heartbeat: None,
             heartbeat_interval_ms: 0,
@@ -34,12 +156,66 @@ impl Gateway {
             session_id: None,
             resume_gateway_url: None,
             reconnect_attempts: 0,
+            shard,
+            metrics,
+            connected_once: false,
+            gateway_url,
+            identify,
+            commands_tx,
+            commands,
+            presence: None,
         };
 
+        if let Some(session) = session {
+            gateway.session_id = Some(session.session_id);
+            gateway.resume_gateway_url = session.resume_gateway_url;
+            gateway.sequence = session.sequence;
+        }
+
         gateway.reconnect(true).await?;
         Ok(gateway)
     }
 
+    /// Returns a handle sending commands (presence updates, ...) through this gateway.
+    pub fn sender(&self) -> GatewaySender {
+        GatewaySender::new(vec![self.commands_tx.clone()])
+    }
+
+    /// Reads commands from a channel created before the connection (used by `ShardManager`)
+    pub(crate) fn with_commands(
+        mut self,
+        commands_tx: mpsc::UnboundedSender<Value>,
+        commands: mpsc::UnboundedReceiver<Value>,
+    ) -> Self {
+        self.commands_tx = commands_tx;
+        self.commands = commands;
+        self
+    }
+
+    /// Returns the health metrics (latency, reconnects, throughput) of this gateway.
+    pub fn metrics(&self) -> &GatewayMetrics {
+        &self.metrics
+    }
+
+    /// Time between the last heartbeat and its ACK (`None` before the first ACK).
+    pub fn latency(&self) -> Option<Duration> {
+        self.metrics.latency()
+    }
+
+    /// Returns the `[shard_id, shard_count]` this gateway identifies with, if sharded.
+    pub fn shard(&self) -> Option<[u32; 2]> {
+        self.shard
+    }
+
+    /// Returns the current resumable session state, if a session has been established.
+    pub fn session_state(&self) -> Option<SessionState> {
+        self.session_id.as_ref().map(|session_id| SessionState {
+            session_id: session_id.clone(),
+            resume_gateway_url: self.resume_gateway_url.clone(),
+            sequence: self.sequence,
+        })
+    }
+
     pub async fn next_event(&mut self) -> Result<Option<Value>> {
         loop {
             if self.pending_heartbeat {
@@ -49,6 +225,7 @@ impl Gateway {
 
             let heartbeat = self.heartbeat.as_mut().ok_or(Error::InvalidPayload)?;
             let connection = self.connection.as_mut().ok_or(Error::InvalidPayload)?;
+            let retry_at = connection.retry_at();
 
             tokio::select! {
                 _ = heartbeat.tick() => {
@@ -59,8 +236,42 @@ impl Gateway {
                     }
                     self.pending_heartbeat = true;
                 }
+                Some(command) = self.commands.recv() => {
+                    self.send_command(command).await;
+                }
+                _ = time::sleep_until(retry_at.unwrap_or_else(Instant::now)), if retry_at.is_some() => {
+                    if let Some(connection) = self.connection.as_mut() {
+                        if let Err(e) = connection.flush_queued().await {
+                            tracing::warn!("Failed to send queued gateway payloads: {}", e);
+                        }
+                    }
+                }
                 payload = connection.receive() => {
-                    let Some(payload) = payload? else {
+                    let payload = match payload {
+                        Ok(payload) => payload,
+                        Err(err @ Error::GatewayClosed { .. }) => {
+                            if err.is_fatal_gateway_close() {
+                                tracing::error!("Gateway closed with a fatal code: {}", err);
+                                self.connection = None;
+                                self.heartbeat = None;
+                                return Err(err);
+                            }
+
+                            let resume = err
+                                .gateway_close_code()
+                                .is_none_or(|code| code.can_resume());
+                            tracing::warn!("{}, reconnecting (resume={})", err, resume);
+                            if !resume {
+                                self.session_id = None;
+                                self.sequence = None;
+                                self.resume_gateway_url = None;
+                            }
+                            self.reconnect(resume).await?;
+                            continue;
+                        }
+                        Err(err) => return Err(err),
+                    };
+                    let Some(payload) = payload else {
                         tracing::warn!("Gateway connection closed, reconnecting");
                         self.reconnect(true).await?;
                         continue;
@@ -78,13 +289,24 @@ impl Gateway {
         }
     }
 
+    /// Closes the connection with code 1000, which also ends the session
     pub async fn shutdown(&mut self) -> Result<()> {
+        self.close(1000).await
+    }
+
+    /// Closes the connection with code 4000, so the session can still be resumed later
+    /// (see `session_state`)
+    pub async fn shutdown_resumable(&mut self) -> Result<()> {
+        self.close(4000).await
+    }
+
+    async fn close(&mut self, code: u16) -> Result<()> {
         self.awaiting_heartbeat_ack = false;
         self.pending_heartbeat = false;
         self.heartbeat = None;
 
         if let Some(mut connection) = self.connection.take() {
-            connection.close().await?;
+            connection.close_with_code(code, "").await?;
         }
 
         Ok(())
@@ -95,6 +317,7 @@ impl Gateway {
 
         match op {
             Some(0) => {
+                self.metrics.record_event();
                 if let Some(event_type) = payload.get("t").and_then(|t| t.as_str()) {
                     match event_type {
                         "READY" => {
@@ -109,23 +332,15 @@ impl Gateway {
                                 self.can_resume()
                             );
                             if let Some(guilds) = payload["d"]["guilds"].as_array() {
-                                let guild_ids: Vec<String> = guilds
+                                let subscriptions: Vec<(String, GuildSubscription)> = guilds
                                     .iter()
                                     .filter_map(|g| g["id"].as_str().map(ToOwned::to_owned))
+                                    .map(|guild_id| (guild_id, GuildSubscription::all()))
                                     .collect();
                                 if let Some(conn) = self.connection.as_mut() {
-                                    for guild_id in guild_ids {
-                                        let op14 = json!({
-                                            "op": 14,
-                                            "d": {
-                                                "guild_id": guild_id,
-                                                "typing": true,
-                                                "threads": true,
-                                                "activities": true,
-                                            }
-                                        });
-                                        if let Err(e) = conn.send(&op14).await {
-                                            tracing::warn!("Failed to subscribe to guild {}: {}", guild_id, e);
+                                    for bulk in guild_subscriptions_payloads(subscriptions) {
+                                        if let Err(e) = conn.send(&bulk).await {
+                                            tracing::warn!("Failed to subscribe to guilds: {}", e);
                                         }
                                     }
                                 }
@@ -168,6 +383,7 @@ impl Gateway {
             }
             Some(11) => {
                 self.awaiting_heartbeat_ack = false;
+                self.metrics.record_heartbeat_ack();
                 tracing::trace!("Heartbeat ACK received");
                 Ok(None)
             }
@@ -175,6 +391,20 @@ impl Gateway {
         }
     }
 
+    /// Sends a command queued by a `GatewaySender`, remembering presence updates
+    /// so they are restored on the next IDENTIFY
+    async fn send_command(&mut self, command: Value) {
+        if command.get("op").and_then(Value::as_u64) == Some(3) {
+            self.presence = command.get("d").cloned();
+        }
+        let Some(connection) = self.connection.as_mut() else {
+            return;
+        };
+        if let Err(e) = connection.send(&command).await {
+            tracing::warn!("Failed to send gateway command: {}", e);
+        }
+    }
+
     async fn send_heartbeat(&mut self) -> Result<()> {
         let payload = json!({
             "op": 1,
@@ -184,6 +414,7 @@ impl Gateway {
         let connection = self.connection.as_mut().ok_or(Error::InvalidPayload)?;
         connection.send(&payload).await?;
         self.awaiting_heartbeat_ack = true;
+        self.metrics.record_heartbeat_sent();
         tracing::trace!("Heartbeat sent (seq: {:?})", self.sequence);
         Ok(())
     }
@@ -210,8 +441,15 @@ impl Gateway {
             match self.open_session(use_resume).await {
                 Ok(()) => {
                     self.reconnect_attempts = 0;
+                    self.metrics
+                        .record_connected(self.connected_once, use_resume);
+                    self.connected_once = true;
                     return Ok(());
                 }
+                Err(err) if err.is_fatal_gateway_close() => {
+                    tracing::error!("Gateway rejected the connection: {}", err);
+                    return Err(err);
+                }
                 Err(err) => {
                     tracing::error!("Failed to reconnect gateway: {}", err);
                     self.reconnect_attempts = self.reconnect_attempts.saturating_add(1);
@@ -227,8 +465,9 @@ impl Gateway {
 
     async fn open_session(&mut self, resume: bool) -> Result<()> {
         let url = self
-            .resume_gateway_url
+            .gateway_url
             .as_deref()
+            .or(self.resume_gateway_url.as_deref())
             .unwrap_or(DEFAULT_GATEWAY_URL);
         let mut connection = Connection::connect(url).await?;
 
@@ -267,10 +506,16 @@ impl Gateway {
     }
 
     async fn send_identify(&self, connection: &mut Connection) -> Result<()> {
-        let identify_payload = json!({
+        let mut identify = Identify::with_properties(self.token.clone(), self.properties.clone())
+            .with_config(self.identify);
+        identify.shard = self.shard;
+        let mut identify_payload = json!({
             "op": 2,
-            "d": Identify::new(self.token.clone()),
+            "d": identify,
         });
+        if let Some(presence) = &self.presence {
+            identify_payload["d"]["presence"] = presence.clone();
+        }
         connection.send(&identify_payload).await
     }
 
@@ -1,3 +1,4 @@
+use crate::model::EditPresence;
 use serde::{Deserialize, Serialize};
 
 //Authentication payload for Discord Gateway
@@ -11,7 +12,7 @@ pub struct Identify {
 
     /// Initial presence (optional)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub presence: Option<PresenceUpdate>,
+    pub presence: Option<EditPresence>,
 
     /// Compression (must be false for selfbots)
     pub compress: Option<bool>,
@@ -22,6 +23,10 @@ pub struct Identify {
     /// Gateway intents (what events we want to receive)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub intents: Option<u32>,
+
+    /// Shard ID and total shard count (`[shard_id, shard_count]`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard: Option<[u32; 2]>,
 }
 
 // Connection properties sent in the Identify payload
@@ -66,11 +71,20 @@ pub struct ConnectionProperties {
     /// Client build number (e.g., 9999)
     #[serde(rename = "$client_build_number")]
     pub client_build_number: u32,
+
+    /// Full User-Agent of the client, also sent as the HTTP `User-Agent` header
+    #[serde(default)]
+    pub browser_user_agent: String,
 }
 
 impl ConnectionProperties {
-    // Returning properties with default values (can be customized if needed)
+    /// Default properties, the same as `web_client`
     pub fn default_client() -> Self {
+        Self::web_client()
+    }
+
+    /// Properties of the desktop (Electron) client on macOS
+    pub fn desktop_client() -> Self {
         Self {
             os: "Mac OS X".to_string(),
             browser: "Discord Client".to_string(),
@@ -82,73 +96,136 @@ impl ConnectionProperties {
             referring_domain: "".to_string(),
             release_channel: "stable".to_string(),
             client_build_number: 500334,
+            browser_user_agent: "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) discord/1.135.0 Chrome/134.0.6998.205 Electron/35.3.0 Safari/537.36".to_string(),
         }
     }
-}
-
-/// Presence update
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PresenceUpdate {
-    /// Status (e.g., "online", "idle", "dnd", "invisible")
-    pub status: String,
 
-    /// Timestamps
-    pub since: Option<u64>,
-
-    /// Activities
-    pub activities: Vec<Activity>,
+    /// Properties of the web client running in Chrome on macOS
+    pub fn web_client() -> Self {
+        Self {
+            os: "Mac OS X".to_string(),
+            browser: "Chrome".to_string(),
+            device: "".to_string(),
+            system_locale: "en-US".to_string(),
+            browser_version: "145.0.0.0".to_string(),
+            os_version: "10.15.7".to_string(),
+            referrer: "https://www.google.com/".to_string(),
+            referring_domain: "www.google.com".to_string(),
+            release_channel: "stable".to_string(),
+            client_build_number: 500334,
+            browser_user_agent: "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/145.0.0.0 Safari/537.36".to_string(),
+        }
+    }
 
-    /// AFK status
-    pub afk: bool,
+    /// Builds the JSON object sent (base64 encoded) in the `X-Super-Properties` HTTP header
+    pub fn super_properties(&self) -> serde_json::Value {
+        serde_json::json!({
+            "os": self.os,
+            "browser": self.browser,
+            "device": self.device,
+            "system_locale": self.system_locale,
+            "browser_user_agent": self.browser_user_agent,
+            "browser_version": self.browser_version,
+            "os_version": self.os_version,
+            "referrer": self.referrer,
+            "referring_domain": self.referring_domain,
+            "referrer_current": self.referrer,
+            "referring_domain_current": self.referring_domain,
+            "release_channel": self.release_channel,
+            "client_build_number": self.client_build_number,
+            "client_event_source": null,
+            "has_client_mods": false,
+        })
+    }
 }
 
-impl Default for PresenceUpdate {
+impl Default for ConnectionProperties {
     fn default() -> Self {
-        Self {
-            status: "online".to_string(),
-            since: None,
-            activities: vec![],
-            afk: false,
-        }
+        Self::default_client()
     }
 }
 
-// Activtiy (playing, streaming, listening, watching)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Capabilities of the official desktop client
+pub const DEFAULT_CAPABILITIES: u32 = 16381;
+
+/// Every gateway intent (bits 0 to 16, 20 and 21)
+pub const ALL_INTENTS: u32 = 3276799;
+
+/// Predefined capabilities and intents of the IDENTIFY payload
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum IdentifyPreset {
+    /// Client capabilities without intents, like the official clients (user accounts get
+    /// every event without intents)
+    #[default]
+    UserDefault,
+    /// Client capabilities and every intent, the payload sent by older versions
+    BotCompat,
+}
 
-pub struct Activity {
-    // Name of the activity (e.g., "Spotify")
-    pub name: String,
+/// Capabilities and intents sent in IDENTIFY
+///
+/// Built from an `IdentifyPreset` and customized with `ClientBuilder::with_capabilities`
+/// and `ClientBuilder::with_intents`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IdentifyConfig {
+    /// Client capabilities bitfield
+    pub capabilities: u32,
 
-    // Type of activity (0 = playing, 1 = streaming, 2 = listening, 3 = watching)
-    #[serde(rename = "type")]
-    pub kind: u8,
+    /// Gateway intents (`None` to send no intents)
+    pub intents: Option<u32>,
+}
 
-    // URL for streaming (optional)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub url: Option<String>,
+impl From<IdentifyPreset> for IdentifyConfig {
+    fn from(preset: IdentifyPreset) -> Self {
+        match preset {
+            IdentifyPreset::UserDefault => Self {
+                capabilities: DEFAULT_CAPABILITIES,
+                intents: None,
+            },
+            IdentifyPreset::BotCompat => Self {
+                capabilities: DEFAULT_CAPABILITIES,
+                intents: Some(ALL_INTENTS),
+            },
+        }
+    }
+}
+
+impl Default for IdentifyConfig {
+    fn default() -> Self {
+        IdentifyPreset::default().into()
+    }
 }
 
 impl Identify {
     pub fn new(token: impl Into<String>) -> Self {
-        // Intents pour recevoir tous les messages:
-        // GUILDS (1 << 0) = 1
-        // GUILD_MEMBERS (1 << 1) = 2
-        // GUILD_MESSAGES (1 << 9) = 512
-        // GUILD_MESSAGE_REACTIONS (1 << 10) = 1024
-        // DIRECT_MESSAGES (1 << 12) = 4096
-        // DIRECT_MESSAGE_REACTIONS (1 << 13) = 8192
-        // MESSAGE_CONTENT (1 << 15) = 32768
-        // Total: 1 + 2 + 512 + 1024 + 4096 + 8192 + 32768 = 46595
-        let intents = 3276799; // All intents (for maximum functionality, but can be customized if needed)
+        Self::with_properties(token, ConnectionProperties::default_client())
+    }
 
+    /// Creates an Identify payload advertising the given connection properties
+    pub fn with_properties(token: impl Into<String>, properties: ConnectionProperties) -> Self {
+        let config = IdentifyConfig::default();
         Self {
             token: token.into(),
-            properties: ConnectionProperties::default_client(),
-            presence: Some(PresenceUpdate::default()),
+            properties,
+            presence: Some(EditPresence::default()),
             compress: Some(false),
-            capabilities: 16381, // Standard capabilities for Discord clients
-            intents: Some(intents),
+            capabilities: config.capabilities,
+            intents: config.intents,
+            shard: None,
         }
     }
+
+    /// Sends the capabilities and intents of `config` (or of an `IdentifyPreset`)
+    pub fn with_config(mut self, config: impl Into<IdentifyConfig>) -> Self {
+        let config = config.into();
+        self.capabilities = config.capabilities;
+        self.intents = config.intents;
+        self
+    }
+
+    /// Identifies as one shard of `shard_count`
+    pub fn with_shard(mut self, shard_id: u32, shard_count: u32) -> Self {
+        self.shard = Some([shard_id, shard_count]);
+        self
+    }
 }
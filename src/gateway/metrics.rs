@@ -0,0 +1,121 @@
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::time::{Duration, Instant};
+
+/// Live health metrics of a gateway connection
+///
+/// Cheap to clone: every clone reads the same counters, so the `Context` copy
+/// stays up to date while the gateway runs.
+#[derive(Debug, Clone, Default)]
+pub struct GatewayMetrics {
+    inner: Arc<MetricsInner>,
+}
+
+#[derive(Debug, Default)]
+struct MetricsInner {
+    heartbeat: Mutex<HeartbeatTimes>,
+    events_received: AtomicU64,
+    reconnects: AtomicU64,
+    resumes: AtomicU64,
+}
+
+#[derive(Debug, Default)]
+struct HeartbeatTimes {
+    pending_since: Option<Instant>,
+    latency: Option<Duration>,
+    last_sent_at: Option<SystemTime>,
+    last_ack_at: Option<SystemTime>,
+    connected_at: Option<Instant>,
+    first_connected_at: Option<Instant>,
+}
+
+impl GatewayMetrics {
+    /// Time between the last heartbeat and its ACK (`None` before the first ACK)
+    pub fn latency(&self) -> Option<Duration> {
+        self.inner.heartbeat.lock().latency
+    }
+
+    /// When the last heartbeat was sent
+    pub fn last_heartbeat_at(&self) -> Option<SystemTime> {
+        self.inner.heartbeat.lock().last_sent_at
+    }
+
+    /// When the last heartbeat ACK was received
+    pub fn last_heartbeat_ack_at(&self) -> Option<SystemTime> {
+        self.inner.heartbeat.lock().last_ack_at
+    }
+
+    /// How long the current connection has been open
+    pub fn uptime(&self) -> Option<Duration> {
+        self.inner
+            .heartbeat
+            .lock()
+            .connected_at
+            .map(|connected_at| connected_at.elapsed())
+    }
+
+    /// Number of reconnections since the gateway was created (resumes included)
+    pub fn reconnects(&self) -> u64 {
+        self.inner.reconnects.load(Ordering::Relaxed)
+    }
+
+    /// Number of reconnections that resumed the previous session
+    pub fn resumes(&self) -> u64 {
+        self.inner.resumes.load(Ordering::Relaxed)
+    }
+
+    /// Number of dispatch events received since the gateway was created
+    pub fn events_received(&self) -> u64 {
+        self.inner.events_received.load(Ordering::Relaxed)
+    }
+
+    /// Average dispatch events per second since the gateway first connected
+    pub fn events_per_second(&self) -> f64 {
+        let Some(first_connected_at) = self.inner.heartbeat.lock().first_connected_at else {
+            return 0.0;
+        };
+        let seconds = first_connected_at.elapsed().as_secs_f64();
+        if seconds <= 0.0 {
+            return 0.0;
+        }
+        self.events_received() as f64 / seconds
+    }
+
+    pub(crate) fn record_connected(&self, reconnect: bool, resumed: bool) {
+        let mut heartbeat = self.inner.heartbeat.lock();
+        let now = Instant::now();
+        heartbeat.connected_at = Some(now);
+        heartbeat.first_connected_at.get_or_insert(now);
+        heartbeat.pending_since = None;
+        drop(heartbeat);
+
+        if reconnect {
+            self.inner.reconnects.fetch_add(1, Ordering::Relaxed);
+            #[cfg(feature = "metrics")]
+            crate::metrics::record_reconnect(resumed);
+        }
+        if resumed {
+            self.inner.resumes.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn record_heartbeat_sent(&self) {
+        let mut heartbeat = self.inner.heartbeat.lock();
+        heartbeat.pending_since = Some(Instant::now());
+        heartbeat.last_sent_at = Some(SystemTime::now());
+    }
+
+    pub(crate) fn record_heartbeat_ack(&self) {
+        let mut heartbeat = self.inner.heartbeat.lock();
+        if let Some(sent) = heartbeat.pending_since.take() {
+            heartbeat.latency = Some(sent.elapsed());
+        }
+        heartbeat.last_ack_at = Some(SystemTime::now());
+    }
+
+    pub(crate) fn record_event(&self) {
+        self.inner.events_received.fetch_add(1, Ordering::Relaxed);
+    }
+}
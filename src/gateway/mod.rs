@@ -1,9 +1,25 @@
+mod close_code;
 mod connection;
 mod gateway;
 mod heartbeat;
 mod identify;
+mod metrics;
+mod ratelimit;
+mod replay;
+mod sender;
+mod session;
+mod shard;
 
+pub use close_code::GatewayCloseCode;
 pub use connection::Connection;
-pub use gateway::Gateway;
+pub use gateway::{Gateway, DEFAULT_GATEWAY_URL};
 pub use heartbeat::Heartbeat;
-pub use identify::Identify;
+pub use identify::{
+    ConnectionProperties, Identify, IdentifyConfig, IdentifyPreset, ALL_INTENTS,
+    DEFAULT_CAPABILITIES,
+};
+pub use metrics::GatewayMetrics;
+pub use replay::{EventRecorder, ReplayGateway};
+pub use sender::GatewaySender;
+pub use session::SessionState;
+pub use shard::{ShardEvent, ShardManager, ShardRunner};
@@ -0,0 +1,72 @@
+use std::collections::VecDeque;
+use tokio::time::{Duration, Instant};
+
+/// Discord closes the connection (4008) after more than 120 sends in 60 seconds.
+const SEND_LIMIT: usize = 120;
+const SEND_WINDOW: Duration = Duration::from_secs(60);
+/// Sends kept free for heartbeats, so a burst of other payloads can't delay them.
+const HEARTBEAT_RESERVE: usize = 5;
+
+/// Sliding-window limiter for gateway sends
+#[derive(Debug)]
+pub(crate) struct SendRateLimiter {
+    sent: VecDeque<Instant>,
+}
+
+impl SendRateLimiter {
+    pub(crate) fn new() -> Self {
+        Self {
+            sent: VecDeque::with_capacity(SEND_LIMIT),
+        }
+    }
+
+    /// Records a send at `now` if the window allows it, otherwise returns how long to wait.
+    ///
+    /// Heartbeats may use the whole window; other payloads leave `HEARTBEAT_RESERVE` free.
+    pub(crate) fn try_acquire(&mut self, now: Instant, heartbeat: bool) -> Option<Duration> {
+        while self
+            .sent
+            .front()
+            .is_some_and(|sent| now.duration_since(*sent) >= SEND_WINDOW)
+        {
+            self.sent.pop_front();
+        }
+
+        let limit = if heartbeat {
+            SEND_LIMIT
+        } else {
+            SEND_LIMIT - HEARTBEAT_RESERVE
+        };
+        if self.sent.len() < limit {
+            self.sent.push_back(now);
+            return None;
+        }
+
+        // Wait until enough old sends leave the window to get back under the limit
+        let oldest_blocking = self.sent[self.sent.len() - limit];
+        Some((oldest_blocking + SEND_WINDOW).saturating_duration_since(now))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regular_sends_leave_room_for_heartbeats() {
+        let mut limiter = SendRateLimiter::new();
+        let start = Instant::now();
+
+        for _ in 0..SEND_LIMIT - HEARTBEAT_RESERVE {
+            assert_eq!(limiter.try_acquire(start, false), None);
+        }
+        assert!(limiter.try_acquire(start, false).is_some());
+        assert_eq!(limiter.try_acquire(start, true), None);
+
+        let wait = limiter
+            .try_acquire(start + Duration::from_secs(10), false)
+            .expect("window is still full");
+        assert_eq!(wait, Duration::from_secs(50));
+        assert_eq!(limiter.try_acquire(start + SEND_WINDOW, false), None);
+    }
+}
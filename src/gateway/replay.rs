@@ -0,0 +1,152 @@
+use crate::error::{Error, Result};
+use crate::model::User;
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::io;
+use std::path::Path;
+use tokio::fs::File;
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio::sync::{mpsc, oneshot};
+
+/// Writes the dispatch payloads received from the gateway to a file, one JSON per line
+///
+/// Enabled on a client with `Client::with_event_recording`. The file can be fed back
+/// to the handlers with `ReplayGateway` and `Client::replay`.
+///
+/// Lines are written by a background task, so recording never blocks the gateway loop.
+/// The file is flushed whenever the task has no line left to write.
+///
+/// Recordings hold everything the account receives (messages, tokens of voice servers,
+/// ...), so keep them private.
+#[derive(Debug, Clone)]
+pub struct EventRecorder {
+    tx: mpsc::UnboundedSender<RecorderCommand>,
+}
+
+#[derive(Debug)]
+enum RecorderCommand {
+    Line(Vec<u8>),
+    Flush(oneshot::Sender<io::Result<()>>),
+}
+
+impl EventRecorder {
+    /// Creates the file, truncating it if it already exists, and spawns its writer task
+    pub async fn create(path: impl AsRef<Path>) -> Result<Self> {
+        let file = File::create(path).await?;
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(write_lines(BufWriter::new(file), rx));
+        Ok(Self { tx })
+    }
+
+    /// Queues a gateway payload to be appended as one line
+    pub fn record(&self, payload: &Value) -> Result<()> {
+        let mut line = serde_json::to_vec(payload)?;
+        line.push(b'\n');
+        self.tx
+            .send(RecorderCommand::Line(line))
+            .map_err(|_| recorder_stopped())
+    }
+
+    /// Waits until every payload recorded so far is written to the file
+    pub async fn flush(&self) -> Result<()> {
+        let (done, written) = oneshot::channel();
+        self.tx
+            .send(RecorderCommand::Flush(done))
+            .map_err(|_| recorder_stopped())?;
+        written.await.map_err(|_| recorder_stopped())??;
+        Ok(())
+    }
+}
+
+fn recorder_stopped() -> Error {
+    io::Error::new(io::ErrorKind::BrokenPipe, "event recorder stopped").into()
+}
+
+/// Writer task of an `EventRecorder`, ending once every handle is dropped
+async fn write_lines(
+    mut writer: BufWriter<File>,
+    mut rx: mpsc::UnboundedReceiver<RecorderCommand>,
+) {
+    while let Some(command) = rx.recv().await {
+        match command {
+            RecorderCommand::Line(line) => {
+                let mut result = writer.write_all(&line).await;
+                if result.is_ok() && rx.is_empty() {
+                    result = writer.flush().await;
+                }
+                if let Err(e) = result {
+                    tracing::warn!("Failed to record gateway event: {}", e);
+                }
+            }
+            RecorderCommand::Flush(done) => {
+                let _ = done.send(writer.flush().await);
+            }
+        }
+    }
+    if let Err(e) = writer.flush().await {
+        tracing::warn!("Failed to flush gateway event recording: {}", e);
+    }
+}
+
+/// Gateway payloads read from a recording, replayed offline with `Client::replay`
+///
+/// # Example
+/// ```ignore
+/// let client = Client::new(token, MyHandler);
+/// let replay = ReplayGateway::open("events.ndjson").await?;
+/// client.replay(replay).await?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ReplayGateway {
+    events: VecDeque<Value>,
+}
+
+impl ReplayGateway {
+    /// Reads a recording written by `EventRecorder`
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let ndjson = tokio::fs::read_to_string(path).await?;
+        Self::from_ndjson(&ndjson)
+    }
+
+    /// Parses newline-delimited JSON payloads, skipping blank lines
+    pub fn from_ndjson(ndjson: &str) -> Result<Self> {
+        let events = ndjson
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<std::result::Result<_, _>>()?;
+        Ok(Self { events })
+    }
+
+    /// Replays the given payloads
+    pub fn from_events(events: impl IntoIterator<Item = Value>) -> Self {
+        Self {
+            events: events.into_iter().collect(),
+        }
+    }
+
+    /// Returns the next payload, or `None` once the recording is exhausted
+    pub fn next_event(&mut self) -> Option<Value> {
+        self.events.pop_front()
+    }
+
+    /// Number of payloads left to replay
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns `true` once every payload was replayed
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// The current user, read from the first READY payload left in the recording
+    pub fn current_user(&self) -> Result<User> {
+        let ready = self
+            .events
+            .iter()
+            .find(|event| event["t"] == "READY")
+            .ok_or_else(|| Error::InvalidArgument("recording has no READY event".to_string()))?;
+        Ok(serde_json::from_value(ready["d"]["user"].clone())?)
+    }
+}
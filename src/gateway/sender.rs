@@ -0,0 +1,170 @@
+use crate::error::{Error, Result};
+use crate::model::{EditPresence, EditVoiceState, GuildSubscription};
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+/// Guilds per GUILD_SUBSCRIPTIONS_BULK payload, keeping it under the gateway size limit
+pub(crate) const GUILD_SUBSCRIPTIONS_PER_PAYLOAD: usize = 25;
+
+/// Sends commands (presence updates, ...) to running gateway connections
+///
+/// Cheap to clone. Commands are queued and sent by the gateway between two received
+/// events, through the send rate limiter. Obtained with `Gateway::sender`,
+/// `ShardRunner::sender` or `Context::gateway`.
+#[derive(Debug, Clone, Default)]
+pub struct GatewaySender {
+    shards: Vec<mpsc::UnboundedSender<Value>>,
+    presence: Arc<Mutex<EditPresence>>,
+    voice_state: Arc<Mutex<Option<EditVoiceState>>>,
+}
+
+impl GatewaySender {
+    pub(crate) fn new(shards: Vec<mpsc::UnboundedSender<Value>>) -> Self {
+        Self {
+            shards,
+            presence: Arc::default(),
+            voice_state: Arc::default(),
+        }
+    }
+
+    /// Sends a raw payload (`{"op": ..., "d": ...}`) to every shard
+    ///
+    /// Fails if no gateway connection is running.
+    pub fn send(&self, payload: Value) -> Result<()> {
+        let mut sent = false;
+        for shard in &self.shards {
+            sent |= shard.send(payload.clone()).is_ok();
+        }
+        if sent {
+            Ok(())
+        } else {
+            Err(Error::GatewayConnection(
+                "no gateway connection is running".to_string(),
+            ))
+        }
+    }
+
+    /// Updates the presence of the current user (op 3)
+    pub fn update_presence(&self, presence: &EditPresence) -> Result<()> {
+        self.send(json!({ "op": 3, "d": presence }))?;
+        *self.presence.lock() = presence.clone();
+        Ok(())
+    }
+
+    /// Subscribes to the typing, thread, activity and member update events of a guild
+    ///
+    /// Done for every guild of READY; needed for guilds joined (or lurked) afterwards.
+    /// See `subscribe_guilds` to choose the events.
+    pub fn subscribe_guild(&self, guild_id: impl Into<String>) -> Result<()> {
+        self.subscribe_guilds([(guild_id.into(), GuildSubscription::all())])
+    }
+
+    /// Updates the subscriptions of many guilds (GUILD_SUBSCRIPTIONS_BULK, op 37)
+    pub fn subscribe_guilds<I, S>(&self, subscriptions: I) -> Result<()>
+    where
+        I: IntoIterator<Item = (S, GuildSubscription)>,
+        S: Into<String>,
+    {
+        let subscriptions: Vec<(String, GuildSubscription)> = subscriptions
+            .into_iter()
+            .map(|(guild_id, subscription)| (guild_id.into(), subscription))
+            .collect();
+        for payload in guild_subscriptions_payloads(subscriptions) {
+            self.send(payload)?;
+        }
+        Ok(())
+    }
+
+    /// Joins, moves or leaves (`channel_id` = `None`) a voice channel (op 4)
+    ///
+    /// `guild_id` is `None` for private calls.
+    pub fn update_voice_state(
+        &self,
+        guild_id: Option<&str>,
+        channel_id: Option<&str>,
+        self_mute: bool,
+        self_deaf: bool,
+    ) -> Result<()> {
+        let state = match channel_id {
+            Some(channel_id) => EditVoiceState::join(guild_id, channel_id),
+            None => EditVoiceState::leave(guild_id),
+        };
+        self.set_voice_state(&state.self_mute(self_mute).self_deaf(self_deaf))
+    }
+
+    /// Sends a voice state update (op 4), with the video flag
+    pub fn set_voice_state(&self, state: &EditVoiceState) -> Result<()> {
+        self.send(json!({ "op": 4, "d": state }))?;
+        let mut current = self.voice_state.lock();
+        // Moving to another channel ends the stream
+        let streaming = current.as_ref().is_some_and(|current| {
+            current.is_streaming() && current.channel_id() == state.channel_id()
+        });
+        *current = state
+            .channel_id()
+            .map(|_| state.clone().self_stream(streaming));
+        Ok(())
+    }
+
+    /// Starts a Go Live stream in a voice channel the current user is connected to (op 18)
+    pub fn create_stream(&self, guild_id: Option<&str>, channel_id: &str) -> Result<()> {
+        self.send(json!({
+            "op": 18,
+            "d": {
+                "type": if guild_id.is_some() { "guild" } else { "call" },
+                "guild_id": guild_id,
+                "channel_id": channel_id,
+                "preferred_region": null,
+            }
+        }))?;
+        self.set_streaming(channel_id, true);
+        Ok(())
+    }
+
+    /// Ends a Go Live stream (op 19); see `EditVoiceState::stream_key`
+    pub fn delete_stream(&self, stream_key: &str) -> Result<()> {
+        self.send(json!({ "op": 19, "d": { "stream_key": stream_key } }))?;
+        if let Some(channel_id) = stream_key.split(':').rev().nth(1) {
+            self.set_streaming(channel_id, false);
+        }
+        Ok(())
+    }
+
+    /// Returns the last voice state sent (`None` when not connected to a voice channel)
+    pub fn voice_state(&self) -> Option<EditVoiceState> {
+        self.voice_state.lock().clone()
+    }
+
+    fn set_streaming(&self, channel_id: &str, streaming: bool) {
+        let mut current = self.voice_state.lock();
+        if let Some(state) = current.as_mut() {
+            if state.channel_id() == Some(channel_id) {
+                *state = state.clone().self_stream(streaming);
+            }
+        }
+    }
+
+    /// Returns the last presence sent with `update_presence` (online without activity
+    /// before the first update)
+    pub fn presence(&self) -> EditPresence {
+        self.presence.lock().clone()
+    }
+}
+
+/// Builds the GUILD_SUBSCRIPTIONS_BULK payloads (op 37) of a list of subscriptions
+pub(crate) fn guild_subscriptions_payloads(
+    subscriptions: Vec<(String, GuildSubscription)>,
+) -> Vec<Value> {
+    subscriptions
+        .chunks(GUILD_SUBSCRIPTIONS_PER_PAYLOAD)
+        .map(|chunk| {
+            let subscriptions: serde_json::Map<String, Value> = chunk
+                .iter()
+                .map(|(guild_id, subscription)| (guild_id.clone(), json!(subscription)))
+                .collect();
+            json!({ "op": 37, "d": { "subscriptions": subscriptions } })
+        })
+        .collect()
+}
@@ -0,0 +1,49 @@
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Resumable gateway session data.
+///
+/// Persist this between process restarts and pass it back to
+/// `Gateway::connect_with_session` (or `ClientBuilder::with_session_state`)
+/// to RESUME the previous session instead of sending a fresh IDENTIFY.
+///
+/// # Example
+/// ```ignore
+/// use diself::gateway::SessionState;
+///
+/// if let Some(state) = client.session_state() {
+///     state.save("session.json").await?;
+/// }
+///
+/// // Later, in a new process
+/// let state = SessionState::load("session.json").await?;
+/// let client = Client::builder(token, handler).with_session_state(state).build();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionState {
+    /// Session ID received in READY
+    pub session_id: String,
+
+    /// Gateway URL to use when resuming (if Discord provided one)
+    pub resume_gateway_url: Option<String>,
+
+    /// Last sequence number received
+    pub sequence: Option<u64>,
+}
+
+impl SessionState {
+    /// Writes the session state as JSON to a file
+    pub async fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let json = serde_json::to_vec_pretty(self)?;
+        tokio::fs::write(path, json).await?;
+        Ok(())
+    }
+
+    /// Reads a session state previously written with `save`
+    pub async fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let bytes = tokio::fs::read(path).await?;
+        let state = serde_json::from_slice(&bytes)?;
+        Ok(state)
+    }
+}
@@ -0,0 +1,222 @@
+use crate::error::Result;
+use crate::gateway::{
+    ConnectionProperties, Gateway, GatewayMetrics, GatewaySender, IdentifyConfig,
+};
+use serde_json::Value;
+use tokio::sync::{mpsc, watch};
+use tokio::task::JoinHandle;
+use tokio::time::{self, Duration};
+
+/// Delay between two IDENTIFY, matching the default `max_concurrency` of 1 per 5 seconds.
+const DEFAULT_IDENTIFY_DELAY: Duration = Duration::from_secs(5);
+
+/// A gateway payload received by one shard
+#[derive(Debug, Clone)]
+pub struct ShardEvent {
+    /// ID of the shard that received the payload
+    pub shard_id: u32,
+    /// Raw gateway payload
+    pub payload: Value,
+}
+
+/// Runs several gateway connections, each identifying as one shard.
+///
+/// Shards are connected one after another, waiting `identify_delay` between two
+/// IDENTIFY so the identify rate limit is respected. Payloads of every shard are
+/// merged into one `ShardRunner`.
+///
+/// # Example
+/// ```ignore
+/// let mut runner = ShardManager::new(token, 4).spawn();
+/// while let Some(event) = runner.next().await {
+///     let event = event?;
+///     println!("shard {} received op {}", event.shard_id, event.payload["op"]);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ShardManager {
+    token: String,
+    properties: ConnectionProperties,
+    shard_count: u32,
+    identify_delay: Duration,
+    gateway_url: Option<String>,
+    identify: IdentifyConfig,
+}
+
+impl ShardManager {
+    /// Creates a manager running `shard_count` shards (min 1)
+    pub fn new(token: impl Into<String>, shard_count: u32) -> Self {
+        Self {
+            token: token.into(),
+            properties: ConnectionProperties::default_client(),
+            shard_count: shard_count.max(1),
+            identify_delay: DEFAULT_IDENTIFY_DELAY,
+            gateway_url: None,
+            identify: IdentifyConfig::default(),
+        }
+    }
+
+    /// Sets the connection properties sent in each shard IDENTIFY
+    pub fn with_properties(mut self, properties: ConnectionProperties) -> Self {
+        self.properties = properties;
+        self
+    }
+
+    /// Sets the delay between two shard IDENTIFY
+    pub fn with_identify_delay(mut self, identify_delay: Duration) -> Self {
+        self.identify_delay = identify_delay;
+        self
+    }
+
+    /// Connects every shard to a custom gateway URL (see `Gateway::connect_with_url`)
+    pub fn with_gateway_url(mut self, url: impl Into<String>) -> Self {
+        self.gateway_url = Some(url.into());
+        self
+    }
+
+    /// Sets the capabilities and intents sent in each shard IDENTIFY
+    pub fn with_identify_config(mut self, config: impl Into<IdentifyConfig>) -> Self {
+        self.identify = config.into();
+        self
+    }
+
+    /// Returns the number of shards
+    pub fn shard_count(&self) -> u32 {
+        self.shard_count
+    }
+
+    /// Returns the shard receiving events of a guild
+    pub fn shard_for_guild(&self, guild_id: &str) -> Option<u32> {
+        let id = guild_id.parse::<u64>().ok()?;
+        Some(((id >> 22) % self.shard_count as u64) as u32)
+    }
+
+    /// Connects every shard in the background and returns the merged event stream
+    pub fn spawn(self) -> ShardRunner {
+        let (events_tx, events_rx) = mpsc::channel(256);
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let metrics: Vec<GatewayMetrics> = (0..self.shard_count)
+            .map(|_| GatewayMetrics::default())
+            .collect();
+        let shard_metrics = metrics.clone();
+        let (commands_tx, mut commands): (Vec<_>, Vec<_>) = (0..self.shard_count)
+            .map(|_| mpsc::unbounded_channel())
+            .unzip();
+        let sender = GatewaySender::new(commands_tx.clone());
+
+        let supervisor = tokio::spawn(async move {
+            let mut stagger_shutdown = shutdown_rx.clone();
+            let mut shards = Vec::new();
+            for shard_id in 0..self.shard_count {
+                if shard_id > 0 {
+                    tokio::select! {
+                        _ = time::sleep(self.identify_delay) => {}
+                        _ = stagger_shutdown.changed() => break,
+                    }
+                }
+
+                let shard_commands = commands.remove(0);
+                let gateway = match Gateway::connect_inner(
+                    self.token.clone(),
+                    None,
+                    self.properties.clone(),
+                    Some([shard_id, self.shard_count]),
+                    shard_metrics[shard_id as usize].clone(),
+                    self.gateway_url.clone(),
+                    self.identify,
+                )
+                .await
+                {
+                    Ok(gateway) => gateway
+                        .with_commands(commands_tx[shard_id as usize].clone(), shard_commands),
+                    Err(e) => {
+                        tracing::error!("Shard {} failed to connect: {}", shard_id, e);
+                        let _ = events_tx.send(Err(e)).await;
+                        break;
+                    }
+                };
+                tracing::info!("Shard {}/{} connected", shard_id, self.shard_count);
+                shards.push(tokio::spawn(run_shard(
+                    shard_id,
+                    gateway,
+                    events_tx.clone(),
+                    shutdown_rx.clone(),
+                )));
+            }
+            for shard in shards {
+                let _ = shard.await;
+            }
+        });
+
+        ShardRunner {
+            events: events_rx,
+            shutdown: shutdown_tx,
+            supervisor: Some(supervisor),
+            metrics,
+            sender,
+        }
+    }
+}
+
+/// Merged event stream of the shards started by `ShardManager::spawn`
+pub struct ShardRunner {
+    events: mpsc::Receiver<Result<ShardEvent>>,
+    shutdown: watch::Sender<bool>,
+    supervisor: Option<JoinHandle<()>>,
+    metrics: Vec<GatewayMetrics>,
+    sender: GatewaySender,
+}
+
+impl ShardRunner {
+    /// Waits for the next payload of any shard
+    ///
+    /// Returns `None` once every shard has stopped.
+    pub async fn next(&mut self) -> Option<Result<ShardEvent>> {
+        self.events.recv().await
+    }
+
+    /// Returns the health metrics of a shard
+    pub fn metrics(&self, shard_id: u32) -> Option<&GatewayMetrics> {
+        self.metrics.get(shard_id as usize)
+    }
+
+    /// Returns a handle sending commands to every shard
+    pub fn sender(&self) -> GatewaySender {
+        self.sender.clone()
+    }
+
+    /// Closes every shard connection and waits for the shards to stop
+    pub async fn shutdown(&mut self) {
+        let _ = self.shutdown.send(true);
+        if let Some(supervisor) = self.supervisor.take() {
+            let _ = supervisor.await;
+        }
+    }
+}
+
+async fn run_shard(
+    shard_id: u32,
+    mut gateway: Gateway,
+    events: mpsc::Sender<Result<ShardEvent>>,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    loop {
+        let event = tokio::select! {
+            event = gateway.next_event() => event,
+            _ = shutdown.changed() => break,
+        };
+        let event = match event {
+            Ok(Some(payload)) => Ok(ShardEvent { shard_id, payload }),
+            Ok(None) => break,
+            Err(e) => Err(e),
+        };
+        let failed = event.is_err();
+        if events.send(event).await.is_err() || failed {
+            break;
+        }
+    }
+
+    if let Err(e) = gateway.shutdown().await {
+        tracing::warn!("Shard {} failed to close cleanly: {}", shard_id, e);
+    }
+}
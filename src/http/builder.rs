@@ -0,0 +1,110 @@
+use crate::error::Result;
+use crate::http::HttpClient;
+use reqwest::Client as ReqwestClient;
+use std::time::Duration;
+
+/// HTTP version used to talk to the API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HttpProtocol {
+    /// HTTP/1.1, or HTTP/2 when negotiated by the TLS backend (default)
+    #[default]
+    Auto,
+    /// HTTP/1.1 only
+    Http1,
+    /// HTTP/2 only, without negotiation (prior knowledge)
+    Http2,
+}
+
+/// Connection settings of an `HttpClient`, created with `HttpClient::builder`
+///
+/// # Example
+/// ```
+/// use diself::http::{HttpClient, HttpProtocol};
+/// use std::time::Duration;
+///
+/// let http = HttpClient::builder("token")
+///     .timeout(Duration::from_secs(120))
+///     .connect_timeout(Duration::from_secs(5))
+///     .protocol(HttpProtocol::Http2)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct HttpClientBuilder {
+    token: String,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    pool_max_idle_per_host: Option<usize>,
+    protocol: HttpProtocol,
+}
+
+impl HttpClientBuilder {
+    /// Total timeout of a request, from connecting to reading the whole body
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// Settings of `HttpClient::new`
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            token: token.into(),
+            timeout: Some(Self::DEFAULT_TIMEOUT),
+            connect_timeout: None,
+            pool_max_idle_per_host: None,
+            protocol: HttpProtocol::default(),
+        }
+    }
+
+    /// Sets the total timeout of a request (`DEFAULT_TIMEOUT` by default)
+    ///
+    /// It covers the upload of the body, so raise it to send large files.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Lets requests run as long as they need
+    pub fn no_timeout(mut self) -> Self {
+        self.timeout = None;
+        self
+    }
+
+    /// Sets the timeout of the connection phase only (none by default)
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets how many idle connections are kept open (unlimited by default)
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Sets the HTTP version used (`HttpProtocol::Auto` by default)
+    pub fn protocol(mut self, protocol: HttpProtocol) -> Self {
+        self.protocol = protocol;
+        self
+    }
+
+    /// Creates the client, failing if the TLS backend cannot be initialized
+    pub fn build(self) -> Result<HttpClient> {
+        let mut builder = ReqwestClient::builder()
+            .gzip(true)
+            .referer(true)
+            .redirect(reqwest::redirect::Policy::limited(10));
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        builder = match self.protocol {
+            HttpProtocol::Auto => builder,
+            HttpProtocol::Http1 => builder.http1_only(),
+            HttpProtocol::Http2 => builder.http2_prior_knowledge(),
+        };
+        Ok(HttpClient::from_reqwest(self.token, builder.build()?))
+    }
+}
@@ -1,4 +1,10 @@
-use crate::error::{CaptchaInfo, Error, Result};
+use crate::error::{CaptchaInfo, Error, MfaInfo, Result};
+use crate::gateway::ConnectionProperties;
+use crate::http::{
+    api_url, HttpClientBuilder, HttpRequest, HttpResponse, HttpTransport, ReqwestTransport,
+    RetryPolicy, API_VERSION,
+};
+use crate::model::{AllowedMentions, DISCORD_EPOCH_MS};
 use base64::Engine;
 use rand::RngCore;
 use reqwest::{Client as ReqwestClient, Method, StatusCode};
@@ -7,6 +13,7 @@ use serde_json::Value;
 use std::fmt::Write;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
+use tracing::Instrument;
 
 /// Type for captcha handler callback
 /// Takes captcha info and returns the solved captcha key
@@ -18,13 +25,86 @@ pub type CaptchaHandler = Arc<
         + Sync,
 >;
 
+/// Type for rate limit hook callback
+/// Called with every 429 response, before the retry policy applies
+pub type RateLimitHook = Arc<dyn Fn(&RateLimitInfo) + Send + Sync>;
+
+/// A 429 response received by the HTTP client
+#[derive(Debug, Clone)]
+pub struct RateLimitInfo {
+    /// HTTP method of the request
+    pub method: Method,
+    /// Route of the request, IDs replaced by `{id}` (e.g. `/channels/{id}/messages`)
+    pub route: String,
+    /// Time to wait before retrying
+    pub retry_after: Duration,
+    /// Whether the global rate limit was hit
+    pub global: bool,
+}
+
+/// Type for MFA handler callback
+/// Takes the MFA challenge and returns the code finishing it
+pub type MfaHandler = Arc<
+    dyn Fn(MfaInfo) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<MfaCode>> + Send>>
+        + Send
+        + Sync,
+>;
+
+/// A code finishing an MFA challenge, returned by an MFA handler
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MfaCode {
+    /// Type of the method used ("totp", "sms", "backup" or "password")
+    pub kind: String,
+
+    /// The code (or password)
+    pub code: String,
+}
+
+impl MfaCode {
+    /// A 6-digit code of an authenticator app
+    pub fn totp(code: impl Into<String>) -> Self {
+        Self::new("totp", code)
+    }
+
+    /// A code received by SMS (see `HttpClient::send_mfa_sms`)
+    pub fn sms(code: impl Into<String>) -> Self {
+        Self::new("sms", code)
+    }
+
+    /// An 8-character backup code
+    pub fn backup(code: impl Into<String>) -> Self {
+        Self::new("backup", code)
+    }
+
+    /// The password of the account
+    pub fn password(password: impl Into<String>) -> Self {
+        Self::new("password", password)
+    }
+
+    fn new(kind: &str, code: impl Into<String>) -> Self {
+        Self {
+            kind: kind.to_string(),
+            code: code.into(),
+        }
+    }
+}
+
 /// Minimal HTTP client for Discord API
 #[derive(Clone)]
 pub struct HttpClient {
     token: String,
     client: ReqwestClient,
+    transport: Arc<dyn HttpTransport>,
     captcha_handler: Option<CaptchaHandler>,
+    captcha_retries: u32,
+    mfa_handler: Option<MfaHandler>,
+    ratelimit_hook: Option<RateLimitHook>,
     heartbeat_session: Arc<parking_lot::RwLock<HeartbeatSession>>,
+    properties: Arc<ConnectionProperties>,
+    timezone: String,
+    retry_policy: RetryPolicy,
+    api_base_url: Option<Arc<str>>,
+    default_allowed_mentions: Option<Arc<Value>>,
 }
 
 #[derive(Debug)]
@@ -35,28 +115,121 @@ struct HeartbeatSession {
 
 impl HttpClient {
     const HEARTBEAT_SESSION_TTL: Duration = Duration::from_secs(30 * 60);
+    const CDN_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+    const DEFAULT_CAPTCHA_RETRIES: u32 = 1;
+    const CAPTCHA_SITE_URL: &'static str = "https://discord.com/";
 
-    /// Creates a new HTTP client
+    /// Creates a new HTTP client, with a 10s timeout per request (see `builder`)
     pub fn new(token: impl Into<String>) -> Self {
-        let client = ReqwestClient::builder()
-            .timeout(Duration::from_secs(10))
-            .gzip(true)
-            .referer(true)
-            .redirect(reqwest::redirect::Policy::limited(10))
+        Self::builder(token)
             .build()
-            .expect("Failed to create HTTP client");
+            .expect("Failed to create HTTP client")
+    }
+
+    /// Creates an HTTP client with custom timeouts, connection pool or HTTP version
+    pub fn builder(token: impl Into<String>) -> HttpClientBuilder {
+        HttpClientBuilder::new(token)
+    }
 
+    pub(crate) fn from_reqwest(token: String, client: ReqwestClient) -> Self {
         Self {
-            token: token.into(),
+            token,
+            transport: Arc::new(ReqwestTransport::new(client.clone())),
             client,
             captcha_handler: None,
+            captcha_retries: Self::DEFAULT_CAPTCHA_RETRIES,
+            mfa_handler: None,
+            ratelimit_hook: None,
             heartbeat_session: Arc::new(parking_lot::RwLock::new(HeartbeatSession {
                 id: generate_uuid_v4_like(),
                 created_at: Instant::now(),
             })),
+            properties: Arc::new(ConnectionProperties::default_client()),
+            timezone: "America/New_York".to_string(),
+            retry_policy: RetryPolicy::default(),
+            api_base_url: None,
+            default_allowed_mentions: None,
         }
     }
 
+    /// Sets the client properties advertised in `User-Agent`, `X-Super-Properties` and `X-Discord-Locale`.
+    ///
+    /// The gateway IDENTIFY uses the same properties when the client is started.
+    pub fn with_connection_properties(mut self, properties: ConnectionProperties) -> Self {
+        self.properties = Arc::new(properties);
+        self
+    }
+
+    /// Sets the IANA timezone sent in `X-Discord-Timezone` (e.g. "Europe/Paris")
+    pub fn with_timezone(mut self, timezone: impl Into<String>) -> Self {
+        self.timezone = timezone.into();
+        self
+    }
+
+    /// Sets how transient failures (429, 5xx, connection errors) are retried
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Sends API requests through another transport, e.g. a `MockTransport` in tests
+    ///
+    /// CDN downloads still go through `reqwest`.
+    pub fn with_transport(mut self, transport: impl HttpTransport + 'static) -> Self {
+        self.transport = Arc::new(transport);
+        self
+    }
+
+    /// Sends API requests to another base URL, e.g. a rate-limit proxy or a local mock server
+    ///
+    /// The URL replaces `BASE_URL` (`https://discord.com/api`), so the `/v10` version is still
+    /// appended. URLs built with `api_url` are rewritten, other absolute URLs are left untouched.
+    pub fn with_api_base_url(mut self, url: impl AsRef<str>) -> Self {
+        self.api_base_url = Some(Arc::from(url.as_ref().trim_end_matches('/')));
+        self
+    }
+
+    /// Sets the mention policy of every message sent or edited through this client
+    ///
+    /// Messages setting their own `allowed_mentions` keep it.
+    pub fn with_default_allowed_mentions(mut self, allowed_mentions: AllowedMentions) -> Self {
+        self.default_allowed_mentions = serde_json::to_value(allowed_mentions).ok().map(Arc::new);
+        self
+    }
+
+    /// Returns the API base URL requests are sent to
+    pub fn api_base_url(&self) -> &str {
+        self.api_base_url
+            .as_deref()
+            .unwrap_or(crate::http::BASE_URL)
+    }
+
+    /// Builds a versioned API URL on the base URL of this client
+    pub fn api_url(&self, endpoint: &str) -> String {
+        format!("{}/v{}{}", self.api_base_url(), API_VERSION, endpoint)
+    }
+
+    /// Returns the retry policy of this HTTP client
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry_policy
+    }
+
+    /// Returns the client properties advertised by this HTTP client
+    pub fn connection_properties(&self) -> &ConnectionProperties {
+        &self.properties
+    }
+
+    /// Returns the base64 encoded `X-Super-Properties` header value
+    pub fn super_properties_header(&self) -> String {
+        let mut properties = self.properties.super_properties();
+        properties["client_launch_id"] = Value::String(generate_uuid_v4_like());
+        properties["launch_signature"] =
+            Value::String("477bea01-90cb-422d-9a38-aaa66ed3e25e".to_string());
+        properties["client_heartbeat_session_id"] =
+            Value::String(self.rotate_heartbeat_session_if_needed());
+        base64::engine::general_purpose::STANDARD.encode(properties.to_string())
+    }
+
     /// Sets a captcha handler for this HTTP client
     pub fn with_captcha_handler<F, Fut>(mut self, handler: F) -> Self
     where
@@ -67,6 +240,75 @@ impl HttpClient {
         self
     }
 
+    /// Sets how many captchas are solved for one request (1 by default)
+    ///
+    /// When Discord rejects every solved key, the request fails with
+    /// `Error::CaptchaRejected`. `0` never calls the captcha handler.
+    pub fn with_captcha_retries(mut self, retries: u32) -> Self {
+        self.captcha_retries = retries;
+        self
+    }
+
+    /// Sets an MFA handler for this HTTP client
+    ///
+    /// The handler is called when a sensitive endpoint asks for MFA. The returned code
+    /// finishes the challenge and the request is retried with the MFA token.
+    pub fn with_mfa_handler<F, Fut>(mut self, handler: F) -> Self
+    where
+        F: Fn(MfaInfo) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<MfaCode>> + Send + 'static,
+    {
+        self.mfa_handler = Some(Arc::new(move |info| Box::pin(handler(info))));
+        self
+    }
+
+    /// Sets a hook called with every rate limited (429) response
+    ///
+    /// # Example
+    /// ```ignore
+    /// let http = HttpClient::new(token).with_ratelimit_hook(|info| {
+    ///     tracing::warn!("{} {} rate limited for {:?}", info.method, info.route, info.retry_after);
+    /// });
+    /// ```
+    pub fn with_ratelimit_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&RateLimitInfo) + Send + Sync + 'static,
+    {
+        self.ratelimit_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Finishes an MFA challenge and returns the MFA token (`POST /mfa/finish`). SEE: <https://docs.discord.food/authentication#verify-mfa-login>
+    ///
+    /// The token authorizes sensitive requests for a few minutes, in the
+    /// `X-Discord-MFA-Authorization` header.
+    pub async fn finish_mfa(&self, ticket: &str, code: MfaCode) -> Result<String> {
+        let mut request = self.base_request(Method::POST, &api_url("/mfa/finish"), None);
+        request.body = Some(serde_json::json!({
+            "ticket": ticket,
+            "mfa_type": code.kind,
+            "data": code.code,
+        }));
+        let response = self.send(request).await?;
+        let json = self.handle_response(response).await?;
+        json["token"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or(Error::InvalidPayload)
+    }
+
+    /// Sends the code of an MFA challenge by SMS and returns the redacted phone number
+    /// it was sent to (`POST /mfa/sms/send`). SEE: <https://docs.discord.food/authentication#send-mfa-sms>
+    pub async fn send_mfa_sms(&self, ticket: &str) -> Result<String> {
+        let response = self
+            .post(
+                api_url("/mfa/sms/send"),
+                serde_json::json!({ "ticket": ticket }),
+            )
+            .await?;
+        Ok(response["phone"].as_str().unwrap_or_default().to_string())
+    }
+
     /// Returns the current analytics heartbeat session id.
     ///
     /// The id rotates automatically every 30 minutes.
@@ -76,278 +318,391 @@ impl HttpClient {
 
     /// Sends a GET request
     pub async fn get(&self, url: impl AsRef<str>) -> Result<Value> {
-        self.request(Method::GET, url.as_ref(), None::<&()>).await
+        self.request(Method::GET, url.as_ref(), None::<&()>, None)
+            .await
     }
 
     /// Sends a POST request
     pub async fn post<T: Serialize>(&self, url: impl AsRef<str>, body: T) -> Result<Value> {
-        self.request(Method::POST, url.as_ref(), Some(&body)).await
+        self.request(Method::POST, url.as_ref(), Some(&body), None)
+            .await
     }
 
     /// Sends a PATCH request
     pub async fn patch<T: Serialize>(&self, url: impl AsRef<str>, body: T) -> Result<Value> {
-        self.request(Method::PATCH, url.as_ref(), Some(&body)).await
+        self.request(Method::PATCH, url.as_ref(), Some(&body), None)
+            .await
     }
 
     /// Sends a PUT request
     pub async fn put<T: Serialize>(&self, url: impl AsRef<str>, body: T) -> Result<Value> {
-        self.request(Method::PUT, url.as_ref(), Some(&body)).await
+        self.request(Method::PUT, url.as_ref(), Some(&body), None)
+            .await
     }
 
     /// Sends a DELETE request
     pub async fn delete(&self, url: impl AsRef<str>) -> Result<Value> {
-        self.request(Method::DELETE, url.as_ref(), None::<&()>)
+        self.request(Method::DELETE, url.as_ref(), None::<&()>, None)
+            .await
+    }
+
+    /// Sends a POST request with an optional audit log reason (`X-Audit-Log-Reason`)
+    pub async fn post_with_reason<T: Serialize>(
+        &self,
+        url: impl AsRef<str>,
+        body: T,
+        reason: Option<&str>,
+    ) -> Result<Value> {
+        self.request(Method::POST, url.as_ref(), Some(&body), reason)
+            .await
+    }
+
+    /// Sends a PATCH request with an optional audit log reason (`X-Audit-Log-Reason`)
+    pub async fn patch_with_reason<T: Serialize>(
+        &self,
+        url: impl AsRef<str>,
+        body: T,
+        reason: Option<&str>,
+    ) -> Result<Value> {
+        self.request(Method::PATCH, url.as_ref(), Some(&body), reason)
+            .await
+    }
+
+    /// Sends a PUT request with an optional audit log reason (`X-Audit-Log-Reason`)
+    pub async fn put_with_reason<T: Serialize>(
+        &self,
+        url: impl AsRef<str>,
+        body: T,
+        reason: Option<&str>,
+    ) -> Result<Value> {
+        self.request(Method::PUT, url.as_ref(), Some(&body), reason)
+            .await
+    }
+
+    /// Sends a DELETE request with an optional audit log reason (`X-Audit-Log-Reason`)
+    pub async fn delete_with_reason(
+        &self,
+        url: impl AsRef<str>,
+        reason: Option<&str>,
+    ) -> Result<Value> {
+        self.request(Method::DELETE, url.as_ref(), None::<&()>, reason)
             .await
     }
 
-    /// Generic HTTP request
+    /// Sends an unauthenticated GET request to a CDN URL and returns the raw response.
+    ///
+    /// The body is not read, so callers can stream it chunk by chunk. Downloads get a
+    /// longer timeout than API calls since the client timeout covers the whole body.
+    pub(crate) async fn cdn_get(&self, url: impl AsRef<str>) -> Result<reqwest::Response> {
+        let response = self
+            .client
+            .get(url.as_ref())
+            .header("User-Agent", &self.properties.browser_user_agent)
+            .timeout(Self::CDN_TIMEOUT)
+            .send()
+            .await?;
+        Ok(response)
+    }
+
+    /// Generic HTTP request, traced in an `http_request` span (method, route, status, retries)
     async fn request<T: Serialize>(
         &self,
         method: Method,
         url: &str,
         body: Option<&T>,
+        reason: Option<&str>,
     ) -> Result<Value> {
-        self.request_with_captcha(method, url, body, None).await
+        let span = tracing::debug_span!(
+            "http_request",
+            %method,
+            route = %route(url),
+            status = tracing::field::Empty,
+            retries = 0u32,
+        );
+        self.request_with_retries(method, url, body, reason)
+            .instrument(span)
+            .await
     }
 
-    /// Generic HTTP request with optional captcha key
+    /// Generic HTTP request, retried according to the retry policy
+    async fn request_with_retries<T: Serialize>(
+        &self,
+        method: Method,
+        url: &str,
+        body: Option<&T>,
+        reason: Option<&str>,
+    ) -> Result<Value> {
+        let started = Instant::now();
+        let mut attempt = 1;
+        loop {
+            let error = match self
+                .request_with_captcha(method.clone(), url, body, reason)
+                .await
+            {
+                Err(error) => error,
+                result => return result,
+            };
+            let Some(delay) =
+                self.retry_policy
+                    .retry_delay(&method, &error, attempt, started.elapsed())
+            else {
+                return Err(error);
+            };
+            tracing::warn!(
+                "{} {} failed (attempt {}): {}, retrying in {:?}",
+                method,
+                route(url),
+                attempt,
+                error,
+                delay
+            );
+            tokio::time::sleep(delay).await;
+            tracing::Span::current().record("retries", attempt);
+            attempt += 1;
+        }
+    }
+
+    /// Generic HTTP request, solving the captchas asked with the captcha handler
     async fn request_with_captcha<T: Serialize>(
         &self,
         method: Method,
         url: &str,
         body: Option<&T>,
-        captcha_key: Option<String>,
+        reason: Option<&str>,
     ) -> Result<Value> {
         // Add a small delay to mimic human behavior (anti-bot measure)
         tokio::time::sleep(Duration::from_millis(100)).await;
 
-        // Keep this heartbeat id fresh for internal analytics/debug use.
-        let heartbeat_session_id = self.rotate_heartbeat_session_if_needed();
-
-        let mut request = self
-            .client
-            .request(method.clone(), url)
-            .header("Authorization", &self.token)
-            .header("User-Agent",   "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/145.0.0.0 Safari/537.36")
-            .header("Accept", "*/*")
-            .header("Accept-Language", "en-US,en;q=0.9")
-            .header("Content-Type", "application/json")
-            .header("Origin", "https://discord.com")
-            .header("Referer", "https://discord.com/channels/@me")
-            .header("Sec-Fetch-Dest", "empty")
-            .header("Sec-Fetch-Mode", "cors")
-            .header("Sec-Fetch-Site", "same-origin")
-            .header("X-Discord-Locale", "en-US")
-            .header("X-Discord-Timezone", "America/New_York");
-
-        let x_super_properties = serde_json::json!({
-          "os": "Mac OS X",
-          "browser": "Chrome",
-          "device": "",
-          "system_locale": "en-US",
-          "browser_user_agent": "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/145.0.0.0 Safari/537.36",
-          "browser_version": "145.0.0.0",
-          "os_version": "10.15.7",
-          "referrer": "https://www.google.com/",
-          "referring_domain": "www.google.com",
-          "referrer_current": "https://www.google.com/",
-          "referring_domain_current": "www.google.com",
-          "search_engine_current": "google",
-          "release_channel": "stable",
-          "client_build_number": 500334,
-          "client_event_source": null,
-          "has_client_mods": false,
-          "client_launch_id": generate_uuid_v4_like(),
-          "launch_signature": "477bea01-90cb-422d-9a38-aaa66ed3e25e",
-          "client_heartbeat_session_id": heartbeat_session_id
-        });
-
-        request = request.header(
-            "X-Super-Properties",
-            base64::engine::general_purpose::STANDARD
-                .encode(serde_json::to_string(&x_super_properties).unwrap()),
-        );
-
-        // Prepare body with captcha key if provided
-        if let Some(body) = body {
-            let mut json_body = serde_json::to_value(body)?;
-            if let Some(ref key) = captcha_key {
-                if let Some(obj) = json_body.as_object_mut() {
-                    obj.insert("captcha_key".to_string(), Value::String(key.clone()));
-                }
+        let body = match body {
+            Some(body) => {
+                let mut json_body = serde_json::to_value(body)?;
+                self.apply_default_allowed_mentions(&method, url, &mut json_body);
+                Some(json_body)
             }
-            request = request.json(&json_body);
-        } else if let Some(key) = captcha_key {
-            // No body but we have captcha key
-            request = request.json(&serde_json::json!({ "captcha_key": key }));
-        }
-
-        let response = request.send().await?;
+            None => None,
+        };
+        let mut request = self.base_request(method.clone(), url, reason);
+        request.body = body.clone();
+        let response = self.send(request).await?;
 
-        // Handle response, check for captcha
         match self.handle_response(response).await {
             Err(Error::CaptchaRequired(captcha_info)) => {
-                // Try to solve captcha if handler is available
-                if let Some(ref handler) = self.captcha_handler {
-                    tracing::info!("Captcha required, calling handler...");
-                    // Clone the fields we need before moving captcha_info
-                    let session_id = captcha_info.captcha_session_id.clone();
-                    let rqtoken = captcha_info.captcha_rqtoken.clone();
-                    let solved_key = handler(captcha_info).await?;
-                    tracing::info!("Captcha solved, retrying request...");
-                    // Retry the request with the captcha key using Box::pin for recursion
-                    let body_json = if let Some(b) = body {
-                        Some(serde_json::to_value(b)?)
-                    } else {
-                        None
-                    };
-                    return Box::pin(self.request_with_captcha_value(
-                        method,
-                        url,
-                        body_json,
-                        Some(solved_key),
-                        session_id,
-                        rqtoken,
-                    ))
-                    .await;
-                } else {
-                    // No handler available
-                    Err(Error::CaptchaRequired(captcha_info))
-                }
+                self.solve_captcha(captcha_info, &method, url, body, reason)
+                    .await
+            }
+            Err(Error::MfaRequired(mfa_info)) => {
+                let Some(ref handler) = self.mfa_handler else {
+                    return Err(Error::MfaRequired(mfa_info));
+                };
+                tracing::info!("MFA required, calling handler...");
+                let ticket = mfa_info.ticket.clone();
+                let code = handler(mfa_info).await?;
+                let token = self.finish_mfa(&ticket, code).await?;
+                tracing::info!("MFA finished, retrying request...");
+
+                let mut request = self.base_request(method.clone(), url, reason);
+                request
+                    .headers
+                    .push(("X-Discord-MFA-Authorization".to_string(), token));
+                request.body = body;
+                let response = self.send(request).await?;
+                self.handle_response(response).await
             }
             result => result,
         }
     }
 
-    /// Helper for recursion with owned values
-    async fn request_with_captcha_value(
+    /// Retries a request with the keys solved by the captcha handler, within the retry budget
+    async fn solve_captcha(
         &self,
-        method: Method,
+        mut captcha_info: Box<CaptchaInfo>,
+        method: &Method,
         url: &str,
         body: Option<Value>,
-        captcha_key: Option<String>,
-        captcha_session_id: Option<String>,
-        captcha_rqtoken: Option<String>,
+        reason: Option<&str>,
     ) -> Result<Value> {
-        // Keep this heartbeat id fresh for internal analytics/debug use.
-        let heartbeat_session_id = self.rotate_heartbeat_session_if_needed();
+        let Some(ref handler) = self.captcha_handler else {
+            return Err(Error::CaptchaRequired(captcha_info));
+        };
+        if self.captcha_retries == 0 {
+            return Err(Error::CaptchaRequired(captcha_info));
+        }
 
-        let mut request = self
-            .client
-            .request(method, url)
-            .header("Authorization", &self.token)
-            .header("User-Agent",   "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/145.0.0.0 Safari/537.36")
-            .header("Accept", "*/*")
-            .header("Accept-Language", "en-US,en;q=0.9")
-            .header("Content-Type", "application/json")
-            .header("Origin", "https://discord.com")
-            .header("Referer", "https://discord.com/channels/@me")
-            .header("Sec-Fetch-Dest", "empty")
-            .header("Sec-Fetch-Mode", "cors")
-            .header("Sec-Fetch-Site", "same-origin")
-            .header("X-Discord-Locale", "en-US")
-            .header("X-Discord-Timezone", "America/New_York")
-            .header("X-Captcha-Key", captcha_key.clone().unwrap_or_default());
-
-        // Add X-Super-Properties header (critical for Discord API)
-        let x_super_properties = serde_json::json!({
-          "os": "Mac OS X",
-          "browser": "Chrome",
-          "device": "",
-          "system_locale": "en-US",
-          "browser_user_agent": "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/145.0.0.0 Safari/537.36",
-          "browser_version": "145.0.0.0",
-          "os_version": "10.15.7",
-          "referrer": "https://www.google.com/",
-          "referring_domain": "www.google.com",
-          "referrer_current": "https://www.google.com/",
-          "referring_domain_current": "www.google.com",
-          "search_engine_current": "google",
-          "release_channel": "stable",
-          "client_build_number": 500334,
-          "client_event_source": null,
-          "has_client_mods": false,
-          "client_launch_id": generate_uuid_v4_like(),
-          "launch_signature": "477bea01-90cb-422d-9a38-aaa66ed3e25e",
-          "client_heartbeat_session_id": heartbeat_session_id
-        });
-
-        request = request.header(
-            "X-Super-Properties",
-            base64::engine::general_purpose::STANDARD
-                .encode(serde_json::to_string(&x_super_properties).unwrap()),
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            tracing::info!(
+                "Captcha required, calling handler (attempt {}/{})...",
+                attempts,
+                self.captcha_retries
+            );
+            captcha_info.site_url = Some(Self::CAPTCHA_SITE_URL.to_string());
+            let mut request = self.base_request(method.clone(), url, reason);
+            let key = handler((*captcha_info).clone()).await?;
+            apply_captcha_key(&mut request, body.clone(), key, &captcha_info);
+
+            let response = self.send(request).await?;
+            match self.handle_response(response).await {
+                Err(Error::CaptchaRequired(next)) => {
+                    tracing::warn!("Captcha key rejected: {:?}", next.captcha_key);
+                    if attempts >= self.captcha_retries {
+                        return Err(Error::CaptchaRejected {
+                            info: next,
+                            attempts,
+                        });
+                    }
+                    captcha_info = next;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Sends a request through the transport, recording the status on the current span
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let (method, url) = (request.method.clone(), request.url.clone());
+        #[cfg(feature = "metrics")]
+        let started = Instant::now();
+        let response = self.transport.send(request).await?;
+        tracing::Span::current().record("status", response.status);
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_http_response(
+            &method,
+            &route(&url),
+            response.status,
+            started.elapsed(),
         );
 
-        if let Some(session_id) = captcha_session_id {
-            request = request.header("X-Captcha-Session-Id", session_id);
+        if response.status == 429 {
+            let body = serde_json::from_slice::<Value>(&response.body).unwrap_or_default();
+            let retry_after = body["retry_after"].as_f64().unwrap_or(1.0).max(0.0);
+            let info = RateLimitInfo {
+                method,
+                route: route(&url),
+                retry_after: Duration::from_secs_f64(retry_after),
+                global: body["global"].as_bool().unwrap_or(false),
+            };
+            #[cfg(feature = "metrics")]
+            crate::metrics::record_rate_limit(&info.route, info.global);
+            if let Some(hook) = &self.ratelimit_hook {
+                hook(&info);
+            }
         }
-        if let Some(rqtoken) = captcha_rqtoken {
-            request = request.header("X-Captcha-RqToken", rqtoken);
+        Ok(response)
+    }
+
+    /// Adds the default mention policy to message bodies that don't set one
+    fn apply_default_allowed_mentions(&self, method: &Method, url: &str, body: &mut Value) {
+        let Some(allowed_mentions) = &self.default_allowed_mentions else {
+            return;
+        };
+        if !is_message_endpoint(method, url) {
+            return;
         }
+        if let Some(obj) = body.as_object_mut() {
+            obj.entry("allowed_mentions")
+                .or_insert_with(|| allowed_mentions.as_ref().clone());
+        }
+    }
 
-        // Prepare body with captcha key if provided
-        if let Some(mut json_body) = body {
-            if let Some(ref key) = captcha_key {
-                if let Some(obj) = json_body.as_object_mut() {
-                    obj.insert("captcha_key".to_string(), Value::String(key.clone()));
-                }
-            }
-            request = request.json(&json_body);
-        } else if let Some(key) = captcha_key {
-            // No body but we have captcha key
-            request = request.json(&serde_json::json!({ "captcha_key": key }));
+    /// Builds a request with the client headers and the optional audit log reason
+    fn base_request(&self, method: Method, url: &str, reason: Option<&str>) -> HttpRequest {
+        let mut headers = self.client_headers();
+        if let Some(reason) = reason {
+            // Discord expects non-ASCII reasons to be percent-encoded
+            headers.push((
+                "X-Audit-Log-Reason".to_string(),
+                urlencoding::encode(reason).into_owned(),
+            ));
+        }
+        HttpRequest {
+            method,
+            url: self.resolve_url(url),
+            headers,
+            body: None,
+        }
+    }
+
+    /// Moves URLs built with the default `api_url` onto the configured base URL
+    fn resolve_url(&self, url: &str) -> String {
+        if self.api_base_url.is_none() {
+            return url.to_string();
+        }
+        match url.strip_prefix(&api_url("")) {
+            Some(endpoint) => self.api_url(endpoint),
+            None => url.to_string(),
         }
+    }
 
-        let response = request.send().await?;
-        self.handle_response(response).await
+    /// Returns the authorization and browser-like client headers of a request
+    fn client_headers(&self) -> Vec<(String, String)> {
+        [
+            ("Authorization", self.token.clone()),
+            ("User-Agent", self.properties.browser_user_agent.clone()),
+            ("Accept", "*/*".to_string()),
+            (
+                "Accept-Language",
+                format!("{},en;q=0.9", self.properties.system_locale),
+            ),
+            ("Content-Type", "application/json".to_string()),
+            ("Origin", "https://discord.com".to_string()),
+            ("Referer", "https://discord.com/channels/@me".to_string()),
+            ("Sec-Fetch-Dest", "empty".to_string()),
+            ("Sec-Fetch-Mode", "cors".to_string()),
+            ("Sec-Fetch-Site", "same-origin".to_string()),
+            ("X-Discord-Locale", self.properties.system_locale.clone()),
+            ("X-Discord-Timezone", self.timezone.clone()),
+            ("X-Super-Properties", self.super_properties_header()),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect()
     }
 
     /// Handles HTTP response
-    async fn handle_response(&self, response: reqwest::Response) -> Result<Value> {
-        let status = response.status();
+    async fn handle_response(&self, response: HttpResponse) -> Result<Value> {
+        let status = StatusCode::from_u16(response.status).map_err(|_| {
+            Error::GatewayConnection(format!("Invalid HTTP status {}", response.status))
+        })?;
 
         if status.is_success() {
             // If no content (204 No Content), return null
-            if status == StatusCode::NO_CONTENT {
+            if status == StatusCode::NO_CONTENT || response.body.is_empty() {
                 return Ok(Value::Null);
             }
 
-            let json = response.json::<Value>().await?;
+            let json = serde_json::from_slice::<Value>(&response.body)?;
             Ok(json)
         } else if status == StatusCode::TOO_MANY_REQUESTS {
             // Rate limit
-            let json = response.json::<Value>().await?;
+            let json = serde_json::from_slice::<Value>(&response.body)?;
             let retry_after = json["retry_after"].as_f64().unwrap_or(1.0);
             Err(Error::RateLimit { retry_after })
         } else if status == StatusCode::BAD_REQUEST {
             // Check if it's a captcha error
-            let json = response.json::<Value>().await?;
-
-            if json.get("captcha_sitekey").is_some() {
-                // It's a captcha error, try to deserialize
-                match serde_json::from_value::<CaptchaInfo>(json.clone()) {
-                    Ok(captcha_info) => Err(Error::CaptchaRequired(captcha_info)),
-                    Err(_) => {
-                        // Failed to parse captcha info, treat as regular error
-                        Err(Error::GatewayConnection(format!(
-                            "HTTP {} - {}",
-                            status, json
-                        )))
-                    }
-                }
-            } else {
+            let json = serde_json::from_slice::<Value>(&response.body)?;
+            match json.get("captcha_sitekey") {
+                Some(_) => match serde_json::from_value::<CaptchaInfo>(json.clone()) {
+                    Ok(captcha_info) => Err(Error::CaptchaRequired(Box::new(captcha_info))),
+                    // Failed to parse captcha info, treat as regular error
+                    Err(_) => Err(Error::from_status(status.as_u16(), json.to_string())),
+                },
                 // Regular 400 error
-                Err(Error::GatewayConnection(format!(
-                    "HTTP {} - {}",
-                    status, json
-                )))
+                None => Err(Error::from_status(status.as_u16(), json.to_string())),
+            }
+        } else if status == StatusCode::UNAUTHORIZED {
+            // Sensitive endpoints answer 401 with an MFA challenge
+            let json = serde_json::from_slice::<Value>(&response.body).unwrap_or_default();
+            match serde_json::from_value::<MfaInfo>(json["mfa"].clone()) {
+                Ok(mfa_info) => Err(Error::MfaRequired(mfa_info)),
+                Err(_) => Err(Error::from_status(
+                    status.as_u16(),
+                    String::from_utf8_lossy(&response.body).into_owned(),
+                )),
             }
         } else {
-            let text = response.text().await.unwrap_or_default();
-            Err(Error::GatewayConnection(format!(
-                "HTTP {} - {}",
-                status, text
-            )))
+            let body = String::from_utf8_lossy(&response.body).into_owned();
+            Err(Error::from_status(status.as_u16(), body))
         }
     }
 
@@ -368,6 +723,91 @@ impl HttpClient {
     }
 }
 
+/// Route of an URL for logs and metrics: no host nor query string, IDs replaced by `{id}`
+/// and other variables (invite and gift codes, emojis, tokens) by a placeholder
+fn route(url: &str) -> String {
+    let path = url.split('?').next().unwrap_or(url);
+    let path = match path.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("", |start| &rest[start..]),
+        None => path,
+    };
+    let path = path.strip_prefix("/api").unwrap_or(path);
+    let version = format!("/v{}", API_VERSION);
+    let path = path.strip_prefix(version.as_str()).unwrap_or(path);
+
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in path.split('/') {
+        let previous = segments.last().copied();
+        let before_previous = segments.len().checked_sub(2).map(|i| segments[i]);
+        let segment = if segment.is_empty() {
+            segment
+        } else if segment.bytes().all(|b| b.is_ascii_digit()) {
+            "{id}"
+        } else if let Some(placeholder) = previous.and_then(variable_after) {
+            placeholder
+        } else if previous == Some("{id}")
+            && matches!(before_previous, Some("webhooks" | "interactions"))
+        {
+            "{token}"
+        } else {
+            segment
+        };
+        segments.push(segment);
+    }
+    segments.join("/")
+}
+
+/// Placeholder of the variable following a path segment, if it is not an ID
+fn variable_after(segment: &str) -> Option<&'static str> {
+    match segment {
+        "invites" | "gift-codes" | "templates" => Some("{code}"),
+        "reactions" => Some("{emoji}"),
+        _ => None,
+    }
+}
+
+/// Adds a solved captcha to a request: `X-Captcha-*` headers and `captcha_key` in the body
+fn apply_captcha_key(
+    request: &mut HttpRequest,
+    body: Option<Value>,
+    key: String,
+    captcha: &CaptchaInfo,
+) {
+    request
+        .headers
+        .push(("X-Captcha-Key".to_string(), key.clone()));
+    if let Some(session_id) = &captcha.captcha_session_id {
+        request
+            .headers
+            .push(("X-Captcha-Session-Id".to_string(), session_id.clone()));
+    }
+    if let Some(rqtoken) = &captcha.captcha_rqtoken {
+        request
+            .headers
+            .push(("X-Captcha-RqToken".to_string(), rqtoken.clone()));
+    }
+
+    let mut body = body.unwrap_or_else(|| serde_json::json!({}));
+    if let Some(obj) = body.as_object_mut() {
+        obj.insert("captcha_key".to_string(), Value::String(key));
+    }
+    request.body = Some(body);
+}
+
+/// Returns `true` for `POST /channels/{id}/messages` and `PATCH /channels/{id}/messages/{id}`
+fn is_message_endpoint(method: &Method, url: &str) -> bool {
+    let path = url.split('?').next().unwrap_or_default();
+    let segments: Vec<&str> = path.trim_end_matches('/').rsplit('/').collect();
+    let is_id = |segment: &str| !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_digit());
+    match segments.as_slice() {
+        ["messages", channel_id, "channels", ..] => *method == Method::POST && is_id(channel_id),
+        [message_id, "messages", channel_id, "channels", ..] => {
+            *method == Method::PATCH && is_id(message_id) && is_id(channel_id)
+        }
+        _ => false,
+    }
+}
+
 fn generate_uuid_v4_like() -> String {
     let mut bytes = [0_u8; 16];
     rand::thread_rng().fill_bytes(&mut bytes);
@@ -385,3 +825,13 @@ fn generate_uuid_v4_like() -> String {
     }
     out
 }
+
+/// Generates a snowflake-shaped nonce for the current time
+pub fn generate_nonce() -> String {
+    let now_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(DISCORD_EPOCH_MS);
+    let increment = rand::thread_rng().next_u32() as u64 & 0x3f_ffff;
+    ((now_ms.saturating_sub(DISCORD_EPOCH_MS) << 22) | increment).to_string()
+}
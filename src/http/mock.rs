@@ -0,0 +1,123 @@
+use crate::error::Result;
+use crate::http::{api_url, HttpRequest, HttpResponse, HttpTransport};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use reqwest::Method;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// In-memory `HttpTransport` returning canned responses, for unit tests
+///
+/// Routes are matched on the method and the API path (`/users/@me`). A route
+/// path without `?` matches any query string. The most recently added matching
+/// route wins; unmatched requests get a 404.
+///
+/// # Example
+/// ```
+/// use diself::http::{HttpClient, Method, MockTransport};
+/// use serde_json::json;
+///
+/// # tokio_test::block_on(async {
+/// let mock = MockTransport::new();
+/// mock.on(Method::GET, "/users/@me", json!({ "id": "1", "username": "me" }));
+///
+/// let http = HttpClient::new("token").with_transport(mock.clone());
+/// let me = http.get(diself::http::api_url("/users/@me")).await.unwrap();
+/// assert_eq!(me["username"], "me");
+/// assert_eq!(mock.requests().len(), 1);
+/// # });
+/// ```
+#[derive(Clone, Default)]
+pub struct MockTransport {
+    state: Arc<Mutex<MockState>>,
+}
+
+#[derive(Default)]
+struct MockState {
+    routes: Vec<MockRoute>,
+    requests: Vec<HttpRequest>,
+}
+
+struct MockRoute {
+    method: Method,
+    path: String,
+    response: HttpResponse,
+}
+
+impl MockTransport {
+    /// Creates a transport without any route
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answers `method path` with a `200` JSON response
+    pub fn on(&self, method: Method, path: impl Into<String>, body: Value) -> &Self {
+        self.respond(method, path, HttpResponse::json(200, &body))
+    }
+
+    /// Answers `method path` with any response (errors, `204`, rate limits, ...)
+    pub fn respond(
+        &self,
+        method: Method,
+        path: impl Into<String>,
+        response: HttpResponse,
+    ) -> &Self {
+        self.state.lock().routes.push(MockRoute {
+            method,
+            path: path.into(),
+            response,
+        });
+        self
+    }
+
+    /// Returns every request sent so far, oldest first
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.state.lock().requests.clone()
+    }
+
+    /// Returns the last request sent
+    pub fn last_request(&self) -> Option<HttpRequest> {
+        self.state.lock().requests.last().cloned()
+    }
+
+    /// Removes every route and recorded request
+    pub fn reset(&self) {
+        let mut state = self.state.lock();
+        state.routes.clear();
+        state.requests.clear();
+    }
+}
+
+#[async_trait]
+impl HttpTransport for MockTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let base = api_url("");
+        let path = request
+            .url
+            .strip_prefix(&base)
+            .unwrap_or(&request.url)
+            .to_string();
+
+        let mut state = self.state.lock();
+        let response = state
+            .routes
+            .iter()
+            .rev()
+            .find(|route| route.method == request.method && route_matches(&route.path, &path))
+            .map(|route| route.response.clone())
+            .unwrap_or_else(|| {
+                let message = format!("No mock response for {} {}", request.method, path);
+                HttpResponse::json(404, &json!({ "message": message, "code": 0 }))
+            });
+        state.requests.push(request);
+        Ok(response)
+    }
+}
+
+fn route_matches(route: &str, path: &str) -> bool {
+    if route.contains('?') {
+        route == path
+    } else {
+        route == path.split('?').next().unwrap_or_default()
+    }
+}
@@ -1,6 +1,17 @@
+mod builder;
 mod client;
+mod mock;
+mod pagination;
+mod retry;
+mod transport;
 
-pub use client::HttpClient;
+pub use builder::{HttpClientBuilder, HttpProtocol};
+pub use client::{generate_nonce, HttpClient, MfaCode, RateLimitInfo};
+pub use mock::MockTransport;
+pub(crate) use pagination::{paginate, Direction};
+pub use reqwest::Method;
+pub use retry::RetryPolicy;
+pub use transport::{HttpRequest, HttpResponse, HttpTransport, ReqwestTransport};
 
 /// Discord API version
 pub const API_VERSION: u8 = 10;
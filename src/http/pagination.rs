@@ -0,0 +1,96 @@
+use crate::error::Result;
+use crate::http::HttpClient;
+use futures::Stream;
+use serde::de::DeserializeOwned;
+use std::collections::VecDeque;
+
+/// Cursor direction used to fetch the next page
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Direction {
+    /// Follows `before`, walking towards older items
+    Before,
+    /// Follows `after`, walking towards newer items
+    After,
+}
+
+impl Direction {
+    fn param(self) -> &'static str {
+        match self {
+            Direction::Before => "before",
+            Direction::After => "after",
+        }
+    }
+}
+
+struct Paginator<T> {
+    http: HttpClient,
+    url: String,
+    direction: Direction,
+    page_size: u32,
+    cursor: Option<String>,
+    cursor_of: fn(&T) -> String,
+    buffer: VecDeque<T>,
+    done: bool,
+}
+
+impl<T: DeserializeOwned> Paginator<T> {
+    fn page_url(&self) -> String {
+        let separator = if self.url.contains('?') { '&' } else { '?' };
+        let mut url = format!("{}{}limit={}", self.url, separator, self.page_size);
+        if let Some(cursor) = &self.cursor {
+            url.push_str(&format!("&{}={}", self.direction.param(), cursor));
+        }
+        url
+    }
+
+    async fn fetch_page(&mut self) -> Result<()> {
+        let response = self.http.get(self.page_url()).await?;
+        let page: Vec<T> = serde_json::from_value(response)?;
+        if page.len() < self.page_size as usize {
+            self.done = true;
+        }
+        if let Some(last) = page.last() {
+            self.cursor = Some((self.cursor_of)(last));
+        }
+        self.buffer.extend(page);
+        Ok(())
+    }
+}
+
+/// Streams every item of a cursor-paginated endpoint, one page of `page_size` at a time.
+///
+/// `cursor_of` extracts the ID to pass as the next `before`/`after` value from the
+/// last item of a page. The stream ends after the first short page or on the first error.
+pub(crate) fn paginate<T>(
+    http: &HttpClient,
+    url: String,
+    direction: Direction,
+    page_size: u32,
+    cursor: Option<String>,
+    cursor_of: fn(&T) -> String,
+) -> impl Stream<Item = Result<T>> + Send + 'static
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    let paginator = Paginator {
+        http: http.clone(),
+        url,
+        direction,
+        page_size,
+        cursor,
+        cursor_of,
+        buffer: VecDeque::new(),
+        done: false,
+    };
+
+    futures::stream::unfold(paginator, |mut paginator| async move {
+        if paginator.buffer.is_empty() && !paginator.done {
+            if let Err(e) = paginator.fetch_page().await {
+                paginator.done = true;
+                return Some((Err(e), paginator));
+            }
+        }
+        let item = paginator.buffer.pop_front()?;
+        Some((Ok(item), paginator))
+    })
+}
@@ -0,0 +1,120 @@
+use crate::error::Error;
+use rand::Rng;
+use reqwest::Method;
+use std::time::Duration;
+
+/// Automatic retry policy for transient HTTP failures
+///
+/// Requests are retried on 429 responses (after `retry_after`), 5xx responses,
+/// and connection errors or timeouts, with exponential backoff and jitter.
+///
+/// A 5xx response or a timeout does not tell whether Discord applied the request,
+/// so these are only retried for idempotent methods (`GET`, `PUT`, `DELETE`, ...)
+/// unless [`retry_non_idempotent`](Self::retry_non_idempotent) is enabled.
+/// A `POST` such as sending a message could otherwise be applied twice.
+///
+/// # Example
+/// ```
+/// use diself::http::RetryPolicy;
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy::default()
+///     .max_attempts(5)
+///     .max_elapsed(Duration::from_secs(60));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    max_elapsed: Duration,
+    base_delay: Duration,
+    max_delay: Duration,
+    retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            max_elapsed: Duration::from_secs(30),
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy that never retries
+    pub fn disabled() -> Self {
+        Self::default().max_attempts(1)
+    }
+
+    /// Sets the total number of attempts, including the first one (min 1)
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the time after which no retry is started
+    pub fn max_elapsed(mut self, max_elapsed: Duration) -> Self {
+        self.max_elapsed = max_elapsed;
+        self
+    }
+
+    /// Sets the delay before the first retry, doubled on each following one
+    pub fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Sets the upper bound of the backoff delay
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Also retries non-idempotent requests (`POST`, `PATCH`) on 5xx responses and
+    /// connection errors, at the risk of applying them twice (default: `false`)
+    ///
+    /// 429 responses are always retried, since Discord rejected the request.
+    pub fn retry_non_idempotent(mut self, enabled: bool) -> Self {
+        self.retry_non_idempotent = enabled;
+        self
+    }
+
+    /// Returns how long to wait before retrying a `method` request after `attempt`
+    /// failed with `error`, or `None` if the request must not be retried.
+    pub(crate) fn retry_delay(
+        &self,
+        method: &Method,
+        error: &Error,
+        attempt: u32,
+        elapsed: Duration,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+        let safe_to_repeat = self.retry_non_idempotent || method.is_idempotent();
+        let delay = match error {
+            Error::RateLimit { retry_after } => Duration::from_secs_f64(retry_after.max(0.0)),
+            Error::ServerError { .. } if safe_to_repeat => self.backoff(attempt),
+            Error::Http(e)
+                if safe_to_repeat && (e.is_connect() || e.is_timeout() || e.is_request()) =>
+            {
+                self.backoff(attempt)
+            }
+            _ => return None,
+        };
+        (elapsed + delay <= self.max_elapsed).then_some(delay)
+    }
+
+    /// Exponential backoff with equal jitter: half the delay is fixed, half is random
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exponential = self
+            .base_delay
+            .saturating_mul(1 << attempt.saturating_sub(1).min(16))
+            .min(self.max_delay);
+        let half = exponential / 2;
+        half + half.mul_f64(rand::thread_rng().gen::<f64>())
+    }
+}
@@ -0,0 +1,95 @@
+use crate::error::Result;
+use async_trait::async_trait;
+use reqwest::{Client as ReqwestClient, Method};
+use serde_json::Value;
+
+/// An API request built by `HttpClient`, with every header already set
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    /// HTTP method
+    pub method: Method,
+    /// Absolute URL, query string included
+    pub url: String,
+    /// Request headers, in the order they were added
+    pub headers: Vec<(String, String)>,
+    /// JSON body, if any
+    pub body: Option<Value>,
+}
+
+impl HttpRequest {
+    /// Returns the first value of a header (case-insensitive)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A raw API response returned by an `HttpTransport`
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// Raw response body
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// Creates a response with a JSON body
+    pub fn json(status: u16, body: &Value) -> Self {
+        Self {
+            status,
+            body: body.to_string().into_bytes(),
+        }
+    }
+
+    /// Creates a `204 No Content` response
+    pub fn no_content() -> Self {
+        Self {
+            status: 204,
+            body: Vec::new(),
+        }
+    }
+}
+
+/// Sends the API requests of an `HttpClient`
+///
+/// The default transport uses `reqwest`. Swap it with `HttpClient::with_transport`,
+/// e.g. with a `MockTransport` to test handlers without a token.
+#[async_trait]
+pub trait HttpTransport: Send + Sync {
+    /// Sends one request and returns the response, whatever its status
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse>;
+}
+
+/// Default `HttpTransport`, backed by a `reqwest` client
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    client: ReqwestClient,
+}
+
+impl ReqwestTransport {
+    /// Wraps a `reqwest` client
+    pub fn new(client: ReqwestClient) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl HttpTransport for ReqwestTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let mut builder = self.client.request(request.method, &request.url);
+        for (name, value) in &request.headers {
+            builder = builder.header(name, value);
+        }
+        if let Some(body) = &request.body {
+            builder = builder.json(body);
+        }
+
+        let response = builder.send().await?;
+        let status = response.status().as_u16();
+        let body = response.bytes().await?.to_vec();
+        Ok(HttpResponse { status, body })
+    }
+}
@@ -1,22 +1,36 @@
 pub mod cache;
+pub mod cdn;
 pub mod client;
 pub mod error;
+#[cfg(feature = "framework")]
+pub mod framework;
 pub mod gateway;
 pub mod http;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod model;
+#[cfg(feature = "triggers")]
+pub mod triggers;
+#[cfg(feature = "voice")]
+pub mod voice;
 
-pub use cache::{Cache, CacheConfig};
+pub use cache::{Cache, CacheConfig, CacheLimits, CachePolicy, CacheSnapshot};
 pub use client::{
-    ChannelsManager, Client, ClientBuilder, CollectorHub, CollectorOptions, Context,
-    DispatchEvent, DispatchEventType, EventHandler, GuildsManager, MessageCollector,
+    AuditLogParams, BanOptions, BlockList, BlockListEntry, BlockListImport, BroadcastReport,
+    BulkAckEntry, BulkReport, ChannelsManager, Client, ClientBuilder, CollectorHub,
+    CollectorLagHandler, CollectorOptions, CollectorStats, ConnectionsManager, Context,
+    DeleteCollectEvent, DeleteCollector, DispatchEvent, DispatchEventType, DispatchMode,
+    EditCollector, EventHandler, GiftsManager, GuildsManager, InteractionsManager, InvitesManager,
+    MessageCollector, MessageScheduler, OAuthManager, PollsManager, PremiumManager,
     ReactionCollectEvent, ReactionCollector, ReactionEventType, RelationshipsManager,
-    SearchThreadsParams, UsersManager,
+    ScheduledMessage, SearchManager, SearchParams, SearchThreadsParams, StickersManager, TypeMap,
+    TypeMapKey, TypingCollector, UsersManager, VoiceStateController, DEFAULT_COLLECTOR_CAPACITY,
 };
-pub use error::{CaptchaInfo, Error, Result};
-pub use http::HttpClient;
+pub use error::{CaptchaInfo, Error, MfaInfo, MfaMethod, Result};
+pub use http::{HttpClient, MfaCode};
 pub use model::{
-    Channel, Message, PassiveChannelState, PassiveUpdateV1, ReadStateEntry, ReadySupplemental,
-    User,
+    Channel, Message, PassiveChannelState, PassiveUpdateV1, ReadStateEntry, ReadyPayload,
+    ReadySupplemental, User,
 };
 
 /// Prelude module for easy imports
@@ -26,18 +40,24 @@ pub use model::{
 /// use diself::prelude::*;
 /// ```
 pub mod prelude {
-    pub use crate::cache::{Cache, CacheConfig};
+    pub use crate::cache::{Cache, CacheConfig, CacheLimits, CachePolicy, CacheSnapshot};
     pub use crate::client::{
-        ChannelsManager, Client, ClientBuilder, CollectorHub, CollectorOptions, Context,
-        DispatchEvent, DispatchEventType, EventHandler, GuildsManager, MessageCollector,
-        ReactionCollectEvent, ReactionCollector, ReactionEventType, RelationshipsManager,
-        SearchThreadsParams, UsersManager,
+        AuditLogParams, BanOptions, BulkAckEntry, BulkReport, ChannelsManager, Client,
+        ClientBuilder, CollectorHub, CollectorOptions, ConnectionsManager, Context,
+        DeleteCollectEvent, DeleteCollector, DispatchEvent, DispatchEventType, DispatchMode,
+        EditCollector, EventHandler, GiftsManager, GuildsManager, InteractionsManager,
+        InvitesManager, MessageCollector, MessageScheduler, OAuthManager, PollsManager,
+        PremiumManager, ReactionCollectEvent, ReactionCollector, ReactionEventType,
+        RelationshipsManager, SearchManager, SearchParams, SearchThreadsParams, StickersManager,
+        TypeMap, TypeMapKey, TypingCollector, UsersManager, VoiceStateController,
     };
-    pub use crate::error::{CaptchaInfo, Error, Result};
-    pub use crate::http::HttpClient;
+    pub use crate::error::{CaptchaInfo, Error, MfaInfo, Result};
+    #[cfg(feature = "framework")]
+    pub use crate::framework::{Args, Command, CommandFramework};
+    pub use crate::http::{HttpClient, MfaCode};
     pub use crate::model::{
-        Channel, Message, PassiveChannelState, PassiveUpdateV1, ReadStateEntry, ReadySupplemental,
-        User,
+        Channel, ChannelId, GuildId, Id, Message, MessageId, PassiveChannelState, PassiveUpdateV1,
+        ReadStateEntry, ReadyPayload, ReadySupplemental, RoleId, Snowflake, User, UserId,
     };
     pub use async_trait::async_trait;
 }
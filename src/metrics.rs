@@ -0,0 +1,88 @@
+//! Metrics recorded through the [`metrics`](https://docs.rs/metrics) facade (feature `metrics`)
+//!
+//! Nothing is exported until a recorder is installed, e.g. with
+//! `metrics-exporter-prometheus`:
+//!
+//! ```ignore
+//! metrics_exporter_prometheus::PrometheusBuilder::new()
+//!     .with_http_listener(([0, 0, 0, 0], 9000))
+//!     .install()?;
+//! client.start().await?;
+//! ```
+//!
+//! Routes are recorded with their IDs replaced by `{id}` (e.g. `/channels/{id}/messages`),
+//! and invite and gift codes, emojis and tokens by `{code}`, `{emoji}` and `{token}`, so
+//! labels stay few and never hold secrets.
+
+use crate::cache::CacheStats;
+use reqwest::Method;
+use std::time::Duration;
+
+/// Counter of the gateway dispatches received, labeled by `event`
+pub const GATEWAY_EVENTS: &str = "diself_gateway_events_total";
+
+/// Counter of the gateway reconnections, labeled by `resumed` (`true` or `false`)
+pub const GATEWAY_RECONNECTS: &str = "diself_gateway_reconnects_total";
+
+/// Counter of the HTTP responses, labeled by `method`, `route` and `status`
+pub const HTTP_REQUESTS: &str = "diself_http_requests_total";
+
+/// Histogram of the HTTP response times in seconds, labeled by `method` and `route`
+pub const HTTP_REQUEST_DURATION: &str = "diself_http_request_duration_seconds";
+
+/// Counter of the rate limited (429) HTTP responses, labeled by `route` and `global`
+pub const HTTP_RATE_LIMITS: &str = "diself_http_rate_limits_total";
+
+/// Gauge of the cached entries, labeled by `cache` (`users`, `guilds`, `messages`, ...)
+pub const CACHE_ENTRIES: &str = "diself_cache_entries";
+
+pub(crate) fn record_event(event: &str) {
+    ::metrics::counter!(GATEWAY_EVENTS, "event" => event.to_string()).increment(1);
+}
+
+pub(crate) fn record_reconnect(resumed: bool) {
+    ::metrics::counter!(GATEWAY_RECONNECTS, "resumed" => resumed.to_string()).increment(1);
+}
+
+pub(crate) fn record_http_response(method: &Method, route: &str, status: u16, elapsed: Duration) {
+    let method = method.to_string();
+    ::metrics::counter!(
+        HTTP_REQUESTS,
+        "method" => method.clone(),
+        "route" => route.to_string(),
+        "status" => status.to_string()
+    )
+    .increment(1);
+    ::metrics::histogram!(
+        HTTP_REQUEST_DURATION,
+        "method" => method,
+        "route" => route.to_string()
+    )
+    .record(elapsed.as_secs_f64());
+}
+
+pub(crate) fn record_rate_limit(route: &str, global: bool) {
+    ::metrics::counter!(
+        HTTP_RATE_LIMITS,
+        "route" => route.to_string(),
+        "global" => global.to_string()
+    )
+    .increment(1);
+}
+
+pub(crate) fn record_cache_stats(stats: &CacheStats) {
+    let caches = [
+        ("users", stats.users),
+        ("channels", stats.channels),
+        ("guilds", stats.guilds),
+        ("members", stats.members),
+        ("presences", stats.presences),
+        ("roles", stats.roles),
+        ("emojis", stats.emojis),
+        ("threads", stats.threads),
+        ("messages", stats.messages),
+    ];
+    for (cache, entries) in caches {
+        ::metrics::gauge!(CACHE_ENTRIES, "cache" => cache).set(entries as f64);
+    }
+}
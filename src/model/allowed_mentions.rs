@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+
+/// Kind of mention parsed from the message content
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MentionType {
+    /// `<@id>` user mentions
+    Users,
+    /// `<@&id>` role mentions
+    Roles,
+    /// `@everyone` and `@here`
+    Everyone,
+}
+
+/// Controls which mentions of a message notify. SEE: <https://docs.discord.food/resources/message#allowed-mentions-object>
+///
+/// The default value notifies no one, not even the author of a replied message.
+///
+/// # Example
+/// ```
+/// use diself::model::AllowedMentions;
+///
+/// // Only ping the replied user and one friend
+/// let mentions = AllowedMentions::none().user("123").replied_user(true);
+/// assert_eq!(mentions.users, vec!["123".to_string()]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllowedMentions {
+    /// Mention kinds parsed from the content
+    #[serde(default)]
+    pub parse: Vec<MentionType>,
+
+    /// Users allowed to be mentioned (max 100)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub users: Vec<String>,
+
+    /// Roles allowed to be mentioned (max 100)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<String>,
+
+    /// Whether the author of the replied message is mentioned
+    #[serde(default)]
+    pub replied_user: bool,
+}
+
+impl AllowedMentions {
+    /// Notifies no one
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Notifies every mentioned user and role, `@everyone` and the replied user
+    pub fn all() -> Self {
+        Self {
+            parse: vec![
+                MentionType::Users,
+                MentionType::Roles,
+                MentionType::Everyone,
+            ],
+            users: Vec::new(),
+            roles: Vec::new(),
+            replied_user: true,
+        }
+    }
+
+    /// Notifies every user mentioned in the content
+    ///
+    /// Clears the explicit user list, the API rejects both at once.
+    pub fn users(mut self) -> Self {
+        self.users.clear();
+        self.add_parse(MentionType::Users)
+    }
+
+    /// Notifies every role mentioned in the content
+    ///
+    /// Clears the explicit role list, the API rejects both at once.
+    pub fn roles(mut self) -> Self {
+        self.roles.clear();
+        self.add_parse(MentionType::Roles)
+    }
+
+    /// Lets `@everyone` and `@here` notify
+    pub fn everyone(self) -> Self {
+        self.add_parse(MentionType::Everyone)
+    }
+
+    /// Allows one user to be notified, instead of every mentioned user
+    pub fn user(mut self, user_id: impl Into<String>) -> Self {
+        self.parse.retain(|kind| *kind != MentionType::Users);
+        self.users.push(user_id.into());
+        self
+    }
+
+    /// Allows one role to be notified, instead of every mentioned role
+    pub fn role(mut self, role_id: impl Into<String>) -> Self {
+        self.parse.retain(|kind| *kind != MentionType::Roles);
+        self.roles.push(role_id.into());
+        self
+    }
+
+    /// Sets whether the author of the replied message is notified
+    pub fn replied_user(mut self, replied_user: bool) -> Self {
+        self.replied_user = replied_user;
+        self
+    }
+
+    fn add_parse(mut self, kind: MentionType) -> Self {
+        if !self.parse.contains(&kind) {
+            self.parse.push(kind);
+        }
+        self
+    }
+}
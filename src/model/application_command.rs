@@ -0,0 +1,244 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum ApplicationCommandType {
+    ChatInput = 1,
+    User = 2,
+    Message = 3,
+    PrimaryEntryPoint = 4,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum ApplicationCommandOptionType {
+    SubCommand = 1,
+    SubCommandGroup = 2,
+    String = 3,
+    Integer = 4,
+    Boolean = 5,
+    User = 6,
+    Channel = 7,
+    Role = 8,
+    Mentionable = 9,
+    Number = 10,
+    Attachment = 11,
+}
+
+/// An application (slash/context menu) command. SEE: <https://docs.discord.food/interactions/application-commands#application-command-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplicationCommand {
+    /// Unique ID of the command
+    pub id: String,
+
+    /// Type of the command (default 1)
+    #[serde(rename = "type", default = "default_command_type")]
+    pub kind: ApplicationCommandType,
+
+    /// ID of the parent application
+    pub application_id: String,
+
+    /// Guild ID of the command, if not global
+    pub guild_id: Option<String>,
+
+    /// Name of the command (1-32 characters)
+    pub name: String,
+
+    /// Description of the command (empty for USER and MESSAGE commands)
+    #[serde(default)]
+    pub description: String,
+
+    /// Parameters for the command (max 25)
+    #[serde(default)]
+    pub options: Vec<ApplicationCommandOption>,
+
+    /// Default permissions required to use the command (bitfield as string)
+    pub default_member_permissions: Option<String>,
+
+    /// Whether the command is age-restricted
+    #[serde(default)]
+    pub nsfw: bool,
+
+    /// Installation contexts where the command is available
+    pub integration_types: Option<Vec<u8>>,
+
+    /// Interaction contexts where the command can be used
+    pub contexts: Option<Vec<u8>>,
+
+    /// Autoincrementing version identifier updated during substantial record changes
+    pub version: String,
+}
+
+fn default_command_type() -> ApplicationCommandType {
+    ApplicationCommandType::ChatInput
+}
+
+impl ApplicationCommand {
+    /// Builds the `data` object of an APPLICATION_COMMAND interaction invoking this command
+    pub fn interaction_data(
+        &self,
+        options: Vec<ApplicationCommandInteractionOption>,
+    ) -> serde_json::Value {
+        json!({
+            "version": self.version,
+            "id": self.id,
+            "name": self.name,
+            "type": self.kind,
+            "options": options,
+            "application_command": self,
+            "attachments": [],
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplicationCommandOption {
+    /// Type of the option
+    #[serde(rename = "type")]
+    pub kind: ApplicationCommandOptionType,
+
+    /// Name of the option (1-32 characters)
+    pub name: String,
+
+    /// Description of the option (1-100 characters)
+    #[serde(default)]
+    pub description: String,
+
+    /// Whether the parameter is required (default false)
+    #[serde(default)]
+    pub required: bool,
+
+    /// Choices for the user to pick from (max 25)
+    #[serde(default)]
+    pub choices: Vec<ApplicationCommandOptionChoice>,
+
+    /// Nested options, if the option is a subcommand or subcommand group
+    #[serde(default)]
+    pub options: Vec<ApplicationCommandOption>,
+
+    /// Whether autocomplete interactions are enabled for this option
+    #[serde(default)]
+    pub autocomplete: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplicationCommandOptionChoice {
+    /// Name of the choice (1-100 characters)
+    pub name: String,
+
+    /// Value of the choice (string, integer or double)
+    pub value: serde_json::Value,
+}
+
+/// Application summary returned alongside commands in the command index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplicationCommandIndexApplication {
+    /// ID of the application
+    pub id: String,
+
+    /// Name of the application
+    pub name: String,
+
+    /// Icon hash of the application
+    pub icon: Option<String>,
+
+    /// Description of the application
+    pub description: Option<String>,
+
+    /// ID of the application's bot user
+    pub bot_id: Option<String>,
+}
+
+/// Commands available in a channel or guild (`GET /channels/{channel.id}/application-command-index`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplicationCommandIndex {
+    /// Applications with commands in the index
+    #[serde(default)]
+    pub applications: Vec<ApplicationCommandIndexApplication>,
+
+    /// Commands in the index
+    #[serde(default)]
+    pub application_commands: Vec<ApplicationCommand>,
+
+    /// Version of the index
+    pub version: Option<String>,
+}
+
+impl ApplicationCommandIndex {
+    /// Finds a command by name, optionally restricted to one application
+    pub fn find(&self, name: &str, application_id: Option<&str>) -> Option<&ApplicationCommand> {
+        self.application_commands.iter().find(|command| {
+            command.name == name && application_id.is_none_or(|id| command.application_id == id)
+        })
+    }
+}
+
+/// A filled option sent when invoking an application command.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplicationCommandInteractionOption {
+    /// Type of the option
+    #[serde(rename = "type")]
+    pub kind: ApplicationCommandOptionType,
+
+    /// Name of the option
+    pub name: String,
+
+    /// Value of the option (absent for subcommands and groups)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<serde_json::Value>,
+
+    /// Nested options, for subcommands and groups
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<ApplicationCommandInteractionOption>,
+}
+
+impl ApplicationCommandInteractionOption {
+    /// Creates an option with a value
+    pub fn new(
+        kind: ApplicationCommandOptionType,
+        name: impl Into<String>,
+        value: impl Into<serde_json::Value>,
+    ) -> Self {
+        Self {
+            kind,
+            name: name.into(),
+            value: Some(value.into()),
+            options: Vec::new(),
+        }
+    }
+
+    /// Creates a string option
+    pub fn string(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self::new(ApplicationCommandOptionType::String, name, value.into())
+    }
+
+    /// Creates an integer option
+    pub fn integer(name: impl Into<String>, value: i64) -> Self {
+        Self::new(ApplicationCommandOptionType::Integer, name, value)
+    }
+
+    /// Creates a boolean option
+    pub fn boolean(name: impl Into<String>, value: bool) -> Self {
+        Self::new(ApplicationCommandOptionType::Boolean, name, value)
+    }
+
+    /// Creates a user option from a user ID
+    pub fn user(name: impl Into<String>, user_id: impl Into<String>) -> Self {
+        Self::new(ApplicationCommandOptionType::User, name, user_id.into())
+    }
+
+    /// Creates a subcommand holding nested options
+    pub fn sub_command(
+        name: impl Into<String>,
+        options: Vec<ApplicationCommandInteractionOption>,
+    ) -> Self {
+        Self {
+            kind: ApplicationCommandOptionType::SubCommand,
+            name: name.into(),
+            value: None,
+            options,
+        }
+    }
+}
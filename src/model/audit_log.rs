@@ -0,0 +1,239 @@
+use super::{Channel, User};
+use serde::{Deserialize, Serialize};
+
+/// Declares `AuditLogActionType` with its conversions from and to the action type number
+macro_rules! audit_log_action_types {
+    ($($variant:ident = $value:literal,)*) => {
+        /// Type of action recorded by an audit log entry. SEE: <https://docs.discord.food/resources/audit-log#audit-log-events>
+        ///
+        /// Actions added after this version are kept as `Unknown`, so the rest of the log
+        /// still parses.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+        #[serde(from = "u16", into = "u16")]
+        pub enum AuditLogActionType {
+            $($variant,)*
+            /// An action this version does not know
+            Unknown(u16),
+        }
+
+        impl From<u16> for AuditLogActionType {
+            fn from(action: u16) -> Self {
+                match action {
+                    $($value => AuditLogActionType::$variant,)*
+                    other => AuditLogActionType::Unknown(other),
+                }
+            }
+        }
+
+        impl From<AuditLogActionType> for u16 {
+            fn from(action: AuditLogActionType) -> Self {
+                match action {
+                    $(AuditLogActionType::$variant => $value,)*
+                    AuditLogActionType::Unknown(other) => other,
+                }
+            }
+        }
+    };
+}
+
+audit_log_action_types! {
+    GuildUpdate = 1,
+    ChannelCreate = 10,
+    ChannelUpdate = 11,
+    ChannelDelete = 12,
+    ChannelOverwriteCreate = 13,
+    ChannelOverwriteUpdate = 14,
+    ChannelOverwriteDelete = 15,
+    MemberKick = 20,
+    MemberPrune = 21,
+    MemberBanAdd = 22,
+    MemberBanRemove = 23,
+    MemberUpdate = 24,
+    MemberRoleUpdate = 25,
+    MemberMove = 26,
+    MemberDisconnect = 27,
+    BotAdd = 28,
+    RoleCreate = 30,
+    RoleUpdate = 31,
+    RoleDelete = 32,
+    InviteCreate = 40,
+    InviteUpdate = 41,
+    InviteDelete = 42,
+    WebhookCreate = 50,
+    WebhookUpdate = 51,
+    WebhookDelete = 52,
+    EmojiCreate = 60,
+    EmojiUpdate = 61,
+    EmojiDelete = 62,
+    MessageDelete = 72,
+    MessageBulkDelete = 73,
+    MessagePin = 74,
+    MessageUnpin = 75,
+    IntegrationCreate = 80,
+    IntegrationUpdate = 81,
+    IntegrationDelete = 82,
+    StageInstanceCreate = 83,
+    StageInstanceUpdate = 84,
+    StageInstanceDelete = 85,
+    StickerCreate = 90,
+    StickerUpdate = 91,
+    StickerDelete = 92,
+    GuildScheduledEventCreate = 100,
+    GuildScheduledEventUpdate = 101,
+    GuildScheduledEventDelete = 102,
+    ThreadCreate = 110,
+    ThreadUpdate = 111,
+    ThreadDelete = 112,
+    ApplicationCommandPermissionUpdate = 121,
+    SoundboardSoundCreate = 130,
+    SoundboardSoundUpdate = 131,
+    SoundboardSoundDelete = 132,
+    AutoModerationRuleCreate = 140,
+    AutoModerationRuleUpdate = 141,
+    AutoModerationRuleDelete = 142,
+    AutoModerationBlockMessage = 143,
+    AutoModerationFlagToChannel = 144,
+    AutoModerationUserCommunicationDisabled = 145,
+    AutoModerationQuarantineUser = 146,
+    CreatorMonetizationRequestCreated = 150,
+    CreatorMonetizationTermsAccepted = 151,
+    OnboardingPromptCreate = 163,
+    OnboardingPromptUpdate = 164,
+    OnboardingPromptDelete = 165,
+    OnboardingCreate = 166,
+    OnboardingUpdate = 167,
+    HomeSettingsCreate = 190,
+    HomeSettingsUpdate = 191,
+    VoiceChannelStatusCreate = 192,
+    VoiceChannelStatusDelete = 193,
+}
+
+/// Response of `GET /guilds/{guild.id}/audit-logs`. SEE: <https://docs.discord.food/resources/audit-log#audit-log-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLog {
+    /// Audit log entries, sorted from most to least recent
+    #[serde(default)]
+    pub audit_log_entries: Vec<AuditLogEntry>,
+
+    /// Users referenced in the audit log
+    #[serde(default)]
+    pub users: Vec<User>,
+
+    /// Threads referenced in the audit log
+    #[serde(default)]
+    pub threads: Vec<Channel>,
+
+    /// Webhooks referenced in the audit log
+    #[serde(default)]
+    pub webhooks: Vec<serde_json::Value>,
+
+    /// Integrations referenced in the audit log
+    #[serde(default)]
+    pub integrations: Vec<serde_json::Value>,
+
+    /// Application commands referenced in the audit log
+    #[serde(default)]
+    pub application_commands: Vec<serde_json::Value>,
+
+    /// Auto moderation rules referenced in the audit log
+    #[serde(default)]
+    pub auto_moderation_rules: Vec<serde_json::Value>,
+
+    /// Guild scheduled events referenced in the audit log
+    #[serde(default)]
+    pub guild_scheduled_events: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLogEntry {
+    /// Unique ID of the entry
+    pub id: String,
+
+    /// ID of the affected entity (webhook, user, role, etc.)
+    pub target_id: Option<String>,
+
+    /// Changes made to the target
+    #[serde(default)]
+    pub changes: Vec<AuditLogChange>,
+
+    /// User or application that made the changes
+    pub user_id: Option<String>,
+
+    /// Type of action that occurred
+    pub action_type: AuditLogActionType,
+
+    /// Additional info for certain action types
+    pub options: Option<AuditLogEntryInfo>,
+
+    /// The reason for the change (1-512 characters)
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLogChange {
+    /// Name of the changed entity's property, with a few exceptions (e.g. `$add`, `$remove`)
+    pub key: String,
+
+    /// New value of the key
+    pub new_value: Option<serde_json::Value>,
+
+    /// Old value of the key
+    pub old_value: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLogEntryInfo {
+    /// ID of the app whose permissions were targeted
+    pub application_id: Option<String>,
+
+    /// Name of the auto moderation rule that was triggered
+    pub auto_moderation_rule_name: Option<String>,
+
+    /// Trigger type of the auto moderation rule that was triggered
+    pub auto_moderation_rule_trigger_type: Option<String>,
+
+    /// Channel in which the entities were targeted
+    pub channel_id: Option<String>,
+
+    /// Number of entities that were targeted
+    pub count: Option<String>,
+
+    /// Number of days after which inactive members were kicked
+    pub delete_member_days: Option<String>,
+
+    /// ID of the overwritten entity
+    pub id: Option<String>,
+
+    /// Number of members removed by the prune
+    pub members_removed: Option<String>,
+
+    /// ID of the message that was targeted
+    pub message_id: Option<String>,
+
+    /// Name of the role if type is "0" (not present if type is "1")
+    pub role_name: Option<String>,
+
+    /// Type of overwritten entity ("0" for role, "1" for member)
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+
+    /// The type of integration which performed the action
+    pub integration_type: Option<String>,
+
+    /// The new voice channel status
+    pub status: Option<String>,
+}
+
+impl AuditLog {
+    /// Gets a referenced user by ID
+    pub fn user(&self, user_id: &str) -> Option<&User> {
+        self.users.iter().find(|user| user.id == user_id)
+    }
+}
+
+impl AuditLogEntry {
+    /// Gets the change recorded for a given key (if any)
+    pub fn change(&self, key: &str) -> Option<&AuditLogChange> {
+        self.changes.iter().find(|change| change.key == key)
+    }
+}
@@ -0,0 +1,54 @@
+use super::VoiceState;
+use serde::{Deserialize, Serialize};
+
+/// A call in a DM or group DM, from CALL_CREATE / CALL_UPDATE. SEE: <https://docs.discord.food/topics/gateway-events#call-create>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Call {
+    /// ID of the private channel
+    pub channel_id: String,
+
+    /// ID of the call message (the "started a call" system message)
+    pub message_id: String,
+
+    /// Voice region of the call
+    #[serde(default)]
+    pub region: Option<String>,
+
+    /// IDs of the recipients being rung
+    #[serde(default)]
+    pub ringing: Vec<String>,
+
+    /// Voice states of the users in the call (CALL_CREATE only)
+    #[serde(default)]
+    pub voice_states: Vec<VoiceState>,
+
+    /// Whether the call is unavailable due to an outage
+    #[serde(default)]
+    pub unavailable: bool,
+}
+
+impl Call {
+    /// Whether a user is being rung
+    pub fn is_ringing(&self, user_id: &str) -> bool {
+        self.ringing.iter().any(|id| id == user_id)
+    }
+
+    /// IDs of the users connected to the call
+    pub fn participants(&self) -> impl Iterator<Item = &str> {
+        self.voice_states
+            .iter()
+            .filter(|state| state.is_connected())
+            .map(|state| state.user_id.as_str())
+    }
+}
+
+/// CALL_DELETE payload. SEE: <https://docs.discord.food/topics/gateway-events#call-delete>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallDelete {
+    /// ID of the private channel
+    pub channel_id: String,
+
+    /// Whether the call is unavailable due to an outage (instead of ended)
+    #[serde(default)]
+    pub unavailable: bool,
+}
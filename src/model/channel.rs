@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
-use super::{Member, PermissionOverwrite, Permissions};
+use super::{
+    ChannelId, CreateMessage, CreatePoll, Embed, Member, PermissionOverwrite, Permissions,
+};
 use crate::{HttpClient, Message, User};
 
 /// Represents a Discord channel (text, voice, DM, etc.)
@@ -66,6 +69,10 @@ pub struct Channel {
     /// recipients (for DM channels)
     pub recipients: Option<Vec<User>>,
 
+    /// IDs of the recipients (for DM channels in READY, which sends users separately)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipient_ids: Option<Vec<String>>,
+
     /// Icon hash (for group DM channels)
     pub icon: Option<String>,
 
@@ -130,11 +137,12 @@ pub struct Channel {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThreadMember {
-    /// The ID of the thread
-    #[serde(rename = "id")]
+    /// The ID of the thread (empty in the `member` of a thread channel)
+    #[serde(rename = "id", default)]
     pub thread_id: String,
 
-    /// The ID of the user
+    /// The ID of the user (empty in the `member` of a thread channel)
+    #[serde(default)]
     pub user_id: String,
 
     /// The timestamp when the user joined the thread
@@ -205,11 +213,44 @@ pub struct ThreadMetadata {
 }
 
 impl Channel {
+    /// Returns the ID of the channel as a typed snowflake
+    pub fn channel_id(&self) -> Option<ChannelId> {
+        ChannelId::parse(&self.id)
+    }
+
     /// Helper method to check if the channel is a DM or Group DM
     pub fn is_dm(&self) -> bool {
         matches!(self.kind, ChannelType::DM | ChannelType::GroupDM)
     }
 
+    /// Returns the IDs of the DM recipients, from `recipients` or `recipient_ids`
+    pub fn recipient_user_ids(&self) -> Vec<String> {
+        match (&self.recipients, &self.recipient_ids) {
+            (Some(recipients), _) if !recipients.is_empty() => {
+                recipients.iter().map(|user| user.id.clone()).collect()
+            }
+            (_, Some(ids)) => ids.clone(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Helper method to check if the channel is a thread
+    pub fn is_thread(&self) -> bool {
+        matches!(
+            self.kind,
+            ChannelType::AnnouncementThread
+                | ChannelType::PublicThread
+                | ChannelType::PrivateThread
+        )
+    }
+
+    /// Returns `true` if the channel is an archived thread
+    pub fn is_archived(&self) -> bool {
+        self.thread_metadata
+            .as_ref()
+            .is_some_and(|metadata| metadata.archived)
+    }
+
     /// Helper method to get the mention string for the channel
     pub fn mention(&self) -> String {
         if self.is_dm() {
@@ -267,6 +308,32 @@ impl Channel {
         Ok(message)
     }
 
+    /// Sends a message built with `CreateMessage` (embeds, poll, flags, ...) in this channel
+    pub async fn send_message(
+        &self,
+        http: &HttpClient,
+        message: CreateMessage,
+    ) -> Result<Message, crate::error::Error> {
+        let url = crate::http::api_url(&format!("/channels/{}/messages", self.id));
+        let response = http.post(&url, message).await?;
+        let message: Message = serde_json::from_value(response)?;
+        Ok(message)
+    }
+
+    /// Sends a poll in this channel. (`POST /channels/{channel_id}/messages`) SEE: <https://docs.discord.food/resources/poll#create-poll>
+    pub async fn send_poll(
+        &self,
+        http: &HttpClient,
+        poll: CreatePoll,
+    ) -> Result<Message, crate::error::Error> {
+        let url = crate::http::api_url(&format!("/channels/{}/messages", self.id));
+        let body = serde_json::json!({ "poll": poll });
+
+        let response = http.post(&url, body).await?;
+        let message: Message = serde_json::from_value(response)?;
+        Ok(message)
+    }
+
     /// Fetches messages from this channel. (`GET /channels/{channel_id}/messages`) SEE: <https://docs.discord.food/resources/message#get-messages>
     /// # Params
     /// - around?: Snowflake - Get messages around this message ID
@@ -313,6 +380,32 @@ impl Channel {
         Ok(messages)
     }
 
+    /// Streams this channel's message history from newest to oldest, following the
+    /// `before` cursor 100 messages at a time.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use futures::StreamExt;
+    ///
+    /// let mut messages = Box::pin(channel.messages_iter(&ctx.http));
+    /// while let Some(message) = messages.next().await {
+    ///     println!("{}", message?.content);
+    /// }
+    /// ```
+    pub fn messages_iter(
+        &self,
+        http: &HttpClient,
+    ) -> impl futures::Stream<Item = crate::Result<Message>> + Send + 'static {
+        crate::http::paginate(
+            http,
+            crate::http::api_url(&format!("/channels/{}/messages", self.id)),
+            crate::http::Direction::Before,
+            100,
+            None,
+            |message: &Message| message.id.clone(),
+        )
+    }
+
     /// Fetches a single message by ID from this channel. (`GET /channels/{channel_id}/messages/{message_id}`) SEE: <https://docs.discord.food/resources/message#get-message>
     pub async fn get_message(
         &self,
@@ -329,3 +422,500 @@ impl Channel {
         Ok(message)
     }
 }
+
+/// THREAD_DELETE payload. SEE: <https://docs.discord.food/topics/gateway-events#thread-delete>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreadDelete {
+    /// ID of the deleted thread
+    pub id: String,
+
+    /// ID of the guild
+    pub guild_id: Option<String>,
+
+    /// ID of the parent channel
+    pub parent_id: Option<String>,
+
+    /// Type of the thread
+    #[serde(rename = "type")]
+    pub kind: ChannelType,
+}
+
+/// THREAD_LIST_SYNC payload, sent when the current user gains access to a channel. SEE: <https://docs.discord.food/topics/gateway-events#thread-list-sync>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreadListSync {
+    /// ID of the guild
+    pub guild_id: String,
+
+    /// Parent channels whose threads are synced (every channel of the guild if `None`)
+    pub channel_ids: Option<Vec<String>>,
+
+    /// Active threads in the synced channels
+    #[serde(default)]
+    pub threads: Vec<Channel>,
+
+    /// Thread members of the current user, for the synced threads it joined
+    #[serde(default)]
+    pub members: Vec<ThreadMember>,
+}
+
+/// A page of threads returned by the thread listing and search endpoints of `ChannelsManager`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ThreadListing {
+    /// Listed threads
+    #[serde(default)]
+    pub threads: Vec<Channel>,
+
+    /// Thread members of the current user, for the listed threads it joined
+    #[serde(default)]
+    pub members: Vec<ThreadMember>,
+
+    /// Whether more threads can be fetched with a subsequent request
+    #[serde(default)]
+    pub has_more: bool,
+
+    /// Total number of matching threads (search only)
+    pub total_results: Option<u64>,
+
+    /// First message of each listed thread, when requested (search only)
+    #[serde(default)]
+    pub first_messages: Vec<Message>,
+}
+
+/// THREAD_MEMBERS_UPDATE payload. SEE: <https://docs.discord.food/topics/gateway-events#thread-members-update>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreadMembersUpdate {
+    /// ID of the thread
+    pub id: String,
+
+    /// ID of the guild
+    pub guild_id: String,
+
+    /// Approximate number of members in the thread (stops counting at 50)
+    pub member_count: u64,
+
+    /// Users added to the thread
+    #[serde(default)]
+    pub added_members: Vec<ThreadMember>,
+
+    /// IDs of the users removed from the thread
+    #[serde(default)]
+    pub removed_member_ids: Vec<String>,
+}
+
+/// CHANNEL_RECIPIENT_ADD / CHANNEL_RECIPIENT_REMOVE payload. SEE: <https://docs.discord.food/topics/gateway-events#channel-recipient-add>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelRecipientEvent {
+    /// ID of the group DM
+    pub channel_id: String,
+
+    /// The user added to or removed from the group
+    pub user: User,
+
+    /// Nickname of the user in the group (if any)
+    pub nick: Option<String>,
+}
+
+/// Builder for a guild channel created with `ChannelsManager::create_guild_channel`. SEE: <https://docs.discord.food/resources/channel#create-guild-channel>
+///
+/// Fields not covered by a setter can be added with `field`.
+///
+/// # Example
+/// ```ignore
+/// let channel = CreateChannel::text("general").topic("Say hi").parent(category_id).nsfw(false);
+/// ctx.channels.create_guild_channel(&ctx.http, guild_id, channel, None).await?;
+/// ```
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateChannel {
+    name: String,
+    #[serde(rename = "type")]
+    kind: ChannelType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    topic: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nsfw: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    position: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bitrate: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user_limit: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rate_limit_per_user: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rtc_region: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default_auto_archive_duration: Option<u64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    permission_overwrites: Vec<PermissionOverwrite>,
+    #[serde(flatten)]
+    extra: Map<String, Value>,
+}
+
+impl CreateChannel {
+    /// Creates a channel of the given type
+    pub fn new(name: impl Into<String>, kind: ChannelType) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+            topic: None,
+            parent_id: None,
+            nsfw: None,
+            position: None,
+            bitrate: None,
+            user_limit: None,
+            rate_limit_per_user: None,
+            rtc_region: None,
+            default_auto_archive_duration: None,
+            permission_overwrites: Vec::new(),
+            extra: Map::new(),
+        }
+    }
+
+    /// Creates a text channel
+    pub fn text(name: impl Into<String>) -> Self {
+        Self::new(name, ChannelType::GuildText)
+    }
+
+    /// Creates a voice channel
+    pub fn voice(name: impl Into<String>) -> Self {
+        Self::new(name, ChannelType::GuildVoice)
+    }
+
+    /// Creates a category
+    pub fn category(name: impl Into<String>) -> Self {
+        Self::new(name, ChannelType::GuildCategory)
+    }
+
+    /// Creates an announcement channel
+    pub fn announcement(name: impl Into<String>) -> Self {
+        Self::new(name, ChannelType::GuildAnnouncement)
+    }
+
+    /// Creates a stage channel
+    pub fn stage(name: impl Into<String>) -> Self {
+        Self::new(name, ChannelType::GuildStageVoice)
+    }
+
+    /// Creates a forum channel
+    pub fn forum(name: impl Into<String>) -> Self {
+        Self::new(name, ChannelType::GuildForum)
+    }
+
+    /// Sets the topic (max 1024 characters, 4096 for forums)
+    pub fn topic(mut self, topic: impl Into<String>) -> Self {
+        self.topic = Some(topic.into());
+        self
+    }
+
+    /// Sets the parent category
+    pub fn parent(mut self, parent_id: impl Into<String>) -> Self {
+        self.parent_id = Some(parent_id.into());
+        self
+    }
+
+    /// Sets whether the channel is NSFW
+    pub fn nsfw(mut self, nsfw: bool) -> Self {
+        self.nsfw = Some(nsfw);
+        self
+    }
+
+    /// Sets the sorting position
+    pub fn position(mut self, position: i32) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    /// Sets the bitrate in bits (voice channels)
+    pub fn bitrate(mut self, bitrate: u64) -> Self {
+        self.bitrate = Some(bitrate);
+        self
+    }
+
+    /// Sets the user limit, 0 for unlimited (voice channels)
+    pub fn user_limit(mut self, user_limit: u64) -> Self {
+        self.user_limit = Some(user_limit);
+        self
+    }
+
+    /// Sets the slowmode in seconds (max 21600)
+    pub fn slowmode(mut self, seconds: u64) -> Self {
+        self.rate_limit_per_user = Some(seconds);
+        self
+    }
+
+    /// Sets the voice region (voice channels)
+    pub fn rtc_region(mut self, region: impl Into<String>) -> Self {
+        self.rtc_region = Some(region.into());
+        self
+    }
+
+    /// Sets the default auto archive duration of threads, in minutes (60, 1440, 4320 or 10080)
+    pub fn default_auto_archive_duration(mut self, minutes: u64) -> Self {
+        self.default_auto_archive_duration = Some(minutes);
+        self
+    }
+
+    /// Adds a permission overwrite
+    pub fn permission_overwrite(mut self, overwrite: PermissionOverwrite) -> Self {
+        self.permission_overwrites.push(overwrite);
+        self
+    }
+
+    /// Sets a raw JSON field not covered by the builder
+    pub fn field(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.extra.insert(key.into(), value.into());
+        self
+    }
+}
+
+/// Builder for a channel edited with `ChannelsManager::edit_channel`. SEE: <https://docs.discord.food/resources/channel#modify-channel>
+///
+/// Only the fields that were set are sent. Fields not covered by a setter can be added with `field`.
+///
+/// # Example
+/// ```ignore
+/// let edit = EditChannel::new().name("rules").slowmode(10).remove_parent();
+/// ctx.channels.edit_channel(&ctx.http, channel_id, edit, None).await?;
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EditChannel {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    topic: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_id: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nsfw: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    position: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bitrate: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user_limit: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rate_limit_per_user: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rtc_region: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default_auto_archive_duration: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    permission_overwrites: Option<Vec<PermissionOverwrite>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    archived: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    locked: Option<bool>,
+    #[serde(flatten)]
+    extra: Map<String, Value>,
+}
+
+impl EditChannel {
+    /// Creates an empty edit
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the name
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the topic
+    pub fn topic(mut self, topic: impl Into<String>) -> Self {
+        self.topic = Some(Some(topic.into()));
+        self
+    }
+
+    /// Removes the topic
+    pub fn remove_topic(mut self) -> Self {
+        self.topic = Some(None);
+        self
+    }
+
+    /// Moves the channel to a category
+    pub fn parent(mut self, parent_id: impl Into<String>) -> Self {
+        self.parent_id = Some(Some(parent_id.into()));
+        self
+    }
+
+    /// Moves the channel out of its category
+    pub fn remove_parent(mut self) -> Self {
+        self.parent_id = Some(None);
+        self
+    }
+
+    /// Sets whether the channel is NSFW
+    pub fn nsfw(mut self, nsfw: bool) -> Self {
+        self.nsfw = Some(nsfw);
+        self
+    }
+
+    /// Sets the sorting position
+    pub fn position(mut self, position: i32) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    /// Sets the bitrate in bits (voice channels)
+    pub fn bitrate(mut self, bitrate: u64) -> Self {
+        self.bitrate = Some(bitrate);
+        self
+    }
+
+    /// Sets the user limit, 0 for unlimited (voice channels)
+    pub fn user_limit(mut self, user_limit: u64) -> Self {
+        self.user_limit = Some(user_limit);
+        self
+    }
+
+    /// Sets the slowmode in seconds (max 21600)
+    pub fn slowmode(mut self, seconds: u64) -> Self {
+        self.rate_limit_per_user = Some(seconds);
+        self
+    }
+
+    /// Sets the voice region (voice channels)
+    pub fn rtc_region(mut self, region: impl Into<String>) -> Self {
+        self.rtc_region = Some(Some(region.into()));
+        self
+    }
+
+    /// Lets Discord pick the voice region automatically (voice channels)
+    pub fn automatic_rtc_region(mut self) -> Self {
+        self.rtc_region = Some(None);
+        self
+    }
+
+    /// Sets the default auto archive duration of threads, in minutes (60, 1440, 4320 or 10080)
+    pub fn default_auto_archive_duration(mut self, minutes: u64) -> Self {
+        self.default_auto_archive_duration = Some(minutes);
+        self
+    }
+
+    /// Replaces every permission overwrite of the channel
+    pub fn permission_overwrites(mut self, overwrites: Vec<PermissionOverwrite>) -> Self {
+        self.permission_overwrites = Some(overwrites);
+        self
+    }
+
+    /// Sets whether the thread is archived (threads)
+    pub fn archived(mut self, archived: bool) -> Self {
+        self.archived = Some(archived);
+        self
+    }
+
+    /// Sets whether the thread is locked (threads)
+    pub fn locked(mut self, locked: bool) -> Self {
+        self.locked = Some(locked);
+        self
+    }
+
+    /// Sets a raw JSON field not covered by the builder
+    pub fn field(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.extra.insert(key.into(), value.into());
+        self
+    }
+}
+
+/// Builder for a forum or media channel post created with `ChannelsManager::create_forum_post`. SEE: <https://docs.discord.food/resources/channel#create-thread>
+///
+/// A post is a thread whose starter message is sent in the same request, nested under
+/// `message`. Fields not covered by a setter can be added with `field`.
+///
+/// # Example
+/// ```ignore
+/// let post = CreateForumPost::new("Bug: crash on start")
+///     .content("Steps to reproduce: ...")
+///     .applied_tag(bug_tag_id);
+/// let thread = ctx.channels.create_forum_post(&ctx.http, forum_id, post).await?;
+/// ```
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateForumPost {
+    name: String,
+    message: CreateMessage,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    applied_tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    auto_archive_duration: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rate_limit_per_user: Option<u64>,
+    #[serde(flatten)]
+    extra: Map<String, Value>,
+}
+
+impl CreateForumPost {
+    /// Creates a post with the given title (1-100 characters)
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            message: CreateMessage::new(),
+            applied_tags: Vec::new(),
+            auto_archive_duration: None,
+            rate_limit_per_user: None,
+            extra: Map::new(),
+        }
+    }
+
+    /// Replaces the starter message
+    pub fn message(mut self, message: impl Into<CreateMessage>) -> Self {
+        self.message = message.into();
+        self
+    }
+
+    /// Sets the text content of the starter message
+    pub fn content(mut self, content: impl Into<String>) -> Self {
+        self.message = self.message.content(content);
+        self
+    }
+
+    /// Adds an embed to the starter message (max 10)
+    pub fn embed(mut self, embed: Embed) -> Self {
+        self.message = self.message.embed(embed);
+        self
+    }
+
+    /// Attaches a file uploaded beforehand to the starter message (see `CreateMessage::uploaded_attachment`)
+    pub fn uploaded_attachment(
+        mut self,
+        filename: impl Into<String>,
+        uploaded_filename: impl Into<String>,
+    ) -> Self {
+        self.message = self
+            .message
+            .uploaded_attachment(filename, uploaded_filename);
+        self
+    }
+
+    /// Applies a tag of the forum (max 5)
+    pub fn applied_tag(mut self, tag_id: impl Into<String>) -> Self {
+        self.applied_tags.push(tag_id.into());
+        self
+    }
+
+    /// Replaces every applied tag
+    pub fn applied_tags(mut self, tag_ids: Vec<String>) -> Self {
+        self.applied_tags = tag_ids;
+        self
+    }
+
+    /// Sets the auto archive duration, in minutes (60, 1440, 4320 or 10080)
+    pub fn auto_archive_duration(mut self, minutes: u64) -> Self {
+        self.auto_archive_duration = Some(minutes);
+        self
+    }
+
+    /// Sets the slowmode of the post in seconds (max 21600)
+    pub fn slowmode(mut self, seconds: u64) -> Self {
+        self.rate_limit_per_user = Some(seconds);
+        self
+    }
+
+    /// Sets a raw JSON field not covered by the builder
+    pub fn field(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.extra.insert(key.into(), value.into());
+        self
+    }
+}
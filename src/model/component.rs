@@ -0,0 +1,284 @@
+use super::Emoji;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum ComponentType {
+    ActionRow = 1,
+    Button = 2,
+    StringSelect = 3,
+    TextInput = 4,
+    UserSelect = 5,
+    RoleSelect = 6,
+    MentionableSelect = 7,
+    ChannelSelect = 8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum ButtonStyle {
+    Primary = 1,
+    Secondary = 2,
+    Success = 3,
+    Danger = 4,
+    Link = 5,
+    Premium = 6,
+}
+
+/// Style of a text input. SEE: <https://docs.discord.food/resources/components#text-input-style>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum TextInputStyle {
+    /// Single-line input
+    Short = 1,
+    /// Multi-line input
+    Paragraph = 2,
+}
+
+/// A message component. SEE: <https://docs.discord.food/resources/components>
+///
+/// Component types without a typed model, and typed components that fail to parse
+/// (e.g. a new button style), are kept as raw JSON in `Unknown`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum Component {
+    ActionRow(ActionRow),
+    Button(Box<Button>),
+    SelectMenu(SelectMenu),
+    TextInput(TextInput),
+    Unknown(serde_json::Value),
+}
+
+impl<'de> Deserialize<'de> for Component {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let component = match value.get("type").and_then(|kind| kind.as_u64()) {
+            Some(1) => serde_json::from_value(value.clone()).map(Component::ActionRow),
+            Some(2) => serde_json::from_value(value.clone())
+                .map(|button| Component::Button(Box::new(button))),
+            Some(3 | 5 | 6 | 7 | 8) => {
+                serde_json::from_value(value.clone()).map(Component::SelectMenu)
+            }
+            Some(4) => serde_json::from_value(value.clone()).map(Component::TextInput),
+            _ => return Ok(Component::Unknown(value)),
+        };
+        // One unexpected component must not fail the whole message
+        Ok(component.unwrap_or(Component::Unknown(value)))
+    }
+}
+
+impl Component {
+    /// Visits this component and every component nested inside it
+    ///
+    /// Nested components of untyped layout components (containers, sections)
+    /// are parsed on the fly so their buttons and selects are reachable too.
+    pub fn walk(&self, f: &mut impl FnMut(&Component)) {
+        f(self);
+        match self {
+            Component::ActionRow(row) => {
+                for component in &row.components {
+                    component.walk(f);
+                }
+            }
+            Component::Unknown(value) => {
+                if let Some(children) = value
+                    .get("components")
+                    .and_then(|c| serde_json::from_value::<Vec<Component>>(c.clone()).ok())
+                {
+                    for component in &children {
+                        component.walk(f);
+                    }
+                }
+                if let Some(accessory) = value
+                    .get("accessory")
+                    .and_then(|a| serde_json::from_value::<Component>(a.clone()).ok())
+                {
+                    accessory.walk(f);
+                }
+            }
+            Component::Button(_) | Component::SelectMenu(_) | Component::TextInput(_) => {}
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionRow {
+    /// Type of the component (always 1)
+    #[serde(rename = "type")]
+    pub kind: ComponentType,
+
+    /// Unique identifier of the component within the message
+    pub id: Option<u32>,
+
+    /// Components in the row (up to 5 buttons or 1 select menu)
+    #[serde(default)]
+    pub components: Vec<Component>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Button {
+    /// Type of the component (always 2)
+    #[serde(rename = "type")]
+    pub kind: ComponentType,
+
+    /// Unique identifier of the component within the message
+    pub id: Option<u32>,
+
+    /// Style of the button
+    pub style: ButtonStyle,
+
+    /// Text that appears on the button (max 80 characters)
+    pub label: Option<String>,
+
+    /// Emoji that appears on the button
+    pub emoji: Option<Emoji>,
+
+    /// Developer-defined identifier for the button (not present on link and premium buttons)
+    pub custom_id: Option<String>,
+
+    /// ID of the purchasable SKU (premium buttons only)
+    pub sku_id: Option<String>,
+
+    /// URL opened by the button (link buttons only)
+    pub url: Option<String>,
+
+    /// Whether the button is disabled
+    #[serde(default)]
+    pub disabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectMenu {
+    /// Type of the select menu (string, user, role, mentionable or channel)
+    #[serde(rename = "type")]
+    pub kind: ComponentType,
+
+    /// Unique identifier of the component within the message
+    pub id: Option<u32>,
+
+    /// Developer-defined identifier for the select menu
+    pub custom_id: String,
+
+    /// Choices of a string select menu (max 25)
+    #[serde(default)]
+    pub options: Vec<SelectOption>,
+
+    /// Channel types to include in a channel select menu
+    pub channel_types: Option<Vec<u8>>,
+
+    /// Placeholder text shown if nothing is selected
+    pub placeholder: Option<String>,
+
+    /// Minimum number of items that must be chosen (default 1)
+    pub min_values: Option<u8>,
+
+    /// Maximum number of items that can be chosen (default 1)
+    pub max_values: Option<u8>,
+
+    /// Whether the select menu is disabled
+    #[serde(default)]
+    pub disabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectOption {
+    /// User-facing name of the option (max 100 characters)
+    pub label: String,
+
+    /// Developer-defined value of the option (max 100 characters)
+    pub value: String,
+
+    /// Additional description of the option (max 100 characters)
+    pub description: Option<String>,
+
+    /// Emoji shown next to the option
+    pub emoji: Option<Emoji>,
+
+    /// Whether the option is selected by default
+    #[serde(default)]
+    pub default: bool,
+}
+
+/// A text field of a modal. SEE: <https://docs.discord.food/resources/components#text-input>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextInput {
+    /// Type of the component (always 4)
+    #[serde(rename = "type")]
+    pub kind: ComponentType,
+
+    /// Unique identifier of the component within the modal
+    pub id: Option<u32>,
+
+    /// Developer-defined identifier for the input
+    pub custom_id: String,
+
+    /// Style of the input
+    pub style: TextInputStyle,
+
+    /// Label shown above the input (max 45 characters)
+    pub label: Option<String>,
+
+    /// Minimum input length
+    pub min_length: Option<u16>,
+
+    /// Maximum input length
+    pub max_length: Option<u16>,
+
+    /// Whether the input must be filled (default true)
+    pub required: Option<bool>,
+
+    /// Pre-filled value
+    pub value: Option<String>,
+
+    /// Placeholder text shown if the input is empty
+    pub placeholder: Option<String>,
+}
+
+/// A modal opened by an application in response to an interaction of the current user. SEE: <https://docs.discord.food/topics/gateway-events#interaction-modal-create>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Modal {
+    /// ID of the interaction that opened the modal
+    pub id: String,
+
+    /// Nonce of the interaction that opened the modal
+    pub nonce: Option<String>,
+
+    /// ID of the channel the interaction was sent in
+    pub channel_id: Option<String>,
+
+    /// Developer-defined identifier for the modal
+    pub custom_id: String,
+
+    /// Title of the modal (max 45 characters)
+    pub title: String,
+
+    /// Components of the modal (action rows of text inputs)
+    #[serde(default)]
+    pub components: Vec<Component>,
+
+    /// Application that opened the modal
+    pub application: Option<serde_json::Value>,
+}
+
+impl Modal {
+    /// Returns every text input of the modal
+    pub fn text_inputs(&self) -> Vec<TextInput> {
+        let mut inputs = Vec::new();
+        for component in &self.components {
+            component.walk(&mut |component| {
+                if let Component::TextInput(input) = component {
+                    inputs.push(input.clone());
+                }
+            });
+        }
+        inputs
+    }
+
+    /// Finds a text input by its custom ID
+    pub fn text_input(&self, custom_id: &str) -> Option<TextInput> {
+        self.text_inputs()
+            .into_iter()
+            .find(|input| input.custom_id == custom_id)
+    }
+}
@@ -0,0 +1,164 @@
+use serde::{Deserialize, Serialize};
+
+/// Service of a connected account. SEE: <https://docs.discord.food/resources/connected-accounts#connection-type>
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum ConnectionType {
+    BattleNet,
+    Bluesky,
+    Crunchyroll,
+    Domain,
+    Ebay,
+    EpicGames,
+    Facebook,
+    GitHub,
+    Instagram,
+    LeagueOfLegends,
+    Mastodon,
+    PayPal,
+    PlayStation,
+    Reddit,
+    RiotGames,
+    Roblox,
+    Spotify,
+    Skype,
+    Steam,
+    TikTok,
+    Twitch,
+    Twitter,
+    Xbox,
+    YouTube,
+    /// A service not known by this library
+    Other(String),
+}
+
+impl ConnectionType {
+    /// Returns the identifier used by the API (e.g. "spotify")
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::BattleNet => "battlenet",
+            Self::Bluesky => "bluesky",
+            Self::Crunchyroll => "crunchyroll",
+            Self::Domain => "domain",
+            Self::Ebay => "ebay",
+            Self::EpicGames => "epicgames",
+            Self::Facebook => "facebook",
+            Self::GitHub => "github",
+            Self::Instagram => "instagram",
+            Self::LeagueOfLegends => "leagueoflegends",
+            Self::Mastodon => "mastodon",
+            Self::PayPal => "paypal",
+            Self::PlayStation => "playstation",
+            Self::Reddit => "reddit",
+            Self::RiotGames => "riotgames",
+            Self::Roblox => "roblox",
+            Self::Spotify => "spotify",
+            Self::Skype => "skype",
+            Self::Steam => "steam",
+            Self::TikTok => "tiktok",
+            Self::Twitch => "twitch",
+            Self::Twitter => "twitter",
+            Self::Xbox => "xbox",
+            Self::YouTube => "youtube",
+            Self::Other(kind) => kind,
+        }
+    }
+}
+
+impl From<String> for ConnectionType {
+    fn from(kind: String) -> Self {
+        match kind.as_str() {
+            "battlenet" => Self::BattleNet,
+            "bluesky" => Self::Bluesky,
+            "crunchyroll" => Self::Crunchyroll,
+            "domain" => Self::Domain,
+            "ebay" => Self::Ebay,
+            "epicgames" => Self::EpicGames,
+            "facebook" => Self::Facebook,
+            "github" => Self::GitHub,
+            "instagram" => Self::Instagram,
+            "leagueoflegends" => Self::LeagueOfLegends,
+            "mastodon" => Self::Mastodon,
+            "paypal" => Self::PayPal,
+            "playstation" => Self::PlayStation,
+            "reddit" => Self::Reddit,
+            "riotgames" => Self::RiotGames,
+            "roblox" => Self::Roblox,
+            "spotify" => Self::Spotify,
+            "skype" => Self::Skype,
+            "steam" => Self::Steam,
+            "tiktok" => Self::TikTok,
+            "twitch" => Self::Twitch,
+            "twitter" => Self::Twitter,
+            "xbox" => Self::Xbox,
+            "youtube" => Self::YouTube,
+            _ => Self::Other(kind),
+        }
+    }
+}
+
+impl From<ConnectionType> for String {
+    fn from(kind: ConnectionType) -> Self {
+        kind.as_str().to_string()
+    }
+}
+
+impl std::fmt::Display for ConnectionType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An account connected to the user (Spotify, Steam, GitHub, ...). SEE: <https://docs.discord.food/resources/connected-accounts#connection-object>
+///
+/// Connections shown on another user's profile only carry `kind`, `id`, `name`, `verified` and `metadata`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Connection {
+    /// ID of the account on the connected service
+    pub id: String,
+
+    /// Service of the connection
+    #[serde(rename = "type")]
+    pub kind: ConnectionType,
+
+    /// Username of the account on the connected service
+    pub name: String,
+
+    /// Whether the connection is verified
+    #[serde(default)]
+    pub verified: bool,
+
+    /// Service-specific metadata (e.g. follower counts, creation date)
+    pub metadata: Option<serde_json::Value>,
+
+    /// Whether the connection is revoked
+    #[serde(default)]
+    pub revoked: bool,
+
+    /// Whether friend sync is enabled for the connection
+    #[serde(default)]
+    pub friend_sync: bool,
+
+    /// Whether activities from the connection are shown in presence
+    #[serde(default)]
+    pub show_activity: bool,
+
+    /// Whether the connection has a corresponding third-party OAuth2 token
+    #[serde(default)]
+    pub two_way_link: bool,
+
+    /// Visibility of the connection on the profile (0 = hidden, 1 = visible)
+    #[serde(default)]
+    pub visibility: u8,
+
+    /// Visibility of the connection's metadata (0 = hidden, 1 = visible)
+    #[serde(default)]
+    pub metadata_visibility: u8,
+}
+
+impl Connection {
+    /// Whether the connection is shown on the user's profile
+    pub fn is_visible(&self) -> bool {
+        self.visibility == 1
+    }
+}
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+/// An application that can run as an activity in a voice channel (e.g. Watch Together). SEE: <https://docs.discord.food/resources/application#embedded-activity-config-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddedApplication {
+    /// ID of the application, used to launch the activity
+    pub id: String,
+
+    /// Name of the application
+    #[serde(default)]
+    pub name: String,
+
+    /// Icon hash of the application
+    pub icon: Option<String>,
+
+    /// Description of the application
+    #[serde(default)]
+    pub description: String,
+
+    /// How the activity runs (only set in the activity shelf)
+    pub embedded_activity_config: Option<EmbeddedActivityConfig>,
+}
+
+/// Launch settings of an embedded activity
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EmbeddedActivityConfig {
+    /// Platforms the activity runs on ("web", "android", "ios")
+    #[serde(default)]
+    pub supported_platforms: Vec<String>,
+
+    /// Boost level the guild needs to launch the activity (0 for none)
+    pub premium_tier_requirement: Option<u8>,
+
+    /// Whether only users old enough can launch the activity
+    #[serde(default)]
+    pub requires_age_gate: bool,
+}
+
+impl EmbeddedApplication {
+    /// Watch Together (YouTube)
+    pub const WATCH_TOGETHER: &'static str = "880218394199220334";
+    /// Poker Night
+    pub const POKER_NIGHT: &'static str = "755827207812677713";
+    /// Chess in the Park
+    pub const CHESS_IN_THE_PARK: &'static str = "832012774040141894";
+    /// Putt Party
+    pub const PUTT_PARTY: &'static str = "945737671223947305";
+    /// Sketch Heads
+    pub const SKETCH_HEADS: &'static str = "902271654783242291";
+    /// Letter League
+    pub const LETTER_LEAGUE: &'static str = "879863686565621790";
+    /// SpellCast
+    pub const SPELLCAST: &'static str = "852509694341283871";
+    /// Blazing 8s
+    pub const BLAZING_8S: &'static str = "832025144389533716";
+
+    /// Whether the activity can be launched in a guild with the given boost level
+    pub fn is_available_at_tier(&self, premium_tier: u8) -> bool {
+        self.embedded_activity_config
+            .as_ref()
+            .and_then(|config| config.premium_tier_requirement)
+            .is_none_or(|required| premium_tier >= required)
+    }
+}
@@ -1,30 +1,233 @@
-use serde::{Deserialize, Serialize};
+use super::{Activity, Channel, Guild, Presence, Relationship, User, VoiceState};
+use serde::{Deserialize, Deserializer, Serialize};
 
-/// READY_SUPPLEMENTAL payload.
+/// READY payload, handed to `EventHandler::on_ready_full`. SEE: <https://docs.discord.food/topics/gateway-events#ready>
+///
+/// Entries of the lists that fail to parse are skipped instead of failing the whole payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadyPayload {
+    /// Gateway API version
+    #[serde(default)]
+    pub v: u8,
+
+    /// The current user
+    pub user: User,
+
+    /// Users related to the current user (friends, DM recipients, ...)
+    #[serde(default, deserialize_with = "lenient_vec")]
+    pub users: Vec<User>,
+
+    /// Guilds the current user is in
+    #[serde(default, deserialize_with = "lenient_vec")]
+    pub guilds: Vec<Guild>,
+
+    /// DMs and group DMs of the current user
+    #[serde(default, deserialize_with = "lenient_vec")]
+    pub private_channels: Vec<Channel>,
+
+    /// Relationships of the current user
+    #[serde(default, deserialize_with = "lenient_vec")]
+    pub relationships: Vec<Relationship>,
+
+    /// Read states of the channels
+    #[serde(default)]
+    pub read_state: ReadStateContainer,
+
+    /// Base64-encoded protobuf user settings (see `user_settings` with the `settings-proto`
+    /// feature)
+    pub user_settings_proto: Option<String>,
+
+    /// ID of the gateway session
+    pub session_id: String,
+
+    /// Type of the gateway session ("normal")
+    pub session_type: Option<String>,
+
+    /// Gateway URL to resume the session on
+    pub resume_gateway_url: Option<String>,
+
+    /// Hashed ID of the authentication session
+    pub auth_session_id_hash: Option<String>,
+
+    /// Other gateway sessions of the current user
+    #[serde(default, deserialize_with = "lenient_vec")]
+    pub sessions: Vec<ReadySession>,
+
+    /// Token used to send analytics events
+    pub analytics_token: Option<String>,
+
+    /// Country of the current user (ISO 3166-1 alpha-2)
+    pub country_code: Option<String>,
+
+    /// Number of pending friend suggestions
+    #[serde(default)]
+    pub friend_suggestion_count: u64,
+}
+
+impl ReadyPayload {
+    /// Decodes the protobuf user settings (`None` if READY had none)
+    ///
+    /// The versions of the settings are in `PreloadedUserSettings::versions`.
+    #[cfg(feature = "settings-proto")]
+    pub fn user_settings(
+        &self,
+    ) -> Option<crate::Result<super::settings_proto::PreloadedUserSettings>> {
+        self.user_settings_proto
+            .as_deref()
+            .map(super::settings_proto::PreloadedUserSettings::from_base64)
+    }
+}
+
+/// A gateway session of the current user, from READY
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadySession {
+    /// ID of the session
+    pub session_id: String,
+
+    /// Online status of the session
+    pub status: Option<String>,
+
+    /// Client that opened the session
+    #[serde(default)]
+    pub client_info: ReadySessionClientInfo,
+
+    /// Activities of the session
+    #[serde(default, deserialize_with = "lenient_vec")]
+    pub activities: Vec<Activity>,
+}
+
+/// Client of a gateway session
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReadySessionClientInfo {
+    /// Client type ("desktop", "web", "mobile")
+    pub client: Option<String>,
+
+    /// Operating system of the client
+    pub os: Option<String>,
+
+    /// Version of the client
+    pub version: Option<u64>,
+}
+
+/// Deserializes a list, skipping the entries that don't match `T`
+fn lenient_vec<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: serde::de::DeserializeOwned,
+{
+    let values = Option::<Vec<serde_json::Value>>::deserialize(deserializer)?.unwrap_or_default();
+    Ok(values
+        .into_iter()
+        .filter_map(|value| serde_json::from_value(value).ok())
+        .collect())
+}
+
+/// Deserializes a list of lists, skipping the entries that don't match `T`
+fn lenient_nested_vec<'de, D, T>(deserializer: D) -> Result<Vec<Vec<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: serde::de::DeserializeOwned,
+{
+    let values = Option::<Vec<Vec<serde_json::Value>>>::deserialize(deserializer)?;
+    Ok(values
+        .unwrap_or_default()
+        .into_iter()
+        .map(|values| {
+            values
+                .into_iter()
+                .filter_map(|value| serde_json::from_value(value).ok())
+                .collect()
+        })
+        .collect())
+}
+
+/// Deserializes the guild states, keeping an empty state for the entries that don't match
+/// so the lists indexed like them stay aligned
+fn aligned_guild_states<'de, D>(deserializer: D) -> Result<Vec<SupplementalGuildState>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let values = Option::<Vec<serde_json::Value>>::deserialize(deserializer)?.unwrap_or_default();
+    Ok(values
+        .into_iter()
+        .map(|value| {
+            serde_json::from_value(value.clone()).unwrap_or_else(|_| SupplementalGuildState {
+                id: value
+                    .get("id")
+                    .and_then(|id| id.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                ..Default::default()
+            })
+        })
+        .collect())
+}
+
+/// READY_SUPPLEMENTAL payload. SEE: <https://docs.discord.food/topics/gateway-events#ready-supplemental>
+///
+/// `merged_members` and `merged_presences.guilds` are indexed like `guilds`, see
+/// `guild_members` and `guild_presences`. Guilds that fail to parse keep their index with
+/// an empty state (and an empty ID if it is missing too).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReadySupplemental {
     #[serde(default)]
     pub merged_presences: MergedPresences,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient_nested_vec")]
     pub merged_members: Vec<Vec<MergedMember>>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "aligned_guild_states")]
     pub guilds: Vec<SupplementalGuildState>,
     #[serde(default)]
     pub user_activities: Vec<serde_json::Value>,
-    #[serde(default)]
-    pub lazy_private_channels: Vec<serde_json::Value>,
+    #[serde(default, deserialize_with = "lenient_vec")]
+    pub lazy_private_channels: Vec<Channel>,
     #[serde(default)]
     pub game_invites: Vec<serde_json::Value>,
     #[serde(default)]
     pub disclose: Vec<String>,
 }
 
+impl ReadySupplemental {
+    /// Members of the current user's guilds, by guild ID
+    pub fn guild_members(&self) -> impl Iterator<Item = (&str, &[MergedMember])> {
+        self.guilds
+            .iter()
+            .zip(&self.merged_members)
+            .filter(|(guild, _)| !guild.id.is_empty())
+            .map(|(guild, members)| (guild.id.as_str(), members.as_slice()))
+    }
+
+    /// Presences of the members of the current user's guilds, by guild ID
+    pub fn guild_presences(&self) -> impl Iterator<Item = (&str, &[MergedPresence])> {
+        self.guilds
+            .iter()
+            .zip(&self.merged_presences.guilds)
+            .filter(|(guild, _)| !guild.id.is_empty())
+            .map(|(guild, presences)| (guild.id.as_str(), presences.as_slice()))
+    }
+
+    /// Every presence of the payload, friends first
+    pub fn presences(&self) -> impl Iterator<Item = &MergedPresence> {
+        self.merged_presences
+            .friends
+            .iter()
+            .chain(self.merged_presences.guilds.iter().flatten())
+    }
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MergedPresences {
-    #[serde(default)]
-    pub friends: Vec<serde_json::Value>,
-    #[serde(default)]
-    pub guilds: Vec<Vec<serde_json::Value>>,
+    #[serde(default, deserialize_with = "lenient_vec")]
+    pub friends: Vec<MergedPresence>,
+    #[serde(default, deserialize_with = "lenient_nested_vec")]
+    pub guilds: Vec<Vec<MergedPresence>>,
+}
+
+/// Presence of a user in READY_SUPPLEMENTAL
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergedPresence {
+    pub user_id: String,
+    #[serde(flatten)]
+    pub presence: Presence,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,11 +251,12 @@ pub struct MergedMember {
     pub avatar: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Lazy state of a guild in READY_SUPPLEMENTAL
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SupplementalGuildState {
     pub id: String,
-    #[serde(default)]
-    pub voice_states: Vec<serde_json::Value>,
+    #[serde(default, deserialize_with = "lenient_vec")]
+    pub voice_states: Vec<VoiceState>,
     #[serde(default)]
     pub embedded_activities: Vec<serde_json::Value>,
     #[serde(default)]
@@ -92,3 +296,40 @@ pub struct ReadStateEntry {
     pub last_pin_timestamp: Option<String>,
     pub flags: Option<u64>,
 }
+
+impl ReadStateEntry {
+    /// Creates an empty read state for a channel
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            read_state_type: None,
+            last_acked_id: None,
+            badge_count: None,
+            mention_count: None,
+            last_message_id: None,
+            last_viewed: None,
+            last_pin_timestamp: None,
+            flags: None,
+        }
+    }
+
+    /// Whether the latest known message is newer than the last acknowledged one
+    pub fn is_unread(&self) -> bool {
+        let Some(last_message) = self.last_message_id.as_deref().and_then(snowflake) else {
+            return false;
+        };
+        match self.last_acked_id.as_deref().and_then(snowflake) {
+            Some(last_acked) => last_message > last_acked,
+            None => true,
+        }
+    }
+
+    /// Number of unacknowledged mentions
+    pub fn mentions(&self) -> u64 {
+        self.mention_count.unwrap_or(0)
+    }
+}
+
+fn snowflake(id: &str) -> Option<u64> {
+    id.parse().ok()
+}
@@ -1,4 +1,9 @@
-use super::{Channel, Emoji, Nameplate, Permissions, Role, Sticker, User};
+use super::timestamp::parse_timestamp;
+use super::{
+    Channel, Emoji, GuildId, GuildTag, Nameplate, PermissionOverwriteType, Permissions, Role,
+    Sticker, Timestamp, User,
+};
+use crate::cdn;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -46,7 +51,8 @@ pub struct Guild {
     /// Guild name
     pub name: Option<String>,
 
-    /// Icon hash (if the guild has an icon)
+    /// Icon hash (if the guild has an icon), sent as `icon` by Discord
+    #[serde(alias = "icon")]
     pub icon_hash: Option<String>,
 
     /// Splash hash
@@ -247,6 +253,74 @@ pub struct Member {
     pub permissions: Option<Permissions>,
 }
 
+/// A guild member built from full or partial member payloads
+///
+/// Message and voice payloads carry members without `user`, so the user lives in the
+/// user cache and fields never received stay at their default.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PartialMember {
+    /// The ID of the guild
+    pub guild_id: String,
+
+    /// The ID of the user this guild member represents
+    pub user_id: String,
+
+    /// The nickname of the member in the guild
+    pub nick: Option<String>,
+
+    /// The member's guild avatar hash (if any)
+    pub avatar: Option<String>,
+
+    /// The member's guild banner hash
+    pub banner: Option<String>,
+
+    /// The member's roles in the guild
+    #[serde(default)]
+    pub roles: Vec<String>,
+
+    /// The timestamp when the member joined the guild, in ISO8601 format
+    pub joined_at: Option<String>,
+
+    /// The timestamp when the member started boosting the guild, in ISO8601 format (if any)
+    pub premium_since: Option<String>,
+
+    /// Whether the member is deafened in voice channels
+    #[serde(default)]
+    pub deaf: bool,
+
+    /// Whether the member is muted in voice channels
+    #[serde(default)]
+    pub mute: bool,
+
+    /// Whether the member has not yet passed the guild's Membership Screening requirements
+    #[serde(default)]
+    pub pending: bool,
+
+    /// When the member's timeout expires, in ISO8601 format (if any)
+    pub communication_disabled_until: Option<String>,
+
+    /// The member's flags
+    #[serde(default)]
+    pub flags: u64,
+}
+
+impl PartialMember {
+    /// Whether the member has a role
+    pub fn has_role(&self, role_id: &str) -> bool {
+        self.roles.iter().any(|role| role == role_id)
+    }
+
+    /// When the member joined the guild (if known)
+    pub fn joined_at_parsed(&self) -> Option<Timestamp> {
+        parse_timestamp(self.joined_at.as_deref())
+    }
+
+    /// When the member started boosting the guild (if any)
+    pub fn premium_since_parsed(&self) -> Option<Timestamp> {
+        parse_timestamp(self.premium_since.as_deref())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SupplementalMember {
     /// The ID of the user this guild member represents
@@ -276,6 +350,147 @@ pub struct Ban {
     pub reason: Option<String>,
 }
 
+/// Result of `GuildsManager::bulk_ban_members`. SEE: <https://docs.discord.food/resources/guild#bulk-guild-ban>
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BulkBanResponse {
+    /// IDs of the users that were banned
+    #[serde(default)]
+    pub banned_users: Vec<String>,
+
+    /// IDs of the users that could not be banned (already banned or not bannable)
+    #[serde(default)]
+    pub failed_users: Vec<String>,
+}
+
+/// Public information of a guild, readable without joining it. SEE: <https://docs.discord.food/resources/guild#guild-preview-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuildPreview {
+    /// Guild ID
+    pub id: String,
+
+    /// Guild name
+    pub name: String,
+
+    /// Icon hash
+    pub icon: Option<String>,
+
+    /// Splash hash
+    pub splash: Option<String>,
+
+    /// Discovery splash hash
+    pub discovery_splash: Option<String>,
+
+    /// Banner hash
+    pub banner: Option<String>,
+
+    /// Description of the guild
+    pub description: Option<String>,
+
+    /// Enabled guild features
+    #[serde(default)]
+    pub features: Vec<String>,
+
+    /// Custom emojis of the guild
+    #[serde(default)]
+    pub emojis: Vec<Emoji>,
+
+    /// Custom stickers of the guild
+    #[serde(default)]
+    pub stickers: Vec<Sticker>,
+
+    /// Approximate count of total members
+    pub approximate_member_count: Option<u64>,
+
+    /// Approximate count of online members
+    pub approximate_presence_count: Option<u64>,
+}
+
+/// Public profile of a guild, including its server tag. SEE: <https://docs.discord.food/resources/discovery#guild-profile-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuildProfile {
+    /// Guild ID
+    pub id: String,
+
+    /// Guild name
+    pub name: String,
+
+    /// Icon hash
+    pub icon_hash: Option<String>,
+
+    /// Description of the guild
+    pub description: Option<String>,
+
+    /// Approximate count of total members
+    pub member_count: Option<u64>,
+
+    /// Approximate count of online members
+    pub online_count: Option<u64>,
+
+    /// Text of the server tag (max 4 characters)
+    pub tag: Option<String>,
+
+    /// Hash of the server tag badge
+    pub badge_hash: Option<String>,
+
+    /// Primary color of the badge
+    pub badge_color_primary: Option<String>,
+
+    /// Secondary color of the badge
+    pub badge_color_secondary: Option<String>,
+
+    /// Enabled guild features
+    #[serde(default)]
+    pub features: Vec<String>,
+
+    /// Who can see the profile (1 = public, 2 = restricted, 3 = public with recruitment)
+    pub visibility: Option<u8>,
+}
+
+impl GuildProfile {
+    /// Returns the server tag of the guild (`None` if it has none)
+    pub fn guild_tag(&self) -> Option<GuildTag> {
+        Some(GuildTag {
+            guild_id: self.id.clone(),
+            tag: self.tag.clone()?,
+            badge: self.badge_hash.clone(),
+        })
+    }
+}
+
+/// Discovery listing settings of a guild. SEE: <https://docs.discord.food/resources/discovery#discovery-metadata-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuildDiscoveryMetadata {
+    /// Guild ID
+    pub guild_id: String,
+
+    /// ID of the primary discovery category
+    pub primary_category_id: u32,
+
+    /// Search keywords (max 10)
+    pub keywords: Option<Vec<String>>,
+
+    /// Whether the guild emojis are shown in discovery
+    #[serde(default)]
+    pub emoji_discoverability_enabled: bool,
+
+    /// When the guild applied to the partner program, in ISO8601 format
+    pub partner_application_timestamp: Option<String>,
+
+    /// When the partner application was accepted or denied, in ISO8601 format
+    pub partner_actioned_timestamp: Option<String>,
+
+    /// Whether the guild is published in discovery
+    #[serde(default)]
+    pub is_published: bool,
+
+    /// IDs of the secondary discovery categories
+    #[serde(default)]
+    pub category_ids: Vec<u32>,
+
+    /// Long description shown on the discovery page
+    pub about: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WelcomeScreen {
     /// The server description shown in the welcome screen
@@ -301,6 +516,39 @@ pub struct WelcomeScreenChannel {
 }
 
 impl Guild {
+    /// Returns the ID of the guild as a typed snowflake
+    pub fn guild_id(&self) -> Option<GuildId> {
+        GuildId::parse(&self.id)
+    }
+
+    /// Returns the URL of the guild's icon (if any), a GIF when animated
+    pub fn icon_url(&self) -> Option<String> {
+        self.icon_hash
+            .as_ref()
+            .map(|hash| cdn::guild_icon(&self.id, hash).into())
+    }
+
+    /// Returns the URL of the guild's banner (if any), a GIF when animated
+    pub fn banner_url(&self) -> Option<String> {
+        self.banner
+            .as_ref()
+            .map(|hash| cdn::guild_banner(&self.id, hash).into())
+    }
+
+    /// Returns the URL of the guild's invite splash (if any)
+    pub fn splash_url(&self) -> Option<String> {
+        self.splash
+            .as_ref()
+            .map(|hash| cdn::guild_splash(&self.id, hash).into())
+    }
+
+    /// Returns the URL of the guild's discovery splash (if any)
+    pub fn discovery_splash_url(&self) -> Option<String> {
+        self.discovery_splash
+            .as_ref()
+            .map(|hash| cdn::guild_discovery_splash(&self.id, hash).into())
+    }
+
     /// Fetches a guild by id.
     pub async fn fetch(http: &crate::HttpClient, guild_id: impl AsRef<str>) -> crate::Result<Self> {
         let url = crate::http::api_url(&format!("/guilds/{}", guild_id.as_ref()));
@@ -315,4 +563,180 @@ impl Guild {
         http.delete(&url).await?;
         Ok(())
     }
+
+    /// Computes the guild-wide permissions of a member from @everyone and their roles
+    ///
+    /// The owner and administrators get every permission.
+    pub fn member_base_permissions(&self, member: &Member) -> Permissions {
+        if self.owner_id.as_deref() == Some(member.user.id.as_str()) {
+            return Permissions::all();
+        }
+
+        // The @everyone role shares its ID with the guild
+        let mut permissions = self
+            .roles
+            .iter()
+            .filter(|role| role.id == self.id || member.roles.contains(&role.id))
+            .fold(Permissions::empty(), |acc, role| acc | role.permissions);
+
+        if permissions.contains(Permissions::ADMINISTRATOR) {
+            return Permissions::all();
+        }
+        if member.is_timed_out() {
+            permissions &= Permissions::VIEW_CHANNEL | Permissions::READ_MESSAGE_HISTORY;
+        }
+        permissions
+    }
+
+    /// Computes the permissions of a member in a channel of this guild
+    ///
+    /// Applies the channel overwrites in Discord's order: @everyone, then the member's roles
+    /// (combined), then the member itself. For threads, pass the parent channel.
+    pub fn member_permissions_in(&self, channel: &Channel, member: &Member) -> Permissions {
+        let mut permissions = self.member_base_permissions(member);
+        if permissions.contains(Permissions::ADMINISTRATOR) {
+            return permissions;
+        }
+
+        let overwrites = &channel.permission_overwrites;
+        if let Some(everyone) = overwrites.iter().find(|overwrite| {
+            overwrite.kind == PermissionOverwriteType::Role && overwrite.id == self.id
+        }) {
+            permissions &= !everyone.deny;
+            permissions |= everyone.allow;
+        }
+
+        let (allow, deny) = overwrites
+            .iter()
+            .filter(|overwrite| {
+                overwrite.kind == PermissionOverwriteType::Role
+                    && member.roles.contains(&overwrite.id)
+            })
+            .fold(
+                (Permissions::empty(), Permissions::empty()),
+                |(allow, deny), overwrite| (allow | overwrite.allow, deny | overwrite.deny),
+            );
+        permissions &= !deny;
+        permissions |= allow;
+
+        if let Some(own) = overwrites.iter().find(|overwrite| {
+            overwrite.kind == PermissionOverwriteType::Member && overwrite.id == member.user.id
+        }) {
+            permissions &= !own.deny;
+            permissions |= own.allow;
+        }
+
+        // Permissions implicitly denied by missing base permissions
+        if !permissions.contains(Permissions::VIEW_CHANNEL) {
+            return Permissions::empty();
+        }
+        if !permissions.contains(Permissions::SEND_MESSAGES) {
+            permissions &= !(Permissions::SEND_TTS_MESSAGES
+                | Permissions::MENTION_EVERYONE
+                | Permissions::EMBED_LINKS
+                | Permissions::ATTACH_FILES);
+        }
+        permissions
+    }
+}
+
+impl Member {
+    /// Whether the member is currently timed out
+    pub fn is_timed_out(&self) -> bool {
+        self.communication_disabled_until_parsed()
+            .is_some_and(|until| !until.is_past())
+    }
+
+    /// When the member joined the guild
+    pub fn joined_at_parsed(&self) -> Option<Timestamp> {
+        Timestamp::parse(&self.joined_at)
+    }
+
+    /// When the member started boosting the guild (if any)
+    pub fn premium_since_parsed(&self) -> Option<Timestamp> {
+        parse_timestamp(self.premium_since.as_deref())
+    }
+
+    /// When the member's timeout expires (if any)
+    pub fn communication_disabled_until_parsed(&self) -> Option<Timestamp> {
+        parse_timestamp(self.communication_disabled_until.as_deref())
+    }
+}
+
+/// GUILD_MEMBER_ADD payload. SEE: <https://docs.discord.food/topics/gateway-events#guild-member-add>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuildMemberAdd {
+    /// ID of the guild
+    pub guild_id: String,
+
+    /// The member who joined
+    #[serde(flatten)]
+    pub member: Member,
+}
+
+/// GUILD_MEMBER_UPDATE payload. SEE: <https://docs.discord.food/topics/gateway-events#guild-member-update>
+///
+/// Unlike `Member`, `joined_at` may be null (guest members of voice channels).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuildMemberUpdate {
+    /// ID of the guild
+    pub guild_id: String,
+
+    /// The updated user
+    pub user: User,
+
+    /// The nickname of the member in the guild
+    pub nick: Option<String>,
+
+    /// The member's guild avatar hash (if any)
+    pub avatar: Option<String>,
+
+    /// The member's guild banner hash
+    pub banner: Option<String>,
+
+    /// The member's roles in the guild
+    #[serde(default)]
+    pub roles: Vec<String>,
+
+    /// The timestamp when the member joined the guild, in ISO8601 format
+    pub joined_at: Option<String>,
+
+    /// The timestamp when the member started boosting the guild, in ISO8601 format (if any)
+    pub premium_since: Option<String>,
+
+    /// Whether the member is deafened in voice channels
+    pub deaf: Option<bool>,
+
+    /// Whether the member is muted in voice channels
+    pub mute: Option<bool>,
+
+    /// Whether the member has not yet passed the guild's Membership Screening requirements
+    #[serde(default)]
+    pub pending: bool,
+
+    /// When the member's timeout expires, in ISO8601 format (if any)
+    pub communication_disabled_until: Option<String>,
+
+    /// The member's flags
+    pub flags: Option<u64>,
+}
+
+/// GUILD_MEMBER_REMOVE payload. SEE: <https://docs.discord.food/topics/gateway-events#guild-member-remove>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuildMemberRemove {
+    /// ID of the guild
+    pub guild_id: String,
+
+    /// The user who left or was removed
+    pub user: User,
+}
+
+/// GUILD_BAN_ADD / GUILD_BAN_REMOVE payload. SEE: <https://docs.discord.food/topics/gateway-events#guild-ban-add>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuildBanEvent {
+    /// ID of the guild
+    pub guild_id: String,
+
+    /// The banned or unbanned user
+    pub user: User,
 }
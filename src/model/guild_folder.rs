@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+/// A folder (or single guild) in the guild sidebar. SEE: <https://docs.discord.food/resources/user-settings#guild-folder-object>
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuildFolder {
+    /// ID of the folder (`None` for a guild outside of any folder)
+    pub id: Option<i64>,
+
+    /// Name of the folder
+    pub name: Option<String>,
+
+    /// Color of the folder (RGB integer)
+    pub color: Option<u32>,
+
+    /// IDs of the guilds in the folder, in display order
+    #[serde(default)]
+    pub guild_ids: Vec<String>,
+}
+
+impl GuildFolder {
+    /// Creates a named folder containing the given guilds
+    ///
+    /// The folder ID is derived from the current time, like the official client does.
+    pub fn new(name: impl Into<String>, guild_ids: Vec<String>) -> Self {
+        let id = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as i64)
+            .unwrap_or_default();
+        Self {
+            id: Some(id),
+            name: Some(name.into()),
+            color: None,
+            guild_ids,
+        }
+    }
+
+    /// Creates an entry for a single guild outside of any folder
+    pub fn single(guild_id: impl Into<String>) -> Self {
+        Self {
+            guild_ids: vec![guild_id.into()],
+            ..Default::default()
+        }
+    }
+
+    /// Sets the folder color (RGB integer, e.g. `0x5865F2`)
+    pub fn with_color(mut self, color: u32) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Whether this entry is an actual folder rather than a single guild
+    pub fn is_folder(&self) -> bool {
+        self.id.is_some()
+    }
+
+    /// Whether the folder contains the guild
+    pub fn contains(&self, guild_id: &str) -> bool {
+        self.guild_ids.iter().any(|id| id == guild_id)
+    }
+
+    /// Flattens folders into the guild order shown in the sidebar
+    pub fn positions(folders: &[GuildFolder]) -> Vec<String> {
+        folders
+            .iter()
+            .flat_map(|folder| folder.guild_ids.iter().cloned())
+            .collect()
+    }
+}
@@ -0,0 +1,109 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Events to receive from a guild, sent with `GatewaySender::subscribe_guilds` (op 37)
+///
+/// User accounts only receive the typing, activity and member events of the guilds they
+/// subscribed to. SEE: <https://docs.discord.food/topics/gateway-events#bulk-guild-subscribe>
+///
+/// # Example
+/// ```ignore
+/// use diself::model::GuildSubscription;
+///
+/// let subscription = GuildSubscription::all()
+///     .members(["user_id"])
+///     .channel("channel_id", [(0, 99)]);
+/// ctx.subscribe_guild("guild_id", subscription)?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct GuildSubscription {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    typing: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    threads: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    activities: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    member_updates: Option<bool>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    members: Vec<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    channels: BTreeMap<String, Vec<[u32; 2]>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    thread_member_lists: Vec<String>,
+}
+
+impl GuildSubscription {
+    /// Empty subscription, keeping the current one of the guild
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Typing, thread, activity and member update events, like the official client
+    pub fn all() -> Self {
+        Self::new()
+            .typing(true)
+            .threads(true)
+            .activities(true)
+            .member_updates(true)
+    }
+
+    /// Receives TYPING_START events
+    pub fn typing(mut self, enabled: bool) -> Self {
+        self.typing = Some(enabled);
+        self
+    }
+
+    /// Receives the thread events of every channel (not only the joined threads)
+    pub fn threads(mut self, enabled: bool) -> Self {
+        self.threads = Some(enabled);
+        self
+    }
+
+    /// Receives the presences and activities of the members
+    pub fn activities(mut self, enabled: bool) -> Self {
+        self.activities = Some(enabled);
+        self
+    }
+
+    /// Receives GUILD_MEMBER_UPDATE events of the cached members
+    pub fn member_updates(mut self, enabled: bool) -> Self {
+        self.member_updates = Some(enabled);
+        self
+    }
+
+    /// Receives the member and presence updates of these users
+    pub fn members<I>(mut self, user_ids: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.members.extend(user_ids.into_iter().map(Into::into));
+        self
+    }
+
+    /// Receives the member list of a channel, by ranges of 100 members (`(0, 99)`, ...)
+    pub fn channel(
+        mut self,
+        channel_id: impl Into<String>,
+        ranges: impl IntoIterator<Item = (u32, u32)>,
+    ) -> Self {
+        let ranges = ranges
+            .into_iter()
+            .map(|(start, end)| [start, end])
+            .collect();
+        self.channels.insert(channel_id.into(), ranges);
+        self
+    }
+
+    /// Receives THREAD_MEMBER_LIST_UPDATE events of these threads
+    pub fn thread_member_lists<I>(mut self, thread_ids: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.thread_member_lists
+            .extend(thread_ids.into_iter().map(Into::into));
+        self
+    }
+}
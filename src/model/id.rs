@@ -0,0 +1,274 @@
+use super::Timestamp;
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+/// First millisecond of 2015, the epoch of Discord snowflakes
+pub const DISCORD_EPOCH_MS: u64 = 1_420_070_400_000;
+
+/// A snowflake ID tagged with the kind of entity it identifies. SEE: <https://docs.discord.food/reference#snowflakes>
+///
+/// `Id<ChannelMarker>` and `Id<UserMarker>` are different types, so passing a user ID
+/// where a channel ID is expected fails to compile. IDs are serialized as strings, like
+/// the API does, and accept both strings and integers when deserialized.
+///
+/// Models still store their IDs as `String` while the crate migrates; typed accessors
+/// such as `Message::message_id` or `User::user_id` parse them on demand.
+///
+/// # Example
+/// ```
+/// use diself::model::{ChannelId, Snowflake};
+///
+/// let channel: ChannelId = "1100000000000000000".parse().unwrap();
+/// assert_eq!(channel.to_string(), "1100000000000000000");
+/// assert!(channel.timestamp_ms() > 1_600_000_000_000);
+///
+/// // Before-cursor for "messages older than a minute ago"
+/// let cutoff = Snowflake::from_timestamp_ms(channel.timestamp_ms() - 60_000);
+/// assert!(cutoff < channel.cast());
+/// ```
+pub struct Id<T> {
+    value: u64,
+    marker: PhantomData<fn() -> T>,
+}
+
+/// Marker of IDs of any entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GenericMarker;
+
+/// Marker of user IDs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UserMarker;
+
+/// Marker of channel (and thread) IDs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChannelMarker;
+
+/// Marker of guild IDs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GuildMarker;
+
+/// Marker of message IDs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MessageMarker;
+
+/// Marker of role IDs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RoleMarker;
+
+/// Marker of custom emoji IDs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EmojiMarker;
+
+/// Marker of application IDs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ApplicationMarker;
+
+/// An ID of any entity
+pub type Snowflake = Id<GenericMarker>;
+/// A user ID
+pub type UserId = Id<UserMarker>;
+/// A channel or thread ID
+pub type ChannelId = Id<ChannelMarker>;
+/// A guild ID
+pub type GuildId = Id<GuildMarker>;
+/// A message ID
+pub type MessageId = Id<MessageMarker>;
+/// A role ID
+pub type RoleId = Id<RoleMarker>;
+/// A custom emoji ID
+pub type EmojiId = Id<EmojiMarker>;
+/// An application ID
+pub type ApplicationId = Id<ApplicationMarker>;
+
+impl<T> Id<T> {
+    /// Wraps a raw snowflake
+    pub const fn new(value: u64) -> Self {
+        Self {
+            value,
+            marker: PhantomData,
+        }
+    }
+
+    /// Parses a snowflake string, returning `None` if it is not a number
+    pub fn parse(id: &str) -> Option<Self> {
+        id.parse().ok().map(Self::new)
+    }
+
+    /// Smallest ID created at a Unix timestamp (in milliseconds)
+    ///
+    /// Useful as a `before`/`after` pagination cursor.
+    pub const fn from_timestamp_ms(timestamp_ms: u64) -> Self {
+        Self::new(timestamp_ms.saturating_sub(DISCORD_EPOCH_MS) << 22)
+    }
+
+    /// Returns the raw snowflake
+    pub const fn get(self) -> u64 {
+        self.value
+    }
+
+    /// Unix timestamp (in milliseconds) at which the entity was created
+    pub const fn timestamp_ms(self) -> u64 {
+        (self.value >> 22) + DISCORD_EPOCH_MS
+    }
+
+    /// Time at which the entity was created, `None` if it is out of the supported range
+    pub fn created_at(self) -> Option<Timestamp> {
+        Timestamp::from_unix_ms(self.timestamp_ms() as i64)
+    }
+
+    /// ID of the internal worker that generated the ID
+    pub const fn worker_id(self) -> u8 {
+        ((self.value >> 17) & 0x1f) as u8
+    }
+
+    /// ID of the internal process that generated the ID
+    pub const fn process_id(self) -> u8 {
+        ((self.value >> 12) & 0x1f) as u8
+    }
+
+    /// Per-process counter incremented for every generated ID
+    pub const fn increment(self) -> u16 {
+        (self.value & 0xfff) as u16
+    }
+
+    /// Reinterprets the ID as the ID of another kind of entity
+    ///
+    /// E.g. the `@everyone` role shares the ID of its guild.
+    pub const fn cast<U>(self) -> Id<U> {
+        Id::new(self.value)
+    }
+}
+
+impl<T> Clone for Id<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Id<T> {}
+
+impl<T> PartialEq for Id<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T> Eq for Id<T> {}
+
+impl<T> PartialOrd for Id<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// IDs are ordered by creation time
+impl<T> Ord for Id<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl<T> Hash for Id<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for Id<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Id({})", self.value)
+    }
+}
+
+impl<T> fmt::Display for Id<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.value, f)
+    }
+}
+
+impl<T> FromStr for Id<T> {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Self::new)
+    }
+}
+
+impl<T> From<u64> for Id<T> {
+    fn from(value: u64) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T> From<Id<T>> for u64 {
+    fn from(id: Id<T>) -> Self {
+        id.value
+    }
+}
+
+impl<T> From<Id<T>> for String {
+    fn from(id: Id<T>) -> Self {
+        id.to_string()
+    }
+}
+
+impl<T> PartialEq<str> for Id<T> {
+    fn eq(&self, other: &str) -> bool {
+        Self::parse(other) == Some(*self)
+    }
+}
+
+impl<T> PartialEq<&str> for Id<T> {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl<T> PartialEq<String> for Id<T> {
+    fn eq(&self, other: &String) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl<T> Serialize for Id<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Id<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct IdVisitor<T>(PhantomData<fn() -> T>);
+
+        impl<T> Visitor<'_> for IdVisitor<T> {
+            type Value = Id<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a snowflake string or integer")
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+                Ok(Id::new(value))
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+                u64::try_from(value)
+                    .map(Id::new)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Signed(value), &self))
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                value
+                    .parse()
+                    .map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self))
+            }
+        }
+
+        deserializer.deserialize_any(IdVisitor(PhantomData))
+    }
+}
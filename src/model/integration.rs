@@ -0,0 +1,99 @@
+use super::User;
+use serde::{Deserialize, Serialize};
+
+/// An integration installed in a guild (bot, Twitch, YouTube, ...). SEE: <https://docs.discord.food/resources/integration#integration-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Integration {
+    /// ID of the integration
+    pub id: String,
+
+    /// Name of the integration
+    pub name: String,
+
+    /// Type of integration ("twitch", "youtube", "discord" or "guild_subscription")
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    /// Whether the integration is enabled
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Whether the integration is syncing (Twitch and YouTube only)
+    pub syncing: Option<bool>,
+
+    /// ID of the role subscribers receive (Twitch and YouTube only)
+    pub role_id: Option<String>,
+
+    /// Whether emoticons should be synced (Twitch only)
+    pub enable_emoticons: Option<bool>,
+
+    /// Behavior when a subscription expires (0 = remove role, 1 = kick)
+    pub expire_behavior: Option<u8>,
+
+    /// Grace period before expiring subscribers, in days
+    pub expire_grace_period: Option<u32>,
+
+    /// User who added the integration
+    pub user: Option<User>,
+
+    /// Account information of the integration
+    pub account: IntegrationAccount,
+
+    /// When the integration was last synced (ISO8601 timestamp)
+    pub synced_at: Option<String>,
+
+    /// Number of subscribers (Twitch and YouTube only)
+    pub subscriber_count: Option<u64>,
+
+    /// Whether the integration has been revoked
+    #[serde(default)]
+    pub revoked: bool,
+
+    /// Application of the integration (bots and OAuth2 applications)
+    pub application: Option<IntegrationApplication>,
+
+    /// OAuth2 scopes the application has been authorized with
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
+/// The external account (Twitch channel, YouTube channel, bot user, ...) behind an integration. SEE: <https://docs.discord.food/resources/integration#integration-account-structure>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrationAccount {
+    /// ID of the account on the external service
+    pub id: String,
+
+    /// Name of the account on the external service
+    pub name: String,
+}
+
+/// The application a bot or OAuth2 integration belongs to. SEE: <https://docs.discord.food/resources/integration#integration-application-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrationApplication {
+    /// ID of the application
+    pub id: String,
+
+    /// Name of the application
+    pub name: String,
+
+    /// Icon hash of the application
+    pub icon: Option<String>,
+
+    /// Description of the application
+    #[serde(default)]
+    pub description: String,
+
+    /// Bot user of the application (if it has one)
+    pub bot: Option<User>,
+}
+
+impl Integration {
+    /// Whether this integration is a bot added to the guild
+    pub fn is_bot(&self) -> bool {
+        self.kind == "discord"
+            && self
+                .application
+                .as_ref()
+                .is_some_and(|application| application.bot.is_some())
+    }
+}
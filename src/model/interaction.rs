@@ -1,3 +1,4 @@
+use super::{ApplicationCommandType, User};
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
@@ -11,6 +12,9 @@ pub enum InteractionType {
     ModalSubmit = 5,
 }
 
+/// Interaction a message responds to (`Message::interaction`). SEE: <https://docs.discord.food/resources/message#message-interaction-object>
+///
+/// Deprecated by Discord in favor of `Message::interaction_metadata`, but still sent.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Interaction {
     /// Unique ID of the interaction
@@ -19,5 +23,56 @@ pub struct Interaction {
     /// Type of the interaction
     #[serde(rename = "type")]
     pub kind: InteractionType,
-    // TODO
+
+    /// Name of the application command, including subcommands (e.g. `config set`)
+    pub name: Option<String>,
+
+    /// User that triggered the interaction
+    pub user: Option<User>,
+}
+
+/// Metadata of the interaction a message responds to (`Message::interaction_metadata`). SEE: <https://docs.discord.food/resources/message#message-interaction-metadata-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageInteractionMetadata {
+    /// ID of the interaction
+    pub id: String,
+
+    /// Type of the interaction
+    #[serde(rename = "type")]
+    pub kind: InteractionType,
+
+    /// User that triggered the interaction
+    pub user: User,
+
+    /// Name of the application command, including subcommands (application commands only)
+    pub name: Option<String>,
+
+    /// Type of the application command (application commands only)
+    pub command_type: Option<ApplicationCommandType>,
+
+    /// ID of the original response message, present only on follow-up messages
+    pub original_response_message_id: Option<String>,
+
+    /// The user the command was run on, present only on user command interactions
+    pub target_user: Option<User>,
+
+    /// The ID of the message the command was run on, present only on message command interactions
+    pub target_message_id: Option<String>,
+
+    /// ID of the message holding the clicked component (message component interactions only)
+    pub interacted_message_id: Option<String>,
+
+    /// Metadata of the interaction that opened the submitted modal (modal submits only)
+    pub triggering_interaction_metadata: Option<Box<MessageInteractionMetadata>>,
+}
+
+impl MessageInteractionMetadata {
+    /// Returns the name of the slash command, walking up from a modal submit to the command that opened it
+    pub fn command_name(&self) -> Option<&str> {
+        self.name.as_deref().or_else(|| {
+            self.triggering_interaction_metadata
+                .as_deref()
+                .and_then(|metadata| metadata.command_name())
+        })
+    }
 }
@@ -0,0 +1,188 @@
+use super::timestamp::parse_timestamp;
+use super::{ChannelType, EmbeddedApplication, Timestamp, User};
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum InviteType {
+    Guild = 0,
+    GroupDM = 1,
+    Friend = 2,
+}
+
+/// An invite to a guild, group DM or friend list. SEE: <https://docs.discord.food/resources/invite#invite-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Invite {
+    /// Type of the invite
+    #[serde(rename = "type")]
+    pub kind: Option<InviteType>,
+
+    /// Unique code of the invite
+    pub code: String,
+
+    /// Guild the invite is for (guild invites only)
+    pub guild: Option<InviteGuild>,
+
+    /// ID of the guild the invite is for (guild invites only)
+    pub guild_id: Option<String>,
+
+    /// Channel the invite is for
+    pub channel: Option<InviteChannel>,
+
+    /// User who created the invite
+    pub inviter: Option<User>,
+
+    /// Type of the target of a voice channel invite (1 = stream, 2 = embedded application)
+    pub target_type: Option<u8>,
+
+    /// User whose stream the invite is for
+    pub target_user: Option<User>,
+
+    /// Activity the invite launches (embedded application invites only)
+    pub target_application: Option<EmbeddedApplication>,
+
+    /// Approximate count of online members (fetched with counts only)
+    pub approximate_presence_count: Option<u64>,
+
+    /// Approximate count of total members (fetched with counts only)
+    pub approximate_member_count: Option<u64>,
+
+    /// When the invite expires, in ISO8601 format (null for never)
+    pub expires_at: Option<String>,
+
+    /// The invite's flags
+    pub flags: Option<u64>,
+
+    /// Usage metadata, only sent to users who can manage the invite
+    #[serde(flatten)]
+    pub metadata: InviteMetadata,
+}
+
+/// Usage metadata of an invite. SEE: <https://docs.discord.food/resources/invite#invite-metadata-object>
+///
+/// Only set on invites fetched from a channel or guild invite list, or just created.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InviteMetadata {
+    /// Number of times the invite has been used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uses: Option<u64>,
+
+    /// Max number of times the invite can be used (0 for unlimited)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_uses: Option<u64>,
+
+    /// Duration (in seconds) after which the invite expires (0 for never)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_age: Option<u64>,
+
+    /// Whether the invite only grants temporary membership
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temporary: Option<bool>,
+
+    /// When the invite was created, in ISO8601 format
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+}
+
+/// The partial guild of an invite, readable without joining it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InviteGuild {
+    /// Guild ID
+    pub id: String,
+
+    /// Guild name
+    pub name: String,
+
+    /// Icon hash
+    pub icon: Option<String>,
+
+    /// Splash hash
+    pub splash: Option<String>,
+
+    /// Banner hash
+    pub banner: Option<String>,
+
+    /// Description of the guild
+    pub description: Option<String>,
+
+    /// Enabled guild features
+    #[serde(default)]
+    pub features: Vec<String>,
+
+    /// Verification level required to talk in the guild
+    pub verification_level: Option<u8>,
+
+    /// Explicit content level of the guild
+    pub nsfw_level: Option<u8>,
+
+    /// Vanity invite code of the guild
+    pub vanity_url_code: Option<String>,
+
+    /// Number of boosts of the guild
+    pub premium_subscription_count: Option<u64>,
+}
+
+/// The partial channel of an invite
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InviteChannel {
+    /// Channel ID
+    pub id: String,
+
+    /// Channel type
+    #[serde(rename = "type")]
+    pub kind: ChannelType,
+
+    /// Channel name (group DMs may have none)
+    pub name: Option<String>,
+
+    /// Icon hash (group DMs only)
+    pub icon: Option<String>,
+}
+
+impl Invite {
+    /// Returns the shareable URL of the invite
+    pub fn url(&self) -> String {
+        format!("https://discord.gg/{}", self.code)
+    }
+
+    /// When the invite expires (`None` if it never does)
+    pub fn expires_at_parsed(&self) -> Option<Timestamp> {
+        parse_timestamp(self.expires_at.as_deref())
+    }
+
+    /// Whether the invite has expired or reached its max uses
+    pub fn is_expired(&self) -> bool {
+        let used_up = match (self.metadata.uses, self.metadata.max_uses) {
+            (Some(uses), Some(max_uses)) => max_uses > 0 && uses >= max_uses,
+            _ => false,
+        };
+        used_up || self.expires_at_parsed().is_some_and(|at| at.is_past())
+    }
+
+    /// Extracts the invite codes of `discord.gg` and `discord.com/invite` links in a text
+    pub fn codes_in(text: &str) -> Vec<String> {
+        const PREFIXES: [&str; 3] = [
+            "discord.gg/",
+            "discord.com/invite/",
+            "discordapp.com/invite/",
+        ];
+        let mut codes: Vec<String> = Vec::new();
+        for word in text.split_whitespace() {
+            let Some(code) = PREFIXES
+                .iter()
+                .find_map(|prefix| word.find(prefix).map(|at| &word[at + prefix.len()..]))
+            else {
+                continue;
+            };
+            let code: String = code
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || *c == '-')
+                .collect();
+            if !code.is_empty() && !codes.contains(&code) {
+                codes.push(code);
+            }
+        }
+        codes
+    }
+}
@@ -0,0 +1,123 @@
+use super::User;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Membership screening form of a guild, answered before talking in it. SEE: <https://docs.discord.food/resources/guild#member-verification-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemberVerification {
+    /// Version of the form (ISO8601 timestamp), sent back with the answers
+    pub version: String,
+
+    /// Questions of the form
+    #[serde(default)]
+    pub form_fields: Vec<VerificationFormField>,
+
+    /// Description of the guild shown above the form
+    pub description: Option<String>,
+}
+
+/// A question of a membership screening form. SEE: <https://docs.discord.food/resources/guild#member-verification-form-field-structure>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerificationFormField {
+    /// Type of the field ("TERMS", "TEXT_INPUT", "PARAGRAPH", "MULTIPLE_CHOICE")
+    pub field_type: String,
+
+    /// Label of the field
+    pub label: String,
+
+    /// Rules to accept ("TERMS") or choices ("MULTIPLE_CHOICE")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub values: Option<Vec<String>>,
+
+    /// Whether the field must be answered
+    #[serde(default)]
+    pub required: bool,
+
+    /// Answer of the user (`true` for accepted rules, text, or the index of a choice)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<Value>,
+}
+
+impl MemberVerification {
+    /// Whether the only questions are rules to accept
+    pub fn is_rules_only(&self) -> bool {
+        self.form_fields
+            .iter()
+            .all(|field| field.field_type == "TERMS" || !field.required)
+    }
+
+    /// Accepts the rules of the form (answers the "TERMS" fields)
+    pub fn accept_rules(mut self) -> Self {
+        for field in &mut self.form_fields {
+            if field.field_type == "TERMS" {
+                field.response = Some(Value::Bool(true));
+            }
+        }
+        self
+    }
+}
+
+/// Status of a join request
+///
+/// Statuses added after this version are read as `Unknown`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum JoinRequestStatus {
+    /// The screening form is being filled in
+    Started,
+    /// Submitted, waiting for a moderator
+    Submitted,
+    /// Rejected by a moderator
+    Rejected,
+    /// Approved, the user is a member
+    Approved,
+    /// A status this version does not know
+    #[serde(other)]
+    Unknown,
+}
+
+/// A request of the current user to join a guild, with the answers to its screening form. SEE: <https://docs.discord.food/resources/guild#join-request-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JoinRequest {
+    /// ID of the join request
+    #[serde(alias = "join_request_id")]
+    pub id: String,
+
+    /// ID of the guild
+    pub guild_id: String,
+
+    /// ID of the user who requested to join
+    pub user_id: Option<String>,
+
+    /// User who requested to join
+    pub user: Option<User>,
+
+    /// Status of the request
+    pub application_status: JoinRequestStatus,
+
+    /// Answers to the screening form
+    #[serde(default)]
+    pub form_responses: Vec<VerificationFormField>,
+
+    /// Why the request was rejected
+    pub rejection_reason: Option<String>,
+
+    /// When the request was created, in ISO8601 format
+    pub created_at: Option<String>,
+}
+
+/// Outcome of `GuildsManager::join`
+#[derive(Debug, Clone)]
+pub struct GuildJoin {
+    /// ID of the joined guild
+    pub guild_id: String,
+
+    /// Whether the guild is only previewed (lurking)
+    pub lurking: bool,
+
+    /// Join request submitted to accept the rules of the guild
+    pub join_request: Option<JoinRequest>,
+
+    /// Screening form with questions left to answer with `GuildsManager::submit_join_request`
+    pub pending_verification: Option<MemberVerification>,
+}
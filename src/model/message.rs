@@ -1,9 +1,11 @@
 use super::{
-    channel::ChannelMention, interaction::InteractionType, poll::Poll, Channel, Embed, Interaction,
-    Reaction, User,
+    channel::ChannelMention, poll::Poll, timestamp::parse_timestamp, AllowedMentions, Button,
+    Channel, Component, ComponentType, CreatePoll, Embed, Interaction, InteractionType, Invite,
+    MessageId, MessageInteractionMetadata, Reaction, ReactionType, SelectMenu, Sticker, Timestamp,
+    User,
 };
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{json, Map, Value};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize_repr, Deserialize_repr)]
@@ -56,6 +58,9 @@ pub struct Message {
     /// ID of the channel the message was sent in
     pub channel_id: String,
 
+    /// ID of the guild the message was sent in (only sent in gateway events)
+    pub guild_id: Option<String>,
+
     /// Author of the message
     pub author: User,
 
@@ -127,10 +132,20 @@ pub struct Message {
     /// Messages Flags (bitfield)
     pub message_flags: Option<u64>,
 
-    /// Message reference data (if the attachment is a reply)
+    /// Message flags (bitfield), as sent by Discord
+    pub flags: Option<u64>,
+
+    /// Message reference data (if the message is a reply, a forward or a crosspost)
     pub message_reference: Option<MessageReference>,
 
-    /// Interaction metadata (if the attachment is from an interaction)
+    /// The message replied to (`None` if it was deleted or is not a reply)
+    pub referenced_message: Option<Box<Message>>,
+
+    /// Copies of the forwarded message (forwards only)
+    #[serde(default)]
+    pub message_snapshots: Vec<MessageSnapshot>,
+
+    /// Interaction metadata (if the message is a response to an interaction)
     pub interaction_metadata: Option<MessageInteractionMetadata>,
 
     /// Deprecated in favor of interaction_metadata; sent if the message is a response to an interaction
@@ -141,7 +156,7 @@ pub struct Message {
 
     /// Components (e.g., buttons, select menus) included in the message
     #[serde(default)]
-    pub components: Vec<serde_json::Value>,
+    pub components: Vec<Component>,
 
     /// Sticker items included in the message
     #[serde(default)]
@@ -200,6 +215,154 @@ pub struct Attachment {
     pub flags: Option<u64>,
 }
 
+impl Attachment {
+    /// Returns when the signed CDN URL expires (Unix time in seconds), read from its `ex` parameter
+    pub fn expires_at(&self) -> Option<u64> {
+        signature_expiry(&self.url)
+    }
+
+    /// Whether the signed CDN URL has expired and must be refreshed before downloading
+    pub fn is_expired(&self) -> bool {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0);
+        self.expires_at()
+            .is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// Fetches a freshly signed URL for this attachment. (`POST /attachments/refresh-urls`) SEE: <https://docs.discord.food/resources/message#refresh-attachment-urls>
+    pub async fn refresh_url(&self, http: &crate::http::HttpClient) -> crate::Result<String> {
+        let url = crate::http::api_url("/attachments/refresh-urls");
+        let response = http
+            .post(&url, json!({ "attachment_urls": [self.url] }))
+            .await?;
+        let refreshed: Vec<RefreshedUrl> =
+            serde_json::from_value(response["refreshed_urls"].clone())?;
+        Ok(refreshed
+            .into_iter()
+            .next()
+            .map(|url| url.refreshed)
+            .unwrap_or_else(|| self.url.clone()))
+    }
+
+    /// Downloads the whole attachment into memory
+    ///
+    /// Expired CDN signatures are refreshed automatically.
+    pub async fn download(&self, http: &crate::http::HttpClient) -> crate::Result<bytes::Bytes> {
+        let response = self.download_response(http).await?;
+        Ok(response.bytes().await?)
+    }
+
+    /// Streams the attachment chunk by chunk without buffering the whole file
+    ///
+    /// # Example
+    /// ```ignore
+    /// use futures::StreamExt;
+    ///
+    /// let mut chunks = Box::pin(attachment.download_stream(&ctx.http).await?);
+    /// while let Some(chunk) = chunks.next().await {
+    ///     println!("received {} bytes", chunk?.len());
+    /// }
+    /// ```
+    pub async fn download_stream(
+        &self,
+        http: &crate::http::HttpClient,
+    ) -> crate::Result<impl futures::Stream<Item = crate::Result<bytes::Bytes>> + Send + 'static>
+    {
+        let response = self.download_response(http).await?;
+        Ok(futures::stream::unfold(
+            Some(response),
+            |response| async move {
+                let mut response = response?;
+                match response.chunk().await {
+                    Ok(Some(chunk)) => Some((Ok(chunk), Some(response))),
+                    Ok(None) => None,
+                    Err(e) => Some((Err(e.into()), None)),
+                }
+            },
+        ))
+    }
+
+    /// Streams the attachment to a file and returns the number of bytes written
+    pub async fn download_to_file(
+        &self,
+        http: &crate::http::HttpClient,
+        path: impl AsRef<std::path::Path>,
+    ) -> crate::Result<u64> {
+        use tokio::io::AsyncWriteExt;
+
+        let mut response = self.download_response(http).await?;
+        let mut file = tokio::fs::File::create(path).await?;
+        let mut written = 0;
+        while let Some(chunk) = response.chunk().await? {
+            file.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+        }
+        file.flush().await?;
+        Ok(written)
+    }
+
+    async fn download_response(
+        &self,
+        http: &crate::http::HttpClient,
+    ) -> crate::Result<reqwest::Response> {
+        let refreshed = self.is_expired();
+        let url = if refreshed {
+            self.refresh_url(http).await?
+        } else {
+            self.url.clone()
+        };
+
+        let mut response = http.cdn_get(&url).await?;
+        // Signatures can expire between parsing `ex` and the request, or be revoked early
+        if !refreshed
+            && matches!(
+                response.status(),
+                reqwest::StatusCode::FORBIDDEN | reqwest::StatusCode::NOT_FOUND
+            )
+        {
+            response = http.cdn_get(self.refresh_url(http).await?).await?;
+        }
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(crate::error::Error::from_status(status.as_u16(), body));
+        }
+        Ok(response)
+    }
+}
+
+fn signature_expiry(url: &str) -> Option<u64> {
+    let url = url::Url::parse(url).ok()?;
+    let (_, expiry) = url.query_pairs().find(|(key, _)| key == "ex")?;
+    u64::from_str_radix(&expiry, 16).ok()
+}
+
+/// Result of refreshing a signed CDN URL
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefreshedUrl {
+    /// URL that was sent for refreshing
+    pub original: String,
+
+    /// Newly signed URL
+    pub refreshed: String,
+}
+
+/// MESSAGE_DELETE_BULK payload. SEE: <https://docs.discord.food/topics/gateway-events#message-delete-bulk>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageDeleteBulk {
+    /// IDs of the deleted messages
+    pub ids: Vec<String>,
+
+    /// ID of the channel
+    pub channel_id: String,
+
+    /// ID of the guild (if the channel is in a guild)
+    pub guild_id: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MessageActivity {
     #[serde(rename = "type")]
@@ -208,11 +371,45 @@ pub struct MessageActivity {
     pub party_id: Option<String>,
 }
 
+/// Type of a `MessageReference`
+///
+/// Types added after this version are kept as `Unknown`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "u8", into = "u8")]
+pub enum MessageReferenceType {
+    /// A reply (or a crosspost, pin or thread starter reference)
+    Default,
+    /// A forwarded message
+    Forward,
+    /// A type this version does not know
+    Unknown(u8),
+}
+
+impl From<u8> for MessageReferenceType {
+    fn from(kind: u8) -> Self {
+        match kind {
+            0 => MessageReferenceType::Default,
+            1 => MessageReferenceType::Forward,
+            other => MessageReferenceType::Unknown(other),
+        }
+    }
+}
+
+impl From<MessageReferenceType> for u8 {
+    fn from(kind: MessageReferenceType) -> Self {
+        match kind {
+            MessageReferenceType::Default => 0,
+            MessageReferenceType::Forward => 1,
+            MessageReferenceType::Unknown(other) => other,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MessageReference {
-    /// Type of the message reference (0 = default, 1 = forward)
+    /// Type of the message reference
     #[serde(rename = "type")]
-    pub kind: Option<u8>,
+    pub kind: Option<MessageReferenceType>,
 
     /// ID of the original message
     pub message_id: Option<String>,
@@ -228,56 +425,63 @@ pub struct MessageReference {
     pub fail_if_not_exists: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MessageInteractionMetadata {
-    /// ID of the interaction
-    pub id: String,
-
-    /// Type of the interaction (1 = ping, 2 = application command, 3 = message component, 4 = application command autocomplete)
-    pub kind: InteractionType,
-
-    /// User that triggered the interaction
-    pub user: User,
-
-    /// ID of the original response message, present only on follow-up messages
-    pub original_response_message_id: Option<String>,
-
-    /// The user the command was run on, present only on user command interactions
-    pub target_user: Option<User>,
+impl MessageReference {
+    /// Whether the reference is a forward
+    pub fn is_forward(&self) -> bool {
+        self.kind == Some(MessageReferenceType::Forward)
+    }
+}
 
-    /// The ID of the message the command was run on, present only on message command interactions
-    pub target_message_id: Option<String>,
+/// A copy of a forwarded message, taken when it was forwarded. SEE: <https://docs.discord.food/resources/message#message-snapshot-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageSnapshot {
+    /// The forwarded message
+    pub message: SnapshotMessage,
 }
 
+/// The fields of a forwarded message kept in a `MessageSnapshot`
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Sticker {
-    /// Unique ID of the sticker
-    pub id: String,
+pub struct SnapshotMessage {
+    /// Type of the message
+    #[serde(rename = "type")]
+    pub kind: MessageType,
+
+    /// Content of the message
+    #[serde(default)]
+    pub content: String,
 
-    /// For standard stickers, the pack ID of the sticker
-    pub pack_id: Option<String>,
+    /// Embeds in the message
+    #[serde(default)]
+    pub embeds: Vec<Embed>,
+
+    /// Attachments in the message
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
 
-    /// Name of the sticker
-    pub name: String,
+    /// Timestamp of when the message was sent
+    pub timestamp: String,
 
-    /// Description of the sticker
-    pub description: String,
+    /// Edit timestamp (if the message was edited)
+    pub edited_timestamp: Option<String>,
 
-    /// The type of sticker format (1 = PNG, 2 = APNG, 3 = Lottie)
-    pub format_type: u8,
+    /// Messages Flags (bitfield)
+    pub flags: Option<u64>,
 
-    /// Whether the sticker is available
+    /// Users mentioned in the message
     #[serde(default)]
-    pub available: bool,
+    pub mentions: Vec<User>,
 
-    /// The ID of the guild that owns this sticker, if it's a guild sticker
-    pub guild_id: Option<String>,
+    /// Roles mentioned in the message
+    #[serde(default)]
+    pub mention_roles: Vec<String>,
 
-    /// User that created this sticker
-    pub user: Option<User>,
+    /// Sticker items included in the message
+    #[serde(default)]
+    pub sticker_items: Vec<Sticker>,
 
-    /// The sort order of the sticker in the pack
-    pub sort_value: Option<u64>,
+    /// Components included in the message
+    #[serde(default)]
+    pub components: Vec<Component>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -289,6 +493,21 @@ pub struct SupplementalMessageRequest {
 }
 
 impl Message {
+    /// Returns the ID of the message as a typed snowflake
+    pub fn message_id(&self) -> Option<MessageId> {
+        MessageId::parse(&self.id)
+    }
+
+    /// When the message was sent
+    pub fn timestamp_parsed(&self) -> Option<Timestamp> {
+        Timestamp::parse(&self.timestamp)
+    }
+
+    /// When the message was last edited (if ever)
+    pub fn edited_timestamp_parsed(&self) -> Option<Timestamp> {
+        parse_timestamp(self.edited_timestamp.as_deref())
+    }
+
     /// Checks if the message starts with a prefix
     pub fn starts_with(&self, prefix: &str) -> bool {
         self.content.starts_with(prefix)
@@ -296,7 +515,75 @@ impl Message {
 
     /// Helper method to check if the message is a reply
     pub fn is_reply(&self) -> bool {
-        self.message_reference.is_some()
+        self.message_reference
+            .as_ref()
+            .is_some_and(|reference| !reference.is_forward())
+    }
+
+    /// Whether the message forwards another message
+    pub fn is_forward(&self) -> bool {
+        self.message_reference
+            .as_ref()
+            .is_some_and(MessageReference::is_forward)
+    }
+
+    /// Returns the forwarded message (forwards only)
+    pub fn forwarded_message(&self) -> Option<&SnapshotMessage> {
+        self.message_snapshots
+            .first()
+            .map(|snapshot| &snapshot.message)
+    }
+
+    /// User whose interaction produced this message (if it is an interaction response)
+    pub fn interaction_user(&self) -> Option<&User> {
+        self.interaction_metadata
+            .as_ref()
+            .map(|metadata| &metadata.user)
+            .or_else(|| self.interaction.as_ref()?.user.as_ref())
+    }
+
+    /// Name of the slash command that produced this message (if any)
+    pub fn command_name(&self) -> Option<&str> {
+        self.interaction_metadata
+            .as_ref()
+            .and_then(|metadata| metadata.command_name())
+            .or_else(|| self.interaction.as_ref()?.name.as_deref())
+    }
+
+    /// Returns the codes of the invite links in the message content
+    pub fn invite_codes(&self) -> Vec<String> {
+        Invite::codes_in(&self.content)
+    }
+
+    /// Resolves the invite links of the message, like the client does to render invite embeds
+    ///
+    /// Unknown or expired invites (404, JSON error code 10006) are skipped, other errors are
+    /// returned.
+    pub async fn resolve_invites(
+        &self,
+        http: &crate::http::HttpClient,
+    ) -> crate::Result<Vec<Invite>> {
+        let mut invites = Vec::new();
+        for code in self.invite_codes() {
+            match crate::client::InvitesManager.get(http, &code).await {
+                Ok(invite) => invites.push(invite),
+                Err(e) if e.status() == Some(404) || e.api_code() == Some(10006) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(invites)
+    }
+
+    /// Type of the interaction that produced this message (if any)
+    pub fn interaction_kind(&self) -> Option<InteractionType> {
+        self.interaction_metadata
+            .as_ref()
+            .map(|metadata| metadata.kind)
+            .or_else(|| {
+                self.interaction
+                    .as_ref()
+                    .map(|interaction| interaction.kind)
+            })
     }
 
     /// Parses message as command + arguments
@@ -308,6 +595,129 @@ impl Message {
         Some((command, args))
     }
 
+    /// Gets every button in the message, including nested ones
+    pub fn buttons(&self) -> Vec<Button> {
+        let mut buttons = Vec::new();
+        for component in &self.components {
+            component.walk(&mut |c| {
+                if let Component::Button(button) = c {
+                    buttons.push(button.as_ref().clone());
+                }
+            });
+        }
+        buttons
+    }
+
+    /// Gets every select menu in the message, including nested ones
+    pub fn select_menus(&self) -> Vec<SelectMenu> {
+        let mut menus = Vec::new();
+        for component in &self.components {
+            component.walk(&mut |c| {
+                if let Component::SelectMenu(menu) = c {
+                    menus.push(menu.clone());
+                }
+            });
+        }
+        menus
+    }
+
+    /// Finds a button by its custom ID
+    pub fn button(&self, custom_id: &str) -> Option<Button> {
+        self.buttons()
+            .into_iter()
+            .find(|button| button.custom_id.as_deref() == Some(custom_id))
+    }
+
+    /// Finds a select menu by its custom ID
+    pub fn select_menu(&self, custom_id: &str) -> Option<SelectMenu> {
+        self.select_menus()
+            .into_iter()
+            .find(|menu| menu.custom_id == custom_id)
+    }
+
+    /// Builds the MESSAGE_COMPONENT interaction payload for a component of this message
+    pub fn component_interaction(
+        &self,
+        session_id: &str,
+        nonce: &str,
+        data: serde_json::Value,
+    ) -> serde_json::Value {
+        let application_id = self
+            .application_id
+            .clone()
+            .unwrap_or_else(|| self.author.id.clone());
+        let mut body = json!({
+            "type": InteractionType::MessageComponent,
+            "nonce": nonce,
+            "channel_id": self.channel_id,
+            "message_flags": self.flags.or(self.message_flags).unwrap_or(0),
+            "message_id": self.id,
+            "application_id": application_id,
+            "session_id": session_id,
+            "data": data,
+        });
+        if let Some(guild_id) = &self.guild_id {
+            body["guild_id"] = json!(guild_id);
+        }
+        body
+    }
+
+    /// Clicks a button of the message. (`POST /interactions`)
+    ///
+    /// `session_id` must be the current gateway session (see `Context::session_id`).
+    /// Returns the nonce sent with the interaction.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let session_id = ctx.session_id().unwrap_or_default();
+    /// msg.click_button(&ctx.http, &session_id, "confirm").await?;
+    /// ```
+    pub async fn click_button(
+        &self,
+        http: &crate::http::HttpClient,
+        session_id: impl AsRef<str>,
+        custom_id: impl AsRef<str>,
+    ) -> crate::Result<String> {
+        let nonce = crate::http::generate_nonce();
+        let data = json!({
+            "component_type": ComponentType::Button,
+            "custom_id": custom_id.as_ref(),
+        });
+        let body = self.component_interaction(session_id.as_ref(), &nonce, data);
+        http.post(crate::http::api_url("/interactions"), body)
+            .await?;
+        Ok(nonce)
+    }
+
+    /// Picks values in a select menu of the message. (`POST /interactions`)
+    ///
+    /// `values` are option values for string selects, or IDs for user/role/channel selects.
+    /// Returns the nonce sent with the interaction.
+    pub async fn select_option(
+        &self,
+        http: &crate::http::HttpClient,
+        session_id: impl AsRef<str>,
+        custom_id: impl AsRef<str>,
+        values: Vec<String>,
+    ) -> crate::Result<String> {
+        let custom_id = custom_id.as_ref();
+        let component_type = self
+            .select_menu(custom_id)
+            .map(|menu| menu.kind)
+            .unwrap_or(ComponentType::StringSelect);
+        let nonce = crate::http::generate_nonce();
+        let data = json!({
+            "component_type": component_type,
+            "custom_id": custom_id,
+            "type": component_type,
+            "values": values,
+        });
+        let body = self.component_interaction(session_id.as_ref(), &nonce, data);
+        http.post(crate::http::api_url("/interactions"), body)
+            .await?;
+        Ok(nonce)
+    }
+
     /// Gets the channel this message was sent in
     ///
     /// # Example
@@ -343,39 +753,142 @@ impl Message {
         &self,
         http: &crate::http::HttpClient,
         content: impl Into<String>,
+    ) -> crate::Result<Message> {
+        self.reply_with(http, CreateMessage::new().content(content))
+            .await
+    }
+
+    /// Replies to the message with a full message (embeds, poll, flags, ...)
+    ///
+    /// # Example
+    /// ```ignore
+    /// let reply = CreateMessage::new().content("Results:").embed(embed).silent();
+    /// msg.reply_with(&ctx.http, reply).await?;
+    /// ```
+    pub async fn reply_with(
+        &self,
+        http: &crate::http::HttpClient,
+        message: CreateMessage,
     ) -> crate::Result<Message> {
         let url = crate::http::api_url(&format!("/channels/{}/messages", self.channel_id));
-        let body = json!({
-            "content": content.into(),
-            "message_reference": {
-                "message_id": self.id,
-                "channel_id": self.channel_id,
-                "fail_if_not_exists": false
-            }
-        });
+        let body = message.reference(self.reference(MessageReferenceType::Default));
         let response = http.post(&url, body).await?;
         let message: Message = serde_json::from_value(response)?;
         Ok(message)
     }
 
-    /// Edits the message
+    /// Edits the message, with either a new content or an `EditMessage`
+    ///
+    /// # Example
+    /// ```ignore
+    /// msg.edit(&ctx.http, "fixed typo").await?;
+    /// msg.edit(&ctx.http, EditMessage::new().suppress_embeds(true)).await?;
+    /// ```
     pub async fn edit(
         &self,
         http: &crate::http::HttpClient,
-        new_content: impl Into<String>,
+        edit: impl Into<EditMessage>,
     ) -> crate::Result<Message> {
         let url = crate::http::api_url(&format!(
             "/channels/{}/messages/{}",
             self.channel_id, self.id
         ));
-        let body = json!({
-            "content": new_content.into()
-        });
-        let response = http.patch(&url, body).await?;
+        let edit = edit.into().with_current_flags(self.flags);
+        let response = http.patch(&url, edit).await?;
+        let message: Message = serde_json::from_value(response)?;
+        Ok(message)
+    }
+
+    /// Publishes a message of an announcement channel to the following channels. (`POST /channels/{channel.id}/messages/{message.id}/crosspost`) SEE: <https://docs.discord.food/resources/message#crosspost-message>
+    pub async fn crosspost(&self, http: &crate::http::HttpClient) -> crate::Result<Message> {
+        let url = crate::http::api_url(&format!(
+            "/channels/{}/messages/{}/crosspost",
+            self.channel_id, self.id
+        ));
+        let response = http.post(&url, json!({})).await?;
+        let message: Message = serde_json::from_value(response)?;
+        Ok(message)
+    }
+
+    /// Pins the message. (`PUT /channels/{channel.id}/messages/pins/{message.id}`) SEE: <https://docs.discord.food/resources/message#pin-message>
+    pub async fn pin(&self, http: &crate::http::HttpClient) -> crate::Result<()> {
+        let url = crate::http::api_url(&format!(
+            "/channels/{}/messages/pins/{}",
+            self.channel_id, self.id
+        ));
+        http.put(&url, json!({})).await?;
+        Ok(())
+    }
+
+    /// Unpins the message. (`DELETE /channels/{channel.id}/messages/pins/{message.id}`) SEE: <https://docs.discord.food/resources/message#unpin-message>
+    pub async fn unpin(&self, http: &crate::http::HttpClient) -> crate::Result<()> {
+        let url = crate::http::api_url(&format!(
+            "/channels/{}/messages/pins/{}",
+            self.channel_id, self.id
+        ));
+        http.delete(&url).await?;
+        Ok(())
+    }
+
+    /// Forwards the message to another channel. SEE: <https://docs.discord.food/resources/message#message-reference-object>
+    ///
+    /// The forward keeps a snapshot of the message, readable with `forwarded_message`.
+    pub async fn forward_to(
+        &self,
+        http: &crate::http::HttpClient,
+        channel_id: impl AsRef<str>,
+    ) -> crate::Result<Message> {
+        let url = crate::http::api_url(&format!("/channels/{}/messages", channel_id.as_ref()));
+        let body = CreateMessage::new().reference(self.reference(MessageReferenceType::Forward));
+        let response = http.post(&url, body).await?;
         let message: Message = serde_json::from_value(response)?;
         Ok(message)
     }
 
+    /// Forwards the message to another channel
+    #[deprecated(note = "renamed to `forward_to`")]
+    pub async fn forward(
+        &self,
+        http: &crate::http::HttpClient,
+        channel_id: impl AsRef<str>,
+    ) -> crate::Result<Message> {
+        self.forward_to(http, channel_id).await
+    }
+
+    /// Waits for the next message replying to this one, sent by another user
+    ///
+    /// Shortcut for `Context::await_reply`.
+    pub async fn await_reply(
+        &self,
+        ctx: &crate::client::Context,
+        timeout: std::time::Duration,
+    ) -> Option<Message> {
+        ctx.await_reply(self, timeout).await
+    }
+
+    /// Waits for another user to react to this message with `emoji`
+    ///
+    /// Shortcut for `Context::await_reaction`.
+    pub async fn await_reaction(
+        &self,
+        ctx: &crate::client::Context,
+        emoji: impl Into<String>,
+        timeout: std::time::Duration,
+    ) -> Option<crate::client::ReactionCollectEvent> {
+        ctx.await_reaction(self, emoji, timeout).await
+    }
+
+    /// Builds a reference pointing to this message
+    fn reference(&self, kind: MessageReferenceType) -> MessageReference {
+        MessageReference {
+            kind: Some(kind),
+            message_id: Some(self.id.clone()),
+            channel_id: Some(self.channel_id.clone()),
+            guild_id: self.guild_id.clone(),
+            fail_if_not_exists: false,
+        }
+    }
+
     /// Deletes the message
     pub async fn delete(&self, http: &crate::http::HttpClient) -> crate::Result<()> {
         let url = crate::http::api_url(&format!(
@@ -445,6 +958,97 @@ impl Message {
         Ok(reactions)
     }
 
+    /// Adds a super reaction to the message, spending a burst credit
+    ///
+    /// The balance is checked with `PremiumManager::burst_credits`.
+    pub async fn react_burst(
+        &self,
+        http: &crate::http::HttpClient,
+        emoji: impl AsRef<str>,
+    ) -> crate::Result<()> {
+        let url = crate::http::api_url(&format!(
+            "/channels/{}/messages/{}/reactions/{}/@me?type={}",
+            self.channel_id,
+            self.id,
+            urlencoding::encode(emoji.as_ref()),
+            ReactionType::Burst as u8
+        ));
+        http.put(&url, json!({})).await?;
+        Ok(())
+    }
+
+    /// Fetches users who reacted with an emoji. (`GET /channels/{channel.id}/messages/{message.id}/reactions/{emoji}`) SEE: <https://docs.discord.food/resources/message#get-reactions>
+    /// # Params
+    /// - kind: ReactionType - Normal reactions or super reactions
+    /// - after?: Snowflake - Get users after this user ID
+    /// - limit?: number - Max number of users to return (1-100, default 25)
+    pub async fn reaction_users(
+        &self,
+        http: &crate::http::HttpClient,
+        emoji: impl AsRef<str>,
+        kind: ReactionType,
+        after: Option<&str>,
+        limit: Option<u8>,
+    ) -> crate::Result<Vec<User>> {
+        let mut url = self.reaction_users_url(emoji.as_ref(), kind);
+        if let Some(after) = after {
+            url.push_str(&format!("&after={after}"));
+        }
+        if let Some(limit) = limit {
+            url.push_str(&format!("&limit={limit}"));
+        }
+        let response = http.get(&url).await?;
+        let users: Vec<User> = serde_json::from_value(response)?;
+        Ok(users)
+    }
+
+    /// Streams every user who reacted with an emoji, fetching 100 users per request
+    ///
+    /// # Example
+    /// ```ignore
+    /// use futures::StreamExt;
+    ///
+    /// let mut users = Box::pin(msg.reaction_users_iter(&ctx.http, "👍", ReactionType::Normal));
+    /// while let Some(user) = users.next().await {
+    ///     println!("{}", user?.tag());
+    /// }
+    /// ```
+    pub fn reaction_users_iter(
+        &self,
+        http: &crate::http::HttpClient,
+        emoji: impl AsRef<str>,
+        kind: ReactionType,
+    ) -> impl futures::Stream<Item = crate::Result<User>> + Send + 'static {
+        crate::http::paginate(
+            http,
+            self.reaction_users_url(emoji.as_ref(), kind),
+            crate::http::Direction::After,
+            100,
+            None,
+            |user: &User| user.id.clone(),
+        )
+    }
+
+    fn reaction_users_url(&self, emoji: &str, kind: ReactionType) -> String {
+        crate::http::api_url(&format!(
+            "/channels/{}/messages/{}/reactions/{}?type={}",
+            self.channel_id,
+            self.id,
+            urlencoding::encode(emoji),
+            kind as u8
+        ))
+    }
+
+    /// Marks the channel as read up to this message
+    pub async fn ack(&self, http: &crate::http::HttpClient) -> crate::Result<()> {
+        let url = crate::http::api_url(&format!(
+            "/channels/{}/messages/{}/ack",
+            self.channel_id, self.id
+        ));
+        http.post(&url, json!({ "token": null })).await?;
+        Ok(())
+    }
+
     /// Deletes all reactions on a message.
     pub async fn clear_reactions(&self, http: &crate::http::HttpClient) -> crate::Result<()> {
         let url = crate::http::api_url(&format!(
@@ -455,3 +1059,261 @@ impl Message {
         Ok(())
     }
 }
+
+/// Builder for a message sent with `Message::reply_with` or `Channel::send_message`. SEE: <https://docs.discord.food/resources/message#create-message>
+///
+/// Fields not covered by a setter can be added with `field`.
+///
+/// # Example
+/// ```ignore
+/// let message = CreateMessage::new().content("Poll time").poll(CreatePoll::new("Pizza?").answer("Yes"));
+/// channel.send_message(&ctx.http, message).await?;
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CreateMessage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    embeds: Vec<Embed>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    tts: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allowed_mentions: Option<AllowedMentions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message_reference: Option<MessageReference>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    poll: Option<CreatePoll>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    sticker_ids: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    flags: Option<u64>,
+    #[serde(flatten)]
+    extra: Map<String, Value>,
+}
+
+impl CreateMessage {
+    /// Creates an empty message
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the text content (max 2000 characters, 4000 with Nitro)
+    pub fn content(mut self, content: impl Into<String>) -> Self {
+        self.content = Some(content.into());
+        self
+    }
+
+    /// Adds an embed (max 10)
+    pub fn embed(mut self, embed: Embed) -> Self {
+        self.embeds.push(embed);
+        self
+    }
+
+    /// Replaces every embed
+    pub fn embeds(mut self, embeds: Vec<Embed>) -> Self {
+        self.embeds = embeds;
+        self
+    }
+
+    /// Sets whether the message is read out with text-to-speech
+    pub fn tts(mut self, tts: bool) -> Self {
+        self.tts = tts;
+        self
+    }
+
+    /// Sets which mentions notify, overriding the client default policy
+    pub fn allowed_mentions(mut self, allowed_mentions: AllowedMentions) -> Self {
+        self.allowed_mentions = Some(allowed_mentions);
+        self
+    }
+
+    /// Sets the message reference (reply or forward)
+    pub fn reference(mut self, reference: MessageReference) -> Self {
+        self.message_reference = Some(reference);
+        self
+    }
+
+    /// Attaches a poll
+    pub fn poll(mut self, poll: CreatePoll) -> Self {
+        self.poll = Some(poll);
+        self
+    }
+
+    /// Adds a sticker by ID (max 3). Guild stickers can only be sent in their guild,
+    /// unless the user has Nitro
+    pub fn sticker(mut self, sticker_id: impl Into<String>) -> Self {
+        self.sticker_ids.push(sticker_id.into());
+        self
+    }
+
+    /// Replaces every sticker
+    pub fn stickers<I>(mut self, sticker_ids: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.sticker_ids = sticker_ids.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the message flags bitfield
+    pub fn flags(mut self, flags: u64) -> Self {
+        self.flags = Some(flags);
+        self
+    }
+
+    /// Hides the link embeds of the message (`SUPPRESS_EMBEDS` flag)
+    pub fn suppress_embeds(self) -> Self {
+        self.add_flag(MESSAGE_FLAG_SUPPRESS_EMBEDS)
+    }
+
+    /// Sends the message without push or desktop notifications (`SUPPRESS_NOTIFICATIONS` flag)
+    pub fn silent(self) -> Self {
+        self.add_flag(MESSAGE_FLAG_SUPPRESS_NOTIFICATIONS)
+    }
+
+    /// Attaches a file uploaded beforehand to the attachment storage
+    ///
+    /// `uploaded_filename` is the name returned by `POST /channels/{channel.id}/attachments`
+    /// once the file was uploaded to its upload URL. SEE: <https://docs.discord.food/resources/message#create-attachments>
+    pub fn uploaded_attachment(
+        mut self,
+        filename: impl Into<String>,
+        uploaded_filename: impl Into<String>,
+    ) -> Self {
+        let attachments = self
+            .extra
+            .entry("attachments")
+            .or_insert_with(|| Value::Array(Vec::new()));
+        if let Value::Array(attachments) = attachments {
+            attachments.push(serde_json::json!({
+                "id": attachments.len().to_string(),
+                "filename": filename.into(),
+                "uploaded_filename": uploaded_filename.into(),
+            }));
+        }
+        self
+    }
+
+    /// Sets a raw JSON field not covered by the builder
+    pub fn field(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.extra.insert(key.into(), value.into());
+        self
+    }
+
+    fn add_flag(mut self, flag: u64) -> Self {
+        self.flags = Some(self.flags.unwrap_or(0) | flag);
+        self
+    }
+}
+
+impl From<String> for CreateMessage {
+    fn from(content: String) -> Self {
+        Self::new().content(content)
+    }
+}
+
+impl From<&str> for CreateMessage {
+    fn from(content: &str) -> Self {
+        Self::new().content(content)
+    }
+}
+
+/// Builder for a message edited with `Message::edit`. SEE: <https://docs.discord.food/resources/message#edit-message>
+///
+/// Only the fields that were set are sent. Fields not covered by a setter can be added with `field`.
+///
+/// # Example
+/// ```ignore
+/// msg.edit(&ctx.http, EditMessage::new().content("edited").embeds(Vec::new())).await?;
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EditMessage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    embeds: Option<Vec<Embed>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    flags: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allowed_mentions: Option<AllowedMentions>,
+    #[serde(flatten)]
+    extra: Map<String, Value>,
+    /// `SUPPRESS_EMBEDS` change, applied to the flags of the edited message
+    #[serde(skip)]
+    suppress_embeds: Option<bool>,
+}
+
+impl EditMessage {
+    /// Creates an empty edit
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the text content
+    pub fn content(mut self, content: impl Into<String>) -> Self {
+        self.content = Some(content.into());
+        self
+    }
+
+    /// Replaces every embed (an empty list removes them)
+    pub fn embeds(mut self, embeds: Vec<Embed>) -> Self {
+        self.embeds = Some(embeds);
+        self
+    }
+
+    /// Sets the message flags bitfield
+    pub fn flags(mut self, flags: u64) -> Self {
+        self.flags = Some(flags);
+        self
+    }
+
+    /// Sets whether the link embeds of the message are hidden
+    ///
+    /// Only the `SUPPRESS_EMBEDS` bit changes, the other flags are taken from `flags`
+    /// or from the edited message.
+    pub fn suppress_embeds(mut self, suppress: bool) -> Self {
+        self.suppress_embeds = Some(suppress);
+        self
+    }
+
+    /// Applies the `SUPPRESS_EMBEDS` change on top of the current flags of the message
+    fn with_current_flags(mut self, current: Option<u64>) -> Self {
+        if let Some(suppress) = self.suppress_embeds.take() {
+            let flags = self.flags.or(current).unwrap_or(0);
+            self.flags = Some(if suppress {
+                flags | MESSAGE_FLAG_SUPPRESS_EMBEDS
+            } else {
+                flags & !MESSAGE_FLAG_SUPPRESS_EMBEDS
+            });
+        }
+        self
+    }
+
+    /// Sets which mentions notify, overriding the client default policy
+    pub fn allowed_mentions(mut self, allowed_mentions: AllowedMentions) -> Self {
+        self.allowed_mentions = Some(allowed_mentions);
+        self
+    }
+
+    /// Sets a raw JSON field not covered by the builder
+    pub fn field(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.extra.insert(key.into(), value.into());
+        self
+    }
+}
+
+impl From<String> for EditMessage {
+    fn from(content: String) -> Self {
+        Self::new().content(content)
+    }
+}
+
+impl From<&str> for EditMessage {
+    fn from(content: &str) -> Self {
+        Self::new().content(content)
+    }
+}
+
+const MESSAGE_FLAG_SUPPRESS_EMBEDS: u64 = 1 << 2;
+const MESSAGE_FLAG_SUPPRESS_NOTIFICATIONS: u64 = 1 << 12;
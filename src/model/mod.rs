@@ -1,33 +1,122 @@
+mod allowed_mentions;
+mod application_command;
+mod audit_log;
+mod call;
 mod channel;
+mod component;
+mod connection;
 mod embed;
+mod embedded_activity;
 mod guild;
+mod guild_folder;
+mod guild_subscription;
+mod id;
 mod gateway_state;
+mod integration;
 mod interaction;
+mod invite;
+mod join_request;
 mod message;
+mod oauth2;
 mod permissions;
+mod pin;
 mod poll;
+mod premium;
+mod presence;
 mod reaction;
 mod relationship;
 mod role;
+mod search;
+mod session;
+mod sticker;
+mod timestamp;
+#[cfg(feature = "settings-proto")]
+pub mod settings_proto;
+mod typing;
 mod user;
+mod voice;
 
-pub use channel::{Channel, ChannelMention, ChannelType, ForumTag, ThreadMember};
+pub use allowed_mentions::{AllowedMentions, MentionType};
+pub use application_command::{
+    ApplicationCommand, ApplicationCommandIndex, ApplicationCommandIndexApplication,
+    ApplicationCommandInteractionOption, ApplicationCommandOption, ApplicationCommandOptionChoice,
+    ApplicationCommandOptionType, ApplicationCommandType,
+};
+pub use audit_log::{
+    AuditLog, AuditLogActionType, AuditLogChange, AuditLogEntry, AuditLogEntryInfo,
+};
+pub use call::{Call, CallDelete};
+pub use channel::{
+    Channel, ChannelMention, ChannelRecipientEvent, ChannelType, CreateChannel, CreateForumPost,
+    EditChannel, ForumTag, ThreadDelete, ThreadListSync, ThreadListing, ThreadMember,
+    ThreadMembersUpdate,
+};
+pub use component::{
+    ActionRow, Button, ButtonStyle, Component, ComponentType, Modal, SelectMenu, SelectOption,
+    TextInput, TextInputStyle,
+};
+pub use connection::{Connection, ConnectionType};
 pub use embed::{
     Embed, EmbedAuthor, EmbedField, EmbedFooter, EmbedImage, EmbedProvider, EmbedThumbnail,
     EmbedVideo,
 };
-pub use guild::{Ban, Guild, Member, SupplementalMember};
+pub use embedded_activity::{EmbeddedActivityConfig, EmbeddedApplication};
+pub use guild::{
+    Ban, BulkBanResponse, Guild, GuildBanEvent, GuildDiscoveryMetadata, GuildFeatures,
+    GuildMemberAdd, GuildMemberRemove, GuildMemberUpdate, GuildPreview, GuildProfile, Member,
+    PartialMember, SupplementalMember,
+};
 pub use gateway_state::{
-    MergedMember, PassiveChannelState, PassiveUpdateV1, ReadStateContainer, ReadStateEntry,
-    ReadySupplemental,
+    MergedMember, MergedPresence, MergedPresences, PassiveChannelState, PassiveUpdateV1,
+    ReadStateContainer, ReadStateEntry, ReadyPayload, ReadySession, ReadySessionClientInfo,
+    ReadySupplemental, SupplementalGuildState,
+};
+pub use guild_folder::GuildFolder;
+pub use guild_subscription::GuildSubscription;
+pub use id::{
+    ApplicationId, ApplicationMarker, ChannelId, ChannelMarker, EmojiId, EmojiMarker,
+    GenericMarker, GuildId, GuildMarker, Id, MessageId, MessageMarker, RoleId, RoleMarker,
+    Snowflake, UserId, UserMarker, DISCORD_EPOCH_MS,
+};
+pub use integration::{Integration, IntegrationAccount, IntegrationApplication};
+pub use interaction::{Interaction, InteractionType, MessageInteractionMetadata};
+pub use invite::{Invite, InviteChannel, InviteGuild, InviteMetadata, InviteType};
+pub use join_request::{
+    GuildJoin, JoinRequest, JoinRequestStatus, MemberVerification, VerificationFormField,
 };
-pub use interaction::Interaction;
 pub use message::{
-    Attachment, Message, MessageActivity, MessageType, Sticker, SupplementalMessageRequest,
+    Attachment, CreateMessage, EditMessage, Message, MessageActivity, MessageDeleteBulk,
+    MessageReference, MessageReferenceType, MessageSnapshot, MessageType, RefreshedUrl,
+    SnapshotMessage, SupplementalMessageRequest,
 };
+pub use oauth2::{AuthorizeGuild, AuthorizeInfo, AuthorizeRequest, OAuthApplication};
 pub use permissions::{PermissionOverwrite, PermissionOverwriteType, Permissions};
-pub use poll::Poll;
-pub use reaction::{Emoji, Reaction};
-pub use relationship::{Relationship, RelationshipType};
-pub use role::{Role, RoleColors, RoleTags};
-pub use user::{Avatar, ClientStatus, Nameplate, Presence, User, UserProfile};
+pub use pin::{ChannelPins, ChannelPinsUpdate, MessagePin};
+pub use poll::{CreatePoll, Poll, PollAnswer, PollAnswerCount, PollMedia, PollResults};
+pub use premium::{
+    BoostSlot, BurstCredits, Entitlement, GiftCode, GuildBoost, PremiumUsage, Subscription,
+    SubscriptionItem, SubscriptionPlan, SubscriptionStatus,
+};
+pub use presence::{
+    Activity, ActivityAssets, ActivityEmoji, ActivityParty, ActivityTimestamps, ActivityType,
+    ClientStatus, CreateActivity, EditPresence, OnlineStatus, Presence, PresenceUpdateEvent,
+    PresenceUser, SpotifyTrack,
+};
+pub use reaction::{
+    Emoji, MessageReactionAdd, MessageReactionRemove, MessageReactionRemoveAll,
+    MessageReactionRemoveEmoji, Reaction, ReactionCountDetails, ReactionType,
+};
+pub use relationship::{
+    FriendSuggestion, FriendSuggestionReason, Relationship, RelationshipAction, RelationshipType,
+};
+pub use role::{CreateRole, EditRole, Role, RoleColors, RoleTags};
+pub use search::{SearchHas, SearchResults};
+pub use session::{Session, SessionClientInfo};
+pub use sticker::{Sticker, StickerFormatType, StickerPack, StickerType};
+pub use timestamp::Timestamp;
+pub use typing::TypingStart;
+pub use user::{
+    Avatar, EditProfile, GuildAffinity, GuildTag, Nameplate, PrimaryGuild, User, UserAffinity,
+    UserProfile,
+};
+pub use voice::{EditVoiceState, VoiceState};
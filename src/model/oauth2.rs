@@ -0,0 +1,214 @@
+use super::{Permissions, User};
+use serde::{Deserialize, Serialize};
+
+/// Parameters of an OAuth2 authorization, as found in an authorize URL. SEE: <https://docs.discord.food/topics/oauth2#authorization-url>
+///
+/// # Example
+/// ```
+/// use diself::model::{AuthorizeRequest, Permissions};
+///
+/// let request = AuthorizeRequest::bot("1234", Permissions::ADMINISTRATOR).guild_id("5678");
+/// assert_eq!(
+///     request.query(),
+///     "client_id=1234&scope=bot%20applications.commands&permissions=8"
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AuthorizeRequest {
+    client_id: String,
+    scopes: Vec<String>,
+    permissions: Option<Permissions>,
+    guild_id: Option<String>,
+    channel_id: Option<String>,
+    response_type: Option<String>,
+    redirect_uri: Option<String>,
+    state: Option<String>,
+    integration_type: Option<u8>,
+}
+
+impl AuthorizeRequest {
+    /// Creates an authorization for an application, without scopes
+    pub fn new(client_id: impl Into<String>) -> Self {
+        Self {
+            client_id: client_id.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Creates the authorization adding a bot (with its commands) to a guild
+    pub fn bot(client_id: impl Into<String>, permissions: Permissions) -> Self {
+        Self::new(client_id)
+            .scope("bot")
+            .scope("applications.commands")
+            .permissions(permissions)
+    }
+
+    /// Adds a scope (e.g. `"identify"`, `"guilds.join"`, `"bot"`)
+    pub fn scope(mut self, scope: impl Into<String>) -> Self {
+        self.scopes.push(scope.into());
+        self
+    }
+
+    /// Sets the permissions requested for the bot
+    pub fn permissions(mut self, permissions: Permissions) -> Self {
+        self.permissions = Some(permissions);
+        self
+    }
+
+    /// Sets the guild the bot is added to (or the webhook created in)
+    pub fn guild_id(mut self, guild_id: impl Into<String>) -> Self {
+        self.guild_id = Some(guild_id.into());
+        self
+    }
+
+    /// Sets the channel of the webhook created with the `webhook.incoming` scope
+    pub fn channel_id(mut self, channel_id: impl Into<String>) -> Self {
+        self.channel_id = Some(channel_id.into());
+        self
+    }
+
+    /// Sets the response type (`"code"` or `"token"`)
+    pub fn response_type(mut self, response_type: impl Into<String>) -> Self {
+        self.response_type = Some(response_type.into());
+        self
+    }
+
+    /// Sets the URI redirected to after the authorization
+    pub fn redirect_uri(mut self, redirect_uri: impl Into<String>) -> Self {
+        self.redirect_uri = Some(redirect_uri.into());
+        self
+    }
+
+    /// Sets the state passed back to the redirect URI
+    pub fn state(mut self, state: impl Into<String>) -> Self {
+        self.state = Some(state.into());
+        self
+    }
+
+    /// Sets where the application is installed (0 = guild, 1 = user)
+    pub fn integration_type(mut self, integration_type: u8) -> Self {
+        self.integration_type = Some(integration_type);
+        self
+    }
+
+    /// Returns the query string of the authorize URL (the guild is sent in the body)
+    pub fn query(&self) -> String {
+        let mut query = vec![format!(
+            "client_id={}",
+            urlencoding::encode(&self.client_id)
+        )];
+        if !self.scopes.is_empty() {
+            query.push(format!(
+                "scope={}",
+                urlencoding::encode(&self.scopes.join(" "))
+            ));
+        }
+        if let Some(permissions) = self.permissions {
+            query.push(format!("permissions={}", permissions.bits()));
+        }
+        let optional = [
+            ("response_type", &self.response_type),
+            ("redirect_uri", &self.redirect_uri),
+            ("state", &self.state),
+        ];
+        for (key, value) in optional {
+            if let Some(value) = value {
+                query.push(format!("{}={}", key, urlencoding::encode(value)));
+            }
+        }
+        if let Some(integration_type) = self.integration_type {
+            query.push(format!("integration_type={}", integration_type));
+        }
+        query.join("&")
+    }
+
+    /// Body of the authorization submitted by `OAuthManager::authorize`
+    pub(crate) fn body(&self) -> serde_json::Value {
+        let mut body = serde_json::json!({ "authorize": true });
+        if let Some(permissions) = self.permissions {
+            body["permissions"] = permissions.bits().to_string().into();
+        }
+        if let Some(guild_id) = &self.guild_id {
+            body["guild_id"] = guild_id.clone().into();
+        }
+        if let Some(channel_id) = &self.channel_id {
+            body["webhook_channel_id"] = channel_id.clone().into();
+        }
+        if let Some(integration_type) = self.integration_type {
+            body["integration_type"] = integration_type.into();
+        }
+        body
+    }
+}
+
+/// What the authorize page shows before the user accepts. SEE: <https://docs.discord.food/topics/oauth2#get-current-authorization-information>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorizeInfo {
+    /// Application asking for the authorization
+    pub application: OAuthApplication,
+
+    /// Bot user of the application (`bot` scope only)
+    pub bot: Option<User>,
+
+    /// Current user
+    pub user: Option<User>,
+
+    /// Guilds the current user can add the bot to (`bot` scope only)
+    #[serde(default)]
+    pub guilds: Vec<AuthorizeGuild>,
+
+    /// Whether the current user already authorized the application with these scopes
+    #[serde(default)]
+    pub authorized: bool,
+
+    /// Redirect URI of the authorization
+    pub redirect_uri: Option<String>,
+}
+
+/// The partial application of an authorization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthApplication {
+    /// ID of the application
+    pub id: String,
+
+    /// Name of the application
+    pub name: String,
+
+    /// Icon hash of the application
+    pub icon: Option<String>,
+
+    /// Description of the application
+    #[serde(default)]
+    pub description: String,
+
+    /// Whether anyone can add the bot of the application
+    #[serde(default)]
+    pub bot_public: bool,
+
+    /// Whether adding the bot requires the full OAuth2 code grant
+    #[serde(default)]
+    pub bot_require_code_grant: bool,
+}
+
+/// A guild the current user can add a bot to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorizeGuild {
+    /// Guild ID
+    pub id: String,
+
+    /// Guild name
+    pub name: String,
+
+    /// Icon hash
+    pub icon: Option<String>,
+
+    /// Permissions of the current user in the guild
+    pub permissions: Option<Permissions>,
+}
+
+impl AuthorizeInfo {
+    /// Returns a guild the bot can be added to by ID
+    pub fn guild(&self, guild_id: &str) -> Option<&AuthorizeGuild> {
+        self.guilds.iter().find(|guild| guild.id == guild_id)
+    }
+}
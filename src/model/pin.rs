@@ -0,0 +1,44 @@
+use super::{Message, Timestamp};
+use serde::{Deserialize, Serialize};
+
+/// A pinned message entry. SEE: <https://docs.discord.food/resources/message#message-pin-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessagePin {
+    /// When the message was pinned (ISO8601 timestamp)
+    pub pinned_at: String,
+
+    /// The pinned message
+    pub message: Message,
+}
+
+impl MessagePin {
+    /// When the message was pinned
+    pub fn pinned_at_parsed(&self) -> Option<Timestamp> {
+        Timestamp::parse(&self.pinned_at)
+    }
+}
+
+/// A page of pinned messages returned by `GET /channels/{channel.id}/messages/pins`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelPins {
+    /// Pinned messages, most recently pinned first
+    #[serde(default)]
+    pub items: Vec<MessagePin>,
+
+    /// Whether more pins can be fetched with the `before` cursor
+    #[serde(default)]
+    pub has_more: bool,
+}
+
+/// CHANNEL_PINS_UPDATE payload. SEE: <https://docs.discord.food/topics/gateway-events#channel-pins-update>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelPinsUpdate {
+    /// ID of the guild (if the channel is in a guild)
+    pub guild_id: Option<String>,
+
+    /// ID of the channel
+    pub channel_id: String,
+
+    /// When the most recent pinned message was pinned (ISO8601 timestamp)
+    pub last_pin_timestamp: Option<String>,
+}
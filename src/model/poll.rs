@@ -1,44 +1,120 @@
 use super::reaction::Emoji;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
+/// A poll attached to a message. SEE: <https://docs.discord.food/resources/poll>
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Poll {
-    ///The question of the poll.
-    pub question: String,
+    /// The question of the poll (only text is supported)
+    pub question: PollMedia,
 
-    /// Answers options for the poll.
-    pub answers: Vec<PollAnswerOption>,
+    /// Answers options for the poll
+    #[serde(default)]
+    pub answers: Vec<PollAnswer>,
 
-    /// The time when the poll ends, in ISO8601 format.
-    pub expiry: String,
+    /// The time when the poll ends, in ISO8601 format (null for non-expiring polls)
+    pub expiry: Option<String>,
 
-    /// Whether the poll allow mutiple answers or not.
+    /// Whether the poll allow mutiple answers or not
     #[serde(default)]
     pub allow_multiselect: bool,
 
-    /// Layout type (only 0 for default layout currently)
+    /// Layout type (only 1 for default layout currently)
+    #[serde(default)]
     pub layout_type: u8,
 
-    /// The results of the poll
-    #[serde(default)]
-    pub results: Vec<PollResult>,
+    /// The results of the poll (may be absent while votes are not counted yet)
+    pub results: Option<PollResults>,
+}
+
+impl Poll {
+    /// Returns the ID of the first answer matching this text
+    pub fn answer_id(&self, text: &str) -> Option<u32> {
+        self.answers
+            .iter()
+            .find(|answer| answer.poll_media.text.as_deref() == Some(text))
+            .and_then(|answer| answer.answer_id)
+    }
+
+    /// Returns the number of votes for an answer (0 if results are not available)
+    pub fn votes(&self, answer_id: u32) -> u64 {
+        self.results
+            .as_ref()
+            .and_then(|results| {
+                results
+                    .answer_counts
+                    .iter()
+                    .find(|count| count.id == answer_id)
+            })
+            .map(|count| count.count)
+            .unwrap_or(0)
+    }
+
+    /// Returns the IDs of the answers the current user voted for
+    pub fn my_votes(&self) -> Vec<u32> {
+        self.results
+            .iter()
+            .flat_map(|results| &results.answer_counts)
+            .filter(|count| count.me_voted)
+            .map(|count| count.id)
+            .collect()
+    }
+
+    /// Whether the votes have been precisely counted (the poll has ended)
+    pub fn is_finalized(&self) -> bool {
+        self.results
+            .as_ref()
+            .is_some_and(|results| results.is_finalized)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PollAnswerOption {
-    /// The answer unique ID
-    pub answer_id: String,
+pub struct PollAnswer {
+    /// The answer unique ID (not sent when creating a poll)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub answer_id: Option<u32>,
+
     /// Poll Media Object
-    pub media: Option<PollMedia>,
+    pub poll_media: PollMedia,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PollMedia {
-    emoji: Option<Emoji>,
+    /// Text of the question or answer (max 300 characters for questions, 55 for answers)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+
+    /// Emoji of the answer
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_partial_emoji"
+    )]
+    pub emoji: Option<Emoji>,
+}
+
+/// Fields of an emoji accepted when creating a poll
+#[derive(Serialize)]
+struct PartialEmoji<'a> {
+    id: &'a Option<String>,
+    name: &'a Option<String>,
+    animated: bool,
+}
+
+fn serialize_partial_emoji<S: Serializer>(
+    emoji: &Option<Emoji>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    emoji
+        .as_ref()
+        .map(|emoji| PartialEmoji {
+            id: &emoji.id,
+            name: &emoji.name,
+            animated: emoji.animated,
+        })
+        .serialize(serializer)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PollResult {
+pub struct PollResults {
     /// Whether the votes have been precisely counted
     #[serde(default)]
     pub is_finalized: bool,
@@ -51,7 +127,7 @@ pub struct PollResult {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PollAnswerCount {
     /// The answer unique ID
-    pub id: String,
+    pub id: u32,
 
     /// The number of votes for this answer
     pub count: u64,
@@ -60,3 +136,81 @@ pub struct PollAnswerCount {
     #[serde(default)]
     pub me_voted: bool,
 }
+
+/// Builder for a poll sent with `Channel::send_poll`. SEE: <https://docs.discord.food/resources/poll#poll-create-request-object>
+#[derive(Debug, Clone, Serialize)]
+pub struct CreatePoll {
+    question: PollMedia,
+    answers: Vec<PollAnswer>,
+    duration: u32,
+    allow_multiselect: bool,
+    layout_type: u8,
+}
+
+impl CreatePoll {
+    /// Creates a poll lasting 24 hours with no answers yet
+    pub fn new(question: impl Into<String>) -> Self {
+        Self {
+            question: PollMedia {
+                text: Some(question.into()),
+                emoji: None,
+            },
+            answers: Vec::new(),
+            duration: 24,
+            allow_multiselect: false,
+            layout_type: 1,
+        }
+    }
+
+    /// Adds a text answer (max 10 answers)
+    pub fn answer(mut self, text: impl Into<String>) -> Self {
+        self.answers.push(PollAnswer {
+            answer_id: None,
+            poll_media: PollMedia {
+                text: Some(text.into()),
+                emoji: None,
+            },
+        });
+        self
+    }
+
+    /// Adds an answer with an emoji (a unicode emoji, or the ID of a custom emoji)
+    pub fn answer_with_emoji(mut self, text: impl Into<String>, emoji: impl Into<String>) -> Self {
+        let emoji = emoji.into();
+        let is_custom = !emoji.is_empty() && emoji.bytes().all(|b| b.is_ascii_digit());
+        let (id, name) = if is_custom {
+            (Some(emoji), None)
+        } else {
+            (None, Some(emoji))
+        };
+        self.answers.push(PollAnswer {
+            answer_id: None,
+            poll_media: PollMedia {
+                text: Some(text.into()),
+                emoji: Some(Emoji {
+                    id,
+                    name,
+                    roles: Vec::new(),
+                    user: None,
+                    require_colons: false,
+                    managed: false,
+                    animated: false,
+                    available: true,
+                }),
+            },
+        });
+        self
+    }
+
+    /// Sets how long the poll lasts, in hours (max 768)
+    pub fn duration(mut self, hours: u32) -> Self {
+        self.duration = hours;
+        self
+    }
+
+    /// Allows voting for several answers
+    pub fn allow_multiselect(mut self, allow: bool) -> Self {
+        self.allow_multiselect = allow;
+        self
+    }
+}
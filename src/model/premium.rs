@@ -0,0 +1,391 @@
+use super::timestamp::parse_timestamp;
+use super::{Timestamp, User};
+use serde::{Deserialize, Serialize};
+
+/// Status of a subscription. SEE: <https://docs.discord.food/resources/subscription#subscription-status>
+///
+/// Statuses added after this version are kept as `Unknown`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "u8", into = "u8")]
+pub enum SubscriptionStatus {
+    /// Created, waiting for the first payment
+    Unpaid,
+    /// Paid and running
+    Active,
+    /// A renewal payment failed, perks are kept while it is retried
+    PastDue,
+    /// Canceled, perks are kept until the end of the current period
+    Canceled,
+    /// Ended, perks are gone
+    Ended,
+    /// Inactive, e.g. replaced by another subscription
+    Inactive,
+    /// Suspended after failed payments
+    AccountHold,
+    /// A status this version does not know
+    Unknown(u8),
+}
+
+impl From<u8> for SubscriptionStatus {
+    fn from(status: u8) -> Self {
+        match status {
+            0 => SubscriptionStatus::Unpaid,
+            1 => SubscriptionStatus::Active,
+            2 => SubscriptionStatus::PastDue,
+            3 => SubscriptionStatus::Canceled,
+            4 => SubscriptionStatus::Ended,
+            5 => SubscriptionStatus::Inactive,
+            6 => SubscriptionStatus::AccountHold,
+            other => SubscriptionStatus::Unknown(other),
+        }
+    }
+}
+
+impl From<SubscriptionStatus> for u8 {
+    fn from(status: SubscriptionStatus) -> Self {
+        match status {
+            SubscriptionStatus::Unpaid => 0,
+            SubscriptionStatus::Active => 1,
+            SubscriptionStatus::PastDue => 2,
+            SubscriptionStatus::Canceled => 3,
+            SubscriptionStatus::Ended => 4,
+            SubscriptionStatus::Inactive => 5,
+            SubscriptionStatus::AccountHold => 6,
+            SubscriptionStatus::Unknown(other) => other,
+        }
+    }
+}
+
+/// A premium (Nitro, boost, application) subscription of the current user. SEE: <https://docs.discord.food/resources/subscription#subscription-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Subscription {
+    /// Subscription ID
+    pub id: String,
+
+    /// Type of the subscription (1 = premium, 2 = guild, 3 = application)
+    #[serde(rename = "type")]
+    pub kind: u8,
+
+    /// Status of the subscription
+    pub status: SubscriptionStatus,
+
+    /// Plans and quantities of the subscription
+    #[serde(default)]
+    pub items: Vec<SubscriptionItem>,
+
+    /// ID of the payment source used to renew the subscription
+    pub payment_source_id: Option<String>,
+
+    /// Currency of the subscription (e.g. "usd")
+    pub currency: Option<String>,
+
+    /// When the current billing period started, in ISO8601 format
+    pub current_period_start: Option<String>,
+
+    /// When the current billing period ends, in ISO8601 format
+    pub current_period_end: Option<String>,
+
+    /// When the subscription was canceled, in ISO8601 format
+    pub canceled_at: Option<String>,
+
+    /// When the trial of the subscription ends, in ISO8601 format
+    pub trial_ends_at: Option<String>,
+}
+
+/// A plan of a subscription
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscriptionItem {
+    /// Item ID
+    pub id: String,
+
+    /// ID of the subscribed plan
+    pub plan_id: String,
+
+    /// Quantity of the plan (e.g. number of boosts)
+    #[serde(default = "default_quantity")]
+    pub quantity: u32,
+}
+
+fn default_quantity() -> u32 {
+    1
+}
+
+impl Subscription {
+    /// Whether the subscription currently grants its perks
+    pub fn is_active(&self) -> bool {
+        matches!(
+            self.status,
+            SubscriptionStatus::Active | SubscriptionStatus::PastDue | SubscriptionStatus::Canceled
+        )
+    }
+
+    /// When the current billing period ends (and a canceled subscription expires)
+    pub fn current_period_end_parsed(&self) -> Option<Timestamp> {
+        parse_timestamp(self.current_period_end.as_deref())
+    }
+}
+
+/// Something the current user owns: Nitro from a gift, a premium item, or an unredeemed gift
+/// they bought. SEE: <https://docs.discord.food/resources/entitlement#entitlement-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entitlement {
+    /// Entitlement ID
+    pub id: String,
+
+    /// Type of the entitlement (1 = purchase, 3 = user gift, 8 = application subscription, ...)
+    #[serde(rename = "type")]
+    pub kind: u8,
+
+    /// ID of the SKU granted
+    pub sku_id: String,
+
+    /// ID of the application the SKU belongs to
+    pub application_id: String,
+
+    /// ID of the user granted
+    pub user_id: Option<String>,
+
+    /// ID of the guild granted
+    pub guild_id: Option<String>,
+
+    /// Whether the entitlement was deleted
+    #[serde(default)]
+    pub deleted: bool,
+
+    /// Whether a consumable entitlement was used
+    #[serde(default)]
+    pub consumed: bool,
+
+    /// When the entitlement starts being valid, in ISO8601 format
+    pub starts_at: Option<String>,
+
+    /// When the entitlement stops being valid, in ISO8601 format
+    pub ends_at: Option<String>,
+
+    /// ID of the subscription plan, for subscription gifts
+    pub subscription_plan_id: Option<String>,
+
+    /// Flags of the gift code, for gifts
+    pub gift_code_flags: Option<u64>,
+}
+
+/// A boost the current user can put on a guild. SEE: <https://docs.discord.food/resources/premium-referral#premium-guild-subscription-slot-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoostSlot {
+    /// Slot ID
+    pub id: String,
+
+    /// ID of the subscription granting the slot
+    pub subscription_id: String,
+
+    /// Boost currently using the slot
+    pub premium_guild_subscription: Option<GuildBoost>,
+
+    /// Whether the slot will be removed at the end of the billing period
+    #[serde(default)]
+    pub canceled: bool,
+
+    /// When the slot can be moved to another guild, in ISO8601 format
+    pub cooldown_ends_at: Option<String>,
+}
+
+impl BoostSlot {
+    /// Whether the slot can boost a guild now (unused and out of cooldown)
+    pub fn is_available(&self) -> bool {
+        let in_cooldown = parse_timestamp(self.cooldown_ends_at.as_deref())
+            .is_some_and(|ends_at| !ends_at.is_past());
+        self.premium_guild_subscription.is_none() && !in_cooldown
+    }
+}
+
+/// A boost applied to a guild. SEE: <https://docs.discord.food/resources/guild#premium-guild-subscription-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuildBoost {
+    /// Boost ID
+    pub id: String,
+
+    /// ID of the boosted guild
+    pub guild_id: String,
+
+    /// ID of the user boosting the guild
+    pub user_id: String,
+
+    /// Whether the boost has ended
+    #[serde(default)]
+    pub ended: bool,
+
+    /// When the boost ends, in ISO8601 format
+    pub ends_at: Option<String>,
+}
+
+/// Super reaction credits of the current user. SEE: <https://docs.discord.food/resources/user#get-user-burst-credits>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BurstCredits {
+    /// Credits left
+    #[serde(default)]
+    pub amount: u32,
+
+    /// Whether the credits were already replenished today
+    #[serde(default)]
+    pub replenished_today: bool,
+
+    /// When the credits are replenished next, in ISO8601 format
+    pub next_replenish_at: Option<String>,
+}
+
+impl BurstCredits {
+    /// Whether a super reaction can be added now
+    pub fn has_credits(&self) -> bool {
+        self.amount > 0
+    }
+
+    /// When the credits are replenished next
+    pub fn next_replenish(&self) -> Option<Timestamp> {
+        parse_timestamp(self.next_replenish_at.as_deref())
+    }
+}
+
+/// Usage of the Nitro perks by the current user. SEE: <https://docs.discord.food/resources/user#premium-usage-object>
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PremiumUsage {
+    /// Stickers sent outside their guild
+    #[serde(default, with = "usage_value")]
+    pub nitro_sticker_sends: u64,
+
+    /// Animated emojis sent
+    #[serde(default, with = "usage_value")]
+    pub total_animated_emojis: u64,
+
+    /// Emojis sent outside their guild
+    #[serde(default, with = "usage_value")]
+    pub total_global_emojis: u64,
+
+    /// Files uploaded above the free size limit
+    #[serde(default, with = "usage_value")]
+    pub total_large_uploads: u64,
+
+    /// Streams in HD
+    #[serde(default, with = "usage_value")]
+    pub total_hd_streams: u64,
+
+    /// Hours streamed in HD
+    #[serde(default, with = "usage_value")]
+    pub hd_hours_streamed: u64,
+}
+
+/// Premium usage counters are sent as `{ "value": n }`
+mod usage_value {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct Usage {
+        value: u64,
+    }
+
+    pub fn serialize<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        Usage { value: *value }.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        Ok(Usage::deserialize(deserializer)?.value)
+    }
+}
+
+/// A gift code (Nitro, game or other SKU) and its redemption state. SEE: <https://docs.discord.food/resources/entitlement#gift-code-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GiftCode {
+    /// The gift code
+    pub code: String,
+
+    /// ID of the SKU the gift grants
+    pub sku_id: String,
+
+    /// ID of the application of the SKU
+    pub application_id: Option<String>,
+
+    /// Number of times the gift was redeemed
+    #[serde(default)]
+    pub uses: u32,
+
+    /// Max number of times the gift can be redeemed
+    #[serde(default)]
+    pub max_uses: u32,
+
+    /// Whether the current user already redeemed the gift
+    #[serde(default)]
+    pub redeemed: bool,
+
+    /// When the gift code expires, in ISO8601 format (null for never)
+    pub expires_at: Option<String>,
+
+    /// ID of the subscription plan the gift grants
+    pub subscription_plan_id: Option<String>,
+
+    /// Subscription plan the gift grants (resolved gifts only)
+    pub subscription_plan: Option<SubscriptionPlan>,
+
+    /// User who created the gift
+    pub user: Option<User>,
+
+    /// Flags of the gift code
+    pub flags: Option<u64>,
+}
+
+/// A purchasable subscription plan (e.g. "Nitro Monthly"). SEE: <https://docs.discord.food/resources/subscription#subscription-plan-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscriptionPlan {
+    /// Plan ID
+    pub id: String,
+
+    /// Name of the plan
+    pub name: String,
+
+    /// Billing interval (1 = month, 2 = year, 3 = day)
+    pub interval: Option<u8>,
+
+    /// Number of intervals between two billings
+    pub interval_count: Option<u32>,
+
+    /// ID of the SKU of the plan
+    pub sku_id: Option<String>,
+}
+
+impl GiftCode {
+    /// Returns the shareable URL of the gift
+    pub fn url(&self) -> String {
+        format!("https://discord.gift/{}", self.code)
+    }
+
+    /// Whether the gift can still be redeemed (not used up nor expired)
+    pub fn is_redeemable(&self) -> bool {
+        let used_up = self.max_uses > 0 && self.uses >= self.max_uses;
+        let expired = parse_timestamp(self.expires_at.as_deref()).is_some_and(|at| at.is_past());
+        !used_up && !expired
+    }
+
+    /// Extracts the gift codes of `discord.gift` and `discord.com/gifts` links in a text
+    pub fn codes_in(text: &str) -> Vec<String> {
+        const PREFIXES: [&str; 3] = [
+            "discord.gift/",
+            "discord.com/gifts/",
+            "discordapp.com/gifts/",
+        ];
+        let mut codes: Vec<String> = Vec::new();
+        for word in text.split_whitespace() {
+            let Some(code) = PREFIXES
+                .iter()
+                .find_map(|prefix| word.find(prefix).map(|at| &word[at + prefix.len()..]))
+            else {
+                continue;
+            };
+            let code: String = code
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric())
+                .collect();
+            if !code.is_empty() && !codes.contains(&code) {
+                codes.push(code);
+            }
+        }
+        codes
+    }
+}
@@ -0,0 +1,679 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{json, Map, Value};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+/// Presence of a user. SEE: <https://docs.discord.food/resources/presence#presence-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Presence {
+    /// Online status (`online`, `idle`, `dnd`, `offline`, ...)
+    pub status: String,
+
+    /// Current activities (entries that fail to parse are skipped)
+    #[serde(default, deserialize_with = "deserialize_activities")]
+    pub activities: Vec<Activity>,
+
+    /// Client platform statuses (`desktop`, `mobile`, `web`) when provided.
+    pub client_status: Option<ClientStatus>,
+
+    /// Unix time (ms) when user went idle, if any.
+    pub since: Option<i64>,
+
+    /// AFK flag from gateway presence payload.
+    pub afk: Option<bool>,
+}
+
+impl Presence {
+    /// Returns the typed online status
+    pub fn online_status(&self) -> OnlineStatus {
+        OnlineStatus::from(self.status.as_str())
+    }
+
+    /// Returns the custom status activity, if set
+    pub fn custom_status(&self) -> Option<&Activity> {
+        self.activities
+            .iter()
+            .find(|activity| activity.kind == ActivityType::Custom)
+    }
+
+    /// Returns the track the user is listening to on Spotify, if any
+    pub fn spotify(&self) -> Option<SpotifyTrack> {
+        self.activities.iter().find_map(Activity::spotify)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientStatus {
+    pub desktop: Option<String>,
+    pub mobile: Option<String>,
+    pub web: Option<String>,
+}
+
+/// Online status of a user
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OnlineStatus {
+    Online,
+    Idle,
+    DoNotDisturb,
+    Invisible,
+    Offline,
+    Unknown,
+}
+
+impl OnlineStatus {
+    /// Returns the status as sent by the gateway (`online`, `idle`, `dnd`, ...)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OnlineStatus::Online => "online",
+            OnlineStatus::Idle => "idle",
+            OnlineStatus::DoNotDisturb => "dnd",
+            OnlineStatus::Invisible => "invisible",
+            OnlineStatus::Offline => "offline",
+            OnlineStatus::Unknown => "unknown",
+        }
+    }
+}
+
+impl From<&str> for OnlineStatus {
+    fn from(status: &str) -> Self {
+        match status {
+            "online" => OnlineStatus::Online,
+            "idle" => OnlineStatus::Idle,
+            "dnd" => OnlineStatus::DoNotDisturb,
+            "invisible" => OnlineStatus::Invisible,
+            "offline" => OnlineStatus::Offline,
+            _ => OnlineStatus::Unknown,
+        }
+    }
+}
+
+/// Type of an activity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum ActivityType {
+    Playing = 0,
+    Streaming = 1,
+    Listening = 2,
+    Watching = 3,
+    Custom = 4,
+    Competing = 5,
+    Hang = 6,
+}
+
+/// An activity of a user's presence. SEE: <https://docs.discord.food/resources/presence#activity-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Activity {
+    /// Name of the activity (e.g. "Spotify")
+    pub name: String,
+
+    /// Type of the activity
+    #[serde(rename = "type")]
+    pub kind: ActivityType,
+
+    /// Stream URL (for streaming activities)
+    pub url: Option<String>,
+
+    /// Unix time (ms) when the activity was added to the session
+    pub created_at: Option<u64>,
+
+    /// ID of the session the activity belongs to
+    pub session_id: Option<String>,
+
+    /// Start and end of the activity
+    pub timestamps: Option<ActivityTimestamps>,
+
+    /// ID of the application of the activity
+    pub application_id: Option<String>,
+
+    /// What the user is currently doing (song title for Spotify)
+    pub details: Option<String>,
+
+    /// Current party status (artists for Spotify, text for custom statuses)
+    pub state: Option<String>,
+
+    /// Emoji of a custom status
+    pub emoji: Option<ActivityEmoji>,
+
+    /// Party of the activity
+    pub party: Option<ActivityParty>,
+
+    /// Images of the activity
+    pub assets: Option<ActivityAssets>,
+
+    /// ID of the synced track (Spotify track ID)
+    pub sync_id: Option<String>,
+
+    /// Activity flags
+    pub flags: Option<u64>,
+
+    /// Labels of the activity buttons
+    #[serde(default)]
+    pub buttons: Vec<Value>,
+}
+
+impl Activity {
+    /// Whether this is a Spotify listening activity
+    pub fn is_spotify(&self) -> bool {
+        self.kind == ActivityType::Listening
+            && self
+                .party
+                .as_ref()
+                .and_then(|party| party.id.as_deref())
+                .is_some_and(|id| id.starts_with("spotify:"))
+    }
+
+    /// Returns the Spotify track details of this activity, if it is a Spotify activity
+    pub fn spotify(&self) -> Option<SpotifyTrack> {
+        if !self.is_spotify() {
+            return None;
+        }
+        let assets = self.assets.as_ref();
+        Some(SpotifyTrack {
+            track_id: self.sync_id.clone()?,
+            title: self.details.clone().unwrap_or_default(),
+            artists: self
+                .state
+                .as_deref()
+                .map(|artists| artists.split("; ").map(ToOwned::to_owned).collect())
+                .unwrap_or_default(),
+            album: assets.and_then(|assets| assets.large_text.clone()),
+            album_art_url: assets
+                .and_then(|assets| assets.large_image.as_deref())
+                .and_then(|image| image.strip_prefix("spotify:"))
+                .map(|image| format!("https://i.scdn.co/image/{image}")),
+            start: self.timestamps.as_ref().and_then(|t| t.start),
+            end: self.timestamps.as_ref().and_then(|t| t.end),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ActivityTimestamps {
+    /// Unix time (ms) when the activity started
+    #[serde(
+        default,
+        deserialize_with = "deserialize_timestamp",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub start: Option<u64>,
+
+    /// Unix time (ms) when the activity ends
+    #[serde(
+        default,
+        deserialize_with = "deserialize_timestamp",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub end: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityEmoji {
+    /// Name of the emoji (the unicode character for standard emojis)
+    pub name: String,
+
+    /// ID of the emoji (custom emojis only)
+    pub id: Option<String>,
+
+    /// Whether the emoji is animated
+    #[serde(default)]
+    pub animated: bool,
+}
+
+/// Parses an emoji in the format of `Message::react` (`"🦀"`, `"name:id"`) or a custom
+/// emoji mention (`"<:name:id>"`, `"<a:name:id>"`)
+impl From<&str> for ActivityEmoji {
+    fn from(emoji: &str) -> Self {
+        let (emoji, animated) = match emoji.strip_prefix('<').and_then(|e| e.strip_suffix('>')) {
+            Some(mention) => match mention.strip_prefix("a:") {
+                Some(emoji) => (emoji, true),
+                None => (mention.strip_prefix(':').unwrap_or(mention), false),
+            },
+            None => (emoji, false),
+        };
+        match emoji.split_once(':') {
+            Some((name, id)) => Self {
+                name: name.to_string(),
+                id: Some(id.to_string()),
+                animated,
+            },
+            None => Self {
+                name: emoji.to_string(),
+                id: None,
+                animated: false,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ActivityParty {
+    /// ID of the party
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// Current and maximum size of the party
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<[u32; 2]>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ActivityAssets {
+    /// Large image key or URL (`spotify:{image_id}` for Spotify album art)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub large_image: Option<String>,
+
+    /// Text shown when hovering the large image (album name for Spotify)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub large_text: Option<String>,
+
+    /// Small image key or URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub small_image: Option<String>,
+
+    /// Text shown when hovering the small image
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub small_text: Option<String>,
+}
+
+/// Spotify track extracted from a listening activity
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpotifyTrack {
+    /// Spotify track ID
+    pub track_id: String,
+
+    /// Title of the track
+    pub title: String,
+
+    /// Artists of the track
+    pub artists: Vec<String>,
+
+    /// Name of the album
+    pub album: Option<String>,
+
+    /// URL of the album art
+    pub album_art_url: Option<String>,
+
+    /// Unix time (ms) when the track started
+    pub start: Option<u64>,
+
+    /// Unix time (ms) when the track ends
+    pub end: Option<u64>,
+}
+
+impl SpotifyTrack {
+    /// URL of the track on Spotify
+    pub fn url(&self) -> String {
+        format!("https://open.spotify.com/track/{}", self.track_id)
+    }
+}
+
+/// Activity flags of a synced Spotify activity (`SYNC | PLAY`)
+const SPOTIFY_ACTIVITY_FLAGS: u64 = (1 << 4) | (1 << 5);
+
+/// Builder for an activity shown in the presence of the current user. SEE: <https://docs.discord.food/resources/presence#activity-object>
+///
+/// Image keys are asset names of the activity application, or `mp:` media proxy paths.
+///
+/// # Example
+/// ```
+/// use diself::model::{ActivityType, CreateActivity};
+///
+/// let activity = CreateActivity::playing("Chess")
+///     .application_id("1234")
+///     .details("Ranked match")
+///     .state("Winning")
+///     .started_at(1_700_000_000_000)
+///     .large_image("board")
+///     .large_text("Board")
+///     .party("match-1", Some([1, 2]))
+///     .button("Spectate", "https://example.com/spectate");
+///
+/// let payload = serde_json::to_value(&activity).unwrap();
+/// assert_eq!(payload["type"], ActivityType::Playing as u8);
+/// assert_eq!(payload["assets"]["large_text"], "Board");
+/// assert_eq!(payload["metadata"]["button_urls"][0], "https://example.com/spectate");
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateActivity {
+    name: String,
+    #[serde(rename = "type")]
+    kind: ActivityType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    application_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    emoji: Option<ActivityEmoji>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timestamps: Option<ActivityTimestamps>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    assets: Option<ActivityAssets>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    party: Option<ActivityParty>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sync_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    flags: Option<u64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    buttons: Vec<String>,
+    #[serde(flatten)]
+    extra: Map<String, Value>,
+}
+
+impl CreateActivity {
+    pub fn new(kind: ActivityType, name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+            url: None,
+            application_id: None,
+            details: None,
+            state: None,
+            emoji: None,
+            timestamps: None,
+            assets: None,
+            party: None,
+            sync_id: None,
+            flags: None,
+            buttons: Vec::new(),
+            extra: Map::new(),
+        }
+    }
+
+    /// "Playing {name}"
+    pub fn playing(name: impl Into<String>) -> Self {
+        Self::new(ActivityType::Playing, name)
+    }
+
+    /// "Streaming {name}", with a Twitch or YouTube URL
+    pub fn streaming(name: impl Into<String>, url: impl Into<String>) -> Self {
+        Self::new(ActivityType::Streaming, name).url(url)
+    }
+
+    /// "Listening to {name}"
+    pub fn listening(name: impl Into<String>) -> Self {
+        Self::new(ActivityType::Listening, name)
+    }
+
+    /// "Watching {name}"
+    pub fn watching(name: impl Into<String>) -> Self {
+        Self::new(ActivityType::Watching, name)
+    }
+
+    /// "Competing in {name}"
+    pub fn competing(name: impl Into<String>) -> Self {
+        Self::new(ActivityType::Competing, name)
+    }
+
+    /// Custom status with a text (see `Context::set_custom_status` to also save it)
+    pub fn custom(text: impl Into<String>) -> Self {
+        Self::new(ActivityType::Custom, "Custom Status").state(text)
+    }
+
+    /// Spotify "Listening to" activity of a track, as sent by the Spotify integration
+    ///
+    /// `user_id` is the ID of the current user, used for the listening party. Album art
+    /// is only shown for `i.scdn.co` image URLs.
+    pub fn spotify(user_id: impl AsRef<str>, track: &SpotifyTrack) -> Self {
+        let mut activity = Self::listening("Spotify")
+            .details(track.title.clone())
+            .state(track.artists.join("; "))
+            .party(format!("spotify:{}", user_id.as_ref()), None)
+            .sync_id(track.track_id.clone())
+            .flags(SPOTIFY_ACTIVITY_FLAGS);
+        if let Some(image) = track
+            .album_art_url
+            .as_deref()
+            .and_then(|url| url.strip_prefix("https://i.scdn.co/image/"))
+        {
+            activity = activity.large_image(format!("spotify:{image}"));
+        }
+        if let Some(album) = &track.album {
+            activity = activity.large_text(album.clone());
+        }
+        if let Some(start) = track.start {
+            activity = activity.started_at(start);
+        }
+        if let Some(end) = track.end {
+            activity = activity.ends_at(end);
+        }
+        activity
+    }
+
+    /// Sets the stream URL (streaming activities only)
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Sets the application whose assets are used for the images
+    pub fn application_id(mut self, application_id: impl Into<String>) -> Self {
+        self.application_id = Some(application_id.into());
+        self
+    }
+
+    /// Sets what the user is doing (first line)
+    pub fn details(mut self, details: impl Into<String>) -> Self {
+        self.details = Some(details.into());
+        self
+    }
+
+    /// Sets the party status (second line), or the text of a custom status
+    pub fn state(mut self, state: impl Into<String>) -> Self {
+        self.state = Some(state.into());
+        self
+    }
+
+    /// Sets the emoji of a custom status (`"🦀"` or `"name:id"`)
+    pub fn emoji(mut self, emoji: impl AsRef<str>) -> Self {
+        self.emoji = Some(ActivityEmoji::from(emoji.as_ref()));
+        self
+    }
+
+    /// Sets when the activity started (Unix time in ms), shown as elapsed time
+    pub fn started_at(mut self, unix_ms: u64) -> Self {
+        self.timestamps.get_or_insert_with(Default::default).start = Some(unix_ms);
+        self
+    }
+
+    /// Sets when the activity ends (Unix time in ms), shown as remaining time
+    pub fn ends_at(mut self, unix_ms: u64) -> Self {
+        self.timestamps.get_or_insert_with(Default::default).end = Some(unix_ms);
+        self
+    }
+
+    /// Sets the large image
+    pub fn large_image(mut self, image: impl Into<String>) -> Self {
+        self.assets.get_or_insert_with(Default::default).large_image = Some(image.into());
+        self
+    }
+
+    /// Sets the text shown when hovering the large image
+    pub fn large_text(mut self, text: impl Into<String>) -> Self {
+        self.assets.get_or_insert_with(Default::default).large_text = Some(text.into());
+        self
+    }
+
+    /// Sets the small image
+    pub fn small_image(mut self, image: impl Into<String>) -> Self {
+        self.assets.get_or_insert_with(Default::default).small_image = Some(image.into());
+        self
+    }
+
+    /// Sets the text shown when hovering the small image
+    pub fn small_text(mut self, text: impl Into<String>) -> Self {
+        self.assets.get_or_insert_with(Default::default).small_text = Some(text.into());
+        self
+    }
+
+    /// Sets the party ID and its current and maximum size
+    pub fn party(mut self, id: impl Into<String>, size: Option<[u32; 2]>) -> Self {
+        self.party = Some(ActivityParty {
+            id: Some(id.into()),
+            size,
+        });
+        self
+    }
+
+    /// Adds a link button (max 2)
+    pub fn button(mut self, label: impl Into<String>, url: impl Into<String>) -> Self {
+        self.buttons.push(label.into());
+        let metadata = self
+            .extra
+            .entry("metadata")
+            .or_insert_with(|| json!({ "button_urls": [] }));
+        if let Some(urls) = metadata
+            .get_mut("button_urls")
+            .and_then(Value::as_array_mut)
+        {
+            urls.push(Value::String(url.into()));
+        }
+        self
+    }
+
+    /// Sets the ID of the synced item (Spotify track ID)
+    pub fn sync_id(mut self, sync_id: impl Into<String>) -> Self {
+        self.sync_id = Some(sync_id.into());
+        self
+    }
+
+    /// Sets the activity flags
+    pub fn flags(mut self, flags: u64) -> Self {
+        self.flags = Some(flags);
+        self
+    }
+
+    /// Sets an arbitrary field of the activity
+    pub fn field(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.extra.insert(key.into(), value.into());
+        self
+    }
+}
+
+/// Builder for the presence of the current user, sent with gateway op 3. SEE: <https://docs.discord.food/topics/gateway-events#update-presence>
+///
+/// The presence is kept across reconnects.
+///
+/// # Example
+/// ```ignore
+/// use diself::model::{CreateActivity, EditPresence, OnlineStatus};
+///
+/// ctx.set_presence(
+///     EditPresence::new()
+///         .status(OnlineStatus::DoNotDisturb)
+///         .activity(CreateActivity::watching("the logs")),
+/// )?;
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditPresence {
+    status: String,
+    since: Option<u64>,
+    activities: Vec<CreateActivity>,
+    afk: bool,
+}
+
+impl Default for EditPresence {
+    fn default() -> Self {
+        Self {
+            status: OnlineStatus::Online.as_str().to_string(),
+            since: None,
+            activities: Vec::new(),
+            afk: false,
+        }
+    }
+}
+
+impl EditPresence {
+    /// Online presence without activity
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the online status
+    pub fn status(mut self, status: OnlineStatus) -> Self {
+        self.status = status.as_str().to_string();
+        self
+    }
+
+    /// Adds an activity
+    pub fn activity(mut self, activity: CreateActivity) -> Self {
+        self.activities.push(activity);
+        self
+    }
+
+    /// Replaces the custom status activity, keeping the other activities (`None` removes it)
+    pub fn custom_status(mut self, custom_status: Option<CreateActivity>) -> Self {
+        self.activities
+            .retain(|activity| activity.kind != ActivityType::Custom);
+        self.activities.extend(custom_status);
+        self
+    }
+
+    /// Sets since when (Unix time in ms) the user is idle
+    pub fn since(mut self, unix_ms: u64) -> Self {
+        self.since = Some(unix_ms);
+        self
+    }
+
+    /// Sets whether the user is AFK (push notifications go to mobile)
+    pub fn afk(mut self, afk: bool) -> Self {
+        self.afk = afk;
+        self
+    }
+}
+
+/// PRESENCE_UPDATE payload. SEE: <https://docs.discord.food/topics/gateway-events#presence-update>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresenceUpdateEvent {
+    /// The user whose presence changed (only `id` is guaranteed)
+    pub user: PresenceUser,
+
+    /// ID of the guild the presence was received for (absent for friends)
+    pub guild_id: Option<String>,
+
+    /// The new presence
+    #[serde(flatten)]
+    pub presence: Presence,
+}
+
+/// Partial user sent with presence updates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresenceUser {
+    /// User ID
+    pub id: String,
+
+    /// Username (only sent when it changed)
+    pub username: Option<String>,
+
+    /// Display name (only sent when it changed)
+    pub global_name: Option<String>,
+
+    /// Avatar hash (only sent when it changed)
+    pub avatar: Option<String>,
+}
+
+fn deserialize_activities<'de, D>(deserializer: D) -> Result<Vec<Activity>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let values = Option::<Vec<Value>>::deserialize(deserializer)?.unwrap_or_default();
+    Ok(values
+        .into_iter()
+        .filter_map(|value| serde_json::from_value(value).ok())
+        .collect())
+}
+
+/// Timestamps are numbers in most payloads but strings in some user account payloads
+fn deserialize_timestamp<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match Option::<Value>::deserialize(deserializer)? {
+        Some(Value::Number(number)) => number.as_u64(),
+        Some(Value::String(string)) => string.parse().ok(),
+        _ => None,
+    })
+}
@@ -1,17 +1,52 @@
-use super::User;
+use super::{Guild, Member, User};
+use crate::cdn;
 use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+/// Kind of a reaction. SEE: <https://docs.discord.food/resources/message#reaction-type>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum ReactionType {
+    #[default]
+    Normal = 0,
+    /// Super reaction (animated, costs burst credits)
+    Burst = 1,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Reaction {
-    /// Count of this reaction
+    /// Total count of this reaction (normal and super reactions)
     pub count: u64,
 
+    /// Breakdown of the count by reaction type
+    #[serde(default)]
+    pub count_details: ReactionCountDetails,
+
     /// Whether the current user has reacted with this emoji
     #[serde(default)]
     pub me: bool,
 
+    /// Whether the current user has super-reacted with this emoji
+    #[serde(default)]
+    pub me_burst: bool,
+
     /// The emoji itself
     pub emoji: Emoji,
+
+    /// HEX colors used for the super reaction animation
+    #[serde(default)]
+    pub burst_colors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReactionCountDetails {
+    /// Count of super reactions
+    #[serde(default)]
+    pub burst: u64,
+
+    /// Count of normal reactions
+    #[serde(default)]
+    pub normal: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,3 +80,191 @@ pub struct Emoji {
     #[serde(default)]
     pub available: bool,
 }
+
+impl Emoji {
+    /// Returns the URL of the image of a custom emoji (`None` for unicode emojis)
+    pub fn url(&self) -> Option<String> {
+        self.id
+            .as_ref()
+            .map(|id| cdn::emoji(id, self.animated).into())
+    }
+
+    /// Whether the emoji matches a reaction string
+    ///
+    /// Accepts the formats of `Message::react` (`"👍"`, `"name:id"`) and custom
+    /// emoji mentions (`"<:name:id>"`, `"<a:name:id>"`).
+    pub fn matches(&self, emoji: &str) -> bool {
+        let emoji = emoji.trim_start_matches('<').trim_end_matches('>');
+        match emoji.rsplit_once(':') {
+            Some((_, id)) if self.id.is_some() => self.id.as_deref() == Some(id),
+            Some(_) => false,
+            None => self.id.is_none() && self.name.as_deref() == Some(emoji),
+        }
+    }
+
+    /// Returns the text showing the emoji in a message (`"<:name:id>"`, `"<a:name:id>"`
+    /// or the unicode emoji itself)
+    pub fn mention(&self) -> String {
+        let name = self.name.as_deref().unwrap_or("_");
+        match &self.id {
+            Some(id) if self.animated => format!("<a:{}:{}>", name, id),
+            Some(id) => format!("<:{}:{}>", name, id),
+            None => name.to_string(),
+        }
+    }
+
+    /// Whether this emoji can be sent, `in_own_guild` being whether the message is sent in
+    /// the guild owning it and `member_roles` the roles of the sender in that guild
+    ///
+    /// Unicode emojis are always usable. Custom emojis must be available, and emojis
+    /// restricted to roles (e.g. managed by an integration) require one of them. Outside
+    /// their guild (other guilds, DMs) and when animated, they also require Nitro.
+    ///
+    /// `available` and `roles` are only sent with full emojis (guild emoji lists, the
+    /// cache), so check those rather than the partial emojis of reactions or messages.
+    pub fn is_usable(&self, in_own_guild: bool, has_nitro: bool, member_roles: &[String]) -> bool {
+        if self.id.is_none() {
+            return true;
+        }
+        if !self.available {
+            return false;
+        }
+        if !self.roles.is_empty() && !self.roles.iter().any(|role| member_roles.contains(role)) {
+            return false;
+        }
+        has_nitro || (in_own_guild && !self.animated)
+    }
+
+    /// Whether `user` can send this emoji in a channel of `guild` (`None` for DMs)
+    ///
+    /// See `is_usable`. The full emoji is read from the emojis of `guild` when it belongs
+    /// to it, `member_roles` being the roles of `user` in the guild owning the emoji
+    /// (`ctx.cache.member(...)`, empty if unknown).
+    pub fn is_usable_by(
+        &self,
+        user: &User,
+        guild: Option<&Guild>,
+        member_roles: &[String],
+    ) -> bool {
+        let own = guild.and_then(|guild| {
+            guild
+                .emojis
+                .iter()
+                .find(|emoji| emoji.id.is_some() && emoji.id == self.id)
+        });
+        own.unwrap_or(self)
+            .is_usable(own.is_some(), user.has_nitro(), member_roles)
+    }
+
+    /// Returns the text to put in a message of `user` in `guild` (`None` for DMs) to show
+    /// the emoji
+    ///
+    /// The mention if the emoji is usable (see `is_usable_by`), else the URL of its image (64px),
+    /// which Discord embeds as a picture.
+    pub fn format_for(
+        &self,
+        user: &User,
+        guild: Option<&Guild>,
+        member_roles: &[String],
+    ) -> String {
+        match &self.id {
+            Some(id) if !self.is_usable_by(user, guild, member_roles) => {
+                cdn::emoji(id, self.animated).size(64).to_string()
+            }
+            _ => self.mention(),
+        }
+    }
+}
+
+/// MESSAGE_REACTION_ADD payload. SEE: <https://docs.discord.food/topics/gateway-events#message-reaction-add>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageReactionAdd {
+    /// ID of the user who reacted
+    pub user_id: String,
+
+    /// ID of the channel
+    pub channel_id: String,
+
+    /// ID of the message
+    pub message_id: String,
+
+    /// ID of the guild (if the channel is in a guild)
+    pub guild_id: Option<String>,
+
+    /// The member who reacted (if in a guild)
+    pub member: Option<Member>,
+
+    /// The emoji used to react
+    pub emoji: Emoji,
+
+    /// ID of the author of the message
+    pub message_author_id: Option<String>,
+
+    /// Whether the reaction is a super reaction
+    #[serde(default)]
+    pub burst: bool,
+
+    /// HEX colors used for the super reaction animation
+    #[serde(default)]
+    pub burst_colors: Vec<String>,
+
+    /// Kind of the reaction
+    #[serde(rename = "type", default)]
+    pub kind: ReactionType,
+}
+
+/// MESSAGE_REACTION_REMOVE payload. SEE: <https://docs.discord.food/topics/gateway-events#message-reaction-remove>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageReactionRemove {
+    /// ID of the user whose reaction was removed
+    pub user_id: String,
+
+    /// ID of the channel
+    pub channel_id: String,
+
+    /// ID of the message
+    pub message_id: String,
+
+    /// ID of the guild (if the channel is in a guild)
+    pub guild_id: Option<String>,
+
+    /// The emoji of the removed reaction
+    pub emoji: Emoji,
+
+    /// Whether the reaction was a super reaction
+    #[serde(default)]
+    pub burst: bool,
+
+    /// Kind of the reaction
+    #[serde(rename = "type", default)]
+    pub kind: ReactionType,
+}
+
+/// MESSAGE_REACTION_REMOVE_ALL payload. SEE: <https://docs.discord.food/topics/gateway-events#message-reaction-remove-all>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageReactionRemoveAll {
+    /// ID of the channel
+    pub channel_id: String,
+
+    /// ID of the message
+    pub message_id: String,
+
+    /// ID of the guild (if the channel is in a guild)
+    pub guild_id: Option<String>,
+}
+
+/// MESSAGE_REACTION_REMOVE_EMOJI payload. SEE: <https://docs.discord.food/topics/gateway-events#message-reaction-remove-emoji>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageReactionRemoveEmoji {
+    /// ID of the channel
+    pub channel_id: String,
+
+    /// ID of the message
+    pub message_id: String,
+
+    /// ID of the guild (if the channel is in a guild)
+    pub guild_id: Option<String>,
+
+    /// The emoji whose reactions were removed
+    pub emoji: Emoji,
+}
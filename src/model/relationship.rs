@@ -15,6 +15,26 @@ pub enum RelationshipType {
     Implicit = 5,
 }
 
+/// Relationship change applied with `PUT /users/@me/relationships/{user.id}`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RelationshipAction {
+    /// Sends a friend request, or accepts the pending incoming one
+    Friend,
+    /// Blocks the user
+    Block,
+}
+
+impl RelationshipAction {
+    /// Request body expected by the API for this action
+    pub fn body(self) -> serde_json::Value {
+        match self {
+            // An empty body lets Discord pick between sending and accepting a request
+            RelationshipAction::Friend => json!({}),
+            RelationshipAction::Block => json!({ "type": RelationshipType::Blocked }),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 /// Represents a relationship between the current user and another user (e.g., friend, blocked, etc.)
 pub struct Relationship {
@@ -68,14 +88,14 @@ impl Relationship {
     /// Accepts/sends a friend relationship for this user id.
     pub async fn add_friend(&self, http: &crate::HttpClient) -> crate::Result<()> {
         let url = crate::http::api_url(&format!("/users/@me/relationships/{}", self.id));
-        http.put(&url, json!({ "type": 1 })).await?;
+        http.put(&url, RelationshipAction::Friend.body()).await?;
         Ok(())
     }
 
     /// Blocks this user id.
     pub async fn block(&self, http: &crate::HttpClient) -> crate::Result<()> {
         let url = crate::http::api_url(&format!("/users/@me/relationships/{}", self.id));
-        http.put(&url, json!({ "type": 2 })).await?;
+        http.put(&url, RelationshipAction::Block.body()).await?;
         Ok(())
     }
 
@@ -86,3 +106,53 @@ impl Relationship {
         Ok(())
     }
 }
+
+/// A user Discord suggests to add as a friend. SEE: <https://docs.discord.food/resources/relationships#friend-suggestion-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FriendSuggestion {
+    /// The suggested user (partial user)
+    pub suggested_user: User,
+
+    /// Why the user is suggested
+    #[serde(default)]
+    pub reasons: Vec<FriendSuggestionReason>,
+
+    /// Whether the suggested user found the current user through their contacts
+    #[serde(default)]
+    pub from_suggested_user_contacts: bool,
+}
+
+impl FriendSuggestion {
+    /// Sends a friend request to the suggested user
+    pub async fn add_friend(&self, http: &crate::HttpClient) -> crate::Result<()> {
+        let url = crate::http::api_url(&format!(
+            "/users/@me/relationships/{}",
+            self.suggested_user.id
+        ));
+        http.put(&url, RelationshipAction::Friend.body()).await?;
+        Ok(())
+    }
+
+    /// Dismisses the suggestion
+    pub async fn dismiss(&self, http: &crate::HttpClient) -> crate::Result<()> {
+        let url = crate::http::api_url(&format!("/friend-suggestions/{}", self.suggested_user.id));
+        http.delete(&url).await?;
+        Ok(())
+    }
+}
+
+/// Reason of a friend suggestion. SEE: <https://docs.discord.food/resources/relationships#friend-suggestion-reason-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FriendSuggestionReason {
+    /// Type of the reason (1: external friend)
+    #[serde(rename = "type", default)]
+    pub kind: u8,
+
+    /// Platform the suggestion comes from (e.g. "contacts")
+    #[serde(default)]
+    pub platform_type: String,
+
+    /// Name of the user on the platform
+    #[serde(default)]
+    pub name: String,
+}
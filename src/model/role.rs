@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
-use super::Permissions;
+use super::{Permissions, RoleId};
+use base64::Engine;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Role {
@@ -50,7 +51,7 @@ pub struct Role {
     pub flags: Option<u64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RoleColors {
     /// Primary color of the role (integer representation of hexadecimal color code)
     #[serde(default)]
@@ -63,6 +64,32 @@ pub struct RoleColors {
     pub tertiary_color: Option<u32>,
 }
 
+impl Role {
+    /// Returns the ID of the role as a typed snowflake
+    pub fn role_id(&self) -> Option<RoleId> {
+        RoleId::parse(&self.id)
+    }
+}
+
+impl RoleColors {
+    /// A single color
+    pub fn solid(color: u32) -> Self {
+        Self {
+            primary_color: Some(color),
+            ..Default::default()
+        }
+    }
+
+    /// A gradient between two colors (requires the guild to have the enhanced role colors feature)
+    pub fn gradient(primary_color: u32, secondary_color: u32) -> Self {
+        Self {
+            primary_color: Some(primary_color),
+            secondary_color: Some(secondary_color),
+            ..Default::default()
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RoleTags {
     /// The ID of the bot that created this role (if any)
@@ -86,3 +113,188 @@ pub struct RoleTags {
     #[serde(default)]
     pub guild_connections: Option<bool>,
 }
+
+/// Builds a `data:` URI from raw image bytes, as expected for role icons and avatars
+pub(super) fn image_data_uri(content_type: &str, image: &[u8]) -> String {
+    format!(
+        "data:{};base64,{}",
+        content_type,
+        base64::engine::general_purpose::STANDARD.encode(image)
+    )
+}
+
+/// Builder for a role created with `GuildsManager::create_role`. SEE: <https://docs.discord.food/resources/guild#create-guild-role>
+///
+/// # Example
+/// ```ignore
+/// let role = CreateRole::new("Moderators")
+///     .permissions(Permissions::KICK_MEMBERS | Permissions::BAN_MEMBERS)
+///     .colors(RoleColors::solid(0x3498DB))
+///     .hoist(true);
+/// ctx.guilds.create_role(&ctx.http, guild_id, role, None).await?;
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CreateRole {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    permissions: Option<Permissions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    colors: Option<RoleColors>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hoist: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    icon: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unicode_emoji: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mentionable: Option<bool>,
+}
+
+impl CreateRole {
+    /// Creates a role with the given name
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: Some(name.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the permissions
+    pub fn permissions(mut self, permissions: Permissions) -> Self {
+        self.permissions = Some(permissions);
+        self
+    }
+
+    /// Sets the colors
+    pub fn colors(mut self, colors: RoleColors) -> Self {
+        self.colors = Some(colors);
+        self
+    }
+
+    /// Sets a single color
+    pub fn color(self, color: u32) -> Self {
+        self.colors(RoleColors::solid(color))
+    }
+
+    /// Sets whether the role is displayed separately in the member list
+    pub fn hoist(mut self, hoist: bool) -> Self {
+        self.hoist = Some(hoist);
+        self
+    }
+
+    /// Sets the icon from a `data:` URI (requires the guild to have the role icons feature)
+    pub fn icon(mut self, data_uri: impl Into<String>) -> Self {
+        self.icon = Some(data_uri.into());
+        self
+    }
+
+    /// Sets the icon from raw image bytes (e.g. `"image/png"`)
+    pub fn icon_bytes(self, content_type: &str, image: &[u8]) -> Self {
+        self.icon(image_data_uri(content_type, image))
+    }
+
+    /// Sets a unicode emoji as icon
+    pub fn unicode_emoji(mut self, emoji: impl Into<String>) -> Self {
+        self.unicode_emoji = Some(emoji.into());
+        self
+    }
+
+    /// Sets whether everyone can mention the role
+    pub fn mentionable(mut self, mentionable: bool) -> Self {
+        self.mentionable = Some(mentionable);
+        self
+    }
+}
+
+/// Builder for a role edited with `GuildsManager::edit_role`. SEE: <https://docs.discord.food/resources/guild#modify-guild-role>
+///
+/// Only the fields that were set are sent.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EditRole {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    permissions: Option<Permissions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    colors: Option<RoleColors>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hoist: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    icon: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unicode_emoji: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mentionable: Option<bool>,
+}
+
+impl EditRole {
+    /// Creates an empty edit
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the name
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the permissions
+    pub fn permissions(mut self, permissions: Permissions) -> Self {
+        self.permissions = Some(permissions);
+        self
+    }
+
+    /// Sets the colors
+    pub fn colors(mut self, colors: RoleColors) -> Self {
+        self.colors = Some(colors);
+        self
+    }
+
+    /// Sets a single color
+    pub fn color(self, color: u32) -> Self {
+        self.colors(RoleColors::solid(color))
+    }
+
+    /// Sets whether the role is displayed separately in the member list
+    pub fn hoist(mut self, hoist: bool) -> Self {
+        self.hoist = Some(hoist);
+        self
+    }
+
+    /// Sets the icon from a `data:` URI
+    pub fn icon(mut self, data_uri: impl Into<String>) -> Self {
+        self.icon = Some(Some(data_uri.into()));
+        self
+    }
+
+    /// Sets the icon from raw image bytes (e.g. `"image/png"`)
+    pub fn icon_bytes(self, content_type: &str, image: &[u8]) -> Self {
+        self.icon(image_data_uri(content_type, image))
+    }
+
+    /// Removes the icon
+    pub fn remove_icon(mut self) -> Self {
+        self.icon = Some(None);
+        self
+    }
+
+    /// Sets a unicode emoji as icon
+    pub fn unicode_emoji(mut self, emoji: impl Into<String>) -> Self {
+        self.unicode_emoji = Some(Some(emoji.into()));
+        self
+    }
+
+    /// Removes the unicode emoji
+    pub fn remove_unicode_emoji(mut self) -> Self {
+        self.unicode_emoji = Some(None);
+        self
+    }
+
+    /// Sets whether everyone can mention the role
+    pub fn mentionable(mut self, mentionable: bool) -> Self {
+        self.mentionable = Some(mentionable);
+        self
+    }
+}
@@ -0,0 +1,74 @@
+use super::{Channel, Message};
+use serde::{Deserialize, Serialize};
+
+/// Results of a message search. SEE: <https://docs.discord.food/resources/message#search-messages>
+///
+/// While Discord is still indexing the guild or channel, the response is empty and
+/// `retry_after` tells when to try again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResults {
+    /// Total number of messages matching the query (across every page)
+    #[serde(default)]
+    pub total_results: u64,
+
+    /// Matching messages of this page, each wrapped in a single-element array
+    #[serde(default)]
+    pub messages: Vec<Vec<Message>>,
+
+    /// Threads containing the matching messages
+    #[serde(default)]
+    pub threads: Vec<Channel>,
+
+    /// Whether Discord is still indexing older messages (results may be incomplete)
+    #[serde(default)]
+    pub doing_deep_historical_index: bool,
+
+    /// Number of messages indexed so far
+    pub documents_indexed: Option<u64>,
+
+    /// Seconds to wait before retrying when the index is not available yet
+    pub retry_after: Option<f64>,
+}
+
+impl SearchResults {
+    /// Iterates over the matching messages of this page
+    pub fn iter_messages(&self) -> impl Iterator<Item = &Message> {
+        self.messages.iter().flatten()
+    }
+
+    /// Whether the index was not ready and the search must be retried later
+    pub fn is_indexing(&self) -> bool {
+        self.retry_after.is_some()
+    }
+}
+
+/// Content type filter for a message search
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SearchHas {
+    Link,
+    Embed,
+    Poll,
+    File,
+    Video,
+    Image,
+    Sound,
+    Sticker,
+    Snapshot,
+}
+
+impl SearchHas {
+    /// Value of the `has` query parameter
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SearchHas::Link => "link",
+            SearchHas::Embed => "embed",
+            SearchHas::Poll => "poll",
+            SearchHas::File => "file",
+            SearchHas::Video => "video",
+            SearchHas::Image => "image",
+            SearchHas::Sound => "sound",
+            SearchHas::Sticker => "sticker",
+            SearchHas::Snapshot => "snapshot",
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+/// A logged-in session of the current user. SEE: <https://docs.discord.food/resources/auth-session#auth-session-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    /// Hashed ID of the session, used to log it out
+    pub id_hash: String,
+
+    /// Approximate time the session was last used (ISO8601 timestamp)
+    pub approx_last_used_time: Option<String>,
+
+    /// Information about the client that created the session
+    #[serde(default)]
+    pub client_info: SessionClientInfo,
+}
+
+/// Client information of a session
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionClientInfo {
+    /// Operating system of the client (e.g. "Windows", "Android")
+    pub os: Option<String>,
+
+    /// Platform of the client (e.g. "Discord Client", "Chrome")
+    pub platform: Option<String>,
+
+    /// Approximate location of the client, derived from its IP address
+    pub location: Option<String>,
+}
+
+impl Session {
+    /// Human readable one-line description (e.g. "Chrome on Windows, Paris, France")
+    pub fn describe(&self) -> String {
+        let info = &self.client_info;
+        let mut description = match (&info.platform, &info.os) {
+            (Some(platform), Some(os)) => format!("{platform} on {os}"),
+            (Some(platform), None) => platform.clone(),
+            (None, Some(os)) => os.clone(),
+            (None, None) => "Unknown client".to_string(),
+        };
+        if let Some(location) = &info.location {
+            description.push_str(&format!(", {location}"));
+        }
+        description
+    }
+}
@@ -0,0 +1,328 @@
+//! Protobuf user settings (`/users/@me/settings-proto/{type}`).
+//!
+//! Only the commonly used fields are modelled; unknown fields are dropped on decode,
+//! which is fine for updates because Discord merges the fields present in a PATCH.
+//! SEE: <https://docs.discord.food/resources/user-settings-proto>
+
+use crate::error::{Error, Result};
+use base64::Engine;
+use prost::Message as _;
+
+/// Type of protobuf settings stored for a user
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum UserSettingsType {
+    PreloadedUserSettings = 1,
+    FrecencyUserSettings = 2,
+    TestSettings = 3,
+}
+
+/// `google.protobuf.StringValue`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct StringValue {
+    #[prost(string, tag = "1")]
+    pub value: String,
+}
+
+/// `google.protobuf.BoolValue`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct BoolValue {
+    #[prost(bool, tag = "1")]
+    pub value: bool,
+}
+
+/// `google.protobuf.Int64Value`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Int64Value {
+    #[prost(int64, tag = "1")]
+    pub value: i64,
+}
+
+/// `google.protobuf.UInt64Value`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct UInt64Value {
+    #[prost(uint64, tag = "1")]
+    pub value: u64,
+}
+
+/// `google.protobuf.Int32Value`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Int32Value {
+    #[prost(int32, tag = "1")]
+    pub value: i32,
+}
+
+/// Settings stored for the client, loaded on startup
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct PreloadedUserSettings {
+    /// Versions of the settings blob
+    #[prost(message, optional, tag = "1")]
+    pub versions: Option<Versions>,
+
+    /// Online status and custom status
+    #[prost(message, optional, tag = "11")]
+    pub status: Option<StatusSettings>,
+
+    /// Locale and timezone offset
+    #[prost(message, optional, tag = "12")]
+    pub localization: Option<LocalizationSettings>,
+
+    /// Theme and developer mode
+    #[prost(message, optional, tag = "13")]
+    pub appearance: Option<AppearanceSettings>,
+
+    /// Guild folders and guild ordering in the sidebar
+    #[prost(message, optional, tag = "14")]
+    pub guild_folders: Option<GuildFolders>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Versions {
+    /// Version of the client that last wrote the settings
+    #[prost(uint32, tag = "1")]
+    pub client_version: u32,
+
+    /// Version of the settings on the server
+    #[prost(uint32, tag = "2")]
+    pub server_version: u32,
+
+    /// Version of the settings data
+    #[prost(uint32, tag = "3")]
+    pub data_version: u32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct StatusSettings {
+    /// Online status ("online", "idle", "dnd", "invisible")
+    #[prost(message, optional, tag = "1")]
+    pub status: Option<StringValue>,
+
+    /// Custom status shown on the profile
+    #[prost(message, optional, tag = "2")]
+    pub custom_status: Option<CustomStatus>,
+
+    /// Whether the current game is shown as activity
+    #[prost(message, optional, tag = "3")]
+    pub show_current_game: Option<BoolValue>,
+
+    /// Unix time (in milliseconds) when the status resets
+    #[prost(fixed64, tag = "4")]
+    pub status_expires_at_ms: u64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CustomStatus {
+    /// Text of the custom status
+    #[prost(string, tag = "1")]
+    pub text: String,
+
+    /// ID of the custom emoji (0 for none or unicode emojis)
+    #[prost(fixed64, tag = "2")]
+    pub emoji_id: u64,
+
+    /// Name of the emoji (unicode character for standard emojis)
+    #[prost(string, tag = "3")]
+    pub emoji_name: String,
+
+    /// Unix time (in milliseconds) when the custom status expires (0 for never)
+    #[prost(fixed64, tag = "4")]
+    pub expires_at_ms: u64,
+
+    /// Unix time (in milliseconds) when the custom status was set
+    #[prost(fixed64, tag = "5")]
+    pub created_at_ms: u64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct LocalizationSettings {
+    /// Locale of the client (e.g. "en-US")
+    #[prost(message, optional, tag = "1")]
+    pub locale: Option<StringValue>,
+
+    /// Offset from UTC in minutes
+    #[prost(message, optional, tag = "2")]
+    pub timezone_offset: Option<Int32Value>,
+}
+
+/// Client theme
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum Theme {
+    Unset = 0,
+    Dark = 1,
+    Light = 2,
+    Darker = 3,
+    Midnight = 4,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct AppearanceSettings {
+    /// Client theme
+    #[prost(enumeration = "Theme", tag = "1")]
+    pub theme: i32,
+
+    /// Whether developer mode is enabled
+    #[prost(bool, tag = "2")]
+    pub developer_mode: bool,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GuildFolders {
+    /// Folders in the sidebar (guilds outside folders are single-guild folders without an ID)
+    #[prost(message, repeated, tag = "1")]
+    pub folders: Vec<GuildFolder>,
+
+    /// Guild IDs in sidebar order
+    #[prost(fixed64, repeated, tag = "2")]
+    pub guild_positions: Vec<u64>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GuildFolder {
+    /// Guild IDs in the folder
+    #[prost(fixed64, repeated, tag = "1")]
+    pub guild_ids: Vec<u64>,
+
+    /// ID of the folder (absent for single guilds)
+    #[prost(message, optional, tag = "2")]
+    pub id: Option<Int64Value>,
+
+    /// Name of the folder
+    #[prost(message, optional, tag = "3")]
+    pub name: Option<StringValue>,
+
+    /// Color of the folder (RGB integer)
+    #[prost(message, optional, tag = "4")]
+    pub color: Option<UInt64Value>,
+}
+
+impl PreloadedUserSettings {
+    /// Decodes settings from the base64 blob returned by the API
+    pub fn from_base64(settings: &str) -> Result<Self> {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(settings)
+            .map_err(|_| Error::InvalidPayload)?;
+        Ok(Self::decode(bytes.as_slice())?)
+    }
+
+    /// Encodes settings to the base64 blob expected by the API
+    pub fn to_base64(&self) -> String {
+        base64::engine::general_purpose::STANDARD.encode(self.encode_to_vec())
+    }
+
+    /// Returns the online status, if set
+    pub fn online_status(&self) -> Option<&str> {
+        self.status
+            .as_ref()
+            .and_then(|status| status.status.as_ref())
+            .map(|status| status.value.as_str())
+    }
+
+    /// Returns the custom status, if set
+    pub fn custom_status(&self) -> Option<&CustomStatus> {
+        self.status
+            .as_ref()
+            .and_then(|status| status.custom_status.as_ref())
+    }
+
+    /// Returns the client theme
+    pub fn theme(&self) -> Theme {
+        self.appearance
+            .as_ref()
+            .and_then(|appearance| Theme::try_from(appearance.theme).ok())
+            .unwrap_or(Theme::Unset)
+    }
+
+    /// Settings update that only changes the online status
+    pub fn with_online_status(status: impl Into<String>) -> Self {
+        Self {
+            status: Some(StatusSettings {
+                status: Some(StringValue {
+                    value: status.into(),
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// Settings update that only changes the custom status (`None` clears it)
+    pub fn with_custom_status(custom_status: Option<CustomStatus>) -> Self {
+        Self {
+            status: Some(StatusSettings {
+                custom_status: Some(custom_status.unwrap_or_default()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// Settings update that only changes the guild folders
+    pub fn with_guild_folders(guild_folders: GuildFolders) -> Self {
+        Self {
+            guild_folders: Some(guild_folders),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<&[crate::model::GuildFolder]> for GuildFolders {
+    fn from(folders: &[crate::model::GuildFolder]) -> Self {
+        let parse_ids =
+            |ids: &[String]| -> Vec<u64> { ids.iter().filter_map(|id| id.parse().ok()).collect() };
+        Self {
+            folders: folders
+                .iter()
+                .map(|folder| GuildFolder {
+                    guild_ids: parse_ids(&folder.guild_ids),
+                    id: folder.id.map(|value| Int64Value { value }),
+                    name: folder.name.clone().map(|value| StringValue { value }),
+                    color: folder.color.map(|color| UInt64Value {
+                        value: color as u64,
+                    }),
+                })
+                .collect(),
+            guild_positions: parse_ids(&crate::model::GuildFolder::positions(folders)),
+        }
+    }
+}
+
+impl From<&GuildFolders> for Vec<crate::model::GuildFolder> {
+    fn from(guild_folders: &GuildFolders) -> Self {
+        guild_folders
+            .folders
+            .iter()
+            .map(|folder| crate::model::GuildFolder {
+                id: folder.id.as_ref().map(|id| id.value),
+                name: folder.name.as_ref().map(|name| name.value.clone()),
+                color: folder.color.as_ref().map(|color| color.value as u32),
+                guild_ids: folder.guild_ids.iter().map(u64::to_string).collect(),
+            })
+            .collect()
+    }
+}
+
+impl CustomStatus {
+    /// Creates a custom status with text only
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the emoji, in the format of `Message::react` (`"🦀"`, `"name:id"`) or as a
+    /// custom emoji mention (`"<:name:id>"`)
+    pub fn emoji(mut self, emoji: impl AsRef<str>) -> Self {
+        let emoji = crate::model::ActivityEmoji::from(emoji.as_ref());
+        self.emoji_id = emoji.id.and_then(|id| id.parse().ok()).unwrap_or_default();
+        self.emoji_name = emoji.name;
+        self
+    }
+
+    /// Sets the expiration time (Unix time in milliseconds)
+    pub fn expires_at_ms(mut self, expires_at_ms: u64) -> Self {
+        self.expires_at_ms = expires_at_ms;
+        self
+    }
+}
@@ -0,0 +1,202 @@
+use super::User;
+use crate::cdn;
+use serde::{Deserialize, Serialize};
+
+/// Type of a sticker
+///
+/// Types added after this version are kept as `Unknown`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "u8", into = "u8")]
+pub enum StickerType {
+    /// An official sticker of a pack
+    Standard,
+    /// A sticker uploaded to a guild
+    Guild,
+    /// A type this version does not know
+    Unknown(u8),
+}
+
+impl From<u8> for StickerType {
+    fn from(kind: u8) -> Self {
+        match kind {
+            1 => StickerType::Standard,
+            2 => StickerType::Guild,
+            other => StickerType::Unknown(other),
+        }
+    }
+}
+
+impl From<StickerType> for u8 {
+    fn from(kind: StickerType) -> Self {
+        match kind {
+            StickerType::Standard => 1,
+            StickerType::Guild => 2,
+            StickerType::Unknown(other) => other,
+        }
+    }
+}
+
+/// File format of a sticker
+///
+/// Formats added after this version are kept as `Unknown`, so messages carrying such
+/// stickers still parse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "u8", into = "u8")]
+pub enum StickerFormatType {
+    /// Static PNG image
+    Png,
+    /// Animated PNG image
+    Apng,
+    /// Lottie animation (JSON)
+    Lottie,
+    /// Animated GIF image
+    Gif,
+    /// A format this version does not know
+    Unknown(u8),
+}
+
+impl StickerFormatType {
+    /// File extension of the format on the CDN (`png` for unknown formats)
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Png | Self::Apng | Self::Unknown(_) => "png",
+            Self::Lottie => "json",
+            Self::Gif => "gif",
+        }
+    }
+}
+
+impl From<u8> for StickerFormatType {
+    fn from(format: u8) -> Self {
+        match format {
+            1 => StickerFormatType::Png,
+            2 => StickerFormatType::Apng,
+            3 => StickerFormatType::Lottie,
+            4 => StickerFormatType::Gif,
+            other => StickerFormatType::Unknown(other),
+        }
+    }
+}
+
+impl From<StickerFormatType> for u8 {
+    fn from(format: StickerFormatType) -> Self {
+        match format {
+            StickerFormatType::Png => 1,
+            StickerFormatType::Apng => 2,
+            StickerFormatType::Lottie => 3,
+            StickerFormatType::Gif => 4,
+            StickerFormatType::Unknown(other) => other,
+        }
+    }
+}
+
+/// A sticker, or the sticker item of a message. SEE: <https://docs.discord.food/resources/sticker#sticker-object>
+///
+/// Message sticker items only carry the ID, name and format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Sticker {
+    /// Unique ID of the sticker
+    pub id: String,
+
+    /// For standard stickers, the pack ID of the sticker
+    pub pack_id: Option<String>,
+
+    /// Name of the sticker
+    pub name: String,
+
+    /// Description of the sticker
+    pub description: Option<String>,
+
+    /// Autocomplete/suggestion tags of the sticker (comma-separated for standard stickers,
+    /// the related emoji for guild stickers)
+    #[serde(default)]
+    pub tags: String,
+
+    /// Type of the sticker (not sent for message sticker items)
+    #[serde(rename = "type")]
+    pub kind: Option<StickerType>,
+
+    /// The type of sticker format
+    pub format_type: StickerFormatType,
+
+    /// Whether the guild sticker can be used (false when the guild lost boosts)
+    #[serde(default = "default_available")]
+    pub available: bool,
+
+    /// The ID of the guild that owns this sticker, if it's a guild sticker
+    pub guild_id: Option<String>,
+
+    /// User that created this sticker
+    pub user: Option<User>,
+
+    /// The sort order of the sticker in the pack
+    pub sort_value: Option<u64>,
+}
+
+fn default_available() -> bool {
+    true
+}
+
+impl Sticker {
+    /// Returns the URL of the sticker file
+    pub fn url(&self) -> String {
+        let base = match self.format_type {
+            // GIF stickers are only served by the media proxy
+            StickerFormatType::Gif => "https://media.discordapp.net",
+            _ => "https://cdn.discordapp.com",
+        };
+        format!(
+            "{}/stickers/{}.{}",
+            base,
+            self.id,
+            self.format_type.extension()
+        )
+    }
+
+    /// Whether the sticker belongs to a guild
+    pub fn is_guild_sticker(&self) -> bool {
+        self.kind == Some(StickerType::Guild) || self.guild_id.is_some()
+    }
+}
+
+/// A pack of standard stickers. SEE: <https://docs.discord.food/resources/sticker#sticker-pack-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StickerPack {
+    /// Pack ID
+    pub id: String,
+
+    /// Stickers of the pack
+    #[serde(default)]
+    pub stickers: Vec<Sticker>,
+
+    /// Name of the pack
+    pub name: String,
+
+    /// ID of the SKU of the pack
+    pub sku_id: String,
+
+    /// ID of the sticker shown as the pack's icon
+    pub cover_sticker_id: Option<String>,
+
+    /// Description of the pack
+    #[serde(default)]
+    pub description: String,
+
+    /// ID of the banner image of the pack
+    pub banner_asset_id: Option<String>,
+}
+
+impl StickerPack {
+    /// Returns the URL of the pack banner
+    pub fn banner_url(&self) -> Option<String> {
+        self.banner_asset_id
+            .as_ref()
+            .map(|asset_id| cdn::sticker_pack_banner(asset_id).into())
+    }
+
+    /// Returns the sticker shown as the pack's icon
+    pub fn cover_sticker(&self) -> Option<&Sticker> {
+        let cover_id = self.cover_sticker_id.as_deref()?;
+        self.stickers.iter().find(|sticker| sticker.id == cover_id)
+    }
+}
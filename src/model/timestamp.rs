@@ -0,0 +1,109 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+/// An ISO8601 timestamp sent by the API, parsed to a UTC `chrono::DateTime`
+///
+/// Serialized back as RFC3339 with millisecond precision, the format the API uses.
+/// Existing models keep their raw `String` fields; use the `*_parsed` accessors
+/// (e.g. `Member::joined_at_parsed`) or `Timestamp::parse` on them.
+///
+/// # Example
+/// ```
+/// use diself::model::Timestamp;
+///
+/// let joined = Timestamp::parse("2024-03-01T12:30:00.000000+00:00").unwrap();
+/// assert_eq!(joined.unix_ms(), 1_709_296_200_000);
+/// assert_eq!(joined.to_string(), "2024-03-01T12:30:00.000Z");
+/// assert!(joined < Timestamp::now());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(DateTime<Utc>);
+
+impl Timestamp {
+    /// Current time
+    pub fn now() -> Self {
+        Self(Utc::now())
+    }
+
+    /// Parses an ISO8601 (RFC3339) timestamp, returning `None` if it is malformed
+    pub fn parse(timestamp: &str) -> Option<Self> {
+        DateTime::parse_from_rfc3339(timestamp)
+            .ok()
+            .map(|datetime| Self(datetime.with_timezone(&Utc)))
+    }
+
+    /// Builds a timestamp from milliseconds since the Unix epoch
+    pub fn from_unix_ms(unix_ms: i64) -> Option<Self> {
+        DateTime::from_timestamp_millis(unix_ms).map(Self)
+    }
+
+    /// Returns the timestamp as a `chrono::DateTime`
+    pub fn as_datetime(&self) -> DateTime<Utc> {
+        self.0
+    }
+
+    /// Milliseconds since the Unix epoch
+    pub fn unix_ms(&self) -> i64 {
+        self.0.timestamp_millis()
+    }
+
+    /// Whether the timestamp is in the past
+    pub fn is_past(&self) -> bool {
+        self.0 <= Utc::now()
+    }
+}
+
+/// Parses an optional raw timestamp field
+pub(crate) fn parse_timestamp(timestamp: Option<&str>) -> Option<Timestamp> {
+    timestamp.and_then(Timestamp::parse)
+}
+
+impl Deref for Timestamp {
+    type Target = DateTime<Utc>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<DateTime<Utc>> for Timestamp {
+    fn from(datetime: DateTime<Utc>) -> Self {
+        Self(datetime)
+    }
+}
+
+impl From<Timestamp> for DateTime<Utc> {
+    fn from(timestamp: Timestamp) -> Self {
+        timestamp.0
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = chrono::ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        DateTime::parse_from_rfc3339(s).map(|datetime| Self(datetime.with_timezone(&Utc)))
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0.to_rfc3339_opts(SecondsFormat::Millis, true))
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        raw.parse().map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,21 @@
+use super::Member;
+use serde::{Deserialize, Serialize};
+
+/// TYPING_START payload. SEE: <https://docs.discord.food/topics/gateway-events#typing-start>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TypingStart {
+    /// ID of the channel
+    pub channel_id: String,
+
+    /// ID of the guild (if the channel is in a guild)
+    pub guild_id: Option<String>,
+
+    /// ID of the user who started typing
+    pub user_id: String,
+
+    /// Unix time (in seconds) of when the user started typing
+    pub timestamp: u64,
+
+    /// The member who started typing (if in a guild)
+    pub member: Option<Member>,
+}
@@ -1,6 +1,9 @@
-use crate::model::{Emoji, Member};
+use super::role::image_data_uri;
+use crate::cdn;
+use crate::error::{Error, Result};
+use crate::model::{Connection, Emoji, Member, Presence, UserId};
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{json, Map, Value};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
@@ -82,32 +85,6 @@ pub struct User {
     pub presence: Option<Presence>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Presence {
-    /// Online status (`online`, `idle`, `dnd`, `offline`, ...)
-    pub status: String,
-
-    /// Current activities as raw payload entries.
-    #[serde(default)]
-    pub activities: Vec<serde_json::Value>,
-
-    /// Client platform statuses (`desktop`, `mobile`, `web`) when provided.
-    pub client_status: Option<ClientStatus>,
-
-    /// Unix time (ms) when user went idle, if any.
-    pub since: Option<i64>,
-
-    /// AFK flag from gateway presence payload.
-    pub afk: Option<bool>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ClientStatus {
-    pub desktop: Option<String>,
-    pub mobile: Option<String>,
-    pub web: Option<String>,
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserProfile {
     /// The bot's application profile
@@ -135,6 +112,10 @@ pub struct UserProfile {
     /// The user's pre-migration username#discriminator, if applicable and shown
     pub legacy_username: Option<String>,
 
+    /// The accounts the user has connected and made visible on their profile
+    #[serde(default)]
+    pub connected_accounts: Vec<Connection>,
+
     /// The mutual guilds of the user with the current user
     #[serde(default)]
     pub mutual_guilds: Option<Vec<MutualGuild>>,
@@ -157,29 +138,51 @@ pub struct UserProfile {
 }
 
 impl User {
+    /// Returns the ID of the user as a typed snowflake
+    pub fn user_id(&self) -> Option<UserId> {
+        UserId::parse(&self.id)
+    }
+
     /// Returns the user's tag (username#discriminator)
     pub fn tag(&self) -> String {
         format!("{}#{}", self.username, self.discriminator)
     }
 
-    /// Returns the URL of the user's avatar (if any)
+    /// Returns the URL of the user's avatar (if any), a GIF when animated
+    ///
+    /// Use `cdn::user_avatar` to pick another size or format.
     pub fn avatar_url(&self) -> Option<String> {
-        self.avatar.as_ref().map(|hash| {
-            format!(
-                "https://cdn.discordapp.com/avatars/{}/{}.png",
-                self.id, hash
-            )
-        })
+        self.avatar
+            .as_ref()
+            .map(|hash| cdn::user_avatar(&self.id, hash).into())
     }
-    /// Returns the URL of the user's banner (if any)
+
+    /// Returns the index of the default avatar shown when the user has none
+    pub fn default_avatar_index(&self) -> u8 {
+        if self.discriminator == "0" {
+            let id = self.id.parse::<u64>().unwrap_or_default();
+            ((id >> 22) % 6) as u8
+        } else {
+            (self.discriminator.parse::<u16>().unwrap_or_default() % 5) as u8
+        }
+    }
+
+    /// Returns the URL of the user's avatar, or of the default avatar if they have none
+    pub fn display_avatar_url(&self) -> String {
+        self.avatar_url()
+            .unwrap_or_else(|| cdn::default_avatar(self.default_avatar_index()).into())
+    }
+
+    /// Returns the URL of the user's banner (if any), a GIF when animated
     pub fn banner_url(&self) -> Option<String> {
-        self.banner.as_ref().map(|hash| {
-            let extension = if hash.starts_with("a_") { "gif" } else { "png" };
-            format!(
-                "https://cdn.discordapp.com/banners/{}/{}.{}",
-                self.id, hash, extension
-            )
-        })
+        self.banner
+            .as_ref()
+            .map(|hash| cdn::user_banner(&self.id, hash).into())
+    }
+
+    /// Returns the server tag displayed next to the user's name (if any)
+    pub fn guild_tag(&self) -> Option<GuildTag> {
+        self.primary_guild.as_ref()?.guild_tag()
     }
 
     /// Returns a string representation of the user's mention (e.g., "<@123456789>")
@@ -342,7 +345,7 @@ pub struct Nameplate {
     pub expires_at: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PrimaryGuild {
     /// User's primary guild ID
     pub identity_guild_id: Option<String>,
@@ -357,6 +360,42 @@ pub struct PrimaryGuild {
     pub badge: Option<String>,
 }
 
+impl PrimaryGuild {
+    /// Returns the server tag shown next to the user's name (`None` if it is hidden)
+    pub fn guild_tag(&self) -> Option<GuildTag> {
+        if self.identity_enabled != Some(true) {
+            return None;
+        }
+        Some(GuildTag {
+            guild_id: self.identity_guild_id.clone()?,
+            tag: self.tag.clone()?,
+            badge: self.badge.clone(),
+        })
+    }
+}
+
+/// A server tag (clan tag) and its badge, displayed next to the name of its members
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GuildTag {
+    /// ID of the guild the tag belongs to
+    pub guild_id: String,
+
+    /// Text of the tag (max 4 characters)
+    pub tag: String,
+
+    /// Badge hash
+    pub badge: Option<String>,
+}
+
+impl GuildTag {
+    /// Returns the URL of the badge (if any)
+    pub fn badge_url(&self) -> Option<String> {
+        self.badge
+            .as_ref()
+            .map(|hash| cdn::guild_tag_badge(&self.guild_id, hash).into())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Avatar {
     /// The avatar ID
@@ -368,3 +407,220 @@ pub struct Avatar {
     /// The avatar's description (if any)
     pub description: Option<String>,
 }
+
+/// How much the current user interacts with another user, from `UsersManager::user_affinities`. SEE: <https://docs.discord.food/resources/user#user-affinity-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserAffinity {
+    /// ID of the other user
+    pub user_id: String,
+
+    /// Interaction score, higher is closer
+    #[serde(default)]
+    pub affinity: f64,
+}
+
+/// How much the current user interacts with a guild, from `UsersManager::guild_affinities`. SEE: <https://docs.discord.food/resources/user#guild-affinity-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuildAffinity {
+    /// ID of the guild
+    pub guild_id: String,
+
+    /// Interaction score, higher is closer
+    #[serde(default)]
+    pub affinity: f64,
+}
+
+/// Longest display name accepted by Discord
+const MAX_GLOBAL_NAME_LENGTH: usize = 32;
+/// Longest bio accepted by Discord
+const MAX_BIO_LENGTH: usize = 190;
+/// Longest pronouns accepted by Discord
+const MAX_PRONOUNS_LENGTH: usize = 40;
+/// Fields of `EditProfile` sent to `PATCH /users/@me/profile` (the others go to `/users/@me`)
+const PROFILE_FIELDS: [&str; 5] = ["bio", "pronouns", "accent_color", "banner", "theme_colors"];
+
+/// Builder for the current user's profile, used with `UsersManager::edit_profile`. SEE: <https://docs.discord.food/resources/user#modify-user-profile>
+///
+/// Only the fields that were set are sent. Lengths and colors are checked by `validate`
+/// before anything is sent.
+///
+/// # Example
+/// ```
+/// use diself::model::EditProfile;
+///
+/// let profile = EditProfile::new()
+///     .global_name("Ferris")
+///     .bio("Rustacean")
+///     .pronouns("they/them")
+///     .accent_color(0xF74C00);
+/// assert!(profile.validate().is_ok());
+/// assert!(EditProfile::new().bio("a".repeat(191)).validate().is_err());
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EditProfile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    global_name: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    avatar: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    avatar_decoration_id: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    avatar_decoration_sku_id: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bio: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pronouns: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    accent_color: Option<Option<u32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    banner: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    theme_colors: Option<Option<[u32; 2]>>,
+}
+
+impl EditProfile {
+    /// Creates an empty edit
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the display name (max 32 characters)
+    pub fn global_name(mut self, global_name: impl Into<String>) -> Self {
+        self.global_name = Some(Some(global_name.into()));
+        self
+    }
+
+    /// Removes the display name, showing the username instead
+    pub fn remove_global_name(mut self) -> Self {
+        self.global_name = Some(None);
+        self
+    }
+
+    /// Sets the avatar from a `data:` URI
+    pub fn avatar(mut self, data_uri: impl Into<String>) -> Self {
+        self.avatar = Some(Some(data_uri.into()));
+        self
+    }
+
+    /// Sets the avatar from raw image bytes (e.g. `"image/png"`)
+    pub fn avatar_bytes(self, content_type: &str, image: &[u8]) -> Self {
+        self.avatar(image_data_uri(content_type, image))
+    }
+
+    /// Removes the avatar
+    pub fn remove_avatar(mut self) -> Self {
+        self.avatar = Some(None);
+        self
+    }
+
+    /// Sets an owned avatar decoration
+    pub fn avatar_decoration(
+        mut self,
+        decoration_id: impl Into<String>,
+        sku_id: impl Into<String>,
+    ) -> Self {
+        self.avatar_decoration_id = Some(Some(decoration_id.into()));
+        self.avatar_decoration_sku_id = Some(Some(sku_id.into()));
+        self
+    }
+
+    /// Removes the avatar decoration
+    pub fn remove_avatar_decoration(mut self) -> Self {
+        self.avatar_decoration_id = Some(None);
+        self.avatar_decoration_sku_id = Some(None);
+        self
+    }
+
+    /// Sets the bio (max 190 characters, empty to remove it)
+    pub fn bio(mut self, bio: impl Into<String>) -> Self {
+        self.bio = Some(bio.into());
+        self
+    }
+
+    /// Sets the pronouns (max 40 characters, empty to remove them)
+    pub fn pronouns(mut self, pronouns: impl Into<String>) -> Self {
+        self.pronouns = Some(pronouns.into());
+        self
+    }
+
+    /// Sets the banner color, shown when there is no banner image (`0xRRGGBB`)
+    pub fn accent_color(mut self, color: u32) -> Self {
+        self.accent_color = Some(Some(color));
+        self
+    }
+
+    /// Removes the banner color
+    pub fn remove_accent_color(mut self) -> Self {
+        self.accent_color = Some(None);
+        self
+    }
+
+    /// Sets the banner from a `data:` URI (requires Nitro)
+    pub fn banner(mut self, data_uri: impl Into<String>) -> Self {
+        self.banner = Some(Some(data_uri.into()));
+        self
+    }
+
+    /// Sets the banner from raw image bytes (e.g. `"image/gif"`)
+    pub fn banner_bytes(self, content_type: &str, image: &[u8]) -> Self {
+        self.banner(image_data_uri(content_type, image))
+    }
+
+    /// Removes the banner
+    pub fn remove_banner(mut self) -> Self {
+        self.banner = Some(None);
+        self
+    }
+
+    /// Sets the profile theme colors (`0xRRGGBB`, requires Nitro)
+    pub fn theme_colors(mut self, primary: u32, accent: u32) -> Self {
+        self.theme_colors = Some(Some([primary, accent]));
+        self
+    }
+
+    /// Removes the profile theme
+    pub fn remove_theme_colors(mut self) -> Self {
+        self.theme_colors = Some(None);
+        self
+    }
+
+    /// Checks the length limits and colors, returning `Error::InvalidArgument` for the
+    /// first invalid field
+    pub fn validate(&self) -> Result<()> {
+        let check_length = |field: &str, value: Option<&str>, max: usize| match value {
+            Some(value) if value.chars().count() > max => Err(Error::InvalidArgument(format!(
+                "{field} is longer than {max} characters"
+            ))),
+            _ => Ok(()),
+        };
+        check_length(
+            "global_name",
+            self.global_name.as_ref().and_then(|name| name.as_deref()),
+            MAX_GLOBAL_NAME_LENGTH,
+        )?;
+        check_length("bio", self.bio.as_deref(), MAX_BIO_LENGTH)?;
+        check_length("pronouns", self.pronouns.as_deref(), MAX_PRONOUNS_LENGTH)?;
+
+        let mut colors = self
+            .accent_color
+            .flatten()
+            .into_iter()
+            .chain(self.theme_colors.flatten().into_iter().flatten());
+        if let Some(color) = colors.find(|color| *color > 0xFF_FF_FF) {
+            return Err(Error::InvalidArgument(format!(
+                "color {color:#x} is not a 0xRRGGBB color"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Splits the edit into the `/users/@me` and `/users/@me/profile` bodies
+    pub(crate) fn into_bodies(self) -> (Map<String, Value>, Map<String, Value>) {
+        let Ok(Value::Object(fields)) = serde_json::to_value(self) else {
+            return Default::default();
+        };
+        fields
+            .into_iter()
+            .partition(|(key, _)| !PROFILE_FIELDS.contains(&key.as_str()))
+    }
+}
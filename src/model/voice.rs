@@ -0,0 +1,174 @@
+use super::Member;
+use serde::{Deserialize, Serialize};
+
+/// Voice connection state of a user. SEE: <https://docs.discord.food/resources/voice#voice-state-object>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoiceState {
+    /// ID of the guild (absent for private calls and in GUILD_CREATE voice states)
+    pub guild_id: Option<String>,
+
+    /// ID of the voice channel the user is connected to (null when the user left)
+    pub channel_id: Option<String>,
+
+    /// ID of the user
+    pub user_id: String,
+
+    /// Guild member of the user (guild voice states only)
+    pub member: Option<Member>,
+
+    /// ID of the voice session
+    #[serde(default)]
+    pub session_id: String,
+
+    /// Whether the user is deafened by the guild
+    #[serde(default)]
+    pub deaf: bool,
+
+    /// Whether the user is muted by the guild
+    #[serde(default)]
+    pub mute: bool,
+
+    /// Whether the user deafened themselves
+    #[serde(default)]
+    pub self_deaf: bool,
+
+    /// Whether the user muted themselves
+    #[serde(default)]
+    pub self_mute: bool,
+
+    /// Whether the user is streaming with Go Live
+    #[serde(default)]
+    pub self_stream: bool,
+
+    /// Whether the user's camera is enabled
+    #[serde(default)]
+    pub self_video: bool,
+
+    /// Whether the user is suppressed (stage channels)
+    #[serde(default)]
+    pub suppress: bool,
+
+    /// When the user requested to speak (stage channels), in ISO8601 format
+    pub request_to_speak_timestamp: Option<String>,
+}
+
+impl VoiceState {
+    /// Whether the user is connected to a voice channel
+    pub fn is_connected(&self) -> bool {
+        self.channel_id.is_some()
+    }
+
+    /// Whether the user can be heard (not muted by themselves, the guild or suppression)
+    pub fn is_speaking_allowed(&self) -> bool {
+        !(self.mute || self.self_mute || self.suppress)
+    }
+}
+
+/// Voice state of the current user, sent with gateway op 4. SEE: <https://docs.discord.food/topics/gateway-events#update-voice-state>
+///
+/// # Example
+/// ```ignore
+/// use diself::model::EditVoiceState;
+///
+/// ctx.gateway()
+///     .set_voice_state(&EditVoiceState::join(Some("guild_id"), "channel_id").self_mute(true))?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EditVoiceState {
+    guild_id: Option<String>,
+    channel_id: Option<String>,
+    self_mute: bool,
+    self_deaf: bool,
+    self_video: bool,
+    /// Go Live is started with op 18, not op 4
+    #[serde(skip)]
+    self_stream: bool,
+}
+
+impl EditVoiceState {
+    /// Joins (or moves to) a voice channel; `guild_id` is `None` for private calls
+    pub fn join(guild_id: Option<&str>, channel_id: impl Into<String>) -> Self {
+        Self {
+            guild_id: guild_id.map(str::to_string),
+            channel_id: Some(channel_id.into()),
+            self_mute: false,
+            self_deaf: false,
+            self_video: false,
+            self_stream: false,
+        }
+    }
+
+    /// Leaves the voice channel of a guild (or the private call with `None`)
+    pub fn leave(guild_id: Option<&str>) -> Self {
+        Self {
+            guild_id: guild_id.map(str::to_string),
+            channel_id: None,
+            self_mute: false,
+            self_deaf: false,
+            self_video: false,
+            self_stream: false,
+        }
+    }
+
+    /// Sets whether the current user is muted
+    pub fn self_mute(mut self, mute: bool) -> Self {
+        self.self_mute = mute;
+        self
+    }
+
+    /// Sets whether the current user is deafened
+    pub fn self_deaf(mut self, deaf: bool) -> Self {
+        self.self_deaf = deaf;
+        self
+    }
+
+    /// Sets whether the camera of the current user is enabled
+    pub fn self_video(mut self, video: bool) -> Self {
+        self.self_video = video;
+        self
+    }
+
+    pub(crate) fn self_stream(mut self, stream: bool) -> Self {
+        self.self_stream = stream;
+        self
+    }
+
+    /// ID of the guild (`None` for private calls)
+    pub fn guild_id(&self) -> Option<&str> {
+        self.guild_id.as_deref()
+    }
+
+    /// ID of the voice channel (`None` when leaving)
+    pub fn channel_id(&self) -> Option<&str> {
+        self.channel_id.as_deref()
+    }
+
+    /// Whether the current user is muted
+    pub fn is_muted(&self) -> bool {
+        self.self_mute
+    }
+
+    /// Whether the current user is deafened
+    pub fn is_deafened(&self) -> bool {
+        self.self_deaf
+    }
+
+    /// Whether the camera of the current user is enabled
+    pub fn is_video(&self) -> bool {
+        self.self_video
+    }
+
+    /// Whether the current user is streaming with Go Live
+    pub fn is_streaming(&self) -> bool {
+        self.self_stream
+    }
+
+    /// Key identifying the Go Live stream of `user_id` in this channel
+    pub fn stream_key(&self, user_id: &str) -> Option<String> {
+        let channel_id = self.channel_id.as_deref()?;
+        Some(match &self.guild_id {
+            Some(guild_id) => format!("guild:{}:{}:{}", guild_id, channel_id, user_id),
+            None => format!("call:{}:{}", channel_id, user_id),
+        })
+    }
+}
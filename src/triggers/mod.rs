@@ -0,0 +1,6 @@
+mod trigger;
+#[allow(clippy::module_inception)]
+mod triggers;
+
+pub use trigger::{Trigger, TriggerFuture, TriggerHandler, TriggerPattern};
+pub use triggers::{TriggerOutcome, TriggerSet};
@@ -0,0 +1,236 @@
+use crate::client::Context;
+use crate::error::{Error, Result};
+use crate::model::Message;
+use regex::Regex;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Boxed future returned by trigger handlers
+pub type TriggerFuture = Pin<Box<dyn Future<Output = Result<()>> + Send>>;
+
+/// Type for trigger handler callbacks
+/// Takes the matched text followed by the regex groups (empty when a group did not match)
+pub type TriggerHandler = Arc<dyn Fn(Context, Message, Vec<String>) -> TriggerFuture + Send + Sync>;
+
+/// What a message must contain to fire a trigger
+#[derive(Debug, Clone)]
+pub enum TriggerPattern {
+    /// The whole message equals the text (surrounding whitespace ignored)
+    Exact(String),
+    /// The message contains the text
+    Contains(String),
+    /// The message matches the regex (use `(?i)` to ignore case)
+    Regex(Regex),
+}
+
+/// Reaction of a trigger to a matching message
+#[derive(Clone)]
+pub(crate) enum TriggerAction {
+    Reply(String),
+    Send(String),
+    React(String),
+    Handler(TriggerHandler),
+}
+
+/// A pattern → response rule registered in a `TriggerSet`
+///
+/// Replies and messages can use `$0` (the matched text) and `$1` to `$9` (the regex groups).
+///
+/// # Example
+/// ```ignore
+/// use diself::triggers::Trigger;
+/// use std::time::Duration;
+///
+/// let hello = Trigger::contains("hello").reply("Hi there!").react("👋");
+/// let issue = Trigger::regex(r"#(\d+)")?
+///     .send("https://github.com/daniilsys/diself/issues/$1")
+///     .in_guild("123456789")
+///     .cooldown(Duration::from_secs(30));
+/// ```
+#[derive(Clone)]
+pub struct Trigger {
+    /// What the message must contain
+    pub pattern: TriggerPattern,
+    /// Whether `Exact` and `Contains` patterns are case sensitive (default: `false`)
+    pub case_sensitive: bool,
+    /// Channels the trigger is limited to (any channel if empty)
+    pub channels: Vec<String>,
+    /// Guilds the trigger is limited to (any guild or DM if empty)
+    pub guilds: Vec<String>,
+    /// Per-channel cooldown between two firings
+    pub cooldown: Option<Duration>,
+    /// Whether messages of the current user fire the trigger (default: `false`)
+    pub include_self: bool,
+    pub(crate) actions: Vec<TriggerAction>,
+}
+
+impl Trigger {
+    /// Creates a trigger from a pattern, without any response yet
+    pub fn new(pattern: TriggerPattern) -> Self {
+        Self {
+            pattern,
+            case_sensitive: false,
+            channels: Vec::new(),
+            guilds: Vec::new(),
+            cooldown: None,
+            include_self: false,
+            actions: Vec::new(),
+        }
+    }
+
+    /// Fires on messages equal to `text`
+    pub fn exact(text: impl Into<String>) -> Self {
+        Self::new(TriggerPattern::Exact(text.into()))
+    }
+
+    /// Fires on messages containing `text`
+    pub fn contains(text: impl Into<String>) -> Self {
+        Self::new(TriggerPattern::Contains(text.into()))
+    }
+
+    /// Fires on messages matching a regex, failing if it is invalid
+    pub fn regex(pattern: &str) -> Result<Self> {
+        let regex = Regex::new(pattern).map_err(|e| Error::InvalidArgument(e.to_string()))?;
+        Ok(Self::new(TriggerPattern::Regex(regex)))
+    }
+
+    /// Replies to the message
+    pub fn reply(mut self, content: impl Into<String>) -> Self {
+        self.actions.push(TriggerAction::Reply(content.into()));
+        self
+    }
+
+    /// Sends a message in the channel, without replying
+    pub fn send(mut self, content: impl Into<String>) -> Self {
+        self.actions.push(TriggerAction::Send(content.into()));
+        self
+    }
+
+    /// Reacts to the message (`"👍"`, `"name:id"`)
+    pub fn react(mut self, emoji: impl Into<String>) -> Self {
+        self.actions.push(TriggerAction::React(emoji.into()));
+        self
+    }
+
+    /// Runs an async handler, given the matched text and regex groups
+    pub fn handler<F, Fut>(mut self, handler: F) -> Self
+    where
+        F: Fn(Context, Message, Vec<String>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        let handler: TriggerHandler =
+            Arc::new(move |ctx, msg, captures| Box::pin(handler(ctx, msg, captures)));
+        self.actions.push(TriggerAction::Handler(handler));
+        self
+    }
+
+    /// Sets whether `Exact` and `Contains` patterns are case sensitive
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Limits the trigger to a channel (can be called several times)
+    pub fn in_channel(mut self, channel_id: impl Into<String>) -> Self {
+        self.channels.push(channel_id.into());
+        self
+    }
+
+    /// Limits the trigger to a guild (can be called several times)
+    pub fn in_guild(mut self, guild_id: impl Into<String>) -> Self {
+        self.guilds.push(guild_id.into());
+        self
+    }
+
+    /// Sets the per-channel cooldown between two firings
+    pub fn cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = Some(cooldown);
+        self
+    }
+
+    /// Lets messages of the current user fire the trigger (e.g. for text expansions)
+    pub fn include_self(mut self, include: bool) -> Self {
+        self.include_self = include;
+        self
+    }
+
+    /// Whether the trigger applies in the channel and guild of the message
+    pub fn in_scope(&self, msg: &Message) -> bool {
+        let channel_ok = self.channels.is_empty() || self.channels.contains(&msg.channel_id);
+        let guild_ok = self.guilds.is_empty()
+            || msg
+                .guild_id
+                .as_ref()
+                .is_some_and(|guild_id| self.guilds.contains(guild_id));
+        channel_ok && guild_ok
+    }
+
+    /// Returns the matched text followed by the regex groups, or `None` if the content
+    /// does not match
+    ///
+    /// The matched text of `Contains` patterns is the pattern text itself.
+    pub fn captures(&self, content: &str) -> Option<Vec<String>> {
+        match &self.pattern {
+            TriggerPattern::Exact(text) => {
+                let content = content.trim();
+                let equal = if self.case_sensitive {
+                    content == text
+                } else {
+                    content.to_lowercase() == text.to_lowercase()
+                };
+                equal.then(|| vec![content.to_string()])
+            }
+            TriggerPattern::Contains(text) => {
+                let found = if self.case_sensitive {
+                    content.contains(text.as_str())
+                } else {
+                    content.to_lowercase().contains(&text.to_lowercase())
+                };
+                found.then(|| vec![text.clone()])
+            }
+            TriggerPattern::Regex(regex) => {
+                let captures = regex.captures(content)?;
+                Some(
+                    captures
+                        .iter()
+                        .map(|group| group.map_or_else(String::new, |m| m.as_str().to_string()))
+                        .collect(),
+                )
+            }
+        }
+    }
+}
+
+/// Replaces `$0` to `$9` with the matched text and regex groups
+pub(crate) fn expand(template: &str, captures: &[String]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        let group = chars.peek().and_then(|next| next.to_digit(10));
+        match group {
+            Some(index) if c == '$' => {
+                chars.next();
+                if let Some(capture) = captures.get(index as usize) {
+                    out.push_str(capture);
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+impl std::fmt::Debug for Trigger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Trigger")
+            .field("pattern", &self.pattern)
+            .field("case_sensitive", &self.case_sensitive)
+            .field("channels", &self.channels)
+            .field("guilds", &self.guilds)
+            .field("cooldown", &self.cooldown)
+            .field("include_self", &self.include_self)
+            .finish_non_exhaustive()
+    }
+}
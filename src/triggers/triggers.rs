@@ -0,0 +1,160 @@
+use crate::client::{Context, EventHandler};
+use crate::error::Result;
+use crate::model::Message;
+use crate::triggers::trigger::{expand, TriggerAction};
+use crate::triggers::Trigger;
+use async_trait::async_trait;
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Cooldown checks between two removals of the expired cooldowns
+const COOLDOWN_PRUNE_INTERVAL: u64 = 256;
+
+/// Result of feeding one message to `TriggerSet::dispatch`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TriggerOutcome {
+    /// No trigger matches the message
+    Ignored,
+    /// The trigger at this index matches but is on cooldown in this channel
+    Cooldown { trigger: usize, remaining: Duration },
+    /// The trigger at this index fired
+    Fired(usize),
+}
+
+/// Auto-replies: pattern → response rules run on every message
+///
+/// Register it with `ClientBuilder::with_triggers` to process messages before
+/// `on_message_create`, or use it directly as the client's `EventHandler`.
+///
+/// Triggers are tried in registration order and only the first matching one fires.
+/// By default messages of the current user are ignored, so replies never trigger
+/// themselves.
+///
+/// # Example
+/// ```ignore
+/// use diself::triggers::{Trigger, TriggerSet};
+/// use std::time::Duration;
+///
+/// let triggers = TriggerSet::new()
+///     .trigger(Trigger::exact("ping").reply("pong"))
+///     .trigger(
+///         Trigger::contains("good morning")
+///             .react("☀️")
+///             .in_channel("123456789")
+///             .cooldown(Duration::from_secs(60)),
+///     );
+///
+/// let client = Client::builder(token, MyHandler).with_triggers(triggers).build();
+/// ```
+#[derive(Debug, Default)]
+pub struct TriggerSet {
+    triggers: Vec<Trigger>,
+    /// End of the cooldown of each (trigger index, channel ID)
+    cooldowns: Arc<DashMap<(usize, String), Instant>>,
+    cooldown_checks: Arc<AtomicU64>,
+}
+
+impl TriggerSet {
+    /// Creates an empty set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a trigger, tried after the ones registered before
+    pub fn trigger(mut self, trigger: Trigger) -> Self {
+        self.triggers.push(trigger);
+        self
+    }
+
+    /// Gets all registered triggers
+    pub fn triggers(&self) -> &[Trigger] {
+        &self.triggers
+    }
+
+    /// Runs the responses of the first trigger matching the message.
+    ///
+    /// Errors returned by the responses are propagated.
+    pub async fn dispatch(&self, ctx: &Context, msg: &Message) -> Result<TriggerOutcome> {
+        let from_self = msg.author.id == ctx.user.id;
+        let matched = self
+            .triggers
+            .iter()
+            .enumerate()
+            .find_map(|(index, trigger)| {
+                if (from_self && !trigger.include_self) || !trigger.in_scope(msg) {
+                    return None;
+                }
+                trigger
+                    .captures(&msg.content)
+                    .map(|captures| (index, trigger, captures))
+            });
+        let Some((index, trigger, captures)) = matched else {
+            return Ok(TriggerOutcome::Ignored);
+        };
+
+        if let Some(remaining) = self.check_cooldown(index, trigger, &msg.channel_id) {
+            return Ok(TriggerOutcome::Cooldown {
+                trigger: index,
+                remaining,
+            });
+        }
+
+        for action in &trigger.actions {
+            match action {
+                TriggerAction::Reply(content) => {
+                    msg.reply(&ctx.http, expand(content, &captures)).await?;
+                }
+                TriggerAction::Send(content) => {
+                    ctx.send_message(&msg.channel_id, expand(content, &captures))
+                        .await?;
+                }
+                TriggerAction::React(emoji) => msg.react(&ctx.http, emoji).await?,
+                TriggerAction::Handler(handler) => {
+                    handler(ctx.clone(), msg.clone(), captures.clone()).await?;
+                }
+            }
+        }
+        Ok(TriggerOutcome::Fired(index))
+    }
+
+    /// Returns the remaining cooldown in the channel, or records this firing
+    fn check_cooldown(
+        &self,
+        index: usize,
+        trigger: &Trigger,
+        channel_id: &str,
+    ) -> Option<Duration> {
+        let cooldown = trigger.cooldown?;
+        let now = Instant::now();
+        let checks = self.cooldown_checks.fetch_add(1, Ordering::Relaxed);
+        if checks.is_multiple_of(COOLDOWN_PRUNE_INTERVAL) {
+            self.cooldowns.retain(|_, until| *until > now);
+        }
+
+        // The entry stays locked between the check and the update, so concurrent
+        // messages cannot both fire
+        match self.cooldowns.entry((index, channel_id.to_string())) {
+            Entry::Occupied(entry) if *entry.get() > now => Some(*entry.get() - now),
+            Entry::Occupied(mut entry) => {
+                entry.insert(now + cooldown);
+                None
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(now + cooldown);
+                None
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl EventHandler for TriggerSet {
+    async fn on_message_create(&self, ctx: &Context, message: Message) {
+        if let Err(e) = self.dispatch(ctx, &message).await {
+            tracing::error!("Trigger failed: {}", e);
+        }
+    }
+}
@@ -0,0 +1,325 @@
+use crate::error::{Error, Result};
+use crate::gateway::Connection;
+use crate::voice::crypto::{rtp_header, EncryptionMode, PacketEncryptor};
+use crate::voice::source::{AudioSource, FRAME_SAMPLES};
+use serde_json::{json, Value};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::net::UdpSocket;
+use tokio::sync::{mpsc, Mutex, Notify};
+use tokio::task::JoinHandle;
+
+/// Voice gateway version
+const VOICE_GATEWAY_VERSION: u8 = 8;
+
+/// How long the voice handshake may take
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Duration of every frame sent
+const FRAME_DURATION: Duration = Duration::from_millis(20);
+
+/// Sent after the audio so the decoders of the listeners don't interpolate
+const SILENCE_FRAME: [u8; 3] = [0xF8, 0xFF, 0xFE];
+const SILENCE_FRAMES: usize = 5;
+
+/// Connection info of a voice server, from the `VOICE_STATE_UPDATE` (session ID) and
+/// `VOICE_SERVER_UPDATE` (token, endpoint) dispatches
+#[derive(Debug, Clone)]
+pub struct VoiceServerInfo {
+    /// ID of the guild, or of the private channel for calls
+    pub server_id: String,
+
+    /// ID of the current user
+    pub user_id: String,
+
+    /// Session ID of the voice state of the current user
+    pub session_id: String,
+
+    /// Voice connection token
+    pub token: String,
+
+    /// Host of the voice server (e.g. "c-cdg07-1a2b3c4d.discord.media:443")
+    pub endpoint: String,
+}
+
+/// A connection to a voice server, sending Opus audio over UDP
+///
+/// Obtained with `Context::join_voice`, or `VoiceConnection::connect` when the voice
+/// server info is already known. Dropping it closes the voice WebSocket; leaving the
+/// channel is done on the main gateway (`Context::leave_voice`).
+///
+/// # Example
+/// ```ignore
+/// use diself::voice::OggOpusSource;
+///
+/// let voice = ctx.join_voice("guild_id", "channel_id").await?;
+/// voice.play(OggOpusSource::open("song.ogg").await?).await?;
+/// ctx.leave_voice("guild_id")?;
+/// ```
+pub struct VoiceConnection {
+    server_id: String,
+    ssrc: u32,
+    mode: EncryptionMode,
+    udp: UdpSocket,
+    commands: mpsc::UnboundedSender<Value>,
+    packets: Mutex<PacketState>,
+    stop: Notify,
+    task: JoinHandle<()>,
+}
+
+struct PacketState {
+    sequence: u16,
+    timestamp: u32,
+    encryptor: PacketEncryptor,
+}
+
+impl VoiceConnection {
+    /// Connects to a voice server: identifies on the voice gateway, discovers the external
+    /// address over UDP and negotiates the encryption
+    pub async fn connect(info: VoiceServerInfo) -> Result<Self> {
+        tokio::time::timeout(HANDSHAKE_TIMEOUT, Self::handshake(info))
+            .await
+            .map_err(|_| Error::Voice("voice handshake timed out".to_string()))?
+    }
+
+    async fn handshake(info: VoiceServerInfo) -> Result<Self> {
+        let mut ws = Connection::connect(&voice_gateway_url(&info.endpoint)).await?;
+        let mut seq_ack = None;
+
+        let hello = receive_op(&mut ws, 8, &mut seq_ack).await?;
+        let heartbeat_interval = hello["heartbeat_interval"].as_f64().unwrap_or(41_250.0);
+
+        ws.send(&json!({
+            "op": 0,
+            "d": {
+                "server_id": info.server_id,
+                "user_id": info.user_id,
+                "session_id": info.session_id,
+                "token": info.token,
+                "max_dave_protocol_version": 0,
+            }
+        }))
+        .await?;
+
+        let ready = receive_op(&mut ws, 2, &mut seq_ack).await?;
+        let ssrc = ready["ssrc"].as_u64().ok_or(Error::InvalidPayload)? as u32;
+        let ip = ready["ip"].as_str().ok_or(Error::InvalidPayload)?;
+        let port = ready["port"].as_u64().ok_or(Error::InvalidPayload)? as u16;
+        let modes: Vec<String> = serde_json::from_value(ready["modes"].clone())?;
+        let mode = EncryptionMode::select(&modes)
+            .ok_or_else(|| Error::Voice(format!("no supported encryption mode in {modes:?}")))?;
+
+        let udp = UdpSocket::bind("0.0.0.0:0").await?;
+        udp.connect((ip, port)).await?;
+        let (address, external_port) = discover_ip(&udp, ssrc).await?;
+        tracing::debug!("Voice UDP external address: {}:{}", address, external_port);
+
+        ws.send(&json!({
+            "op": 1,
+            "d": {
+                "protocol": "udp",
+                "data": { "address": address, "port": external_port, "mode": mode.name() }
+            }
+        }))
+        .await?;
+
+        let description = receive_op(&mut ws, 4, &mut seq_ack).await?;
+        let secret_key: Vec<u8> = serde_json::from_value(description["secret_key"].clone())?;
+        let encryptor = PacketEncryptor::new(mode, &secret_key)?;
+
+        let (commands, rx) = mpsc::unbounded_channel();
+        let interval = Duration::from_secs_f64(heartbeat_interval / 1000.0);
+        let task = tokio::spawn(run_voice_gateway(ws, interval, rx, seq_ack));
+        tracing::info!("Connected to voice server {}", info.endpoint);
+
+        Ok(Self {
+            server_id: info.server_id,
+            ssrc,
+            mode,
+            udp,
+            commands,
+            packets: Mutex::new(PacketState {
+                sequence: rand::random(),
+                timestamp: rand::random(),
+                encryptor,
+            }),
+            stop: Notify::new(),
+            task,
+        })
+    }
+
+    /// ID of the guild (or private channel) of the connection
+    pub fn server_id(&self) -> &str {
+        &self.server_id
+    }
+
+    /// SSRC identifying the audio of the current user
+    pub fn ssrc(&self) -> u32 {
+        self.ssrc
+    }
+
+    /// Encryption mode negotiated with the voice server
+    pub fn encryption_mode(&self) -> EncryptionMode {
+        self.mode
+    }
+
+    /// Whether the voice WebSocket is still open
+    pub fn is_connected(&self) -> bool {
+        !self.task.is_finished()
+    }
+
+    /// Plays a source until it ends or `stop` is called
+    ///
+    /// Frames are sent every 20ms. A second `play` waits for the current one to end.
+    pub async fn play(&self, mut source: impl AudioSource) -> Result<()> {
+        let mut packets = self.packets.lock().await;
+        let stopped = self.stop.notified();
+        tokio::pin!(stopped);
+        stopped.as_mut().enable();
+
+        self.set_speaking(true)?;
+        let mut ticks = tokio::time::interval(FRAME_DURATION);
+        let result = loop {
+            tokio::select! {
+                _ = &mut stopped => break Ok(()),
+                _ = ticks.tick() => {}
+            }
+            let frame = match source.next_frame() {
+                Some(Ok(frame)) => frame,
+                Some(Err(err)) => break Err(err),
+                None => break Ok(()),
+            };
+            if let Err(err) = self.send_frame(&mut packets, &frame).await {
+                break Err(err);
+            }
+        };
+
+        for _ in 0..SILENCE_FRAMES {
+            ticks.tick().await;
+            self.send_frame(&mut packets, &SILENCE_FRAME).await?;
+        }
+        self.set_speaking(false)?;
+        result
+    }
+
+    /// Stops the source being played
+    pub fn stop(&self) {
+        self.stop.notify_waiters();
+    }
+
+    /// Closes the voice WebSocket (same as dropping the connection)
+    pub fn disconnect(self) {}
+
+    /// Updates the speaking state (op 5), required before sending audio
+    fn set_speaking(&self, speaking: bool) -> Result<()> {
+        self.commands
+            .send(json!({
+                "op": 5,
+                "d": { "speaking": u8::from(speaking), "delay": 0, "ssrc": self.ssrc }
+            }))
+            .map_err(|_| Error::Voice("voice connection closed".to_string()))
+    }
+
+    async fn send_frame(&self, packets: &mut PacketState, opus: &[u8]) -> Result<()> {
+        let header = rtp_header(packets.sequence, packets.timestamp, self.ssrc);
+        let packet = packets.encryptor.encrypt(&header, opus)?;
+        self.udp.send(&packet).await?;
+        packets.sequence = packets.sequence.wrapping_add(1);
+        packets.timestamp = packets.timestamp.wrapping_add(FRAME_SAMPLES as u32);
+        Ok(())
+    }
+}
+
+/// Builds the voice gateway URL of an endpoint (`ws://` endpoints are kept, for tests)
+fn voice_gateway_url(endpoint: &str) -> String {
+    let endpoint = endpoint.trim_end_matches('/');
+    if endpoint.starts_with("ws://") || endpoint.starts_with("wss://") {
+        format!("{}/?v={}", endpoint, VOICE_GATEWAY_VERSION)
+    } else {
+        format!("wss://{}/?v={}", endpoint, VOICE_GATEWAY_VERSION)
+    }
+}
+
+/// Waits for a payload with the given opcode, skipping the others
+async fn receive_op(ws: &mut Connection, op: u64, seq_ack: &mut Option<u64>) -> Result<Value> {
+    loop {
+        let Some(mut payload) = ws.receive().await? else {
+            return Err(Error::Voice("voice gateway closed".to_string()));
+        };
+        if let Some(seq) = payload["seq"].as_u64() {
+            *seq_ack = Some(seq);
+        }
+        if payload["op"].as_u64() == Some(op) {
+            return Ok(payload["d"].take());
+        }
+    }
+}
+
+/// Finds the external address and port of the UDP socket. SEE: <https://docs.discord.food/topics/voice-connections#ip-discovery>
+async fn discover_ip(udp: &UdpSocket, ssrc: u32) -> Result<(String, u16)> {
+    let mut request = [0u8; 74];
+    request[0..2].copy_from_slice(&1u16.to_be_bytes());
+    request[2..4].copy_from_slice(&70u16.to_be_bytes());
+    request[4..8].copy_from_slice(&ssrc.to_be_bytes());
+    udp.send(&request).await?;
+
+    let mut response = [0u8; 74];
+    let len = udp.recv(&mut response).await?;
+    if len < 74 || response[0..2] != 2u16.to_be_bytes() {
+        return Err(Error::Voice("invalid IP discovery response".to_string()));
+    }
+    let address = &response[8..72];
+    let address_len = address
+        .iter()
+        .position(|&b| b == 0)
+        .unwrap_or(address.len());
+    let address = String::from_utf8_lossy(&address[..address_len]).into_owned();
+    let port = u16::from_be_bytes([response[72], response[73]]);
+    Ok((address, port))
+}
+
+/// Heartbeats the voice gateway and sends the queued commands until the connection is dropped
+async fn run_voice_gateway(
+    mut ws: Connection,
+    interval: Duration,
+    mut commands: mpsc::UnboundedReceiver<Value>,
+    mut seq_ack: Option<u64>,
+) {
+    let mut heartbeat = tokio::time::interval(interval);
+    loop {
+        tokio::select! {
+            _ = heartbeat.tick() => {
+                let nonce = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|elapsed| elapsed.as_millis() as u64)
+                    .unwrap_or_default();
+                let payload = json!({ "op": 3, "d": { "t": nonce, "seq_ack": seq_ack } });
+                if let Err(err) = ws.send(&payload).await {
+                    tracing::warn!("Voice heartbeat failed: {}", err);
+                    break;
+                }
+            }
+            command = commands.recv() => {
+                let Some(command) = command else {
+                    let _ = ws.close().await;
+                    break;
+                };
+                if let Err(err) = ws.send(&command).await {
+                    tracing::warn!("Voice gateway send failed: {}", err);
+                    break;
+                }
+            }
+            payload = ws.receive() => match payload {
+                Ok(Some(payload)) => {
+                    if let Some(seq) = payload["seq"].as_u64() {
+                        seq_ack = Some(seq);
+                    }
+                }
+                Ok(None) => break,
+                Err(err) => {
+                    tracing::warn!("Voice gateway closed: {}", err);
+                    break;
+                }
+            },
+        }
+    }
+}
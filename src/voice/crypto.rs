@@ -0,0 +1,110 @@
+use crate::error::{Error, Result};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::XChaCha20Poly1305;
+use crypto_secretbox::XSalsa20Poly1305;
+
+/// Encryption of the voice packets, negotiated with the voice server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EncryptionMode {
+    /// `aead_xchacha20_poly1305_rtpsize`: RTP header as associated data, 32-bit nonce
+    /// appended to the packet
+    XChaCha20Poly1305RtpSize,
+    /// `xsalsa20_poly1305`: the RTP header is used as the nonce
+    XSalsa20Poly1305,
+}
+
+impl EncryptionMode {
+    /// Supported modes, most preferred first
+    pub const SUPPORTED: [EncryptionMode; 2] = [
+        EncryptionMode::XChaCha20Poly1305RtpSize,
+        EncryptionMode::XSalsa20Poly1305,
+    ];
+
+    /// Name of the mode in the voice gateway payloads
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::XChaCha20Poly1305RtpSize => "aead_xchacha20_poly1305_rtpsize",
+            Self::XSalsa20Poly1305 => "xsalsa20_poly1305",
+        }
+    }
+
+    /// Picks the preferred supported mode among the modes offered by a voice server
+    pub fn select<S: AsRef<str>>(modes: &[S]) -> Option<Self> {
+        Self::SUPPORTED
+            .into_iter()
+            .find(|mode| modes.iter().any(|offered| offered.as_ref() == mode.name()))
+    }
+}
+
+/// Length of an RTP header without extension
+pub(crate) const RTP_HEADER_LEN: usize = 12;
+
+/// Builds the RTP header of an Opus packet (version 2, payload type 120)
+pub(crate) fn rtp_header(sequence: u16, timestamp: u32, ssrc: u32) -> [u8; RTP_HEADER_LEN] {
+    let mut header = [0u8; RTP_HEADER_LEN];
+    header[0] = 0x80;
+    header[1] = 0x78;
+    header[2..4].copy_from_slice(&sequence.to_be_bytes());
+    header[4..8].copy_from_slice(&timestamp.to_be_bytes());
+    header[8..12].copy_from_slice(&ssrc.to_be_bytes());
+    header
+}
+
+/// Encrypts the Opus packets of a voice connection with the session key
+pub(crate) struct PacketEncryptor {
+    cipher: Cipher,
+    nonce: u32,
+}
+
+enum Cipher {
+    XChaCha20(XChaCha20Poly1305),
+    XSalsa20(XSalsa20Poly1305),
+}
+
+impl PacketEncryptor {
+    pub(crate) fn new(mode: EncryptionMode, secret_key: &[u8]) -> Result<Self> {
+        let invalid_key = |_| Error::Voice("invalid secret key".to_string());
+        let cipher = match mode {
+            EncryptionMode::XChaCha20Poly1305RtpSize => Cipher::XChaCha20(
+                XChaCha20Poly1305::new_from_slice(secret_key).map_err(invalid_key)?,
+            ),
+            EncryptionMode::XSalsa20Poly1305 => {
+                Cipher::XSalsa20(XSalsa20Poly1305::new_from_slice(secret_key).map_err(invalid_key)?)
+            }
+        };
+        Ok(Self { cipher, nonce: 0 })
+    }
+
+    /// Returns the UDP packet carrying `opus` after `header`
+    pub(crate) fn encrypt(
+        &mut self,
+        header: &[u8; RTP_HEADER_LEN],
+        opus: &[u8],
+    ) -> Result<Vec<u8>> {
+        let mut packet = header.to_vec();
+        let encrypted = match &self.cipher {
+            Cipher::XChaCha20(cipher) => {
+                self.nonce = self.nonce.wrapping_add(1);
+                let mut nonce = [0u8; 24];
+                nonce[..4].copy_from_slice(&self.nonce.to_be_bytes());
+                let payload = Payload {
+                    msg: opus,
+                    aad: header,
+                };
+                let mut encrypted = cipher.encrypt(&nonce.into(), payload);
+                if let Ok(encrypted) = &mut encrypted {
+                    encrypted.extend_from_slice(&self.nonce.to_be_bytes());
+                }
+                encrypted
+            }
+            Cipher::XSalsa20(cipher) => {
+                let mut nonce = [0u8; 24];
+                nonce[..RTP_HEADER_LEN].copy_from_slice(header);
+                cipher.encrypt(&nonce.into(), opus)
+            }
+        };
+        let encrypted = encrypted.map_err(|_| Error::Voice("packet encryption failed".into()))?;
+        packet.extend_from_slice(&encrypted);
+        Ok(packet)
+    }
+}
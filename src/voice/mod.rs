@@ -0,0 +1,7 @@
+mod connection;
+mod crypto;
+mod source;
+
+pub use connection::{VoiceConnection, VoiceServerInfo};
+pub use crypto::EncryptionMode;
+pub use source::{AudioSource, OggOpusSource, OpusEncoder, OpusFrames, PcmSource, FRAME_SAMPLES};
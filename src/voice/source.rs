@@ -0,0 +1,183 @@
+use crate::error::{Error, Result};
+use std::collections::VecDeque;
+use std::path::Path;
+
+/// Samples per channel in 20ms of 48kHz audio, the duration of every frame sent
+pub const FRAME_SAMPLES: usize = 960;
+
+/// Audio played by `VoiceConnection::play`
+///
+/// Every call returns the next Opus packet, holding 20ms of 48kHz stereo audio, or
+/// `None` once the source is exhausted.
+pub trait AudioSource: Send {
+    /// Returns the next Opus packet
+    fn next_frame(&mut self) -> Option<Result<Vec<u8>>>;
+}
+
+/// Opus packets encoded beforehand, played in order
+pub struct OpusFrames {
+    frames: std::vec::IntoIter<Vec<u8>>,
+}
+
+impl OpusFrames {
+    /// Plays the given Opus packets (20ms each)
+    pub fn new(frames: impl IntoIterator<Item = Vec<u8>>) -> Self {
+        let frames: Vec<Vec<u8>> = frames.into_iter().collect();
+        Self {
+            frames: frames.into_iter(),
+        }
+    }
+}
+
+impl AudioSource for OpusFrames {
+    fn next_frame(&mut self) -> Option<Result<Vec<u8>>> {
+        self.frames.next().map(Ok)
+    }
+}
+
+/// The Opus packets of an Ogg Opus file
+///
+/// The file must use 20ms frames, e.g. converted with
+/// `ffmpeg -i song.mp3 -c:a libopus -ar 48000 -ac 2 -frame_duration 20 song.ogg`.
+pub struct OggOpusSource {
+    data: Vec<u8>,
+    offset: usize,
+    packets: VecDeque<Vec<u8>>,
+    partial: Vec<u8>,
+}
+
+impl OggOpusSource {
+    /// Reads the packets of an Ogg Opus file loaded in memory
+    pub fn new(data: impl Into<Vec<u8>>) -> Self {
+        Self {
+            data: data.into(),
+            offset: 0,
+            packets: VecDeque::new(),
+            partial: Vec::new(),
+        }
+    }
+
+    /// Loads an Ogg Opus file
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self::new(tokio::fs::read(path).await?))
+    }
+
+    /// Splits the next Ogg page into packets, returning `false` at the end of the file
+    fn read_page(&mut self) -> Result<bool> {
+        let page = &self.data[self.offset..];
+        if page.is_empty() {
+            return Ok(false);
+        }
+        if page.len() < 27 || &page[..4] != b"OggS" {
+            return Err(Error::Voice(format!(
+                "invalid Ogg page at byte {}",
+                self.offset
+            )));
+        }
+        let segment_count = page[26] as usize;
+        let segments = page
+            .get(27..27 + segment_count)
+            .ok_or_else(|| Error::Voice("truncated Ogg page".to_string()))?;
+        let body_len: usize = segments.iter().map(|&len| len as usize).sum();
+        let mut body_start = 27 + segment_count;
+        if page.len() < body_start + body_len {
+            return Err(Error::Voice("truncated Ogg page".to_string()));
+        }
+
+        for &len in segments {
+            let len = len as usize;
+            self.partial
+                .extend_from_slice(&page[body_start..body_start + len]);
+            body_start += len;
+            // A segment shorter than 255 bytes ends the packet
+            if len < 255 {
+                let packet = std::mem::take(&mut self.partial);
+                if !packet.starts_with(b"OpusHead") && !packet.starts_with(b"OpusTags") {
+                    self.packets.push_back(packet);
+                }
+            }
+        }
+        self.offset += body_start;
+        Ok(true)
+    }
+}
+
+impl AudioSource for OggOpusSource {
+    fn next_frame(&mut self) -> Option<Result<Vec<u8>>> {
+        loop {
+            if let Some(packet) = self.packets.pop_front() {
+                return Some(Ok(packet));
+            }
+            match self.read_page() {
+                Ok(true) => continue,
+                Ok(false) => return None,
+                Err(err) => {
+                    self.offset = self.data.len();
+                    return Some(Err(err));
+                }
+            }
+        }
+    }
+}
+
+/// Encodes 20ms of PCM audio into an Opus packet
+///
+/// Implemented on top of an Opus binding, e.g. `audiopus`:
+/// ```ignore
+/// struct Encoder(audiopus::coder::Encoder);
+///
+/// impl OpusEncoder for Encoder {
+///     fn encode(&mut self, pcm: &[i16]) -> diself::Result<Vec<u8>> {
+///         let mut out = vec![0; 4000];
+///         let len = self.0.encode(pcm, &mut out).map_err(|e| diself::Error::Voice(e.to_string()))?;
+///         out.truncate(len);
+///         Ok(out)
+///     }
+/// }
+/// ```
+pub trait OpusEncoder: Send {
+    /// Encodes `FRAME_SAMPLES` interleaved stereo samples at 48kHz
+    fn encode(&mut self, pcm: &[i16]) -> Result<Vec<u8>>;
+}
+
+/// Raw PCM audio (48kHz, stereo, interleaved 16-bit samples) encoded frame by frame
+pub struct PcmSource<E> {
+    samples: Vec<i16>,
+    offset: usize,
+    encoder: E,
+}
+
+impl<E: OpusEncoder> PcmSource<E> {
+    /// Plays interleaved stereo samples
+    pub fn new(samples: Vec<i16>, encoder: E) -> Self {
+        Self {
+            samples,
+            offset: 0,
+            encoder,
+        }
+    }
+
+    /// Plays little-endian 16-bit samples (`ffmpeg -f s16le -ar 48000 -ac 2` output)
+    pub fn from_le_bytes(bytes: &[u8], encoder: E) -> Self {
+        let samples = bytes
+            .chunks_exact(2)
+            .map(|sample| i16::from_le_bytes([sample[0], sample[1]]))
+            .collect();
+        Self::new(samples, encoder)
+    }
+}
+
+impl<E: OpusEncoder> AudioSource for PcmSource<E> {
+    fn next_frame(&mut self) -> Option<Result<Vec<u8>>> {
+        if self.offset >= self.samples.len() {
+            return None;
+        }
+        let frame_len = FRAME_SAMPLES * 2;
+        let end = (self.offset + frame_len).min(self.samples.len());
+        let mut frame = self.samples[self.offset..end].to_vec();
+        // The last frame is padded with silence
+        frame.resize(frame_len, 0);
+        self.offset = end;
+        Some(self.encoder.encode(&frame))
+    }
+}
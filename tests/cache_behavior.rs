@@ -1,5 +1,5 @@
-use diself::{Cache, CacheConfig};
-use diself::model::User;
+use diself::{Cache, CacheConfig, CacheLimits, CachePolicy, CacheSnapshot};
+use diself::model::{OnlineStatus, User};
 use serde_json::json;
 
 fn sample_user(id: &str) -> User {
@@ -30,6 +30,14 @@ fn cache_respects_disabled_user_cache() {
         cache_channels: true,
         cache_guilds: true,
         cache_relationships: true,
+        cache_presences: true,
+        cache_voice_states: true,
+        cache_members: true,
+        cache_roles: true,
+        cache_emojis: true,
+        cache_threads: true,
+        cache_messages: true,
+        limits: CacheLimits::default(),
     });
 
     cache.cache_user(sample_user("999"));
@@ -124,6 +132,44 @@ fn cache_updates_user_from_partial_presence_event() {
     );
 }
 
+#[test]
+fn cache_indexes_dm_channels_by_recipient() {
+    let cache = Cache::new();
+    cache.initialize(json!({
+        "user": { "id": "555", "username": "ready_user", "discriminator": "1234" },
+        "users": [],
+        "guilds": [],
+        "relationships": [],
+        "private_channels": [
+            { "id": "dm1", "type": 1, "recipient_ids": ["1"] },
+            { "id": "group1", "type": 3, "recipient_ids": ["1", "2"] }
+        ]
+    }));
+
+    assert_eq!(
+        cache.dm_channel_with("1").map(|c| c.id),
+        Some("dm1".to_string())
+    );
+    assert!(cache.dm_channel_with("2").is_none());
+    assert_eq!(cache.private_channels().len(), 2);
+
+    cache.update_from_dispatch(
+        "CHANNEL_CREATE",
+        &json!({
+            "id": "dm2",
+            "type": 1,
+            "recipients": [{ "id": "2", "username": "user_2", "discriminator": "0" }]
+        }),
+    );
+    assert_eq!(
+        cache.dm_channel_with("2").map(|c| c.id),
+        Some("dm2".to_string())
+    );
+
+    cache.update_from_dispatch("CHANNEL_DELETE", &json!({ "id": "dm1", "type": 1 }));
+    assert!(cache.dm_channel_with("1").is_none());
+}
+
 #[test]
 fn cache_updates_channel_lifecycle_from_dispatch() {
     let cache = Cache::new();
@@ -154,6 +200,21 @@ fn cache_updates_channel_lifecycle_from_dispatch() {
         Some("general-2")
     );
 
+    cache.update_from_dispatch(
+        "CHANNEL_PINS_UPDATE",
+        &json!({
+            "channel_id": "c1",
+            "last_pin_timestamp": "2024-01-01T00:00:00+00:00"
+        }),
+    );
+    assert_eq!(
+        cache
+            .channel("c1")
+            .and_then(|channel| channel.last_pin_timestamp)
+            .as_deref(),
+        Some("2024-01-01T00:00:00+00:00")
+    );
+
     cache.update_from_dispatch("CHANNEL_DELETE", &json!({ "id": "c1" }));
     assert!(cache.channel("c1").is_none());
 }
@@ -188,6 +249,20 @@ fn cache_updates_guild_and_relationship_from_dispatch() {
     );
     assert!(cache.relationship("u999").is_some());
 
+    cache.update_from_dispatch(
+        "RELATIONSHIP_UPDATE",
+        &json!({
+            "id": "u999",
+            "type": 1,
+            "nickname": "bestie"
+        }),
+    );
+    let relationship = cache
+        .relationship("u999")
+        .expect("relationship should exist");
+    assert!(relationship.is_friend());
+    assert_eq!(relationship.nickname.as_deref(), Some("bestie"));
+
     cache.update_from_dispatch("RELATIONSHIP_REMOVE", &json!({ "id": "u999" }));
     assert!(cache.relationship("u999").is_none());
 
@@ -276,6 +351,89 @@ fn cache_updates_merged_members_from_ready_supplemental() {
     assert_eq!(member.roles, vec!["r1".to_string()]);
 }
 
+#[test]
+fn ready_supplemental_typed_model_feeds_presences_members_and_voice_states() {
+    use diself::model::ReadySupplemental;
+
+    let data = json!({
+        "guilds": [
+            {
+                "id": "g1",
+                "voice_states": [
+                    { "user_id": "u1", "channel_id": "v1", "session_id": "s" },
+                    { "channel_id": "v1" }
+                ]
+            }
+        ],
+        "merged_presences": {
+            "friends": [
+                { "user_id": "f1", "status": "idle" },
+                { "status": "online" }
+            ],
+            "guilds": [[{ "user_id": "u1", "status": "dnd", "activities": [] }]]
+        },
+        "merged_members": [[{ "user_id": "u1", "roles": ["r1"] }]],
+        "lazy_private_channels": [{ "id": "dm1", "type": 1 }]
+    });
+
+    let ready: ReadySupplemental = serde_json::from_value(data.clone()).unwrap();
+    assert_eq!(ready.merged_presences.friends.len(), 1);
+    assert_eq!(ready.presences().count(), 2);
+    assert_eq!(ready.guilds[0].voice_states.len(), 1);
+    assert_eq!(ready.lazy_private_channels[0].id, "dm1");
+    let (guild_id, members) = ready.guild_members().next().unwrap();
+    assert_eq!((guild_id, members[0].user_id.as_str()), ("g1", "u1"));
+    let (_, presences) = ready.guild_presences().next().unwrap();
+    assert_eq!(presences[0].presence.status, "dnd");
+
+    let cache = Cache::new();
+    cache.update_from_dispatch("READY_SUPPLEMENTAL", &data);
+    assert_eq!(cache.presence("f1").unwrap().status, "idle");
+    assert_eq!(cache.presence("u1").unwrap().status, "dnd");
+    assert_eq!(cache.guild_member("g1", "u1").unwrap().roles, vec!["r1"]);
+    let voice_state = cache.voice_state(Some("g1"), "u1").unwrap();
+    assert_eq!(voice_state.channel_id.as_deref(), Some("v1"));
+}
+
+#[test]
+fn ready_supplemental_keeps_guild_indexes_when_a_guild_is_malformed() {
+    use diself::model::ReadySupplemental;
+
+    let member = |user_id: &str| json!({ "user_id": user_id, "roles": [] });
+    let presence = |user_id: &str| json!({ "user_id": user_id, "status": "online" });
+    let data = json!({
+        "guilds": [
+            { "id": "g1" },
+            { "id": "g2", "embedded_activities": "malformed" },
+            { "voice_states": [] },
+            { "id": "g4" }
+        ],
+        "merged_presences": {
+            "friends": [],
+            "guilds": [[presence("u1")], [presence("u2")], [presence("u3")], [presence("u4")]]
+        },
+        "merged_members": [[member("u1")], [member("u2")], [member("u3")], [member("u4")]]
+    });
+
+    let ready: ReadySupplemental = serde_json::from_value(data.clone()).unwrap();
+    assert_eq!(ready.guilds.len(), 4);
+    let members: Vec<_> = ready
+        .guild_members()
+        .map(|(guild_id, members)| (guild_id, members[0].user_id.as_str()))
+        .collect();
+    assert_eq!(members, [("g1", "u1"), ("g2", "u2"), ("g4", "u4")]);
+    let presences: Vec<_> = ready
+        .guild_presences()
+        .map(|(guild_id, presences)| (guild_id, presences[0].user_id.as_str()))
+        .collect();
+    assert_eq!(presences, [("g1", "u1"), ("g2", "u2"), ("g4", "u4")]);
+
+    let cache = Cache::new();
+    cache.update_from_dispatch("READY_SUPPLEMENTAL", &data);
+    assert!(cache.guild_member("g4", "u4").is_some());
+    assert!(cache.guild_member("g4", "u3").is_none());
+}
+
 #[test]
 fn cache_updates_channel_from_passive_update() {
     let cache = Cache::new();
@@ -314,3 +472,597 @@ fn cache_updates_channel_from_passive_update() {
         Some("m77")
     );
 }
+
+#[test]
+fn cache_tracks_unread_state_from_messages_and_acks() {
+    let cache = Cache::new();
+    cache.set_current_user(sample_user("555"));
+
+    let incoming = json!({
+        "id": "110",
+        "channel_id": "chan_1",
+        "author": { "id": "777", "username": "other", "discriminator": "0001" },
+        "content": "hey <@555>",
+        "timestamp": "2024-01-01T00:00:00+00:00",
+        "edited_timestamp": null,
+        "mentions": [{ "id": "555", "username": "me", "discriminator": "0001" }],
+        "type": 0
+    });
+    cache.update_from_dispatch("MESSAGE_CREATE", &incoming);
+
+    assert!(cache.is_unread("chan_1"));
+    assert_eq!(cache.mention_count("chan_1"), 1);
+    assert_eq!(cache.unread_read_states().len(), 1);
+
+    cache.update_from_dispatch(
+        "MESSAGE_ACK",
+        &json!({ "channel_id": "chan_1", "message_id": "110", "version": 3 }),
+    );
+
+    let read_state = cache.read_state("chan_1").expect("read state should exist");
+    assert_eq!(read_state.last_acked_id.as_deref(), Some("110"));
+    assert!(!cache.is_unread("chan_1"));
+    assert_eq!(cache.mention_count("chan_1"), 0);
+}
+
+#[test]
+fn cache_tracks_group_dm_recipients() {
+    let cache = Cache::new();
+    cache.update_from_dispatch(
+        "CHANNEL_CREATE",
+        &json!({ "id": "50", "type": 3, "recipients": [sample_user("1")] }),
+    );
+
+    cache.update_from_dispatch(
+        "CHANNEL_RECIPIENT_ADD",
+        &json!({ "channel_id": "50", "user": sample_user("2") }),
+    );
+    let recipients = cache.channel("50").and_then(|c| c.recipients).unwrap();
+    assert_eq!(recipients.len(), 2);
+    assert!(cache.user("2").is_some());
+
+    cache.update_from_dispatch(
+        "CHANNEL_RECIPIENT_REMOVE",
+        &json!({ "channel_id": "50", "user": sample_user("1") }),
+    );
+    let recipients = cache.channel("50").and_then(|c| c.recipients).unwrap();
+    assert_eq!(
+        recipients.iter().map(|u| u.id.as_str()).collect::<Vec<_>>(),
+        ["2"]
+    );
+}
+
+#[test]
+fn cache_stores_typed_presences_for_uncached_users() {
+    let cache = Cache::new();
+
+    cache.update_from_dispatch(
+        "PRESENCE_UPDATE",
+        &json!({
+            "user": { "id": "77" },
+            "status": "dnd",
+            "client_status": { "mobile": "dnd" },
+            "activities": [
+                {
+                    "name": "Spotify",
+                    "type": 2,
+                    "sync_id": "4uLU6hMCjMI75M1A2tKUQC",
+                    "details": "Never Gonna Give You Up",
+                    "state": "Rick Astley",
+                    "party": { "id": "spotify:77" },
+                    "assets": {
+                        "large_image": "spotify:ab67616d0000b273",
+                        "large_text": "Whenever You Need Somebody"
+                    },
+                    "timestamps": { "start": "1700000000000", "end": 1700000213000u64 }
+                },
+                { "name": "Unparseable", "type": 99 }
+            ]
+        }),
+    );
+
+    assert!(cache.user("77").is_none());
+    let presence = cache.presence("77").expect("presence should be cached");
+    assert_eq!(presence.online_status(), OnlineStatus::DoNotDisturb);
+    assert_eq!(presence.activities.len(), 1);
+
+    let track = presence.spotify().expect("spotify activity");
+    assert_eq!(track.title, "Never Gonna Give You Up");
+    assert_eq!(track.artists, ["Rick Astley"]);
+    assert_eq!(track.start, Some(1_700_000_000_000));
+    assert_eq!(
+        track.album_art_url.as_deref(),
+        Some("https://i.scdn.co/image/ab67616d0000b273")
+    );
+}
+
+#[test]
+fn cache_tracks_voice_states_per_guild_and_user() {
+    let cache = Cache::new();
+    cache.update_from_dispatch(
+        "GUILD_CREATE",
+        &json!({
+            "id": "g1",
+            "voice_states": [
+                { "channel_id": "v1", "user_id": "1", "session_id": "s1" },
+                { "channel_id": "v2", "user_id": "2", "session_id": "s2" }
+            ]
+        }),
+    );
+    assert_eq!(cache.guild_voice_states("g1").len(), 2);
+    assert_eq!(
+        cache.voice_state(Some("g1"), "1").and_then(|s| s.guild_id),
+        Some("g1".to_string())
+    );
+
+    cache.update_from_dispatch(
+        "VOICE_STATE_UPDATE",
+        &json!({
+            "guild_id": "g1",
+            "channel_id": "v2",
+            "user_id": "1",
+            "session_id": "s1",
+            "self_mute": true
+        }),
+    );
+    let in_v2 = cache.voice_states_in_channel("v2");
+    assert_eq!(in_v2.len(), 2);
+    assert!(cache.voice_state(Some("g1"), "1").unwrap().self_mute);
+
+    cache.update_from_dispatch(
+        "VOICE_STATE_UPDATE",
+        &json!({ "guild_id": "g1", "channel_id": null, "user_id": "2", "session_id": "s2" }),
+    );
+    assert!(cache.voice_state(Some("g1"), "2").is_none());
+    assert_eq!(cache.voice_state_count(), 1);
+}
+
+#[test]
+fn cache_merges_partial_guild_members() {
+    let cache = Cache::new();
+    cache.update_from_dispatch(
+        "GUILD_MEMBER_ADD",
+        &json!({
+            "guild_id": "g1",
+            "user": { "id": "1", "username": "alice", "discriminator": "0", "global_name": "Alice" },
+            "roles": ["r1"],
+            "joined_at": "2026-01-01T00:00:00.000Z",
+            "flags": 0
+        }),
+    );
+    assert_eq!(
+        cache.member_display_name("g1", "1").as_deref(),
+        Some("Alice")
+    );
+
+    // Message members carry no `user` and only some fields
+    cache.update_from_dispatch(
+        "MESSAGE_CREATE",
+        &json!({
+            "id": "m1",
+            "channel_id": "c1",
+            "guild_id": "g1",
+            "author": { "id": "1", "username": "alice", "discriminator": "0" },
+            "member": { "nick": "Ally", "roles": ["r1", "r2"] },
+            "content": "hi",
+            "timestamp": "2026-02-22T00:00:00.000Z",
+            "type": 0
+        }),
+    );
+    let member = cache.member("g1", "1").expect("member should be cached");
+    assert_eq!(member.nick.as_deref(), Some("Ally"));
+    assert_eq!(
+        member.joined_at.as_deref(),
+        Some("2026-01-01T00:00:00.000Z")
+    );
+    assert_eq!(cache.members_with_role("g1", "r2").len(), 1);
+
+    cache.update_from_dispatch(
+        "GUILD_MEMBERS_CHUNK",
+        &json!({
+            "guild_id": "g1",
+            "members": [
+                { "user": { "id": "2", "username": "bob", "discriminator": "0" }, "roles": [] }
+            ]
+        }),
+    );
+    assert_eq!(cache.members_in_guild("g1").len(), 2);
+
+    cache.update_from_dispatch(
+        "GUILD_MEMBER_REMOVE",
+        &json!({ "guild_id": "g1", "user": { "id": "1", "username": "alice", "discriminator": "0" } }),
+    );
+    assert!(cache.member("g1", "1").is_none());
+    assert_eq!(cache.member_count(), 1);
+}
+
+#[test]
+fn cache_tracks_guild_roles_and_emojis() {
+    let cache = Cache::new();
+    cache.set_current_user(sample_user("1"));
+    cache.update_from_dispatch(
+        "GUILD_CREATE",
+        &json!({
+            "id": "g1",
+            "name": "Guild One",
+            "roles": [{ "id": "r1", "name": "@everyone", "position": 0 }],
+            "emojis": [
+                { "id": "e1", "name": "wave", "available": true },
+                { "id": "e2", "name": "dance", "animated": true, "available": true }
+            ],
+            "members": [{
+                "user": { "id": "1", "username": "me", "discriminator": "0" },
+                "roles": ["r2"],
+                "joined_at": "2024-01-01T00:00:00+00:00",
+                "flags": 0
+            }]
+        }),
+    );
+    assert_eq!(
+        cache.role("r1").map(|r| r.name),
+        Some("@everyone".to_string())
+    );
+    assert_eq!(cache.guild_emojis("g1").len(), 2);
+
+    cache.update_from_dispatch(
+        "GUILD_ROLE_CREATE",
+        &json!({ "guild_id": "g1", "role": { "id": "r2", "name": "mods", "position": 1 } }),
+    );
+    let roles: Vec<String> = cache.guild_roles("g1").into_iter().map(|r| r.id).collect();
+    assert_eq!(roles, vec!["r1", "r2"]);
+    assert_eq!(cache.member_roles("g1", "1").len(), 1);
+    assert_eq!(cache.guild("g1").map(|g| g.roles.len()), Some(2));
+
+    cache.update_from_dispatch(
+        "GUILD_ROLE_DELETE",
+        &json!({ "guild_id": "g1", "role_id": "r1" }),
+    );
+    assert!(cache.role("r1").is_none());
+
+    // Without Nitro, only static emojis of the current guild are usable
+    let usable: Vec<_> = cache
+        .usable_emojis(Some("g1"))
+        .into_iter()
+        .filter_map(|e| e.id)
+        .collect();
+    assert_eq!(usable, vec!["e1"]);
+    assert!(cache.usable_emojis(None).is_empty());
+
+    cache.update_from_dispatch(
+        "GUILD_EMOJIS_UPDATE",
+        &json!({
+            "guild_id": "g1",
+            "emojis": [{ "id": "e3", "name": "staff", "roles": ["r3"], "available": true }]
+        }),
+    );
+    assert!(cache.emoji("e1").is_none());
+    assert!(cache.emoji("e3").is_some());
+    assert!(cache.usable_emojis(Some("g1")).is_empty());
+
+    cache.update_from_dispatch("GUILD_DELETE", &json!({ "id": "g1" }));
+    assert_eq!((cache.role_count(), cache.emoji_count()), (0, 0));
+}
+
+#[test]
+fn cache_syncs_guild_channel_lists() {
+    let cache = Cache::new();
+    cache.update_from_dispatch(
+        "GUILD_CREATE",
+        &json!({
+            "id": "g1",
+            "name": "Guild One",
+            "channels": [{ "id": "c1", "type": 0, "guild_id": "g1", "name": "general" }]
+        }),
+    );
+
+    cache.update_from_dispatch(
+        "CHANNEL_CREATE",
+        &json!({ "id": "c2", "type": 2, "guild_id": "g1", "name": "voice" }),
+    );
+    cache.update_from_dispatch(
+        "CHANNEL_UPDATE",
+        &json!({ "id": "c1", "type": 0, "guild_id": "g1", "name": "chat" }),
+    );
+    let guild = cache.guild("g1").unwrap();
+    assert_eq!(guild.channels.len(), 2);
+    assert!(guild
+        .channels
+        .iter()
+        .any(|channel| channel.name.as_deref() == Some("chat")));
+
+    cache.update_from_dispatch(
+        "CHANNEL_DELETE",
+        &json!({ "id": "c2", "type": 2, "guild_id": "g1", "name": "voice" }),
+    );
+    assert_eq!(cache.guild("g1").unwrap().channels.len(), 1);
+    assert!(cache.channel("c2").is_none());
+}
+
+#[test]
+fn cache_tracks_active_threads_per_parent_channel() {
+    let cache = Cache::new();
+    cache.set_current_user(sample_user("1"));
+    let thread = |id: &str, parent_id: &str, archived: bool| {
+        json!({
+            "id": id,
+            "type": 11,
+            "guild_id": "g1",
+            "parent_id": parent_id,
+            "thread_metadata": {
+                "archived": archived,
+                "archive_timestamp": "2024-01-01T00:00:00+00:00",
+                "locked": false
+            }
+        })
+    };
+
+    cache.update_from_dispatch("THREAD_CREATE", &thread("t1", "c1", false));
+    cache.update_from_dispatch("THREAD_CREATE", &thread("t2", "c2", false));
+    assert_eq!(cache.active_threads("c1").len(), 1);
+    assert_eq!(cache.guild_threads("g1").len(), 2);
+    assert!(cache.channel("t1").is_some());
+
+    cache.update_from_dispatch(
+        "THREAD_MEMBERS_UPDATE",
+        &json!({
+            "id": "t1",
+            "guild_id": "g1",
+            "member_count": 2,
+            "added_members": [
+                { "id": "t1", "user_id": "1", "join_timestamp": "2024-01-01T00:00:00+00:00", "flags": 0 }
+            ]
+        }),
+    );
+    let t1 = cache.thread("t1").expect("thread should be cached");
+    assert_eq!(t1.member_count, Some(2));
+    assert_eq!(cache.joined_threads("g1").len(), 1);
+
+    cache.update_from_dispatch("THREAD_UPDATE", &thread("t1", "c1", true));
+    assert!(cache.thread("t1").is_none());
+
+    cache.update_from_dispatch(
+        "THREAD_LIST_SYNC",
+        &json!({ "guild_id": "g1", "channel_ids": ["c2"], "threads": [thread("t3", "c2", false)] }),
+    );
+    let in_c2: Vec<String> = cache
+        .active_threads("c2")
+        .into_iter()
+        .map(|t| t.id)
+        .collect();
+    assert_eq!(in_c2, vec!["t3"]);
+
+    cache.update_from_dispatch(
+        "THREAD_DELETE",
+        &json!({ "id": "t3", "guild_id": "g1", "parent_id": "c2", "type": 11 }),
+    );
+    assert_eq!(cache.thread_count(), 0);
+}
+
+#[test]
+fn cache_evicts_least_recently_used_users() {
+    let cache = Cache::with_config(CacheConfig {
+        limits: CacheLimits {
+            users: CachePolicy::default().max_entries(2),
+            ..CacheLimits::default()
+        },
+        ..CacheConfig::default()
+    });
+
+    cache.cache_user(sample_user("1"));
+    cache.cache_user(sample_user("2"));
+    assert!(cache.user("1").is_some());
+    cache.cache_user(sample_user("3"));
+
+    assert!(cache.user("2").is_none());
+    assert!(cache.user("1").is_some());
+    assert!(cache.user("3").is_some());
+
+    let stats = cache.stats();
+    assert_eq!(stats.users, 2);
+    assert_eq!(stats.evictions, 1);
+    assert_eq!((stats.hits, stats.misses), (3, 1));
+    assert_eq!(stats.hit_rate(), 0.75);
+}
+
+#[test]
+fn cache_updates_do_not_count_as_lookups() {
+    let cache = Cache::new();
+    cache.cache_guild(serde_json::from_value(json!({ "id": "g1", "name": "Guild" })).unwrap());
+
+    cache.update_from_dispatch(
+        "CHANNEL_UPDATE",
+        &json!({ "id": "c1", "guild_id": "g1", "type": 0, "name": "general" }),
+    );
+    cache.update_from_dispatch(
+        "CHANNEL_PINS_UPDATE",
+        &json!({ "channel_id": "c2", "last_pin_timestamp": null }),
+    );
+    cache.update_from_dispatch(
+        "MESSAGE_CREATE",
+        &json!({
+            "id": "m1",
+            "channel_id": "c1",
+            "guild_id": "g1",
+            "author": { "id": "u1", "username": "author", "discriminator": "0" },
+            "content": "hello",
+            "timestamp": "2026-01-01T00:00:00.000Z",
+            "type": 0
+        }),
+    );
+    // Partial updates of a cached and of an unknown message
+    for id in ["m1", "m2"] {
+        cache.update_from_dispatch(
+            "MESSAGE_UPDATE",
+            &json!({ "id": id, "channel_id": "c1", "guild_id": "g1", "embeds": [] }),
+        );
+    }
+    for nick in ["first", "second"] {
+        cache.update_from_dispatch(
+            "GUILD_MEMBER_UPDATE",
+            &json!({
+                "guild_id": "g1",
+                "user": { "id": "u1", "username": "author", "discriminator": "0" },
+                "nick": nick,
+                "roles": []
+            }),
+        );
+    }
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses), (0, 0));
+
+    assert_eq!(cache.guild("g1").unwrap().channels.len(), 1);
+    assert_eq!(cache.member_nick("g1", "u1").as_deref(), Some("second"));
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses), (2, 0));
+}
+
+#[test]
+fn cache_expires_entries_after_ttl() {
+    let cache = Cache::with_config(CacheConfig {
+        limits: CacheLimits {
+            users: CachePolicy::default().ttl(std::time::Duration::from_millis(20)),
+            ..CacheLimits::default()
+        },
+        ..CacheConfig::default()
+    });
+
+    cache.cache_user(sample_user("1"));
+    assert!(cache.user("1").is_some());
+    std::thread::sleep(std::time::Duration::from_millis(30));
+
+    assert!(cache.user("1").is_none());
+    assert_eq!(cache.user_count(), 0);
+    assert_eq!(cache.stats().evictions, 1);
+}
+
+#[tokio::test]
+async fn cache_snapshot_roundtrips_through_a_file() {
+    let cache = Cache::new();
+    cache.cache_user(sample_user("1"));
+    cache.update_from_dispatch(
+        "GUILD_CREATE",
+        &json!({
+            "id": "g1",
+            "name": "Guild One",
+            "channels": [{ "id": "c1", "type": 0, "name": "chat" }]
+        }),
+    );
+    cache.update_from_dispatch("RELATIONSHIP_ADD", &json!({ "id": "1", "type": 1 }));
+
+    let path = std::env::temp_dir().join(format!("diself-cache-{}.json", std::process::id()));
+    cache.snapshot().save(&path).await.expect("snapshot saved");
+    let snapshot = CacheSnapshot::load(&path).await.expect("snapshot loaded");
+    let _ = std::fs::remove_file(&path);
+
+    let restored = Cache::new();
+    restored.restore(snapshot);
+
+    assert_eq!(
+        restored.user("1").map(|u| u.username),
+        Some("user_1".to_string())
+    );
+    assert_eq!(
+        restored.guild("g1").and_then(|g| g.name).as_deref(),
+        Some("Guild One")
+    );
+    assert!(restored.channel("c1").is_some());
+    assert!(restored.relationship("1").is_some_and(|r| r.is_friend()));
+}
+
+#[test]
+fn cache_keeps_gateway_messages_and_merges_partial_updates() {
+    let cache = Cache::new();
+    let message = |id: &str, content: &str| {
+        json!({
+            "id": id,
+            "channel_id": "c1",
+            "guild_id": "g1",
+            "author": { "id": "1", "username": "alice", "discriminator": "0" },
+            "content": content,
+            "timestamp": "2026-02-22T00:00:00.000Z",
+            "type": 0
+        })
+    };
+    cache.update_from_dispatch("MESSAGE_CREATE", &message("2", "second"));
+    cache.update_from_dispatch("MESSAGE_CREATE", &message("1", "first"));
+    assert_eq!(cache.message_count(), 2);
+
+    // Embed resolution sends a partial update without author or content
+    cache.update_from_dispatch(
+        "MESSAGE_UPDATE",
+        &json!({ "id": "1", "channel_id": "c1", "embeds": [{ "type": "link", "title": "Link" }] }),
+    );
+    let first = cache.message("1").expect("message should stay cached");
+    assert_eq!(first.content, "first");
+    assert_eq!(first.embeds.len(), 1);
+
+    let ids: Vec<String> = cache
+        .channel_messages("c1")
+        .into_iter()
+        .map(|m| m.id)
+        .collect();
+    assert_eq!(ids, vec!["1", "2"]);
+
+    cache.update_from_dispatch(
+        "MESSAGE_DELETE_BULK",
+        &json!({ "ids": ["1", "2"], "channel_id": "c1" }),
+    );
+    assert_eq!(cache.message_count(), 0);
+    assert_eq!(
+        CacheLimits::default().messages,
+        CachePolicy::default().max_entries(diself::cache::DEFAULT_MESSAGE_CACHE_SIZE)
+    );
+}
+
+#[test]
+fn cache_refreshes_current_user_server_tag_from_user_update() {
+    let cache = Cache::with_config(CacheConfig {
+        cache_users: false,
+        ..CacheConfig::default()
+    });
+    cache.set_current_user(sample_user("555"));
+    assert!(cache.current_user().unwrap().guild_tag().is_none());
+
+    cache.update_from_dispatch(
+        "USER_UPDATE",
+        &json!({
+            "id": "555",
+            "primary_guild": {
+                "identity_guild_id": "42",
+                "identity_enabled": true,
+                "tag": "RUST",
+                "badge": "abc"
+            }
+        }),
+    );
+
+    let tag = cache.current_user().unwrap().guild_tag().unwrap();
+    assert_eq!(tag.guild_id, "42");
+    assert_eq!(tag.tag, "RUST");
+    assert_eq!(
+        tag.badge_url().as_deref(),
+        Some("https://cdn.discordapp.com/guild-tag-badges/42/abc.png")
+    );
+    assert_eq!(cache.current_user().unwrap().username, "user_555");
+}
+
+#[test]
+fn cache_tracks_friend_suggestion_count() {
+    let cache = Cache::new();
+    cache.update_from_dispatch(
+        "READY",
+        &json!({ "user": sample_user("1"), "friend_suggestion_count": 2 }),
+    );
+    assert_eq!(cache.friend_suggestion_count(), 2);
+
+    let suggestion = json!({ "suggested_user": sample_user("2"), "reasons": [] });
+    cache.update_from_dispatch("FRIEND_SUGGESTION_CREATE", &suggestion);
+    assert_eq!(cache.friend_suggestion_count(), 3);
+    for _ in 0..4 {
+        cache.update_from_dispatch(
+            "FRIEND_SUGGESTION_DELETE",
+            &json!({ "suggested_user_id": "2" }),
+        );
+    }
+    assert_eq!(cache.friend_suggestion_count(), 0);
+}
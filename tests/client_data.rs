@@ -0,0 +1,74 @@
+use diself::{Client, DispatchMode, EventHandler, TypeMap, TypeMapKey};
+
+struct Handler;
+
+impl EventHandler for Handler {}
+
+struct Counter;
+
+impl TypeMapKey for Counter {
+    type Value = u64;
+}
+
+struct Prefix;
+
+impl TypeMapKey for Prefix {
+    type Value = String;
+}
+
+#[test]
+fn type_map_stores_values_per_key() {
+    let mut data = TypeMap::new();
+    assert!(data.insert::<Counter>(1).is_none());
+    assert_eq!(data.insert::<Counter>(2), Some(1));
+    data.insert::<Prefix>("!".to_string());
+
+    *data.get_mut::<Counter>().expect("counter should exist") += 1;
+    assert_eq!(data.get::<Counter>(), Some(&3));
+    assert_eq!(data.get::<Prefix>().map(String::as_str), Some("!"));
+    assert_eq!(data.len(), 2);
+
+    assert_eq!(data.remove::<Prefix>().as_deref(), Some("!"));
+    assert!(!data.contains_key::<Prefix>());
+    assert_eq!(*data.get_or_insert_with::<Prefix>(|| ".".to_string()), ".");
+}
+
+#[tokio::test]
+async fn builder_data_is_shared_with_client() {
+    let client = Client::builder("token", Handler)
+        .with_data::<Counter>(41)
+        .build();
+
+    *client
+        .data()
+        .write()
+        .await
+        .get_mut::<Counter>()
+        .expect("counter should exist") += 1;
+    assert_eq!(client.data().read().await.get::<Counter>(), Some(&42));
+}
+
+#[tokio::test]
+async fn client_data_fails_instead_of_panicking_while_locked() {
+    let client = Client::new("token", Handler)
+        .with_data::<Counter>(1)
+        .unwrap();
+    let data = client.data();
+    let guard = data.read().await;
+    assert!(client.with_data::<Prefix>("!".to_string()).is_err());
+    drop(guard);
+}
+
+#[test]
+fn dispatch_mode_defaults_to_sequential() {
+    assert_eq!(
+        Client::new("token", Handler).dispatch_mode(),
+        DispatchMode::Sequential
+    );
+
+    let mode = DispatchMode::OrderedPerChannel { max_concurrency: 8 };
+    let client = Client::builder("token", Handler)
+        .with_dispatch_mode(mode)
+        .build();
+    assert_eq!(client.dispatch_mode(), mode);
+}
@@ -1,6 +1,7 @@
 use diself::{
     CollectorHub, CollectorOptions, DispatchEvent, DispatchEventType, ReactionEventType,
 };
+use diself::model::ReactionType;
 use serde_json::json;
 
 #[tokio::test]
@@ -63,7 +64,9 @@ async fn reaction_collector_collects_reaction_add() {
             "channel_id": "c9",
             "message_id": "m42",
             "user_id": "u4",
-            "emoji": { "id": null, "name": "👍" }
+            "emoji": { "id": null, "name": "👍" },
+            "burst": true,
+            "type": 1
         }),
     });
 
@@ -71,4 +74,437 @@ async fn reaction_collector_collects_reaction_add() {
     assert_eq!(item.channel_id, "c9");
     assert_eq!(item.message_id, "m42");
     assert_eq!(item.user_id, "u4");
+    assert_eq!(item.reaction_type, ReactionType::Burst);
+}
+
+#[tokio::test]
+async fn typing_collector_collects_typing_start() {
+    let hub = CollectorHub::new();
+    let mut collector = hub.typing_collector(
+        CollectorOptions {
+            time: None,
+            max: Some(1),
+        },
+        |typing| typing.channel_id == "c7",
+    );
+
+    hub.dispatch(DispatchEvent {
+        kind: DispatchEventType::TypingStart,
+        sequence: Some(20),
+        data: json!({ "channel_id": "c1", "user_id": "u1", "timestamp": 1_700_000_000 }),
+    });
+
+    hub.dispatch(DispatchEvent {
+        kind: DispatchEventType::TypingStart,
+        sequence: Some(21),
+        data: json!({
+            "channel_id": "c7",
+            "guild_id": "g1",
+            "user_id": "u2",
+            "timestamp": 1_700_000_001
+        }),
+    });
+
+    let item = collector
+        .next()
+        .await
+        .expect("expected collected typing event");
+    assert_eq!(item.user_id, "u2");
+    assert_eq!(item.guild_id.as_deref(), Some("g1"));
+    assert_eq!(item.timestamp, 1_700_000_001);
+}
+
+#[tokio::test]
+async fn closing_hub_ends_collectors() {
+    let hub = CollectorHub::new();
+    let mut active = hub.message_collector(
+        CollectorOptions {
+            time: None,
+            max: None,
+        },
+        |_| true,
+    );
+
+    hub.close();
+    assert!(hub.is_closed());
+    assert!(active.next().await.is_none());
+
+    let mut late = hub.typing_collector(CollectorOptions::default(), |_| true);
+    assert!(late.next().await.is_none());
+}
+
+#[tokio::test]
+async fn edit_collector_collects_full_message_updates() {
+    let hub = CollectorHub::new();
+    let mut collector = hub.edit_collector(
+        CollectorOptions {
+            time: None,
+            max: Some(1),
+        },
+        |msg| msg.id == "m5",
+    );
+
+    // Partial embed update, skipped
+    hub.dispatch(DispatchEvent {
+        kind: DispatchEventType::MessageUpdate,
+        sequence: Some(30),
+        data: json!({ "id": "m5", "channel_id": "c1", "embeds": [] }),
+    });
+
+    hub.dispatch(DispatchEvent {
+        kind: DispatchEventType::MessageUpdate,
+        sequence: Some(31),
+        data: json!({
+            "id": "m5",
+            "channel_id": "c1",
+            "author": { "id": "u1", "username": "bot", "discriminator": "0" },
+            "content": "done",
+            "timestamp": "2026-02-22T00:00:00.000Z",
+            "edited_timestamp": "2026-02-22T00:00:05.000Z",
+            "type": 0
+        }),
+    });
+
+    let item = collector.next().await.expect("expected collected edit");
+    assert_eq!(item.content, "done");
+    assert!(collector.next().await.is_none());
+}
+
+#[tokio::test]
+async fn delete_collector_splits_bulk_deletes() {
+    let hub = CollectorHub::new();
+    let collector = hub.delete_collector(
+        CollectorOptions {
+            time: None,
+            max: Some(3),
+        },
+        |deleted| deleted.channel_id == "c1",
+    );
+
+    hub.dispatch(DispatchEvent {
+        kind: DispatchEventType::MessageDelete,
+        sequence: Some(40),
+        data: json!({ "id": "m1", "channel_id": "c1", "guild_id": "g1" }),
+    });
+    hub.dispatch(DispatchEvent {
+        kind: DispatchEventType::MessageDeleteBulk,
+        sequence: Some(41),
+        data: json!({ "ids": ["m2", "m3", "m4"], "channel_id": "c1", "guild_id": "g1" }),
+    });
+
+    let deleted = collector.collect().await;
+    let ids: Vec<&str> = deleted.iter().map(|d| d.message_id.as_str()).collect();
+    assert_eq!(ids, vec!["m1", "m2", "m3"]);
+    assert!(!deleted[0].bulk);
+    assert!(deleted[1].bulk);
+}
+
+#[tokio::test]
+async fn collectors_are_streams() {
+    use futures::StreamExt;
+
+    let hub = CollectorHub::new();
+    let collector = hub.typing_collector(CollectorOptions::default(), |_| true);
+
+    for (sequence, user_id) in [(50, "u1"), (51, "u2"), (52, "u3")] {
+        hub.dispatch(DispatchEvent {
+            kind: DispatchEventType::TypingStart,
+            sequence: Some(sequence),
+            data: json!({ "channel_id": "c1", "user_id": user_id, "timestamp": 1 }),
+        });
+    }
+
+    let users: Vec<String> = collector
+        .filter(|typing| std::future::ready(typing.user_id != "u2"))
+        .map(|typing| typing.user_id)
+        .take(2)
+        .collect()
+        .await;
+    assert_eq!(users, vec!["u1".to_string(), "u3".to_string()]);
+}
+
+#[tokio::test]
+async fn await_reply_and_reaction_wait_for_other_users() {
+    use diself::model::{Message, User};
+    use diself::{Cache, Context, HttpClient};
+    use std::time::Duration;
+
+    let me: User = serde_json::from_value(json!({
+        "id": "1",
+        "username": "me",
+        "discriminator": "0"
+    }))
+    .unwrap();
+    let ctx = Context::new(HttpClient::new("token"), me, Cache::new());
+    let message = |id: &str, author_id: &str, reply_to: Option<&str>| {
+        let reference =
+            reply_to.map(|reply_to| json!({ "message_id": reply_to, "channel_id": "c1" }));
+        json!({
+            "id": id,
+            "channel_id": "c1",
+            "author": { "id": author_id, "username": "user", "discriminator": "0" },
+            "content": "yes",
+            "timestamp": "2026-02-22T00:00:00.000Z",
+            "type": if reply_to.is_some() { 19 } else { 0 },
+            "message_reference": reference
+        })
+    };
+    let prompt: Message = serde_json::from_value(message("p1", "1", None)).unwrap();
+
+    let waiter = {
+        let (ctx, prompt) = (ctx.clone(), prompt.clone());
+        tokio::spawn(async move { prompt.await_reply(&ctx, Duration::from_secs(5)).await })
+    };
+    tokio::task::yield_now().await;
+    for (sequence, data) in [
+        (60, message("m1", "2", None)),
+        (61, message("m2", "1", Some("p1"))),
+        (62, message("m3", "2", Some("p1"))),
+    ] {
+        ctx.collectors.dispatch(DispatchEvent {
+            kind: DispatchEventType::MessageCreate,
+            sequence: Some(sequence),
+            data,
+        });
+    }
+    let reply = waiter.await.unwrap().expect("expected reply");
+    assert_eq!(reply.id, "m3");
+
+    let waiter = {
+        let (ctx, prompt) = (ctx.clone(), prompt.clone());
+        tokio::spawn(async move {
+            prompt
+                .await_reaction(&ctx, "✅", Duration::from_secs(5))
+                .await
+        })
+    };
+    tokio::task::yield_now().await;
+    for (sequence, user_id, emoji) in [(63, "1", "✅"), (64, "2", "❌"), (65, "2", "✅")] {
+        ctx.collectors.dispatch(DispatchEvent {
+            kind: DispatchEventType::MessageReactionAdd,
+            sequence: Some(sequence),
+            data: json!({
+                "user_id": user_id,
+                "channel_id": "c1",
+                "message_id": "p1",
+                "emoji": { "id": null, "name": emoji }
+            }),
+        });
+    }
+    let reaction = waiter.await.unwrap().expect("expected reaction");
+    assert_eq!(reaction.user_id, "2");
+    assert_eq!(reaction.emoji.name.as_deref(), Some("✅"));
+
+    let none = prompt.await_reply(&ctx, Duration::from_millis(10)).await;
+    assert!(none.is_none());
+}
+
+#[tokio::test]
+async fn lagging_collectors_are_counted_and_reported() {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+
+    let reported = Arc::new(AtomicU64::new(0));
+    let hub = {
+        let reported = reported.clone();
+        CollectorHub::with_capacity(2).with_lag_handler(move |skipped| {
+            reported.fetch_add(skipped, Ordering::SeqCst);
+        })
+    };
+    assert_eq!(hub.capacity(), 2);
+    let mut collector = hub.typing_collector(
+        CollectorOptions {
+            time: None,
+            max: Some(2),
+        },
+        |_| true,
+    );
+
+    // Sent before the collector task runs: only the last 2 fit in the buffer
+    for sequence in 70..75 {
+        hub.dispatch(DispatchEvent {
+            kind: DispatchEventType::TypingStart,
+            sequence: Some(sequence),
+            data: json!({ "channel_id": "c1", "user_id": sequence.to_string(), "timestamp": 1 }),
+        });
+    }
+
+    assert_eq!(collector.next().await.unwrap().user_id, "73");
+    assert_eq!(collector.next().await.unwrap().user_id, "74");
+    let stats = hub.stats();
+    assert_eq!(stats.dispatched, 5);
+    assert_eq!(stats.lags, 1);
+    assert_eq!(stats.dropped, 3);
+    assert_eq!(reported.load(Ordering::SeqCst), 3);
+}
+
+/// Local gateway answering each connection with HELLO, then one MESSAGE_CREATE after IDENTIFY
+async fn fake_gateway() -> String {
+    use futures::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!(
+        "ws://{}/?v=10&encoding=json",
+        listener.local_addr().unwrap()
+    );
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+                let hello = json!({ "op": 10, "d": { "heartbeat_interval": 45000 } });
+                ws.send(WsMessage::Text(hello.to_string())).await.unwrap();
+                let _identify = ws.next().await;
+                let message = json!({
+                    "op": 0,
+                    "s": 1,
+                    "t": "MESSAGE_CREATE",
+                    "d": {
+                        "id": "m1",
+                        "channel_id": "c1",
+                        "author": { "id": "2", "username": "name", "discriminator": "0001" },
+                        "content": "hello",
+                        "timestamp": "2026-02-22T00:00:00.000Z",
+                        "type": 0
+                    }
+                });
+                ws.send(WsMessage::Text(message.to_string())).await.unwrap();
+                while let Some(Ok(_)) = ws.next().await {}
+            });
+        }
+    });
+    url
+}
+
+#[tokio::test]
+async fn collectors_work_again_after_restarting_the_client() {
+    use diself::http::{Method, MockTransport};
+    use diself::prelude::async_trait;
+    use diself::{Client, Context, EventHandler, HttpClient, Message};
+    use std::sync::Arc;
+    use tokio::sync::mpsc;
+
+    struct Handler(mpsc::UnboundedSender<bool>);
+
+    #[async_trait]
+    impl EventHandler for Handler {
+        async fn on_message_create(&self, ctx: &Context, _msg: Message) {
+            let _ = self.0.send(ctx.collectors.is_closed());
+        }
+    }
+
+    let mock = MockTransport::new();
+    mock.on(
+        Method::GET,
+        "/users/@me",
+        json!({ "id": "1", "username": "me", "discriminator": "0001" }),
+    );
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let hub = CollectorHub::new();
+    let client = Arc::new(
+        Client::builder("token", Handler(tx))
+            .with_http_client(HttpClient::new("token").with_transport(mock))
+            .with_gateway_url(fake_gateway().await)
+            .with_collectors(hub.clone())
+            .build(),
+    );
+
+    for _ in 0..2 {
+        let run = tokio::spawn({
+            let client = client.clone();
+            async move { client.start().await }
+        });
+        let closed = rx.recv().await.expect("handler ran");
+        assert!(!closed, "collectors must be open while the client runs");
+        client.shutdown();
+        run.await.unwrap().unwrap();
+        assert!(hub.is_closed());
+    }
+}
+
+#[tokio::test]
+async fn collectors_end_when_the_gateway_fails() {
+    use diself::http::{Method, MockTransport};
+    use diself::prelude::async_trait;
+    use diself::{Client, Context, EventHandler, HttpClient, Message, MessageCollector};
+    use futures::{SinkExt, StreamExt};
+    use std::time::Duration;
+    use tokio::sync::{mpsc, oneshot};
+    use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+    use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+    use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+    struct Handler(mpsc::UnboundedSender<MessageCollector>);
+
+    #[async_trait]
+    impl EventHandler for Handler {
+        async fn on_message_create(&self, ctx: &Context, _msg: Message) {
+            let collector = ctx
+                .collectors
+                .message_collector(CollectorOptions::default(), |_| true);
+            let _ = self.0.send(collector);
+        }
+    }
+
+    // Sends one MESSAGE_CREATE, then closes with 4004 (authentication failed) when told to
+    let (close_tx, close_rx) = oneshot::channel::<()>();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!(
+        "ws://{}/?v=10&encoding=json",
+        listener.local_addr().unwrap()
+    );
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        let hello = json!({ "op": 10, "d": { "heartbeat_interval": 45000 } });
+        ws.send(WsMessage::Text(hello.to_string())).await.unwrap();
+        let _identify = ws.next().await;
+        let message = json!({
+            "op": 0,
+            "s": 1,
+            "t": "MESSAGE_CREATE",
+            "d": {
+                "id": "m1",
+                "channel_id": "c1",
+                "author": { "id": "2", "username": "name", "discriminator": "0001" },
+                "content": "hello",
+                "timestamp": "2026-02-22T00:00:00.000Z",
+                "type": 0
+            }
+        });
+        ws.send(WsMessage::Text(message.to_string())).await.unwrap();
+        let _ = close_rx.await;
+        let frame = CloseFrame {
+            code: CloseCode::from(4004),
+            reason: "".into(),
+        };
+        ws.close(Some(frame)).await.unwrap();
+        while let Some(Ok(_)) = ws.next().await {}
+    });
+
+    let mock = MockTransport::new();
+    mock.on(
+        Method::GET,
+        "/users/@me",
+        json!({ "id": "1", "username": "me", "discriminator": "0001" }),
+    );
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let client = Client::builder("token", Handler(tx))
+        .with_http_client(HttpClient::new("token").with_transport(mock))
+        .with_gateway_url(url)
+        .build();
+
+    let run = client.start();
+    tokio::pin!(run);
+    let mut collector = tokio::select! {
+        collector = rx.recv() => collector.expect("handler ran"),
+        _ = &mut run => panic!("client stopped before the handler ran"),
+    };
+    close_tx.send(()).unwrap();
+
+    assert!(run.await.is_err());
+    let next = tokio::time::timeout(Duration::from_secs(5), collector.next())
+        .await
+        .expect("collector was left open after the gateway failed");
+    assert!(next.is_none());
 }
@@ -0,0 +1,221 @@
+use diself::gateway::ReplayGateway;
+use diself::model::Call;
+use diself::prelude::async_trait;
+use diself::{Client, Context, DispatchMode, EventHandler, Message};
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+fn replay(messages: &[(&str, &str)]) -> ReplayGateway {
+    let ready = json!({
+        "op": 0,
+        "t": "READY",
+        "s": 1,
+        "d": { "user": { "id": "1", "username": "me", "discriminator": "0" } }
+    });
+    let events = messages
+        .iter()
+        .enumerate()
+        .map(|(i, (channel_id, content))| {
+            json!({
+                "op": 0,
+                "t": "MESSAGE_CREATE",
+                "s": i + 2,
+                "d": {
+                    "id": format!("{}", i + 10),
+                    "channel_id": channel_id,
+                    "author": { "id": "2", "username": "alice", "discriminator": "0" },
+                    "content": content,
+                    "timestamp": "2026-01-01T00:00:00.000Z",
+                    "type": 0
+                }
+            })
+        });
+    ReplayGateway::from_events(std::iter::once(ready).chain(events))
+}
+
+struct Recorder {
+    handled: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl EventHandler for Recorder {
+    async fn on_message_create(&self, _ctx: &Context, msg: Message) {
+        // The first message of each channel is the slowest one
+        if msg.content.ends_with('1') {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        self.handled.lock().unwrap().push(msg.content);
+    }
+}
+
+#[tokio::test]
+async fn ordered_per_channel_keeps_channel_order_only() {
+    let handled = Arc::new(Mutex::new(Vec::new()));
+    let client = Client::builder(
+        "token",
+        Recorder {
+            handled: handled.clone(),
+        },
+    )
+    .with_dispatch_mode(DispatchMode::OrderedPerChannel { max_concurrency: 4 })
+    .build();
+
+    client
+        .replay(replay(&[
+            ("c1", "c1-1"),
+            ("c2", "c2-2"),
+            ("c1", "c1-2"),
+            ("c1", "c1-3"),
+        ]))
+        .await
+        .unwrap();
+
+    let handled = handled.lock().unwrap().clone();
+    let channel_1: Vec<&String> = handled.iter().filter(|c| c.starts_with("c1")).collect();
+    assert_eq!(channel_1, ["c1-1", "c1-2", "c1-3"]);
+    // The other channel didn't wait for the slow message
+    assert_eq!(handled[0], "c2-2");
+}
+
+struct Counter {
+    running: AtomicUsize,
+    max_running: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl EventHandler for Counter {
+    async fn on_message_create(&self, _ctx: &Context, _msg: Message) {
+        let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_running.fetch_max(running, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        self.running.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[tokio::test]
+async fn concurrent_mode_runs_at_most_max_concurrency_handlers() {
+    let max_running = Arc::new(AtomicUsize::new(0));
+    let client = Client::builder(
+        "token",
+        Counter {
+            running: AtomicUsize::new(0),
+            max_running: max_running.clone(),
+        },
+    )
+    .with_dispatch_mode(DispatchMode::Concurrent { max_concurrency: 2 })
+    .build();
+
+    let messages: Vec<(&str, &str)> = (0..6).map(|_| ("c1", "hi")).collect();
+    client.replay(replay(&messages)).await.unwrap();
+
+    assert_eq!(max_running.load(Ordering::SeqCst), 2);
+}
+
+struct Ringing {
+    rung: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl EventHandler for Ringing {
+    async fn on_incoming_call(&self, _ctx: &Context, _call: Call) {
+        self.rung.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[tokio::test]
+async fn incoming_call_fires_when_the_current_user_starts_ringing() {
+    let rung = Arc::new(AtomicUsize::new(0));
+    let client = Client::builder("token", Ringing { rung: rung.clone() }).build();
+
+    let call = |t: &str, s: u64, ringing: &[&str]| {
+        json!({
+            "op": 0,
+            "t": t,
+            "s": s,
+            "d": { "channel_id": "c1", "message_id": "m1", "ringing": ringing }
+        })
+    };
+    let events = vec![
+        json!({
+            "op": 0,
+            "t": "READY",
+            "s": 1,
+            "d": { "user": { "id": "1", "username": "me", "discriminator": "0" } }
+        }),
+        call("CALL_CREATE", 2, &["3"]),
+        // The current user is rung into the existing call
+        call("CALL_UPDATE", 3, &["3", "1"]),
+        // Still ringing, not a new incoming call
+        call("CALL_UPDATE", 4, &["1"]),
+        call("CALL_UPDATE", 5, &[]),
+        call("CALL_UPDATE", 6, &["1"]),
+    ];
+    client
+        .replay(ReplayGateway::from_events(events))
+        .await
+        .unwrap();
+
+    assert_eq!(rung.load(Ordering::SeqCst), 2);
+}
+
+struct Edits {
+    edited: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl EventHandler for Edits {
+    async fn on_message_update(&self, _ctx: &Context, _old: Option<Message>, new: Message) {
+        self.edited.lock().unwrap().push(new.content);
+    }
+}
+
+#[tokio::test]
+async fn partial_message_updates_do_not_count_as_cache_lookups() {
+    let edited = Arc::new(Mutex::new(Vec::new()));
+    let client = Client::builder(
+        "token",
+        Edits {
+            edited: edited.clone(),
+        },
+    )
+    .build();
+
+    let events = vec![
+        json!({
+            "op": 0,
+            "t": "READY",
+            "s": 1,
+            "d": { "user": { "id": "1", "username": "me", "discriminator": "0" } }
+        }),
+        json!({
+            "op": 0,
+            "t": "MESSAGE_CREATE",
+            "s": 2,
+            "d": {
+                "id": "10",
+                "channel_id": "c1",
+                "author": { "id": "2", "username": "alice", "discriminator": "0" },
+                "content": "hello",
+                "timestamp": "2026-01-01T00:00:00.000Z",
+                "type": 0
+            }
+        }),
+        // Embeds resolved later: the partial payload only parses once merged in cache
+        json!({
+            "op": 0,
+            "t": "MESSAGE_UPDATE",
+            "s": 3,
+            "d": { "id": "10", "channel_id": "c1", "embeds": [] }
+        }),
+    ];
+    client
+        .replay(ReplayGateway::from_events(events))
+        .await
+        .unwrap();
+
+    assert_eq!(*edited.lock().unwrap(), ["hello"]);
+    let stats = client.cache().stats();
+    assert_eq!((stats.hits, stats.misses), (0, 0));
+}
@@ -14,7 +14,7 @@ fn live_http() -> HttpClient {
 
 #[tokio::test]
 #[ignore = "Live Discord endpoint smoke test; requires DISCORD_TOKEN"]
-async fn users_me_endpoint_smoke() -> diself::Result<()> {
+async fn users_me_endpoint_smoke() -> Result<(), Box<dyn std::error::Error>> {
     let http = live_http();
     let users = UsersManager;
 
@@ -26,7 +26,7 @@ async fn users_me_endpoint_smoke() -> diself::Result<()> {
 
 #[tokio::test]
 #[ignore = "Live Discord endpoint smoke test; requires DISCORD_TOKEN"]
-async fn guilds_list_endpoint_smoke() -> diself::Result<()> {
+async fn guilds_list_endpoint_smoke() -> Result<(), Box<dyn std::error::Error>> {
     let http = live_http();
     let guilds = GuildsManager;
 
@@ -37,7 +37,7 @@ async fn guilds_list_endpoint_smoke() -> diself::Result<()> {
 
 #[tokio::test]
 #[ignore = "Live Discord endpoint smoke test; requires DISCORD_TOKEN"]
-async fn channels_dm_channels_endpoint_smoke() -> diself::Result<()> {
+async fn channels_dm_channels_endpoint_smoke() -> Result<(), Box<dyn std::error::Error>> {
     let http = live_http();
     let channels = ChannelsManager;
 
@@ -48,7 +48,7 @@ async fn channels_dm_channels_endpoint_smoke() -> diself::Result<()> {
 
 #[tokio::test]
 #[ignore = "Live Discord endpoint smoke test; requires DISCORD_TOKEN"]
-async fn relationships_list_endpoint_smoke() -> diself::Result<()> {
+async fn relationships_list_endpoint_smoke() -> Result<(), Box<dyn std::error::Error>> {
     let http = live_http();
     let relationships = RelationshipsManager;
 
@@ -59,7 +59,7 @@ async fn relationships_list_endpoint_smoke() -> diself::Result<()> {
 
 #[tokio::test]
 #[ignore = "Live Discord endpoint smoke test; requires DISCORD_TOKEN and DISELF_TEST_GUILD_ID"]
-async fn guilds_get_endpoint_smoke() -> diself::Result<()> {
+async fn guilds_get_endpoint_smoke() -> Result<(), Box<dyn std::error::Error>> {
     let Some(guild_id) = optional_env("DISELF_TEST_GUILD_ID") else {
         eprintln!("Skipping: DISELF_TEST_GUILD_ID is not set");
         return Ok(());
@@ -72,3 +72,27 @@ async fn guilds_get_endpoint_smoke() -> diself::Result<()> {
     assert!(!guild.id.is_empty());
     Ok(())
 }
+
+#[tokio::test]
+#[ignore = "Live Discord endpoint smoke test; requires DISCORD_TOKEN and DISELF_TEST_GUILD_ID"]
+async fn guilds_members_iter_follows_cursor_smoke() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::StreamExt;
+
+    let Some(guild_id) = optional_env("DISELF_TEST_GUILD_ID") else {
+        eprintln!("Skipping: DISELF_TEST_GUILD_ID is not set");
+        return Ok(());
+    };
+
+    let http = live_http();
+    let guilds = GuildsManager;
+
+    let members: Vec<_> = guilds
+        .members_iter(&http, guild_id)
+        .take(1500)
+        .collect()
+        .await;
+    for member in members {
+        assert!(!member?.user.id.is_empty());
+    }
+    Ok(())
+}
@@ -0,0 +1,109 @@
+#![cfg(feature = "framework")]
+
+use diself::framework::{Args, Command, CommandFramework, CommandOutcome};
+use diself::model::{Message, User};
+use diself::{Cache, Context, HttpClient};
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+fn sample_user(id: &str) -> User {
+    serde_json::from_value(json!({
+        "id": id,
+        "username": format!("user_{id}"),
+        "discriminator": "0001"
+    }))
+    .expect("valid user json")
+}
+
+fn sample_message(author_id: &str, content: &str) -> Message {
+    serde_json::from_value(json!({
+        "id": "m1",
+        "channel_id": "c1",
+        "author": { "id": author_id, "username": "name", "discriminator": "0001" },
+        "content": content,
+        "timestamp": "2026-02-22T00:00:00.000Z",
+        "type": 0
+    }))
+    .expect("valid message json")
+}
+
+#[test]
+fn args_parse_typed_and_quoted_values() {
+    let mut args = Args::new(r#"42 "two words"   tail  end "#);
+    assert_eq!(args.len(), 4);
+    assert_eq!(args.single::<u64>().unwrap(), 42);
+    assert_eq!(args.single::<String>().unwrap(), "two words");
+    assert_eq!(args.rest(), "tail  end");
+    assert!(args.single::<u8>().is_err());
+    assert_eq!(args.optional::<u8>(), None);
+    assert_eq!(args.current(), Some("tail"));
+}
+
+#[test]
+fn help_lists_commands_with_usage_and_aliases() {
+    let framework = CommandFramework::new("!").command(
+        Command::new("echo", |_, _, _| async { Ok(()) })
+            .alias("say")
+            .usage("<text>")
+            .description("Repeats text")
+            .cooldown(Duration::from_secs(3)),
+    );
+
+    assert!(framework
+        .help_text()
+        .contains("`!echo <text>` - Repeats text (aliases: say)"));
+    assert!(framework
+        .command_help("SAY")
+        .expect("alias lookup")
+        .ends_with("Cooldown: 3s"));
+    assert!(framework.command_help("nope").is_none());
+}
+
+#[tokio::test]
+async fn dispatch_runs_commands_and_applies_cooldowns() {
+    let me = sample_user("1");
+    let ctx = Context::new(HttpClient::new("token"), me, Cache::new());
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+
+    let framework = CommandFramework::new("!").command(
+        Command::new("count", move |_, _, mut args| {
+            let counter = counter.clone();
+            async move {
+                let step: usize = args.single()?;
+                counter.fetch_add(step, Ordering::SeqCst);
+                Ok(())
+            }
+        })
+        .alias("c")
+        .cooldown(Duration::from_secs(60)),
+    );
+
+    let outcome = framework
+        .dispatch(&ctx, &sample_message("1", "!c 5"))
+        .await
+        .expect("command should run");
+    assert_eq!(outcome, CommandOutcome::Executed("count".to_string()));
+    assert_eq!(calls.load(Ordering::SeqCst), 5);
+
+    let outcome = framework
+        .dispatch(&ctx, &sample_message("1", "!count 5"))
+        .await
+        .expect("cooldown is not an error");
+    assert!(matches!(outcome, CommandOutcome::Cooldown { .. }));
+
+    let outcome = framework
+        .dispatch(&ctx, &sample_message("1", "!missing"))
+        .await
+        .unwrap();
+    assert_eq!(outcome, CommandOutcome::Unknown("missing".to_string()));
+
+    let outcome = framework
+        .dispatch(&ctx, &sample_message("2", "!count 1"))
+        .await
+        .unwrap();
+    assert_eq!(outcome, CommandOutcome::Ignored);
+    assert_eq!(calls.load(Ordering::SeqCst), 5);
+}
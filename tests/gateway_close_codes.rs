@@ -0,0 +1,47 @@
+use diself::gateway::GatewayCloseCode;
+use diself::Error;
+
+#[test]
+fn gateway_close_codes_classify_fatal_and_resumable() {
+    let auth = GatewayCloseCode::from_code(4004).expect("known code");
+    assert_eq!(auth, GatewayCloseCode::AuthenticationFailed);
+    assert!(auth.is_fatal());
+    assert!(!auth.can_resume());
+
+    let rate_limited = GatewayCloseCode::from_code(4008).expect("known code");
+    assert!(!rate_limited.is_fatal());
+    assert!(rate_limited.can_resume());
+
+    let timed_out = GatewayCloseCode::SessionTimedOut;
+    assert_eq!(timed_out.code(), 4009);
+    assert!(!timed_out.is_fatal());
+    assert!(!timed_out.can_resume());
+
+    assert!(GatewayCloseCode::from_code(4013).is_some_and(|c| c.is_fatal()));
+    assert!(GatewayCloseCode::from_code(4014).is_some_and(|c| c.is_fatal()));
+    assert_eq!(GatewayCloseCode::from_code(1000), None);
+}
+
+#[test]
+fn gateway_closed_error_exposes_close_code() {
+    let err = Error::GatewayClosed {
+        code: 4014,
+        reason: "Disallowed intent(s).".to_string(),
+    };
+    assert_eq!(
+        err.gateway_close_code(),
+        Some(GatewayCloseCode::DisallowedIntents)
+    );
+    assert!(err.is_fatal_gateway_close());
+    assert_eq!(
+        err.to_string(),
+        "Gateway closed with code 4014: Disallowed intent(s)."
+    );
+
+    let normal = Error::GatewayClosed {
+        code: 1000,
+        reason: String::new(),
+    };
+    assert_eq!(normal.gateway_close_code(), None);
+    assert!(!normal.is_fatal_gateway_close());
+}
@@ -0,0 +1,110 @@
+use diself::gateway::GatewaySender;
+
+#[test]
+fn voice_state_payload_includes_video_and_stream_keys() {
+    use diself::model::EditVoiceState;
+
+    let state = EditVoiceState::join(Some("1"), "2")
+        .self_mute(true)
+        .self_video(true);
+    assert_eq!(
+        serde_json::to_value(&state).unwrap(),
+        serde_json::json!({
+            "guild_id": "1",
+            "channel_id": "2",
+            "self_mute": true,
+            "self_deaf": false,
+            "self_video": true,
+        })
+    );
+    assert_eq!(state.stream_key("3").as_deref(), Some("guild:1:2:3"));
+    assert_eq!(
+        EditVoiceState::join(None, "2").stream_key("3").as_deref(),
+        Some("call:2:3")
+    );
+    assert_eq!(EditVoiceState::leave(Some("1")).stream_key("3"), None);
+
+    // Toggles need a voice channel joined through a running gateway
+    let sender = GatewaySender::default();
+    assert!(sender.set_voice_state(&state).is_err());
+    assert!(sender.voice_state().is_none());
+}
+
+#[test]
+fn guild_subscription_serializes_only_set_fields() {
+    use diself::model::GuildSubscription;
+
+    let subscription = GuildSubscription::all()
+        .members(["10", "11"])
+        .channel("20", [(0, 99), (100, 199)]);
+    assert_eq!(
+        serde_json::to_value(&subscription).unwrap(),
+        serde_json::json!({
+            "typing": true,
+            "threads": true,
+            "activities": true,
+            "member_updates": true,
+            "members": ["10", "11"],
+            "channels": { "20": [[0, 99], [100, 199]] },
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(GuildSubscription::new().typing(false)).unwrap(),
+        serde_json::json!({ "typing": false })
+    );
+
+    let sender = GatewaySender::default();
+    assert!(sender.subscribe_guilds([("1", subscription)]).is_err());
+}
+
+#[tokio::test]
+async fn heartbeats_are_sent_while_other_payloads_are_rate_limited() {
+    use diself::gateway::{ConnectionProperties, Gateway};
+    use futures::{SinkExt, StreamExt};
+    use std::time::Duration;
+    use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!(
+        "ws://{}/?v=10&encoding=json",
+        listener.local_addr().unwrap()
+    );
+    let server = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        let hello = serde_json::json!({ "op": 10, "d": { "heartbeat_interval": 500 } });
+        ws.send(WsMessage::Text(hello.to_string())).await.unwrap();
+
+        let mut presence_updates = 0;
+        while let Some(Ok(message)) = ws.next().await {
+            let payload: serde_json::Value =
+                serde_json::from_str(message.to_text().unwrap()).unwrap();
+            match payload["op"].as_u64() {
+                Some(1) => return presence_updates,
+                Some(3) => presence_updates += 1,
+                _ => {}
+            }
+        }
+        panic!("gateway closed before a heartbeat");
+    });
+
+    let mut gateway =
+        Gateway::connect_with_url(url, "token", ConnectionProperties::web_client(), None)
+            .await
+            .unwrap();
+    let sender = gateway.sender();
+    for _ in 0..130 {
+        sender
+            .update_presence(&diself::model::EditPresence::new())
+            .unwrap();
+    }
+    tokio::spawn(async move { while let Ok(Some(_)) = gateway.next_event().await {} });
+
+    // The window is full after the IDENTIFY and 114 presence updates, yet the
+    // heartbeat still goes out on its first tick instead of a minute later
+    let presence_updates = tokio::time::timeout(Duration::from_secs(5), server)
+        .await
+        .expect("heartbeat was held back by the send rate limit")
+        .unwrap();
+    assert_eq!(presence_updates, 114);
+}
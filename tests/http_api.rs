@@ -1,3 +1,4 @@
+use diself::gateway::ConnectionProperties;
 use diself::http;
 
 #[test]
@@ -5,3 +6,1777 @@ fn api_url_uses_v10_base_url() {
     let url = http::api_url("/channels/123/messages");
     assert_eq!(url, "https://discord.com/api/v10/channels/123/messages");
 }
+
+#[test]
+fn generated_nonce_is_a_recent_snowflake() {
+    let nonce: u64 = http::generate_nonce()
+        .parse()
+        .expect("nonce should be numeric");
+    // Anything generated after 2024-01-01 is above this value
+    assert!(nonce >> 22 > 1_704_067_200_000 - 1_420_070_400_000);
+}
+
+#[test]
+fn super_properties_header_reflects_connection_properties() {
+    use base64::Engine;
+
+    let decode = |client: &http::HttpClient| -> serde_json::Value {
+        let decoded = base64::engine::general_purpose::STANDARD
+            .decode(client.super_properties_header())
+            .expect("header should be base64");
+        serde_json::from_slice(&decoded).expect("header should be JSON")
+    };
+
+    // The web client is the default, the desktop client is opt-in
+    assert_eq!(decode(&http::HttpClient::new("token"))["browser"], "Chrome");
+    let client = http::HttpClient::new("token")
+        .with_connection_properties(ConnectionProperties::desktop_client());
+    let properties = decode(&client);
+
+    assert_eq!(properties["browser"], "Discord Client");
+    assert_eq!(properties["system_locale"], "en-US");
+    assert_eq!(
+        properties["browser_user_agent"],
+        client.connection_properties().browser_user_agent.as_str()
+    );
+    assert!(properties["client_heartbeat_session_id"].is_string());
+}
+
+#[tokio::test]
+async fn attachments_stream_to_file_from_cdn() {
+    use diself::model::Attachment;
+    use futures::StreamExt;
+
+    let mut server = mockito::Server::new_async().await;
+    let body = vec![7_u8; 64 * 1024];
+    let mock = server
+        .mock("GET", "/attachments/1/2/file.bin")
+        .match_query(mockito::Matcher::Any)
+        .with_body(&body)
+        .expect(3)
+        .create_async()
+        .await;
+
+    // `ex` is far in the future so no refresh (and no API call) is needed
+    let attachment: Attachment = serde_json::from_value(serde_json::json!({
+        "id": "2",
+        "filename": "file.bin",
+        "size": body.len(),
+        "url": format!("{}/attachments/1/2/file.bin?ex=ffffffff&is=0&hm=abc", server.url()),
+        "proxy_url": "https://media.discordapp.net/attachments/1/2/file.bin"
+    }))
+    .unwrap();
+    assert_eq!(attachment.expires_at(), Some(0xffff_ffff));
+    assert!(!attachment.is_expired());
+
+    let http = diself::HttpClient::new("token");
+    let bytes = attachment.download(&http).await.unwrap();
+    assert_eq!(bytes.len(), body.len());
+
+    let mut chunks = Box::pin(attachment.download_stream(&http).await.unwrap());
+    let mut streamed = 0;
+    while let Some(chunk) = chunks.next().await {
+        streamed += chunk.unwrap().len();
+    }
+    assert_eq!(streamed, body.len());
+
+    let path = std::env::temp_dir().join(format!("diself-attachment-{}", std::process::id()));
+    let written = attachment.download_to_file(&http, &path).await.unwrap();
+    assert_eq!(written, body.len() as u64);
+    assert_eq!(std::fs::read(&path).unwrap(), body);
+    std::fs::remove_file(&path).unwrap();
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn requests_are_retried_on_server_errors() {
+    use diself::http::RetryPolicy;
+    use std::time::Duration;
+
+    let mut server = mockito::Server::new_async().await;
+    let failing = server
+        .mock("GET", "/flaky")
+        .with_status(502)
+        .expect(1)
+        .create_async()
+        .await;
+    let ok = server
+        .mock("GET", "/flaky")
+        .with_body(r#"{"ok":true}"#)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let policy = RetryPolicy::default().base_delay(Duration::from_millis(10));
+    let http = diself::HttpClient::new("token").with_retry_policy(policy);
+    let response = http.get(format!("{}/flaky", server.url())).await.unwrap();
+    assert_eq!(response["ok"], true);
+    failing.assert_async().await;
+    ok.assert_async().await;
+
+    let down = server
+        .mock("GET", "/down")
+        .with_status(500)
+        .expect(1)
+        .create_async()
+        .await;
+    let http = diself::HttpClient::new("token").with_retry_policy(RetryPolicy::disabled());
+    let error = http
+        .get(format!("{}/down", server.url()))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        diself::Error::ServerError { status: 500, .. }
+    ));
+    down.assert_async().await;
+}
+
+#[tokio::test]
+async fn non_idempotent_requests_are_not_retried_on_server_errors() {
+    use diself::http::RetryPolicy;
+    use serde_json::json;
+    use std::time::Duration;
+
+    let mut server = mockito::Server::new_async().await;
+    let failing = server
+        .mock("POST", "/messages")
+        .with_status(502)
+        .expect(1)
+        .create_async()
+        .await;
+    let policy = RetryPolicy::default().base_delay(Duration::from_millis(10));
+    let http = diself::HttpClient::new("token").with_retry_policy(policy);
+    let error = http
+        .post(
+            format!("{}/messages", server.url()),
+            json!({ "content": "hi" }),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        diself::Error::ServerError { status: 502, .. }
+    ));
+    failing.assert_async().await;
+    failing.remove_async().await;
+
+    let failing = server
+        .mock("POST", "/messages")
+        .with_status(502)
+        .expect(1)
+        .create_async()
+        .await;
+    let ok = server
+        .mock("POST", "/messages")
+        .with_body(r#"{"id":"1"}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let http =
+        diself::HttpClient::new("token").with_retry_policy(policy.retry_non_idempotent(true));
+    let response = http
+        .post(
+            format!("{}/messages", server.url()),
+            json!({ "content": "hi" }),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response["id"], "1");
+    failing.assert_async().await;
+    ok.assert_async().await;
+}
+
+#[tokio::test]
+async fn mfa_challenges_are_finished_with_the_handler() {
+    use mockito::Matcher;
+    use serde_json::json;
+
+    let mut server = mockito::Server::new_async().await;
+    let challenge = json!({
+        "message": "Two factor is required for this operation",
+        "code": 60003,
+        "mfa": {
+            "ticket": "ticket",
+            "methods": [
+                { "type": "totp", "backup_codes_allowed": true },
+                { "type": "password" }
+            ]
+        }
+    })
+    .to_string();
+    let challenged = server
+        .mock("POST", "/v10/guilds/1/delete")
+        .match_header("X-Discord-MFA-Authorization", Matcher::Missing)
+        .with_status(401)
+        .with_body(&challenge)
+        .expect(2)
+        .create_async()
+        .await;
+    let finish = server
+        .mock("POST", "/v10/mfa/finish")
+        .match_body(Matcher::Json(
+            json!({ "ticket": "ticket", "mfa_type": "totp", "data": "123456" }),
+        ))
+        .with_body(r#"{"token":"mfa-token"}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let authorized = server
+        .mock("POST", "/v10/guilds/1/delete")
+        .match_header("X-Discord-MFA-Authorization", "mfa-token")
+        .with_status(204)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let url = http::api_url("/guilds/1/delete");
+    let http = diself::HttpClient::new("token").with_api_base_url(server.url());
+    let error = http.post(&url, json!({})).await.unwrap_err();
+    let diself::Error::MfaRequired(info) = error else {
+        panic!("expected an MFA challenge, got {error:?}");
+    };
+    assert_eq!(info.ticket, "ticket");
+    assert!(info.supports("totp") && info.supports("password"));
+    assert!(!info.supports("sms"));
+
+    let http = http.with_mfa_handler(|info| async move {
+        assert_eq!(info.ticket, "ticket");
+        Ok(diself::MfaCode::totp("123456"))
+    });
+    let response = http.post(&url, json!({})).await.unwrap();
+    assert!(response.is_null());
+    challenged.assert_async().await;
+    finish.assert_async().await;
+    authorized.assert_async().await;
+}
+
+#[tokio::test]
+async fn captchas_are_solved_within_the_retry_budget() {
+    use mockito::Matcher;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    let captcha = |reason: &str| {
+        json!({
+            "captcha_key": [reason],
+            "captcha_sitekey": "sitekey",
+            "captcha_service": "hcaptcha",
+            "captcha_session_id": "session",
+            "captcha_rqdata": "rqdata",
+            "captcha_rqtoken": "rqtoken"
+        })
+        .to_string()
+    };
+    let mut server = mockito::Server::new_async().await;
+    let _challenge = server
+        .mock("POST", "/v10/users/@me/channels")
+        .match_header("X-Captcha-Key", Matcher::Missing)
+        .with_status(400)
+        .with_body(captcha("captcha-required"))
+        .create_async()
+        .await;
+    let _rejected = server
+        .mock("POST", "/v10/users/@me/channels")
+        .match_header("X-Captcha-Key", "bad")
+        .with_status(400)
+        .with_body(captcha("invalid-response"))
+        .create_async()
+        .await;
+    let accepted = server
+        .mock("POST", "/v10/users/@me/channels")
+        .match_header("X-Captcha-Key", "good")
+        .match_header("X-Captcha-RqToken", "rqtoken")
+        .match_body(Matcher::Json(
+            json!({ "recipients": ["1"], "captcha_key": "good" }),
+        ))
+        .with_body(r#"{"id":"2"}"#)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let url = http::api_url("/users/@me/channels");
+    let calls = Arc::new(AtomicU32::new(0));
+    let handler_calls = calls.clone();
+    let http = diself::HttpClient::new("token")
+        .with_api_base_url(server.url())
+        .with_captcha_retries(2)
+        .with_captcha_handler(move |info| {
+            let calls = handler_calls.clone();
+            async move {
+                assert_eq!(info.captcha_rqdata.as_deref(), Some("rqdata"));
+                assert_eq!(info.site_url.as_deref(), Some("https://discord.com/"));
+                // The first key is rejected, the second accepted
+                let key = match calls.fetch_add(1, Ordering::SeqCst) {
+                    0 => "bad",
+                    _ => "good",
+                };
+                Ok(key.to_string())
+            }
+        });
+    let body = json!({ "recipients": ["1"] });
+    let response = http.post(&url, body.clone()).await.unwrap();
+    assert_eq!(response["id"], "2");
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    accepted.assert_async().await;
+
+    let http = diself::HttpClient::new("token")
+        .with_api_base_url(server.url())
+        .with_captcha_handler(|_| async { Ok("bad".to_string()) });
+    let error = http.post(&url, body).await.unwrap_err();
+    let diself::Error::CaptchaRejected { info, attempts } = error else {
+        panic!("expected a rejected captcha, got {error:?}");
+    };
+    assert_eq!(attempts, 1);
+    assert_eq!(info.captcha_key, vec!["invalid-response"]);
+}
+
+#[tokio::test]
+async fn audit_log_reason_is_sent_percent_encoded() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("DELETE", "/guilds/1/members/2")
+        .match_header("X-Audit-Log-Reason", "spam%20%26%20raids%20%F0%9F%9A%AB")
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let http = diself::HttpClient::new("token");
+    http.delete_with_reason(
+        format!("{}/guilds/1/members/2", server.url()),
+        Some("spam & raids 🚫"),
+    )
+    .await
+    .unwrap();
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn timeouts_longer_than_28_days_are_rejected() {
+    let http = diself::HttpClient::new("token");
+    let error = diself::GuildsManager
+        .timeout_member_for(
+            &http,
+            "1",
+            "2",
+            std::time::Duration::from_secs(29 * 24 * 60 * 60),
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(error, diself::Error::InvalidArgument(_)));
+}
+
+#[tokio::test]
+async fn mock_transport_serves_context_and_managers() {
+    use diself::http::{HttpResponse, Method, MockTransport};
+    use serde_json::json;
+
+    let mock = MockTransport::new();
+    mock.on(
+        Method::GET,
+        "/users/@me",
+        json!({ "id": "1", "username": "me", "discriminator": "0" }),
+    );
+    mock.on(
+        Method::PATCH,
+        "/guilds/1/members/2",
+        json!({
+            "user": { "id": "2", "username": "member", "discriminator": "0" },
+            "roles": [],
+            "joined_at": "2026-01-01T00:00:00.000Z",
+            "flags": 0
+        }),
+    );
+    mock.respond(
+        Method::DELETE,
+        "/channels/3",
+        HttpResponse::json(404, &json!({ "message": "Unknown Channel", "code": 10003 })),
+    );
+
+    let http = diself::HttpClient::new("token").with_transport(mock.clone());
+    let ctx = diself::Context::create(http, diself::Cache::new())
+        .await
+        .unwrap();
+    assert_eq!(ctx.user.id, "1");
+
+    ctx.guilds
+        .remove_timeout(&ctx.http, "1", "2", Some("appeal"))
+        .await
+        .unwrap();
+    let request = mock.last_request().unwrap();
+    assert_eq!(request.method, Method::PATCH);
+    assert_eq!(request.header("authorization"), Some("token"));
+    assert_eq!(request.header("X-Audit-Log-Reason"), Some("appeal"));
+    assert_eq!(
+        request.body,
+        Some(json!({ "communication_disabled_until": null }))
+    );
+
+    assert!(ctx.http.delete(http::api_url("/channels/3")).await.is_err());
+    assert!(ctx.http.get(http::api_url("/unmocked")).await.is_err());
+    assert_eq!(mock.requests().len(), 4);
+}
+
+#[tokio::test]
+async fn api_base_url_redirects_api_requests() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/proxy/api/v10/users/@me")
+        .with_status(200)
+        .with_body(r#"{"id":"1"}"#)
+        .create_async()
+        .await;
+
+    let http =
+        diself::HttpClient::new("token").with_api_base_url(format!("{}/proxy/api/", server.url()));
+    assert_eq!(
+        http.api_url("/users/@me"),
+        format!("{}/proxy/api/v10/users/@me", server.url())
+    );
+
+    let me = http.get(http::api_url("/users/@me")).await.unwrap();
+    assert_eq!(me["id"], "1");
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn message_helpers_send_builders_and_references() {
+    use diself::http::{Method, MockTransport};
+    use diself::model::{CreateMessage, EditMessage, Message};
+    use serde_json::json;
+
+    let message = json!({
+        "id": "10",
+        "channel_id": "20",
+        "guild_id": "30",
+        "author": { "id": "1", "username": "me", "discriminator": "0" },
+        "content": "hi",
+        "timestamp": "2026-01-01T00:00:00.000Z",
+        "edited_timestamp": null,
+        "type": 0,
+        "flags": 1 << 2 | 1 << 1
+    });
+    let mock = MockTransport::new();
+    mock.on(Method::POST, "/channels/20/messages", message.clone());
+    mock.on(Method::POST, "/channels/40/messages", message.clone());
+    mock.on(Method::PATCH, "/channels/20/messages/10", message.clone());
+    let http = diself::HttpClient::new("token").with_transport(mock.clone());
+    let msg: Message = serde_json::from_value(message).unwrap();
+
+    msg.reply_with(&http, CreateMessage::new().content("pong").silent())
+        .await
+        .unwrap();
+    let body = mock.last_request().unwrap().body.unwrap();
+    assert_eq!(body["content"], "pong");
+    assert_eq!(body["flags"], 1 << 12);
+    assert_eq!(body["message_reference"]["message_id"], "10");
+    assert_eq!(body["message_reference"]["type"], 0);
+
+    msg.edit(&http, EditMessage::new().suppress_embeds(true))
+        .await
+        .unwrap();
+    assert_eq!(
+        mock.last_request().unwrap().body,
+        Some(json!({ "flags": 6 }))
+    );
+    // Only SUPPRESS_EMBEDS is cleared, the other flags of the message are kept
+    msg.edit(&http, EditMessage::new().suppress_embeds(false))
+        .await
+        .unwrap();
+    assert_eq!(
+        mock.last_request().unwrap().body,
+        Some(json!({ "flags": 2 }))
+    );
+    msg.edit(&http, "edited").await.unwrap();
+    assert_eq!(
+        mock.last_request().unwrap().body,
+        Some(json!({ "content": "edited" }))
+    );
+
+    msg.forward_to(&http, "40").await.unwrap();
+    let request = mock.last_request().unwrap();
+    assert!(request.url.ends_with("/channels/40/messages"));
+    let reference = &request.body.unwrap()["message_reference"];
+    assert_eq!(reference["type"], 1);
+    assert_eq!(reference["guild_id"], "30");
+}
+
+#[tokio::test]
+async fn default_allowed_mentions_apply_to_message_bodies_only() {
+    use diself::http::{Method, MockTransport};
+    use diself::model::{AllowedMentions, CreateMessage};
+    use serde_json::json;
+
+    let mock = MockTransport::new();
+    mock.on(Method::POST, "/channels/20/messages", json!({}));
+    mock.on(Method::PATCH, "/channels/20/messages/10", json!({}));
+    mock.on(Method::PATCH, "/channels/20", json!({}));
+    let http = diself::HttpClient::new("token")
+        .with_transport(mock.clone())
+        .with_default_allowed_mentions(AllowedMentions::none().user("1"));
+
+    http.post(
+        http::api_url("/channels/20/messages"),
+        json!({ "content": "hi" }),
+    )
+    .await
+    .unwrap();
+    let body = mock.last_request().unwrap().body.unwrap();
+    assert_eq!(
+        body["allowed_mentions"],
+        json!({ "parse": [], "users": ["1"], "replied_user": false })
+    );
+
+    let explicit = CreateMessage::new()
+        .content("@everyone")
+        .allowed_mentions(AllowedMentions::none().everyone());
+    http.post(http::api_url("/channels/20/messages"), explicit)
+        .await
+        .unwrap();
+    let body = mock.last_request().unwrap().body.unwrap();
+    assert_eq!(body["allowed_mentions"]["parse"], json!(["everyone"]));
+
+    http.patch(
+        http::api_url("/channels/20/messages/10"),
+        json!({ "content": "edited" }),
+    )
+    .await
+    .unwrap();
+    let body = mock.last_request().unwrap().body.unwrap();
+    assert_eq!(body["allowed_mentions"]["users"], json!(["1"]));
+
+    http.patch(http::api_url("/channels/20"), json!({ "name": "general" }))
+        .await
+        .unwrap();
+    let body = mock.last_request().unwrap().body.unwrap();
+    assert!(body.get("allowed_mentions").is_none());
+}
+
+#[tokio::test]
+async fn invites_parse_metadata_and_resolve_from_messages() {
+    use diself::http::{HttpResponse, Method, MockTransport};
+    use diself::model::{InviteType, Message};
+    use serde_json::json;
+
+    let mock = MockTransport::new();
+    mock.on(
+        Method::GET,
+        "/invites/rust",
+        json!({
+            "type": 0,
+            "code": "rust",
+            "guild": { "id": "1", "name": "Rust", "features": ["COMMUNITY"] },
+            "channel": { "id": "2", "type": 0, "name": "general" },
+            "approximate_member_count": 120,
+            "approximate_presence_count": 30,
+            "expires_at": null
+        }),
+    );
+    mock.respond(
+        Method::GET,
+        "/invites/gone",
+        HttpResponse::json(404, &json!({ "message": "Unknown Invite", "code": 10006 })),
+    );
+    mock.on(
+        Method::GET,
+        "/channels/2/invites",
+        json!([{
+            "code": "abc",
+            "uses": 5,
+            "max_uses": 5,
+            "max_age": 0,
+            "temporary": false,
+            "created_at": "2026-01-01T00:00:00+00:00"
+        }]),
+    );
+    let http = diself::HttpClient::new("token").with_transport(mock.clone());
+
+    let message: Message = serde_json::from_value(json!({
+        "id": "10",
+        "channel_id": "2",
+        "author": { "id": "1", "username": "me", "discriminator": "0" },
+        "content": "join https://discord.gg/rust or discord.com/invite/gone, again discord.gg/rust!",
+        "timestamp": "2026-01-01T00:00:00.000Z",
+        "edited_timestamp": null,
+        "type": 0
+    }))
+    .unwrap();
+    assert_eq!(message.invite_codes(), vec!["rust", "gone"]);
+
+    let invites = message.resolve_invites(&http).await.unwrap();
+    assert_eq!(invites.len(), 1);
+    let invite = &invites[0];
+    assert_eq!(invite.kind, Some(InviteType::Guild));
+    assert_eq!(invite.guild.as_ref().unwrap().name, "Rust");
+    assert_eq!(invite.approximate_member_count, Some(120));
+    assert_eq!(invite.url(), "https://discord.gg/rust");
+    assert!(!invite.is_expired());
+
+    let listed = diself::InvitesManager
+        .channel_invites(&http, "2")
+        .await
+        .unwrap();
+    assert_eq!(listed[0].metadata.uses, Some(5));
+    assert!(listed[0].is_expired());
+
+    // Errors other than unknown invites are not swallowed
+    mock.reset();
+    mock.respond(
+        Method::GET,
+        "/invites/rust",
+        HttpResponse::json(403, &json!({ "message": "Missing Access", "code": 50001 })),
+    );
+    let error = message.resolve_invites(&http).await.unwrap_err();
+    assert!(matches!(
+        error,
+        diself::Error::ClientError {
+            status: 403,
+            code: Some(50001),
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn guild_preview_and_discovery_join() {
+    use diself::http::{Method, MockTransport};
+    use serde_json::json;
+
+    let mock = MockTransport::new();
+    mock.on(
+        Method::GET,
+        "/guilds/1/preview",
+        json!({
+            "id": "1",
+            "name": "Rust",
+            "icon": null,
+            "splash": null,
+            "discovery_splash": null,
+            "features": ["DISCOVERABLE", "SOME_NEW_FEATURE"],
+            "emojis": [],
+            "stickers": [],
+            "approximate_member_count": 1000,
+            "approximate_presence_count": 250,
+            "description": "A guild about Rust"
+        }),
+    );
+    mock.on(Method::PUT, "/guilds/1/members/@me", json!({ "id": "1" }));
+    let http = diself::HttpClient::new("token").with_transport(mock.clone());
+
+    let preview = diself::GuildsManager.preview(&http, "1").await.unwrap();
+    assert_eq!(preview.name, "Rust");
+    assert_eq!(preview.approximate_presence_count, Some(250));
+    assert!(preview.features.iter().any(|f| f == "DISCOVERABLE"));
+
+    diself::GuildsManager
+        .join_guild_via_discovery(&http, "1", true, Some("abc"))
+        .await
+        .unwrap();
+    let request = mock.last_request().unwrap();
+    assert!(request
+        .url
+        .ends_with("/guilds/1/members/@me?lurker=true&session_id=abc"));
+}
+
+#[tokio::test]
+async fn bans_take_options_and_bulk_bans_are_typed() {
+    use diself::http::{HttpResponse, Method, MockTransport};
+    use diself::BanOptions;
+    use serde_json::json;
+
+    let mock = MockTransport::new();
+    mock.respond(Method::PUT, "/guilds/1/bans/2", HttpResponse::no_content());
+    mock.on(
+        Method::POST,
+        "/guilds/1/bulk-ban",
+        json!({ "banned_users": ["2"], "failed_users": ["3"] }),
+    );
+    let http = diself::HttpClient::new("token").with_transport(mock.clone());
+    let options = BanOptions {
+        delete_message_seconds: Some(3600),
+        reason: Some("spam".to_string()),
+    };
+
+    diself::GuildsManager
+        .ban_member(&http, "1", "2", options.clone())
+        .await
+        .unwrap();
+    let request = mock.last_request().unwrap();
+    assert_eq!(request.header("X-Audit-Log-Reason"), Some("spam"));
+    assert_eq!(
+        request.body,
+        Some(json!({ "delete_message_seconds": 3600 }))
+    );
+
+    let result = diself::GuildsManager
+        .bulk_ban_members(&http, "1", vec!["2".into(), "3".into()], options)
+        .await
+        .unwrap();
+    assert_eq!(result.banned_users, vec!["2"]);
+    assert_eq!(result.failed_users, vec!["3"]);
+    let body = mock.last_request().unwrap().body.unwrap();
+    assert_eq!(body["user_ids"], json!(["2", "3"]));
+}
+
+#[tokio::test]
+async fn thread_listings_are_typed() {
+    use diself::http::{Method, MockTransport};
+    use serde_json::json;
+
+    let mock = MockTransport::new();
+    mock.on(
+        Method::GET,
+        "/channels/1/threads/archived/public",
+        json!({
+            "threads": [{ "id": "10", "type": 11, "parent_id": "1", "name": "old" }],
+            "members": [{ "id": "10", "user_id": "2", "join_timestamp": "2024-01-01T00:00:00.000000+00:00", "flags": 0 }],
+            "has_more": true
+        }),
+    );
+    let http = diself::HttpClient::new("token").with_transport(mock.clone());
+
+    let listing = diself::ChannelsManager
+        .public_archived_threads(&http, "1", None, Some(50))
+        .await
+        .unwrap();
+    assert_eq!(listing.threads[0].id, "10");
+    assert_eq!(listing.members.len(), 1);
+    assert!(listing.has_more);
+    assert_eq!(listing.total_results, None);
+    assert!(mock
+        .last_request()
+        .unwrap()
+        .url
+        .ends_with("/channels/1/threads/archived/public?limit=50"));
+}
+
+#[tokio::test]
+async fn forum_posts_nest_the_starter_message() {
+    use diself::http::{Method, MockTransport};
+    use diself::model::CreateForumPost;
+    use serde_json::json;
+
+    let mock = MockTransport::new();
+    mock.on(
+        Method::POST,
+        "/channels/1/threads",
+        json!({ "id": "10", "type": 11, "parent_id": "1", "name": "Bug report" }),
+    );
+    let http = diself::HttpClient::new("token").with_transport(mock.clone());
+    let post = CreateForumPost::new("Bug report")
+        .content("It crashes")
+        .uploaded_attachment("log.txt", "123/0/log.txt")
+        .applied_tag("5");
+
+    let thread = diself::ChannelsManager
+        .create_forum_post(&http, "1", post)
+        .await
+        .unwrap();
+    assert_eq!(thread.id, "10");
+    assert_eq!(
+        mock.last_request().unwrap().body,
+        Some(json!({
+            "name": "Bug report",
+            "message": {
+                "content": "It crashes",
+                "attachments": [
+                    { "id": "0", "filename": "log.txt", "uploaded_filename": "123/0/log.txt" }
+                ]
+            },
+            "applied_tags": ["5"]
+        }))
+    );
+}
+
+#[tokio::test]
+async fn edit_profile_splits_user_and_profile_fields() {
+    use diself::http::{Method, MockTransport};
+    use diself::model::EditProfile;
+    use serde_json::json;
+
+    let mock = MockTransport::new();
+    mock.on(Method::PATCH, "/users/@me/profile", json!({}));
+    mock.on(
+        Method::PATCH,
+        "/users/@me",
+        json!({ "id": "1", "username": "me", "discriminator": "0", "global_name": "Ferris" }),
+    );
+    let http = diself::HttpClient::new("token").with_transport(mock.clone());
+
+    let profile = EditProfile::new()
+        .global_name("Ferris")
+        .remove_avatar_decoration()
+        .bio("Rustacean")
+        .theme_colors(0xF74C00, 0x000000);
+    let user = diself::UsersManager
+        .edit_profile(&http, profile)
+        .await
+        .unwrap();
+    assert_eq!(user.global_name.as_deref(), Some("Ferris"));
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(
+        requests[0].body,
+        Some(json!({ "bio": "Rustacean", "theme_colors": [0xF74C00, 0] }))
+    );
+    assert_eq!(
+        requests[1].body,
+        Some(json!({
+            "global_name": "Ferris",
+            "avatar_decoration_id": null,
+            "avatar_decoration_sku_id": null
+        }))
+    );
+
+    // Invalid edits are rejected before any request
+    let error = diself::UsersManager
+        .edit_profile(&http, EditProfile::new().pronouns("x".repeat(41)))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, diself::Error::InvalidArgument(_)));
+    assert!(EditProfile::new()
+        .accent_color(0x1000000)
+        .validate()
+        .is_err());
+    assert_eq!(mock.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn account_changes_send_the_password() {
+    use diself::http::{Method, MockTransport};
+    use serde_json::json;
+
+    let mock = MockTransport::new();
+    mock.on(
+        Method::PATCH,
+        "/users/@me",
+        json!({ "id": "1", "username": "ferris", "discriminator": "0", "token": "new-token" }),
+    );
+    let http = diself::HttpClient::new("token").with_transport(mock.clone());
+    let users = diself::UsersManager;
+
+    let user = users
+        .change_username(&http, "ferris", "hunter2")
+        .await
+        .unwrap();
+    assert_eq!(user.username, "ferris");
+    assert_eq!(
+        mock.last_request().unwrap().body,
+        Some(json!({ "username": "ferris", "password": "hunter2" }))
+    );
+
+    users
+        .change_email(
+            &http,
+            "ferris@rust-lang.org",
+            "hunter2",
+            Some("email-token"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        mock.last_request().unwrap().body,
+        Some(json!({
+            "email": "ferris@rust-lang.org",
+            "password": "hunter2",
+            "email_token": "email-token"
+        }))
+    );
+
+    let token = users
+        .change_password(&http, "hunter2", "correct horse")
+        .await
+        .unwrap();
+    assert_eq!(token, "new-token");
+    assert_eq!(
+        mock.last_request().unwrap().body,
+        Some(json!({ "password": "hunter2", "new_password": "correct horse" }))
+    );
+
+    // Missing passwords and invalid usernames are rejected before any request
+    let error = users
+        .change_username(&http, "ferris", "")
+        .await
+        .unwrap_err();
+    assert!(matches!(error, diself::Error::InvalidArgument(_)));
+    let error = users
+        .change_username(&http, "f", "hunter2")
+        .await
+        .unwrap_err();
+    assert!(matches!(error, diself::Error::InvalidArgument(_)));
+    assert_eq!(mock.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn broadcast_reports_failures_without_stopping() {
+    use diself::http::{HttpResponse, Method, MockTransport, RetryPolicy};
+    use diself::model::CreateMessage;
+    use diself::BulkReport;
+    use serde_json::json;
+    use std::time::Duration;
+
+    let mock = MockTransport::new();
+    mock.on(
+        Method::GET,
+        "/users/@me",
+        json!({ "id": "1", "username": "me", "discriminator": "0" }),
+    );
+    mock.on(
+        Method::POST,
+        "/channels/10/messages",
+        json!({
+            "id": "100",
+            "channel_id": "10",
+            "content": "Hello",
+            "author": { "id": "1", "username": "me", "discriminator": "0" },
+            "timestamp": "2026-01-01T00:00:00.000Z",
+            "type": 0
+        }),
+    );
+    mock.respond(
+        Method::POST,
+        "/channels/20/messages",
+        HttpResponse::json(403, &json!({ "message": "Missing Access", "code": 50001 })),
+    );
+    mock.respond(
+        Method::POST,
+        "/channels/30/messages",
+        HttpResponse::json(
+            429,
+            &json!({ "message": "You are being rate limited.", "retry_after": 0.01 }),
+        ),
+    );
+    let http = diself::HttpClient::new("token")
+        .with_transport(mock.clone())
+        .with_retry_policy(RetryPolicy::disabled());
+    let ctx = diself::Context::create(http, diself::Cache::new())
+        .await
+        .unwrap();
+
+    let report = ctx
+        .broadcast_with_interval(
+            ["20", "10", "30", "10"],
+            CreateMessage::new().content("Hello"),
+            Duration::ZERO,
+        )
+        .await;
+    assert!(!report.is_complete());
+    assert_eq!(report.sent.len(), 1);
+    assert_eq!(report.sent[0].channel_id, "10");
+    assert_eq!(report.failed_ids(), ["20", "30"]);
+    assert!(matches!(
+        report.failed[1].1,
+        diself::Error::RateLimit { .. }
+    ));
+
+    // 1 user fetch, 1 send to 20 and 10, and 1 + 3 retries to 30
+    let sends = mock.requests();
+    assert_eq!(sends.len(), 7);
+    assert_eq!(sends[2].body, Some(json!({ "content": "Hello" })));
+}
+
+#[tokio::test]
+async fn sticker_packs_and_stickers_are_fetched_and_sent() {
+    use diself::http::{Method, MockTransport};
+    use diself::model::{CreateMessage, StickerFormatType, StickerType};
+    use serde_json::json;
+
+    let wave = json!({
+        "id": "749054660769218631",
+        "pack_id": "847199849233514549",
+        "name": "Wave",
+        "description": "Wumpus waves hello",
+        "tags": "wumpus, hello, wave",
+        "type": 1,
+        "format_type": 3,
+        "sort_value": 12
+    });
+    let mock = MockTransport::new();
+    mock.on(
+        Method::GET,
+        "/sticker-packs",
+        json!({
+            "sticker_packs": [{
+                "id": "847199849233514549",
+                "stickers": [wave.clone()],
+                "name": "Wumpus Beyond",
+                "sku_id": "847199849233514547",
+                "cover_sticker_id": "749054660769218631",
+                "description": "Say hello to Wumpus!",
+                "banner_asset_id": "761773777976819732"
+            }]
+        }),
+    );
+    mock.on(Method::GET, "/stickers/749054660769218631", wave);
+    mock.on(Method::POST, "/channels/20/messages", json!({}));
+    let http = diself::HttpClient::new("token").with_transport(mock.clone());
+    let stickers = diself::StickersManager;
+
+    let packs = stickers.packs(&http).await.unwrap();
+    assert_eq!(packs.len(), 1);
+    assert_eq!(packs[0].cover_sticker().unwrap().name, "Wave");
+    assert!(packs[0]
+        .banner_url()
+        .unwrap()
+        .ends_with("/761773777976819732.png"));
+
+    let sticker = stickers.get(&http, "749054660769218631").await.unwrap();
+    assert_eq!(sticker.kind, Some(StickerType::Standard));
+    assert_eq!(sticker.format_type, StickerFormatType::Lottie);
+    assert!(sticker.available && !sticker.is_guild_sticker());
+    let future: diself::model::Sticker =
+        serde_json::from_value(json!({ "id": "1", "name": "New", "format_type": 9 })).unwrap();
+    assert_eq!(future.format_type, StickerFormatType::Unknown(9));
+    assert_eq!(serde_json::to_value(&future).unwrap()["format_type"], 9);
+    assert_eq!(
+        sticker.url(),
+        "https://cdn.discordapp.com/stickers/749054660769218631.json"
+    );
+
+    http.post(
+        http::api_url("/channels/20/messages"),
+        CreateMessage::new().content("hi").sticker(&sticker.id),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        mock.last_request().unwrap().body,
+        Some(json!({ "content": "hi", "sticker_ids": ["749054660769218631"] }))
+    );
+}
+
+#[tokio::test]
+async fn available_guild_tags_skip_guilds_without_tag() {
+    use diself::http::{Method, MockTransport};
+    use serde_json::json;
+
+    let mock = MockTransport::new();
+    mock.on(
+        Method::GET,
+        "/guilds/1/profile",
+        json!({ "id": "1", "name": "Rust", "tag": "RUST", "badge_hash": "abc", "visibility": 1 }),
+    );
+    mock.on(
+        Method::GET,
+        "/guilds/2/profile",
+        json!({ "id": "2", "name": "No tag", "tag": null }),
+    );
+    let http = diself::HttpClient::new("token").with_transport(mock.clone());
+
+    let tags = diself::UsersManager
+        .available_guild_tags(&http, ["1", "2"])
+        .await
+        .unwrap();
+    assert_eq!(tags.len(), 1);
+    assert_eq!(tags[0].guild_id, "1");
+    assert_eq!(tags[0].badge.as_deref(), Some("abc"));
+}
+
+#[tokio::test]
+async fn boost_slots_pick_the_first_available_slot() {
+    use diself::http::{Method, MockTransport};
+    use diself::model::SubscriptionStatus;
+    use serde_json::json;
+
+    let mock = MockTransport::new();
+    mock.on(
+        Method::GET,
+        "/users/@me/guilds/premium/subscription-slots",
+        json!([
+            {
+                "id": "1",
+                "subscription_id": "9",
+                "premium_guild_subscription": {
+                    "id": "100", "guild_id": "5", "user_id": "7", "ended": false
+                },
+                "canceled": false,
+                "cooldown_ends_at": null
+            },
+            {
+                "id": "2",
+                "subscription_id": "9",
+                "premium_guild_subscription": null,
+                "canceled": false,
+                "cooldown_ends_at": "2999-01-01T00:00:00.000Z"
+            },
+            {
+                "id": "3",
+                "subscription_id": "9",
+                "premium_guild_subscription": null,
+                "canceled": false,
+                "cooldown_ends_at": "2020-01-01T00:00:00.000Z"
+            }
+        ]),
+    );
+    mock.on(
+        Method::PUT,
+        "/guilds/6/premium/subscriptions",
+        json!([{ "id": "101", "guild_id": "6", "user_id": "7", "ended": false }]),
+    );
+    mock.on(
+        Method::GET,
+        "/users/@me/billing/subscriptions",
+        json!([{
+            "id": "9",
+            "type": 1,
+            "status": 3,
+            "items": [{ "id": "10", "plan_id": "511651880837840896", "quantity": 1 }],
+            "current_period_end": "2999-01-01T00:00:00.000Z"
+        }, {
+            "id": "11",
+            "type": 1,
+            "status": 42
+        }]),
+    );
+    let http = diself::HttpClient::new("token").with_transport(mock.clone());
+
+    let boost = diself::PremiumManager
+        .use_boost_slot(&http, "6")
+        .await
+        .unwrap();
+    assert_eq!(boost.guild_id, "6");
+    assert_eq!(
+        mock.last_request().unwrap().body,
+        Some(json!({ "user_premium_guild_subscription_slot_ids": ["3"] }))
+    );
+
+    let subscriptions = diself::PremiumManager.subscriptions(&http).await.unwrap();
+    assert!(subscriptions[0].is_active());
+    assert_eq!(subscriptions[0].items[0].quantity, 1);
+    assert_eq!(subscriptions[1].status, SubscriptionStatus::Unknown(42));
+    assert!(!subscriptions[1].is_active());
+}
+
+#[tokio::test]
+async fn gift_codes_resolve_and_redeem_from_links() {
+    use diself::http::{Method, MockTransport};
+    use diself::model::GiftCode;
+    use serde_json::json;
+
+    assert_eq!(
+        GiftCode::codes_in("free nitro https://discord.gift/AbC123xyz! and discord.com/gifts/Q9"),
+        vec!["AbC123xyz".to_string(), "Q9".to_string()]
+    );
+
+    let mock = MockTransport::new();
+    mock.on(
+        Method::GET,
+        "/entitlements/gift-codes/AbC123xyz",
+        json!({
+            "code": "AbC123xyz",
+            "sku_id": "521847234246082599",
+            "uses": 0,
+            "max_uses": 1,
+            "redeemed": false,
+            "expires_at": null,
+            "subscription_plan": {
+                "id": "511651880837840896",
+                "name": "Nitro Monthly",
+                "interval": 1
+            }
+        }),
+    );
+    mock.on(
+        Method::POST,
+        "/entitlements/gift-codes/AbC123xyz/redeem",
+        json!({
+            "id": "1",
+            "type": 3,
+            "sku_id": "521847234246082599",
+            "application_id": "521842831262875670"
+        }),
+    );
+    let http = diself::HttpClient::new("token").with_transport(mock.clone());
+
+    let gift = diself::GiftsManager
+        .resolve(&http, "https://discord.gift/AbC123xyz")
+        .await
+        .unwrap();
+    assert!(gift.is_redeemable());
+    assert_eq!(gift.subscription_plan.unwrap().name, "Nitro Monthly");
+    assert!(mock
+        .last_request()
+        .unwrap()
+        .url
+        .ends_with("?with_application=false&with_subscription_plan=true"));
+
+    let entitlement = diself::GiftsManager
+        .redeem(&http, "AbC123xyz", Some("5"))
+        .await
+        .unwrap();
+    assert_eq!(entitlement.kind, 3);
+    assert_eq!(
+        mock.last_request().unwrap().body,
+        Some(json!({ "channel_id": "5" }))
+    );
+}
+
+#[tokio::test]
+async fn activity_invites_target_embedded_applications() {
+    use diself::http::{Method, MockTransport};
+    use diself::model::EmbeddedApplication;
+    use serde_json::json;
+
+    let mock = MockTransport::new();
+    mock.on(
+        Method::GET,
+        "/activities/guilds/1/shelf",
+        json!({
+            "applications": [{
+                "id": EmbeddedApplication::PUTT_PARTY,
+                "name": "Putt Party",
+                "embedded_activity_config": { "premium_tier_requirement": 1 }
+            }]
+        }),
+    );
+    mock.on(
+        Method::POST,
+        "/channels/2/invites",
+        json!({
+            "type": 0,
+            "code": "abc",
+            "target_type": 2,
+            "target_application": { "id": EmbeddedApplication::PUTT_PARTY, "name": "Putt Party" }
+        }),
+    );
+    let http = diself::HttpClient::new("token").with_transport(mock.clone());
+
+    let shelf = diself::GuildsManager
+        .activity_shelf(&http, "1")
+        .await
+        .unwrap();
+    assert_eq!(shelf[0].name, "Putt Party");
+    assert!(!shelf[0].is_available_at_tier(0));
+    assert!(shelf[0].is_available_at_tier(2));
+
+    let invite = diself::InvitesManager
+        .create_activity_invite(&http, "2", EmbeddedApplication::PUTT_PARTY, 3600)
+        .await
+        .unwrap();
+    assert_eq!(invite.target_application.unwrap().name, "Putt Party");
+    assert_eq!(
+        mock.last_request().unwrap().body,
+        Some(json!({
+            "target_type": 2,
+            "target_application_id": "945737671223947305",
+            "max_age": 3600
+        }))
+    );
+}
+
+#[tokio::test]
+async fn oauth_add_bot_submits_guild_and_permissions() {
+    use diself::http::{Method, MockTransport};
+    use diself::model::{AuthorizeRequest, Permissions};
+    use serde_json::json;
+
+    let mock = MockTransport::new();
+    mock.on(
+        Method::GET,
+        "/oauth2/authorize",
+        json!({
+            "application": { "id": "1234", "name": "Helper", "bot_public": true },
+            "bot": { "id": "1234", "username": "Helper", "discriminator": "0", "bot": true },
+            "guilds": [{ "id": "5678", "name": "Home", "permissions": "8" }],
+            "authorized": false
+        }),
+    );
+    mock.on(
+        Method::POST,
+        "/oauth2/authorize",
+        json!({ "location": "https://discord.com/oauth2/authorized" }),
+    );
+    let http = diself::HttpClient::new("token").with_transport(mock.clone());
+
+    let request = AuthorizeRequest::bot("1234", Permissions::SEND_MESSAGES);
+    let info = diself::OAuthManager
+        .authorize_info(&http, &request)
+        .await
+        .unwrap();
+    assert!(info.application.bot_public);
+    assert!(info.guild("5678").is_some());
+
+    let location = diself::OAuthManager
+        .add_bot(&http, "1234", "5678", Permissions::SEND_MESSAGES)
+        .await
+        .unwrap();
+    assert_eq!(location, "https://discord.com/oauth2/authorized");
+    let request = mock.last_request().unwrap();
+    assert!(request.url.ends_with(
+        "/oauth2/authorize?client_id=1234&scope=bot%20applications.commands&permissions=2048"
+    ));
+    assert_eq!(
+        request.body,
+        Some(json!({ "authorize": true, "permissions": "2048", "guild_id": "5678" }))
+    );
+}
+
+#[tokio::test]
+async fn joining_from_an_invite_accepts_rules_only_screening() {
+    use diself::http::{Method, MockTransport};
+    use diself::model::JoinRequestStatus;
+    use serde_json::json;
+
+    let mock = MockTransport::new();
+    mock.on(
+        Method::POST,
+        "/invites/rust",
+        json!({
+            "code": "rust",
+            "guild_id": "1",
+            "guild": { "id": "1", "name": "Rust", "features": ["MEMBER_VERIFICATION_GATE_ENABLED"] }
+        }),
+    );
+    mock.on(
+        Method::GET,
+        "/guilds/1/member-verification",
+        json!({
+            "version": "2024-01-01T00:00:00.000000+00:00",
+            "form_fields": [
+                { "field_type": "TERMS", "label": "Rules", "values": ["Be nice"], "required": true }
+            ]
+        }),
+    );
+    mock.on(
+        Method::PUT,
+        "/guilds/1/requests/@me",
+        json!({ "id": "9", "guild_id": "1", "application_status": "APPROVED" }),
+    );
+    let http = diself::HttpClient::new("token").with_transport(mock.clone());
+
+    let join = diself::GuildsManager
+        .join(&http, "https://discord.gg/rust", false, None)
+        .await
+        .unwrap();
+    assert_eq!(join.guild_id, "1");
+    assert!(join.pending_verification.is_none());
+    assert_eq!(
+        join.join_request.unwrap().application_status,
+        JoinRequestStatus::Approved
+    );
+    assert_eq!(
+        serde_json::from_value::<JoinRequestStatus>(json!("ON_HOLD")).unwrap(),
+        JoinRequestStatus::Unknown
+    );
+
+    let requests = mock.requests();
+    assert!(requests[1].url.ends_with("invite_code=rust"));
+    assert_eq!(
+        requests[2].body,
+        Some(json!({
+            "version": "2024-01-01T00:00:00.000000+00:00",
+            "form_fields": [{
+                "field_type": "TERMS",
+                "label": "Rules",
+                "values": ["Be nice"],
+                "required": true,
+                "response": true
+            }]
+        }))
+    );
+}
+
+#[tokio::test]
+async fn calls_are_rung_and_declined() {
+    use diself::http::{Method, MockTransport};
+    use diself::model::Call;
+    use serde_json::json;
+
+    let mock = MockTransport::new();
+    mock.on(Method::POST, "/channels/30/call/ring", json!({}));
+    mock.on(Method::POST, "/channels/30/call/stop-ringing", json!({}));
+    let http = diself::HttpClient::new("token").with_transport(mock.clone());
+    let channels = diself::ChannelsManager;
+
+    channels
+        .ring_call(&http, "30", Some(vec!["2".to_string()]))
+        .await
+        .unwrap();
+    assert_eq!(
+        mock.last_request().unwrap().body,
+        Some(json!({ "recipients": ["2"] }))
+    );
+    channels.decline_call(&http, "30").await.unwrap();
+    let request = mock.last_request().unwrap();
+    assert!(request.url.ends_with("/channels/30/call/stop-ringing"));
+    assert_eq!(request.body, Some(json!({ "recipients": null })));
+
+    // Without a running gateway the voice state update can't be sent
+    let gateway = diself::gateway::GatewaySender::default();
+    assert!(channels.join_call(&gateway, "30").is_err());
+
+    let call: Call = serde_json::from_value(json!({
+        "channel_id": "30",
+        "message_id": "31",
+        "region": "rotterdam",
+        "ringing": ["1"],
+        "voice_states": [{ "user_id": "2", "channel_id": "30", "session_id": "s" }]
+    }))
+    .unwrap();
+    assert!(call.is_ringing("1"));
+    assert!(!call.is_ringing("2"));
+    assert_eq!(call.participants().collect::<Vec<_>>(), ["2"]);
+}
+
+#[tokio::test]
+async fn friend_suggestions_are_listed_and_dismissed() {
+    use diself::http::{HttpResponse, Method, MockTransport};
+    use serde_json::json;
+
+    let mock = MockTransport::new();
+    mock.on(
+        Method::GET,
+        "/friend-suggestions",
+        json!([{
+            "suggested_user": { "id": "5", "username": "suggested", "discriminator": "0" },
+            "reasons": [{ "type": 1, "platform_type": "contacts", "name": "Sam" }]
+        }]),
+    );
+    mock.respond(
+        Method::DELETE,
+        "/friend-suggestions/5",
+        HttpResponse::no_content(),
+    );
+    let http = diself::HttpClient::new("token").with_transport(mock.clone());
+
+    let suggestions = diself::RelationshipsManager
+        .friend_suggestions(&http)
+        .await
+        .unwrap();
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions[0].suggested_user.username, "suggested");
+    assert_eq!(suggestions[0].reasons[0].platform_type, "contacts");
+    assert!(!suggestions[0].from_suggested_user_contacts);
+
+    suggestions[0].dismiss(&http).await.unwrap();
+    let request = mock.last_request().unwrap();
+    assert_eq!(request.method, Method::DELETE);
+    assert!(request.url.ends_with("/friend-suggestions/5"));
+}
+
+#[tokio::test]
+async fn affinities_are_ranked_by_score() {
+    use diself::http::{Method, MockTransport};
+    use serde_json::json;
+
+    let mock = MockTransport::new();
+    mock.on(
+        Method::GET,
+        "/users/@me/affinities/users",
+        json!({
+            "user_affinities": [
+                { "user_id": "1", "affinity": 0.2 },
+                { "user_id": "2", "affinity": 7.5 },
+                { "user_id": "3", "affinity": 1.0 }
+            ],
+            "inverse_user_affinities": []
+        }),
+    );
+    mock.on(
+        Method::GET,
+        "/users/@me/affinities/guilds",
+        json!({ "guild_affinities": [{ "guild_id": "10", "affinity": 3.25 }] }),
+    );
+    let http = diself::HttpClient::new("token").with_transport(mock);
+    let users = diself::UsersManager;
+
+    let ranked: Vec<String> = users
+        .user_affinities(&http)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|affinity| affinity.user_id)
+        .collect();
+    assert_eq!(ranked, ["2", "3", "1"]);
+
+    let guilds = users.guild_affinities(&http).await.unwrap();
+    assert_eq!(guilds[0].guild_id, "10");
+    assert_eq!(guilds[0].affinity, 3.25);
+}
+
+#[tokio::test]
+async fn block_lists_are_exported_and_imported() {
+    use diself::client::BLOCK_LIST_VERSION;
+    use diself::http::{HttpResponse, Method, MockTransport};
+    use diself::{BlockList, BulkReport};
+    use serde_json::json;
+    use std::time::Duration;
+
+    let user =
+        |id: &str| json!({ "id": id, "username": format!("user_{id}"), "discriminator": "0" });
+    let mock = MockTransport::new();
+    mock.on(
+        Method::GET,
+        "/users/@me/relationships",
+        json!([
+            { "id": "1", "type": 2, "user": user("1") },
+            { "id": "2", "type": 1, "user": user("2") },
+            { "id": "3", "type": 1, "user": user("3"), "user_ignored": true }
+        ]),
+    );
+    let http = diself::HttpClient::new("token").with_transport(mock.clone());
+    let relationships = diself::RelationshipsManager;
+
+    let list = relationships.export_block_list(&http).await.unwrap();
+    assert_eq!(list.blocked.len(), 1);
+    assert_eq!(list.blocked[0].username.as_deref(), Some("user_1"));
+    assert_eq!(list.ignored[0].id, "3");
+
+    let path = std::env::temp_dir().join(format!("diself-block-list-{}.json", std::process::id()));
+    list.save(&path).await.unwrap();
+    let mut list = BlockList::load(&path).await.unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    // "1" is already blocked on the other account, "4" gets blocked and ignoring "3" fails
+    list.blocked.push(diself::BlockListEntry {
+        id: "4".to_string(),
+        username: None,
+    });
+    mock.on(
+        Method::GET,
+        "/users/@me/relationships",
+        json!([{ "id": "1", "type": 2 }]),
+    );
+    mock.respond(
+        Method::PUT,
+        "/users/@me/relationships/4",
+        HttpResponse::no_content(),
+    );
+    mock.respond(
+        Method::PUT,
+        "/users/@me/relationships/3/ignore",
+        HttpResponse::json(404, &json!({ "message": "Unknown User", "code": 10013 })),
+    );
+    let report = relationships
+        .import_block_list_with_interval(&http, &list, Duration::ZERO)
+        .await
+        .unwrap();
+    assert_eq!(report.skipped, ["1"]);
+    assert_eq!(report.blocked, ["4"]);
+    assert!(report.ignored.is_empty());
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].0, "3");
+    assert!(!report.is_complete());
+
+    let mut newer = list.clone();
+    newer.version = BLOCK_LIST_VERSION + 1;
+    newer.save(&path).await.unwrap();
+    let error = BlockList::load(&path).await.unwrap_err();
+    let _ = std::fs::remove_file(&path);
+    assert!(matches!(
+        error,
+        diself::Error::UnsupportedVersion {
+            found: 2,
+            supported: 1
+        }
+    ));
+}
+
+#[tokio::test]
+async fn burst_credits_and_premium_usage_are_fetched() {
+    use diself::http::{Method, MockTransport};
+    use serde_json::json;
+
+    let mock = MockTransport::new();
+    mock.on(
+        Method::GET,
+        "/users/@me/burst-credits",
+        json!({
+            "amount": 0,
+            "replenished_today": true,
+            "next_replenish_at": "2030-01-06T00:00:00+00:00"
+        }),
+    );
+    mock.on(
+        Method::GET,
+        "/users/@me/premium-usage",
+        json!({
+            "premium_usage": {
+                "nitro_sticker_sends": { "value": 3 },
+                "total_animated_emojis": { "value": 42 },
+                "hd_hours_streamed": { "value": 1 }
+            }
+        }),
+    );
+    let http = diself::HttpClient::new("token").with_transport(mock);
+    let premium = diself::PremiumManager;
+
+    let credits = premium.burst_credits(&http).await.unwrap();
+    assert!(!credits.has_credits());
+    assert!(credits.replenished_today);
+    assert!(!credits.next_replenish().unwrap().is_past());
+
+    let usage = premium.usage(&http).await.unwrap();
+    assert_eq!(usage.nitro_sticker_sends, 3);
+    assert_eq!(usage.total_animated_emojis, 42);
+    assert_eq!(usage.total_large_uploads, 0);
+}
+
+#[tokio::test]
+async fn ratelimit_hook_receives_route_and_retry_after() {
+    use diself::http::{HttpResponse, Method, MockTransport, RateLimitInfo, RetryPolicy};
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    let mock = MockTransport::new();
+    mock.respond(
+        Method::POST,
+        "/channels/123/messages",
+        HttpResponse::json(429, &json!({ "retry_after": 0.25, "global": true })),
+    );
+    let seen: Arc<Mutex<Vec<RateLimitInfo>>> = Arc::default();
+    let hook_seen = seen.clone();
+    let http = diself::HttpClient::new("token")
+        .with_transport(mock.clone())
+        .with_retry_policy(RetryPolicy::disabled())
+        .with_ratelimit_hook(move |info| hook_seen.lock().unwrap().push(info.clone()));
+
+    let url = http::api_url("/channels/123/messages?limit=1");
+    let error = http.post(&url, json!({})).await.unwrap_err();
+    assert!(matches!(error, diself::Error::RateLimit { .. }));
+
+    {
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 1);
+        assert_eq!(seen[0].method, Method::POST);
+        assert_eq!(seen[0].route, "/channels/{id}/messages");
+        assert_eq!(seen[0].retry_after, Duration::from_millis(250));
+        assert!(seen[0].global);
+    }
+
+    // Codes, emojis and tokens never reach the route
+    let limited = HttpResponse::json(429, &json!({ "retry_after": 1 }));
+    for path in [
+        "/entitlements/gift-codes/SECRETGIFT/redeem",
+        "/invites/rust",
+        "/channels/1/messages/2/reactions/%F0%9F%91%8B/@me",
+        "/webhooks/3/SECRETTOKEN",
+    ] {
+        mock.respond(Method::POST, path, limited.clone());
+        let _ = http.post(http::api_url(path), json!({})).await;
+    }
+    let routes: Vec<String> = seen
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|info| info.route.clone())
+        .collect();
+    assert_eq!(
+        routes,
+        [
+            "/channels/{id}/messages",
+            "/entitlements/gift-codes/{code}/redeem",
+            "/invites/{code}",
+            "/channels/{id}/messages/{id}/reactions/{emoji}/@me",
+            "/webhooks/{id}/{token}",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn builder_timeout_applies_to_requests() {
+    use diself::http::{HttpClient, HttpProtocol, RetryPolicy};
+    use std::time::Duration;
+    use tokio::net::TcpListener;
+
+    // Accepts connections but never answers
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let server = tokio::spawn(async move {
+        let mut sockets = Vec::new();
+        while let Ok((socket, _)) = listener.accept().await {
+            sockets.push(socket);
+        }
+    });
+
+    let http = HttpClient::builder("token")
+        .timeout(Duration::from_millis(200))
+        .connect_timeout(Duration::from_secs(1))
+        .pool_max_idle_per_host(1)
+        .protocol(HttpProtocol::Http1)
+        .build()
+        .unwrap()
+        .with_retry_policy(RetryPolicy::disabled())
+        .with_api_base_url(format!("http://127.0.0.1:{port}/api"));
+
+    let error = http.get(http::api_url("/users/@me")).await.unwrap_err();
+    assert!(matches!(error, diself::Error::Http(ref e) if e.is_timeout()));
+    server.abort();
+}
+
+#[tokio::test]
+async fn cdn_fetch_returns_bytes_and_fails_on_errors() {
+    let mut server = mockito::Server::new_async().await;
+    let image = server
+        .mock("GET", "/icons/1/hash.png")
+        .with_body([1_u8, 2, 3])
+        .create_async()
+        .await;
+    server
+        .mock("GET", "/missing.png")
+        .with_status(404)
+        .create_async()
+        .await;
+
+    let http = diself::HttpClient::new("token");
+    let bytes = diself::cdn::fetch(&http, format!("{}/icons/1/hash.png", server.url()))
+        .await
+        .unwrap();
+    assert_eq!(bytes, [1, 2, 3]);
+    image.assert_async().await;
+
+    let error = diself::cdn::fetch(&http, format!("{}/missing.png", server.url()))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        diself::Error::ClientError { status: 404, .. }
+    ));
+}
+
+#[tokio::test]
+async fn bans_iter_follows_the_cursor_until_a_short_page() {
+    use diself::http::{Method, MockTransport};
+    use futures::StreamExt;
+    use serde_json::json;
+
+    let bans = |ids: std::ops::Range<u64>| {
+        json!(ids
+            .map(|id| json!({
+                "reason": null,
+                "user": { "id": id.to_string(), "username": "banned", "discriminator": "0" }
+            }))
+            .collect::<Vec<_>>())
+    };
+
+    let mock = MockTransport::new();
+    mock.on(Method::GET, "/guilds/1/bans?limit=1000", bans(1..1001));
+    mock.on(
+        Method::GET,
+        "/guilds/1/bans?limit=1000&after=1000",
+        bans(1001..2001),
+    );
+    mock.on(
+        Method::GET,
+        "/guilds/1/bans?limit=1000&after=2000",
+        bans(2001..2004),
+    );
+
+    let http = diself::HttpClient::new("token").with_transport(mock.clone());
+    let ids: Vec<String> = diself::GuildsManager
+        .bans_iter(&http, "1")
+        .map(|ban| ban.unwrap().user.id)
+        .collect()
+        .await;
+
+    assert_eq!(ids.len(), 2003);
+    assert_eq!(ids.first().map(String::as_str), Some("1"));
+    assert_eq!(ids.last().map(String::as_str), Some("2003"));
+    // The short third page ends the stream without asking for a fourth
+    assert_eq!(mock.requests().len(), 3);
+}
@@ -0,0 +1,107 @@
+#![cfg(feature = "metrics")]
+
+use metrics::{Counter, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Records the counters by name and labels (`name{label=value,...}`)
+#[derive(Clone, Default)]
+struct CountingRecorder {
+    counters: Arc<Mutex<HashMap<String, Arc<AtomicU64>>>>,
+}
+
+impl CountingRecorder {
+    fn count(&self, key: &str) -> u64 {
+        self.counters
+            .lock()
+            .unwrap()
+            .get(key)
+            .map_or(0, |counter| counter.load(Ordering::SeqCst))
+    }
+}
+
+impl Recorder for CountingRecorder {
+    fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+        let labels: Vec<String> = key
+            .labels()
+            .map(|label| format!("{}={}", label.key(), label.value()))
+            .collect();
+        let name = format!("{}{{{}}}", key.name(), labels.join(","));
+        let counter = self
+            .counters
+            .lock()
+            .unwrap()
+            .entry(name)
+            .or_default()
+            .clone();
+        Counter::from_arc(counter)
+    }
+
+    fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+        Gauge::noop()
+    }
+
+    fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+        Histogram::noop()
+    }
+}
+
+#[tokio::test]
+async fn http_responses_and_rate_limits_are_counted() {
+    use diself::http::{self, HttpResponse, Method, MockTransport, RetryPolicy};
+    use serde_json::json;
+
+    let recorder = CountingRecorder::default();
+    metrics::set_global_recorder(recorder.clone()).unwrap();
+
+    let mock = MockTransport::new();
+    mock.on(Method::GET, "/users/42", json!({ "id": "42" }));
+    mock.on(
+        Method::POST,
+        "/entitlements/gift-codes/SECRETGIFT/redeem",
+        json!({}),
+    );
+    mock.respond(
+        Method::POST,
+        "/channels/1/messages",
+        HttpResponse::json(429, &json!({ "retry_after": 1.0, "global": false })),
+    );
+    let http = diself::HttpClient::new("token")
+        .with_transport(mock)
+        .with_retry_policy(RetryPolicy::disabled());
+
+    http.get(http::api_url("/users/42")).await.unwrap();
+    http.get(http::api_url("/users/42")).await.unwrap();
+    let url = http::api_url("/channels/1/messages");
+    assert!(http.post(&url, json!({ "content": "hi" })).await.is_err());
+    let url = http::api_url("/entitlements/gift-codes/SECRETGIFT/redeem");
+    http.post(&url, json!({})).await.unwrap();
+
+    assert_eq!(
+        recorder.count("diself_http_requests_total{method=GET,route=/users/{id},status=200}"),
+        2
+    );
+    assert_eq!(
+        recorder.count(
+            "diself_http_requests_total{method=POST,route=/channels/{id}/messages,status=429}"
+        ),
+        1
+    );
+    assert_eq!(
+        recorder.count("diself_http_rate_limits_total{route=/channels/{id}/messages,global=false}"),
+        1
+    );
+    assert_eq!(
+        recorder.count(
+            "diself_http_requests_total{method=POST,route=/entitlements/gift-codes/{code}/redeem,status=200}"
+        ),
+        1
+    );
+    let counters = recorder.counters.lock().unwrap();
+    assert!(!counters.keys().any(|name| name.contains("SECRETGIFT")));
+}
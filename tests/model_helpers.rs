@@ -1,4 +1,10 @@
-use diself::model::{Relationship, RelationshipType, User};
+use diself::model::{
+    ApplicationCommandIndex, ApplicationCommandInteractionOption, ApplicationCommandType, AuditLog,
+    AuditLogActionType, ButtonStyle, Channel, Component, ComponentType, Connection, ConnectionType,
+    CreateChannel, CreatePoll, CreateRole, EditChannel, EditRole, Guild, GuildFolder, Integration,
+    Member, Message, MessageReference, MessageReferenceType, Permissions, Poll, ReadyPayload,
+    Relationship, RelationshipAction, RelationshipType, RoleColors, SearchResults, Session, User,
+};
 use serde_json::json;
 
 fn sample_user() -> User {
@@ -31,6 +37,137 @@ fn user_helper_methods_work() {
     );
 }
 
+#[test]
+fn cdn_urls_pick_format_and_size() {
+    use diself::cdn::{self, ImageFormat};
+    use diself::model::Emoji;
+
+    let avatar = cdn::user_avatar("123", "a_hash");
+    assert!(avatar.is_animated());
+    assert_eq!(
+        avatar.to_string(),
+        "https://cdn.discordapp.com/avatars/123/a_hash.gif"
+    );
+    assert_eq!(
+        avatar.size(300).format(ImageFormat::WebP).to_string(),
+        "https://cdn.discordapp.com/avatars/123/a_hash.webp?size=512"
+    );
+    // Static assets have no GIF version
+    assert_eq!(
+        cdn::guild_icon("1", "hash")
+            .format(ImageFormat::Gif)
+            .to_string(),
+        "https://cdn.discordapp.com/icons/1/hash.png"
+    );
+
+    let user: User = serde_json::from_value(json!({
+        "id": "80351110224678912",
+        "username": "nelly",
+        "discriminator": "0"
+    }))
+    .unwrap();
+    assert_eq!(
+        user.display_avatar_url(),
+        "https://cdn.discordapp.com/embed/avatars/5.png"
+    );
+    assert_eq!(sample_user().default_avatar_index(), 1);
+
+    // As sent by Discord, with the icon hash under `icon`
+    let guild: Guild = serde_json::from_value(json!({
+        "id": "9",
+        "name": "Rust",
+        "icon": "a_icon",
+        "splash": null,
+        "banner": "banner",
+        "owner_id": "1",
+        "features": []
+    }))
+    .unwrap();
+    assert_eq!(
+        guild.icon_url().as_deref(),
+        Some("https://cdn.discordapp.com/icons/9/a_icon.gif")
+    );
+    assert_eq!(
+        guild.banner_url().as_deref(),
+        Some("https://cdn.discordapp.com/banners/9/banner.png")
+    );
+    assert!(guild.splash_url().is_none());
+
+    let emoji: Emoji =
+        serde_json::from_value(json!({ "id": "5", "name": "wave", "animated": true })).unwrap();
+    assert_eq!(
+        emoji.url().as_deref(),
+        Some("https://cdn.discordapp.com/emojis/5.gif")
+    );
+    let unicode: Emoji = serde_json::from_value(json!({ "id": null, "name": "👍" })).unwrap();
+    assert!(unicode.url().is_none());
+}
+
+#[test]
+fn emoji_usability_depends_on_nitro_and_roles() {
+    use diself::model::Emoji;
+
+    let emoji = |id: &str, animated: bool, roles: Vec<&str>| -> Emoji {
+        serde_json::from_value(json!({
+            "id": id,
+            "name": format!("e{id}"),
+            "animated": animated,
+            "available": true,
+            "roles": roles
+        }))
+        .unwrap()
+    };
+    let guild: Guild = serde_json::from_value(json!({
+        "id": "9",
+        "emojis": [
+            emoji("1", false, vec![]),
+            emoji("2", true, vec![]),
+            emoji("3", false, vec!["r1"])
+        ]
+    }))
+    .unwrap();
+    let user = |premium_type: u8| -> User {
+        serde_json::from_value(json!({
+            "id": "100",
+            "username": "u",
+            "discriminator": "0",
+            "premium_type": premium_type
+        }))
+        .unwrap()
+    };
+    let (free, nitro) = (user(0), user(2));
+    let member_roles = ["r1".to_string()];
+    let guild = Some(&guild);
+
+    // Partial emojis (as in reactions) are checked with the full emoji of the guild
+    let partial = |id: &str, animated: bool| emoji(id, animated, vec![]);
+    assert!(partial("1", false).is_usable_by(&free, guild, &[]));
+    assert!(!partial("2", true).is_usable_by(&free, guild, &[]));
+    assert!(partial("2", true).is_usable_by(&nitro, guild, &[]));
+    assert!(partial("3", false).is_usable_by(&free, guild, &member_roles));
+    assert!(!partial("3", false).is_usable_by(&nitro, guild, &[]));
+    // In DMs, custom emojis need Nitro
+    assert!(!partial("1", false).is_usable_by(&free, None, &[]));
+    assert!(partial("1", false).is_usable_by(&nitro, None, &[]));
+
+    let external = emoji("4", true, vec![]);
+    assert!(!external.is_usable_by(&free, guild, &[]));
+    assert!(external.is_usable_by(&nitro, guild, &[]));
+    let locked = emoji("5", false, vec!["r2"]);
+    assert!(!locked.is_usable_by(&nitro, guild, &member_roles));
+    let mut unavailable = emoji("6", false, vec![]);
+    unavailable.available = false;
+    assert!(!unavailable.is_usable_by(&nitro, guild, &[]));
+
+    assert_eq!(external.mention(), "<a:e4:4>");
+    assert_eq!(external.format_for(&nitro, guild, &[]), "<a:e4:4>");
+    assert_eq!(
+        external.format_for(&free, guild, &[]),
+        "https://cdn.discordapp.com/emojis/4.gif?size=64"
+    );
+    assert_eq!(emoji("1", false, vec![]).mention(), "<:e1:1>");
+}
+
 #[test]
 fn relationship_state_helpers_work() {
     let friend: Relationship = serde_json::from_value(json!({
@@ -57,3 +194,850 @@ fn relationship_state_helpers_work() {
     assert!(!blocked.is_friend());
     assert!(blocked.is_blocked());
 }
+
+#[test]
+fn audit_log_deserializes_entries_and_changes() {
+    let audit_log: AuditLog = serde_json::from_value(json!({
+        "audit_log_entries": [
+            {
+                "id": "e1",
+                "target_id": "u2",
+                "user_id": "u1",
+                "action_type": 24,
+                "changes": [
+                    { "key": "nick", "old_value": "old", "new_value": "new" }
+                ],
+                "reason": "cleanup"
+            },
+            { "id": "e2", "action_type": 999 }
+        ],
+        "users": [
+            { "id": "u1", "username": "moderator", "discriminator": "0" }
+        ]
+    }))
+    .expect("valid audit log json");
+
+    let entry = &audit_log.audit_log_entries[0];
+    assert_eq!(entry.action_type, AuditLogActionType::MemberUpdate);
+    assert_eq!(
+        audit_log.audit_log_entries[1].action_type,
+        AuditLogActionType::Unknown(999)
+    );
+    assert_eq!(
+        serde_json::to_value(AuditLogActionType::Unknown(999)).unwrap(),
+        999
+    );
+    assert_eq!(
+        entry.change("nick").and_then(|c| c.new_value.as_ref()),
+        Some(&json!("new"))
+    );
+    assert_eq!(
+        audit_log.user("u1").map(|u| u.username.as_str()),
+        Some("moderator")
+    );
+}
+
+#[test]
+fn application_command_index_finds_command_and_builds_interaction_data() {
+    let index: ApplicationCommandIndex = serde_json::from_value(serde_json::json!({
+        "applications": [{ "id": "42", "name": "Helper", "icon": null, "bot_id": "42" }],
+        "application_commands": [{
+            "id": "100",
+            "type": 1,
+            "application_id": "42",
+            "name": "echo",
+            "description": "Echo text",
+            "version": "7",
+            "options": [{ "type": 3, "name": "text", "description": "Text", "required": true }]
+        }],
+        "version": "1"
+    }))
+    .expect("index should deserialize");
+
+    assert!(index.find("echo", Some("99")).is_none());
+    let command = index
+        .find("echo", Some("42"))
+        .expect("command should exist");
+    assert_eq!(command.kind, ApplicationCommandType::ChatInput);
+    assert!(command.options[0].required);
+
+    let data = command.interaction_data(vec![ApplicationCommandInteractionOption::string(
+        "text", "hi",
+    )]);
+    assert_eq!(data["id"], "100");
+    assert_eq!(data["version"], "7");
+    assert_eq!(data["options"][0]["type"], 3);
+    assert_eq!(data["options"][0]["value"], "hi");
+    assert_eq!(data["application_command"]["name"], "echo");
+}
+
+#[test]
+fn message_components_parse_and_build_interaction_payload() {
+    let message: Message = serde_json::from_value(json!({
+        "id": "500",
+        "channel_id": "600",
+        "guild_id": "700",
+        "author": { "id": "42", "username": "helper", "discriminator": "0", "bot": true },
+        "content": "",
+        "timestamp": "2024-01-01T00:00:00+00:00",
+        "edited_timestamp": null,
+        "type": 0,
+        "flags": 64,
+        "components": [
+            {
+                "type": 1,
+                "components": [
+                    { "type": 2, "style": 1, "label": "Yes", "custom_id": "confirm" },
+                    { "type": 2, "style": 5, "label": "Docs", "url": "https://example.com" }
+                ]
+            },
+            {
+                "type": 17,
+                "components": [{
+                    "type": 1,
+                    "components": [{
+                        "type": 6,
+                        "custom_id": "pick_role",
+                        "min_values": 1,
+                        "max_values": 1
+                    }]
+                }]
+            }
+        ]
+    }))
+    .expect("message should deserialize");
+
+    assert!(matches!(message.components[1], Component::Unknown(_)));
+    assert_eq!(message.buttons().len(), 2);
+    let button = message.button("confirm").expect("button should exist");
+    assert_eq!(button.style, ButtonStyle::Primary);
+    let menu = message
+        .select_menu("pick_role")
+        .expect("nested select should be found");
+    assert_eq!(menu.kind, ComponentType::RoleSelect);
+
+    let body = message.component_interaction(
+        "session",
+        "1",
+        json!({ "component_type": 2, "custom_id": "confirm" }),
+    );
+    assert_eq!(body["type"], 3);
+    assert_eq!(body["guild_id"], "700");
+    assert_eq!(body["application_id"], "42");
+    assert_eq!(body["message_flags"], 64);
+    assert_eq!(body["data"]["custom_id"], "confirm");
+}
+
+#[test]
+fn malformed_components_and_duplicate_flag_keys_keep_the_message() {
+    let message: Message = serde_json::from_value(json!({
+        "id": "500",
+        "channel_id": "600",
+        "author": { "id": "42", "username": "helper", "discriminator": "0" },
+        "content": "",
+        "timestamp": "2024-01-01T00:00:00+00:00",
+        "type": 0,
+        "flags": 64,
+        "message_flags": 0,
+        "components": [{
+            "type": 1,
+            "components": [
+                { "type": 2, "style": 99, "label": "New style", "custom_id": "new" },
+                { "type": 2, "style": 1, "label": "Yes", "custom_id": "confirm" }
+            ]
+        }]
+    }))
+    .expect("message should deserialize");
+
+    assert_eq!(message.flags, Some(64));
+    let Component::ActionRow(row) = &message.components[0] else {
+        panic!("expected an action row");
+    };
+    assert!(matches!(row.components[0], Component::Unknown(_)));
+    assert!(message.button("confirm").is_some());
+}
+
+#[test]
+fn session_deserializes_and_describes_client() {
+    let sessions: Vec<Session> = serde_json::from_value(json!([
+        {
+            "id_hash": "abc",
+            "approx_last_used_time": "2024-01-01T00:00:00+00:00",
+            "client_info": { "os": "Windows", "platform": "Chrome", "location": "Paris, France" }
+        },
+        { "id_hash": "def", "client_info": {} }
+    ]))
+    .expect("sessions should deserialize");
+
+    assert_eq!(sessions[0].describe(), "Chrome on Windows, Paris, France");
+    assert_eq!(sessions[1].describe(), "Unknown client");
+}
+
+#[test]
+fn connections_parse_known_and_unknown_services() {
+    let connections: Vec<Connection> = serde_json::from_value(json!([
+        { "id": "1", "type": "spotify", "name": "daniil", "verified": true, "visibility": 1 },
+        { "id": "2", "type": "newservice", "name": "daniil" }
+    ]))
+    .expect("connections should deserialize");
+
+    assert_eq!(connections[0].kind, ConnectionType::Spotify);
+    assert!(connections[0].is_visible());
+    assert_eq!(
+        connections[1].kind,
+        ConnectionType::Other("newservice".to_string())
+    );
+    assert_eq!(
+        serde_json::to_value(&connections[1].kind).expect("type should serialize"),
+        json!("newservice")
+    );
+}
+
+#[test]
+fn guild_folders_flatten_to_positions() {
+    let folders = vec![
+        GuildFolder::new("Work", vec!["1".to_string(), "2".to_string()]).with_color(0x5865F2),
+        GuildFolder::single("3"),
+    ];
+
+    assert!(folders[0].is_folder());
+    assert!(!folders[1].is_folder());
+    assert!(folders[0].contains("2"));
+    assert_eq!(GuildFolder::positions(&folders), vec!["1", "2", "3"]);
+
+    let value = serde_json::to_value(&folders[1]).expect("folder should serialize");
+    assert_eq!(
+        value,
+        json!({ "id": null, "name": null, "color": null, "guild_ids": ["3"] })
+    );
+}
+
+#[test]
+fn relationship_actions_build_expected_bodies() {
+    assert_eq!(RelationshipAction::Friend.body(), json!({}));
+    assert_eq!(RelationshipAction::Block.body(), json!({ "type": 2 }));
+}
+
+#[test]
+fn polls_parse_results_and_build_create_body() {
+    let poll: Poll = serde_json::from_value(json!({
+        "question": { "text": "Best language?" },
+        "answers": [
+            { "answer_id": 1, "poll_media": { "text": "Rust", "emoji": { "id": null, "name": "🦀" } } },
+            { "answer_id": 2, "poll_media": { "text": "Go" } }
+        ],
+        "expiry": "2026-03-01T00:00:00.000000+00:00",
+        "allow_multiselect": false,
+        "layout_type": 1,
+        "results": {
+            "is_finalized": false,
+            "answer_counts": [
+                { "id": 1, "count": 4, "me_voted": true },
+                { "id": 2, "count": 1, "me_voted": false }
+            ]
+        }
+    }))
+    .expect("poll should deserialize");
+
+    assert_eq!(poll.question.text.as_deref(), Some("Best language?"));
+    assert_eq!(poll.answer_id("Go"), Some(2));
+    assert_eq!(poll.votes(1), 4);
+    assert_eq!(poll.votes(3), 0);
+    assert_eq!(poll.my_votes(), vec![1]);
+    assert!(!poll.is_finalized());
+
+    let body = serde_json::to_value(
+        CreatePoll::new("Lunch?")
+            .answer("Pizza")
+            .answer_with_emoji("Sushi", "🍣")
+            .duration(4)
+            .allow_multiselect(true),
+    )
+    .unwrap();
+    assert_eq!(body["question"], json!({ "text": "Lunch?" }));
+    assert_eq!(
+        body["answers"][0],
+        json!({ "poll_media": { "text": "Pizza" } })
+    );
+    assert_eq!(
+        body["answers"][1]["poll_media"]["emoji"],
+        json!({ "id": null, "name": "🍣", "animated": false })
+    );
+    assert_eq!(body["duration"], 4);
+    assert_eq!(body["allow_multiselect"], true);
+}
+
+#[test]
+fn integrations_detect_bots() {
+    let integrations: Vec<Integration> = serde_json::from_value(json!([
+        {
+            "id": "1",
+            "name": "Helper",
+            "type": "discord",
+            "enabled": true,
+            "account": { "id": "10", "name": "Helper" },
+            "application": {
+                "id": "10",
+                "name": "Helper",
+                "icon": null,
+                "description": "",
+                "bot": { "id": "11", "username": "helper", "discriminator": "0000", "bot": true }
+            },
+            "scopes": ["bot", "applications.commands"]
+        },
+        {
+            "id": "2",
+            "name": "streamer",
+            "type": "twitch",
+            "enabled": true,
+            "syncing": false,
+            "account": { "id": "20", "name": "streamer" },
+            "subscriber_count": 3
+        }
+    ]))
+    .expect("integrations should deserialize");
+
+    assert!(integrations[0].is_bot());
+    assert_eq!(integrations[0].scopes.len(), 2);
+    assert!(!integrations[1].is_bot());
+    assert_eq!(integrations[1].subscriber_count, Some(3));
+}
+
+#[test]
+fn member_permissions_apply_channel_overwrites_in_order() {
+    let guild: Guild = serde_json::from_value(json!({
+        "id": "g1",
+        "name": "guild",
+        "owner_id": "owner",
+        "roles": [
+            { "id": "g1", "name": "@everyone", "permissions": "3072" },
+            { "id": "mod", "name": "mod", "permissions": "8192" },
+            { "id": "admin", "name": "admin", "permissions": "8" }
+        ]
+    }))
+    .expect("guild should deserialize");
+    let member = |id: &str, roles: &[&str]| -> Member {
+        serde_json::from_value(json!({
+            "user": { "id": id, "username": id, "discriminator": "0" },
+            "roles": roles,
+            "joined_at": "2026-01-01T00:00:00.000000+00:00",
+            "flags": 0
+        }))
+        .unwrap()
+    };
+    // @everyone can't send, mods can, one mod is explicitly denied
+    let channel: Channel = serde_json::from_value(json!({
+        "id": "c1",
+        "type": 0,
+        "guild_id": "g1",
+        "permission_overwrites": [
+            { "id": "g1", "type": 0, "allow": "0", "deny": "2048" },
+            { "id": "mod", "type": 0, "allow": "2048", "deny": "0" },
+            { "id": "muted", "type": 1, "allow": "0", "deny": "2048" }
+        ]
+    }))
+    .unwrap();
+
+    let regular = member("regular", &[]);
+    let moderator = member("moderator", &["mod"]);
+    let muted = member("muted", &["mod"]);
+
+    let base = guild.member_base_permissions(&regular);
+    assert!(base.contains(Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL));
+    assert!(!guild
+        .member_permissions_in(&channel, &regular)
+        .contains(Permissions::SEND_MESSAGES));
+    assert!(guild
+        .member_permissions_in(&channel, &moderator)
+        .contains(Permissions::SEND_MESSAGES | Permissions::MANAGE_MESSAGES));
+    assert!(!guild
+        .member_permissions_in(&channel, &muted)
+        .contains(Permissions::SEND_MESSAGES));
+    assert_eq!(
+        guild.member_permissions_in(&channel, &member("admin", &["admin"])),
+        Permissions::all()
+    );
+    assert_eq!(
+        guild.member_permissions_in(&channel, &member("owner", &[])),
+        Permissions::all()
+    );
+}
+
+#[test]
+fn channel_builders_serialize_only_set_fields() {
+    let create = serde_json::to_value(
+        CreateChannel::text("general")
+            .topic("Say hi")
+            .parent("10")
+            .nsfw(true)
+            .field("flags", 16),
+    )
+    .unwrap();
+    assert_eq!(
+        create,
+        serde_json::json!({
+            "name": "general",
+            "type": 0,
+            "topic": "Say hi",
+            "parent_id": "10",
+            "nsfw": true,
+            "flags": 16,
+        })
+    );
+
+    let edit = serde_json::to_value(EditChannel::new().slowmode(5).remove_parent()).unwrap();
+    assert_eq!(
+        edit,
+        serde_json::json!({ "rate_limit_per_user": 5, "parent_id": null })
+    );
+}
+
+#[test]
+fn role_builders_serialize_typed_fields() {
+    let create = serde_json::to_value(
+        CreateRole::new("Mods")
+            .permissions(Permissions::KICK_MEMBERS | Permissions::BAN_MEMBERS)
+            .colors(RoleColors::gradient(0xFF0000, 0x0000FF))
+            .icon_bytes("image/png", b"png"),
+    )
+    .unwrap();
+    assert_eq!(create["name"], "Mods");
+    assert_eq!(create["permissions"], "6");
+    assert_eq!(create["colors"]["primary_color"], 0xFF0000);
+    assert_eq!(create["colors"]["secondary_color"], 0x0000FF);
+    assert_eq!(create["icon"], "data:image/png;base64,cG5n");
+    assert!(create.get("hoist").is_none());
+
+    let edit = serde_json::to_value(EditRole::new().remove_icon().unicode_emoji("🛡️")).unwrap();
+    assert_eq!(
+        edit,
+        serde_json::json!({ "icon": null, "unicode_emoji": "🛡️" })
+    );
+}
+
+#[test]
+fn search_results_flatten_messages_and_page_by_offset() {
+    let message = serde_json::json!({
+        "id": "2",
+        "channel_id": "1",
+        "author": { "id": "3", "username": "user", "discriminator": "0" },
+        "content": "hello",
+        "timestamp": "2024-01-01T00:00:00.000000+00:00",
+        "type": 0
+    });
+    let results: SearchResults = serde_json::from_value(serde_json::json!({
+        "total_results": 3,
+        "messages": [[message.clone()], [message]],
+        "doing_deep_historical_index": false
+    }))
+    .unwrap();
+    assert_eq!(results.iter_messages().count(), 2);
+    assert!(!results.is_indexing());
+
+    let params = diself::SearchParams::new().content("hello");
+    let next = params.next_page(&results).expect("one result left");
+    assert!(next.next_page(&results).is_none());
+
+    let indexing: SearchResults =
+        serde_json::from_value(serde_json::json!({ "code": 110000, "retry_after": 2 })).unwrap();
+    assert!(indexing.is_indexing());
+}
+
+#[test]
+fn snowflake_ids_parse_order_and_serialize_as_strings() {
+    use diself::model::{ChannelId, MessageId, UserId};
+
+    let id: MessageId = serde_json::from_value(json!("175928847299117063")).unwrap();
+    assert_eq!(id.timestamp_ms(), 1_462_015_105_796);
+    assert_eq!(id.worker_id(), 1);
+    assert_eq!(id.process_id(), 0);
+    assert_eq!(id.increment(), 7);
+    assert_eq!(
+        serde_json::to_value(id).unwrap(),
+        json!("175928847299117063")
+    );
+    assert_eq!(
+        serde_json::from_value::<MessageId>(json!(175928847299117063u64)).unwrap(),
+        id
+    );
+    assert!(serde_json::from_value::<MessageId>(json!("general")).is_err());
+
+    let later = MessageId::from_timestamp_ms(id.timestamp_ms() + 1);
+    assert!(later > id);
+    assert_eq!(later.timestamp_ms(), id.timestamp_ms() + 1);
+    assert_eq!(id, "175928847299117063");
+
+    let channel: ChannelId = id.cast();
+    assert_eq!(channel.get(), id.get());
+    assert_eq!(sample_user().user_id(), UserId::parse("123"));
+}
+
+#[test]
+fn timestamps_parse_raw_fields_and_serialize_as_rfc3339() {
+    use diself::model::{MessageId, Timestamp};
+
+    let member: Member = serde_json::from_value(json!({
+        "user": { "id": "1", "username": "me", "discriminator": "0" },
+        "roles": [],
+        "joined_at": "2024-03-01T12:30:00.000000+00:00",
+        "premium_since": null,
+        "communication_disabled_until": "2999-01-01T00:00:00+00:00",
+        "flags": 0
+    }))
+    .unwrap();
+    let joined = member.joined_at_parsed().unwrap();
+    assert_eq!(joined.unix_ms(), 1_709_296_200_000);
+    assert!(member.premium_since_parsed().is_none());
+    assert!(member.is_timed_out());
+
+    assert_eq!(
+        serde_json::to_value(joined).unwrap(),
+        json!("2024-03-01T12:30:00.000Z")
+    );
+    let back: Timestamp = serde_json::from_value(json!("2024-03-01T13:30:00+01:00")).unwrap();
+    assert_eq!(back, joined);
+    assert!(Timestamp::parse("yesterday").is_none());
+
+    let id = MessageId::from_timestamp_ms(1_709_296_200_000);
+    assert_eq!(id.created_at(), Some(joined));
+}
+
+#[test]
+fn modals_parse_text_inputs() {
+    use diself::model::{Modal, TextInputStyle};
+
+    let modal: Modal = serde_json::from_value(json!({
+        "id": "1",
+        "nonce": "2",
+        "channel_id": "3",
+        "custom_id": "feedback",
+        "title": "Feedback",
+        "application": { "id": "4", "name": "Bot" },
+        "components": [{
+            "type": 1,
+            "components": [{
+                "type": 4,
+                "custom_id": "comment",
+                "style": 2,
+                "label": "Comment",
+                "required": false
+            }]
+        }]
+    }))
+    .unwrap();
+
+    assert_eq!(modal.text_inputs().len(), 1);
+    let input = modal.text_input("comment").unwrap();
+    assert_eq!(input.style, TextInputStyle::Paragraph);
+    assert_eq!(input.required, Some(false));
+    assert!(modal.text_input("missing").is_none());
+}
+
+#[test]
+fn interaction_responses_expose_the_command_and_its_user() {
+    use diself::model::InteractionType;
+
+    let user = json!({ "id": "1", "username": "alice", "discriminator": "0" });
+    let message: Message = serde_json::from_value(json!({
+        "id": "10",
+        "channel_id": "20",
+        "author": { "id": "2", "username": "bot", "discriminator": "0", "bot": true },
+        "content": "",
+        "timestamp": "2026-01-01T00:00:00.000Z",
+        "edited_timestamp": null,
+        "type": 20,
+        "interaction_metadata": {
+            "id": "30",
+            "type": 5,
+            "user": user,
+            "triggering_interaction_metadata": {
+                "id": "29",
+                "type": 2,
+                "user": user,
+                "name": "config set",
+                "command_type": 1
+            }
+        },
+        "interaction": { "id": "30", "type": 5, "name": "config set", "user": user }
+    }))
+    .unwrap();
+
+    assert_eq!(message.command_name(), Some("config set"));
+    assert_eq!(
+        message.interaction_kind(),
+        Some(InteractionType::ModalSubmit)
+    );
+    assert_eq!(message.interaction_user().unwrap().username, "alice");
+}
+
+#[test]
+fn reaction_event_payloads_deserialize() {
+    use diself::model::{
+        MessageDeleteBulk, MessageReactionAdd, MessageReactionRemoveEmoji, ReactionType,
+    };
+
+    let add: MessageReactionAdd = serde_json::from_value(json!({
+        "user_id": "1",
+        "channel_id": "2",
+        "message_id": "3",
+        "guild_id": "4",
+        "emoji": { "id": null, "name": "🔥" },
+        "message_author_id": "5",
+        "burst": true,
+        "burst_colors": ["#ff0000"],
+        "type": 1
+    }))
+    .unwrap();
+    assert_eq!(add.emoji.name.as_deref(), Some("🔥"));
+    assert_eq!(add.kind, ReactionType::Burst);
+    assert!(add.member.is_none());
+
+    let removed: MessageReactionRemoveEmoji = serde_json::from_value(json!({
+        "channel_id": "2",
+        "message_id": "3",
+        "emoji": { "id": "6", "name": "party", "animated": true }
+    }))
+    .unwrap();
+    assert!(removed.emoji.animated);
+    assert!(removed.guild_id.is_none());
+    assert!(removed.emoji.matches("party:6"));
+    assert!(removed.emoji.matches("<a:party:6>"));
+    assert!(!removed.emoji.matches("party"));
+    assert!(add.emoji.matches("🔥"));
+    assert!(!add.emoji.matches("fire:6"));
+
+    let bulk: MessageDeleteBulk =
+        serde_json::from_value(json!({ "ids": ["7", "8"], "channel_id": "2" })).unwrap();
+    assert_eq!(bulk.ids, vec!["7", "8"]);
+}
+
+#[test]
+fn guild_member_event_payloads_deserialize() {
+    use diself::model::{GuildMemberAdd, GuildMemberRemove, GuildMemberUpdate};
+
+    let user = json!({ "id": "1", "username": "alice", "discriminator": "0" });
+    let join: GuildMemberAdd = serde_json::from_value(json!({
+        "guild_id": "g1",
+        "user": user,
+        "roles": ["r1"],
+        "joined_at": "2026-01-01T00:00:00.000Z",
+        "flags": 0
+    }))
+    .unwrap();
+    assert_eq!(join.guild_id, "g1");
+    assert_eq!(join.member.user.id, "1");
+    assert!(join.member.joined_at_parsed().is_some());
+
+    // Guests of voice channels have no join date
+    let update: GuildMemberUpdate = serde_json::from_value(json!({
+        "guild_id": "g1",
+        "user": user,
+        "nick": "Ally",
+        "roles": [],
+        "joined_at": null
+    }))
+    .unwrap();
+    assert_eq!(update.nick.as_deref(), Some("Ally"));
+    assert!(update.joined_at.is_none());
+
+    let leave: GuildMemberRemove =
+        serde_json::from_value(json!({ "guild_id": "g1", "user": user })).unwrap();
+    assert_eq!(leave.user.username, "alice");
+}
+
+#[test]
+fn guild_ban_event_payload_deserializes() {
+    use diself::model::GuildBanEvent;
+
+    let ban: GuildBanEvent = serde_json::from_value(json!({
+        "guild_id": "g1",
+        "user": { "id": "1", "username": "spammer", "discriminator": "0" }
+    }))
+    .unwrap();
+    assert_eq!(ban.guild_id, "g1");
+    assert_eq!(ban.user.username, "spammer");
+}
+
+#[test]
+fn presence_builders_serialize_rich_and_spotify_activities() {
+    use diself::model::{Activity, CreateActivity, EditPresence, OnlineStatus, SpotifyTrack};
+
+    let track = SpotifyTrack {
+        track_id: "4uLU6hMCjMI75M1A2tKUQC".to_string(),
+        title: "Never Gonna Give You Up".to_string(),
+        artists: vec!["Rick Astley".to_string()],
+        album: Some("Whenever You Need Somebody".to_string()),
+        album_art_url: Some("https://i.scdn.co/image/ab67616d0000b273".to_string()),
+        start: Some(1_700_000_000_000),
+        end: Some(1_700_000_213_000),
+    };
+    let presence = EditPresence::new()
+        .status(OnlineStatus::DoNotDisturb)
+        .activity(CreateActivity::spotify("123", &track));
+    let payload = serde_json::to_value(&presence).unwrap();
+    assert_eq!(payload["status"], "dnd");
+    assert_eq!(payload["since"], serde_json::Value::Null);
+    assert_eq!(
+        payload["activities"][0]["party"],
+        json!({ "id": "spotify:123" })
+    );
+
+    // The activity reads back as the same track
+    let activity: Activity = serde_json::from_value(payload["activities"][0].clone()).unwrap();
+    assert_eq!(activity.spotify(), Some(track));
+
+    let streaming = serde_json::to_value(
+        CreateActivity::streaming("Speedrun", "https://twitch.tv/someone")
+            .small_image("mp:external/icon.png"),
+    )
+    .unwrap();
+    assert_eq!(streaming["url"], "https://twitch.tv/someone");
+    assert_eq!(
+        streaming["assets"],
+        json!({ "small_image": "mp:external/icon.png" })
+    );
+    assert!(streaming.get("buttons").is_none());
+}
+
+#[test]
+fn custom_status_activity_replaces_the_previous_one() {
+    use diself::model::{ActivityEmoji, CreateActivity, EditPresence};
+
+    let unicode = ActivityEmoji::from("🦀");
+    assert_eq!(
+        (unicode.name.as_str(), unicode.id, unicode.animated),
+        ("🦀", None, false)
+    );
+    let custom = ActivityEmoji::from("<a:ferris:42>");
+    assert_eq!(custom.name, "ferris");
+    assert_eq!(custom.id.as_deref(), Some("42"));
+    assert!(custom.animated);
+    assert!(!ActivityEmoji::from("ferris:42").animated);
+    let still = ActivityEmoji::from("<:ferris:42>");
+    assert_eq!(
+        (still.name.as_str(), still.id.as_deref(), still.animated),
+        ("ferris", Some("42"), false)
+    );
+
+    let presence = EditPresence::new()
+        .activity(CreateActivity::playing("Minecraft"))
+        .custom_status(Some(CreateActivity::custom("Old")))
+        .custom_status(Some(CreateActivity::custom("Coding").emoji("🦀")));
+    let payload = serde_json::to_value(&presence).unwrap();
+    assert_eq!(payload["activities"].as_array().unwrap().len(), 2);
+    assert_eq!(
+        payload["activities"][1],
+        json!({
+            "name": "Custom Status",
+            "type": 4,
+            "state": "Coding",
+            "emoji": { "name": "🦀", "id": null, "animated": false }
+        })
+    );
+
+    let cleared = serde_json::to_value(presence.custom_status(None)).unwrap();
+    assert_eq!(cleared["activities"][0]["name"], "Minecraft");
+    assert_eq!(cleared["activities"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn replies_and_forwards_keep_the_referenced_message() {
+    let author = json!({ "id": "1", "username": "me", "discriminator": "0" });
+    let reply: Message = serde_json::from_value(json!({
+        "id": "11",
+        "channel_id": "20",
+        "author": author,
+        "content": "same",
+        "timestamp": "2026-01-01T00:01:00.000Z",
+        "type": 19,
+        "message_reference": { "type": 0, "message_id": "10", "channel_id": "20" },
+        "referenced_message": {
+            "id": "10",
+            "channel_id": "20",
+            "author": author,
+            "content": "hello",
+            "timestamp": "2026-01-01T00:00:00.000Z",
+            "type": 0
+        }
+    }))
+    .unwrap();
+    assert!(reply.is_reply());
+    assert!(!reply.is_forward());
+    assert_eq!(reply.referenced_message.as_ref().unwrap().content, "hello");
+    assert!(reply.forwarded_message().is_none());
+
+    let forward: Message = serde_json::from_value(json!({
+        "id": "12",
+        "channel_id": "40",
+        "author": author,
+        "content": "",
+        "timestamp": "2026-01-01T00:02:00.000Z",
+        "type": 0,
+        "message_reference": { "type": 1, "message_id": "10", "channel_id": "20" },
+        "message_snapshots": [{
+            "message": {
+                "type": 0,
+                "content": "hello",
+                "embeds": [],
+                "attachments": [],
+                "timestamp": "2026-01-01T00:00:00.000Z",
+                "edited_timestamp": null,
+                "flags": 0
+            }
+        }]
+    }))
+    .unwrap();
+    assert!(forward.is_forward());
+    assert!(!forward.is_reply());
+    assert!(forward.referenced_message.is_none());
+    assert_eq!(forward.forwarded_message().unwrap().content, "hello");
+
+    let reference: MessageReference =
+        serde_json::from_value(json!({ "type": 7, "message_id": "10" })).unwrap();
+    assert_eq!(reference.kind, Some(MessageReferenceType::Unknown(7)));
+    assert!(!reference.is_forward());
+}
+
+#[test]
+fn ready_payload_parses_and_skips_malformed_entries() {
+    let ready: ReadyPayload = serde_json::from_value(json!({
+        "v": 9,
+        "user": sample_user(),
+        "users": [{ "id": "200", "username": "friend", "discriminator": "0" }],
+        "guilds": [{ "id": "300", "name": "guild" }, { "id": 301 }],
+        "private_channels": [{ "id": "400", "type": 1, "recipient_ids": ["200"] }],
+        "relationships": [{ "id": "200", "type": 1, "user_id": "200" }],
+        "read_state": { "version": 3, "partial": false, "entries": [{ "id": "400" }] },
+        "user_settings_proto": "",
+        "session_id": "session",
+        "session_type": "normal",
+        "resume_gateway_url": "wss://gateway-us-east1-b.discord.gg",
+        "sessions": [{
+            "session_id": "session",
+            "status": "online",
+            "client_info": { "client": "desktop", "os": "windows", "version": 0 },
+            "activities": []
+        }],
+        "analytics_token": "token",
+        "country_code": "FR",
+        "friend_suggestion_count": 2
+    }))
+    .expect("ready should deserialize");
+
+    assert_eq!(ready.v, 9);
+    assert_eq!(ready.user.id, "123");
+    assert_eq!(ready.users.len(), 1);
+    assert_eq!(ready.guilds.len(), 1);
+    assert_eq!(ready.guilds[0].id, "300");
+    assert_eq!(ready.private_channels[0].id, "400");
+    assert_eq!(ready.relationships.len(), 1);
+    assert_eq!(ready.read_state.entries[0].id, "400");
+    assert_eq!(ready.session_id, "session");
+    assert_eq!(
+        ready.sessions[0].client_info.client.as_deref(),
+        Some("desktop")
+    );
+    assert_eq!(ready.analytics_token.as_deref(), Some("token"));
+    assert_eq!(ready.country_code.as_deref(), Some("FR"));
+    assert_eq!(ready.friend_suggestion_count, 2);
+}
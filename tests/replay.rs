@@ -0,0 +1,69 @@
+use diself::gateway::{EventRecorder, ReplayGateway};
+use diself::prelude::async_trait;
+use diself::{Client, CollectorHub, Context, EventHandler, Message};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+struct Handler {
+    messages: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl EventHandler for Handler {
+    async fn on_message_create(&self, ctx: &Context, msg: Message) {
+        assert_eq!(ctx.user.id, "1");
+        self.messages.lock().unwrap().push(msg.content);
+    }
+}
+
+#[tokio::test]
+async fn recorded_events_replay_through_cache_and_handlers() {
+    let path = std::env::temp_dir().join(format!("diself-events-{}.ndjson", std::process::id()));
+    let recorder = EventRecorder::create(&path).await.unwrap();
+    let user = json!({ "id": "1", "username": "me", "discriminator": "0" });
+    let author = json!({ "id": "2", "username": "alice", "discriminator": "0" });
+    let events = [
+        json!({ "op": 0, "t": "READY", "s": 1, "d": { "v": 10, "user": user, "session_id": "s" } }),
+        json!({
+            "op": 0,
+            "t": "MESSAGE_CREATE",
+            "s": 2,
+            "d": {
+                "id": "10",
+                "channel_id": "20",
+                "author": author,
+                "content": "hello",
+                "timestamp": "2026-01-01T00:00:00.000Z",
+                "type": 0
+            }
+        }),
+    ];
+    for event in &events {
+        recorder.record(event).unwrap();
+    }
+    recorder.flush().await.unwrap();
+
+    let replay = ReplayGateway::open(&path).await.unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(replay.len(), 2);
+    assert_eq!(replay.current_user().unwrap().id, "1");
+
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let handler = Handler {
+        messages: messages.clone(),
+    };
+    let collectors = CollectorHub::new();
+    let client = Client::builder("token", handler)
+        .with_collectors(collectors.clone())
+        .build();
+    client.replay(replay).await.unwrap();
+    // The replay ends its own collectors only
+    assert!(!collectors.is_closed());
+
+    assert_eq!(*messages.lock().unwrap(), ["hello"]);
+    assert_eq!(client.cache().user("2").unwrap().username, "alice");
+    assert!(client.cache().message("10").is_some());
+
+    let no_ready = ReplayGateway::from_events([events[1].clone()]);
+    assert!(client.replay(no_ready).await.is_err());
+}
@@ -0,0 +1,178 @@
+use diself::client::MIN_RECURRING_INTERVAL;
+use diself::http::MockTransport;
+use diself::model::CreateMessage;
+use diself::{Client, EventHandler, HttpClient, MessageScheduler};
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn scheduler_sends_cancels_and_persists_messages() {
+    let mut server = mockito::Server::new_async().await;
+    let once = server
+        .mock("POST", "/api/v10/channels/1/messages")
+        .match_body(mockito::Matcher::PartialJsonString(
+            r#"{"content":"once"}"#.to_string(),
+        ))
+        .with_body(r#"{"id":"10"}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let recurring = server
+        .mock("POST", "/api/v10/channels/2/messages")
+        .with_body(r#"{"id":"11"}"#)
+        .expect_at_least(2)
+        .create_async()
+        .await;
+
+    let http = HttpClient::new("token").with_api_base_url(format!("{}/api/", server.url()));
+    let scheduler = MessageScheduler::new(http.clone());
+    let sent = scheduler
+        .schedule_message(
+            "1",
+            CreateMessage::new().content("once"),
+            Instant::now() + Duration::from_millis(20),
+        )
+        .unwrap();
+    let repeated = scheduler
+        .schedule_recurring(
+            "2",
+            CreateMessage::new().content("again"),
+            MIN_RECURRING_INTERVAL,
+        )
+        .unwrap();
+    let cancelled = scheduler
+        .schedule_message(
+            "3",
+            CreateMessage::new().content("never"),
+            Instant::now() + Duration::from_millis(20),
+        )
+        .unwrap();
+    assert!(scheduler.cancel(cancelled));
+    assert!(!scheduler.cancel(cancelled));
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while !once.matched_async().await
+            || !recurring.matched_async().await
+            || scheduler.scheduled().len() != 1
+        {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("scheduled messages should be sent");
+    let pending = scheduler.scheduled();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].id, repeated);
+    assert!(!scheduler.cancel(sent));
+
+    let path = std::env::temp_dir().join(format!("diself-schedules-{}.json", std::process::id()));
+    scheduler.save(&path).await.unwrap();
+    scheduler.cancel_all();
+    assert!(scheduler.scheduled().is_empty());
+
+    let restored = MessageScheduler::new(http);
+    assert_eq!(restored.load(&path).await.unwrap(), 1);
+    let pending = restored.scheduled();
+    assert_eq!(pending[0].channel_id, "2");
+    assert_eq!(pending[0].message["content"], "again");
+    assert_eq!(pending[0].every, Some(MIN_RECURRING_INTERVAL));
+    restored.cancel_all();
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn client_scheduler_holds_messages_while_the_client_is_stopped() {
+    struct Handler;
+    impl EventHandler for Handler {}
+
+    let mock = MockTransport::new();
+    let client = Client::builder("token", Handler)
+        .with_http_client(HttpClient::new("token").with_transport(mock.clone()))
+        .build();
+    let id = client
+        .scheduler()
+        .schedule_message("1", CreateMessage::new().content("later"), Instant::now())
+        .unwrap();
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(mock.requests().is_empty());
+    assert_eq!(client.scheduler().scheduled()[0].id, id);
+}
+
+#[tokio::test]
+async fn client_scheduler_is_paused_when_a_shard_fails() {
+    use diself::http::Method;
+    use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+    use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+
+    struct Handler;
+    impl EventHandler for Handler {}
+
+    // Rejects every connection with 4004 (authentication failed) instead of HELLO
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!(
+        "ws://{}/?v=10&encoding=json",
+        listener.local_addr().unwrap()
+    );
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            let frame = CloseFrame {
+                code: CloseCode::from(4004),
+                reason: "".into(),
+            };
+            let _ = ws.close(Some(frame)).await;
+        }
+    });
+
+    let mock = MockTransport::new();
+    mock.on(
+        Method::GET,
+        "/users/@me",
+        serde_json::json!({ "id": "1", "username": "me", "discriminator": "0001" }),
+    );
+    let client = Client::builder("token", Handler)
+        .with_http_client(HttpClient::new("token").with_transport(mock.clone()))
+        .with_gateway_url(url)
+        .with_shards(1)
+        .build();
+    client
+        .scheduler()
+        .schedule_message(
+            "1",
+            CreateMessage::new().content("later"),
+            Instant::now() + Duration::from_millis(300),
+        )
+        .unwrap();
+
+    assert!(client.start().await.is_err());
+    tokio::time::sleep(Duration::from_millis(600)).await;
+    assert!(mock
+        .requests()
+        .iter()
+        .all(|request| request.method != Method::POST));
+    assert_eq!(client.scheduler().scheduled().len(), 1);
+}
+
+#[tokio::test]
+async fn recurring_schedules_reject_short_intervals() {
+    let mock = MockTransport::new();
+    let scheduler = MessageScheduler::new(HttpClient::new("token").with_transport(mock.clone()));
+
+    for every in [Duration::ZERO, Duration::from_millis(10)] {
+        let error = scheduler
+            .schedule_recurring("1", CreateMessage::new().content("spam"), every)
+            .unwrap_err();
+        assert!(matches!(error, diself::Error::InvalidArgument(_)));
+    }
+    assert!(scheduler.scheduled().is_empty());
+
+    let id = scheduler
+        .schedule_recurring(
+            "1",
+            CreateMessage::new().content("tick"),
+            MIN_RECURRING_INTERVAL,
+        )
+        .unwrap();
+    assert_eq!(scheduler.scheduled()[0].id, id);
+    scheduler.cancel_all();
+}
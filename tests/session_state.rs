@@ -0,0 +1,143 @@
+use diself::gateway::SessionState;
+use serde_json::json;
+
+#[tokio::test]
+async fn session_state_round_trips_through_disk() {
+    let state = SessionState {
+        session_id: "abc123".to_string(),
+        resume_gateway_url: Some("wss://gateway-us-east1-b.discord.gg/?v=10&encoding=json".into()),
+        sequence: Some(42),
+    };
+
+    let path = std::env::temp_dir().join(format!("diself-session-{}.json", std::process::id()));
+    state
+        .save(&path)
+        .await
+        .expect("session state should be written");
+    let loaded = SessionState::load(&path)
+        .await
+        .expect("session state should be read");
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(loaded, state);
+}
+
+/// Local gateway expecting a RESUME, answering RESUMED then one MESSAGE_CREATE
+async fn resuming_gateway() -> String {
+    use futures::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!(
+        "ws://{}/?v=10&encoding=json",
+        listener.local_addr().unwrap()
+    );
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        let hello = json!({ "op": 10, "d": { "heartbeat_interval": 45000 } });
+        ws.send(WsMessage::Text(hello.to_string())).await.unwrap();
+        let resume = ws.next().await.unwrap().unwrap();
+        let resume: serde_json::Value = serde_json::from_str(resume.to_text().unwrap()).unwrap();
+        assert_eq!(resume["op"], 6);
+        for event in [
+            json!({ "op": 0, "s": 43, "t": "RESUMED", "d": {} }),
+            json!({
+                "op": 0,
+                "s": 44,
+                "t": "MESSAGE_CREATE",
+                "d": {
+                    "id": "m1",
+                    "channel_id": "c1",
+                    "author": { "id": "2", "username": "name", "discriminator": "0001" },
+                    "content": "hello",
+                    "timestamp": "2026-02-22T00:00:00.000Z",
+                    "type": 0
+                }
+            }),
+        ] {
+            ws.send(WsMessage::Text(event.to_string())).await.unwrap();
+        }
+        while let Some(Ok(_)) = ws.next().await {}
+    });
+    url
+}
+
+#[tokio::test]
+async fn resumed_session_fills_the_cache_without_ready() {
+    use diself::http::{Method, MockTransport};
+    use diself::prelude::async_trait;
+    use diself::{Client, Context, EventHandler, HttpClient, Message};
+    use std::sync::Arc;
+    use tokio::sync::mpsc;
+
+    struct Handler(mpsc::UnboundedSender<(String, Option<String>, usize, usize, usize)>);
+
+    #[async_trait]
+    impl EventHandler for Handler {
+        async fn on_message_create(&self, ctx: &Context, _msg: Message) {
+            let cached_user = ctx.cache.current_user().map(|user| user.id);
+            let _ = self.0.send((
+                ctx.user.id.clone(),
+                cached_user,
+                ctx.cache.relationship_count(),
+                ctx.cache.private_channels().len(),
+                ctx.cache.guild_count(),
+            ));
+        }
+    }
+
+    let mock = MockTransport::new();
+    mock.on(
+        Method::GET,
+        "/users/@me",
+        json!({ "id": "1", "username": "me", "discriminator": "0001" }),
+    );
+    mock.on(
+        Method::GET,
+        "/users/@me/relationships",
+        json!([{
+            "id": "2",
+            "type": 1,
+            "user": { "id": "2", "username": "friend", "discriminator": "0001" }
+        }]),
+    );
+    mock.on(
+        Method::GET,
+        "/users/@me/channels",
+        json!([{
+            "id": "10",
+            "type": 1,
+            "recipients": [{ "id": "2", "username": "friend", "discriminator": "0001" }]
+        }]),
+    );
+    mock.on(
+        Method::GET,
+        "/users/@me/guilds",
+        json!([{ "id": "100", "name": "Guild", "icon": null, "owner": false }]),
+    );
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let session = SessionState {
+        session_id: "abc123".to_string(),
+        resume_gateway_url: None,
+        sequence: Some(42),
+    };
+    let client = Arc::new(
+        Client::builder("token", Handler(tx))
+            .with_http_client(HttpClient::new("token").with_transport(mock))
+            .with_gateway_url(resuming_gateway().await)
+            .with_session_state(session)
+            .build(),
+    );
+
+    let run = tokio::spawn({
+        let client = client.clone();
+        async move { client.start().await }
+    });
+    let seen = rx.recv().await.expect("handler ran");
+    client.shutdown();
+    run.await.unwrap().unwrap();
+
+    assert_eq!(seen, ("1".to_string(), Some("1".to_string()), 1, 1, 1));
+}
@@ -0,0 +1,119 @@
+#![cfg(feature = "settings-proto")]
+
+use diself::model::settings_proto::{
+    CustomStatus, GuildFolder, GuildFolders, PreloadedUserSettings, StringValue, Theme,
+};
+
+#[test]
+fn preloaded_settings_round_trip_through_base64() {
+    let mut settings = PreloadedUserSettings::with_custom_status(Some(
+        CustomStatus::new("busy coding").emoji("🦀"),
+    ));
+    settings.status.as_mut().unwrap().status = Some(StringValue {
+        value: "dnd".to_string(),
+    });
+    settings.guild_folders = Some(GuildFolders {
+        folders: vec![GuildFolder {
+            guild_ids: vec![1, 2],
+            ..Default::default()
+        }],
+        guild_positions: vec![2, 1],
+    });
+
+    let decoded =
+        PreloadedUserSettings::from_base64(&settings.to_base64()).expect("settings should decode");
+
+    assert_eq!(decoded, settings);
+    assert_eq!(decoded.online_status(), Some("dnd"));
+    assert_eq!(
+        decoded.custom_status().map(|status| status.text.as_str()),
+        Some("busy coding")
+    );
+    assert_eq!(decoded.theme(), Theme::Unset);
+}
+
+#[test]
+fn invalid_settings_blob_is_rejected() {
+    assert!(PreloadedUserSettings::from_base64("not base64!").is_err());
+}
+
+#[tokio::test]
+async fn custom_status_is_saved_in_protobuf_settings() {
+    use diself::http::{Method, MockTransport};
+    use diself::model::Timestamp;
+    use serde_json::json;
+
+    let mock = MockTransport::new();
+    mock.on(
+        Method::GET,
+        "/users/@me",
+        json!({ "id": "1", "username": "me", "discriminator": "0" }),
+    );
+    mock.on(
+        Method::PATCH,
+        "/users/@me/settings-proto/1",
+        json!({ "settings": "" }),
+    );
+    let http = diself::HttpClient::new("token").with_transport(mock.clone());
+    let ctx = diself::Context::create(http, diself::Cache::new())
+        .await
+        .unwrap();
+    let sent_status = || {
+        let body = mock.last_request().unwrap().body.unwrap();
+        PreloadedUserSettings::from_base64(body["settings"].as_str().unwrap())
+            .unwrap()
+            .custom_status()
+            .cloned()
+    };
+
+    // Without a running gateway, only the settings are updated
+    let expires_at = Timestamp::parse("2026-01-01T12:00:00.000Z");
+    ctx.set_custom_status("Coding", Some("<:ferris:42>"), expires_at)
+        .await
+        .unwrap();
+    assert_eq!(
+        sent_status(),
+        Some(
+            CustomStatus::new("Coding")
+                .emoji("ferris:42")
+                .expires_at_ms(expires_at.unwrap().unix_ms() as u64)
+        )
+    );
+    assert_eq!(sent_status().unwrap().emoji_id, 42);
+
+    ctx.clear_custom_status().await.unwrap();
+    assert_eq!(sent_status(), Some(CustomStatus::default()));
+}
+
+#[tokio::test]
+async fn guild_folders_are_saved_in_protobuf_settings() {
+    use diself::http::{Method, MockTransport};
+    use diself::model::GuildFolder as SidebarFolder;
+    use serde_json::json;
+
+    let folders = vec![
+        SidebarFolder::new("Work", vec!["1".to_string(), "2".to_string()]).with_color(0x5865F2),
+        SidebarFolder::single("3"),
+    ];
+    let stored = PreloadedUserSettings::with_guild_folders(GuildFolders::from(folders.as_slice()));
+    let mock = MockTransport::new();
+    mock.on(
+        Method::PATCH,
+        "/users/@me/settings-proto/1",
+        json!({ "settings": stored.to_base64() }),
+    );
+    let http = diself::HttpClient::new("token").with_transport(mock.clone());
+
+    let saved = diself::UsersManager
+        .set_guild_folders(&http, folders.clone())
+        .await
+        .unwrap();
+    assert_eq!(saved, folders);
+
+    let body = mock.last_request().unwrap().body.unwrap();
+    let sent = PreloadedUserSettings::from_base64(body["settings"].as_str().unwrap()).unwrap();
+    let sent = sent.guild_folders.unwrap();
+    assert_eq!(sent.guild_positions, vec![1, 2, 3]);
+    assert_eq!(sent.folders[0].guild_ids, vec![1, 2]);
+    assert!(sent.folders[1].id.is_none());
+}
@@ -0,0 +1,65 @@
+use diself::gateway::{
+    GatewayMetrics, GatewaySender, Identify, IdentifyConfig, IdentifyPreset, ShardManager,
+};
+
+#[test]
+fn identify_includes_shard_only_when_sharded() {
+    let identify = serde_json::to_value(Identify::new("token")).unwrap();
+    assert!(identify.get("shard").is_none());
+    assert_eq!(
+        identify["presence"],
+        serde_json::json!({ "status": "online", "since": null, "activities": [], "afk": false })
+    );
+
+    let identify = serde_json::to_value(Identify::new("token").with_shard(2, 4)).unwrap();
+    assert_eq!(identify["shard"], serde_json::json!([2, 4]));
+}
+
+#[test]
+fn identify_presets_set_capabilities_and_intents() {
+    let identify = serde_json::to_value(Identify::new("token")).unwrap();
+    assert_eq!(identify["capabilities"], 16381);
+    assert!(identify.get("intents").is_none());
+
+    let identify = Identify::new("token").with_config(IdentifyPreset::BotCompat);
+    assert_eq!(identify.intents, Some(diself::gateway::ALL_INTENTS));
+
+    let config = IdentifyConfig {
+        capabilities: 1 << 13,
+        intents: Some(1 << 9),
+    };
+    let identify = serde_json::to_value(Identify::new("token").with_config(config)).unwrap();
+    assert_eq!(identify["capabilities"], 8192);
+    assert_eq!(identify["intents"], 512);
+}
+
+#[test]
+fn shard_manager_routes_guilds_by_snowflake() {
+    let manager = ShardManager::new("token", 4);
+    assert_eq!(manager.shard_count(), 4);
+    // (197038439483310086 >> 22) % 4 == 2
+    assert_eq!(manager.shard_for_guild("197038439483310086"), Some(2));
+    assert_eq!(manager.shard_for_guild("not-a-snowflake"), None);
+    assert_eq!(ShardManager::new("token", 0).shard_count(), 1);
+}
+
+#[test]
+fn gateway_metrics_start_empty_and_are_shared_between_clones() {
+    let metrics = GatewayMetrics::default();
+    let shared = metrics.clone();
+    assert_eq!(shared.latency(), None);
+    assert_eq!(shared.last_heartbeat_at(), None);
+    assert_eq!(shared.uptime(), None);
+    assert_eq!(shared.reconnects(), 0);
+    assert_eq!(shared.resumes(), 0);
+    assert_eq!(shared.events_received(), 0);
+    assert_eq!(shared.events_per_second(), 0.0);
+}
+
+#[test]
+fn gateway_sender_fails_without_running_gateway() {
+    let sender = GatewaySender::default();
+    assert!(sender
+        .update_presence(&diself::model::EditPresence::new())
+        .is_err());
+}
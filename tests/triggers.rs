@@ -0,0 +1,142 @@
+#![cfg(feature = "triggers")]
+
+use diself::http::{HttpResponse, Method, MockTransport};
+use diself::model::{Message, User};
+use diself::triggers::{Trigger, TriggerOutcome, TriggerSet};
+use diself::{Cache, Context, HttpClient};
+use serde_json::json;
+use std::time::Duration;
+
+fn sample_user(id: &str) -> User {
+    serde_json::from_value(json!({
+        "id": id,
+        "username": format!("user_{id}"),
+        "discriminator": "0001"
+    }))
+    .expect("valid user json")
+}
+
+fn sample_message(author_id: &str, channel_id: &str, content: &str) -> Message {
+    serde_json::from_value(json!({
+        "id": "m1",
+        "channel_id": channel_id,
+        "guild_id": "g1",
+        "author": { "id": author_id, "username": "name", "discriminator": "0001" },
+        "content": content,
+        "timestamp": "2026-02-22T00:00:00.000Z",
+        "type": 0
+    }))
+    .expect("valid message json")
+}
+
+#[test]
+fn patterns_match_literals_and_regex_groups() {
+    let exact = Trigger::exact("Ping");
+    assert!(exact.captures("  ping ").is_some());
+    assert!(exact.captures("ping pong").is_none());
+    assert!(exact.case_sensitive(true).captures("ping").is_none());
+
+    let contains = Trigger::contains("good morning");
+    assert_eq!(
+        contains.captures("Good Morning everyone"),
+        Some(vec!["good morning".to_string()])
+    );
+
+    let regex = Trigger::regex(r"#(\d+)(?: in (\w+))?").unwrap();
+    assert_eq!(
+        regex.captures("see #42"),
+        Some(vec!["#42".to_string(), "42".to_string(), String::new()])
+    );
+    assert!(Trigger::regex("(unclosed").is_err());
+
+    let scoped = Trigger::contains("hi").in_guild("g1").in_channel("c1");
+    assert!(scoped.in_scope(&sample_message("2", "c1", "hi")));
+    assert!(!scoped.in_scope(&sample_message("2", "c2", "hi")));
+}
+
+#[tokio::test]
+async fn dispatch_fires_first_matching_trigger_with_cooldown() {
+    let mock = MockTransport::new();
+    let reply = json!({
+        "id": "m2",
+        "channel_id": "c1",
+        "author": { "id": "1", "username": "me", "discriminator": "0001" },
+        "content": "",
+        "timestamp": "2026-02-22T00:00:00.000Z",
+        "type": 0
+    });
+    mock.on(Method::POST, "/channels/c1/messages", reply);
+    mock.respond(
+        Method::PUT,
+        "/channels/c1/messages/m1/reactions/%F0%9F%91%8B/@me",
+        HttpResponse::no_content(),
+    );
+    let http = HttpClient::new("token").with_transport(mock.clone());
+    let ctx = Context::new(http, sample_user("1"), Cache::new());
+
+    let triggers = TriggerSet::new()
+        .trigger(
+            Trigger::regex(r"(?i)issue #(\d+)")
+                .unwrap()
+                .reply("https://example.com/issues/$1")
+                .cooldown(Duration::from_secs(60)),
+        )
+        .trigger(Trigger::contains("hello").react("👋"));
+
+    let outcome = triggers
+        .dispatch(&ctx, &sample_message("2", "c1", "Issue #42 is back"))
+        .await
+        .unwrap();
+    assert_eq!(outcome, TriggerOutcome::Fired(0));
+    let request = mock.last_request().unwrap();
+    let body = request.body.unwrap();
+    assert_eq!(body["content"], "https://example.com/issues/42");
+    assert_eq!(body["message_reference"]["message_id"], "m1");
+
+    let outcome = triggers
+        .dispatch(&ctx, &sample_message("2", "c1", "issue #43"))
+        .await
+        .unwrap();
+    assert!(matches!(
+        outcome,
+        TriggerOutcome::Cooldown { trigger: 0, .. }
+    ));
+
+    let outcome = triggers
+        .dispatch(&ctx, &sample_message("2", "c1", "hello there"))
+        .await
+        .unwrap();
+    assert_eq!(outcome, TriggerOutcome::Fired(1));
+    assert_eq!(mock.last_request().unwrap().method, Method::PUT);
+
+    // Messages of the current user are ignored by default
+    let outcome = triggers
+        .dispatch(&ctx, &sample_message("1", "c1", "hello"))
+        .await
+        .unwrap();
+    assert_eq!(outcome, TriggerOutcome::Ignored);
+    assert_eq!(mock.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn replays_do_not_fire_triggers() {
+    use diself::gateway::ReplayGateway;
+    use diself::Client;
+
+    struct Handler;
+    impl diself::EventHandler for Handler {}
+
+    let mock = MockTransport::new();
+    let client = Client::builder("token", Handler)
+        .with_http_client(HttpClient::new("token").with_transport(mock.clone()))
+        .with_triggers(TriggerSet::new().trigger(Trigger::exact("ping").reply("pong")))
+        .build();
+    let message = serde_json::to_value(sample_message("2", "c1", "ping")).unwrap();
+    let replay = ReplayGateway::from_events([
+        json!({ "op": 0, "t": "READY", "s": 1, "d": { "user": sample_user("1") } }),
+        json!({ "op": 0, "t": "MESSAGE_CREATE", "s": 2, "d": message }),
+    ]);
+
+    client.replay(replay).await.unwrap();
+    assert!(mock.requests().is_empty());
+}
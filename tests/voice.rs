@@ -0,0 +1,219 @@
+#![cfg(feature = "voice")]
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::XChaCha20Poly1305;
+use diself::voice::{
+    AudioSource, EncryptionMode, OggOpusSource, OpusEncoder, OpusFrames, PcmSource,
+    VoiceConnection, VoiceServerInfo, FRAME_SAMPLES,
+};
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::net::{TcpListener, UdpSocket};
+use tokio_tungstenite::tungstenite::Message;
+
+/// Builds an Ogg page holding `packets` (checksums are not verified by the reader)
+fn ogg_page(packets: &[&[u8]]) -> Vec<u8> {
+    let mut segments = Vec::new();
+    let mut body = Vec::new();
+    for packet in packets {
+        let mut len = packet.len();
+        while len >= 255 {
+            segments.push(255u8);
+            len -= 255;
+        }
+        segments.push(len as u8);
+        body.extend_from_slice(packet);
+    }
+    let mut page = b"OggS".to_vec();
+    page.extend_from_slice(&[0; 22]);
+    page.push(segments.len() as u8);
+    page.extend_from_slice(&segments);
+    page.extend_from_slice(&body);
+    page
+}
+
+#[test]
+fn encryption_mode_prefers_rtpsize_aead() {
+    let modes = ["xsalsa20_poly1305", "aead_xchacha20_poly1305_rtpsize"];
+    assert_eq!(
+        EncryptionMode::select(&modes),
+        Some(EncryptionMode::XChaCha20Poly1305RtpSize)
+    );
+    assert_eq!(
+        EncryptionMode::select(&["xsalsa20_poly1305_lite", "xsalsa20_poly1305"]),
+        Some(EncryptionMode::XSalsa20Poly1305)
+    );
+    assert_eq!(EncryptionMode::select(&["aead_aes256_gcm"]), None);
+}
+
+#[test]
+fn ogg_opus_source_skips_headers_and_joins_segments() {
+    let long_packet = vec![7u8; 300];
+    let mut file = ogg_page(&[b"OpusHead\x01\x02"]);
+    file.extend(ogg_page(&[b"OpusTags"]));
+    file.extend(ogg_page(&[&[1, 2, 3], &long_packet]));
+
+    let mut source = OggOpusSource::new(file);
+    assert_eq!(source.next_frame().unwrap().unwrap(), vec![1, 2, 3]);
+    assert_eq!(source.next_frame().unwrap().unwrap(), long_packet);
+    assert!(source.next_frame().is_none());
+
+    let mut invalid = OggOpusSource::new(b"RIFF....".to_vec());
+    assert!(invalid.next_frame().unwrap().is_err());
+    assert!(invalid.next_frame().is_none());
+}
+
+#[test]
+fn pcm_source_encodes_padded_frames() {
+    struct LengthEncoder;
+    impl OpusEncoder for LengthEncoder {
+        fn encode(&mut self, pcm: &[i16]) -> diself::Result<Vec<u8>> {
+            assert_eq!(pcm.len(), FRAME_SAMPLES * 2);
+            let sum: i32 = pcm.iter().map(|&sample| sample as i32).sum();
+            Ok(sum.to_le_bytes().to_vec())
+        }
+    }
+
+    let mut source = PcmSource::new(vec![1; FRAME_SAMPLES * 2 + 10], LengthEncoder);
+    let first = source.next_frame().unwrap().unwrap();
+    assert_eq!(first, ((FRAME_SAMPLES * 2) as i32).to_le_bytes());
+    let second = source.next_frame().unwrap().unwrap();
+    assert_eq!(second, 10i32.to_le_bytes());
+    assert!(source.next_frame().is_none());
+}
+
+/// Next payload sent by the client, heartbeats excluded
+async fn next_json(ws: &mut tokio_tungstenite::WebSocketStream<tokio::net::TcpStream>) -> Value {
+    loop {
+        if let Message::Text(text) = ws.next().await.unwrap().unwrap() {
+            let payload: Value = serde_json::from_str(&text).unwrap();
+            // Heartbeats can be interleaved with any other payload
+            if payload["op"] != 3 {
+                return payload;
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn voice_connection_handshakes_and_sends_encrypted_rtp() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let ws_port = listener.local_addr().unwrap().port();
+    let udp = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let udp_port = udp.local_addr().unwrap().port();
+    let key = [7u8; 32];
+
+    let server = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        let hello = json!({ "op": 8, "d": { "heartbeat_interval": 41250 } });
+        ws.send(Message::Text(hello.to_string())).await.unwrap();
+
+        let identify = next_json(&mut ws).await;
+        assert_eq!(identify["op"], 0);
+        assert_eq!(identify["d"]["server_id"], "1");
+        assert_eq!(identify["d"]["session_id"], "session");
+        assert_eq!(identify["d"]["token"], "voice-token");
+
+        let ready = json!({
+            "op": 2,
+            "d": {
+                "ssrc": 42,
+                "ip": "127.0.0.1",
+                "port": udp_port,
+                "modes": ["xsalsa20_poly1305", "aead_xchacha20_poly1305_rtpsize"]
+            }
+        });
+        ws.send(Message::Text(ready.to_string())).await.unwrap();
+
+        // IP discovery
+        let mut buf = [0u8; 1500];
+        let (len, client) = udp.recv_from(&mut buf).await.unwrap();
+        assert_eq!(len, 74);
+        assert_eq!(&buf[4..8], &42u32.to_be_bytes());
+        let mut response = [0u8; 74];
+        response[0..2].copy_from_slice(&2u16.to_be_bytes());
+        response[8..17].copy_from_slice(b"127.0.0.1");
+        response[72..74].copy_from_slice(&client.port().to_be_bytes());
+        udp.send_to(&response, client).await.unwrap();
+
+        let select = next_json(&mut ws).await;
+        assert_eq!(select["op"], 1);
+        assert_eq!(select["d"]["data"]["address"], "127.0.0.1");
+        assert_eq!(select["d"]["data"]["port"], client.port());
+        assert_eq!(
+            select["d"]["data"]["mode"],
+            "aead_xchacha20_poly1305_rtpsize"
+        );
+        let description = json!({
+            "op": 4,
+            "d": { "mode": "aead_xchacha20_poly1305_rtpsize", "secret_key": key }
+        });
+        ws.send(Message::Text(description.to_string()))
+            .await
+            .unwrap();
+
+        let speaking = next_json(&mut ws).await;
+        assert_eq!(speaking["op"], 5);
+        assert_eq!(speaking["d"]["speaking"], 1);
+
+        // 2 frames followed by 5 silence frames
+        let mut packets = Vec::new();
+        for _ in 0..7 {
+            let (len, _) = udp.recv_from(&mut buf).await.unwrap();
+            packets.push(buf[..len].to_vec());
+        }
+        let stopped = next_json(&mut ws).await;
+        assert_eq!(stopped["d"]["speaking"], 0);
+        packets
+    });
+
+    let voice = VoiceConnection::connect(VoiceServerInfo {
+        server_id: "1".to_string(),
+        user_id: "2".to_string(),
+        session_id: "session".to_string(),
+        token: "voice-token".to_string(),
+        endpoint: format!("ws://127.0.0.1:{ws_port}"),
+    })
+    .await
+    .unwrap();
+    assert_eq!(voice.ssrc(), 42);
+    assert_eq!(
+        voice.encryption_mode(),
+        EncryptionMode::XChaCha20Poly1305RtpSize
+    );
+    voice
+        .play(OpusFrames::new(vec![vec![1, 2, 3], vec![4, 5, 6]]))
+        .await
+        .unwrap();
+
+    let packets = server.await.unwrap();
+    let cipher = XChaCha20Poly1305::new(&key.into());
+    let mut frames = Vec::new();
+    for (i, packet) in packets.iter().enumerate() {
+        let (header, rest) = packet.split_at(12);
+        assert_eq!(header[0..2], [0x80, 0x78]);
+        assert_eq!(header[8..12], 42u32.to_be_bytes());
+        let (encrypted, counter) = rest.split_at(rest.len() - 4);
+        assert_eq!(counter, (i as u32 + 1).to_be_bytes());
+        let mut nonce = [0u8; 24];
+        nonce[..4].copy_from_slice(counter);
+        let payload = Payload {
+            msg: encrypted,
+            aad: header,
+        };
+        frames.push(cipher.decrypt(&nonce.into(), payload).unwrap());
+    }
+    assert_eq!(frames[0], [1, 2, 3]);
+    assert_eq!(frames[1], [4, 5, 6]);
+    assert_eq!(frames[2], [0xF8, 0xFF, 0xFE]);
+
+    // Sequence numbers and timestamps advance by one frame
+    let sequence = |packet: &Vec<u8>| u16::from_be_bytes([packet[2], packet[3]]);
+    let timestamp = |packet: &Vec<u8>| u32::from_be_bytes(packet[4..8].try_into().unwrap());
+    assert_eq!(sequence(&packets[1]), sequence(&packets[0]).wrapping_add(1));
+    assert_eq!(
+        timestamp(&packets[1]),
+        timestamp(&packets[0]).wrapping_add(FRAME_SAMPLES as u32)
+    );
+}